@@ -0,0 +1,67 @@
+//! Time every rule on a large document, sharing one `LintContext` across rules
+//! versus building a fresh one (and so parsing the YAML again) for every rule.
+//!
+//! Run with `cargo bench -p yl-core --bench context`.
+
+use std::hint::black_box;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use yl_core::RuleRegistry;
+use yl_core::linter::LintContext;
+
+const ITERATIONS: u32 = 5;
+
+/// A document of `services` entries, each with a few nested maps, lists and a block scalar
+fn document(services: usize) -> String {
+    let mut content = String::from("---\nservices:\n");
+    for i in 0..services {
+        content.push_str(&format!(
+            "  service-{i}:\n    image: \"registry.example.com/service-{i}:1.{i}\"\n    \
+             replicas: {}\n    ports:\n      - 80\n      - 443\n    env:\n      \
+             NAME: service-{i}\n      DEBUG: false\n    script: |\n      echo start {i}\n      \
+             run --port 80\n",
+            i % 5 + 1
+        ));
+    }
+    content
+}
+
+fn time(label: &str, mut run: impl FnMut()) -> Duration {
+    run();
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        run();
+    }
+    let elapsed = start.elapsed() / ITERATIONS;
+    println!("{label:<24} {elapsed:>12.2?}");
+    elapsed
+}
+
+fn main() {
+    let registry = RuleRegistry::with_default_rules();
+    let rules: Vec<_> = registry
+        .rules()
+        .into_iter()
+        .map(|rule| (rule, rule.default_config()))
+        .collect();
+    let content = document(2_000);
+    let path = Path::new("bench.yaml");
+    println!("{} rules, {} lines", rules.len(), content.lines().count());
+
+    let shared = time("shared context", || {
+        let context = LintContext::new(path, &content);
+        for (rule, config) in &rules {
+            black_box(rule.check(&context, config).ok());
+        }
+    });
+    let per_rule = time("context per rule", || {
+        for (rule, config) in &rules {
+            let context = LintContext::new(path, &content);
+            black_box(rule.check(&context, config).ok());
+        }
+    });
+    println!(
+        "speedup                  {:>11.1}x",
+        per_rule.as_secs_f64() / shared.as_secs_f64()
+    );
+}
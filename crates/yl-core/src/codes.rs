@@ -0,0 +1,248 @@
+//! Problem codes: stable identifiers such as `YL10101` for every rule and message.
+//!
+//! A code is `YL` followed by the rule's category (1 style, 2 syntax, 3 formatting,
+//! 4 semantic, 9 problems yl reports itself), two digits for the rule and two for the
+//! message. The rule's own code ends in 00 and identifies problems whose message is not
+//! from the catalog. Codes are never renumbered or reused; new rules and messages take the
+//! next free number, and a rule has room for 99 messages.
+
+/// Documentation of every code, with a section per rule
+pub const DOCS_URL: &str = "https://github.com/scottidler/yl/blob/main/docs/codes.md";
+
+/// Code of every rule and catalog message, by rule ID or message ID
+const CODES: &[(&str, &str)] = &[
+    // Style rules
+    ("line-length", "YL10100"),
+    ("line-length.too-long", "YL10101"),
+    ("trailing-spaces", "YL10200"),
+    ("trailing-spaces.found", "YL10201"),
+    ("empty-lines", "YL10300"),
+    ("empty-lines.too-many", "YL10301"),
+    ("empty-lines.too-many-at-start", "YL10302"),
+    ("empty-lines.too-many-at-end", "YL10303"),
+    ("indentation", "YL10400"),
+    ("indentation.wrong", "YL10401"),
+    ("indentation.sequence-indented", "YL10402"),
+    ("indentation.sequence-aligned", "YL10403"),
+    ("new-line-at-end-of-file", "YL10500"),
+    ("new-line-at-end-of-file.missing", "YL10501"),
+    ("no-tabs", "YL10600"),
+    ("no-tabs.in-indentation", "YL10601"),
+    ("no-tabs.at-end-of-line", "YL10602"),
+    ("no-tabs.in-comment", "YL10603"),
+    ("no-tabs.in-value", "YL10604"),
+    ("value-length", "YL10700"),
+    ("value-length.too-long", "YL10701"),
+    ("value-length.block-scalar-too-long", "YL10702"),
+    // Syntax rules
+    ("key-duplicates", "YL20100"),
+    ("key-duplicates.found", "YL20101"),
+    ("document-structure", "YL20200"),
+    ("document-structure.missing-start", "YL20201"),
+    ("document-structure.missing-end", "YL20202"),
+    ("document-count", "YL20300"),
+    ("document-count.too-few", "YL20301"),
+    ("document-count.too-many", "YL20302"),
+    ("document-count.too-few-plural", "YL20303"),
+    ("anchors", "YL20400"),
+    ("anchors.forbidden-anchor", "YL20401"),
+    ("anchors.duplicate-anchor", "YL20402"),
+    ("anchors.forbidden-alias", "YL20403"),
+    ("anchors.alias-before-anchor", "YL20404"),
+    ("anchors.undefined-alias", "YL20405"),
+    ("anchors.too-many-aliases", "YL20406"),
+    ("anchors.unused-anchor", "YL20407"),
+    ("anchors.merge-not-alias", "YL20408"),
+    ("anchors.merge-not-mapping", "YL20409"),
+    ("yaml-syntax", "YL20500"),
+    ("yaml-syntax.error", "YL20501"),
+    ("comments", "YL20600"),
+    ("comments.missing-space", "YL20601"),
+    ("comments.too-few-spaces-before", "YL20602"),
+    ("directives", "YL20700"),
+    ("directives.missing-yaml", "YL20701"),
+    ("directives.forbidden-yaml", "YL20702"),
+    ("directives.duplicate-yaml", "YL20703"),
+    ("directives.wrong-version", "YL20704"),
+    ("directives.forbidden-tag-handle", "YL20705"),
+    ("directives.unknown", "YL20706"),
+    ("directives.undeclared-tag-handle", "YL20707"),
+    // Formatting rules
+    ("brackets", "YL30100"),
+    ("brackets.too-few-spaces-inside", "YL30101"),
+    ("brackets.too-many-spaces-inside", "YL30102"),
+    ("brackets.too-few-spaces-inside-empty", "YL30103"),
+    ("brackets.too-many-spaces-inside-empty", "YL30104"),
+    ("braces", "YL30200"),
+    ("braces.too-few-spaces-inside", "YL30201"),
+    ("braces.too-many-spaces-inside", "YL30202"),
+    ("braces.too-few-spaces-inside-empty", "YL30203"),
+    ("braces.too-many-spaces-inside-empty", "YL30204"),
+    ("colons", "YL30300"),
+    ("colons.too-many-spaces-before", "YL30301"),
+    ("colons.too-few-spaces-after", "YL30302"),
+    ("colons.too-many-spaces-after", "YL30303"),
+    ("commas", "YL30400"),
+    ("commas.too-many-spaces-before", "YL30401"),
+    ("commas.too-few-spaces-after", "YL30402"),
+    ("commas.too-many-spaces-after", "YL30403"),
+    ("hyphens", "YL30500"),
+    ("hyphens.missing-space", "YL30501"),
+    ("hyphens.too-many-spaces-after", "YL30502"),
+    ("flow-collections", "YL30600"),
+    ("flow-collections.too-many-entries", "YL30601"),
+    ("flow-collections.too-deep", "YL30602"),
+    ("flow-collections.multi-line", "YL30603"),
+    ("dangling-commas", "YL30700"),
+    ("dangling-commas.empty-entry", "YL30701"),
+    ("dangling-commas.trailing", "YL30702"),
+    // Semantic rules
+    ("truthy", "YL40100"),
+    ("truthy.not-allowed", "YL40101"),
+    ("quoted-strings", "YL40200"),
+    ("quoted-strings.single", "YL40201"),
+    ("quoted-strings.double", "YL40202"),
+    ("quoted-strings.not-needed", "YL40203"),
+    ("key-ordering", "YL40300"),
+    ("key-ordering.wrong", "YL40301"),
+    ("float-values", "YL40400"),
+    ("float-values.scientific-notation", "YL40401"),
+    ("float-values.numeral-before-decimal", "YL40402"),
+    ("octal-values", "YL40500"),
+    ("octal-values.implicit", "YL40501"),
+    ("octal-values.explicit", "YL40502"),
+    ("required-keys", "YL40600"),
+    ("required-keys.missing", "YL40601"),
+    ("forbidden-keys", "YL40700"),
+    ("forbidden-keys.found", "YL40701"),
+    ("sequence-duplicates", "YL40800"),
+    ("sequence-duplicates.found", "YL40801"),
+    ("sequence-ordering", "YL40900"),
+    ("sequence-ordering.wrong", "YL40901"),
+    ("date-format", "YL41000"),
+    ("date-format.invalid", "YL41001"),
+    ("quoted-keys", "YL41100"),
+    ("quoted-keys.required", "YL41101"),
+    ("quoted-keys.not-needed", "YL41102"),
+    ("empty-collections", "YL41200"),
+    ("empty-collections.mapping-as-empty", "YL41201"),
+    ("empty-collections.mapping-as-null", "YL41202"),
+    ("empty-collections.sequence-as-empty", "YL41203"),
+    ("empty-collections.sequence-as-null", "YL41204"),
+    ("empty-collections.empty-as-mapping", "YL41205"),
+    ("empty-collections.empty-as-sequence", "YL41206"),
+    ("empty-collections.empty-is-ambiguous", "YL41207"),
+    // Reported by yl itself
+    ("file-error", "YL90100"),
+    ("rule-timeout", "YL90200"),
+    ("rule-timeout.skipped", "YL90201"),
+    ("policy", "YL90300"),
+];
+
+/// Code of a problem: the code of its catalog message, or of its rule when the message
+/// has none. Rules that are not built in, such as plugins, have no codes.
+pub fn code(rule: &str, message_id: Option<&str>) -> Option<&'static str> {
+    message_id.and_then(lookup).or_else(|| lookup(rule))
+}
+
+/// Rule or message ID a code stands for
+pub fn id_of(code: &str) -> Option<&'static str> {
+    CODES
+        .iter()
+        .find(|(_, known)| known.eq_ignore_ascii_case(code))
+        .map(|(id, _)| *id)
+}
+
+/// Rule a code belongs to
+pub fn rule_of(code: &str) -> Option<&'static str> {
+    id_of(code).map(|id| id.split_once('.').map_or(id, |(rule, _)| rule))
+}
+
+/// Link to the documentation of a rule's codes
+pub fn docs_url(rule: &str) -> Option<String> {
+    lookup(rule).map(|_| format!("{DOCS_URL}#{rule}"))
+}
+
+/// Every code with the rule or message ID it stands for, in order
+pub fn all() -> impl Iterator<Item = (&'static str, &'static str)> {
+    CODES.iter().map(|(id, code)| (*code, *id))
+}
+
+fn lookup(id: &str) -> Option<&'static str> {
+    CODES
+        .iter()
+        .find(|(known, _)| *known == id)
+        .map(|(_, code)| *code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::{self, Catalog};
+    use crate::rules::RuleRegistry;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_code() {
+        assert_eq!(
+            code("line-length", Some("line-length.too-long")),
+            Some("YL10101")
+        );
+        // Messages outside the catalog get the code of their rule
+        assert_eq!(code("forbidden-keys", None), Some("YL40700"));
+        assert_eq!(code("my-plugin", None), None);
+        assert_eq!(rule_of("yl41102"), Some("quoted-keys"));
+        assert_eq!(rule_of("YL10200"), Some("trailing-spaces"));
+        assert_eq!(rule_of("YL00000"), None);
+        assert_eq!(
+            docs_url("indentation").as_deref(),
+            Some("https://github.com/scottidler/yl/blob/main/docs/codes.md#indentation")
+        );
+    }
+
+    #[test]
+    fn test_codes_are_unique_and_complete() {
+        let mut codes = HashSet::new();
+        for (code, id) in all() {
+            assert!(codes.insert(code), "code {code} is used twice");
+            let (rule, variant) = id.split_once('.').unwrap_or((id, ""));
+            let rule_code = lookup(rule).unwrap_or_else(|| panic!("{id} has no rule code"));
+            // Category, rule and message digits: a rule past 99 messages does not fit
+            assert!(
+                code.len() == 7
+                    && code.starts_with("YL")
+                    && code[2..].bytes().all(|digit| digit.is_ascii_digit()),
+                "{id} has malformed code {code}"
+            );
+            assert!(matches!(&code[2..3], "1" | "2" | "3" | "4" | "9"), "{id}");
+            // A message's code extends its rule's
+            assert_eq!(code[..5], rule_code[..5], "{id}");
+            assert_eq!(variant.is_empty(), code.ends_with("00"), "{id}");
+            if !variant.is_empty() {
+                assert!(messages::english(id).is_some(), "{id} is not a message");
+            }
+        }
+
+        for rule in RuleRegistry::with_default_rules().rule_ids() {
+            assert!(lookup(rule).is_some(), "rule {rule} has no code");
+        }
+        // Every message of a rule with a code has one
+        for id in Catalog::english().messages.keys() {
+            let rule = id.split_once('.').map_or(id.as_str(), |(rule, _)| rule);
+            if lookup(rule).is_some() {
+                assert!(lookup(id).is_some(), "message {id} has no code");
+            }
+        }
+    }
+
+    #[test]
+    fn test_codes_are_documented() {
+        let docs = include_str!("../../../docs/codes.md");
+        for (code, _) in all() {
+            assert!(
+                docs.contains(&format!("`{code}`")),
+                "{code} is not documented"
+            );
+        }
+    }
+}
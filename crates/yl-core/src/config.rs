@@ -0,0 +1,811 @@
+mod canonical;
+pub mod editorconfig;
+mod env;
+pub(crate) mod glob;
+pub mod ignore;
+pub mod inline;
+pub mod layers;
+pub mod packages;
+pub mod plugins;
+pub mod projects;
+pub mod resolver;
+pub mod watcher;
+
+use crate::linter::{Extractor, Level, TemplateMode};
+use crate::rules::{RuleConfig, RuleRegistry};
+use eyre::{Context, ContextCompat, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+pub use ignore::IgnorePatterns;
+pub use inline::InlineConfigManager;
+pub use layers::{ConfigLayer, ConfigSource};
+pub use packages::{PackageResolver, PackageSpec, Registry};
+pub use plugins::{AllowedPlugin, PluginsConfig};
+pub use projects::{ProjectConfig, Projects};
+pub use resolver::ConfigResolver;
+pub use watcher::{ConfigReload, ConfigWatcher};
+
+/// Configuration file names searched for in each directory, in order of preference
+pub const CONFIG_FILE_NAMES: [&str; 4] = [".yl.yaml", ".yl.yml", "yl.yaml", "yl.yml"];
+
+/// Main configuration for the YAML linter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    /// Base configuration to extend from
+    pub extends: Option<String>,
+    /// Rule-specific configurations
+    #[serde(default)]
+    pub rules: HashMap<String, RuleConfig>,
+    /// File patterns to ignore
+    #[serde(default)]
+    pub ignore: Vec<String>,
+    /// File patterns that should be treated as YAML files
+    #[serde(rename = "yaml-files", default)]
+    pub yaml_files: Vec<String>,
+    /// Use `.editorconfig` settings as defaults for indentation and line length
+    #[serde(default = "default_true")]
+    pub editorconfig: bool,
+    /// Other kinds of files to lint the embedded YAML of (`markdown`, `html`, `heredoc`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub embedded: Vec<Extractor>,
+    /// Templating language to mask before linting (`helm`, `jinja` or `none`)
+    #[serde(rename = "template-mode", default)]
+    pub template_mode: TemplateMode,
+    /// Skip files whose header marks them as generated
+    #[serde(rename = "skip-generated", default)]
+    pub skip_generated: bool,
+    /// Accept empty and whitespace-only files without running any rule on them; off if
+    /// unset
+    #[serde(
+        rename = "allow-empty-files",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub allow_empty_files: Option<bool>,
+    /// Report only the syntax error of a file that does not parse, without running the
+    /// rules that check its text, keys and values; on if unset
+    #[serde(
+        rename = "stop-on-syntax-error",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub stop_on_syntax_error: Option<bool>,
+    /// Markers that identify a generated file when found in its header; `@generated` and
+    /// `DO NOT EDIT` if unset
+    #[serde(
+        rename = "generated-markers",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub generated_markers: Option<Vec<String>>,
+    /// Number of lines at the start of a file searched for generated markers; 10 if unset
+    #[serde(
+        rename = "generated-header-lines",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub generated_header_lines: Option<usize>,
+    /// Severity of each listed rule's problems, overriding the level in its rule block
+    #[serde(
+        default,
+        deserialize_with = "deserialize_severities",
+        skip_serializing_if = "HashMap::is_empty"
+    )]
+    pub severities: HashMap<String, Level>,
+    /// Severity of rules configured neither in `rules` nor in `severities`, including
+    /// those only configured by a preset
+    #[serde(
+        rename = "default-severity",
+        default,
+        deserialize_with = "deserialize_default_severity",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub default_severity: Option<Level>,
+    /// Milliseconds a rule may run on one file before it is skipped with a
+    /// `rule-timeout` warning; no limit if unset
+    #[serde(
+        rename = "rule-timeout",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub rule_timeout: Option<u64>,
+    /// Weights of problems in `yl report score`, keyed by severity (`error`, `warning`,
+    /// `info`) or rule id
+    #[serde(
+        rename = "score-weights",
+        default,
+        deserialize_with = "deserialize_score_weights",
+        skip_serializing_if = "HashMap::is_empty"
+    )]
+    pub score_weights: HashMap<String, f64>,
+    /// Where to post the run summary after linting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<WebhookConfig>,
+    /// Projects of a monorepo, which files and their problems are grouped by; only read
+    /// from the root configuration
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub projects: Vec<ProjectConfig>,
+    /// Native rule plugins, and which of them may be loaded
+    #[serde(default, skip_serializing_if = "PluginsConfig::is_empty")]
+    pub plugins: PluginsConfig,
+    /// Named configurations selected with `--profile`, each overriding this one like a
+    /// configuration extending it; only read from the root configuration
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub profiles: HashMap<String, Config>,
+}
+
+/// Webhook receiving a JSON summary of each lint run, e.g. a relay posting to chat
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    /// URL the summary is POSTed to
+    pub url: String,
+    /// Seconds to wait for each attempt
+    #[serde(default = "default_webhook_timeout")]
+    pub timeout: u64,
+    /// Attempts made after the first one fails
+    #[serde(default = "default_webhook_retries")]
+    pub retries: u32,
+}
+
+impl WebhookConfig {
+    /// Post to `url` with the default timeout and retries
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            timeout: default_webhook_timeout(),
+            retries: default_webhook_retries(),
+        }
+    }
+}
+
+fn default_webhook_timeout() -> u64 {
+    10
+}
+
+fn default_webhook_retries() -> u32 {
+    2
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Severities are written like levels on the command line: `error`, `warning` or `info`
+fn parse_level<E: serde::de::Error>(value: &str) -> Result<Level, E> {
+    value.parse().map_err(|e: eyre::Report| E::custom(e))
+}
+
+fn deserialize_severities<'de, D>(deserializer: D) -> Result<HashMap<String, Level>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    HashMap::<String, String>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(rule_id, level)| Ok((rule_id, parse_level(&level)?)))
+        .collect()
+}
+
+fn deserialize_score_weights<'de, D>(deserializer: D) -> Result<HashMap<String, f64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let weights = HashMap::<String, f64>::deserialize(deserializer)?;
+    match weights
+        .iter()
+        .find(|(_, weight)| !weight.is_finite() || **weight < 0.0)
+    {
+        Some((key, weight)) => Err(serde::de::Error::custom(format!(
+            "score weight of {key} must be a non-negative number, got {weight}"
+        ))),
+        None => Ok(weights),
+    }
+}
+
+fn deserialize_default_severity<'de, D>(deserializer: D) -> Result<Option<Level>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|level| parse_level(&level))
+        .transpose()
+}
+
+/// Markers of generated files when `generated-markers` is unset
+const DEFAULT_GENERATED_MARKERS: [&str; 2] = ["@generated", "DO NOT EDIT"];
+
+/// Lines searched for generated markers when `generated-header-lines` is unset
+const DEFAULT_GENERATED_HEADER_LINES: usize = 10;
+
+impl Config {
+    /// Load configuration from a file path
+    pub fn load(config_path: Option<&PathBuf>) -> Result<Self> {
+        let config_file = match config_path {
+            Some(path) => path.clone(),
+            None => Self::default_config_path()?,
+        };
+
+        if config_file.exists() {
+            debug!(config = %config_file.display(), "loading configuration");
+            let mut config = Self::from_file(&config_file)?;
+
+            // A root configuration without yaml-files would match nothing
+            if config.yaml_files.is_empty() {
+                config.yaml_files = Self::default().yaml_files;
+            }
+
+            Ok(config)
+        } else {
+            // Return default config if file doesn't exist
+            debug!(config = %config_file.display(), "configuration file not found, using defaults");
+            Ok(Self::default())
+        }
+    }
+
+    /// Read a single configuration file, resolving its `extends` chain
+    pub fn from_file(config_file: &Path) -> Result<Self> {
+        let content = fs::read_to_string(config_file)
+            .with_context(|| format!("Failed to read config file: {}", config_file.display()))?;
+
+        let mut config: Config = serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse config file: {}", config_file.display()))?;
+
+        // Handle extends
+        if let Some(base_name) = &config.extends {
+            debug!(config = %config_file.display(), extends = %base_name, "extending base configuration");
+            let base_config = Self::load_base_config(base_name, config_file)?;
+            config = config.merge_with_base(base_config)?;
+        }
+
+        Ok(config)
+    }
+
+    /// Expand `${VAR}` and `${VAR:-default}` in the settings that name files: the global
+    /// and per-rule `ignore` patterns and the plugin directories, in profiles as well.
+    /// Every other value, such as `webhook.url`, is used as written, so a configuration
+    /// cannot send the environment anywhere.
+    pub fn expand_env(&mut self) -> Result<()> {
+        self.expand_env_with(&|name| std::env::var(name).ok())
+    }
+
+    fn expand_env_with(&mut self, lookup: &impl Fn(&str) -> Option<String>) -> Result<()> {
+        let rule_ignores = self.rules.values_mut().flat_map(|rule| &mut rule.ignore);
+        for pattern in self.ignore.iter_mut().chain(rule_ignores) {
+            *pattern = env::expand(pattern, lookup)?;
+        }
+        for dir in &mut self.plugins.directories {
+            if let Some(path) = dir.to_str() {
+                *dir = PathBuf::from(env::expand(path, lookup)?);
+            }
+        }
+        for profile in self.profiles.values_mut() {
+            profile.expand_env_with(lookup)?;
+        }
+        Ok(())
+    }
+
+    /// Apply the named profile over this configuration. The profile's settings win as
+    /// those of a configuration extending this one would; the profiles are dropped.
+    pub fn with_profile(mut self, name: &str) -> Result<Self> {
+        let mut profiles = std::mem::take(&mut self.profiles);
+        let Some(profile) = profiles.remove(name) else {
+            let mut names: Vec<&str> = profiles.keys().map(String::as_str).collect();
+            names.sort();
+            return Err(eyre::eyre!(
+                "Profile '{}' not found (profiles: {})",
+                name,
+                if names.is_empty() {
+                    "none".to_string()
+                } else {
+                    names.join(", ")
+                }
+            ));
+        };
+
+        debug!(profile = %name, "applying configuration profile");
+        let extends = self.extends.take();
+        let mut config = profile.merge_with_base(self)?;
+        config.extends = extends;
+        config.profiles.clear();
+        Ok(config)
+    }
+
+    /// Find a configuration file directly inside the given directory
+    pub fn find_in_dir(dir: &Path) -> Option<PathBuf> {
+        CONFIG_FILE_NAMES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|candidate| candidate.is_file())
+    }
+
+    /// Load a base configuration by name
+    fn load_base_config(base_name: &str, current_config_path: &Path) -> Result<Self> {
+        // First try built-in configurations
+        match Self::preset(base_name) {
+            Some(preset) => Ok(preset),
+            None => Self::load(Some(&Self::base_config_path(
+                base_name,
+                current_config_path,
+            )?)),
+        }
+    }
+
+    /// Built-in configuration of the given name
+    fn preset(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::default()),
+            "strict" => Some(Self::strict()),
+            "relaxed" => Some(Self::relaxed()),
+            _ => None,
+        }
+    }
+
+    /// Path of a base configuration that is not built in: a package, or a file
+    /// relative to the configuration extending it
+    fn base_config_path(base_name: &str, current_config_path: &Path) -> Result<PathBuf> {
+        if base_name.starts_with(packages::PACKAGE_PREFIX) {
+            let spec: PackageSpec = base_name.parse()?;
+            return PackageResolver::from_user_config()?.resolve(&spec);
+        }
+
+        // Try to load as a file path relative to current config
+        let base_path = if base_name.starts_with('/') {
+            PathBuf::from(base_name)
+        } else {
+            current_config_path
+                .parent()
+                .unwrap_or_else(|| Path::new("."))
+                .join(base_name)
+        };
+
+        if base_path.exists() {
+            Ok(base_path)
+        } else {
+            Err(eyre::eyre!("Base configuration '{}' not found", base_name))
+        }
+    }
+
+    /// Merge this configuration with a base configuration
+    fn merge_with_base(mut self, base: Self) -> Result<Self> {
+        // Start with base rules
+        let mut merged_rules = base.rules;
+
+        // Override with current rules
+        for (rule_id, rule_config) in self.rules {
+            merged_rules.insert(rule_id, rule_config);
+        }
+
+        self.rules = merged_rules;
+
+        // Use current ignore patterns if specified, otherwise use base
+        if self.ignore.is_empty() {
+            self.ignore = base.ignore;
+        }
+
+        // Use current yaml-files patterns if specified, otherwise use base
+        if self.yaml_files.is_empty() {
+            self.yaml_files = base.yaml_files;
+        }
+
+        // Use current extractors if specified, otherwise use base
+        if self.embedded.is_empty() {
+            self.embedded = base.embedded;
+        }
+
+        // Use current template mode if specified, otherwise use base
+        if self.template_mode == TemplateMode::None {
+            self.template_mode = base.template_mode;
+        }
+
+        // Skipping generated files enabled in the base stays enabled
+        self.skip_generated |= base.skip_generated;
+
+        // Use current allow-empty-files if specified, otherwise use base
+        if self.allow_empty_files.is_none() {
+            self.allow_empty_files = base.allow_empty_files;
+        }
+
+        // Use current stop-on-syntax-error if specified, otherwise use base
+        if self.stop_on_syntax_error.is_none() {
+            self.stop_on_syntax_error = base.stop_on_syntax_error;
+        }
+
+        // Use current generated file detection if specified, otherwise use base
+        if self.generated_markers.is_none() {
+            self.generated_markers = base.generated_markers;
+        }
+        if self.generated_header_lines.is_none() {
+            self.generated_header_lines = base.generated_header_lines;
+        }
+
+        // Severities of the current config override those of the base
+        for (rule_id, level) in base.severities {
+            self.severities.entry(rule_id).or_insert(level);
+        }
+        if self.default_severity.is_none() {
+            self.default_severity = base.default_severity;
+        }
+
+        // Use current rule timeout if specified, otherwise use base
+        if self.rule_timeout.is_none() {
+            self.rule_timeout = base.rule_timeout;
+        }
+
+        // Score weights of the current config override those of the base
+        for (key, weight) in base.score_weights {
+            self.score_weights.entry(key).or_insert(weight);
+        }
+
+        // Use current webhook if specified, otherwise use base
+        if self.webhook.is_none() {
+            self.webhook = base.webhook;
+        }
+
+        // Use current projects if specified, otherwise use base
+        if self.projects.is_empty() {
+            self.projects = base.projects;
+        }
+
+        // Use current plugins if specified, otherwise use base
+        if self.plugins.is_empty() {
+            self.plugins = base.plugins;
+        }
+
+        // Profiles of the current config override those of the base with the same name
+        for (name, profile) in base.profiles {
+            self.profiles.entry(name).or_insert(profile);
+        }
+
+        Ok(self)
+    }
+
+    /// Get the configuration file path used when none is given: the first of the
+    /// project config file names that exists, or the user configuration file
+    pub fn default_config_path() -> Result<PathBuf> {
+        // Look for config files in order of preference
+        for name in CONFIG_FILE_NAMES {
+            let candidate = PathBuf::from(name);
+            if candidate.exists() {
+                return Ok(candidate);
+            }
+        }
+
+        // If no config file found, return default location
+        let config_dir = dirs::config_local_dir()
+            .or_else(|| dirs::home_dir().map(|h| h.join(".config")))
+            .context("Could not determine config directory")?;
+
+        Ok(config_dir.join("yl").join("config.yaml"))
+    }
+
+    /// Get the effective configuration for a rule
+    pub fn get_rule_config(&self, rule_id: &str, registry: &RuleRegistry) -> RuleConfig {
+        // Fall back to rule's default configuration, or a generic default
+        let mut config = self.rules.get(rule_id).cloned().unwrap_or_else(|| {
+            registry
+                .get(rule_id)
+                .map(|rule| rule.default_config())
+                .unwrap_or_default()
+        });
+
+        // Rules without a block of their own, including those a preset fills in
+        if !config.explicit
+            && let Some(level) = &self.default_severity
+        {
+            config.level = level.clone();
+        }
+        if let Some(level) = self.severities.get(rule_id) {
+            config.level = level.clone();
+        }
+        config
+    }
+
+    /// Validate the ignore patterns and every known rule's configuration
+    pub fn validate(&self, registry: &RuleRegistry) -> Result<()> {
+        for pattern in &self.ignore {
+            IgnorePatterns::validate(pattern)?;
+        }
+        Projects::validate(&self.projects)?;
+
+        let mut rule_ids: Vec<_> = self.rules.keys().collect();
+        rule_ids.sort();
+
+        for rule_id in rule_ids {
+            for pattern in &self.rules[rule_id].ignore {
+                IgnorePatterns::validate(pattern)
+                    .with_context(|| format!("Invalid configuration for rule '{rule_id}'"))?;
+            }
+
+            if let Some(rule) = registry.get(rule_id) {
+                let mut rule_config = self.rules[rule_id].clone();
+                registry
+                    .resolve_params(rule_id, &mut rule_config)
+                    .and_then(|()| rule.validate_config(&rule_config))
+                    .with_context(|| format!("Invalid configuration for rule '{rule_id}'"))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether empty and whitespace-only files are accepted without running any rule
+    pub fn allow_empty_files(&self) -> bool {
+        self.allow_empty_files.unwrap_or(false)
+    }
+
+    /// Whether only the syntax error of a file that does not parse is reported
+    pub fn stop_on_syntax_error(&self) -> bool {
+        self.stop_on_syntax_error.unwrap_or(true)
+    }
+
+    /// Check if a file should be ignored based on ignore patterns
+    pub fn is_file_ignored(&self, file_path: &Path) -> bool {
+        IgnorePatterns::new(&self.ignore).is_ignored(file_path)
+    }
+
+    /// Check if content is marked as generated in its first `generated-header-lines` lines
+    pub fn is_generated(&self, content: &str) -> bool {
+        let markers: Vec<&str> = match &self.generated_markers {
+            Some(markers) => markers.iter().map(String::as_str).collect(),
+            None => DEFAULT_GENERATED_MARKERS.to_vec(),
+        };
+        content
+            .lines()
+            .take(
+                self.generated_header_lines
+                    .unwrap_or(DEFAULT_GENERATED_HEADER_LINES),
+            )
+            .any(|line| {
+                markers
+                    .iter()
+                    .any(|marker| !marker.is_empty() && line.contains(marker))
+            })
+    }
+
+    /// Check if a file should be treated as a YAML file
+    pub fn is_yaml_file(&self, file_path: &Path) -> bool {
+        let path_str = file_path.to_string_lossy();
+
+        for pattern in &self.yaml_files {
+            if pattern.contains('*') {
+                let pattern_regex = pattern.replace('*', ".*");
+                if regex::Regex::new(&pattern_regex)
+                    .map(|re| re.is_match(&path_str))
+                    .unwrap_or(false)
+                {
+                    return true;
+                }
+            } else if path_str.ends_with(pattern) {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// The enabled extractor that reads the YAML embedded in a file, if any
+    pub fn extractor_for(&self, file_path: &Path) -> Option<Extractor> {
+        self.embedded
+            .iter()
+            .copied()
+            .find(|extractor| extractor.matches(file_path))
+    }
+
+    /// Create a strict configuration preset
+    pub fn strict() -> Self {
+        let mut config = Self::default();
+
+        // Make all rules errors
+        for (_, rule_config) in config.rules.iter_mut() {
+            rule_config.level = crate::linter::Level::Error;
+        }
+
+        config
+    }
+
+    /// Create a relaxed configuration preset
+    pub fn relaxed() -> Self {
+        let mut config = Self::default();
+
+        // Make most rules warnings
+        for (_, rule_config) in config.rules.iter_mut() {
+            rule_config.level = crate::linter::Level::Warning;
+        }
+
+        config
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let registry = RuleRegistry::with_default_rules();
+        let mut rules = HashMap::new();
+
+        // Add default configurations for all built-in rules
+        for rule in registry.rules() {
+            rules.insert(rule.id().to_string(), rule.default_config());
+        }
+
+        Self {
+            extends: None,
+            rules,
+            ignore: vec![
+                "*.generated.yaml".to_string(),
+                "*.generated.yml".to_string(),
+                ".git/**".to_string(),
+                "node_modules/**".to_string(),
+            ],
+            yaml_files: vec![
+                "*.yaml".to_string(),
+                "*.yml".to_string(),
+                ".yamllint".to_string(),
+            ],
+            editorconfig: true,
+            embedded: Vec::new(),
+            template_mode: TemplateMode::None,
+            skip_generated: false,
+            allow_empty_files: None,
+            stop_on_syntax_error: None,
+            generated_markers: None,
+            generated_header_lines: None,
+            severities: HashMap::new(),
+            default_severity: None,
+            rule_timeout: None,
+            score_weights: HashMap::new(),
+            webhook: None,
+            projects: Vec::new(),
+            plugins: PluginsConfig::default(),
+            profiles: HashMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_severities() {
+        let config: Config = serde_yaml::from_str(
+            "rules:\n  line-length:\n    enabled: true\n    level: Error\n    params: {}\n  truthy:\n    enabled: true\n    level: Error\n    params: {}\nseverities:\n  truthy: info\ndefault-severity: Warning\n",
+        )
+        .unwrap();
+        let registry = RuleRegistry::with_default_rules();
+
+        let level = |rule_id| config.get_rule_config(rule_id, &registry).level;
+        assert_eq!(level("line-length"), Level::Error);
+        assert_eq!(level("truthy"), Level::Info);
+        assert_eq!(level("trailing-spaces"), Level::Warning);
+        assert!(config.get_rule_config("trailing-spaces", &registry).enabled);
+    }
+
+    #[test]
+    fn test_default_severity_with_preset() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(".yl.yaml");
+        fs::write(
+            &path,
+            "extends: default\ndefault-severity: info\nrules:\n  line-length:\n    enabled: true\n    level: Error\n    params: {}\n",
+        )
+        .unwrap();
+        let config = Config::from_file(&path).unwrap();
+        let registry = RuleRegistry::with_default_rules();
+
+        let level = |rule_id| config.get_rule_config(rule_id, &registry).level;
+        assert_eq!(level("line-length"), Level::Error);
+        assert_eq!(level("trailing-spaces"), Level::Info);
+
+        // Without a configuration file
+        let config = Config {
+            default_severity: Some(Level::Warning),
+            ..Config::default()
+        };
+        assert_eq!(
+            config.get_rule_config("truthy", &registry).level,
+            Level::Warning
+        );
+    }
+
+    #[test]
+    fn test_severities_invalid_level() {
+        let result: Result<Config, _> = serde_yaml::from_str("severities:\n  truthy: fatal\n");
+        assert!(result.is_err());
+        let result: Result<Config, _> = serde_yaml::from_str("default-severity: loud\n");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_severities_merge_with_base() {
+        let base: Config = serde_yaml::from_str(
+            "severities:\n  truthy: info\n  colons: info\ndefault-severity: warning\n",
+        )
+        .unwrap();
+        let config: Config = serde_yaml::from_str("severities:\n  truthy: error\n").unwrap();
+        let merged = config.merge_with_base(base).unwrap();
+
+        assert_eq!(merged.severities["truthy"], Level::Error);
+        assert_eq!(merged.severities["colons"], Level::Info);
+        assert_eq!(merged.default_severity, Some(Level::Warning));
+    }
+
+    #[test]
+    fn test_score_weights() {
+        let base: Config =
+            serde_yaml::from_str("score-weights:\n  error: 10\n  truthy: 1\n").unwrap();
+        let config: Config = serde_yaml::from_str("score-weights:\n  error: 4.5\n").unwrap();
+        let merged = config.merge_with_base(base).unwrap();
+
+        assert_eq!(merged.score_weights["error"], 4.5);
+        assert_eq!(merged.score_weights["truthy"], 1.0);
+
+        let result: Result<Config, _> = serde_yaml::from_str("score-weights:\n  error: -1\n");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_expand_env() {
+        let mut config: Config = serde_yaml::from_str(
+            "ignore:\n  - ${BUILD_DIR:-target}/**\nrules:\n  truthy:\n    enabled: true\n    level: Error\n    params: {}\n    ignore:\n      - ${HOME}/tmp/**\nplugins:\n  directories:\n    - ${HOME}/plugins\nwebhook:\n  url: http://example.com/hook?t=${TOKEN}\nprofiles:\n  ci:\n    ignore:\n      - $${HOME}/**\n",
+        )
+        .unwrap();
+        let lookup = |name: &str| match name {
+            "HOME" => Some("/home/ci".to_string()),
+            "TOKEN" => Some("secret".to_string()),
+            _ => None,
+        };
+        config.expand_env_with(&lookup).unwrap();
+
+        assert_eq!(config.ignore, vec!["target/**"]);
+        assert_eq!(config.rules["truthy"].ignore, vec!["/home/ci/tmp/**"]);
+        assert_eq!(
+            config.plugins.directories,
+            vec![PathBuf::from("/home/ci/plugins")]
+        );
+        assert_eq!(config.profiles["ci"].ignore, vec!["${HOME}/**"]);
+        // Values that do not name files are never expanded
+        assert_eq!(
+            config.webhook.unwrap().url,
+            "http://example.com/hook?t=${TOKEN}"
+        );
+    }
+
+    #[test]
+    fn test_with_profile() {
+        let config: Config = serde_yaml::from_str(
+            "ignore:\n  - vendor/**\nrule-timeout: 500\nseverities:\n  truthy: info\nprofiles:\n  ci:\n    severities:\n      truthy: error\n    rule-timeout: 2000\n  dev:\n    ignore:\n      - tmp/**\n",
+        )
+        .unwrap();
+
+        let ci = config.clone().with_profile("ci").unwrap();
+        assert_eq!(ci.severities["truthy"], Level::Error);
+        assert_eq!(ci.rule_timeout, Some(2000));
+        assert_eq!(ci.ignore, vec!["vendor/**"]);
+        assert!(ci.profiles.is_empty());
+
+        let dev = config.clone().with_profile("dev").unwrap();
+        assert_eq!(dev.severities["truthy"], Level::Info);
+        assert_eq!(dev.ignore, vec!["tmp/**"]);
+
+        let error = config.with_profile("prod").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Profile 'prod' not found (profiles: ci, dev)"
+        );
+    }
+
+    #[test]
+    fn test_webhook() {
+        let base: Config =
+            serde_yaml::from_str("webhook:\n  url: https://relay.example.com/yl\n  retries: 5\n")
+                .unwrap();
+        let webhook = base.webhook.clone().unwrap();
+        assert_eq!(webhook.timeout, 10);
+        assert_eq!(webhook.retries, 5);
+
+        let merged = Config::default().merge_with_base(base).unwrap();
+        assert_eq!(merged.webhook, Some(webhook));
+    }
+}
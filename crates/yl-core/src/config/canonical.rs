@@ -0,0 +1,285 @@
+//! Canonical form of the configuration files yl writes, so that writing a file again
+//! only changes the lines whose settings changed
+
+use super::Config;
+use crate::rules::RuleRegistry;
+use eyre::{Context, Result};
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
+
+/// Top-level settings held in hash maps, whose keys are sorted
+const SORTED_SETTINGS: [&str; 2] = ["severities", "score-weights"];
+
+impl Config {
+    /// Serialize as YAML in canonical form: top-level settings in declaration order,
+    /// rules grouped by category and sorted by id within each group, and parameters
+    /// sorted by name. Comments of `existing`, the content of the file being replaced,
+    /// are kept at the top of the file and before the settings and rules still written.
+    pub fn to_canonical_yaml(
+        &self,
+        registry: &RuleRegistry,
+        existing: Option<&str>,
+    ) -> Result<String> {
+        let Value::Mapping(mapping) =
+            serde_yaml::to_value(self).context("Failed to serialize configuration")?
+        else {
+            eyre::bail!("Configuration did not serialize to a mapping");
+        };
+        let comments = existing.map(Comments::parse).unwrap_or_default();
+
+        let mut output = String::new();
+        for line in &comments.header {
+            output.push_str(&format!("{line}\n"));
+        }
+        if !comments.header.is_empty() {
+            output.push('\n');
+        }
+
+        for (key, value) in mapping {
+            let name = key.as_str().unwrap_or_default().to_string();
+            if value.is_null() {
+                continue;
+            }
+            if name == "rules" {
+                output.push_str(&self.canonical_rules(registry, &comments)?);
+                output.push('\n');
+                continue;
+            }
+
+            let value = if SORTED_SETTINGS.contains(&name.as_str()) {
+                sort_keys(value)
+            } else {
+                value
+            };
+            push_comments(&mut output, comments.settings.get(&name), "");
+            output.push_str(&to_yaml(key, value)?);
+        }
+
+        // No blank line after the last setting
+        output.truncate(output.trim_end().len());
+        output.push('\n');
+        Ok(output)
+    }
+
+    /// The `rules` setting, one group of rules per category with uncategorized rules last
+    fn canonical_rules(&self, registry: &RuleRegistry, comments: &Comments) -> Result<String> {
+        if self.rules.is_empty() {
+            return Ok("rules: {}\n".to_string());
+        }
+
+        let mut ids: Vec<&String> = self.rules.keys().collect();
+        ids.sort_by_key(|id| {
+            let category = registry.category(id);
+            (category.is_none(), category, *id)
+        });
+
+        let mut output = String::from("\n");
+        push_comments(&mut output, comments.settings.get("rules"), "");
+        output.push_str("rules:\n");
+
+        let mut heading = None;
+        for id in ids {
+            let category = registry
+                .category(id)
+                .map_or_else(|| "other".to_string(), |category| category.to_string());
+            if heading.as_ref() != Some(&category) {
+                if heading.is_some() {
+                    output.push('\n');
+                }
+                output.push_str(&format!("  # {category} rules\n"));
+                heading = Some(category);
+            }
+
+            let mut value = serde_yaml::to_value(&self.rules[id])
+                .with_context(|| format!("Failed to serialize rule '{id}'"))?;
+            if let Some(params) = value.get_mut("params") {
+                *params = sort_keys(std::mem::take(params));
+            }
+
+            push_comments(&mut output, comments.rules.get(id.as_str()), "  ");
+            for line in to_yaml(Value::from(id.as_str()), value)?.lines() {
+                output.push_str(&format!("  {line}\n"));
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+/// A single `key: value` entry as YAML
+fn to_yaml(key: Value, value: Value) -> Result<String> {
+    let entry: Mapping = [(key, value)].into_iter().collect();
+    serde_yaml::to_string(&entry).context("Failed to serialize configuration")
+}
+
+/// A mapping with its keys sorted, nested mappings included
+fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Mapping(mapping) => {
+            let mut entries: Vec<(Value, Value)> = mapping
+                .into_iter()
+                .map(|(key, value)| (key, sort_keys(value)))
+                .collect();
+            entries.sort_by(|(a, _), (b, _)| {
+                a.as_str()
+                    .unwrap_or_default()
+                    .cmp(b.as_str().unwrap_or_default())
+            });
+            Value::Mapping(entries.into_iter().collect())
+        }
+        Value::Sequence(items) => Value::Sequence(items.into_iter().map(sort_keys).collect()),
+        value => value,
+    }
+}
+
+fn push_comments(output: &mut String, comments: Option<&Vec<String>>, indent: &str) {
+    for comment in comments.into_iter().flatten() {
+        output.push_str(&format!("{indent}{comment}\n"));
+    }
+}
+
+/// Comments of a configuration file that can be carried over to its canonical form
+#[derive(Debug, Default)]
+struct Comments {
+    /// Comments at the top of the file, separated from the first setting by a blank line
+    header: Vec<String>,
+    /// Comments directly before each top-level setting
+    settings: HashMap<String, Vec<String>>,
+    /// Comments directly before each rule of `rules`
+    rules: HashMap<String, Vec<String>>,
+}
+
+impl Comments {
+    /// Collect the comments on lines of their own. Comments inside values and at the
+    /// end of lines are not kept, nor the category headings of earlier canonical forms.
+    fn parse(content: &str) -> Self {
+        let mut comments = Self::default();
+        let mut pending = Vec::new();
+        let mut seen_setting = false;
+        let mut in_rules = false;
+        let mut rule_indent = None;
+
+        for line in content.lines() {
+            let trimmed = line.trim_start();
+            let indent = line.len() - trimmed.len();
+
+            if trimmed.starts_with('#') {
+                if !is_category_heading(trimmed) {
+                    pending.push(trimmed.trim_end().to_string());
+                }
+                continue;
+            }
+            if trimmed.is_empty() {
+                if !seen_setting {
+                    comments.header.append(&mut pending);
+                }
+                continue;
+            }
+
+            let key = trimmed
+                .split_once(':')
+                .map(|(key, _)| key.trim().trim_matches(['"', '\'']).to_string());
+            if indent == 0 {
+                seen_setting = true;
+                in_rules = key.as_deref() == Some("rules");
+                rule_indent = None;
+                if let Some(key) = key {
+                    comments.settings.insert(key, std::mem::take(&mut pending));
+                }
+            } else if in_rules
+                && *rule_indent.get_or_insert(indent) == indent
+                && let Some(key) = key
+            {
+                comments.rules.insert(key, std::mem::take(&mut pending));
+            }
+            pending.clear();
+        }
+
+        comments
+    }
+}
+
+/// Whether a comment is a heading written by [`Config::to_canonical_yaml`]
+fn is_category_heading(comment: &str) -> bool {
+    comment
+        .strip_prefix("# ")
+        .and_then(|heading| heading.strip_suffix(" rules"))
+        .is_some_and(|category| {
+            matches!(
+                category,
+                "style" | "syntax" | "formatting" | "semantic" | "other"
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::RuleConfig;
+
+    fn config() -> Config {
+        let registry = RuleRegistry::with_default_rules();
+        let mut config = Config {
+            rules: HashMap::new(),
+            ..Config::default()
+        };
+        for id in ["truthy", "line-length", "colons", "trailing-spaces"] {
+            let rule_config = registry.get(id).unwrap().default_config();
+            config.rules.insert(id.to_string(), rule_config);
+        }
+        config
+            .rules
+            .insert("custom".to_string(), RuleConfig::default());
+        config
+    }
+
+    #[test]
+    fn test_canonical_order_is_stable() {
+        let registry = RuleRegistry::with_default_rules();
+        let yaml = config().to_canonical_yaml(&registry, None).unwrap();
+
+        // Hash map order differs between instances; the canonical form does not
+        for _ in 0..5 {
+            assert_eq!(config().to_canonical_yaml(&registry, None).unwrap(), yaml);
+        }
+
+        let positions: Vec<usize> = [
+            "  # style rules",
+            "  line-length:",
+            "  trailing-spaces:",
+            "  # formatting rules",
+            "  colons:",
+            "  # semantic rules",
+            "  truthy:",
+            "  # other rules",
+            "  custom:",
+        ]
+        .iter()
+        .map(|line| yaml.find(&format!("\n{line}\n")).expect(line))
+        .collect();
+        assert!(positions.is_sorted());
+        assert!(yaml.find("    allow-non-breakable-inline-mappings:") < yaml.find("    max:"));
+        assert!(!yaml.contains("extends"));
+
+        // The canonical form reads back as the same configuration
+        let parsed: Config = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed.rules, config().rules);
+    }
+
+    #[test]
+    fn test_canonical_keeps_comments() {
+        let registry = RuleRegistry::with_default_rules();
+        let existing = "# Team configuration\n\n# Files we never lint\nignore:\n  - vendor/**\nrules:\n  # style rules\n  # Long URLs in annotations\n  line-length:\n    enabled: true\n    # inside a value\n    level: Error\n  # Removed rule\n  gone:\n    enabled: false\n";
+
+        let yaml = config()
+            .to_canonical_yaml(&registry, Some(existing))
+            .unwrap();
+
+        assert!(yaml.starts_with("# Team configuration\n\n"));
+        assert!(yaml.contains("# Files we never lint\nignore:\n"));
+        assert!(yaml.contains("  # Long URLs in annotations\n  line-length:\n"));
+        assert_eq!(yaml.matches("# style rules").count(), 1);
+        assert!(!yaml.contains("inside a value"));
+        assert!(!yaml.contains("Removed rule"));
+    }
+}
@@ -0,0 +1,341 @@
+use super::glob;
+use crate::rules::RuleConfig;
+use regex::Regex;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Name of the editorconfig file looked up in each directory
+const EDITORCONFIG_FILE_NAME: &str = ".editorconfig";
+
+/// Editorconfig properties that apply to a single file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EditorConfigSettings {
+    /// Lowercased property names mapped to their lowercased values
+    properties: HashMap<String, String>,
+}
+
+impl EditorConfigSettings {
+    /// Get a raw property value
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.properties.get(key).map(|value| value.as_str())
+    }
+
+    /// Maximum line length, unless set to `off`
+    pub fn max_line_length(&self) -> Option<usize> {
+        self.get("max_line_length")?.parse().ok()
+    }
+
+    /// Indentation width in spaces.
+    ///
+    /// YAML cannot be indented with tabs, so tab-indented files leave the
+    /// indentation rule alone.
+    pub fn indent_size(&self) -> Option<usize> {
+        if self.get("indent_style") == Some("tab") {
+            return None;
+        }
+
+        match self.get("indent_size")? {
+            "tab" => self.get("tab_width")?.parse().ok(),
+            size => size.parse().ok(),
+        }
+    }
+
+    /// Use these settings as defaults for the rule parameters they map to.
+    ///
+    /// Parameters the user set keep their value, even when it is the rule's default.
+    pub fn apply(&self, rule_id: &str, rule_config: &mut RuleConfig) {
+        let (param, value) = match rule_id {
+            "line-length" => ("max", self.max_line_length()),
+            "indentation" => ("spaces", self.indent_size()),
+            _ => return,
+        };
+
+        let Some(value) = value else {
+            return;
+        };
+
+        if !rule_config.user_params.contains(param) {
+            rule_config.set_param(param, value as i64);
+        }
+    }
+}
+
+/// A `[glob]` section of an editorconfig file
+#[derive(Debug)]
+struct Section {
+    /// Compiled glob; `None` if the glob could not be compiled
+    pattern: Option<Regex>,
+    properties: Vec<(String, String)>,
+}
+
+/// A parsed `.editorconfig` file
+#[derive(Debug)]
+struct EditorConfigFile {
+    /// Directory containing the file; section globs are relative to it
+    dir: PathBuf,
+    /// Whether lookup stops at this file
+    root: bool,
+    sections: Vec<Section>,
+}
+
+impl EditorConfigFile {
+    /// Parse the INI-style contents of an editorconfig file
+    fn parse(dir: &Path, content: &str) -> Self {
+        let mut file = Self {
+            dir: dir.to_path_buf(),
+            root: false,
+            sections: Vec::new(),
+        };
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+
+            if let Some(glob) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                // Sections with globs we cannot compile are skipped entirely
+                file.sections.push(Section {
+                    pattern: Regex::new(&glob_to_regex(glob)).ok(),
+                    properties: Vec::new(),
+                });
+                continue;
+            }
+
+            if let Some((key, value)) = line.split_once('=') {
+                let key = key.trim().to_lowercase();
+                let value = value.trim().to_lowercase();
+
+                match file.sections.last_mut() {
+                    Some(section) => section.properties.push((key, value)),
+                    None if key == "root" => file.root = value == "true",
+                    None => {}
+                }
+            }
+        }
+
+        file
+    }
+
+    /// Apply every section matching the given absolute file path
+    fn apply_to(&self, file_path: &Path, settings: &mut EditorConfigSettings) {
+        let Ok(relative) = file_path.strip_prefix(&self.dir) else {
+            return;
+        };
+        let relative = relative.to_string_lossy().replace('\\', "/");
+
+        for section in &self.sections {
+            let matched = section
+                .pattern
+                .as_ref()
+                .is_some_and(|pattern| pattern.is_match(&relative));
+            if matched {
+                for (key, value) in &section.properties {
+                    settings.properties.insert(key.clone(), value.clone());
+                }
+            }
+        }
+    }
+}
+
+/// Translate an editorconfig glob into an anchored regex
+fn glob_to_regex(glob: &str) -> String {
+    // Globs without a slash match the file name in any subdirectory
+    let prefix = if glob.contains('/') { "" } else { "(?:.*/)?" };
+    format!(
+        "^{prefix}{}$",
+        glob::translate(glob.trim_start_matches('/'))
+    )
+}
+
+/// Make a path absolute without touching the filesystem
+fn absolute_path(path: &Path) -> PathBuf {
+    let joined = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().unwrap_or_default().join(path)
+    };
+
+    joined
+        .components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .collect()
+}
+
+/// Looks up and caches the `.editorconfig` files that apply to each directory
+#[derive(Default)]
+pub struct EditorConfigResolver {
+    /// Parsed editorconfig chains (outermost first) keyed by absolute directory
+    cache: Mutex<HashMap<PathBuf, Arc<Vec<EditorConfigFile>>>>,
+}
+
+impl EditorConfigResolver {
+    /// Create a new resolver
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolve the editorconfig settings for a file
+    pub fn resolve(&self, file_path: &Path) -> EditorConfigSettings {
+        let file_path = absolute_path(file_path);
+        let mut settings = EditorConfigSettings::default();
+
+        if let Some(dir) = file_path.parent() {
+            for file in self.chain_for_dir(dir).iter() {
+                file.apply_to(&file_path, &mut settings);
+            }
+        }
+
+        settings
+    }
+
+    /// Forget all parsed editorconfig files
+    pub fn clear_cache(&self) {
+        self.cache
+            .lock()
+            .expect("editorconfig cache poisoned")
+            .clear();
+    }
+
+    /// Collect editorconfig files from `dir` upwards until one declares `root = true`
+    fn chain_for_dir(&self, dir: &Path) -> Arc<Vec<EditorConfigFile>> {
+        if let Some(chain) = self
+            .cache
+            .lock()
+            .expect("editorconfig cache poisoned")
+            .get(dir)
+        {
+            return Arc::clone(chain);
+        }
+
+        let mut files = Vec::new();
+        for ancestor in dir.ancestors() {
+            if let Ok(content) = fs::read_to_string(ancestor.join(EDITORCONFIG_FILE_NAME)) {
+                let file = EditorConfigFile::parse(ancestor, &content);
+                let root = file.root;
+                files.push(file);
+                if root {
+                    break;
+                }
+            }
+        }
+
+        // Closer files take precedence, so apply them last
+        files.reverse();
+
+        let chain = Arc::new(files);
+        self.cache
+            .lock()
+            .expect("editorconfig cache poisoned")
+            .insert(dir.to_path_buf(), Arc::clone(&chain));
+        chain
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::Level;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_glob_to_regex() {
+        let matches =
+            |glob: &str, path: &str| Regex::new(&glob_to_regex(glob)).unwrap().is_match(path);
+
+        assert!(matches("*", "values.yaml"));
+        assert!(matches("*", "charts/values.yaml"));
+        assert!(matches("*.{yaml,yml}", "deep/dir/file.yml"));
+        assert!(!matches("*.{yaml,yml}", "file.json"));
+        assert!(matches("charts/**.yaml", "charts/a/b.yaml"));
+        assert!(!matches("charts/*.yaml", "charts/a/b.yaml"));
+        assert!(matches("/top.yaml", "top.yaml"));
+        assert!(!matches("/top.yaml", "sub/top.yaml"));
+    }
+
+    #[test]
+    fn test_resolve_settings() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(
+            root.join(EDITORCONFIG_FILE_NAME),
+            "root = true\n\n[*]\nindent_size = 4\n\n[*.yaml]\nindent_size = 2\nmax_line_length = 120\n",
+        )
+        .unwrap();
+        fs::create_dir(root.join("docs")).unwrap();
+        fs::write(
+            root.join("docs").join(EDITORCONFIG_FILE_NAME),
+            "[*.yaml]\nmax_line_length = off\n",
+        )
+        .unwrap();
+
+        let resolver = EditorConfigResolver::new();
+
+        let settings = resolver.resolve(&root.join("values.yaml"));
+        assert_eq!(settings.indent_size(), Some(2));
+        assert_eq!(settings.max_line_length(), Some(120));
+
+        let settings = resolver.resolve(&root.join("values.json"));
+        assert_eq!(settings.indent_size(), Some(4));
+        assert_eq!(settings.max_line_length(), None);
+
+        let settings = resolver.resolve(&root.join("docs/example.yaml"));
+        assert_eq!(settings.indent_size(), Some(2));
+        assert_eq!(settings.max_line_length(), None);
+    }
+
+    #[test]
+    fn test_indent_size_tab() {
+        let mut settings = EditorConfigSettings::default();
+        settings
+            .properties
+            .insert("indent_size".into(), "tab".into());
+        settings.properties.insert("tab_width".into(), "8".into());
+        assert_eq!(settings.indent_size(), Some(8));
+
+        settings
+            .properties
+            .insert("indent_style".into(), "tab".into());
+        assert_eq!(settings.indent_size(), None);
+    }
+
+    #[test]
+    fn test_apply_only_to_unconfigured_params() {
+        let mut settings = EditorConfigSettings::default();
+        settings
+            .properties
+            .insert("max_line_length".into(), "120".into());
+
+        let mut defaults = RuleConfig::new(true, Level::Error);
+        defaults.set_param("max", 80i64);
+
+        // Default value is replaced by the editorconfig setting
+        let mut config = defaults.clone();
+        settings.apply("line-length", &mut config);
+        assert_eq!(config.get_int("max"), Some(120));
+
+        // Missing value is filled in
+        let mut config = RuleConfig::new(true, Level::Error);
+        settings.apply("line-length", &mut config);
+        assert_eq!(config.get_int("max"), Some(120));
+
+        // Explicit value wins
+        let mut config = defaults.clone();
+        config.set_user_param("max", 100i64);
+        settings.apply("line-length", &mut config);
+        assert_eq!(config.get_int("max"), Some(100));
+
+        // Explicit value wins even when it is the rule's default
+        let mut config: RuleConfig =
+            serde_yaml::from_str("enabled: true\nlevel: Error\nparams:\n  max: 80\n").unwrap();
+        settings.apply("line-length", &mut config);
+        assert_eq!(config.get_int("max"), Some(80));
+
+        // Unrelated rules are untouched
+        let mut config = defaults.clone();
+        settings.apply("trailing-spaces", &mut config);
+        assert_eq!(config, defaults);
+    }
+}
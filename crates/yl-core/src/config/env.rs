@@ -0,0 +1,96 @@
+//! Expansion of `${VAR}` and `${VAR:-default}` in configuration values
+
+use eyre::Result;
+
+/// Expand `${VAR}` and `${VAR:-default}` in one string; `$${` stands for a literal `${`.
+/// A variable that is unset and has no default is an error.
+pub(crate) fn expand(input: &str, lookup: &impl Fn(&str) -> Option<String>) -> Result<String> {
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(start) = rest.find('$') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(escaped) = rest.strip_prefix("$${") {
+            output.push_str("${");
+            rest = escaped;
+            continue;
+        }
+        let Some(reference) = rest.strip_prefix("${") else {
+            output.push('$');
+            rest = &rest[1..];
+            continue;
+        };
+        let Some(end) = reference.find('}') else {
+            return Err(eyre::eyre!("Unterminated '${{' in '{}'", input));
+        };
+
+        let (name, default) = match reference[..end].split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (&reference[..end], None),
+        };
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(eyre::eyre!(
+                "Invalid environment variable name '{}' in '{}'",
+                name,
+                input
+            ));
+        }
+
+        match (lookup(name).filter(|value| !value.is_empty()), default) {
+            (Some(value), _) => output.push_str(&value),
+            (None, Some(default)) => output.push_str(default),
+            (None, None) => {
+                return Err(eyre::eyre!(
+                    "Environment variable '{}' is not set (use '${{{}:-default}}' for a fallback)",
+                    name,
+                    name
+                ));
+            }
+        }
+        rest = &reference[end + 1..];
+    }
+
+    output.push_str(rest);
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "HOME" => Some("/home/ci".to_string()),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_expand() {
+        assert_eq!(
+            expand("${HOME}/schemas", &lookup).unwrap(),
+            "/home/ci/schemas"
+        );
+        assert_eq!(
+            expand("${MISSING:-vendor}/**", &lookup).unwrap(),
+            "vendor/**"
+        );
+        assert_eq!(expand("${EMPTY:-fallback}", &lookup).unwrap(), "fallback");
+        assert_eq!(expand("${MISSING:-}x", &lookup).unwrap(), "x");
+        assert_eq!(
+            expand("cost $5 $${HOME}", &lookup).unwrap(),
+            "cost $5 ${HOME}"
+        );
+
+        let error = expand("${MISSING}", &lookup).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Environment variable 'MISSING' is not set (use '${MISSING:-default}' for a fallback)"
+        );
+        assert!(expand("${HOME", &lookup).is_err());
+        assert!(expand("${A-B}", &lookup).is_err());
+    }
+}
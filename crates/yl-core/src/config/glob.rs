@@ -0,0 +1,68 @@
+//! Glob patterns shared by `.editorconfig` sections and `ignore` patterns
+
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+/// Compiled regexes by source, since patterns are matched against every linted file
+static COMPILED: LazyLock<Mutex<HashMap<String, Regex>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Translate a glob into an unanchored regex.
+///
+/// `*` and `?` stay within a path component, `**` crosses components, and `[...]`,
+/// `[!...]` and `{a,b}` work as in shells.
+pub(crate) fn translate(glob: &str) -> String {
+    let mut regex = String::new();
+    let chars: Vec<char> = glob.chars().collect();
+    let mut brace_depth = 0;
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                regex.push_str(".*");
+                i += 1;
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            '{' => {
+                brace_depth += 1;
+                regex.push_str("(?:");
+            }
+            '}' if brace_depth > 0 => {
+                brace_depth -= 1;
+                regex.push(')');
+            }
+            ',' if brace_depth > 0 => regex.push('|'),
+            '[' => {
+                regex.push('[');
+                if chars.get(i + 1) == Some(&'!') {
+                    regex.push('^');
+                    i += 1;
+                }
+            }
+            ']' => regex.push(']'),
+            '\\' if i + 1 < chars.len() => {
+                regex.push_str(&regex::escape(&chars[i + 1].to_string()));
+                i += 1;
+            }
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+        i += 1;
+    }
+
+    regex
+}
+
+/// Compile a regex produced from a glob, reusing earlier compilations
+pub(crate) fn compile(source: &str) -> Result<Regex, regex::Error> {
+    let mut compiled = COMPILED.lock().expect("glob cache poisoned");
+    if let Some(regex) = compiled.get(source) {
+        return Ok(regex.clone());
+    }
+
+    let regex = Regex::new(source)?;
+    compiled.insert(source.to_string(), regex.clone());
+    Ok(regex)
+}
@@ -0,0 +1,142 @@
+//! Matching of the `ignore` patterns of a configuration
+
+use super::glob;
+use eyre::{Context, Result};
+use regex::Regex;
+use std::path::Path;
+
+/// Compiled `ignore` patterns.
+///
+/// Patterns are globs, checked in order with the last matching pattern winning:
+/// - a pattern without a `/` matches a file or directory name at any depth
+/// - a pattern with a `/` matches from any directory, e.g. `docs/**` or `charts/*/values.yaml`
+/// - a leading `/` matches from the start of the path only
+/// - a trailing `/` matches directories only
+/// - a leading `!` re-includes paths excluded by an earlier pattern
+///
+/// A pattern matching a directory applies to everything below it.
+#[derive(Debug, Clone)]
+pub struct IgnorePatterns {
+    patterns: Vec<IgnorePattern>,
+}
+
+#[derive(Debug, Clone)]
+struct IgnorePattern {
+    regex: Regex,
+    negated: bool,
+}
+
+impl IgnorePatterns {
+    /// Compile the given patterns, skipping any that are not valid globs
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> Self {
+        Self {
+            patterns: patterns
+                .iter()
+                .filter_map(|pattern| IgnorePattern::parse(pattern.as_ref()).ok())
+                .collect(),
+        }
+    }
+
+    /// Check that a pattern is a valid glob
+    pub fn validate(pattern: &str) -> Result<()> {
+        IgnorePattern::parse(pattern)
+            .map(|_| ())
+            .with_context(|| format!("Invalid ignore pattern '{pattern}'"))
+    }
+
+    /// Check if a path is excluded by the patterns
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        let path = path.to_string_lossy().replace('\\', "/");
+        let path = path.trim_start_matches("./");
+
+        self.patterns
+            .iter()
+            .rfind(|pattern| pattern.regex.is_match(path))
+            .is_some_and(|pattern| !pattern.negated)
+    }
+}
+
+impl IgnorePattern {
+    fn parse(pattern: &str) -> Result<Self, regex::Error> {
+        let (negated, pattern) = match pattern.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, pattern),
+        };
+        let pattern = pattern.trim_start_matches("./");
+
+        let (directory_only, pattern) = match pattern.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, pattern),
+        };
+
+        let prefix = if pattern.starts_with('/') {
+            ""
+        } else {
+            "(?:.*/)?"
+        };
+        // Matching a directory also matches everything below it
+        let suffix = if directory_only { "/.*" } else { "(?:/.*)?" };
+
+        let regex = glob::compile(&format!("^{prefix}{}{suffix}$", glob::translate(pattern)))?;
+        Ok(Self { regex, negated })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ignored(patterns: &[&str], path: &str) -> bool {
+        IgnorePatterns::new(patterns).is_ignored(Path::new(path))
+    }
+
+    #[test]
+    fn test_file_name_patterns_match_at_any_depth() {
+        assert!(ignored(&["*.generated.yaml"], "a.generated.yaml"));
+        assert!(ignored(
+            &["*.generated.yaml"],
+            "./deep/dir/a.generated.yaml"
+        ));
+        assert!(!ignored(&["*.generated.yaml"], "a.yaml"));
+        assert!(!ignored(&["test"], "contest.yaml"));
+    }
+
+    #[test]
+    fn test_directory_patterns() {
+        assert!(ignored(&["docs/**"], "docs/a.yaml"));
+        assert!(ignored(&["docs/**"], "/repo/docs/sub/a.yaml"));
+        assert!(!ignored(&["docs/**"], "mydocs/a.yaml"));
+        assert!(ignored(&["vendor"], "vendor/lib/a.yaml"));
+        assert!(ignored(&["vendor/"], "./vendor/a.yaml"));
+        assert!(!ignored(&["vendor/"], "vendor"));
+        assert!(ignored(&["charts/*/values.yaml"], "charts/app/values.yaml"));
+        assert!(!ignored(
+            &["charts/*/values.yaml"],
+            "charts/app/sub/values.yaml"
+        ));
+    }
+
+    #[test]
+    fn test_anchored_patterns() {
+        assert!(ignored(&["/repo/build/**"], "/repo/build/a.yaml"));
+        assert!(!ignored(&["/build/**"], "/repo/build/a.yaml"));
+    }
+
+    #[test]
+    fn test_negation_last_match_wins() {
+        let patterns = ["docs/**", "!docs/keep.yaml"];
+        assert!(ignored(&patterns, "docs/drop.yaml"));
+        assert!(!ignored(&patterns, "docs/keep.yaml"));
+
+        // A later exclusion overrides an earlier re-inclusion
+        let patterns = ["!*.yaml", "generated/"];
+        assert!(ignored(&patterns, "generated/a.yaml"));
+        assert!(!ignored(&patterns, "a.yaml"));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(IgnorePatterns::validate("docs/**").is_ok());
+        assert!(IgnorePatterns::validate("[unclosed").is_err());
+    }
+}
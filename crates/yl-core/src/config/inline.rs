@@ -10,6 +10,8 @@ pub struct InlineConfigManager {
     directives: HashMap<usize, Vec<Directive>>,
     /// Currently active rule configurations
     active_configs: HashMap<String, RuleConfig>,
+    /// File-level rule parameter overrides from the header
+    file_configs: HashMap<String, RuleConfig>,
     /// Rules that are currently disabled
     disabled_rules: HashSet<String>,
     /// Rules disabled for specific lines
@@ -27,6 +29,7 @@ impl InlineConfigManager {
             processor: CommentProcessor::new(),
             directives: HashMap::new(),
             active_configs: HashMap::new(),
+            file_configs: HashMap::new(),
             disabled_rules: HashSet::new(),
             line_disabled_rules: HashMap::new(),
             file_ignored: false,
@@ -39,16 +42,31 @@ impl InlineConfigManager {
         // Reset state for new file
         self.reset();
 
+        // File-level directives are only allowed before the first YAML content
+        let mut in_header = true;
+
         // Process each line for directives
         for (line_no, line) in content.lines().enumerate() {
             let line_number = line_no + 1;
 
+            let trimmed = line.trim();
+            if in_header && !Self::is_header_line(trimmed) {
+                in_header = false;
+            }
+
             // Look for comments in the line
             if let Some(comment_start) = line.find('#') {
                 let comment = &line[comment_start..];
 
                 // Try to parse directive
                 if let Some(directive) = self.processor.parse_directive(comment)? {
+                    if matches!(directive, Directive::Configure { .. }) && !in_header {
+                        return Err(eyre::eyre!(
+                            "yl:configure on line {} must appear before any YAML content",
+                            line_number
+                        ));
+                    }
+
                     self.directives
                         .entry(line_number)
                         .or_default()
@@ -99,6 +117,19 @@ impl InlineConfigManager {
         self.active_configs.get(rule_id)
     }
 
+    /// Get the file-level parameter overrides for a rule
+    pub fn get_file_rule_config(&self, rule_id: &str) -> Option<&RuleConfig> {
+        self.file_configs.get(rule_id)
+    }
+
+    /// Whether a line can appear in the file header (comments, blanks, document markers)
+    fn is_header_line(trimmed: &str) -> bool {
+        trimmed.is_empty()
+            || trimmed.starts_with('#')
+            || trimmed == "---"
+            || trimmed.starts_with('%')
+    }
+
     /// Apply a directive to the current state
     fn apply_directive(&mut self, _line_number: usize, directive: Directive) -> Result<()> {
         match directive {
@@ -167,6 +198,13 @@ impl InlineConfigManager {
                     config.set_param(key, config_value);
                 }
             }
+            Directive::Configure { rule, params } => {
+                let config = self.file_configs.entry(rule).or_default();
+                for (key, value) in params {
+                    let config_value = Self::parse_config_value(&value)?;
+                    config.set_param(key, config_value);
+                }
+            }
             Directive::IgnoreFile => {
                 self.file_ignored = true;
             }
@@ -205,6 +243,7 @@ impl InlineConfigManager {
     fn reset(&mut self) {
         self.directives.clear();
         self.active_configs.clear();
+        self.file_configs.clear();
         self.disabled_rules.clear();
         self.line_disabled_rules.clear();
         self.file_ignored = false;
@@ -290,6 +329,26 @@ mod tests {
         assert_eq!(config.get_bool("allow-non-breakable-words"), Some(false));
     }
 
+    #[test]
+    fn test_configure_file_level() {
+        let mut manager = InlineConfigManager::new();
+        let content = "# Helm values\n# yl:configure line-length max=160\n---\nkey: value";
+
+        manager.process_file(content).unwrap();
+
+        let config = manager.get_file_rule_config("line-length").unwrap();
+        assert_eq!(config.get_int("max"), Some(160));
+        assert!(manager.get_rule_config("line-length", 4).is_none());
+    }
+
+    #[test]
+    fn test_configure_after_content_rejected() {
+        let mut manager = InlineConfigManager::new();
+        let content = "key: value\n# yl:configure line-length max=160";
+
+        assert!(manager.process_file(content).is_err());
+    }
+
     #[test]
     fn test_enable_after_disable() {
         let mut manager = InlineConfigManager::new();
@@ -0,0 +1,131 @@
+//! The layers a resolved configuration is built from, for reporting where its settings
+//! come from
+
+use super::Config;
+use eyre::{Context, Result};
+use serde::{Serialize, Serializer};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where a layer of configuration comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// Built-in defaults of yl and its rules
+    Default,
+    /// A built-in configuration named by `extends`, e.g. `strict`
+    Preset(String),
+    /// A configuration file
+    File(PathBuf),
+    /// A profile of the root configuration, selected with `--profile`
+    Profile(String),
+    /// Options given on the command line
+    CommandLine,
+    /// Properties of the `.editorconfig` files that apply
+    EditorConfig,
+    /// `yl:configure` and `yl:set` directives in the linted file
+    Directive,
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::Preset(name) => write!(f, "preset {name}"),
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Profile(name) => write!(f, "profile {name}"),
+            Self::CommandLine => write!(f, "command line"),
+            Self::EditorConfig => write!(f, ".editorconfig"),
+            Self::Directive => write!(f, "directive"),
+        }
+    }
+}
+
+impl Serialize for ConfigSource {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// A configuration with everything up to and including one more source applied
+#[derive(Debug, Clone)]
+pub struct ConfigLayer {
+    pub source: ConfigSource,
+    pub config: Config,
+}
+
+impl ConfigLayer {
+    pub fn new(source: ConfigSource, config: Config) -> Self {
+        Self { source, config }
+    }
+}
+
+impl Config {
+    /// Layers of a configuration file: its `extends` chain from the outermost base,
+    /// ending with the file itself, which is equal to [`Config::from_file`]
+    pub fn layers(config_file: &Path) -> Result<Vec<ConfigLayer>> {
+        let content = fs::read_to_string(config_file)
+            .with_context(|| format!("Failed to read config file: {}", config_file.display()))?;
+        let extends = serde_yaml::from_str::<Config>(&content)
+            .with_context(|| format!("Failed to parse config file: {}", config_file.display()))?
+            .extends;
+
+        let mut layers = match &extends {
+            Some(base_name) => match Self::preset(base_name) {
+                Some(preset) => vec![ConfigLayer::new(
+                    ConfigSource::Preset(base_name.clone()),
+                    preset,
+                )],
+                None => {
+                    let mut layers =
+                        Self::layers(&Self::base_config_path(base_name, config_file)?)?;
+                    // Bases are loaded like a root configuration
+                    if let Some(base) = layers.last_mut()
+                        && base.config.yaml_files.is_empty()
+                    {
+                        base.config.yaml_files = Self::default().yaml_files;
+                    }
+                    layers
+                }
+            },
+            None => Vec::new(),
+        };
+
+        layers.push(ConfigLayer::new(
+            ConfigSource::File(config_file.to_path_buf()),
+            Self::from_file(config_file)?,
+        ));
+        Ok(layers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_layers_follow_extends() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let base = temp_dir.path().join("base.yaml");
+        let config_file = temp_dir.path().join(".yl.yaml");
+        fs::write(&base, "extends: strict\nrule-timeout: 100\n").unwrap();
+        fs::write(&config_file, "extends: base.yaml\n").unwrap();
+
+        let layers = Config::layers(&config_file).unwrap();
+        let sources: Vec<_> = layers.iter().map(|layer| &layer.source).collect();
+        assert_eq!(
+            sources,
+            vec![
+                &ConfigSource::Preset("strict".to_string()),
+                &ConfigSource::File(base),
+                &ConfigSource::File(config_file),
+            ]
+        );
+        assert_eq!(layers[2].config.rule_timeout, Some(100));
+        assert_eq!(
+            ConfigSource::Preset("strict".to_string()).to_string(),
+            "preset strict"
+        );
+    }
+}
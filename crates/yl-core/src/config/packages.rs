@@ -0,0 +1,335 @@
+use eyre::{Context, ContextCompat, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::str::FromStr;
+use tracing::debug;
+
+/// Prefix of `extends` values that name a configuration package
+pub const PACKAGE_PREFIX: &str = "package:";
+
+/// Name of the file listing the registries packages are fetched from, in the user
+/// configuration directory
+const REGISTRIES_FILE_NAME: &str = "registries.yaml";
+
+/// A pinned configuration package, written `package:<name>@<version>` in `extends`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageSpec {
+    pub name: String,
+    pub version: String,
+}
+
+impl FromStr for PackageSpec {
+    type Err = eyre::Report;
+
+    /// Parse `<name>@<version>`, with or without the `package:` prefix
+    fn from_str(value: &str) -> Result<Self> {
+        let value = value.strip_prefix(PACKAGE_PREFIX).unwrap_or(value);
+        let (name, version) = value.split_once('@').with_context(|| {
+            format!("Package '{value}' must pin a version: package:<name>@<version>")
+        })?;
+
+        for part in [name, version] {
+            // Both parts become path components in the registry and the cache
+            let valid = !part.is_empty()
+                && !part.starts_with('.')
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+            if !valid {
+                return Err(eyre::eyre!(
+                    "Invalid package '{value}': name and version may only contain letters, digits, '.', '-' and '_'"
+                ));
+            }
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            version: version.to_string(),
+        })
+    }
+}
+
+impl std::fmt::Display for PackageSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}@{}", self.name, self.version)
+    }
+}
+
+/// A place packages are published to, laid out as `<name>/<version>.yaml`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "RegistryEntry")]
+pub enum Registry {
+    /// A local directory
+    Path(PathBuf),
+    /// A git repository, cloned into the cache
+    Git(String),
+}
+
+/// A registry as written in the registries file: `- path: <dir>` or `- git: <url>`
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RegistryEntry {
+    path: Option<PathBuf>,
+    git: Option<String>,
+}
+
+impl TryFrom<RegistryEntry> for Registry {
+    type Error = String;
+
+    fn try_from(entry: RegistryEntry) -> std::result::Result<Self, Self::Error> {
+        match (entry.path, entry.git) {
+            (Some(path), None) => Ok(Self::Path(path)),
+            (None, Some(url)) => Ok(Self::Git(url)),
+            _ => Err("a registry needs exactly one of `path` or `git`".to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RegistriesFile {
+    #[serde(default)]
+    registries: Vec<Registry>,
+}
+
+/// Resolves configuration packages to files, fetching them from the configured
+/// registries into a local cache on first use
+#[derive(Debug, Clone)]
+pub struct PackageResolver {
+    registries: Vec<Registry>,
+    cache_dir: PathBuf,
+}
+
+impl PackageResolver {
+    /// Create a resolver over the given registries, searched in order
+    pub fn new(registries: Vec<Registry>, cache_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            registries,
+            cache_dir: cache_dir.into(),
+        }
+    }
+
+    /// Create a resolver from the user's `~/.config/yl/registries.yaml`, caching under
+    /// the user cache directory
+    pub fn from_user_config() -> Result<Self> {
+        let config_dir = dirs::config_local_dir()
+            .or_else(|| dirs::home_dir().map(|h| h.join(".config")))
+            .context("Could not determine config directory")?;
+        let cache_dir = dirs::cache_dir()
+            .or_else(|| dirs::home_dir().map(|h| h.join(".cache")))
+            .context("Could not determine cache directory")?;
+
+        let registries_file = config_dir.join("yl").join(REGISTRIES_FILE_NAME);
+        let registries = if registries_file.is_file() {
+            Self::read_registries(&registries_file)?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self::new(registries, cache_dir.join("yl")))
+    }
+
+    /// Read the registries listed in a registries file
+    pub fn read_registries(path: &Path) -> Result<Vec<Registry>> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read registries file: {}", path.display()))?;
+        let file: RegistriesFile = serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse registries file: {}", path.display()))?;
+        Ok(file.registries)
+    }
+
+    /// Path of the cached configuration file of a package, fetching it if it is not
+    /// cached yet. Versions are immutable, so a cached package is never fetched again.
+    pub fn resolve(&self, spec: &PackageSpec) -> Result<PathBuf> {
+        let cached = self
+            .cache_dir
+            .join("packages")
+            .join(&spec.name)
+            .join(format!("{}.yaml", spec.version));
+        if cached.is_file() {
+            debug!(package = %spec, path = %cached.display(), "using cached package");
+            return Ok(cached);
+        }
+
+        if self.registries.is_empty() {
+            return Err(eyre::eyre!(
+                "Package '{spec}' cannot be fetched: no registries are configured in ~/.config/yl/{REGISTRIES_FILE_NAME}"
+            ));
+        }
+
+        for registry in &self.registries {
+            let root = match registry {
+                Registry::Path(path) => path.clone(),
+                Registry::Git(url) => self.fetch_git(url)?,
+            };
+            let source = root.join(&spec.name).join(format!("{}.yaml", spec.version));
+            if !source.is_file() {
+                continue;
+            }
+
+            debug!(package = %spec, source = %source.display(), "caching package");
+            let parent = cached.parent().expect("cached package path has a parent");
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+            fs::copy(&source, &cached)
+                .with_context(|| format!("Failed to cache package '{spec}'"))?;
+            return Ok(cached);
+        }
+
+        Err(eyre::eyre!(
+            "Package '{spec}' not found in any registry (looked for {}/{}.yaml)",
+            spec.name,
+            spec.version
+        ))
+    }
+
+    /// Clone a git registry into the cache, or update the existing clone
+    fn fetch_git(&self, url: &str) -> Result<PathBuf> {
+        let name: String = url
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        let checkout = self.cache_dir.join("registries").join(name);
+
+        if checkout.join(".git").is_dir() {
+            debug!(registry = url, "updating git registry");
+            git(
+                &checkout,
+                &["fetch", "--quiet", "--depth", "1", "origin", "HEAD"],
+            )?;
+            git(&checkout, &["checkout", "--quiet", "--force", "FETCH_HEAD"])?;
+        } else {
+            debug!(registry = url, "cloning git registry");
+            let parent = checkout.parent().expect("checkout path has a parent");
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+            let target = checkout.to_string_lossy();
+            git(parent, &["clone", "--quiet", "--depth", "1", url, &target])?;
+        }
+
+        Ok(checkout)
+    }
+}
+
+/// Run a git command in `dir`
+fn git(dir: &Path, args: &[&str]) -> Result<()> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .context("Failed to run git")?;
+
+    if !output.status.success() {
+        return Err(eyre::eyre!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_parse_spec() {
+        let spec: PackageSpec = "package:platform@1.2.0".parse().unwrap();
+        assert_eq!(spec.name, "platform");
+        assert_eq!(spec.version, "1.2.0");
+        assert_eq!(spec.to_string(), "platform@1.2.0");
+
+        assert!("package:platform".parse::<PackageSpec>().is_err());
+        assert!("package:../etc@1".parse::<PackageSpec>().is_err());
+        assert!("package:platform@".parse::<PackageSpec>().is_err());
+    }
+
+    #[test]
+    fn test_read_registries() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("registries.yaml");
+        write(
+            &file,
+            "registries:\n  - path: /srv/yl\n  - git: https://example.com/yl.git\n",
+        );
+
+        let registries = PackageResolver::read_registries(&file).unwrap();
+        assert_eq!(
+            registries,
+            vec![
+                Registry::Path(PathBuf::from("/srv/yl")),
+                Registry::Git("https://example.com/yl.git".to_string()),
+            ]
+        );
+
+        write(
+            &file,
+            "registries:\n  - path: /srv/yl\n    git: https://example.com/yl.git\n",
+        );
+        assert!(PackageResolver::read_registries(&file).is_err());
+    }
+
+    #[test]
+    fn test_resolve_from_path_registry_and_cache() {
+        let dir = TempDir::new().unwrap();
+        let registry = dir.path().join("registry");
+        write(&registry.join("platform/1.0.0.yaml"), "rules: {}\n");
+
+        let resolver = PackageResolver::new(
+            vec![
+                Registry::Path(dir.path().join("missing")),
+                Registry::Path(registry.clone()),
+            ],
+            dir.path().join("cache"),
+        );
+        let spec: PackageSpec = "platform@1.0.0".parse().unwrap();
+        let cached = resolver.resolve(&spec).unwrap();
+        assert_eq!(
+            cached,
+            dir.path().join("cache/packages/platform/1.0.0.yaml")
+        );
+
+        // A cached version is used even once the registry no longer has it
+        fs::remove_dir_all(&registry).unwrap();
+        assert_eq!(resolver.resolve(&spec).unwrap(), cached);
+
+        let missing: PackageSpec = "platform@2.0.0".parse().unwrap();
+        let error = resolver.resolve(&missing).unwrap_err().to_string();
+        assert!(error.contains("not found in any registry"), "{error}");
+    }
+
+    /// Commit everything in a repository
+    fn commit(repo: &Path, message: &str) {
+        git(repo, &["add", "."]).unwrap();
+        let identity = ["-c", "user.name=yl", "-c", "user.email=yl@example.com"];
+        let args = [&identity[..], &["commit", "--quiet", "-m", message]].concat();
+        git(repo, &args).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_from_git_registry() {
+        let dir = TempDir::new().unwrap();
+        let repo = dir.path().join("repo");
+        write(&repo.join("security/3.yaml"), "rules: {}\n");
+        git(&repo, &["init", "--quiet"]).unwrap();
+        commit(&repo, "Add security 3");
+
+        let url = format!("file://{}", repo.display());
+        let resolver = PackageResolver::new(vec![Registry::Git(url)], dir.path().join("cache"));
+        let cached = resolver.resolve(&"security@3".parse().unwrap()).unwrap();
+        assert_eq!(fs::read_to_string(cached).unwrap(), "rules: {}\n");
+
+        // The existing clone is updated for versions published later
+        write(&repo.join("security/4.yaml"), "rules: {}\n");
+        commit(&repo, "Add security 4");
+        assert!(resolver.resolve(&"security@4".parse().unwrap()).is_ok());
+    }
+}
@@ -0,0 +1,186 @@
+//! Native plugins yl may load, from the `plugins` section of the configuration
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Where plugins are looked for and which of them may be loaded. Libraries of plugins
+/// missing from `allow` are refused, so nothing is loaded until plugins are listed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginsConfig {
+    /// Directories `yl plugin list` loads plugins from; `~/` is the home directory
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub directories: Vec<PathBuf>,
+    /// Plugins that may be loaded
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<AllowedPlugin>,
+}
+
+impl PluginsConfig {
+    /// Check if nothing is configured
+    pub fn is_empty(&self) -> bool {
+        self.directories.is_empty() && self.allow.is_empty()
+    }
+
+    /// The configured directories, with `~/` expanded
+    pub fn directories(&self) -> Vec<PathBuf> {
+        self.directories
+            .iter()
+            .map(|dir| match (dir.strip_prefix("~"), dirs::home_dir()) {
+                (Ok(rest), Some(home)) => home.join(rest),
+                _ => dir.clone(),
+            })
+            .collect()
+    }
+
+    /// The allowlist entry of a library, found by its file name or its checksum before
+    /// the library is opened
+    pub fn allowed_library(&self, file_name: &str, sha256: &str) -> Option<&AllowedPlugin> {
+        self.allow.iter().find(|plugin| {
+            plugin.file.as_deref() == Some(file_name) || plugin.sha256.as_deref() == Some(sha256)
+        })
+    }
+}
+
+/// A plugin that may be loaded: the plugin as `name@version`, and its library by `file`
+/// name, by the SHA-256 of its content in hex, or by both
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "AllowedPluginSpec", into = "AllowedPluginSpec")]
+pub struct AllowedPlugin {
+    pub name: String,
+    pub version: String,
+    pub file: Option<String>,
+    pub sha256: Option<String>,
+}
+
+impl std::fmt::Display for AllowedPlugin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}@{}", self.name, self.version)
+    }
+}
+
+/// How an allowed plugin is written in the configuration; a bare `name@version` is
+/// accepted only to report that it does not name a library
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum AllowedPluginSpec {
+    Plugin(String),
+    Library {
+        plugin: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        file: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        sha256: Option<String>,
+    },
+}
+
+impl TryFrom<AllowedPluginSpec> for AllowedPlugin {
+    type Error = String;
+
+    fn try_from(spec: AllowedPluginSpec) -> Result<Self, Self::Error> {
+        let (plugin, file, sha256) = match spec {
+            AllowedPluginSpec::Plugin(plugin) => (plugin, None, None),
+            AllowedPluginSpec::Library {
+                plugin,
+                file,
+                sha256,
+            } => (plugin, file, sha256),
+        };
+        let Some((name, version)) = plugin
+            .split_once('@')
+            .filter(|(name, version)| !name.is_empty() && !version.is_empty())
+        else {
+            return Err(format!(
+                "allowed plugin '{plugin}' must be written name@version"
+            ));
+        };
+        if file.is_none() && sha256.is_none() {
+            return Err(format!(
+                "allowed plugin '{plugin}' must name its library with file or sha256"
+            ));
+        }
+        if let Some(file) = &file
+            && (file.is_empty() || file.contains(['/', '\\']))
+        {
+            return Err(format!(
+                "file of plugin '{plugin}' must be the name of the library, without a directory"
+            ));
+        }
+        let sha256 = sha256.map(|sha256| sha256.to_ascii_lowercase());
+        if let Some(sha256) = &sha256
+            && (sha256.len() != 64 || !sha256.chars().all(|c| c.is_ascii_hexdigit()))
+        {
+            return Err(format!(
+                "sha256 of plugin '{plugin}' must be 64 hexadecimal digits"
+            ));
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            version: version.to_string(),
+            file,
+            sha256,
+        })
+    }
+}
+
+impl From<AllowedPlugin> for AllowedPluginSpec {
+    fn from(plugin: AllowedPlugin) -> Self {
+        Self::Library {
+            plugin: plugin.to_string(),
+            file: plugin.file,
+            sha256: plugin.sha256,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHA256: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+    #[test]
+    fn test_allow_list() {
+        let config: PluginsConfig = serde_yaml::from_str(&format!(
+            "directories: [~/.yl/plugins]\nallow:\n  - plugin: team-rules@0.1.0\n    file: libteam_rules.so\n  - plugin: k8s@1.2.0\n    sha256: {}\n",
+            SHA256.to_uppercase()
+        ))
+        .unwrap();
+
+        let team = config.allowed_library("libteam_rules.so", "").unwrap();
+        assert_eq!(
+            (team.name.as_str(), team.sha256.as_deref()),
+            ("team-rules", None)
+        );
+        assert_eq!(
+            config.allowed_library("libk8s.so", SHA256).unwrap().name,
+            "k8s"
+        );
+        assert_eq!(config.allowed_library("libother.so", ""), None);
+        assert!(!config.directories()[0].starts_with("~"));
+
+        let serialized = serde_yaml::to_string(&config).unwrap();
+        assert_eq!(
+            serde_yaml::from_str::<PluginsConfig>(&serialized).unwrap(),
+            config
+        );
+    }
+
+    #[test]
+    fn test_invalid_entries() {
+        for allow in [
+            "[team-rules@0.1.0]",
+            "[team-rules]",
+            "['@1.0']",
+            "['team-rules@']",
+            "[{plugin: k8s@1.2.0, sha256: abc}]",
+            "[{plugin: k8s@1.2.0, file: lib/libk8s.so}]",
+        ] {
+            let yaml = format!("allow: {allow}\n");
+            assert!(
+                serde_yaml::from_str::<PluginsConfig>(&yaml).is_err(),
+                "{allow}"
+            );
+        }
+    }
+}
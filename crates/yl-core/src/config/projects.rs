@@ -0,0 +1,107 @@
+//! Projects of a monorepo, from the `projects` section of the root configuration
+
+use super::IgnorePatterns;
+use eyre::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// A project of a monorepo: the files below the directories its root glob matches
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectConfig {
+    /// Name problems and statistics are grouped under
+    pub name: String,
+    /// Glob of the project's directories, matched like an `ignore` pattern
+    pub root: String,
+}
+
+/// Compiled project roots, resolving each file to the first project containing it
+#[derive(Debug, Clone, Default)]
+pub struct Projects {
+    projects: Vec<(String, IgnorePatterns)>,
+}
+
+impl Projects {
+    /// Compile the roots of the configured projects
+    pub fn new(projects: &[ProjectConfig]) -> Self {
+        Self {
+            projects: projects
+                .iter()
+                .map(|project| {
+                    (
+                        project.name.clone(),
+                        IgnorePatterns::new(&[project.root.as_str()]),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Check that every project has a unique name and a valid root
+    pub fn validate(projects: &[ProjectConfig]) -> Result<()> {
+        let mut names = HashSet::new();
+        for project in projects {
+            if project.name.is_empty() {
+                return Err(eyre::eyre!(
+                    "Project with root '{}' has no name",
+                    project.root
+                ));
+            }
+            if !names.insert(project.name.as_str()) {
+                return Err(eyre::eyre!("Duplicate project name '{}'", project.name));
+            }
+            IgnorePatterns::validate(&project.root)
+                .with_context(|| format!("Invalid root of project '{}'", project.name))?;
+        }
+        Ok(())
+    }
+
+    /// Name of the first project whose root contains the file
+    pub fn project_for(&self, file_path: &Path) -> Option<&str> {
+        self.projects
+            .iter()
+            .find(|(_, root)| root.is_ignored(file_path))
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Check if no projects are configured
+    pub fn is_empty(&self) -> bool {
+        self.projects.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(name: &str, root: &str) -> ProjectConfig {
+        ProjectConfig {
+            name: name.to_string(),
+            root: root.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_project_for() {
+        let projects = Projects::new(&[
+            project("api", "services/api"),
+            project("services", "services/*/"),
+            project("charts", "charts"),
+        ]);
+
+        let resolve = |path: &str| projects.project_for(Path::new(path));
+        assert_eq!(resolve("services/api/deploy.yaml"), Some("api"));
+        assert_eq!(resolve("./services/web/deploy.yaml"), Some("services"));
+        assert_eq!(resolve("infra/charts/app/values.yaml"), Some("charts"));
+        assert_eq!(resolve("services/root.yaml"), None);
+        assert_eq!(resolve("other.yaml"), None);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(Projects::validate(&[project("api", "services/api")]).is_ok());
+        assert!(Projects::validate(&[project("", "services/api")]).is_err());
+        assert!(Projects::validate(&[project("a", "x"), project("a", "y")]).is_err());
+        assert!(Projects::validate(&[project("api", "services/[api")]).is_err());
+    }
+}
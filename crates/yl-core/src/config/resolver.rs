@@ -0,0 +1,478 @@
+use super::editorconfig::{EditorConfigResolver, EditorConfigSettings};
+use super::{Config, ConfigLayer, ConfigSource};
+use eyre::{Context, Result};
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tracing::debug;
+
+/// Adjustments applied on top of every resolved configuration (e.g. CLI overrides)
+type ConfigOverrides = Arc<dyn Fn(&mut Config) + Send + Sync>;
+
+/// Resolves the effective configuration for each linted file.
+///
+/// The nearest `.yl.yaml` to a file applies, merged over the configs found in
+/// its ancestor directories, which are in turn merged over the root config. Only
+/// directories below the root directory are searched, so configuration files above
+/// the project never apply, and files outside it get the root config alone.
+pub struct ConfigResolver {
+    /// Root configuration (loaded from the working directory or `--config`)
+    base: Arc<Config>,
+    /// Whether nested configuration files are looked up at all
+    discovery: bool,
+    /// Whether nested configuration files have environment variables expanded
+    env_expand: bool,
+    /// Directory whose subdirectories are searched for nested configuration files; the
+    /// working directory if unset
+    root: Option<PathBuf>,
+    /// Adjustments applied after merging nested configurations
+    overrides: Option<ConfigOverrides>,
+    /// Resolved configurations keyed by directory
+    cache: Mutex<HashMap<PathBuf, Arc<Config>>>,
+    /// Lookup of `.editorconfig` defaults
+    editorconfig: EditorConfigResolver,
+}
+
+impl ConfigResolver {
+    /// Create a resolver with nested config discovery enabled
+    pub fn new(base: Config) -> Self {
+        Self {
+            base: Arc::new(base),
+            discovery: true,
+            env_expand: true,
+            root: None,
+            overrides: None,
+            cache: Mutex::new(HashMap::new()),
+            editorconfig: EditorConfigResolver::new(),
+        }
+    }
+
+    /// Enable or disable lookup of nested configuration files
+    pub fn with_discovery(mut self, enabled: bool) -> Self {
+        self.discovery = enabled;
+        self.clear_cache();
+        self
+    }
+
+    /// Enable or disable [`Config::expand_env`] on nested configuration files
+    pub fn with_env_expand(mut self, enabled: bool) -> Self {
+        self.env_expand = enabled;
+        self.clear_cache();
+        self
+    }
+
+    /// Search for nested configuration files below `dir` rather than the working directory
+    pub fn with_root(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.root = Some(dir.into());
+        self.clear_cache();
+        self
+    }
+
+    /// Apply the given adjustments to every resolved configuration
+    pub fn with_overrides<F>(mut self, overrides: F) -> Self
+    where
+        F: Fn(&mut Config) + Send + Sync + 'static,
+    {
+        self.overrides = Some(Arc::new(overrides));
+        self.clear_cache();
+        self
+    }
+
+    /// Resolve the configuration that applies to the given file
+    pub fn resolve(&self, file_path: &Path) -> Result<Arc<Config>> {
+        let dir = file_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+
+        if let Some(config) = self.cache.lock().expect("config cache poisoned").get(&dir) {
+            return Ok(Arc::clone(config));
+        }
+
+        let mut config = if self.discovery {
+            self.merge_for_dir(&dir)?
+        } else {
+            (*self.base).clone()
+        };
+
+        if let Some(overrides) = &self.overrides {
+            overrides(&mut config);
+        }
+
+        let config = Arc::new(config);
+        self.cache
+            .lock()
+            .expect("config cache poisoned")
+            .insert(dir, Arc::clone(&config));
+
+        Ok(config)
+    }
+
+    /// Layers of the configuration [`ConfigResolver::resolve`] returns for a file: `root`,
+    /// the layers of the root configuration, then those of each nested configuration
+    /// file and the overrides. Without `root` layers, the root configuration is one layer.
+    pub fn layers(&self, file_path: &Path, root: Vec<ConfigLayer>) -> Result<Vec<ConfigLayer>> {
+        let mut layers = root;
+        if layers.is_empty() {
+            layers.push(ConfigLayer::new(
+                ConfigSource::Default,
+                (*self.base).clone(),
+            ));
+        }
+
+        if self.discovery {
+            let dir = file_path.parent().unwrap_or(Path::new(""));
+            for config_file in self.config_files_for_dir(dir) {
+                let base = layers.last().map(|layer| layer.config.clone());
+                for layer in Config::layers(&config_file)? {
+                    let config = match &base {
+                        Some(base) => layer.config.merge_with_base(base.clone())?,
+                        None => layer.config,
+                    };
+                    layers.push(ConfigLayer::new(layer.source, config));
+                }
+            }
+        }
+
+        if let Some(overrides) = &self.overrides
+            && let Some(last) = layers.last()
+        {
+            let mut config = last.config.clone();
+            overrides(&mut config);
+            layers.push(ConfigLayer::new(ConfigSource::CommandLine, config));
+        }
+
+        Ok(layers)
+    }
+
+    /// Resolve the `.editorconfig` settings that apply to the given file
+    pub fn editorconfig(&self, file_path: &Path) -> EditorConfigSettings {
+        self.editorconfig.resolve(file_path)
+    }
+
+    /// Forget all resolved configurations
+    pub fn clear_cache(&self) {
+        self.cache.lock().expect("config cache poisoned").clear();
+        self.editorconfig.clear_cache();
+    }
+
+    /// Merge every config file from the outermost ancestor down to `dir`
+    fn merge_for_dir(&self, dir: &Path) -> Result<Config> {
+        let mut config = (*self.base).clone();
+
+        for config_file in self.config_files_for_dir(dir) {
+            debug!(
+                config = %config_file.display(),
+                dir = %dir.display(),
+                "applying nested configuration"
+            );
+            let mut nested = Config::from_file(&config_file)?;
+            if self.env_expand {
+                nested.expand_env().with_context(|| {
+                    format!("Failed to expand config file: {}", config_file.display())
+                })?;
+            }
+            config = nested.merge_with_base(config)?;
+        }
+
+        Ok(config)
+    }
+
+    /// Config files in `dir` and its ancestors below the root directory, outermost first.
+    /// The root directory's own config file is the root config, and is not repeated.
+    fn config_files_for_dir(&self, dir: &Path) -> Vec<PathBuf> {
+        let root = match &self.root {
+            Some(root) => normalize(root),
+            None => std::env::current_dir().ok(),
+        };
+        let (Some(root), Some(dir)) = (root, normalize(dir)) else {
+            return Vec::new();
+        };
+        if !dir.starts_with(&root) {
+            return Vec::new();
+        }
+
+        let ancestors: Vec<&Path> = dir
+            .ancestors()
+            .take_while(|ancestor| *ancestor != root)
+            .collect();
+
+        ancestors
+            .into_iter()
+            .rev()
+            .filter_map(Config::find_in_dir)
+            .collect()
+    }
+}
+
+/// The absolute form of a path without `.` and `..` components, so relative and absolute
+/// paths of the same file compare equal. Symbolic links are not followed.
+fn normalize(path: &Path) -> Option<PathBuf> {
+    let mut normalized = PathBuf::new();
+    for component in std::path::absolute(path).ok()?.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    Some(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write_config(dir: &Path, max: i64) {
+        let content = format!(
+            "rules:\n  line-length:\n    enabled: true\n    level: Error\n    params:\n      max: {max}\n"
+        );
+        fs::create_dir_all(dir).expect("Failed to create dir");
+        fs::write(dir.join(".yl.yaml"), content).expect("Failed to write config");
+    }
+
+    fn max_for(resolver: &ConfigResolver, path: &Path) -> Option<i64> {
+        resolver
+            .resolve(path)
+            .expect("Failed to resolve config")
+            .rules
+            .get("line-length")
+            .and_then(|rule| rule.get_int("max"))
+    }
+
+    #[test]
+    fn test_nearest_config_wins() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let root = temp_dir.path();
+        write_config(root, 100);
+        write_config(&root.join("charts"), 120);
+
+        let base = Config::from_file(&root.join(".yl.yaml")).unwrap();
+        let resolver = ConfigResolver::new(base).with_root(root);
+
+        assert_eq!(max_for(&resolver, &root.join("values.yaml")), Some(100));
+        assert_eq!(
+            max_for(&resolver, &root.join("charts/values.yaml")),
+            Some(120)
+        );
+        assert_eq!(
+            max_for(&resolver, &root.join("charts/nested/values.yaml")),
+            Some(120)
+        );
+        assert_eq!(
+            max_for(&resolver, &root.join("other/values.yaml")),
+            Some(100)
+        );
+    }
+
+    #[test]
+    fn test_nested_config_inherits_ancestor_settings() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let root = temp_dir.path();
+        write_config(&root.join("charts"), 120);
+
+        let resolver = ConfigResolver::new(Config::default()).with_root(root);
+        let config = resolver
+            .resolve(&root.join("charts/values.yaml"))
+            .expect("Failed to resolve config");
+
+        // Rules and patterns not mentioned in the nested file come from the root config
+        assert!(config.rules.contains_key("trailing-spaces"));
+        assert_eq!(config.yaml_files, Config::default().yaml_files);
+    }
+
+    #[test]
+    fn test_layers() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let root = temp_dir.path();
+        write_config(&root.join("charts"), 120);
+
+        let resolver = ConfigResolver::new(Config::default())
+            .with_root(root)
+            .with_overrides(|config| {
+                config.rule_timeout = Some(50);
+            });
+        let file_path = root.join("charts/values.yaml");
+        let layers = resolver.layers(&file_path, Vec::new()).unwrap();
+
+        let sources: Vec<_> = layers.iter().map(|layer| &layer.source).collect();
+        assert_eq!(
+            sources,
+            vec![
+                &ConfigSource::Default,
+                &ConfigSource::File(root.join("charts/.yl.yaml")),
+                &ConfigSource::CommandLine,
+            ]
+        );
+        // The last layer is the resolved configuration
+        let last = &layers[2].config;
+        let resolved = resolver.resolve(&file_path).unwrap();
+        assert_eq!(last.rules, resolved.rules);
+        assert_eq!(last.rule_timeout, Some(50));
+    }
+
+    #[test]
+    fn test_discovery_disabled() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let root = temp_dir.path();
+        write_config(&root.join("charts"), 100);
+
+        let resolver = ConfigResolver::new(Config::default())
+            .with_root(root)
+            .with_discovery(false);
+        assert_eq!(
+            max_for(&resolver, &root.join("charts/values.yaml")),
+            Some(80)
+        );
+    }
+
+    #[test]
+    fn test_overrides_apply_after_nested_configs() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let root = temp_dir.path();
+        write_config(&root.join("charts"), 100);
+
+        let resolver = ConfigResolver::new(Config::default())
+            .with_root(root)
+            .with_overrides(|config| {
+                if let Some(rule) = config.rules.get_mut("line-length") {
+                    rule.set_param("max", 200i64);
+                }
+            });
+
+        assert_eq!(
+            max_for(&resolver, &root.join("charts/values.yaml")),
+            Some(200)
+        );
+    }
+
+    #[test]
+    fn test_nested_config_overrides_stop_on_syntax_error() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let root = temp_dir.path();
+        for (dir, stop) in [
+            ("on", true),
+            ("on/off", false),
+            ("off", false),
+            ("off/on", true),
+        ] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+            fs::write(
+                root.join(dir).join(".yl.yaml"),
+                format!("stop-on-syntax-error: {stop}\n"),
+            )
+            .unwrap();
+        }
+
+        let resolver = ConfigResolver::new(Config::default()).with_root(root);
+        let stop = |path: &str| {
+            resolver
+                .resolve(&root.join(path))
+                .expect("Failed to resolve config")
+                .stop_on_syntax_error()
+        };
+
+        assert!(stop("values.yaml"));
+        assert!(stop("on/values.yaml"));
+        assert!(!stop("on/off/values.yaml"));
+        assert!(!stop("off/values.yaml"));
+        assert!(stop("off/on/values.yaml"));
+        // A nested file that does not set it inherits the parent's setting
+        fs::create_dir_all(root.join("on/unset")).unwrap();
+        fs::write(root.join("on/unset/.yl.yaml"), "ignore: [tmp/**]\n").unwrap();
+        assert!(stop("on/unset/values.yaml"));
+    }
+
+    #[test]
+    fn test_nested_config_overrides_allow_empty_files() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let root = temp_dir.path();
+        for (dir, allow) in [("strict", false), ("strict/lenient", true)] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+            fs::write(
+                root.join(dir).join(".yl.yaml"),
+                format!("allow-empty-files: {allow}\n"),
+            )
+            .unwrap();
+        }
+        fs::create_dir_all(root.join("unset")).unwrap();
+        fs::write(root.join("unset/.yl.yaml"), "ignore: [tmp/**]\n").unwrap();
+
+        let base: Config = serde_yaml::from_str("allow-empty-files: true\n").unwrap();
+        let resolver = ConfigResolver::new(base).with_root(root);
+        let allow = |path: &str| {
+            resolver
+                .resolve(&root.join(path))
+                .expect("Failed to resolve config")
+                .allow_empty_files()
+        };
+
+        assert!(allow("values.yaml"));
+        assert!(!allow("strict/values.yaml"));
+        assert!(allow("strict/lenient/values.yaml"));
+        assert!(allow("unset/values.yaml"));
+    }
+
+    #[test]
+    fn test_nested_config_keeps_generated_detection() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("charts/vendored")).unwrap();
+        fs::write(root.join("charts/.yl.yaml"), "ignore: [tmp/**]\n").unwrap();
+        fs::write(
+            root.join("charts/vendored/.yl.yaml"),
+            "generated-markers: [VENDORED]\n",
+        )
+        .unwrap();
+
+        let base: Config =
+            serde_yaml::from_str("generated-markers: [GENERATED]\ngenerated-header-lines: 1\n")
+                .unwrap();
+        let resolver = ConfigResolver::new(base).with_root(root);
+        let generated = |path: &str, content: &str| {
+            resolver
+                .resolve(&root.join(path))
+                .expect("Failed to resolve config")
+                .is_generated(content)
+        };
+
+        // A nested file that does not set them inherits the root's settings
+        assert!(generated("charts/values.yaml", "# GENERATED\n"));
+        assert!(!generated("charts/values.yaml", "a: 1\n# GENERATED\n"));
+        assert!(!generated("charts/values.yaml", "# DO NOT EDIT\n"));
+        // One that sets the markers keeps the root's header lines
+        assert!(generated("charts/vendored/values.yaml", "# VENDORED\n"));
+        assert!(!generated(
+            "charts/vendored/values.yaml",
+            "a: 1\n# VENDORED\n"
+        ));
+    }
+
+    #[test]
+    fn test_only_configs_below_root_apply() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let outside = temp_dir.path();
+        let project = outside.join("project");
+        write_config(outside, 100);
+        write_config(&project.join("charts"), 120);
+
+        let resolver = ConfigResolver::new(Config::default()).with_root(&project);
+
+        // The config above the project is never layered in
+        assert_eq!(max_for(&resolver, &project.join("values.yaml")), Some(80));
+        assert_eq!(
+            max_for(&resolver, &project.join("charts/values.yaml")),
+            Some(120)
+        );
+        assert_eq!(
+            max_for(&resolver, &project.join("charts/../values.yaml")),
+            Some(80)
+        );
+        // Files outside the project get the root config alone
+        assert_eq!(max_for(&resolver, &outside.join("values.yaml")), Some(80));
+    }
+}
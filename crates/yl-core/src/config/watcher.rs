@@ -0,0 +1,330 @@
+use super::{CONFIG_FILE_NAMES, Config};
+use crate::rules::{RuleConfig, RuleRegistry};
+use eyre::Result;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Outcome of checking the root configuration file for changes
+#[derive(Debug)]
+pub enum ConfigReload {
+    /// The configuration file has not changed since the last check
+    Unchanged,
+    /// The configuration was reloaded; `changes` describes what differs from before
+    Reloaded {
+        config: Box<Config>,
+        changes: Vec<String>,
+    },
+    /// The configuration file changed but could not be loaded or failed validation.
+    /// The previous configuration stays in effect.
+    Invalid(eyre::Report),
+}
+
+/// Watches the root configuration file and reloads it when it changes.
+///
+/// Changes are detected by comparing the file's modification time, so callers decide
+/// when to check: the LSP server checks when the client reports a changed config file.
+pub struct ConfigWatcher {
+    /// Explicit configuration path (`--config`), or `None` to search the working directory
+    config_path: Option<PathBuf>,
+    /// Configuration file being watched
+    watched: PathBuf,
+    /// Modification time of the watched file when it was last loaded
+    modified: Option<SystemTime>,
+    /// Configuration currently in effect
+    current: Config,
+    /// Profile applied to each reloaded configuration (`--profile`)
+    profile: Option<String>,
+    /// Whether reloaded configurations have environment variables expanded
+    env_expand: bool,
+}
+
+#[allow(dead_code)] // Some methods are part of API for future phases
+impl ConfigWatcher {
+    /// Start watching, with `config` being the configuration already loaded from `config_path`
+    pub fn new(config_path: Option<PathBuf>, config: Config) -> Self {
+        let watched = Self::locate(config_path.as_ref());
+        let modified = Self::modified_time(&watched);
+
+        Self {
+            config_path,
+            watched,
+            modified,
+            current: config,
+            profile: None,
+            env_expand: true,
+        }
+    }
+
+    /// Apply the named profile to each reloaded configuration, as it was to the first one
+    pub fn with_profile(mut self, profile: Option<String>) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Enable or disable [`Config::expand_env`] on each reloaded configuration
+    pub fn with_env_expand(mut self, enabled: bool) -> Self {
+        self.env_expand = enabled;
+        self
+    }
+
+    /// Configuration currently in effect
+    pub fn config(&self) -> &Config {
+        &self.current
+    }
+
+    /// Path of the configuration file being watched
+    pub fn watched_path(&self) -> &Path {
+        &self.watched
+    }
+
+    /// Whether a path names a configuration file that can affect linting results,
+    /// including nested `.yl.yaml` files and `.editorconfig`
+    pub fn is_config_file(path: &Path) -> bool {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| CONFIG_FILE_NAMES.contains(&name) || name == ".editorconfig")
+    }
+
+    /// Reload the configuration if the watched file was created, modified or removed
+    pub fn poll(&mut self) -> ConfigReload {
+        // A config file may have been created where none was found before
+        let watched = Self::locate(self.config_path.as_ref());
+        let modified = Self::modified_time(&watched);
+        if watched == self.watched && modified == self.modified {
+            return ConfigReload::Unchanged;
+        }
+
+        self.watched = watched;
+        self.modified = modified;
+
+        match self.load() {
+            Ok(config) => {
+                let changes = describe_changes(&self.current, &config);
+                self.current = config.clone();
+                ConfigReload::Reloaded {
+                    config: Box::new(config),
+                    changes,
+                }
+            }
+            Err(e) => ConfigReload::Invalid(e),
+        }
+    }
+
+    /// Load and validate the configuration
+    fn load(&self) -> Result<Config> {
+        let mut config = Config::load(self.config_path.as_ref())?;
+        if self.env_expand {
+            config.expand_env()?;
+        }
+        if let Some(name) = &self.profile {
+            config = config.with_profile(name)?;
+        }
+        config.validate(&RuleRegistry::with_default_rules())?;
+        Ok(config)
+    }
+
+    /// Resolve which file `Config::load` would read
+    fn locate(config_path: Option<&PathBuf>) -> PathBuf {
+        match config_path {
+            Some(path) => path.clone(),
+            None => Config::default_config_path().unwrap_or_default(),
+        }
+    }
+
+    fn modified_time(path: &Path) -> Option<SystemTime> {
+        path.metadata().and_then(|m| m.modified()).ok()
+    }
+}
+
+/// Rule configuration with parameters missing from the config file filled in from the rule's defaults
+fn effective_rule_config(config: &Config, rule_id: &str, registry: &RuleRegistry) -> RuleConfig {
+    let mut rule_config = config.get_rule_config(rule_id, registry);
+    if let Some(rule) = registry.get(rule_id) {
+        for (key, value) in rule.default_config().params {
+            rule_config.params.entry(key).or_insert(value);
+        }
+    }
+    rule_config
+}
+
+/// Describe the differences between two configurations, one line per change
+pub fn describe_changes(old: &Config, new: &Config) -> Vec<String> {
+    let mut changes = Vec::new();
+
+    // Rules missing from a config file fall back to their defaults, so compare effective configs
+    let registry = RuleRegistry::with_default_rules();
+    let rule_ids: BTreeSet<&String> = old
+        .rules
+        .keys()
+        .chain(new.rules.keys())
+        .chain(old.severities.keys())
+        .chain(new.severities.keys())
+        .collect();
+    for rule_id in rule_ids {
+        let old_rule = effective_rule_config(old, rule_id, &registry);
+        let new_rule = effective_rule_config(new, rule_id, &registry);
+
+        if old_rule.enabled != new_rule.enabled {
+            let state = if new_rule.enabled {
+                "enabled"
+            } else {
+                "disabled"
+            };
+            changes.push(format!("{rule_id}: {state}"));
+        }
+        if old_rule.level != new_rule.level {
+            changes.push(format!(
+                "{rule_id}: level {} -> {}",
+                old_rule.level, new_rule.level
+            ));
+        }
+        if old_rule.ignore != new_rule.ignore {
+            changes.push(format!("{rule_id}: ignore patterns changed"));
+        }
+
+        let params: BTreeSet<&String> = old_rule
+            .params
+            .keys()
+            .chain(new_rule.params.keys())
+            .collect();
+        for param in params {
+            match (old_rule.params.get(param), new_rule.params.get(param)) {
+                (Some(a), Some(b)) if a != b => {
+                    changes.push(format!("{rule_id}.{param}: {a} -> {b}"));
+                }
+                (None, Some(b)) => changes.push(format!("{rule_id}.{param}: set to {b}")),
+                (Some(_), None) => changes.push(format!("{rule_id}.{param}: unset")),
+                _ => {}
+            }
+        }
+    }
+
+    if old.ignore != new.ignore {
+        changes.push("ignore patterns changed".to_string());
+    }
+    if old.yaml_files != new.yaml_files {
+        changes.push("yaml-files patterns changed".to_string());
+    }
+    if old.editorconfig != new.editorconfig {
+        changes.push(format!(
+            "editorconfig: {} -> {}",
+            old.editorconfig, new.editorconfig
+        ));
+    }
+    if old.template_mode != new.template_mode {
+        changes.push(format!(
+            "template-mode: {:?} -> {:?}",
+            old.template_mode, new.template_mode
+        ));
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn write_config(path: &Path, content: &str) {
+        fs::write(path, content).expect("Failed to write config");
+        // Make sure the modification time differs from the previous write
+        let mtime = SystemTime::now() + Duration::from_secs(2);
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .and_then(|file| file.set_modified(mtime))
+            .expect("Failed to set modification time");
+    }
+
+    #[test]
+    fn test_is_config_file() {
+        assert!(ConfigWatcher::is_config_file(Path::new("/repo/.yl.yaml")));
+        assert!(ConfigWatcher::is_config_file(Path::new("sub/yl.yml")));
+        assert!(ConfigWatcher::is_config_file(Path::new(".editorconfig")));
+        assert!(!ConfigWatcher::is_config_file(Path::new("values.yaml")));
+    }
+
+    #[test]
+    fn test_poll_reloads_changed_config() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let path = temp_dir.path().join(".yl.yaml");
+        fs::write(&path, "rules:\n  line-length:\n    enabled: true\n    level: Error\n    params:\n      max: 80\n").unwrap();
+
+        let config = Config::load(Some(&path)).unwrap();
+        let mut watcher = ConfigWatcher::new(Some(path.clone()), config);
+        assert!(matches!(watcher.poll(), ConfigReload::Unchanged));
+
+        write_config(
+            &path,
+            "rules:\n  line-length:\n    enabled: true\n    level: Warning\n    params:\n      max: 120\n",
+        );
+
+        match watcher.poll() {
+            ConfigReload::Reloaded { changes, .. } => {
+                assert!(changes.contains(&"line-length: level error -> warning".to_string()));
+                assert!(changes.contains(&"line-length.max: 80 -> 120".to_string()));
+            }
+            other => panic!("expected reload, got {other:?}"),
+        }
+        assert_eq!(
+            watcher.config().rules["line-length"].get_int("max"),
+            Some(120)
+        );
+        assert!(matches!(watcher.poll(), ConfigReload::Unchanged));
+    }
+
+    #[test]
+    fn test_poll_keeps_previous_config_when_invalid() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let path = temp_dir.path().join(".yl.yaml");
+        fs::write(&path, "rules:\n  line-length:\n    enabled: true\n    level: Error\n    params:\n      max: 80\n").unwrap();
+
+        let config = Config::load(Some(&path)).unwrap();
+        let mut watcher = ConfigWatcher::new(Some(path.clone()), config);
+
+        write_config(
+            &path,
+            "rules:\n  line-length:\n    enabled: true\n    level: Error\n    params:\n      max: 0\n",
+        );
+
+        assert!(matches!(watcher.poll(), ConfigReload::Invalid(_)));
+        assert_eq!(
+            watcher.config().rules["line-length"].get_int("max"),
+            Some(80)
+        );
+    }
+
+    #[test]
+    fn test_describe_changes() {
+        let old = Config::default();
+        let mut new = Config::default();
+        new.rules.get_mut("line-length").unwrap().enabled = false;
+        new.rules.get_mut("truthy").unwrap().ignore = vec!["ci/**".to_string()];
+        new.ignore.push("vendor/".to_string());
+
+        assert_eq!(
+            describe_changes(&old, &new),
+            vec![
+                "line-length: disabled".to_string(),
+                "truthy: ignore patterns changed".to_string(),
+                "ignore patterns changed".to_string()
+            ]
+        );
+        assert!(describe_changes(&old, &old).is_empty());
+
+        // Rules left out of a config file keep their defaults
+        let mut partial = Config::default();
+        partial.rules.retain(|rule_id, _| rule_id == "line-length");
+        partial
+            .rules
+            .get_mut("line-length")
+            .unwrap()
+            .params
+            .retain(|param, _| param == "max");
+        assert!(describe_changes(&old, &partial).is_empty());
+    }
+}
@@ -0,0 +1,151 @@
+//! Semantic comparison of YAML content before and after fixing
+
+use eyre::{Context, Result};
+use serde::Deserialize;
+use serde_yaml::Value;
+
+/// Parse every document of a YAML stream
+fn parse_documents(content: &str) -> Result<Vec<Value>> {
+    serde_yaml::Deserializer::from_str(content)
+        .map(Value::deserialize)
+        .collect::<Result<_, _>>()
+        .context("content does not parse as YAML")
+}
+
+/// Differences in the parsed values of two YAML streams, one line per difference
+/// (`spec.replicas: 1 -> 2`). Empty if the streams are semantically equivalent.
+///
+/// Fails if either stream does not parse.
+pub fn semantic_differences(before: &str, after: &str) -> Result<Vec<String>> {
+    let before = parse_documents(before).context("before")?;
+    let after = parse_documents(after).context("after")?;
+
+    let mut differences = Vec::new();
+    if before.len() != after.len() {
+        differences.push(format!(
+            "document count: {} -> {}",
+            before.len(),
+            after.len()
+        ));
+    }
+    for (index, (before, after)) in before.iter().zip(&after).enumerate() {
+        // Documents after the first are prefixed with their index
+        let root = if index > 0 {
+            format!("[document {index}]")
+        } else {
+            String::new()
+        };
+        diff_values(&root, before, after, &mut differences);
+    }
+
+    Ok(differences)
+}
+
+/// Check that two YAML streams parse to the same values
+pub fn is_semantically_equivalent(before: &str, after: &str) -> bool {
+    semantic_differences(before, after).is_ok_and(|differences| differences.is_empty())
+}
+
+fn diff_values(path: &str, before: &Value, after: &Value, differences: &mut Vec<String>) {
+    let child = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{path}.{key}")
+        }
+    };
+
+    match (before, after) {
+        (Value::Mapping(before), Value::Mapping(after)) => {
+            for (key, value) in before {
+                let name = key_name(key);
+                match after.get(key) {
+                    Some(other) => diff_values(&child(&name), value, other, differences),
+                    None => differences.push(format!("{}: removed", child(&name))),
+                }
+            }
+            for key in after.keys().filter(|key| !before.contains_key(*key)) {
+                differences.push(format!("{}: added", child(&key_name(key))));
+            }
+        }
+        (Value::Sequence(before), Value::Sequence(after)) => {
+            for (index, (value, other)) in before.iter().zip(after).enumerate() {
+                diff_values(&child(&index.to_string()), value, other, differences);
+            }
+            if before.len() != after.len() {
+                differences.push(format!(
+                    "{}: length {} -> {}",
+                    if path.is_empty() { "(root)" } else { path },
+                    before.len(),
+                    after.len()
+                ));
+            }
+        }
+        (Value::Tagged(before), Value::Tagged(after)) if before.tag == after.tag => {
+            diff_values(path, &before.value, &after.value, differences);
+        }
+        _ if before == after => {}
+        _ => differences.push(format!(
+            "{}: {} -> {}",
+            if path.is_empty() { "(root)" } else { path },
+            display(before),
+            display(after)
+        )),
+    }
+}
+
+/// Render a mapping key as a path segment
+fn key_name(key: &Value) -> String {
+    match key {
+        Value::String(s) => s.clone(),
+        _ => display(key),
+    }
+}
+
+/// Render a value on one line
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(s) => format!("{s:?}"),
+        _ => serde_yaml::to_string(value)
+            .map(|yaml| yaml.trim_end().replace('\n', " "))
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_whitespace_changes_are_equivalent() {
+        let before = "key: value   \n\n\n\nlist:\n  - a\n";
+        let after = "key: value\n\nlist:\n- a\n";
+        assert!(semantic_differences(before, after).unwrap().is_empty());
+        assert!(is_semantically_equivalent(before, after));
+    }
+
+    #[test]
+    fn test_semantic_differences() {
+        let before = "spec:\n  replicas: 1\n  name: app\nlist: [a, b]\n";
+        let after = "spec:\n  replicas: 2\n  image: app\nlist: [b, a]\n";
+        assert_eq!(
+            semantic_differences(before, after).unwrap(),
+            vec![
+                "spec.replicas: 1 -> 2",
+                "spec.name: removed",
+                "spec.image: added",
+                "list.0: \"a\" -> \"b\"",
+                "list.1: \"b\" -> \"a\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_semantic_differences_documents() {
+        let differences =
+            semantic_differences("a: 1\n---\nb: 1\n", "a: 1\n---\nb: |\n  1\n").unwrap();
+        assert_eq!(differences, vec!["[document 1].b: 1 -> \"1\\n\""]);
+
+        assert!(semantic_differences("a: 1\n", "a: [1\n").is_err());
+    }
+}
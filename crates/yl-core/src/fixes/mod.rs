@@ -0,0 +1,1027 @@
+pub mod equivalence;
+pub mod plan;
+
+use crate::config::Config;
+use crate::linter::{Chomping, LintContext, Problem};
+use crate::rules::common;
+use crate::rules::semantic::{EmptyCollectionsRule, QuotedKeysRule, SequenceOrderingRule};
+use crate::rules::style::IndentationRule;
+use crate::rules::{RuleConfig, RuleRegistry};
+use eyre::Result;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+pub use equivalence::{is_semantically_equivalent, semantic_differences};
+pub use plan::{FixEdit, FixPlan, LineMap};
+
+/// Trait for implementing automatic fixes for linting problems
+pub trait AutoFix: Send + Sync {
+    /// Check if this fix can handle the given problem
+    fn can_fix(&self, problem: &Problem) -> bool;
+
+    /// Apply the fix to the content and return the fixed content, using the
+    /// configuration of the rule that reported the problem
+    fn apply_fix(&self, content: &str, problem: &Problem, config: &RuleConfig) -> Result<String>;
+}
+
+/// Engine for applying automatic fixes to YAML content
+pub struct FixEngine {
+    fixes: HashMap<String, Box<dyn AutoFix>>,
+    registry: RuleRegistry,
+}
+
+impl FixEngine {
+    /// Create a new fix engine with default fixes
+    pub fn new() -> Self {
+        let mut engine = Self {
+            fixes: HashMap::new(),
+            registry: RuleRegistry::with_default_rules(),
+        };
+
+        // Register default fixes
+        engine.register_fix("trailing-spaces", Box::new(TrailingSpacesFix));
+        engine.register_fix("new-line-at-end-of-file", Box::new(NewLineAtEndOfFileFix));
+        engine.register_fix("empty-lines", Box::new(EmptyLinesFix));
+        engine.register_fix("no-tabs", Box::new(NoTabsFix));
+        engine.register_fix("sequence-ordering", Box::new(SequenceOrderingFix));
+        engine.register_fix("dangling-commas", Box::new(DanglingCommasFix));
+        engine.register_fix("quoted-keys", Box::new(QuotedKeysFix));
+        engine.register_fix("empty-collections", Box::new(EmptyCollectionsFix));
+        engine.register_fix("indentation", Box::new(SequenceIndentationFix));
+
+        engine
+    }
+
+    /// Register a fix for a specific rule
+    pub fn register_fix(&mut self, rule_id: &str, fix: Box<dyn AutoFix>) {
+        self.fixes.insert(rule_id.to_string(), fix);
+    }
+
+    /// Check whether problems reported by a rule can be fixed automatically
+    pub fn has_fix(&self, rule_id: &str) -> bool {
+        self.fixes.contains_key(rule_id)
+    }
+
+    /// Apply fixes to content for the given problems
+    pub fn fix_problems(
+        &self,
+        content: &str,
+        problems: &[Problem],
+        config: &Config,
+    ) -> Result<String> {
+        let mut fixed_content = content.to_string();
+
+        // Group problems by rule and sort by line number (reverse order to maintain positions)
+        let mut rule_problems: HashMap<String, Vec<&Problem>> = HashMap::new();
+        for problem in problems {
+            rule_problems
+                .entry(problem.rule.clone())
+                .or_default()
+                .push(problem);
+        }
+
+        // Apply fixes for each rule in a consistent order
+        let mut rule_ids: Vec<_> = rule_problems.keys().collect();
+        rule_ids.sort(); // Ensure consistent ordering
+
+        for rule_id in rule_ids {
+            if let Some(fix) = self.fixes.get(rule_id) {
+                let rule_config = config.get_rule_config(rule_id, &self.registry);
+                let rule_problems = rule_problems.get(rule_id).unwrap();
+                // Sort problems in reverse position order to maintain positions when fixing
+                let mut sorted_problems = rule_problems.clone();
+                sorted_problems
+                    .sort_by_key(|problem| std::cmp::Reverse((problem.line, problem.column)));
+
+                for problem in sorted_problems {
+                    if fix.can_fix(problem) {
+                        fixed_content = fix.apply_fix(&fixed_content, problem, &rule_config)?;
+                    }
+                }
+            }
+        }
+
+        Ok(fixed_content)
+    }
+
+    /// Plan the fixes of the given problems as edits of `content`, each computed by
+    /// fixing its problem alone. Unlike [`FixEngine::fix_problems`], some of the edits
+    /// can be applied and the positions of the remaining problems updated from the
+    /// returned [`LineMap`] without linting again.
+    pub fn plan(&self, content: &str, problems: &[Problem], config: &Config) -> Result<FixPlan> {
+        let mut candidates = Vec::new();
+        for problem in problems {
+            let Some(fix) = self.fixes.get(&problem.rule) else {
+                continue;
+            };
+            if !fix.can_fix(problem) {
+                continue;
+            }
+            let rule_config = config.get_rule_config(&problem.rule, &self.registry);
+            let fixed = fix.apply_fix(content, problem, &rule_config)?;
+            if let Some(edit) = FixEdit::between(content, &fixed, problem) {
+                candidates.push((edit, problem.clone()));
+            }
+        }
+
+        Ok(FixPlan::new(content, candidates))
+    }
+}
+
+impl Default for FixEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Check whether whitespace on any of the given lines (1-based) belongs to the value of a
+/// block scalar, so that removing it would change what the YAML means
+fn in_block_scalar(content: &str, lines: RangeInclusive<usize>) -> bool {
+    LintContext::new(Path::new(""), content)
+        .block_scalars()
+        .iter()
+        .any(|scalar| scalar.lines.start() <= lines.end() && lines.start() <= scalar.lines.end())
+}
+
+/// Fix for trailing spaces
+pub struct TrailingSpacesFix;
+
+impl AutoFix for TrailingSpacesFix {
+    fn can_fix(&self, problem: &Problem) -> bool {
+        problem.rule == "trailing-spaces"
+    }
+
+    fn apply_fix(&self, content: &str, problem: &Problem, _config: &RuleConfig) -> Result<String> {
+        if in_block_scalar(content, problem.line..=problem.line) {
+            return Ok(content.to_string());
+        }
+
+        let lines: Vec<&str> = content.lines().collect();
+        let mut fixed_lines = Vec::new();
+
+        for (i, line) in lines.iter().enumerate() {
+            let line_number = i + 1;
+            if line_number == problem.line {
+                // Remove trailing whitespace from this line
+                fixed_lines.push(line.trim_end());
+            } else {
+                fixed_lines.push(*line);
+            }
+        }
+
+        let mut result = fixed_lines.join("\n");
+
+        // Preserve the original ending - if the original content ended with a newline, keep it
+        if content.ends_with('\n') {
+            result.push('\n');
+        }
+
+        Ok(result)
+    }
+}
+
+/// Fix for missing newline at end of file
+pub struct NewLineAtEndOfFileFix;
+
+impl AutoFix for NewLineAtEndOfFileFix {
+    fn can_fix(&self, problem: &Problem) -> bool {
+        problem.rule == "new-line-at-end-of-file"
+    }
+
+    fn apply_fix(&self, content: &str, _problem: &Problem, _config: &RuleConfig) -> Result<String> {
+        if content.is_empty() {
+            return Ok(content.to_string());
+        }
+
+        // The final line break of a block scalar is part of its value unless stripped
+        let last_line = content.lines().count();
+        let ends_in_block_scalar = LintContext::new(Path::new(""), content)
+            .block_scalars()
+            .iter()
+            .any(|scalar| scalar.chomping != Chomping::Strip && scalar.lines.contains(&last_line));
+        if ends_in_block_scalar {
+            return Ok(content.to_string());
+        }
+
+        if content.ends_with('\n') {
+            Ok(content.to_string())
+        } else {
+            Ok(format!("{content}\n"))
+        }
+    }
+}
+
+/// Fix for tab characters
+pub struct NoTabsFix;
+
+impl AutoFix for NoTabsFix {
+    fn can_fix(&self, problem: &Problem) -> bool {
+        problem.rule == "no-tabs"
+    }
+
+    fn apply_fix(&self, content: &str, problem: &Problem, config: &RuleConfig) -> Result<String> {
+        if in_block_scalar(content, problem.line..=problem.line) {
+            return Ok(content.to_string());
+        }
+
+        let width = config.get_int("indent-width").unwrap_or(2).max(1) as usize;
+        let allow_in_comments = config.get_bool("allow-in-comments").unwrap_or(false);
+        let spaces = " ".repeat(width);
+        let comment_start = LintContext::new(Path::new(""), content)
+            .tokens()
+            .comment_start(problem.line);
+
+        let mut fixed_lines = Vec::new();
+        for (i, line) in content.lines().enumerate() {
+            if i + 1 != problem.line {
+                fixed_lines.push(line.to_string());
+                continue;
+            }
+
+            // Tabs inside values are part of them; only whitespace around them and
+            // comments are rewritten
+            let trailing = common::trailing_whitespace_start(line).unwrap_or(line.len());
+            let indent_end = (line.len() - line.trim_start().len()).min(trailing);
+            let comment = comment_start
+                .unwrap_or(trailing)
+                .clamp(indent_end, trailing);
+
+            let mut fixed = line[..indent_end].replace('\t', &spaces);
+            fixed.push_str(&line[indent_end..comment]);
+            if allow_in_comments {
+                fixed.push_str(&line[comment..trailing]);
+            } else {
+                fixed.push_str(&line[comment..trailing].replace('\t', &spaces));
+            }
+            if !line[trailing..].contains('\t') {
+                fixed.push_str(&line[trailing..]);
+            }
+            fixed_lines.push(fixed);
+        }
+
+        let mut result = fixed_lines.join("\n");
+        if content.ends_with('\n') {
+            result.push('\n');
+        }
+
+        Ok(result)
+    }
+}
+
+/// Fix for empty lines issues
+pub struct EmptyLinesFix;
+
+impl AutoFix for EmptyLinesFix {
+    fn can_fix(&self, problem: &Problem) -> bool {
+        problem.rule == "empty-lines"
+            && (problem.has_message("empty-lines.too-many")
+                || problem.has_message("empty-lines.too-many-at-start")
+                || problem.has_message("empty-lines.too-many-at-end"))
+    }
+
+    fn apply_fix(&self, content: &str, problem: &Problem, config: &RuleConfig) -> Result<String> {
+        let lines: Vec<&str> = content.lines().collect();
+        let is_blank = |index: usize| lines[index].trim().is_empty();
+
+        // Blank run to collapse and the number of blank lines allowed to remain
+        let (run, keep) = if problem.has_message("empty-lines.too-many-at-start") {
+            let end = (0..lines.len())
+                .find(|&i| !is_blank(i))
+                .unwrap_or(lines.len());
+            (0..end, config.get_int("max-start").unwrap_or(0))
+        } else if problem.has_message("empty-lines.too-many-at-end") {
+            let start = (0..lines.len())
+                .rev()
+                .find(|&i| !is_blank(i))
+                .map_or(0, |i| i + 1);
+            (start..lines.len(), config.get_int("max-end").unwrap_or(1))
+        } else if problem.has_message("empty-lines.too-many") {
+            // The problem points at a line within the run of blank lines
+            let Some(index) = problem
+                .line
+                .checked_sub(1)
+                .filter(|&i| i < lines.len() && is_blank(i))
+            else {
+                return Ok(content.to_string());
+            };
+            let start = (0..index)
+                .rev()
+                .find(|&i| !is_blank(i))
+                .map_or(0, |i| i + 1);
+            let end = (index..lines.len())
+                .find(|&i| !is_blank(i))
+                .unwrap_or(lines.len());
+            (start..end, config.get_int("max").unwrap_or(2))
+        } else {
+            return Ok(content.to_string());
+        };
+
+        let keep = keep.max(0) as usize;
+        if run.len() <= keep || in_block_scalar(content, run.start + 1..=run.end) {
+            return Ok(content.to_string());
+        }
+
+        let mut fixed_lines = lines[..run.start].to_vec();
+        fixed_lines.extend(&lines[run.start..run.start + keep]);
+        fixed_lines.extend(&lines[run.end..]);
+
+        let mut result = fixed_lines.join("\n");
+        if content.ends_with('\n') && !result.is_empty() {
+            result.push('\n');
+        }
+
+        Ok(result)
+    }
+}
+
+/// Fix for unsorted sequences: sorts the items of the reported sequence.
+///
+/// Only sequences of single-line scalar items are sorted; comment and blank lines
+/// directly above an item move with it.
+pub struct SequenceOrderingFix;
+
+impl AutoFix for SequenceOrderingFix {
+    fn can_fix(&self, problem: &Problem) -> bool {
+        problem.rule == "sequence-ordering"
+    }
+
+    fn apply_fix(&self, content: &str, problem: &Problem, config: &RuleConfig) -> Result<String> {
+        let context = LintContext::new(Path::new(""), content);
+        let items = context.sequence_items();
+        let Some(position) = items.iter().position(|item| item.line == problem.line) else {
+            return Ok(content.to_string());
+        };
+        let sequence_path = &items[position].path;
+
+        // The reported sequence runs from its first item up to the next sequence at its path
+        let Some(first) = items[..=position]
+            .iter()
+            .rposition(|item| item.path == *sequence_path && item.index == 0)
+        else {
+            return Ok(content.to_string());
+        };
+        let sequence: Vec<_> = items[first..]
+            .iter()
+            .filter(|item| item.path == *sequence_path)
+            .enumerate()
+            .take_while(|(i, item)| *i == 0 || item.index != 0)
+            .map(|(_, item)| item)
+            .collect();
+
+        let lines: Vec<&str> = content.lines().collect();
+        let is_item_line = |line: &str, indent: usize| {
+            common::count_leading_whitespace(line) == indent && line.trim_start().starts_with('-')
+        };
+        let is_filler = |line: &str| line.trim().is_empty() || common::is_comment_only_line(line);
+
+        let indent = common::count_leading_whitespace(lines[sequence[0].line - 1]);
+        // Every item must be a plain `- value` line, preceded only by comments or blank lines
+        for (i, item) in sequence.iter().enumerate() {
+            let line = lines[item.line - 1];
+            if item.index != i
+                || !is_item_line(line, indent)
+                || !line.trim_start().starts_with("- ")
+            {
+                return Ok(content.to_string());
+            }
+            if i > 0
+                && !lines[sequence[i - 1].line..item.line - 1]
+                    .iter()
+                    .all(|l| is_filler(l))
+            {
+                return Ok(content.to_string());
+            }
+        }
+
+        // Nothing else may belong to the sequence: no continuation lines and no further items
+        let last = sequence[sequence.len() - 1].line;
+        if let Some(next) = lines[last..].iter().find(|line| !is_filler(line))
+            && (common::count_leading_whitespace(next) > indent || is_item_line(next, indent))
+        {
+            return Ok(content.to_string());
+        }
+
+        let start = sequence[0].line - 1;
+        let mut blocks: Vec<(&str, &[&str])> = sequence
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                let from = if i == 0 { start } else { sequence[i - 1].line };
+                (item.value.as_str(), &lines[from..item.line])
+            })
+            .collect();
+
+        let numeric = config.get_string("order") == Some("numeric");
+        blocks.sort_by(|(a, _), (b, _)| SequenceOrderingRule::compare_items(numeric, a, b));
+
+        let mut fixed_lines = lines[..start].to_vec();
+        fixed_lines.extend(blocks.iter().flat_map(|(_, block)| block.iter()));
+        fixed_lines.extend(&lines[last..]);
+
+        let mut result = fixed_lines.join("\n");
+        if content.ends_with('\n') {
+            result.push('\n');
+        }
+
+        Ok(result)
+    }
+}
+
+/// Fix for dangling commas: removes the comma with the spaces that would be left over
+pub struct DanglingCommasFix;
+
+impl AutoFix for DanglingCommasFix {
+    fn can_fix(&self, problem: &Problem) -> bool {
+        problem.rule == "dangling-commas"
+    }
+
+    fn apply_fix(&self, content: &str, problem: &Problem, _config: &RuleConfig) -> Result<String> {
+        let mut fixed_lines = Vec::new();
+        for (i, line) in content.lines().enumerate() {
+            if i + 1 != problem.line {
+                fixed_lines.push(line.to_string());
+                continue;
+            }
+
+            let Some((comma, ',')) = line.char_indices().nth(problem.column.saturating_sub(1))
+            else {
+                return Ok(content.to_string());
+            };
+            let before = line[..comma].trim_end_matches([' ', '\t']);
+            let after = line[comma + 1..].trim_start_matches([' ', '\t']);
+
+            // Keep the spaces that separate the comma's neighbours from each other
+            let fixed = if after.starts_with([']', '}']) || before.ends_with(['[', '{']) {
+                format!("{}{}", &line[..comma], after)
+            } else if before.ends_with(',') {
+                format!("{}{}", before, &line[comma + 1..])
+            } else {
+                format!("{}{}", &line[..comma], &line[comma + 1..])
+            };
+            fixed_lines.push(fixed);
+        }
+
+        let mut result = fixed_lines.join("\n");
+        if content.ends_with('\n') {
+            result.push('\n');
+        }
+
+        Ok(result)
+    }
+}
+
+/// Fix for quoted keys: removes quotes that the key does not need
+pub struct QuotedKeysFix;
+
+impl AutoFix for QuotedKeysFix {
+    fn can_fix(&self, problem: &Problem) -> bool {
+        problem.rule == "quoted-keys" && problem.has_message("quoted-keys.not-needed")
+    }
+
+    fn apply_fix(&self, content: &str, problem: &Problem, _config: &RuleConfig) -> Result<String> {
+        let mut fixed_lines = Vec::new();
+        for (i, line) in content.lines().enumerate() {
+            if i + 1 != problem.line {
+                fixed_lines.push(line.to_string());
+                continue;
+            }
+
+            let Some((start, _)) = line.char_indices().nth(problem.column.saturating_sub(1)) else {
+                return Ok(content.to_string());
+            };
+            let token = QuotedKeysRule::key_token(line, start);
+            // Under `required: never` keys that need their quotes are reported too
+            let Some(key) = QuotedKeysRule::quoted_content(token)
+                .filter(|_| !QuotedKeysRule::needs_quotes(token))
+            else {
+                return Ok(content.to_string());
+            };
+            fixed_lines.push(format!(
+                "{}{}{}",
+                &line[..start],
+                key,
+                &line[start + token.len()..]
+            ));
+        }
+
+        let mut result = fixed_lines.join("\n");
+        if content.ends_with('\n') {
+            result.push('\n');
+        }
+
+        Ok(result)
+    }
+}
+
+/// Fix for empty-collections: rewrites an empty value to the form the rule requires
+pub struct EmptyCollectionsFix;
+
+impl AutoFix for EmptyCollectionsFix {
+    fn can_fix(&self, problem: &Problem) -> bool {
+        problem.rule == "empty-collections"
+            && !problem.has_message("empty-collections.empty-is-ambiguous")
+    }
+
+    fn apply_fix(&self, content: &str, problem: &Problem, _config: &RuleConfig) -> Result<String> {
+        let mut fixed_lines = Vec::new();
+        for (i, line) in content.lines().enumerate() {
+            if i + 1 != problem.line {
+                fixed_lines.push(line.to_string());
+                continue;
+            }
+
+            // The value ends the line, before any comment
+            let code = common::strip_comment(line).trim_end();
+            let comment = &line[code.len()..];
+            let fixed = if problem.has_message("empty-collections.empty-as-mapping") {
+                Some(format!("{code} {{}}"))
+            } else if problem.has_message("empty-collections.empty-as-sequence") {
+                Some(format!("{code} []"))
+            } else {
+                EmptyCollectionsRule::trailing_empty_collection(code)
+                    .map(|start| &code[..start])
+                    .map(|rest| {
+                        if problem.has_message("empty-collections.mapping-as-null")
+                            || problem.has_message("empty-collections.sequence-as-null")
+                        {
+                            format!("{rest}null")
+                        } else {
+                            rest.trim_end().to_string()
+                        }
+                    })
+            };
+            let Some(fixed) = fixed else {
+                return Ok(content.to_string());
+            };
+            fixed_lines.push(format!("{fixed}{comment}"));
+        }
+
+        let mut result = fixed_lines.join("\n");
+        if content.ends_with('\n') {
+            result.push('\n');
+        }
+
+        Ok(result)
+    }
+}
+
+/// Fix for the sequence style of indentation: moves a sequence under a mapping key, with
+/// everything nested in it, to be indented by `spaces` or aligned with its key
+pub struct SequenceIndentationFix;
+
+impl AutoFix for SequenceIndentationFix {
+    fn can_fix(&self, problem: &Problem) -> bool {
+        problem.rule == "indentation"
+            && (problem.has_message("indentation.sequence-indented")
+                || problem.has_message("indentation.sequence-aligned"))
+    }
+
+    fn apply_fix(&self, content: &str, problem: &Problem, config: &RuleConfig) -> Result<String> {
+        let context = LintContext::new(Path::new(""), content);
+        let Some(sequence) = IndentationRule::nested_sequences(&context)
+            .into_iter()
+            .find(|sequence| sequence.line == problem.line)
+        else {
+            return Ok(content.to_string());
+        };
+
+        let target = if problem.has_message("indentation.sequence-indented") {
+            sequence.key_indent + config.get_int("spaces").unwrap_or(2).max(1) as usize
+        } else {
+            sequence.key_indent
+        };
+        let (add, remove) = (
+            target.saturating_sub(sequence.dash_indent),
+            sequence.dash_indent.saturating_sub(target),
+        );
+
+        let mut fixed_lines = Vec::new();
+        for (i, line) in content.lines().enumerate() {
+            let line_number = i + 1;
+            // Empty lines and comments left of the items stay where they are
+            let moves = (sequence.line..=sequence.end).contains(&line_number)
+                && !line.is_empty()
+                && common::count_leading_whitespace(line) >= sequence.dash_indent
+                && line.starts_with(&" ".repeat(remove));
+            if moves {
+                fixed_lines.push(format!("{}{}", " ".repeat(add), &line[remove..]));
+            } else {
+                fixed_lines.push(line.to_string());
+            }
+        }
+
+        let mut result = fixed_lines.join("\n");
+        if content.ends_with('\n') {
+            result.push('\n');
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::Level;
+    use crate::messages::Message;
+    use crate::rules::Rule;
+    use crate::rules::style::EmptyLinesRule;
+
+    fn empty_lines_config() -> RuleConfig {
+        EmptyLinesRule::new().default_config()
+    }
+
+    fn empty_lines_problem(line: usize, id: &'static str, count: usize, max: usize) -> Problem {
+        let message = Message::new(id).arg("count", count).arg("max", max);
+        Problem::from_message(line, 1, Level::Error, "empty-lines", message)
+    }
+
+    #[test]
+    fn test_fix_engine_creation() {
+        let engine = FixEngine::new();
+        assert!(!engine.fixes.is_empty());
+    }
+
+    #[test]
+    fn test_has_fix() {
+        let engine = FixEngine::new();
+        assert!(engine.has_fix("trailing-spaces"));
+        assert!(!engine.has_fix("key-duplicates"));
+    }
+
+    #[test]
+    fn test_trailing_spaces_fix() {
+        let fix = TrailingSpacesFix;
+        let problem = Problem::new(
+            2,
+            10,
+            Level::Error,
+            "trailing-spaces",
+            "trailing whitespace",
+        );
+        let content = "line1\nline2   \nline3";
+
+        assert!(fix.can_fix(&problem));
+
+        let fixed = fix
+            .apply_fix(content, &problem, &RuleConfig::default())
+            .unwrap();
+        assert_eq!(fixed, "line1\nline2\nline3");
+    }
+
+    #[test]
+    fn test_no_tabs_fix() {
+        let config = crate::rules::style::NoTabsRule::new().default_config();
+        let fix_line = |content: &str, line: usize, config: &RuleConfig| {
+            let problem = Problem::new(line, 1, Level::Warning, "no-tabs", "found tab character");
+            NoTabsFix.apply_fix(content, &problem, config).unwrap()
+        };
+
+        assert_eq!(fix_line("a:\n\t\tb: 1\n", 2, &config), "a:\n    b: 1\n");
+        assert_eq!(fix_line("a: 1\t \n", 1, &config), "a: 1\n");
+        assert_eq!(fix_line("\t\n", 1, &config), "\n");
+        assert_eq!(fix_line("a: 1 #\tx\n", 1, &config), "a: 1 #  x\n");
+        // Tabs inside values are part of them
+        assert_eq!(fix_line("a: \"x\ty\"\n", 1, &config), "a: \"x\ty\"\n");
+        assert_eq!(fix_line("a: |\n  x\ty\n", 2, &config), "a: |\n  x\ty\n");
+
+        let mut config = config;
+        config.set_param("indent-width", 4i64);
+        config.set_param("allow-in-comments", true);
+        assert_eq!(fix_line("\tb: 1 #\tx\n", 1, &config), "    b: 1 #\tx\n");
+    }
+
+    #[test]
+    fn test_quoted_keys_fix() {
+        let fix_at = |content: &str, line: usize, column: usize| {
+            let problem = Problem::from_message(
+                line,
+                column,
+                Level::Error,
+                "quoted-keys",
+                Message::new("quoted-keys.not-needed").arg("key", "\"name\""),
+            );
+            QuotedKeysFix
+                .apply_fix(content, &problem, &RuleConfig::default())
+                .unwrap()
+        };
+
+        assert_eq!(fix_at("\"name\": app\n", 1, 1), "name: app\n");
+        assert_eq!(
+            fix_at("a:\n  - 'port': 80 # http\n", 2, 5),
+            "a:\n  - port: 80 # http\n"
+        );
+        // Keys that need their quotes keep them
+        assert_eq!(fix_at("\"on\": push\n", 1, 1), "\"on\": push\n");
+        assert_eq!(fix_at("\"a: b\": 1\n", 1, 1), "\"a: b\": 1\n");
+    }
+
+    #[test]
+    fn test_empty_collections_fix() {
+        let fix_at = |content: &str, line: usize, id: &'static str| {
+            let problem =
+                Problem::from_message(line, 1, Level::Error, "empty-collections", Message::new(id));
+            EmptyCollectionsFix
+                .apply_fix(content, &problem, &RuleConfig::default())
+                .unwrap()
+        };
+
+        assert_eq!(
+            fix_at(
+                "a: {} # none\nb: 1\n",
+                1,
+                "empty-collections.mapping-as-empty"
+            ),
+            "a: # none\nb: 1\n"
+        );
+        assert_eq!(
+            fix_at("a:\n  - []\n", 2, "empty-collections.sequence-as-null"),
+            "a:\n  - null\n"
+        );
+        assert_eq!(
+            fix_at("a: {  }\n", 1, "empty-collections.mapping-as-null"),
+            "a: null\n"
+        );
+        assert_eq!(
+            fix_at("- [ ] # none\n", 1, "empty-collections.sequence-as-empty"),
+            "- # none\n"
+        );
+        assert_eq!(
+            fix_at("a:\n", 1, "empty-collections.empty-as-mapping"),
+            "a: {}\n"
+        );
+        assert_eq!(
+            fix_at("a:\n  -\n", 2, "empty-collections.empty-as-sequence"),
+            "a:\n  - []\n"
+        );
+        let ambiguous = Problem::from_message(
+            1,
+            3,
+            Level::Error,
+            "empty-collections",
+            Message::new("empty-collections.empty-is-ambiguous"),
+        );
+        assert!(!EmptyCollectionsFix.can_fix(&ambiguous));
+    }
+
+    #[test]
+    fn test_sequence_indentation_fix() {
+        let fix_at = |content: &str, line: usize, id: &'static str| {
+            let problem =
+                Problem::from_message(line, 1, Level::Error, "indentation", Message::new(id));
+            let mut config = RuleConfig::default();
+            config.set_param("spaces", 4i64);
+            SequenceIndentationFix
+                .apply_fix(content, &problem, &config)
+                .unwrap()
+        };
+        let indent = "indentation.sequence-indented";
+        let align = "indentation.sequence-aligned";
+
+        let aligned = "a:\n- x: 1\n  y: |\n    text\n\n# note\n- z\nb: 2\n";
+        let indented = "a:\n    - x: 1\n      y: |\n        text\n\n    # note\n    - z\nb: 2\n";
+        assert_eq!(fix_at(aligned, 2, indent), indented);
+        assert_eq!(fix_at(indented, 2, align), aligned);
+        // Lines that do not start a sequence under a key are left alone
+        assert_eq!(fix_at(aligned, 7, indent), aligned);
+        assert!(!SequenceIndentationFix.can_fix(&Problem::new(
+            1,
+            1,
+            Level::Error,
+            "indentation",
+            "wrong indentation: expected multiple of 2, got 3",
+        )));
+    }
+
+    #[test]
+    fn test_dangling_commas_fix() {
+        let fix_at = |content: &str, line: usize, column: usize| {
+            let problem = Problem::new(line, column, Level::Error, "dangling-commas", "comma");
+            DanglingCommasFix
+                .apply_fix(content, &problem, &RuleConfig::default())
+                .unwrap()
+        };
+
+        assert_eq!(fix_at("a: [1, 2,]\n", 1, 9), "a: [1, 2]\n");
+        assert_eq!(fix_at("a: [1, 2, ]\n", 1, 9), "a: [1, 2]\n");
+        assert_eq!(fix_at("a: [1,, 2]\n", 1, 7), "a: [1, 2]\n");
+        assert_eq!(fix_at("a: [1, , 2]\n", 1, 8), "a: [1, 2]\n");
+        assert_eq!(fix_at("a: [, 1]\n", 1, 5), "a: [1]\n");
+        assert_eq!(
+            fix_at("a: [\n  3, # last\n]\n", 2, 4),
+            "a: [\n  3 # last\n]\n"
+        );
+        // Nothing is removed where the problem does not point at a comma
+        assert_eq!(fix_at("a: [1, 2]\n", 1, 5), "a: [1, 2]\n");
+
+        let engine = FixEngine::new();
+        let problems = vec![
+            Problem::new(1, 7, Level::Error, "dangling-commas", "empty entry"),
+            Problem::new(1, 10, Level::Error, "dangling-commas", "trailing comma"),
+        ];
+        let fixed = engine
+            .fix_problems("a: [1,, 2,]\n", &problems, &Config::default())
+            .unwrap();
+        assert_eq!(fixed, "a: [1, 2]\n");
+    }
+
+    #[test]
+    fn test_whitespace_fixes_keep_block_scalars() {
+        let trailing = Problem::new(2, 6, Level::Error, "trailing-spaces", "trailing whitespace");
+        let content = "text: |\n  one  \n";
+        let fixed = TrailingSpacesFix
+            .apply_fix(content, &trailing, &RuleConfig::default())
+            .unwrap();
+        assert_eq!(fixed, content);
+
+        let newline = Problem::new(2, 6, Level::Error, "new-line-at-end-of-file", "missing");
+        let content = "text: |\n  one";
+        let fixed = NewLineAtEndOfFileFix
+            .apply_fix(content, &newline, &RuleConfig::default())
+            .unwrap();
+        assert_eq!(fixed, content);
+        let content = "text: |-\n  one";
+        let fixed = NewLineAtEndOfFileFix
+            .apply_fix(content, &newline, &RuleConfig::default())
+            .unwrap();
+        assert_eq!(fixed, "text: |-\n  one\n");
+
+        let blank = empty_lines_problem(4, "empty-lines.too-many", 4, 2);
+        let content = "text: |\n  one\n\n\n\n\n  two\n";
+        let fixed = EmptyLinesFix
+            .apply_fix(content, &blank, &empty_lines_config())
+            .unwrap();
+        assert_eq!(fixed, content);
+    }
+
+    #[test]
+    fn test_newline_at_end_fix() {
+        let fix = NewLineAtEndOfFileFix;
+        let problem = Problem::new(
+            1,
+            5,
+            Level::Error,
+            "new-line-at-end-of-file",
+            "missing newline",
+        );
+        let content = "line1\nline2";
+
+        assert!(fix.can_fix(&problem));
+
+        let fixed = fix
+            .apply_fix(content, &problem, &RuleConfig::default())
+            .unwrap();
+        assert_eq!(fixed, "line1\nline2\n");
+    }
+
+    #[test]
+    fn test_empty_lines_fix_consecutive() {
+        let fix = EmptyLinesFix;
+        let problem = empty_lines_problem(3, "empty-lines.too-many", 3, 2);
+        let content = "line1\n\n\n\nline2";
+
+        assert!(fix.can_fix(&problem));
+
+        let fixed = fix
+            .apply_fix(content, &problem, &empty_lines_config())
+            .unwrap();
+        assert_eq!(fixed, "line1\n\n\nline2");
+    }
+
+    #[test]
+    fn test_empty_lines_fix_only_reported_run() {
+        let fix = EmptyLinesFix;
+        let problem = empty_lines_problem(7, "empty-lines.too-many", 2, 1);
+        let content = "a\n\n\nb\nc\n\n\nd\n";
+        let mut config = empty_lines_config();
+        config.set_param("max", 1i64);
+
+        // Only the run containing line 7 is collapsed, using the configured max
+        let fixed = fix.apply_fix(content, &problem, &config).unwrap();
+        assert_eq!(fixed, "a\n\n\nb\nc\n\nd\n");
+    }
+
+    #[test]
+    fn test_empty_lines_fix_at_end_keeps_max_end() {
+        let fix = EmptyLinesFix;
+        let problem = empty_lines_problem(4, "empty-lines.too-many-at-end", 3, 1);
+        let content = "a\n\n\n\n";
+
+        let fixed = fix
+            .apply_fix(content, &problem, &empty_lines_config())
+            .unwrap();
+        assert_eq!(fixed, "a\n\n");
+    }
+
+    #[test]
+    fn test_empty_lines_fix_at_beginning() {
+        let fix = EmptyLinesFix;
+        let problem = empty_lines_problem(1, "empty-lines.too-many-at-start", 2, 0);
+        let content = "\n\nline1\nline2";
+
+        assert!(fix.can_fix(&problem));
+
+        let fixed = fix
+            .apply_fix(content, &problem, &empty_lines_config())
+            .unwrap();
+        assert_eq!(fixed, "line1\nline2");
+    }
+
+    #[test]
+    fn test_fix_engine_apply_multiple() {
+        let engine = FixEngine::new();
+        let problems = vec![
+            Problem::new(1, 8, Level::Error, "trailing-spaces", "trailing whitespace"),
+            Problem::new(
+                3,
+                1,
+                Level::Error,
+                "new-line-at-end-of-file",
+                "missing newline",
+            ),
+        ];
+        let content = "line1   \nline2\nline3";
+
+        let fixed = engine
+            .fix_problems(content, &problems, &Config::default())
+            .unwrap();
+        assert_eq!(fixed, "line1\nline2\nline3\n");
+    }
+
+    #[test]
+    fn test_fix_engine_plan() {
+        let engine = FixEngine::new();
+        let problems = vec![
+            Problem::new(1, 8, Level::Error, "trailing-spaces", "trailing whitespace"),
+            empty_lines_problem(4, "empty-lines.too-many", 4, 2),
+            Problem::new(7, 3, Level::Error, "key-duplicates", "duplication of key"),
+        ];
+        let content = "line1   \nline2\n\n\n\n\nline3\n";
+
+        let plan = engine.plan(content, &problems, &Config::default()).unwrap();
+        assert_eq!(plan.edits.len(), 2);
+        assert!(plan.deferred.is_empty());
+
+        let (fixed, line_map) = plan.apply();
+        assert_eq!(
+            fixed,
+            engine
+                .fix_problems(content, &problems, &Config::default())
+                .unwrap()
+        );
+        let remaining = line_map.remap(problems[2..].to_vec());
+        assert_eq!(remaining[0].line, 5);
+    }
+
+    #[test]
+    fn test_fix_engine_no_applicable_fixes() {
+        let engine = FixEngine::new();
+        let problems = vec![Problem::new(
+            1,
+            5,
+            Level::Error,
+            "unknown-rule",
+            "some error",
+        )];
+        let content = "line1\nline2";
+
+        let fixed = engine
+            .fix_problems(content, &problems, &Config::default())
+            .unwrap();
+        assert_eq!(fixed, content); // Should be unchanged
+    }
+
+    #[test]
+    fn test_sequence_ordering_fix() {
+        let config = SequenceOrderingRule::new().default_config();
+        let content = "dependencies:\n  - requests\n  # pinned for py3.8\n  - attrs\n  - certifi\nname: app\n";
+        let problem = Problem::new(
+            4,
+            5,
+            Level::Error,
+            "sequence-ordering",
+            "sequence item \"attrs\" should come before \"requests\"",
+        );
+
+        let fixed = SequenceOrderingFix
+            .apply_fix(content, &problem, &config)
+            .unwrap();
+        assert_eq!(
+            fixed,
+            "dependencies:\n  # pinned for py3.8\n  - attrs\n  - certifi\n  - requests\nname: app\n"
+        );
+    }
+
+    #[test]
+    fn test_sequence_ordering_fix_skips_nested_items() {
+        let config = SequenceOrderingRule::new().default_config();
+        let content = "items:\n  - b\n  - a\n  - name: c\n";
+        let problem = Problem::new(3, 5, Level::Error, "sequence-ordering", "unsorted");
+
+        let fixed = SequenceOrderingFix
+            .apply_fix(content, &problem, &config)
+            .unwrap();
+        assert_eq!(fixed, content);
+    }
+}
@@ -0,0 +1,248 @@
+//! Fixes planned as line edits of the original content, for callers that apply some of
+//! them and need to know where the remaining lines moved
+
+use crate::linter::Problem;
+
+/// Replacement of whole lines of the original content that fixes one problem
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixEdit {
+    /// Rule that reported the problem
+    pub rule: String,
+    /// Line (1-based) of the problem
+    pub problem_line: usize,
+    /// First replaced line (1-based)
+    pub start_line: usize,
+    /// Number of replaced lines; with none, `text` is inserted before `start_line`
+    pub removed_lines: usize,
+    /// Replacement lines, each with its line break except possibly the last line of the file
+    pub text: String,
+}
+
+impl FixEdit {
+    /// The edit turning `before` into `after`, if they differ: the lines between their
+    /// common prefix and suffix
+    pub fn between(before: &str, after: &str, problem: &Problem) -> Option<Self> {
+        if before == after {
+            return None;
+        }
+        let old: Vec<&str> = before.split_inclusive('\n').collect();
+        let new: Vec<&str> = after.split_inclusive('\n').collect();
+
+        let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+        let suffix = old[prefix..]
+            .iter()
+            .rev()
+            .zip(new[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        Some(Self {
+            rule: problem.rule.clone(),
+            problem_line: problem.line,
+            start_line: prefix + 1,
+            removed_lines: old.len() - suffix - prefix,
+            text: new[prefix..new.len() - suffix].concat(),
+        })
+    }
+
+    /// Line after the last replaced line
+    pub fn end_line(&self) -> usize {
+        self.start_line + self.removed_lines
+    }
+
+    /// Number of lines the edit puts in place of the replaced ones
+    pub fn added_lines(&self) -> usize {
+        self.text.split_inclusive('\n').count()
+    }
+}
+
+/// Edits fixing problems in one piece of content, none of which overlap
+#[derive(Debug, Clone)]
+pub struct FixPlan {
+    content: String,
+    /// Edits in line order; remove some to apply only the others
+    pub edits: Vec<FixEdit>,
+    /// Problems whose fix overlaps an edit of the plan. Lint the fixed content again to
+    /// fix them.
+    pub deferred: Vec<Problem>,
+}
+
+impl FixPlan {
+    /// Plan the given edits of `content`; an edit overlapping an earlier one defers its problem
+    pub(crate) fn new(content: &str, candidates: Vec<(FixEdit, Problem)>) -> Self {
+        let mut candidates = candidates;
+        candidates.sort_by_key(|(edit, _)| (edit.start_line, edit.end_line()));
+
+        let mut edits: Vec<FixEdit> = Vec::new();
+        let mut deferred = Vec::new();
+        for (edit, problem) in candidates {
+            match edits.last() {
+                // Problems reported twice, or by rules sharing a fix, make the same edit
+                Some(last)
+                    if last.start_line == edit.start_line
+                        && last.removed_lines == edit.removed_lines
+                        && last.text == edit.text => {}
+                Some(last)
+                    if edit.start_line < last.end_line() || edit.start_line == last.start_line =>
+                {
+                    deferred.push(problem)
+                }
+                _ => edits.push(edit),
+            }
+        }
+
+        Self {
+            content: content.to_string(),
+            edits,
+            deferred,
+        }
+    }
+
+    /// Content the plan was made for
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// Whether the plan changes nothing
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+
+    /// Apply the edits of the plan, returning the fixed content and where its lines came from
+    pub fn apply(&self) -> (String, LineMap) {
+        let lines: Vec<&str> = self.content.split_inclusive('\n').collect();
+        let mut fixed = String::with_capacity(self.content.len());
+        let mut next = 0;
+
+        for edit in &self.edits {
+            let start = (edit.start_line - 1).min(lines.len());
+            fixed.extend(lines[next.min(start)..start].iter().copied());
+            fixed.push_str(&edit.text);
+            next = (edit.end_line() - 1).min(lines.len());
+        }
+        fixed.extend(lines[next.min(lines.len())..].iter().copied());
+
+        let line_map = LineMap {
+            edits: self
+                .edits
+                .iter()
+                .map(|edit| (edit.start_line, edit.removed_lines, edit.added_lines()))
+                .collect(),
+        };
+        (fixed, line_map)
+    }
+}
+
+/// Where the lines of content ended up after a [`FixPlan`] was applied
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LineMap {
+    /// First replaced line, replaced lines and added lines of each edit, in line order
+    edits: Vec<(usize, usize, usize)>,
+}
+
+impl LineMap {
+    /// New line (1-based) of an original line, or `None` if an edit replaced it
+    pub fn map(&self, line: usize) -> Option<usize> {
+        let mut shifted = line;
+        for &(start, removed, added) in &self.edits {
+            if line < start {
+                break;
+            }
+            if line < start + removed {
+                return None;
+            }
+            shifted = shifted + added - removed;
+        }
+        Some(shifted)
+    }
+
+    /// Problems moved to their new lines, leaving out those on replaced lines
+    pub fn remap(&self, problems: Vec<Problem>) -> Vec<Problem> {
+        problems
+            .into_iter()
+            .filter_map(|mut problem| {
+                problem.line = self.map(problem.line)?;
+                Some(problem)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::Level;
+
+    fn edit(start_line: usize, removed_lines: usize, text: &str) -> FixEdit {
+        FixEdit {
+            rule: "test".to_string(),
+            problem_line: start_line,
+            start_line,
+            removed_lines,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_edit_between() {
+        let problem = Problem::new(2, 1, Level::Error, "empty-lines", "too many blank lines");
+        let edit = FixEdit::between("a: 1\n\n\n\nb: 2\n", "a: 1\n\nb: 2\n", &problem).unwrap();
+        assert_eq!((edit.start_line, edit.removed_lines), (3, 2));
+        assert_eq!(edit.text, "");
+
+        let edit = FixEdit::between("a: 1\nb: 2", "a: 1\nb: 2\n", &problem).unwrap();
+        assert_eq!((edit.start_line, edit.removed_lines), (2, 1));
+        assert_eq!(edit.text, "b: 2\n");
+
+        assert_eq!(FixEdit::between("a: 1\n", "a: 1\n", &problem), None);
+    }
+
+    #[test]
+    fn test_apply_and_line_map() {
+        let content = "a: 1  \n\n\n\nb: 2\nc: 3\n";
+        let problem = |line| Problem::new(line, 1, Level::Error, "test", "problem");
+        let plan = FixPlan::new(
+            content,
+            vec![
+                (edit(2, 2, ""), problem(2)),
+                (edit(1, 1, "a: 1\n"), problem(1)),
+                (edit(6, 0, "b2: 2\nb3: 3\n"), problem(6)),
+                // Overlaps the edit of line 2
+                (edit(3, 1, "x\n"), problem(3)),
+            ],
+        );
+        assert_eq!(plan.edits.len(), 3);
+        assert_eq!(plan.deferred.len(), 1);
+
+        let (fixed, line_map) = plan.apply();
+        assert_eq!(fixed, "a: 1\n\nb: 2\nb2: 2\nb3: 3\nc: 3\n");
+        assert_eq!(line_map.map(1), None);
+        assert_eq!(line_map.map(3), None);
+        assert_eq!(line_map.map(4), Some(2));
+        assert_eq!(line_map.map(5), Some(3));
+        assert_eq!(line_map.map(6), Some(6));
+
+        let remapped = line_map.remap(vec![problem(1), problem(5), problem(6)]);
+        let lines: Vec<usize> = remapped.iter().map(|problem| problem.line).collect();
+        assert_eq!(lines, vec![3, 6]);
+    }
+
+    #[test]
+    fn test_partial_apply() {
+        let content = "a: 1  \nb: 2  \nc: 3\n";
+        let problem = |line| Problem::new(line, 1, Level::Error, "test", "problem");
+        let mut plan = FixPlan::new(
+            content,
+            vec![
+                (edit(1, 1, "a: 1\n"), problem(1)),
+                (edit(2, 1, "b: 2\n"), problem(2)),
+            ],
+        );
+        plan.edits.retain(|edit| edit.start_line == 2);
+
+        let (fixed, line_map) = plan.apply();
+        assert_eq!(fixed, "a: 1  \nb: 2\nc: 3\n");
+        assert_eq!(line_map.map(1), Some(1));
+        assert_eq!(line_map.map(3), Some(3));
+    }
+}
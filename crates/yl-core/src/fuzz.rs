@@ -0,0 +1,111 @@
+//! Entry points for fuzzing the linter, shared by the cargo-fuzz targets in `fuzz/` and
+//! the regression tests below.
+//!
+//! Each entry point takes arbitrary bytes and panics if an invariant is violated:
+//! linting never panics, and every problem points at an existing line with a column
+//! at most one past the end of that line.
+
+use crate::linter::{LintContext, Problem};
+use crate::{Config, Linter, RuleRegistry};
+use std::path::Path;
+
+/// Lint the input with every rule enabled, as `yl` would
+pub fn lint_content(data: &[u8]) {
+    let Ok(content) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let mut config = Config::default();
+    for rule_config in config.rules.values_mut() {
+        rule_config.enabled = true;
+    }
+    let linter = Linter::new(config).with_config_discovery(false);
+
+    if let Ok(problems) = linter.lint_content("fuzz.yaml", content) {
+        for problem in &problems {
+            check_position(content, problem);
+        }
+    }
+}
+
+/// Run each rule's `check` on the input directly, with its default configuration
+pub fn rule_checks(data: &[u8]) {
+    let Ok(content) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let registry = RuleRegistry::with_default_rules();
+    let context = LintContext::new(Path::new("fuzz.yaml"), content);
+    for rule in registry.rules() {
+        let mut config = rule.default_config();
+        config.enabled = true;
+        if let Ok(problems) = rule.check(&context, &config) {
+            for problem in &problems {
+                check_position(content, problem);
+            }
+        }
+    }
+}
+
+/// Assert that a problem points into the content
+fn check_position(content: &str, problem: &Problem) {
+    let lines: Vec<&str> = content.lines().collect();
+    let line_count = lines.len().max(1);
+    assert!(
+        (1..=line_count).contains(&problem.line),
+        "{} reported line {} of {line_count}: {problem:?}",
+        problem.rule,
+        problem.line
+    );
+
+    let line_chars = lines
+        .get(problem.line - 1)
+        .map_or(0, |line| line.chars().count());
+    assert!(
+        (1..=line_chars + 1).contains(&problem.column),
+        "{} reported column {} of a {line_chars}-character line: {problem:?}",
+        problem.rule,
+        problem.column
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Inputs that once broke an invariant, kept as regression tests
+    const CORPUS: &[&str] = &[
+        "",
+        "é",
+        "a_at: \u{200b}",
+        "{{ x }}!yes\u{feff}1.0",
+        "{\u{feff}*a: ...'",
+        "{  key: 1.0\\...\r\n# : %TAG !e! x:\u{200b}}&a  ",
+        "[\té\n[<<: x[\\!!str*a[| é",
+        "- - - \n  - |\n\t\u{feff}",
+        "%YAML 1.2\n%TAG !e! x:\n--- !e!a\n...\n",
+        "\": x",
+        "': x",
+    ];
+
+    #[test]
+    fn test_lint_content_corpus() {
+        for input in CORPUS {
+            lint_content(input.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_rule_checks_corpus() {
+        for input in CORPUS {
+            rule_checks(input.as_bytes());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "reported column")]
+    fn test_check_position() {
+        let problem = Problem::new(1, 3, crate::Level::Error, "rule", "message");
+        check_position("é", &problem);
+    }
+}
@@ -0,0 +1,39 @@
+//! Core of the `yl` YAML linter: the linting engine, built-in rules, configuration
+//! loading and automatic fixes.
+//!
+//! The types re-exported at the crate root form the stable public API and follow
+//! semantic versioning: [`Linter`], [`RuleRegistry`], [`Problem`], [`Level`],
+//! [`FixEngine`] and [`Config`]. The modules themselves are public so that tools can
+//! reach further, but items not re-exported here may change between minor versions.
+//!
+//! ```
+//! use yl_core::{Config, FixEngine, Linter};
+//!
+//! let config = Config::default();
+//! let linter = Linter::new(config.clone());
+//!
+//! let content = "key: value   \n";
+//! let problems = linter.lint_content("example.yaml", content)?;
+//! assert_eq!(problems[0].rule, "trailing-spaces");
+//!
+//! let fixed = FixEngine::new().fix_problems(content, &problems, &config)?;
+//! assert_eq!(fixed, "key: value\n");
+//! # Ok::<(), eyre::Report>(())
+//! ```
+
+pub mod codes;
+pub mod config;
+pub mod fixes;
+#[cfg(any(test, feature = "fuzzing"))]
+pub mod fuzz;
+pub mod linter;
+pub mod messages;
+pub mod parser;
+pub mod read;
+pub mod rules;
+pub mod write;
+
+pub use config::Config;
+pub use fixes::FixEngine;
+pub use linter::{Level, Linter, Problem};
+pub use rules::RuleRegistry;
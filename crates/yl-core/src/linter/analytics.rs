@@ -0,0 +1,252 @@
+use super::{Level, LintObserver, Problem};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Number of slowest files kept in a report
+const SLOWEST_FILES: usize = 10;
+
+/// Files taking longer than this are called out in the suggestions
+const SLOW_FILE: Duration = Duration::from_millis(100);
+
+/// Statistics of a linting run, collected as a [`LintObserver`]; safe to share across
+/// threads
+#[derive(Debug, Default)]
+pub struct LintAnalytics {
+    state: Mutex<AnalyticsState>,
+}
+
+#[derive(Debug, Default)]
+struct AnalyticsState {
+    files: usize,
+    problems_by_rule: HashMap<String, usize>,
+    problems_by_level: BTreeMap<Level, usize>,
+    file_times: Vec<(PathBuf, Duration)>,
+    rule_timeouts: BTreeMap<String, usize>,
+    rule_file_times: HashMap<(String, PathBuf), Duration>,
+}
+
+/// Summary of the statistics collected so far
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct AnalyticsReport {
+    /// Number of files finished
+    pub files: usize,
+    /// Number of problems reported
+    pub problems: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    pub info: usize,
+    /// Problems reported per rule
+    pub problems_by_rule: BTreeMap<String, usize>,
+    /// Time spent on all files, summed over worker threads
+    pub total_time: Duration,
+    /// Files that took longest, slowest first
+    pub slowest_files: Vec<(PathBuf, Duration)>,
+    /// Number of files each rule was skipped on because of `rule-timeout`
+    pub rule_timeouts: BTreeMap<String, usize>,
+    /// Time each rule spent on each file, summed over fix passes
+    pub rule_file_times: BTreeMap<String, BTreeMap<PathBuf, Duration>>,
+}
+
+impl AnalyticsReport {
+    /// The time of each rule on each file as folded stacks, one `rule;file microseconds`
+    /// line per pair, as taken by flamegraph tools such as `inferno-flamegraph`.
+    /// Semicolons in paths, which would start a new frame, are replaced by `_`.
+    pub fn to_folded(&self) -> String {
+        let mut folded = String::new();
+        for (rule, files) in &self.rule_file_times {
+            for (path, elapsed) in files {
+                let path = path.display().to_string().replace(';', "_");
+                let _ = writeln!(folded, "{rule};{path} {}", elapsed.as_micros());
+            }
+        }
+        folded
+    }
+}
+
+impl LintAnalytics {
+    /// Create analytics with nothing recorded
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Summarize the files and problems recorded so far
+    pub fn report(&self) -> AnalyticsReport {
+        let state = self.state.lock().expect("analytics lock poisoned");
+        let level = |level| state.problems_by_level.get(&level).copied().unwrap_or(0);
+
+        let mut rule_file_times: BTreeMap<String, BTreeMap<PathBuf, Duration>> = BTreeMap::new();
+        for ((rule, path), elapsed) in &state.rule_file_times {
+            rule_file_times
+                .entry(rule.clone())
+                .or_default()
+                .insert(path.clone(), *elapsed);
+        }
+
+        let mut slowest_files = state.file_times.clone();
+        slowest_files.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        slowest_files.truncate(SLOWEST_FILES);
+
+        AnalyticsReport {
+            files: state.files,
+            problems: state.problems_by_rule.values().sum(),
+            errors: level(Level::Error),
+            warnings: level(Level::Warning),
+            info: level(Level::Info),
+            problems_by_rule: state
+                .problems_by_rule
+                .iter()
+                .map(|(rule, count)| (rule.clone(), *count))
+                .collect(),
+            total_time: state.file_times.iter().map(|(_, elapsed)| *elapsed).sum(),
+            slowest_files,
+            rule_timeouts: state.rule_timeouts.clone(),
+            rule_file_times,
+        }
+    }
+
+    /// Suggestions for making linting faster or less noisy, based on the recorded run
+    pub fn suggest_optimizations(&self) -> Vec<String> {
+        let report = self.report();
+        let mut suggestions = Vec::new();
+
+        for (path, elapsed) in &report.slowest_files {
+            if *elapsed > SLOW_FILE {
+                suggestions.push(format!(
+                    "{} took {} ms; consider splitting it or adding it to ignore patterns",
+                    path.display(),
+                    elapsed.as_millis()
+                ));
+            }
+        }
+
+        for (rule, count) in &report.rule_timeouts {
+            suggestions.push(format!(
+                "{rule} timed out on {count} file(s); consider disabling it for those files"
+            ));
+        }
+
+        // A single rule producing most of the problems is usually misconfigured
+        if report.problems >= 10 {
+            for (rule, count) in &report.problems_by_rule {
+                if count * 2 > report.problems {
+                    suggestions.push(format!(
+                        "{rule} reported {count} of {} problems; consider adjusting or disabling it",
+                        report.problems
+                    ));
+                }
+            }
+        }
+
+        suggestions
+    }
+}
+
+impl LintObserver for LintAnalytics {
+    fn on_problem(&self, _path: &Path, problem: &Problem) {
+        let mut state = self.state.lock().expect("analytics lock poisoned");
+        *state
+            .problems_by_rule
+            .entry(problem.rule.clone())
+            .or_default() += 1;
+        *state
+            .problems_by_level
+            .entry(problem.level.clone())
+            .or_default() += 1;
+    }
+
+    fn on_rule_timeout(&self, _path: &Path, rule: &str) {
+        let mut state = self.state.lock().expect("analytics lock poisoned");
+        *state.rule_timeouts.entry(rule.to_string()).or_default() += 1;
+    }
+
+    fn on_rule_end(&self, path: &Path, rule: &str, elapsed: Duration) {
+        let mut state = self.state.lock().expect("analytics lock poisoned");
+        *state
+            .rule_file_times
+            .entry((rule.to_string(), path.to_path_buf()))
+            .or_default() += elapsed;
+    }
+
+    fn on_file_end(&self, path: &Path, _problems: &[Problem], elapsed: Duration) {
+        let mut state = self.state.lock().expect("analytics lock poisoned");
+        state.files += 1;
+        state.file_times.push((path.to_path_buf(), elapsed));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_report() {
+        let report = LintAnalytics::new().report();
+        assert_eq!(report, AnalyticsReport::default());
+    }
+
+    #[test]
+    fn test_report_counts_problems_and_files() {
+        let analytics = LintAnalytics::new();
+        let path = Path::new("a.yaml");
+        let problems = vec![
+            Problem::new(1, 1, Level::Error, "key-duplicates", "duplicate key"),
+            Problem::new(2, 81, Level::Warning, "line-length", "line too long"),
+            Problem::new(3, 81, Level::Warning, "line-length", "line too long"),
+        ];
+        for problem in &problems {
+            analytics.on_problem(path, problem);
+        }
+        analytics.on_file_end(path, &problems, Duration::from_millis(3));
+        analytics.on_file_end(Path::new("b.yaml"), &[], Duration::from_millis(5));
+
+        let report = analytics.report();
+        assert_eq!(report.files, 2);
+        assert_eq!(report.problems, 3);
+        assert_eq!((report.errors, report.warnings, report.info), (1, 2, 0));
+        assert_eq!(report.problems_by_rule["line-length"], 2);
+        assert_eq!(report.total_time, Duration::from_millis(8));
+        assert_eq!(report.slowest_files[0].0, PathBuf::from("b.yaml"));
+    }
+
+    #[test]
+    fn test_rule_file_times_folded() {
+        let analytics = LintAnalytics::new();
+        analytics.on_rule_end(Path::new("b.yaml"), "truthy", Duration::from_micros(40));
+        analytics.on_rule_end(Path::new("a;1.yaml"), "truthy", Duration::from_micros(7));
+        analytics.on_rule_end(Path::new("a;1.yaml"), "truthy", Duration::from_micros(3));
+        analytics.on_rule_end(Path::new("b.yaml"), "anchors", Duration::from_millis(2));
+
+        let report = analytics.report();
+        assert_eq!(
+            report.rule_file_times["truthy"][Path::new("a;1.yaml")],
+            Duration::from_micros(10)
+        );
+        assert_eq!(
+            report.to_folded(),
+            "anchors;b.yaml 2000\ntruthy;a_1.yaml 10\ntruthy;b.yaml 40\n"
+        );
+    }
+
+    #[test]
+    fn test_suggest_optimizations() {
+        let analytics = LintAnalytics::new();
+        let path = Path::new("big.yaml");
+        let problem = Problem::new(1, 81, Level::Warning, "line-length", "line too long");
+        for _ in 0..10 {
+            analytics.on_problem(path, &problem);
+        }
+        analytics.on_rule_timeout(path, "indentation");
+        analytics.on_file_end(path, &[], Duration::from_millis(150));
+
+        assert_eq!(analytics.report().rule_timeouts["indentation"], 1);
+        let suggestions = analytics.suggest_optimizations();
+        assert_eq!(suggestions.len(), 3);
+        assert!(suggestions[0].starts_with("big.yaml took 150 ms"));
+        assert!(suggestions[1].starts_with("indentation timed out on 1 file(s)"));
+        assert!(suggestions[2].starts_with("line-length reported 10 of 10"));
+    }
+}
@@ -0,0 +1,802 @@
+use super::tokens::{FlowCollection, TokenMap};
+use crate::rules::common;
+use serde::Deserialize;
+use serde_yaml::Value;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// A block mapping key found in the content, with the path of keys leading to it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyPath {
+    /// Line of the key (1-based)
+    pub line: usize,
+    /// Column of the key (1-based, in characters)
+    pub column: usize,
+    /// Keys from the document root to this key; sequence items appear as their index
+    pub path: Vec<String>,
+}
+
+impl KeyPath {
+    /// Get the path as a dot-separated string
+    pub fn dotted(&self) -> String {
+        self.path.join(".")
+    }
+}
+
+/// A scalar item of a block sequence, with the path of keys leading to the sequence
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SequenceItem {
+    /// Line of the item's value (1-based)
+    pub line: usize,
+    /// Column of the item's value (1-based, in characters)
+    pub column: usize,
+    /// Keys from the document root to the sequence holding the item
+    pub path: Vec<String>,
+    /// Position of the item within its sequence (0-based)
+    pub index: usize,
+    /// The item's value, without quotes
+    pub value: String,
+}
+
+/// How a block scalar treats the line breaks at its end
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Chomping {
+    /// `-`: no final line break
+    Strip,
+    /// Default: a single final line break
+    Clip,
+    /// `+`: every trailing line break, including those of blank lines after the content
+    Keep,
+}
+
+/// A literal (`|`) or folded (`>`) block scalar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockScalar {
+    /// Line of the `key: |` header (1-based)
+    pub header: usize,
+    pub chomping: Chomping,
+    /// Lines whose whitespace is part of the value: the content, and with `+` chomping the
+    /// blank lines after it (1-based, inclusive; empty if the scalar has no such lines)
+    pub lines: RangeInclusive<usize>,
+}
+
+/// A key or sequence item on the walk from the document root
+struct PathSegment {
+    /// Byte column the key or `-` indicator starts at
+    indent: usize,
+    name: String,
+    /// Index of the item, for sequence items
+    item: Option<usize>,
+}
+
+/// Context information available to rules during linting
+#[derive(Debug)]
+#[allow(dead_code)] // Fields are part of API for future phases
+pub struct LintContext<'a> {
+    /// Path to the file being linted
+    pub file_path: &'a Path,
+    /// Content of the file being linted
+    pub content: &'a str,
+    /// Current line number being processed (1-based)
+    pub current_line: usize,
+    /// Path within the YAML structure (e.g., ["spec", "containers", "0", "name"])
+    pub yaml_path: Vec<String>,
+    /// Each document of the content parsed as YAML, or the parse error, on first use
+    yaml: OnceLock<Result<Vec<Value>, String>>,
+    /// Comments and quoted and block scalars, found on first use
+    tokens: OnceLock<TokenMap>,
+    /// Lines of block scalar content, found on first use
+    block_scalar_spans: OnceLock<Vec<RangeInclusive<usize>>>,
+    /// Keys and sequence items of block collections, found on first use
+    block_nodes: OnceLock<(Vec<KeyPath>, Vec<SequenceItem>)>,
+}
+
+#[allow(dead_code)] // Methods are part of API for future phases
+impl<'a> LintContext<'a> {
+    /// Create a new lint context. Nothing is parsed until a rule asks for it, and then
+    /// only once for all the rules linting the file.
+    pub fn new(file_path: &'a Path, content: &'a str) -> Self {
+        Self {
+            file_path,
+            content,
+            current_line: 0,
+            yaml_path: Vec::new(),
+            yaml: OnceLock::new(),
+            tokens: OnceLock::new(),
+            block_scalar_spans: OnceLock::new(),
+            block_nodes: OnceLock::new(),
+        }
+    }
+
+    /// Comments, quoted scalars and block scalars of the content, shared by all rules
+    /// linting the file
+    pub fn tokens(&self) -> &TokenMap {
+        self.tokens
+            .get_or_init(|| TokenMap::new(self.content, self.block_scalar_spans().to_vec()))
+    }
+
+    /// Each document of the content parsed as YAML, or the parser's error message
+    fn parsed(&self) -> &Result<Vec<Value>, String> {
+        self.yaml.get_or_init(|| {
+            serde_yaml::Deserializer::from_str(self.content)
+                .map(Value::deserialize)
+                .collect::<Result<_, _>>()
+                .map_err(|e| e.to_string())
+        })
+    }
+
+    /// The error the YAML parser reports for the content, if it is not valid YAML
+    pub fn parse_error(&self) -> Option<&str> {
+        self.parsed().as_ref().err().map(String::as_str)
+    }
+
+    /// Flow sequences and mappings of the content, ordered by position
+    pub fn flow_collections(&self) -> Vec<FlowCollection> {
+        self.tokens().flow_collections(self.content)
+    }
+
+    /// Get the file name as a string
+    pub fn file_name(&self) -> &str {
+        self.file_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("<unknown>")
+    }
+
+    /// Get the lines of the content as an iterator
+    pub fn lines(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.content
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line))
+    }
+
+    /// Get a specific line by number (1-based)
+    pub fn get_line(&self, line_number: usize) -> Option<&str> {
+        if line_number == 0 {
+            return None;
+        }
+        self.content.lines().nth(line_number - 1)
+    }
+
+    /// Get the total number of lines
+    pub fn line_count(&self) -> usize {
+        self.content.lines().count()
+    }
+
+    /// Whether the file is empty or holds only whitespace
+    pub fn is_blank(&self) -> bool {
+        self.content.trim().is_empty()
+    }
+
+    /// Line ranges (1-based, inclusive) holding the content of literal (`|`) and
+    /// folded (`>`) block scalars. Trailing blank lines are not part of a span.
+    pub fn block_scalar_spans(&self) -> &[RangeInclusive<usize>] {
+        self.block_scalar_spans
+            .get_or_init(|| self.find_block_scalar_spans())
+    }
+
+    fn find_block_scalar_spans(&self) -> Vec<RangeInclusive<usize>> {
+        let mut spans = Vec::new();
+        // Indentation of the line that introduced the current block scalar
+        let mut parent_indent = None;
+        let mut span_start = 0;
+        let mut last_content = 0;
+
+        for (line_no, line) in self.lines() {
+            let indent = common::count_leading_whitespace(line);
+
+            if let Some(parent) = parent_indent {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                if indent > parent {
+                    last_content = line_no;
+                    continue;
+                }
+
+                // Dedent ends the block scalar
+                if last_content >= span_start {
+                    spans.push(span_start..=last_content);
+                }
+                parent_indent = None;
+            }
+
+            if Self::starts_block_scalar(line) {
+                parent_indent = Some(indent);
+                span_start = line_no + 1;
+                last_content = line_no;
+            }
+        }
+
+        if parent_indent.is_some() && last_content >= span_start {
+            spans.push(span_start..=last_content);
+        }
+
+        spans
+    }
+
+    /// Block scalars with the lines whose whitespace belongs to their value.
+    ///
+    /// Unlike [`Self::block_scalar_spans`], blank lines count: between content lines, after
+    /// the content with `+` chomping, and as the only content of an otherwise empty scalar.
+    pub fn block_scalars(&self) -> Vec<BlockScalar> {
+        let mut scalars = Vec::new();
+        // Header line, chomping and parent indentation of the open block scalar
+        let mut open: Option<(usize, Chomping, usize)> = None;
+        let mut last_content = 0;
+        let mut last_line = 0;
+
+        let close = |(header, chomping, _): (usize, Chomping, usize), last_content, end| {
+            let end = if chomping == Chomping::Keep {
+                end
+            } else {
+                last_content
+            };
+            BlockScalar {
+                header,
+                chomping,
+                lines: header + 1..=end,
+            }
+        };
+
+        for (line_no, line) in self.lines() {
+            last_line = line_no;
+            let indent = common::count_leading_whitespace(line);
+
+            if let Some((_, _, parent)) = open {
+                if line.is_empty() {
+                    continue;
+                }
+                // Spaces on an otherwise blank line may be content, depending on the indentation
+                if indent > parent || line.trim().is_empty() {
+                    last_content = line_no;
+                    continue;
+                }
+                scalars.push(close(open.take().unwrap(), last_content, line_no - 1));
+            }
+
+            if let Some(chomping) = Self::block_scalar_header(line) {
+                open = Some((line_no, chomping, indent));
+                last_content = line_no;
+            }
+        }
+
+        if let Some(scalar) = open {
+            scalars.push(close(scalar, last_content, last_line));
+        }
+
+        scalars
+    }
+
+    /// Check if a line ends with a block scalar header such as `key: |` or `- >-`
+    fn starts_block_scalar(line: &str) -> bool {
+        Self::block_scalar_header(line).is_some()
+    }
+
+    /// Chomping of the block scalar introduced by a line ending with a header such as
+    /// `key: |` or `- >-`
+    fn block_scalar_header(line: &str) -> Option<Chomping> {
+        let content = common::strip_comment(line).trim_end();
+        let (before, token) = match content.rfind(char::is_whitespace) {
+            Some(pos) => (content[..pos].trim_end(), &content[pos + 1..]),
+            None => ("", content),
+        };
+
+        let mut chars = token.chars();
+        let is_header = matches!(chars.next(), Some('|' | '>'))
+            && chars.all(|c| c.is_ascii_digit() || c == '+' || c == '-');
+
+        // The header follows a key, a sequence indicator, a tag/anchor, or stands alone
+        let introduced = before.is_empty()
+            || before.ends_with(':')
+            || before.ends_with('-')
+            || before
+                .rsplit(char::is_whitespace)
+                .next()
+                .is_some_and(|prev| prev.starts_with('&') || prev.starts_with('!'));
+
+        if !(is_header && introduced) {
+            return None;
+        }
+        Some(if token.contains('+') {
+            Chomping::Keep
+        } else if token.contains('-') {
+            Chomping::Strip
+        } else {
+            Chomping::Clip
+        })
+    }
+
+    /// Check if the current YAML path matches a pattern
+    /// Pattern examples: "spec.containers.*", "metadata.name"
+    pub fn yaml_path_matches(&self, pattern: &str) -> bool {
+        Self::path_matches(pattern, &self.yaml_path)
+    }
+
+    /// Check if a path matches a dotted pattern in which `*` stands for any one segment
+    pub fn path_matches(pattern: &str, path: &[String]) -> bool {
+        let pattern_parts: Vec<&str> = pattern.split('.').collect();
+
+        if pattern_parts.len() != path.len() {
+            return false;
+        }
+
+        pattern_parts
+            .iter()
+            .zip(path.iter())
+            .all(|(pattern_part, path_part)| pattern_part == &"*" || pattern_part == path_part)
+    }
+
+    /// Walk the block mappings and sequences of every document, returning each key
+    /// with its position and path. Keys inside flow collections and block scalars
+    /// are not included.
+    pub fn key_paths(&self) -> &[KeyPath] {
+        &self.block_nodes().0
+    }
+
+    /// Walk the block sequences of every document, returning each single-line scalar
+    /// item with its position and the path of its sequence
+    pub fn sequence_items(&self) -> &[SequenceItem] {
+        &self.block_nodes().1
+    }
+
+    fn block_nodes(&self) -> &(Vec<KeyPath>, Vec<SequenceItem>) {
+        self.block_nodes.get_or_init(|| self.walk_block_nodes())
+    }
+
+    /// Track the path through block mappings and sequences line by line
+    fn walk_block_nodes(&self) -> (Vec<KeyPath>, Vec<SequenceItem>) {
+        let block_scalars = self.block_scalar_spans();
+        let mut keys = Vec::new();
+        let mut items = Vec::new();
+        let mut stack: Vec<PathSegment> = Vec::new();
+
+        for (line_no, line) in self.lines() {
+            if block_scalars.iter().any(|span| span.contains(&line_no)) {
+                continue;
+            }
+            if line.starts_with("---") || line.starts_with("...") {
+                stack.clear();
+                continue;
+            }
+
+            let content = common::strip_comment(line).trim_end();
+            let mut indent = common::count_leading_whitespace(content);
+            let mut rest = &content[indent..];
+            if rest.is_empty() {
+                continue;
+            }
+
+            // Each `- ` opens a sequence item, possibly several on one line
+            let mut opened_item = None;
+            while rest == "-" || rest.starts_with("- ") {
+                let previous = Self::pop_to(&mut stack, indent, true);
+                let index = previous.filter(|p| p.indent == indent).and_then(|p| p.item);
+                let index = index.map_or(0, |index| index + 1);
+                stack.push(PathSegment {
+                    indent,
+                    name: index.to_string(),
+                    item: Some(index),
+                });
+                opened_item = Some(index);
+
+                let after = rest[1..].trim_start();
+                indent += rest.len() - after.len();
+                rest = after;
+            }
+
+            let Some(colon) = common::find_mapping_colon(rest) else {
+                if let Some(index) = opened_item
+                    && !rest.is_empty()
+                    && !rest.starts_with(['{', '[', '|', '>', '&', '*', '!'])
+                {
+                    let value = rest
+                        .strip_prefix('"')
+                        .and_then(|v| v.strip_suffix('"'))
+                        .or_else(|| rest.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+                        .unwrap_or(rest);
+                    items.push(SequenceItem {
+                        line: line_no,
+                        column: Self::char_column(line, indent),
+                        path: stack[..stack.len() - 1]
+                            .iter()
+                            .map(|segment| segment.name.clone())
+                            .collect(),
+                        index,
+                        value: value.to_string(),
+                    });
+                }
+                continue;
+            };
+            let key = rest[..colon].trim();
+            if key.is_empty() || key.starts_with(['{', '[', '?']) {
+                continue;
+            }
+            let key = key
+                .strip_prefix('"')
+                .and_then(|k| k.strip_suffix('"'))
+                .or_else(|| key.strip_prefix('\'').and_then(|k| k.strip_suffix('\'')))
+                .unwrap_or(key);
+
+            Self::pop_to(&mut stack, indent, false);
+            stack.push(PathSegment {
+                indent,
+                name: key.to_string(),
+                item: None,
+            });
+            keys.push(KeyPath {
+                line: line_no,
+                column: Self::char_column(line, indent),
+                path: stack.iter().map(|segment| segment.name.clone()).collect(),
+            });
+        }
+
+        (keys, items)
+    }
+
+    /// Pop the segments that a node starting at `indent` closes, returning the last one.
+    /// A sequence item may sit at the same indentation as the key that holds it.
+    fn pop_to(stack: &mut Vec<PathSegment>, indent: usize, item: bool) -> Option<PathSegment> {
+        let mut last = None;
+        while stack.last().is_some_and(|segment| {
+            segment.indent > indent
+                || (segment.indent == indent && (!item || segment.item.is_some()))
+        }) {
+            last = stack.pop();
+        }
+        last
+    }
+
+    /// Get the current YAML path as a dot-separated string
+    pub fn yaml_path_string(&self) -> String {
+        self.yaml_path.join(".")
+    }
+
+    /// Check if YAML parsing was successful
+    pub fn has_valid_yaml(&self) -> bool {
+        self.parsed().is_ok()
+    }
+
+    /// Get the parsed YAML value of the first document
+    pub fn yaml(&self) -> Option<&Value> {
+        self.documents().first()
+    }
+
+    /// Get the parsed YAML value of every document, in order; empty if the content is
+    /// not valid YAML. Content without documents parses as a single null document.
+    pub fn documents(&self) -> &[Value] {
+        self.parsed().as_deref().unwrap_or(&[])
+    }
+
+    /// Navigate to a specific path in the YAML structure
+    pub fn get_yaml_at_path(&self, path: &[&str]) -> Option<&Value> {
+        let mut current = self.yaml()?;
+        for segment in path {
+            match current {
+                Value::Mapping(map) => {
+                    current = map.get(Value::String(segment.to_string()))?;
+                }
+                Value::Sequence(seq) => {
+                    if let Ok(index) = segment.parse::<usize>() {
+                        current = seq.get(index)?;
+                    } else {
+                        return None;
+                    }
+                }
+                _ => return None,
+            }
+        }
+        Some(current)
+    }
+
+    /// Check if the current YAML path contains duplicate keys
+    pub fn has_duplicate_keys(&self) -> Vec<String> {
+        let mut duplicates = Vec::new();
+        if let Some(Value::Mapping(map)) = self.yaml() {
+            let mut seen_keys = std::collections::HashSet::new();
+            for key in map.keys() {
+                if let Value::String(key_str) = key
+                    && !seen_keys.insert(key_str.clone())
+                {
+                    duplicates.push(key_str.clone());
+                }
+            }
+        }
+        duplicates
+    }
+
+    /// Convert a byte offset within `line` to a 1-based column counted in characters
+    /// (Unicode scalar values), the unit of [`Problem::column`](super::Problem::column)
+    pub fn char_column(line: &str, byte_offset: usize) -> usize {
+        let chars_before = line
+            .char_indices()
+            .take_while(|&(index, _)| index < byte_offset)
+            .count();
+        chars_before + byte_offset.saturating_sub(line.len()) + 1
+    }
+
+    /// Convert a 1-based character column within `line` to the 0-based UTF-16 offset
+    /// used by LSP positions
+    pub fn utf16_offset(line: &str, column: usize) -> usize {
+        let chars = column.saturating_sub(1);
+        let within_line: usize = line.chars().take(chars).map(char::len_utf16).sum();
+        within_line + chars.saturating_sub(line.chars().count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_context_creation() {
+        let path = PathBuf::from("test.yaml");
+        let content = "key: value\nother: data";
+        let context = LintContext::new(&path, content);
+
+        assert_eq!(context.file_path, &path);
+        assert_eq!(context.content, content);
+        assert_eq!(context.current_line, 0);
+        assert!(context.yaml_path.is_empty());
+    }
+
+    #[test]
+    fn test_parsed_once() {
+        let path = PathBuf::from("test.yaml");
+        let context = LintContext::new(&path, "key: value\nlist:\n  - a\n");
+
+        // Every call returns the value parsed on first use
+        assert!(std::ptr::eq(
+            context.yaml().unwrap(),
+            context.yaml().unwrap()
+        ));
+        assert!(std::ptr::eq(context.key_paths(), context.key_paths()));
+        assert!(std::ptr::eq(
+            context.sequence_items(),
+            context.sequence_items()
+        ));
+        assert!(std::ptr::eq(
+            context.block_scalar_spans(),
+            context.block_scalar_spans()
+        ));
+        assert_eq!(context.parse_error(), None);
+
+        let context = LintContext::new(&path, "key: [unclosed\n");
+        assert!(context.yaml().is_none());
+        assert!(context.documents().is_empty());
+        assert!(!context.has_valid_yaml());
+        assert!(context.parse_error().is_some());
+    }
+
+    #[test]
+    fn test_documents() {
+        let path = PathBuf::from("test.yaml");
+        let context = LintContext::new(&path, "---\nkind: Service\n---\nkind: Deployment\n");
+        let kinds: Vec<_> = context
+            .documents()
+            .iter()
+            .map(|document| document["kind"].as_str().unwrap())
+            .collect();
+        assert_eq!(kinds, ["Service", "Deployment"]);
+        assert_eq!(context.yaml(), context.documents().first());
+        assert!(context.has_valid_yaml());
+
+        // An error in any document makes the content invalid
+        let context = LintContext::new(&path, "---\na: 1\n---\nb: [unclosed\n");
+        assert!(context.parse_error().unwrap().contains("line 5"));
+        assert!(context.yaml().is_none());
+    }
+
+    #[test]
+    fn test_file_name() {
+        let path = PathBuf::from("/path/to/test.yaml");
+        let content = "";
+        let context = LintContext::new(&path, content);
+
+        assert_eq!(context.file_name(), "test.yaml");
+    }
+
+    #[test]
+    fn test_file_name_unknown() {
+        let path = PathBuf::from("");
+        let content = "";
+        let context = LintContext::new(&path, content);
+
+        assert_eq!(context.file_name(), "<unknown>");
+    }
+
+    #[test]
+    fn test_char_column() {
+        let line = "é: 😀 # comment";
+        assert_eq!(LintContext::char_column(line, 0), 1);
+        assert_eq!(LintContext::char_column(line, line.find(':').unwrap()), 2);
+        assert_eq!(LintContext::char_column(line, line.find('#').unwrap()), 6);
+        assert_eq!(LintContext::char_column(line, line.len()), 15);
+        assert_eq!(LintContext::char_column("ab", 3), 4);
+    }
+
+    #[test]
+    fn test_utf16_offset() {
+        let line = "é: 😀 # comment";
+        assert_eq!(LintContext::utf16_offset(line, 1), 0);
+        assert_eq!(LintContext::utf16_offset(line, 2), 1);
+        // The emoji takes two UTF-16 code units
+        assert_eq!(LintContext::utf16_offset(line, 6), 6);
+        assert_eq!(LintContext::utf16_offset("ab", 4), 3);
+    }
+
+    #[test]
+    fn test_lines_iterator() {
+        let path = PathBuf::from("test.yaml");
+        let content = "line1\nline2\nline3";
+        let context = LintContext::new(&path, content);
+
+        let lines: Vec<(usize, &str)> = context.lines().collect();
+        assert_eq!(lines, vec![(1, "line1"), (2, "line2"), (3, "line3")]);
+    }
+
+    #[test]
+    fn test_get_line() {
+        let path = PathBuf::from("test.yaml");
+        let content = "line1\nline2\nline3";
+        let context = LintContext::new(&path, content);
+
+        assert_eq!(context.get_line(1), Some("line1"));
+        assert_eq!(context.get_line(2), Some("line2"));
+        assert_eq!(context.get_line(3), Some("line3"));
+        assert_eq!(context.get_line(0), None);
+        assert_eq!(context.get_line(4), None);
+    }
+
+    #[test]
+    fn test_line_count() {
+        let path = PathBuf::from("test.yaml");
+        let content = "line1\nline2\nline3";
+        let context = LintContext::new(&path, content);
+
+        assert_eq!(context.line_count(), 3);
+    }
+
+    #[test]
+    fn test_line_count_empty() {
+        let path = PathBuf::from("test.yaml");
+        let content = "";
+        let context = LintContext::new(&path, content);
+
+        assert_eq!(context.line_count(), 0);
+    }
+
+    #[test]
+    fn test_block_scalar_spans() {
+        let path = PathBuf::from("test.yaml");
+        let content = "script: |\n  echo one\n\n  echo two\n\nnext: >-\n  folded\nlist:\n  - |\n    item\nplain: a|b\ntail: |+\n  end";
+        let context = LintContext::new(&path, content);
+
+        assert_eq!(
+            context.block_scalar_spans(),
+            vec![2..=4, 7..=7, 10..=10, 13..=13]
+        );
+    }
+
+    #[test]
+    fn test_block_scalars() {
+        let path = PathBuf::from("test.yaml");
+        let content = "keep: |+\n  one\n\n\nclip: >\n  two\n   \n\nstrip: |-\nempty: |2+\n ";
+        let context = LintContext::new(&path, content);
+
+        let scalars: Vec<_> = context
+            .block_scalars()
+            .into_iter()
+            .map(|scalar| (scalar.header, scalar.chomping, scalar.lines))
+            .collect();
+        assert_eq!(
+            scalars,
+            vec![
+                (1, Chomping::Keep, 2..=4),
+                (5, Chomping::Clip, 6..=7),
+                (9, Chomping::Strip, RangeInclusive::new(10, 9)),
+                (10, Chomping::Keep, 11..=11),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_yaml_path_matches() {
+        let path = PathBuf::from("test.yaml");
+        let content = "";
+        let mut context = LintContext::new(&path, content);
+
+        context.yaml_path = vec![
+            "spec".to_string(),
+            "containers".to_string(),
+            "0".to_string(),
+        ];
+
+        assert!(context.yaml_path_matches("spec.containers.0"));
+        assert!(context.yaml_path_matches("spec.containers.*"));
+        assert!(context.yaml_path_matches("spec.*.0"));
+        assert!(context.yaml_path_matches("*.*.*"));
+
+        assert!(!context.yaml_path_matches("spec.containers"));
+        assert!(!context.yaml_path_matches("spec.containers.0.name"));
+        assert!(!context.yaml_path_matches("metadata.name.test"));
+    }
+
+    #[test]
+    fn test_key_paths() {
+        let path = PathBuf::from("test.yaml");
+        let content = "spec:\n  containers:\n    - name: app  # main\n      env:\n        - name: A\n    - name: sidecar\n  script: |\n    key: not a key\n\"quoted\": 1\n---\nnext: {inline: 1}\n";
+        let context = LintContext::new(&path, content);
+
+        let keys: Vec<(usize, usize, String)> = context
+            .key_paths()
+            .iter()
+            .map(|key| (key.line, key.column, key.dotted()))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                (1, 1, "spec".to_string()),
+                (2, 3, "spec.containers".to_string()),
+                (3, 7, "spec.containers.0.name".to_string()),
+                (4, 7, "spec.containers.0.env".to_string()),
+                (5, 11, "spec.containers.0.env.0.name".to_string()),
+                (6, 7, "spec.containers.1.name".to_string()),
+                (7, 3, "spec.script".to_string()),
+                (9, 1, "quoted".to_string()),
+                (11, 1, "next".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sequence_items() {
+        let path = PathBuf::from("test.yaml");
+        let content = "hosts:\n  - a.example.com\n  - \"b.example.com\"\n  - name: c\n  - - nested\nports:\n- 80\n- 443\n";
+        let context = LintContext::new(&path, content);
+
+        let items: Vec<(usize, usize, String, usize, String)> = context
+            .sequence_items()
+            .iter()
+            .map(|item| {
+                (
+                    item.line,
+                    item.column,
+                    item.path.join("."),
+                    item.index,
+                    item.value.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            items,
+            vec![
+                (2, 5, "hosts".to_string(), 0, "a.example.com".to_string()),
+                (3, 5, "hosts".to_string(), 1, "b.example.com".to_string()),
+                (5, 7, "hosts.3".to_string(), 0, "nested".to_string()),
+                (7, 3, "ports".to_string(), 0, "80".to_string()),
+                (8, 3, "ports".to_string(), 1, "443".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_yaml_path_string() {
+        let path = PathBuf::from("test.yaml");
+        let content = "";
+        let mut context = LintContext::new(&path, content);
+
+        context.yaml_path = vec![
+            "spec".to_string(),
+            "containers".to_string(),
+            "0".to_string(),
+        ];
+        assert_eq!(context.yaml_path_string(), "spec.containers.0");
+
+        context.yaml_path.clear();
+        assert_eq!(context.yaml_path_string(), "");
+    }
+}
@@ -0,0 +1,307 @@
+use super::Level;
+use crate::config::editorconfig::EditorConfigSettings;
+use crate::config::{Config, ConfigLayer, ConfigSource, IgnorePatterns, InlineConfigManager};
+use crate::rules::{ConfigValue, RuleConfig, RuleRegistry};
+use eyre::Result;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// A setting's value, with the source that last changed it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Setting<T> {
+    pub value: T,
+    pub source: ConfigSource,
+}
+
+/// The configuration that applies to one file, with where each setting comes from
+#[derive(Debug, Clone, Serialize)]
+pub struct EffectiveConfig {
+    pub path: PathBuf,
+    /// Sources applied to the file, in order; later sources override earlier ones
+    pub sources: Vec<ConfigSource>,
+    /// Why the file is not linted at all, if it is not
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped: Option<String>,
+    /// Top-level settings other than `rules`, by their configuration key
+    pub settings: BTreeMap<String, Setting<serde_yaml::Value>>,
+    /// Every rule's configuration, by rule id
+    pub rules: Vec<EffectiveRule>,
+}
+
+/// A rule's configuration for one file
+#[derive(Debug, Clone, Serialize)]
+pub struct EffectiveRule {
+    pub id: String,
+    pub enabled: Setting<bool>,
+    pub level: Setting<Level>,
+    pub params: BTreeMap<String, Setting<ConfigValue>>,
+    /// Whether one of the rule's `ignore` patterns excludes the file
+    pub ignored: bool,
+}
+
+impl EffectiveConfig {
+    /// Attribute each setting of the last layer to the layer that last changed it. The
+    /// rules' `.editorconfig` defaults and the file's own directives (from `content`, if
+    /// the file exists) are applied on top, as when the file is linted.
+    pub fn from_layers(
+        file_path: &Path,
+        layers: &[ConfigLayer],
+        registry: &RuleRegistry,
+        editorconfig: Option<&EditorConfigSettings>,
+        content: Option<&str>,
+    ) -> Result<Self> {
+        let default = ConfigLayer::new(ConfigSource::Default, Config::default());
+        let config = &layers.last().unwrap_or(&default).config;
+
+        let directives = match content {
+            Some(content) => {
+                let mut directives = InlineConfigManager::new();
+                directives.process_file(content)?;
+                Some(directives)
+            }
+            None => None,
+        };
+
+        let skipped = if config.is_file_ignored(file_path) {
+            Some("matched by `ignore`".to_string())
+        } else if !config.is_yaml_file(file_path) && config.extractor_for(file_path).is_none() {
+            Some("not matched by `yaml-files`".to_string())
+        } else if directives.as_ref().is_some_and(|d| d.is_file_ignored()) {
+            Some("yl:ignore-file directive".to_string())
+        } else if content
+            .is_some_and(|content| config.skip_generated && config.is_generated(content))
+        {
+            Some("generated file (`skip-generated`)".to_string())
+        } else if content
+            .is_some_and(|content| config.allow_empty_files() && content.trim().is_empty())
+        {
+            Some("empty file (`allow-empty-files`)".to_string())
+        } else {
+            None
+        };
+
+        let mut rules: Vec<EffectiveRule> = registry
+            .rules()
+            .iter()
+            .map(|rule| {
+                let mut states = vec![(ConfigSource::Default, rule.default_config())];
+                for layer in layers {
+                    states.push((
+                        layer.source.clone(),
+                        layer.config.get_rule_config(rule.id(), registry),
+                    ));
+                }
+
+                let mut rule_config = states[states.len() - 1].1.clone();
+                if let Some(editorconfig) = editorconfig {
+                    editorconfig.apply(rule.id(), &mut rule_config);
+                    states.push((ConfigSource::EditorConfig, rule_config.clone()));
+                }
+                if let Some(directives) = &directives {
+                    for directive_config in [
+                        directives.get_file_rule_config(rule.id()),
+                        directives.get_rule_config(rule.id(), 0),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    {
+                        for (key, value) in &directive_config.params {
+                            rule_config.set_param(key.clone(), value.clone());
+                        }
+                    }
+                    states.push((ConfigSource::Directive, rule_config.clone()));
+                }
+
+                EffectiveRule::from_states(rule.id(), &states, file_path)
+            })
+            .collect();
+        rules.sort_by(|a, b| a.id.cmp(&b.id));
+
+        Ok(Self {
+            path: file_path.to_path_buf(),
+            sources: layers.iter().map(|layer| layer.source.clone()).collect(),
+            skipped,
+            settings: Self::settings(&default, layers)?,
+            rules,
+        })
+    }
+
+    /// Attribute the top-level settings, compared as they are written in configuration files
+    fn settings(
+        default: &ConfigLayer,
+        layers: &[ConfigLayer],
+    ) -> Result<BTreeMap<String, Setting<serde_yaml::Value>>> {
+        let mut states = Vec::new();
+        for layer in std::iter::once(default).chain(layers) {
+            let serde_yaml::Value::Mapping(mut mapping) = serde_yaml::to_value(&layer.config)?
+            else {
+                continue;
+            };
+            for key in ["rules", "extends", "profiles"] {
+                mapping.remove(key);
+            }
+            states.push((&layer.source, mapping));
+        }
+
+        let keys: BTreeSet<String> = states
+            .iter()
+            .flat_map(|(_, mapping)| mapping.keys())
+            .filter_map(|key| key.as_str().map(str::to_string))
+            .collect();
+
+        let mut settings = BTreeMap::new();
+        for key in keys {
+            let values: Vec<_> = states
+                .iter()
+                .map(|(source, mapping)| (*source, mapping.get(key.as_str()).cloned()))
+                .collect();
+            if let Some(setting) = last_change(&values) {
+                settings.insert(key, setting);
+            }
+        }
+        Ok(settings)
+    }
+}
+
+impl EffectiveRule {
+    /// Attribute a rule's settings, from its configuration after each source
+    fn from_states(id: &str, states: &[(ConfigSource, RuleConfig)], file_path: &Path) -> Self {
+        let last = &states[states.len() - 1].1;
+        let enabled: Vec<_> = states
+            .iter()
+            .map(|(source, config)| (source, Some(config.enabled)))
+            .collect();
+        let level: Vec<_> = states
+            .iter()
+            .map(|(source, config)| (source, Some(config.level.clone())))
+            .collect();
+
+        let params = last
+            .params
+            .keys()
+            .filter_map(|key| {
+                let values: Vec<_> = states
+                    .iter()
+                    .map(|(source, config)| (source, config.params.get(key).cloned()))
+                    .collect();
+                Some((key.clone(), last_change(&values)?))
+            })
+            .collect();
+
+        Self {
+            id: id.to_string(),
+            enabled: last_change(&enabled).expect("rules are always enabled or disabled"),
+            level: last_change(&level).expect("rules always have a level"),
+            params,
+            ignored: IgnorePatterns::new(&last.ignore).is_ignored(file_path),
+        }
+    }
+}
+
+/// The final value of a setting and the source that last changed it, from the setting's
+/// value after each source; `None` if the setting is not set in the end
+fn last_change<T: Clone + PartialEq>(values: &[(&ConfigSource, Option<T>)]) -> Option<Setting<T>> {
+    let (_, value) = values.last()?;
+    let value = value.clone()?;
+
+    let source = values
+        .windows(2)
+        .rev()
+        .find(|pair| pair[0].1 != pair[1].1)
+        .map_or(values[0].0, |pair| pair[1].0);
+
+    Some(Setting {
+        value,
+        source: source.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule<'a>(effective: &'a EffectiveConfig, id: &str) -> &'a EffectiveRule {
+        effective.rules.iter().find(|rule| rule.id == id).unwrap()
+    }
+
+    #[test]
+    fn test_sources_of_settings() {
+        let registry = RuleRegistry::with_default_rules();
+        let root = Config::default();
+        let mut nested = root.clone();
+        nested.rule_timeout = Some(100);
+        nested
+            .rules
+            .get_mut("line-length")
+            .unwrap()
+            .set_param("max", 120i64);
+        let mut cli = nested.clone();
+        cli.rules.get_mut("trailing-spaces").unwrap().enabled = false;
+
+        let nested_file = PathBuf::from("charts/.yl.yaml");
+        let layers = vec![
+            ConfigLayer::new(ConfigSource::Default, root),
+            ConfigLayer::new(ConfigSource::File(nested_file.clone()), nested),
+            ConfigLayer::new(ConfigSource::CommandLine, cli),
+        ];
+        let content = "# yl:configure line-length allow-non-breakable-words=false\nkey: value\n";
+
+        let effective = EffectiveConfig::from_layers(
+            Path::new("charts/values.yaml"),
+            &layers,
+            &registry,
+            None,
+            Some(content),
+        )
+        .unwrap();
+
+        assert_eq!(effective.skipped, None);
+        let line_length = rule(&effective, "line-length");
+        assert_eq!(line_length.enabled.source, ConfigSource::Default);
+        assert_eq!(
+            line_length.params["max"],
+            Setting {
+                value: ConfigValue::Int(120),
+                source: ConfigSource::File(nested_file.clone())
+            }
+        );
+        assert_eq!(
+            line_length.params["allow-non-breakable-words"].source,
+            ConfigSource::Directive
+        );
+
+        let trailing_spaces = rule(&effective, "trailing-spaces");
+        assert!(!trailing_spaces.enabled.value);
+        assert_eq!(trailing_spaces.enabled.source, ConfigSource::CommandLine);
+
+        assert_eq!(
+            effective.settings["rule-timeout"],
+            Setting {
+                value: serde_yaml::Value::from(100),
+                source: ConfigSource::File(nested_file)
+            }
+        );
+        assert_eq!(
+            effective.settings["template-mode"].source,
+            ConfigSource::Default
+        );
+    }
+
+    #[test]
+    fn test_skipped_file() {
+        let registry = RuleRegistry::with_default_rules();
+        let layers = vec![ConfigLayer::new(ConfigSource::Default, Config::default())];
+        let skipped = |path: &str, content| {
+            EffectiveConfig::from_layers(Path::new(path), &layers, &registry, None, content)
+                .unwrap()
+                .skipped
+        };
+
+        assert_eq!(skipped("values.yaml", None), None);
+        assert!(skipped("node_modules/a/values.yaml", None).is_some());
+        assert!(skipped("README.md", None).is_some());
+        assert!(skipped("values.yaml", Some("# yl:ignore-file\nkey: value\n")).is_some());
+        assert_eq!(skipped("values.yaml", Some("\n")), None);
+    }
+}
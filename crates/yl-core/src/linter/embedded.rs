@@ -0,0 +1,320 @@
+//! YAML embedded in other kinds of files, e.g. docker-compose examples in documentation
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// Opening tag of an HTML script element holding YAML
+static SCRIPT_OPEN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)<script\b[^>]*\btype\s*=\s*["']?(?:text|application)/(?:x-)?ya?ml\b[^>]*>"#)
+        .expect("valid regex")
+});
+
+/// Start of a here-document: `<<`, `<<-` or a quoted delimiter, but not a `<<<` here-string
+static HEREDOC_START: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?:^|[^<])<<(-?)\s*['"]?([A-Za-z_][A-Za-z0-9_]*)['"]?"#).expect("valid regex")
+});
+
+/// A YAML file named on a command line, e.g. `cat > compose.yaml <<EOF`
+static YAML_FILE_NAME: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\.ya?ml\b").expect("valid regex"));
+
+/// Kind of file YAML is extracted from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Extractor {
+    /// Fenced code blocks tagged `yaml` or `yml` in Markdown files
+    Markdown,
+    /// `<script type="text/yaml">` elements in HTML files, with the tags on lines of their own
+    Html,
+    /// Here-documents in shell scripts whose delimiter names YAML (`<<YAML`, `<<'EOF_YML'`)
+    /// or whose command names a `.yaml` or `.yml` file
+    Heredoc,
+}
+
+impl Extractor {
+    /// Every extractor, as enabled by `--embedded`
+    pub const ALL: [Extractor; 3] = [Extractor::Markdown, Extractor::Html, Extractor::Heredoc];
+
+    /// File extensions of the files the extractor reads
+    fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            Extractor::Markdown => &["md", "markdown"],
+            Extractor::Html => &["html", "htm"],
+            Extractor::Heredoc => &["sh", "bash"],
+        }
+    }
+
+    /// Whether the extractor reads a file, by its extension
+    pub fn matches(&self, file_path: &Path) -> bool {
+        file_path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| {
+                self.extensions()
+                    .iter()
+                    .any(|known| extension.eq_ignore_ascii_case(known))
+            })
+    }
+
+    /// The YAML blocks of a file, in order
+    pub fn extract(&self, content: &str) -> Vec<EmbeddedYaml> {
+        let lines: Vec<&str> = content.lines().collect();
+        match self {
+            Extractor::Markdown => markdown_blocks(&lines),
+            Extractor::Html => html_blocks(&lines),
+            Extractor::Heredoc => heredoc_blocks(&lines),
+        }
+    }
+}
+
+/// A block of YAML found in another file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmbeddedYaml {
+    /// Line of the file the block starts on (1-based)
+    pub line: usize,
+    /// Characters of indentation removed from every line of the block
+    pub indent: usize,
+    /// The YAML, without the indentation
+    pub content: String,
+}
+
+impl EmbeddedYaml {
+    /// The block made of `lines`, the first of which is line `start` (0-based) of the
+    /// file. Indentation shared by all lines is removed, so that `line` and `indent`
+    /// map positions in the block back to the file.
+    fn new(lines: &[&str], start: usize) -> Self {
+        let leading = |line: &str| line.chars().take_while(|c| *c == ' ' || *c == '\t').count();
+        let indent = lines
+            .iter()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| leading(line))
+            .min()
+            .unwrap_or(0);
+
+        let mut content = String::new();
+        for line in lines {
+            let skipped = leading(line).min(indent);
+            content.extend(line.chars().skip(skipped));
+            content.push('\n');
+        }
+
+        Self {
+            line: start + 1,
+            indent,
+            content,
+        }
+    }
+}
+
+/// Fenced code blocks whose info string starts with `yaml` or `yml`. An unclosed fence
+/// runs to the end of the file, as in CommonMark.
+fn markdown_blocks(lines: &[&str]) -> Vec<EmbeddedYaml> {
+    let mut blocks = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let Some((fence, info)) = fence_open(lines[i]) else {
+            i += 1;
+            continue;
+        };
+
+        let start = i + 1;
+        let end = (start..lines.len())
+            .find(|&j| is_fence_close(lines[j], &fence))
+            .unwrap_or(lines.len());
+
+        let language = info
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .trim_matches(|c| c == '{' || c == '}' || c == '.');
+        if language.eq_ignore_ascii_case("yaml") || language.eq_ignore_ascii_case("yml") {
+            blocks.push(EmbeddedYaml::new(&lines[start..end], start));
+        }
+
+        i = end + 1;
+    }
+
+    blocks
+}
+
+/// The fence (e.g. "```") and info string of a line opening a fenced code block
+fn fence_open(line: &str) -> Option<(String, &str)> {
+    let trimmed = line.trim_start_matches(' ');
+    let marker = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let length = trimmed.chars().take_while(|c| *c == marker).count();
+    if length < 3 {
+        return None;
+    }
+
+    let info = &trimmed[length..];
+    // Backtick fences cannot have backticks in their info string
+    if marker == '`' && info.contains('`') {
+        return None;
+    }
+    Some((trimmed[..length].to_string(), info.trim()))
+}
+
+/// Whether a line closes a fenced code block opened with `fence`
+fn is_fence_close(line: &str, fence: &str) -> bool {
+    let trimmed = line.trim();
+    let marker = fence.chars().next().unwrap_or('`');
+    trimmed.len() >= fence.len() && trimmed.chars().all(|c| c == marker)
+}
+
+/// Contents of `<script type="text/yaml">` elements, between the lines of their tags
+fn html_blocks(lines: &[&str]) -> Vec<EmbeddedYaml> {
+    let mut blocks = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let Some(open) = SCRIPT_OPEN.find(lines[i]) else {
+            i += 1;
+            continue;
+        };
+        // Single-line elements are left alone
+        if lines[i][open.end()..]
+            .to_ascii_lowercase()
+            .contains("</script")
+        {
+            i += 1;
+            continue;
+        }
+
+        let start = i + 1;
+        let end = (start..lines.len())
+            .find(|&j| lines[j].to_ascii_lowercase().contains("</script"))
+            .unwrap_or(lines.len());
+
+        let mut block = lines[start..end].to_vec();
+        if let Some(last) = lines.get(end) {
+            let before = &last[..last.to_ascii_lowercase().find("</script").unwrap_or(0)];
+            if !before.trim().is_empty() {
+                block.push(before);
+            }
+        }
+        blocks.push(EmbeddedYaml::new(&block, start));
+
+        i = end + 1;
+    }
+
+    blocks
+}
+
+/// Here-documents that hold YAML, judged by their delimiter or the command they feed
+fn heredoc_blocks(lines: &[&str]) -> Vec<EmbeddedYaml> {
+    let mut blocks = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let Some(captures) = HEREDOC_START.captures(lines[i]) else {
+            i += 1;
+            continue;
+        };
+        let strip_tabs = !captures[1].is_empty();
+        let delimiter = &captures[2];
+
+        let start = i + 1;
+        let end = (start..lines.len())
+            .find(|&j| {
+                let line = if strip_tabs {
+                    lines[j].trim_start_matches('\t')
+                } else {
+                    lines[j]
+                };
+                line.trim_end() == delimiter
+            })
+            .unwrap_or(lines.len());
+
+        let upper = delimiter.to_ascii_uppercase();
+        if upper.contains("YAML") || upper.contains("YML") || YAML_FILE_NAME.is_match(lines[i]) {
+            blocks.push(EmbeddedYaml::new(&lines[start..end], start));
+        }
+
+        i = end + 1;
+    }
+
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_by_extension() {
+        assert!(Extractor::Markdown.matches(Path::new("docs/README.md")));
+        assert!(Extractor::Html.matches(Path::new("site/INDEX.HTML")));
+        assert!(Extractor::Heredoc.matches(Path::new("deploy.sh")));
+        assert!(!Extractor::Markdown.matches(Path::new("values.yaml")));
+        assert!(!Extractor::Heredoc.matches(Path::new("Makefile")));
+    }
+
+    #[test]
+    fn test_markdown_blocks() {
+        let content = "# Example\n\n```yaml\nservices:\n  web: {}\n```\n\n```bash\nls\n```\n\n- item\n\n  ~~~yml title=\"compose\"\n  key: value\n  ~~~\n";
+        let blocks = Extractor::Markdown.extract(content);
+
+        assert_eq!(
+            blocks,
+            vec![
+                EmbeddedYaml {
+                    line: 4,
+                    indent: 0,
+                    content: "services:\n  web: {}\n".to_string(),
+                },
+                EmbeddedYaml {
+                    line: 15,
+                    indent: 2,
+                    content: "key: value\n".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_markdown_unclosed_fence() {
+        let blocks = Extractor::Markdown.extract("```yaml\nkey: value\n");
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].content, "key: value\n");
+    }
+
+    #[test]
+    fn test_html_blocks() {
+        let content = "<html>\n  <script type=\"text/yaml\" id=\"config\">\n    key: value\n    list:\n      - a\n  </script>\n  <script type=\"text/javascript\">\n    let a = 1;\n  </script>\n</html>\n";
+        let blocks = Extractor::Html.extract(content);
+
+        assert_eq!(
+            blocks,
+            vec![EmbeddedYaml {
+                line: 3,
+                indent: 4,
+                content: "key: value\nlist:\n  - a\n".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_heredoc_blocks() {
+        let content = "#!/bin/sh\ncat <<EOF | kubectl apply -f -\nnot: yaml by name\nEOF\ncat > compose.yaml <<'EOF'\nservices: {}\nEOF\nif true; then\n\tcat <<-YAML\n\tkey: value\n\tYAML\nfi\ncat <<<\"$YAML\"\n";
+        let blocks = Extractor::Heredoc.extract(content);
+
+        assert_eq!(
+            blocks,
+            vec![
+                EmbeddedYaml {
+                    line: 6,
+                    indent: 0,
+                    content: "services: {}\n".to_string(),
+                },
+                EmbeddedYaml {
+                    line: 10,
+                    indent: 1,
+                    content: "key: value\n".to_string(),
+                },
+            ]
+        );
+    }
+}
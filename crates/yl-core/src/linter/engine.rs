@@ -0,0 +1,1571 @@
+use super::runner::{RuleOutcome, RuleRunner};
+use super::template::mask_templates;
+use super::{
+    EffectiveConfig, Extractor, LintObserver, Problem, ProblemLimit, RuleProfiler, RuleTiming,
+    walk_files,
+};
+use crate::config::{
+    Config, ConfigLayer, ConfigResolver, IgnorePatterns, InlineConfigManager, Projects,
+};
+use crate::fixes::FixEngine;
+use crate::read::{FileContent, read_file};
+use crate::rules::{RulePhase, RuleRegistry};
+use crate::write::write_atomic;
+use eyre::Result;
+use rayon::prelude::*;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, trace, warn};
+
+/// Upper bound on fix-then-lint rounds, in case fixes keep producing new problems
+const MAX_FIX_PASSES: usize = 10;
+
+/// Content after applying auto-fixes, with the problems that could not be fixed
+#[derive(Debug, Clone)]
+pub struct FixedContent {
+    pub content: String,
+    pub problems: Vec<Problem>,
+}
+
+/// Main linting engine that coordinates rule execution
+pub struct Linter {
+    registry: RuleRegistry,
+    resolver: ConfigResolver,
+    /// Per-rule timings, collected only when profiling is enabled
+    profiler: Option<RuleProfiler>,
+    /// Stop linting once this many problems have been found across all files
+    limit: Option<ProblemLimit>,
+    /// Files skipped because their header marks them as generated
+    skipped_generated: Mutex<BTreeSet<PathBuf>>,
+    /// Follow symbolic links when walking directories
+    follow_symlinks: bool,
+    /// Dedicated worker threads; the global rayon pool is used when `None`
+    pool: Option<rayon::ThreadPool>,
+    /// Suffix of the backups kept of files before fixes are written to them
+    backup_suffix: Option<String>,
+    /// Hooks notified as files are linted
+    observers: Vec<Arc<dyn LintObserver>>,
+    /// Projects of the root configuration, which problems of multi-file runs are tagged with
+    projects: Projects,
+    /// Return problems suppressed by directives, flagged, instead of dropping them
+    keep_suppressed: bool,
+    /// Size from which files linted without fixing are memory-mapped, or never if `None`
+    mmap_threshold: Option<u64>,
+}
+
+impl Linter {
+    /// Create a new linter with the given root configuration
+    pub fn new(config: Config) -> Self {
+        Self {
+            registry: RuleRegistry::with_default_rules(),
+            projects: Projects::new(&config.projects),
+            resolver: ConfigResolver::new(config),
+            profiler: None,
+            limit: None,
+            skipped_generated: Mutex::new(BTreeSet::new()),
+            follow_symlinks: false,
+            pool: None,
+            backup_suffix: None,
+            observers: Vec::new(),
+            keep_suppressed: false,
+            mmap_threshold: None,
+        }
+    }
+
+    /// Enable or disable lookup of nested `.yl.yaml` files per directory
+    pub fn with_config_discovery(mut self, enabled: bool) -> Self {
+        self.resolver = self.resolver.with_discovery(enabled);
+        self
+    }
+
+    /// Enable or disable expansion of environment variables in nested `.yl.yaml` files
+    pub fn with_config_env_expand(mut self, enabled: bool) -> Self {
+        self.resolver = self.resolver.with_env_expand(enabled);
+        self
+    }
+
+    /// Look up nested `.yl.yaml` files below `dir` rather than the working directory
+    pub fn with_config_root(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.resolver = self.resolver.with_root(dir);
+        self
+    }
+
+    /// Apply adjustments on top of every resolved per-directory configuration
+    pub fn with_config_overrides<F>(mut self, overrides: F) -> Self
+    where
+        F: Fn(&mut Config) + Send + Sync + 'static,
+    {
+        self.resolver = self.resolver.with_overrides(overrides);
+        self
+    }
+
+    /// Record how long each rule takes and how many problems it finds
+    pub fn with_rule_profiling(mut self, enabled: bool) -> Self {
+        self.profiler = enabled.then(RuleProfiler::new);
+        self
+    }
+
+    /// Stop linting once `max` problems have been found, or never if `None`
+    pub fn with_max_problems(mut self, max: Option<usize>) -> Self {
+        self.limit = max.map(ProblemLimit::new);
+        self
+    }
+
+    /// Follow symbolic links when expanding directories into files
+    pub fn with_follow_symlinks(mut self, enabled: bool) -> Self {
+        self.follow_symlinks = enabled;
+        self
+    }
+
+    /// Keep each file as `<file><suffix>` before writing fixes to it
+    pub fn with_backup_suffix(mut self, suffix: Option<String>) -> Self {
+        self.backup_suffix = suffix;
+        self
+    }
+
+    /// Return the problems that directives suppress along with the others, with
+    /// `suppressed` set, so that they can be counted or listed
+    pub fn with_suppressed_problems(mut self, enabled: bool) -> Self {
+        self.keep_suppressed = enabled;
+        self
+    }
+
+    /// Memory-map files of at least `threshold` bytes when linting them, instead of
+    /// reading them into memory, or never if `None` (the default). Files being fixed are
+    /// always read. Nothing may change or truncate a mapped file while it is linted.
+    pub fn with_mmap_threshold(mut self, threshold: Option<u64>) -> Self {
+        self.mmap_threshold = threshold;
+        self
+    }
+
+    /// Notify `observer` of every file and problem of multi-file runs. The linter
+    /// shares the observer, so the caller can keep a handle to read what it collected.
+    pub fn with_observer(mut self, observer: Arc<dyn LintObserver>) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    /// Lint files on `jobs` worker threads, or on the global rayon pool (one thread
+    /// per CPU) if `None` or 0
+    pub fn with_jobs(mut self, jobs: Option<usize>) -> Self {
+        self.pool = jobs.filter(|&jobs| jobs > 0).and_then(|jobs| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(jobs)
+                .build()
+                .inspect_err(
+                    |e| warn!(error = %e, "failed to start worker threads, using defaults"),
+                )
+                .ok()
+        });
+        self
+    }
+
+    /// Whether linting stopped early because the problem limit was reached
+    pub fn problem_limit_reached(&self) -> bool {
+        self.limit.as_ref().is_some_and(ProblemLimit::is_reached)
+    }
+
+    /// Files skipped so far because of `skip-generated`, in path order
+    pub fn skipped_generated(&self) -> Vec<PathBuf> {
+        self.skipped_generated
+            .lock()
+            .expect("skipped files lock poisoned")
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Per-rule timings collected so far, if profiling is enabled
+    pub fn rule_timings(&self) -> Option<Vec<RuleTiming>> {
+        self.profiler.as_ref().map(RuleProfiler::timings)
+    }
+
+    /// Get the effective configuration for a file
+    pub fn config_for<P: AsRef<Path>>(&self, file_path: P) -> Result<Arc<Config>> {
+        self.resolver.resolve(file_path.as_ref())
+    }
+
+    /// The configuration that applies to a file, with the source of each setting.
+    /// `root` are the layers of the root configuration, see [`ConfigResolver::layers`].
+    pub fn effective_config<P: AsRef<Path>>(
+        &self,
+        file_path: P,
+        root: Vec<ConfigLayer>,
+    ) -> Result<EffectiveConfig> {
+        let file_path = file_path.as_ref();
+        let layers = self.resolver.layers(file_path, root)?;
+        let config = self.config_for(file_path)?;
+
+        let editorconfig = config
+            .editorconfig
+            .then(|| self.resolver.editorconfig(file_path));
+        // Directives are read like when linting; a file that does not exist has none
+        let content = std::fs::read_to_string(file_path)
+            .ok()
+            .map(|content| mask_templates(&content, config.template_mode).into_owned());
+
+        EffectiveConfig::from_layers(
+            file_path,
+            &layers,
+            &self.registry,
+            editorconfig.as_ref(),
+            content.as_deref(),
+        )
+    }
+
+    /// Name of the configured project containing a file, if any
+    pub fn project_for<P: AsRef<Path>>(&self, file_path: P) -> Option<&str> {
+        self.projects.project_for(file_path.as_ref())
+    }
+
+    /// Forget resolved per-directory configurations so changed config files are re-read
+    pub fn clear_config_cache(&self) {
+        self.resolver.clear_cache();
+    }
+
+    /// Lint a single file
+    pub fn lint_file<P: AsRef<Path>>(&self, file_path: P) -> Result<Vec<Problem>> {
+        let file_path = file_path.as_ref();
+
+        if let Some((content, extractor)) = self.read_embedded(file_path, self.mmap_threshold)? {
+            return self.lint_embedded(file_path, text(file_path, &content)?, extractor);
+        }
+
+        match self.read_lintable(file_path, self.mmap_threshold)? {
+            Some(content) => self.lint_content(file_path, text(file_path, &content)?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Lint the YAML embedded in the content of a file, reporting each problem at its
+    /// position in the file
+    pub fn lint_embedded<P: AsRef<Path>>(
+        &self,
+        file_path: P,
+        content: &str,
+        extractor: Extractor,
+    ) -> Result<Vec<Problem>> {
+        let file_path = file_path.as_ref();
+        let mut problems = Vec::new();
+
+        for block in extractor.extract(content) {
+            for mut problem in self.lint_content(file_path, &block.content)? {
+                problem.line += block.line - 1;
+                problem.column += block.indent;
+                problems.push(problem);
+            }
+        }
+
+        problems.sort();
+        Ok(problems)
+    }
+
+    /// Apply auto-fixes to a single file, writing it back if anything changed,
+    /// and return the problems that remain
+    pub fn fix_file<P: AsRef<Path>>(
+        &self,
+        file_path: P,
+        fixes: &FixEngine,
+    ) -> Result<Vec<Problem>> {
+        let file_path = file_path.as_ref();
+        // Embedded YAML is reported, but never rewritten in its host file
+        if let Some((content, extractor)) = self.read_embedded(file_path, None)? {
+            return self.lint_embedded(file_path, text(file_path, &content)?, extractor);
+        }
+        let Some(content) = self.read_lintable(file_path, None)? else {
+            return Ok(Vec::new());
+        };
+        let content = text(file_path, &content)?;
+
+        let fixed = self.fix_content(file_path, content, fixes)?;
+        if fixed.content != content {
+            write_atomic(file_path, &fixed.content, self.backup_suffix.as_deref())
+                .map_err(|e| eyre::eyre!("Failed to write file {}: {}", file_path.display(), e))?;
+            debug!(file = %file_path.display(), "applied fixes");
+        }
+
+        Ok(fixed.problems)
+    }
+
+    /// Apply auto-fixes to content in memory, re-linting after each round until
+    /// no more fixes apply
+    pub fn fix_content<P: AsRef<Path>>(
+        &self,
+        file_path: P,
+        content: &str,
+        fixes: &FixEngine,
+    ) -> Result<FixedContent> {
+        let file_path = file_path.as_ref();
+        let config = self.config_for(file_path)?;
+
+        let mut content = content.to_string();
+        let mut problems = self.lint_content(file_path, &content)?;
+
+        for _ in 0..MAX_FIX_PASSES {
+            // Problems suppressed by directives are left alone
+            let reported: Vec<Problem> = problems
+                .iter()
+                .filter(|problem| !problem.suppressed)
+                .cloned()
+                .collect();
+            let fixed = fixes.fix_problems(&content, &reported, &config)?;
+            if fixed == content {
+                break;
+            }
+
+            content = fixed;
+            problems = self.lint_content(file_path, &content)?;
+        }
+
+        Ok(FixedContent { content, problems })
+    }
+
+    /// Read a file unless the configuration excludes it from linting, mapping it if it
+    /// is at least `mmap_threshold` bytes long
+    fn read_lintable(
+        &self,
+        file_path: &Path,
+        mmap_threshold: Option<u64>,
+    ) -> Result<Option<FileContent>> {
+        let config = self.config_for(file_path)?;
+
+        // Check if file should be ignored
+        if config.is_file_ignored(file_path) {
+            debug!(file = %file_path.display(), "skipping file matched by ignore patterns");
+            return Ok(None);
+        }
+
+        // Check if file is a YAML file
+        if !config.is_yaml_file(file_path) {
+            debug!(file = %file_path.display(), "skipping file not matched by yaml-files");
+            return Ok(None);
+        }
+
+        // Read file content
+        let content = read_file(file_path, mmap_threshold)
+            .map_err(|e| eyre::eyre!("Failed to read file {}: {}", file_path.display(), e))?;
+
+        Ok(Some(content))
+    }
+
+    /// Read a file that is not YAML itself, with the enabled extractor for its embedded YAML
+    fn read_embedded(
+        &self,
+        file_path: &Path,
+        mmap_threshold: Option<u64>,
+    ) -> Result<Option<(FileContent, Extractor)>> {
+        let config = self.config_for(file_path)?;
+        let Some(extractor) = config.extractor_for(file_path) else {
+            return Ok(None);
+        };
+        if config.is_file_ignored(file_path) || config.is_yaml_file(file_path) {
+            return Ok(None);
+        }
+
+        let content = read_file(file_path, mmap_threshold)
+            .map_err(|e| eyre::eyre!("Failed to read file {}: {}", file_path.display(), e))?;
+
+        Ok(Some((content, extractor)))
+    }
+
+    /// Lint content with a given file path context
+    pub fn lint_content<P: AsRef<Path>>(
+        &self,
+        file_path: P,
+        content: &str,
+    ) -> Result<Vec<Problem>> {
+        let never_cancelled = AtomicBool::new(false);
+        let problems = self.lint_content_cancellable(file_path, content, &never_cancelled)?;
+        Ok(problems.unwrap_or_default())
+    }
+
+    /// Lint content, giving up between rules once `cancelled` is set.
+    /// Returns `None` if the lint was cancelled before it completed.
+    pub fn lint_content_cancellable<P: AsRef<Path>>(
+        &self,
+        file_path: P,
+        content: &str,
+        cancelled: &AtomicBool,
+    ) -> Result<Option<Vec<Problem>>> {
+        let file_path = file_path.as_ref();
+        let config = self.config_for(file_path)?;
+        let started = Instant::now();
+
+        if config.skip_generated && config.is_generated(content) {
+            info!(file = %file_path.display(), "skipping generated file");
+            self.skipped_generated
+                .lock()
+                .expect("skipped files lock poisoned")
+                .insert(file_path.to_path_buf());
+            return Ok(Some(Vec::new()));
+        }
+
+        if config.allow_empty_files() && content.trim().is_empty() {
+            debug!(file = %file_path.display(), "skipping empty file");
+            return Ok(Some(Vec::new()));
+        }
+
+        // Mask template expressions so they parse as plain YAML
+        let masked = mask_templates(content, config.template_mode);
+        let content = masked.as_ref();
+
+        let mut runner = RuleRunner::new(
+            file_path,
+            content,
+            config.rule_timeout.map(Duration::from_millis),
+        );
+        let mut all_problems = Vec::new();
+        // Problems not suppressed by directives, which count towards the problem limit
+        let mut reported = 0;
+
+        // Process inline directives
+        let mut inline_config = InlineConfigManager::new();
+        inline_config.process_file(content)?;
+
+        // Check if entire file should be ignored
+        if inline_config.is_file_ignored() {
+            debug!(file = %file_path.display(), "skipping file ignored by directive");
+            return Ok(Some(Vec::new()));
+        }
+
+        let editorconfig = config
+            .editorconfig
+            .then(|| self.resolver.editorconfig(file_path));
+
+        // Whether a parse rule found the content to be invalid YAML
+        let mut parse_failed = false;
+
+        // Run all enabled rules, phase by phase
+        for rule in self.registry.shared_rules() {
+            if cancelled.load(Ordering::Relaxed) {
+                return Ok(None);
+            }
+
+            // Checks of keys and values would only repeat the parse error in other words;
+            // with `stop-on-syntax-error` the checks of the text are left out as well
+            if parse_failed
+                && (config.stop_on_syntax_error() || rule.phase() == RulePhase::Semantic)
+            {
+                trace!(file = %file_path.display(), rule = rule.id(), "rule skipped after parse error");
+                continue;
+            }
+
+            let mut rule_config = config.get_rule_config(rule.id(), &self.registry);
+
+            // Fill in indentation and line length from .editorconfig
+            if let Some(editorconfig) = &editorconfig {
+                editorconfig.apply(rule.id(), &mut rule_config);
+            }
+
+            // Apply file-level overrides from the header
+            if let Some(file_rule_config) = inline_config.get_file_rule_config(rule.id()) {
+                for (key, value) in &file_rule_config.params {
+                    rule_config.set_param(key.clone(), value.clone());
+                }
+            }
+
+            // Apply inline configuration overrides
+            if let Some(inline_rule_config) = inline_config.get_rule_config(rule.id(), 0) {
+                // Merge inline config with base config
+                for (key, value) in &inline_rule_config.params {
+                    rule_config.set_param(key.clone(), value.clone());
+                }
+            }
+
+            if !rule_config.enabled {
+                trace!(file = %file_path.display(), rule = rule.id(), "rule disabled");
+                continue;
+            }
+
+            if !rule_config.ignore.is_empty()
+                && IgnorePatterns::new(&rule_config.ignore).is_ignored(file_path)
+            {
+                trace!(file = %file_path.display(), rule = rule.id(), "rule ignores file");
+                continue;
+            }
+
+            // Validate rule configuration
+            if let Err(e) = self
+                .registry
+                .resolve_params(rule.id(), &mut rule_config)
+                .and_then(|()| rule.validate_config(&rule_config))
+            {
+                return Err(eyre::eyre!(
+                    "Invalid configuration for rule '{}': {}",
+                    rule.id(),
+                    e
+                ));
+            }
+
+            // Run the rule
+            let started = Instant::now();
+            let result = match runner.check(&rule, &rule_config) {
+                RuleOutcome::Finished(result) => result,
+                RuleOutcome::TimedOut => {
+                    let timeout = Duration::from_millis(config.rule_timeout.unwrap_or_default());
+                    warn!(file = %file_path.display(), rule = rule.id(), "rule timed out");
+                    for observer in &self.observers {
+                        observer.on_rule_timeout(file_path, rule.id());
+                    }
+                    all_problems.push(Problem::rule_timeout(rule.id(), timeout));
+                    reported += 1;
+                    continue;
+                }
+            };
+            match result {
+                Ok(problems) => {
+                    let elapsed = started.elapsed();
+                    parse_failed |= rule.phase() == RulePhase::Parse && !problems.is_empty();
+                    for observer in &self.observers {
+                        observer.on_rule_end(file_path, rule.id(), elapsed);
+                    }
+
+                    // Filter problems based on inline configuration
+                    let (filtered_problems, suppressed): (Vec<Problem>, Vec<Problem>) = problems
+                        .into_iter()
+                        .partition(|p| !inline_config.is_rule_disabled(&p.rule, p.line));
+
+                    trace!(
+                        file = %file_path.display(),
+                        rule = rule.id(),
+                        problems = filtered_problems.len(),
+                        suppressed = suppressed.len(),
+                        "rule checked"
+                    );
+
+                    if let Some(profiler) = &self.profiler {
+                        profiler.record(rule.id(), elapsed, filtered_problems.len());
+                    }
+                    reported += filtered_problems.len();
+                    all_problems.extend(filtered_problems);
+                    if self.keep_suppressed {
+                        all_problems.extend(suppressed.into_iter().map(|mut problem| {
+                            problem.suppressed = true;
+                            problem
+                        }));
+                    }
+
+                    // The remaining rules cannot add anything that would be reported
+                    if self
+                        .limit
+                        .as_ref()
+                        .is_some_and(|limit| limit.is_reached_with(reported))
+                    {
+                        debug!(file = %file_path.display(), "problem limit reached");
+                        break;
+                    }
+                }
+                Err(e) => {
+                    return Err(eyre::eyre!(
+                        "Rule '{}' failed on file {}: {}",
+                        rule.id(),
+                        file_path.display(),
+                        e
+                    ));
+                }
+            }
+        }
+
+        if cancelled.load(Ordering::Relaxed) {
+            return Ok(None);
+        }
+
+        // Sort problems by line and column
+        all_problems.sort();
+
+        debug!(
+            file = %file_path.display(),
+            problems = all_problems.len(),
+            elapsed = ?started.elapsed(),
+            "linted file"
+        );
+        Ok(Some(all_problems))
+    }
+
+    /// Lint multiple files or directories
+    pub fn lint_paths<P: AsRef<Path>>(
+        &self,
+        paths: &[P],
+    ) -> Result<Vec<(std::path::PathBuf, Vec<Problem>)>> {
+        let file_paths = self.collect_files(paths)?;
+
+        // Process files in parallel
+        self.lint_files_parallel(&file_paths)
+    }
+
+    /// Expand files and directories into the YAML files that would be linted,
+    /// skipping files ignored by the configuration
+    pub fn collect_files<P: AsRef<Path>>(&self, paths: &[P]) -> Result<Vec<std::path::PathBuf>> {
+        let mut file_paths = Vec::new();
+
+        for path in paths {
+            let path = path.as_ref();
+
+            if path.is_file() {
+                file_paths.push(path.to_path_buf());
+            } else if path.is_dir() {
+                // Recursively find YAML files in directory
+                for file_path in walk_files(path, self.follow_symlinks) {
+                    let config = self.config_for(&file_path)?;
+
+                    // Skip if ignored or neither a YAML file nor one with embedded YAML
+                    if config.is_file_ignored(&file_path)
+                        || !(config.is_yaml_file(&file_path)
+                            || config.extractor_for(&file_path).is_some())
+                    {
+                        continue;
+                    }
+
+                    file_paths.push(file_path);
+                }
+            } else if path.exists() {
+                return Err(eyre::eyre!("Not a regular file: {}", path.display()));
+            } else {
+                return Err(eyre::eyre!("Path does not exist: {}", path.display()));
+            }
+        }
+
+        Ok(file_paths)
+    }
+
+    /// Lint multiple files in parallel.
+    ///
+    /// A file that cannot be read or linted does not stop the run; it is reported
+    /// with a single [`FILE_ERROR_RULE`](super::FILE_ERROR_RULE) problem instead.
+    /// Once the problem limit is reached, files not yet started are left out of the results.
+    pub fn lint_files_parallel(
+        &self,
+        file_paths: &[std::path::PathBuf],
+    ) -> Result<Vec<(std::path::PathBuf, Vec<Problem>)>> {
+        self.lint_files_streaming(file_paths, |_, _| {})
+    }
+
+    /// Lint multiple files in parallel like [`Linter::lint_files_parallel`], calling
+    /// `on_file` from the worker thread as soon as each file is finished
+    pub fn lint_files_streaming<F>(
+        &self,
+        file_paths: &[std::path::PathBuf],
+        on_file: F,
+    ) -> Result<Vec<(std::path::PathBuf, Vec<Problem>)>>
+    where
+        F: Fn(&Path, &[Problem]) + Sync,
+    {
+        Ok(self.for_each_file(
+            file_paths,
+            PathBuf::as_path,
+            |file_path| self.lint_file(file_path),
+            on_file,
+        ))
+    }
+
+    /// Lint in-memory documents in parallel, each under its (virtual) path.
+    ///
+    /// Configuration is resolved from the paths as for files on disk, but the documents
+    /// themselves are never read from or written to the filesystem. Failures and the
+    /// problem limit are handled like in [`Linter::lint_files_parallel`].
+    pub fn lint_virtual(
+        &self,
+        files: &[(PathBuf, String)],
+    ) -> Result<Vec<(PathBuf, Vec<Problem>)>> {
+        Ok(self.for_each_file(
+            files,
+            |(path, _)| path.as_path(),
+            |(path, content)| self.lint_content(path, content),
+            |_, _| {},
+        ))
+    }
+
+    /// Fix multiple files in parallel, returning the problems that remain in each.
+    /// Files that cannot be fixed are reported like in [`Linter::lint_files_parallel`].
+    pub fn fix_files_parallel(
+        &self,
+        file_paths: &[std::path::PathBuf],
+        fixes: &FixEngine,
+    ) -> Result<Vec<(std::path::PathBuf, Vec<Problem>)>> {
+        self.fix_files_streaming(file_paths, fixes, |_, _| {})
+    }
+
+    /// Fix multiple files in parallel, calling `on_file` with the remaining problems
+    /// as soon as each file is finished
+    pub fn fix_files_streaming<F>(
+        &self,
+        file_paths: &[std::path::PathBuf],
+        fixes: &FixEngine,
+        on_file: F,
+    ) -> Result<Vec<(std::path::PathBuf, Vec<Problem>)>>
+    where
+        F: Fn(&Path, &[Problem]) + Sync,
+    {
+        Ok(self.for_each_file(
+            file_paths,
+            PathBuf::as_path,
+            |file_path| self.fix_file(file_path, fixes),
+            on_file,
+        ))
+    }
+
+    /// Run `check` on every file in parallel, turning failures into file-error problems
+    /// and stopping at the problem limit, then hand each finished file to `on_file`
+    fn for_each_file<T, F, G>(
+        &self,
+        files: &[T],
+        path_of: fn(&T) -> &Path,
+        check: F,
+        on_file: G,
+    ) -> Vec<(PathBuf, Vec<Problem>)>
+    where
+        T: Sync,
+        F: Fn(&T) -> Result<Vec<Problem>> + Sync,
+        G: Fn(&Path, &[Problem]) + Sync,
+    {
+        // Only references to the callbacks move to the pool's threads
+        let (check, on_file) = (&check, &on_file);
+        match &self.pool {
+            Some(pool) => {
+                pool.install(|| self.for_each_file_in_pool(files, path_of, check, on_file))
+            }
+            None => self.for_each_file_in_pool(files, path_of, check, on_file),
+        }
+    }
+
+    /// [`Linter::for_each_file`] on the current rayon pool
+    fn for_each_file_in_pool<T, F, G>(
+        &self,
+        files: &[T],
+        path_of: fn(&T) -> &Path,
+        check: F,
+        on_file: G,
+    ) -> Vec<(PathBuf, Vec<Problem>)>
+    where
+        T: Sync,
+        F: Fn(&T) -> Result<Vec<Problem>> + Sync,
+        G: Fn(&Path, &[Problem]) + Sync,
+    {
+        files
+            .par_iter()
+            .filter_map(|file| {
+                let file_path = path_of(file);
+                if self.problem_limit_reached() {
+                    trace!(file = %file_path.display(), "skipping file after problem limit");
+                    return None;
+                }
+
+                for observer in &self.observers {
+                    observer.on_file_start(file_path);
+                }
+                let started = Instant::now();
+
+                let mut problems = check(file).unwrap_or_else(|e| {
+                    debug!(file = %file_path.display(), error = %e, "failed to lint file");
+                    vec![Problem::file_error(format!("{e:#}"))]
+                });
+
+                if let Some(project) = self.project_for(file_path) {
+                    for problem in &mut problems {
+                        problem.project = Some(project.to_string());
+                    }
+                }
+
+                // Files linted concurrently may find more problems than the limit allows;
+                // suppressed problems do not count towards it
+                let found = problems
+                    .iter()
+                    .filter(|problem| !problem.suppressed)
+                    .count();
+                if let Some(limit) = &self.limit {
+                    let mut allowed = limit.claim(found);
+                    problems.retain(|problem| {
+                        let keep = problem.suppressed || allowed > 0;
+                        if !problem.suppressed {
+                            allowed = allowed.saturating_sub(1);
+                        }
+                        keep
+                    });
+                }
+
+                for observer in &self.observers {
+                    for problem in problems.iter().filter(|problem| !problem.suppressed) {
+                        observer.on_problem(file_path, problem);
+                    }
+                    observer.on_file_end(file_path, &problems, started.elapsed());
+                }
+                if found > 0 && problems.iter().all(|problem| problem.suppressed) {
+                    return None;
+                }
+
+                on_file(file_path, &problems);
+                Some((file_path.to_path_buf(), problems))
+            })
+            .collect()
+    }
+}
+
+/// The text of a file that was read, which fails if mapped content is no longer UTF-8
+fn text<'a>(file_path: &Path, content: &'a FileContent) -> Result<&'a str> {
+    content
+        .as_str()
+        .map_err(|e| eyre::eyre!("Failed to read file {}: {}", file_path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::{Level, TemplateMode};
+    use crate::rules::RuleConfig;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_test_file(dir: &TempDir, name: &str, content: &str) -> std::path::PathBuf {
+        let file_path = dir.path().join(name);
+        fs::write(&file_path, content).expect("Failed to write test file");
+        file_path
+    }
+
+    #[test]
+    fn test_linter_creation() {
+        let config = Config::default();
+        let linter = Linter::new(config);
+
+        assert!(!linter.registry.rule_ids().is_empty());
+    }
+
+    #[test]
+    fn test_lint_content_valid_yaml() {
+        let config = Config::default();
+        let linter = Linter::new(config);
+
+        let content = "key: value\nother: data";
+        let problems = linter
+            .lint_content("test.yaml", content)
+            .expect("Linting failed");
+
+        // Should have no problems for valid, short content
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_lint_content_stops_on_syntax_error() {
+        // Only the syntax error is reported by default
+        let mut config = Config::default();
+        config
+            .rules
+            .insert("truthy".to_string(), RuleConfig::new(true, Level::Error));
+        let linter = Linter::new(config);
+        let rules = |content| {
+            let problems = linter
+                .lint_content("test.yaml", content)
+                .expect("Linting failed");
+            problems
+                .into_iter()
+                .map(|problem| problem.rule)
+                .collect::<BTreeSet<_>>()
+        };
+
+        assert!(rules("---\nenabled: yes\n").contains("truthy"));
+        let broken = rules("---\nenabled: yes\nkey: [unclosed\ntrailing: x   \n");
+        assert_eq!(broken, BTreeSet::from(["yaml-syntax".to_string()]));
+    }
+
+    #[test]
+    fn test_lint_content_counts_documents() {
+        let mut config = Config::default();
+        config.rules.get_mut("document-count").unwrap().enabled = true;
+        let linter = Linter::new(config);
+
+        let problems = linter
+            .lint_content("test.yaml", "---\nkind: Service\n---\nkind: Deployment\n")
+            .expect("Linting failed");
+        let found: Vec<_> = problems
+            .iter()
+            .map(|problem| (problem.rule.as_str(), problem.line))
+            .collect();
+        assert_eq!(found, [("document-count", 3)]);
+    }
+
+    #[test]
+    fn test_lint_content_multiple_documents() {
+        let linter = Linter::new(Config::default());
+
+        // Documents separated by markers are checked by every rule, as a single one is
+        let problems = linter
+            .lint_content("test.yaml", "---\na: 1\n---\nb: 2   \n")
+            .expect("Linting failed");
+        let found: Vec<_> = problems
+            .iter()
+            .map(|problem| (problem.rule.as_str(), problem.line))
+            .collect();
+        assert_eq!(found, [("trailing-spaces", 4)]);
+    }
+
+    #[test]
+    fn test_lint_content_continues_after_parse_error() {
+        // Rules that check the text still run without stop-on-syntax-error
+        let mut config = Config::default();
+        config.stop_on_syntax_error = Some(false);
+        config
+            .rules
+            .insert("truthy".to_string(), RuleConfig::new(true, Level::Error));
+        let linter = Linter::new(config);
+
+        let problems = linter
+            .lint_content(
+                "test.yaml",
+                "---\nenabled: yes\nkey: [unclosed\ntrailing: x   \n",
+            )
+            .expect("Linting failed");
+        let rules: BTreeSet<&str> = problems
+            .iter()
+            .map(|problem| problem.rule.as_str())
+            .collect();
+        assert_eq!(rules, BTreeSet::from(["trailing-spaces", "yaml-syntax"]));
+    }
+
+    #[test]
+    fn test_lint_content_long_lines() {
+        let config = Config::default();
+        let linter = Linter::new(config);
+
+        // Use a breakable long line (with spaces)
+        let long_line = "this is a very long line with many words that definitely exceeds the eighty character limit";
+        let problems = linter
+            .lint_content("test.yaml", long_line)
+            .expect("Linting failed");
+
+        // Should have line-length problem
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].rule, "line-length");
+        assert_eq!(problems[0].line, 1);
+    }
+
+    #[test]
+    fn test_lint_content_trailing_spaces() {
+        let config = Config::default();
+        let linter = Linter::new(config);
+
+        let content = "key: value   \nother: data";
+        let problems = linter
+            .lint_content("test.yaml", content)
+            .expect("Linting failed");
+
+        // Should have trailing-spaces problem
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].rule, "trailing-spaces");
+        assert_eq!(problems[0].line, 1);
+    }
+
+    #[test]
+    fn test_lint_content_allow_empty_files() {
+        let mut config = Config::default();
+        config.rules.get_mut("document-structure").unwrap().enabled = true;
+
+        let linter = Linter::new(config.clone());
+        let problems = linter
+            .lint_content("test.yaml", "\n  \n")
+            .expect("Linting failed");
+        assert!(!problems.is_empty());
+
+        config.allow_empty_files = Some(true);
+        let linter = Linter::new(config);
+        let problems = linter
+            .lint_content("test.yaml", "\n  \n")
+            .expect("Linting failed");
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_lint_file() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let file_path = create_test_file(&temp_dir, "test.yaml", "key: value");
+
+        let config = Config::default();
+        let linter = Linter::new(config);
+
+        let problems = linter.lint_file(&file_path).expect("Linting failed");
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_lint_file_mapped() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let file_path = create_test_file(&temp_dir, "test.yaml", "key: value   \n");
+
+        // Mapped and read files give the same problems
+        let mapped = Linter::new(Config::default()).with_mmap_threshold(Some(1));
+        let read = Linter::new(Config::default()).with_mmap_threshold(None);
+        let problems = mapped.lint_file(&file_path).expect("Linting failed");
+        assert_eq!(
+            problems,
+            read.lint_file(&file_path).expect("Linting failed")
+        );
+        assert_eq!(problems[0].rule, "trailing-spaces");
+
+        fs::write(&file_path, b"key: \xff\n").unwrap();
+        assert!(mapped.lint_file(&file_path).is_err());
+    }
+
+    #[test]
+    fn test_lint_file_ignored() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let file_path = create_test_file(&temp_dir, "test.generated.yaml", "key: value");
+
+        let config = Config::default(); // Default config ignores *.generated.yaml
+        let linter = Linter::new(config);
+
+        let problems = linter.lint_file(&file_path).expect("Linting failed");
+        assert!(problems.is_empty()); // Should be ignored
+    }
+
+    #[test]
+    fn test_lint_file_not_yaml() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let file_path = create_test_file(&temp_dir, "test.txt", "not yaml content");
+
+        let config = Config::default();
+        let linter = Linter::new(config);
+
+        let problems = linter.lint_file(&file_path).expect("Linting failed");
+        assert!(problems.is_empty()); // Should be ignored as not YAML
+    }
+
+    #[test]
+    fn test_lint_content_cancelled() {
+        let linter = Linter::new(Config::default());
+
+        let cancelled = AtomicBool::new(true);
+        let problems = linter
+            .lint_content_cancellable("test.yaml", "key: value  \n", &cancelled)
+            .unwrap();
+        assert!(problems.is_none());
+
+        let cancelled = AtomicBool::new(false);
+        let problems = linter
+            .lint_content_cancellable("test.yaml", "key: value  \n", &cancelled)
+            .unwrap();
+        assert_eq!(problems.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_rule_profiling() {
+        let linter = Linter::new(Config::default());
+        linter.lint_content("test.yaml", "key: value  \n").unwrap();
+        assert!(linter.rule_timings().is_none());
+
+        let linter = Linter::new(Config::default()).with_rule_profiling(true);
+        linter.lint_content("a.yaml", "key: value  \n").unwrap();
+        linter.lint_content("b.yaml", "key: value\n").unwrap();
+
+        let timings = linter.rule_timings().unwrap();
+        let trailing = timings
+            .iter()
+            .find(|timing| timing.rule == "trailing-spaces")
+            .unwrap();
+        assert_eq!(trailing.files, 2);
+        assert_eq!(trailing.problems, 1);
+    }
+
+    #[test]
+    fn test_lint_paths_max_problems() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        for name in ["a.yaml", "b.yaml", "c.yaml"] {
+            create_test_file(&temp_dir, name, "a: 1   \nb: 2   \n");
+        }
+
+        let linter = Linter::new(Config::default());
+        linter
+            .lint_paths(&[temp_dir.path()])
+            .expect("Linting failed");
+        assert!(!linter.problem_limit_reached());
+
+        let linter = Linter::new(Config::default()).with_max_problems(Some(3));
+        let results = linter
+            .lint_paths(&[temp_dir.path()])
+            .expect("Linting failed");
+
+        let total: usize = results.iter().map(|(_, problems)| problems.len()).sum();
+        assert_eq!(total, 3);
+        assert!(linter.problem_limit_reached());
+    }
+
+    #[test]
+    fn test_lint_files_streaming() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let files = vec![
+            create_test_file(&temp_dir, "a.yaml", "key: value   \n"),
+            create_test_file(&temp_dir, "b.yaml", "key: value\n"),
+        ];
+
+        let linter = Linter::new(Config::default());
+        let streamed = std::sync::Mutex::new(Vec::new());
+        let results = linter
+            .lint_files_streaming(&files, |path, problems| {
+                streamed
+                    .lock()
+                    .unwrap()
+                    .push((path.to_path_buf(), problems.to_vec()));
+            })
+            .expect("Linting failed");
+
+        let mut streamed = streamed.into_inner().unwrap();
+        streamed.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(streamed, results);
+    }
+
+    #[test]
+    #[cfg(feature = "analytics")]
+    fn test_observers() {
+        use crate::linter::LintAnalytics;
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let files = vec![
+            create_test_file(&temp_dir, "a.yaml", "---\nkey: value   \nother: 1  \n"),
+            create_test_file(&temp_dir, "b.yaml", "---\nkey: value\n"),
+        ];
+
+        let analytics = Arc::new(LintAnalytics::new());
+        let linter = Linter::new(Config::default()).with_observer(analytics.clone());
+        let results = linter.lint_files_parallel(&files).expect("Linting failed");
+
+        let report = analytics.report();
+        assert_eq!(report.files, 2);
+        assert_eq!(
+            report.problems,
+            results
+                .iter()
+                .map(|(_, problems)| problems.len())
+                .sum::<usize>()
+        );
+        assert_eq!(report.problems_by_rule["trailing-spaces"], 2);
+        assert!(report.rule_file_times["trailing-spaces"].contains_key(&files[1]));
+    }
+
+    #[test]
+    fn test_rule_timeout_keeps_results() {
+        let content = "---\nkey: value   \nlist: [1,2]\n";
+        let expected = Linter::new(Config::default())
+            .lint_content("test.yaml", content)
+            .expect("Linting failed");
+
+        let config = Config {
+            rule_timeout: Some(10_000),
+            ..Config::default()
+        };
+        let problems = Linter::new(config)
+            .lint_content("test.yaml", content)
+            .expect("Linting failed");
+        assert_eq!(problems, expected);
+        assert!(!problems.is_empty());
+    }
+
+    #[test]
+    fn test_fix_content_reports_remaining_problems() {
+        let linter = Linter::new(Config::default());
+        let long_line = format!("key: {}", "word ".repeat(20));
+        let content = format!("a: 1   \n{}\n", long_line.trim_end());
+
+        let fixed = linter
+            .fix_content("test.yaml", &content, &FixEngine::new())
+            .expect("Fixing failed");
+
+        assert!(fixed.content.starts_with("a: 1\n"));
+        let rules: Vec<&str> = fixed.problems.iter().map(|p| p.rule.as_str()).collect();
+        assert_eq!(rules, vec!["line-length"]);
+    }
+
+    #[test]
+    fn test_fix_file_writes_fixed_content() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let file_path = create_test_file(&temp_dir, "test.yaml", "key: value   \n");
+
+        let linter = Linter::new(Config::default());
+        let problems = linter
+            .fix_file(&file_path, &FixEngine::new())
+            .expect("Fixing failed");
+
+        assert!(problems.is_empty());
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "key: value\n");
+    }
+
+    #[test]
+    fn test_lint_paths_single_file() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let file_path = create_test_file(&temp_dir, "test.yaml", "key: value");
+
+        let config = Config::default();
+        let linter = Linter::new(config);
+
+        let results = linter.lint_paths(&[&file_path]).expect("Linting failed");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, file_path);
+        assert!(results[0].1.is_empty());
+    }
+
+    #[test]
+    fn test_lint_paths_directory() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        create_test_file(&temp_dir, "test1.yaml", "key: value");
+        create_test_file(&temp_dir, "test2.yml", "other: data");
+        create_test_file(&temp_dir, "ignored.txt", "not yaml");
+
+        let config = Config::default();
+        let linter = Linter::new(config);
+
+        let results = linter
+            .lint_paths(&[temp_dir.path()])
+            .expect("Linting failed");
+        assert_eq!(results.len(), 2); // Only YAML files should be processed
+
+        let file_names: Vec<String> = results
+            .iter()
+            .map(|(path, _)| path.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert!(file_names.contains(&"test1.yaml".to_string()));
+        assert!(file_names.contains(&"test2.yml".to_string()));
+    }
+
+    #[test]
+    fn test_lint_paths_continues_after_file_error() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        create_test_file(&temp_dir, "good.yaml", "key: value   \n");
+        let bad = temp_dir.path().join("bad.yaml");
+        fs::write(&bad, b"key: \xff\xfe\n").expect("Failed to write test file");
+
+        let linter = Linter::new(Config::default());
+        let results = linter
+            .lint_paths(&[temp_dir.path()])
+            .expect("Linting failed");
+        assert_eq!(results.len(), 2);
+
+        let problems_for = |name: &str| {
+            &results
+                .iter()
+                .find(|(path, _)| path.file_name().unwrap() == name)
+                .unwrap()
+                .1
+        };
+
+        let bad_problems = problems_for("bad.yaml");
+        assert_eq!(bad_problems.len(), 1);
+        assert!(bad_problems[0].is_file_error());
+        assert_eq!(bad_problems[0].level, crate::linter::Level::Error);
+
+        assert_eq!(problems_for("good.yaml")[0].rule, "trailing-spaces");
+    }
+
+    #[test]
+    fn test_lint_file_rule_ignore_patterns() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let generated = create_test_file(&temp_dir, "a.generated.yaml", "key: value   \n");
+        let regular = create_test_file(&temp_dir, "a.yaml", "key: value   \n");
+
+        let mut config = Config::default();
+        config.ignore.clear();
+        config.rules.get_mut("trailing-spaces").unwrap().ignore =
+            vec!["*.generated.yaml".to_string()];
+        let linter = Linter::new(config).with_config_discovery(false);
+
+        let problems = linter.lint_file(&generated).expect("Linting failed");
+        assert!(problems.iter().all(|p| p.rule != "trailing-spaces"));
+
+        let problems = linter.lint_file(&regular).expect("Linting failed");
+        assert!(problems.iter().any(|p| p.rule == "trailing-spaces"));
+    }
+
+    #[test]
+    fn test_lint_virtual() {
+        let linter = Linter::new(Config::default()).with_config_discovery(false);
+        let files = vec![
+            (
+                PathBuf::from("virtual/a.yaml"),
+                "key: value   \n".to_string(),
+            ),
+            (PathBuf::from("virtual/b.yaml"), "key: value\n".to_string()),
+        ];
+
+        let results = linter.lint_virtual(&files).expect("Linting failed");
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, PathBuf::from("virtual/a.yaml"));
+        assert_eq!(results[0].1[0].rule, "trailing-spaces");
+        assert!(results[1].1.is_empty());
+        assert!(!Path::new("virtual").exists());
+    }
+
+    #[test]
+    fn test_lint_files_with_jobs() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let file_paths: Vec<_> = (0..8)
+            .map(|i| create_test_file(&temp_dir, &format!("f{i}.yaml"), "key: value   \n"))
+            .collect();
+
+        let linter = Linter::new(Config::default()).with_jobs(Some(2));
+        let results = linter
+            .lint_files_parallel(&file_paths)
+            .expect("Linting failed");
+
+        let paths: Vec<_> = results.iter().map(|(path, _)| path.clone()).collect();
+        assert_eq!(paths, file_paths);
+        assert!(results.iter().all(|(_, problems)| problems.len() == 1));
+    }
+
+    #[test]
+    fn test_lint_paths_skip_generated() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let long_line = format!("key: {}\n", "word ".repeat(20).trim_end());
+        create_test_file(
+            &temp_dir,
+            "generated.yaml",
+            &format!("# Code generated by protoc. DO NOT EDIT.\n{long_line}"),
+        );
+        create_test_file(&temp_dir, "authored.yaml", &long_line);
+
+        let config = Config {
+            skip_generated: true,
+            ..Config::default()
+        };
+        let linter = Linter::new(config).with_config_discovery(false);
+
+        let results = linter
+            .lint_paths(&[temp_dir.path()])
+            .expect("Linting failed");
+        let with_problems: Vec<String> = results
+            .iter()
+            .filter(|(_, problems)| !problems.is_empty())
+            .map(|(path, _)| path.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(with_problems, vec!["authored.yaml"]);
+        assert_eq!(
+            linter.skipped_generated(),
+            vec![temp_dir.path().join("generated.yaml")]
+        );
+    }
+
+    #[test]
+    fn test_lint_paths_embedded() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        create_test_file(
+            &temp_dir,
+            "README.md",
+            "# Usage\n\n- Run:\n\n  ```yaml\n  key: value   \n  ```\n",
+        );
+        create_test_file(&temp_dir, "deploy.sh", "cat <<YAML\nkey: value   \nYAML\n");
+
+        let linter = Linter::new(Config::default()).with_config_discovery(false);
+        assert!(
+            linter
+                .lint_paths(&[temp_dir.path()])
+                .expect("Linting failed")
+                .is_empty()
+        );
+
+        let config = Config {
+            embedded: vec![Extractor::Markdown],
+            ..Config::default()
+        };
+        let linter = Linter::new(config).with_config_discovery(false);
+        let results = linter
+            .lint_paths(&[temp_dir.path()])
+            .expect("Linting failed");
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, temp_dir.path().join("README.md"));
+        let problems = &results[0].1;
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].rule, "trailing-spaces");
+        assert_eq!((problems[0].line, problems[0].column), (6, 13));
+    }
+
+    #[test]
+    fn test_lint_paths_ignore_patterns() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        fs::create_dir_all(temp_dir.path().join("docs")).expect("Failed to create dir");
+        create_test_file(&temp_dir, "docs/drop.yaml", "key: value");
+        create_test_file(&temp_dir, "docs/keep.yaml", "key: value");
+        create_test_file(&temp_dir, "top.yaml", "key: value");
+
+        let config = Config {
+            ignore: vec!["docs/**".to_string(), "!docs/keep.yaml".to_string()],
+            ..Config::default()
+        };
+        let linter = Linter::new(config).with_config_discovery(false);
+
+        let results = linter
+            .lint_paths(&[temp_dir.path()])
+            .expect("Linting failed");
+        let mut file_names: Vec<String> = results
+            .iter()
+            .map(|(path, _)| path.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        file_names.sort();
+        assert_eq!(file_names, vec!["keep.yaml", "top.yaml"]);
+    }
+
+    #[test]
+    fn test_lint_paths_projects() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        fs::create_dir_all(temp_dir.path().join("services/api")).expect("Failed to create dir");
+        create_test_file(&temp_dir, "services/api/app.yaml", "key: value   \n");
+        create_test_file(&temp_dir, "top.yaml", "key: value   \n");
+
+        let config = Config {
+            projects: vec![crate::config::ProjectConfig {
+                name: "api".to_string(),
+                root: "services/api".to_string(),
+            }],
+            ..Config::default()
+        };
+        let linter = Linter::new(config).with_config_discovery(false);
+
+        let results = linter
+            .lint_paths(&[temp_dir.path()])
+            .expect("Linting failed");
+        assert_eq!(results.len(), 2);
+        for (path, problems) in &results {
+            let expected = path
+                .starts_with(temp_dir.path().join("services"))
+                .then_some("api");
+            assert_eq!(linter.project_for(path), expected);
+            assert!(!problems.is_empty());
+            assert!(
+                problems
+                    .iter()
+                    .all(|problem| problem.project.as_deref() == expected)
+            );
+        }
+    }
+
+    #[test]
+    fn test_lint_paths_nested_config() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let long_line = format!("key: {}\n", "word ".repeat(20).trim_end());
+        create_test_file(&temp_dir, "root.yaml", &long_line);
+        fs::create_dir(temp_dir.path().join("charts")).expect("Failed to create dir");
+        create_test_file(
+            &temp_dir,
+            "charts/.yl.yaml",
+            "rules:\n  line-length:\n    enabled: false\n    level: Error\n    params: {}\n",
+        );
+        create_test_file(&temp_dir, "charts/values.yaml", &long_line);
+
+        let linter = Linter::new(Config::default()).with_config_root(temp_dir.path());
+        let results = linter
+            .lint_paths(&[temp_dir.path()])
+            .expect("Linting failed");
+
+        let problems_for = |name: &str| {
+            results
+                .iter()
+                .find(|(path, _)| path.ends_with(name))
+                .map(|(_, problems)| problems.clone())
+                .expect("File was not linted")
+        };
+
+        assert_eq!(problems_for("root.yaml").len(), 1);
+        assert_eq!(problems_for("root.yaml")[0].rule, "line-length");
+        assert!(problems_for("charts/values.yaml").is_empty());
+    }
+
+    #[test]
+    fn test_lint_paths_editorconfig() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        create_test_file(
+            &temp_dir,
+            ".editorconfig",
+            "root = true\n\n[*.yaml]\nmax_line_length = 120\n",
+        );
+        let long_line = format!("key: {}\n", "word ".repeat(20).trim_end());
+        let file_path = create_test_file(&temp_dir, "values.yaml", &long_line);
+
+        let linter = Linter::new(Config::default());
+        let problems = linter.lint_file(&file_path).expect("Linting failed");
+        assert!(problems.is_empty());
+
+        let mut config = Config::default();
+        config.editorconfig = false;
+        let linter = Linter::new(config);
+        let problems = linter.lint_file(&file_path).expect("Linting failed");
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].rule, "line-length");
+    }
+
+    #[test]
+    fn test_lint_content_configure_directive() {
+        let linter = Linter::new(Config::default());
+        let long_line = format!("key: {}", "word ".repeat(20).trim_end());
+
+        let problems = linter
+            .lint_content("test.yaml", &format!("{long_line}\n"))
+            .expect("Linting failed");
+        assert_eq!(problems.len(), 1);
+
+        let content = format!("# yl:configure line-length max=120\n{long_line}\n");
+        let problems = linter
+            .lint_content("test.yaml", &content)
+            .expect("Linting failed");
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_lint_content_suppressed_problems() {
+        let content = "a: 1   # yl:disable-line trailing-spaces   \nb: 2   \n";
+
+        let problems = Linter::new(Config::default())
+            .lint_content("test.yaml", content)
+            .expect("Linting failed");
+        assert_eq!(problems.len(), 1);
+
+        let problems = Linter::new(Config::default())
+            .with_suppressed_problems(true)
+            .lint_content("test.yaml", content)
+            .expect("Linting failed");
+        let suppressed: Vec<usize> = problems
+            .iter()
+            .filter(|problem| problem.suppressed)
+            .map(|problem| problem.line)
+            .collect();
+        assert_eq!(problems.len(), 2);
+        assert_eq!(suppressed, vec![1]);
+    }
+
+    #[test]
+    fn test_lint_content_template_mode() {
+        let content = "spec:\n  {{- if .Values.enabled }}\n  replicas: {{ .Values.replicas }}\n  {{- end }}\n";
+
+        let linter = Linter::new(Config::default());
+        let problems = linter
+            .lint_content("test.yaml", content)
+            .expect("Linting failed");
+        assert!(problems.iter().any(|p| p.rule == "yaml-syntax"));
+
+        let mut config = Config::default();
+        config.template_mode = TemplateMode::Helm;
+        let linter = Linter::new(config);
+        let problems = linter
+            .lint_content("test.yaml", content)
+            .expect("Linting failed");
+        assert!(problems.is_empty(), "unexpected problems: {problems:?}");
+    }
+
+    #[test]
+    fn test_problem_sorting() {
+        let config = Config::default();
+        let linter = Linter::new(config);
+
+        let content = format!(
+            "{}\n{}\n{}",
+            "this is a very long line with many words that definitely exceeds the eighty character limit", // Line 1: long line
+            "short",       // Line 2: ok
+            "trailing   "  // Line 3: trailing spaces
+        );
+
+        let problems = linter
+            .lint_content("test.yaml", &content)
+            .expect("Linting failed");
+
+        // Problems should be sorted by line number
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].line, 1); // line-length problem
+        assert_eq!(problems[1].line, 3); // trailing-spaces problem
+    }
+}
@@ -0,0 +1,71 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Cap on the number of problems reported in a run; safe to share across threads
+#[derive(Debug)]
+pub struct ProblemLimit {
+    max: usize,
+    found: AtomicUsize,
+}
+
+impl ProblemLimit {
+    /// Create a limit that is reached once `max` problems have been found
+    pub fn new(max: usize) -> Self {
+        Self {
+            max,
+            found: AtomicUsize::new(0),
+        }
+    }
+
+    /// Maximum number of problems to report
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    /// Claim room for the problems of a finished file, returning how many of
+    /// them may still be reported
+    pub fn claim(&self, problems: usize) -> usize {
+        let previous = self
+            .found
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |found| {
+                Some((found + problems).min(self.max))
+            })
+            .unwrap_or(self.max);
+        (self.max - previous).min(problems)
+    }
+
+    /// Whether a file with `pending` problems so far would reach the limit
+    pub fn is_reached_with(&self, pending: usize) -> bool {
+        self.found.load(Ordering::Relaxed) + pending >= self.max
+    }
+
+    /// Whether enough problems have been found to stop linting
+    pub fn is_reached(&self) -> bool {
+        self.is_reached_with(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_reached() {
+        let limit = ProblemLimit::new(3);
+        assert!(!limit.is_reached());
+        assert!(limit.is_reached_with(3));
+
+        limit.claim(2);
+        assert!(!limit.is_reached());
+        limit.claim(1);
+        assert!(limit.is_reached());
+    }
+
+    #[test]
+    fn test_claim() {
+        let limit = ProblemLimit::new(3);
+        assert_eq!(limit.claim(0), 0);
+        assert_eq!(limit.claim(2), 2);
+        assert_eq!(limit.claim(4), 1);
+        assert_eq!(limit.claim(1), 0);
+    }
+}
@@ -0,0 +1,30 @@
+#[cfg(feature = "analytics")]
+pub mod analytics;
+pub mod context;
+pub mod effective;
+pub mod embedded;
+pub mod engine;
+pub mod limit;
+pub mod observer;
+pub mod problem;
+pub mod profile;
+pub mod runner;
+pub mod score;
+pub mod template;
+pub mod tokens;
+pub mod walk;
+
+#[cfg(feature = "analytics")]
+pub use analytics::{AnalyticsReport, LintAnalytics};
+pub use context::{BlockScalar, Chomping, KeyPath, LintContext, SequenceItem};
+pub use effective::{EffectiveConfig, EffectiveRule, Setting};
+pub use embedded::{EmbeddedYaml, Extractor};
+pub use engine::{FixedContent, Linter};
+pub use limit::ProblemLimit;
+pub use observer::LintObserver;
+pub use problem::{FILE_ERROR_RULE, Level, LineChange, Problem, RULE_TIMEOUT_RULE};
+pub use profile::{RuleProfiler, RuleTiming};
+pub use score::{Badge, HealthScore, ScoreWeights};
+pub use template::TemplateMode;
+pub use tokens::{FlowCollection, FlowKind, LineTokens, TokenMap};
+pub use walk::walk_files;
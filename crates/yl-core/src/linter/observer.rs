@@ -0,0 +1,45 @@
+use super::Problem;
+use std::path::Path;
+use std::time::Duration;
+
+/// Hooks called while the linter works through a set of files, for custom reporters,
+/// progress displays or statistics.
+///
+/// Files are linted in parallel, so the hooks of different files may be called
+/// concurrently and in any order; the hooks of one file are called in order on one
+/// thread. They are called by the multi-file entry points ([`Linter::lint_files_streaming`],
+/// [`Linter::fix_files_streaming`], [`Linter::lint_virtual`] and the functions built on
+/// them), not by single-file linting.
+///
+/// [`Linter::lint_files_streaming`]: super::Linter::lint_files_streaming
+/// [`Linter::fix_files_streaming`]: super::Linter::fix_files_streaming
+/// [`Linter::lint_virtual`]: super::Linter::lint_virtual
+pub trait LintObserver: Send + Sync {
+    /// A file is about to be linted
+    fn on_file_start(&self, path: &Path) {
+        let _ = path;
+    }
+
+    /// A problem will be reported for a file, after inline directives and the problem
+    /// limit were applied
+    fn on_problem(&self, path: &Path, problem: &Problem) {
+        let _ = (path, problem);
+    }
+
+    /// A rule was skipped on a file because it ran longer than `rule-timeout`. Unlike
+    /// the other hooks, this is called by single-file linting too.
+    fn on_rule_timeout(&self, path: &Path, rule: &str) {
+        let _ = (path, rule);
+    }
+
+    /// A rule finished checking a file, with the time its check took. Like
+    /// [`LintObserver::on_rule_timeout`], this is called by single-file linting too.
+    fn on_rule_end(&self, path: &Path, rule: &str, elapsed: Duration) {
+        let _ = (path, rule, elapsed);
+    }
+
+    /// A file is finished, with all of its reported problems and the time it took
+    fn on_file_end(&self, path: &Path, problems: &[Problem], elapsed: Duration) {
+        let _ = (path, problems, elapsed);
+    }
+}
@@ -1,4 +1,7 @@
+use crate::codes;
+use crate::messages::Message;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Represents the severity level of a linting problem
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
@@ -21,21 +24,80 @@ impl std::fmt::Display for Level {
     }
 }
 
+impl std::str::FromStr for Level {
+    type Err = eyre::Report;
+
+    /// Parse a level name, ignoring case
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "info" => Ok(Level::Info),
+            "warning" => Ok(Level::Warning),
+            "error" => Ok(Level::Error),
+            _ => Err(eyre::eyre!(
+                "Unknown level '{value}' (expected error, warning or info)"
+            )),
+        }
+    }
+}
+
+/// How the line of a problem was touched by the change being linted, in diff mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LineChange {
+    /// A line the change adds or modifies
+    Added,
+    /// An unchanged line shown around the change
+    Context,
+}
+
+impl std::fmt::Display for LineChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LineChange::Added => write!(f, "added"),
+            LineChange::Context => write!(f, "context"),
+        }
+    }
+}
+
+/// Rule ID of the problems reported for files that could not be linted at all
+pub const FILE_ERROR_RULE: &str = "file-error";
+
+/// Rule ID of the problems reported for rules skipped because they exceeded `rule-timeout`
+pub const RULE_TIMEOUT_RULE: &str = "rule-timeout";
+
 /// Represents a linting problem found in a YAML file
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Problem {
     /// Line number where the problem was found (1-based)
     pub line: usize,
-    /// Column number where the problem was found (1-based)
+    /// Column number where the problem was found (1-based, in characters rather than bytes)
     pub column: usize,
     /// Severity level of the problem
     pub level: Level,
     /// ID of the rule that detected the problem
     pub rule: String,
-    /// Human-readable description of the problem
+    /// Human-readable description of the problem, in the language of the active message
+    /// catalog
     pub message: String,
+    /// ID of the message in the message catalog, for messages that come from it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+    /// Values of the message's placeholders, by name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub message_args: BTreeMap<String, String>,
     /// Optional suggestion for fixing the problem
     pub suggestion: Option<String>,
+    /// Project of the file, when the configuration defines `projects`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// Whether a directive suppresses the problem; such problems are only returned by
+    /// linters that keep them (see [`crate::linter::Linter::with_suppressed_problems`])
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub suppressed: bool,
+    /// Whether the problem's line was added by the change or shown as its context, when
+    /// only changed files are linted; `None` on lines outside the diff
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub change: Option<LineChange>,
 }
 
 #[allow(dead_code)] // Some methods are part of API for future phases
@@ -54,7 +116,12 @@ impl Problem {
             level,
             rule: rule.into(),
             message: message.into(),
+            message_id: None,
+            message_args: BTreeMap::new(),
             suggestion: None,
+            project: None,
+            suppressed: false,
+            change: None,
         }
     }
 
@@ -73,10 +140,67 @@ impl Problem {
             level,
             rule: rule.into(),
             message: message.into(),
+            message_id: None,
+            message_args: BTreeMap::new(),
             suggestion: Some(suggestion.into()),
+            project: None,
+            suppressed: false,
+            change: None,
         }
     }
 
+    /// Create a problem with a message from the message catalog, rendered in the language
+    /// of the active catalog
+    pub fn from_message(
+        line: usize,
+        column: usize,
+        level: Level,
+        rule: impl Into<String>,
+        message: Message,
+    ) -> Self {
+        let mut problem = Self::new(line, column, level, rule, message.text());
+        problem.message_id = Some(message.id.to_string());
+        problem.message_args = message
+            .args
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect();
+        problem
+    }
+
+    /// Stable code of the problem, such as `YL10101`; see [`crate::codes`]
+    pub fn code(&self) -> Option<&'static str> {
+        codes::code(&self.rule, self.message_id.as_deref())
+    }
+
+    /// Whether the problem's message has the given catalog ID
+    pub fn has_message(&self, id: &str) -> bool {
+        self.message_id.as_deref() == Some(id)
+    }
+
+    /// Create the problem reported for a file that could not be read or linted
+    pub fn file_error(message: impl Into<String>) -> Self {
+        Self::new(1, 1, Level::Error, FILE_ERROR_RULE, message)
+    }
+
+    /// Create the problem reported for a rule that was skipped after running too long
+    pub fn rule_timeout(rule: &str, timeout: std::time::Duration) -> Self {
+        Self::from_message(
+            1,
+            1,
+            Level::Warning,
+            RULE_TIMEOUT_RULE,
+            Message::new("rule-timeout.skipped")
+                .arg("rule", rule)
+                .arg("timeout", timeout.as_millis()),
+        )
+    }
+
+    /// Check if this problem reports a file that could not be linted
+    pub fn is_file_error(&self) -> bool {
+        self.rule == FILE_ERROR_RULE
+    }
+
     /// Get a formatted message including the rule ID
     pub fn formatted_message(&self) -> String {
         format!("{} ({})", self.message, self.rule)
@@ -121,6 +245,13 @@ mod tests {
         assert_eq!(Level::Error.to_string(), "error");
     }
 
+    #[test]
+    fn test_level_from_str() {
+        assert_eq!("warning".parse::<Level>().unwrap(), Level::Warning);
+        assert_eq!("Error".parse::<Level>().unwrap(), Level::Error);
+        assert!("fatal".parse::<Level>().is_err());
+    }
+
     #[test]
     fn test_level_ordering() {
         assert!(Level::Info < Level::Warning);
@@ -140,6 +271,28 @@ mod tests {
         assert_eq!(problem.suggestion, None);
     }
 
+    #[test]
+    fn test_file_error() {
+        let problem = Problem::file_error("Failed to read file");
+        assert_eq!(problem.rule, FILE_ERROR_RULE);
+        assert_eq!(problem.level, Level::Error);
+        assert!(problem.is_file_error());
+        assert!(!Problem::new(1, 1, Level::Error, "rule", "msg").is_file_error());
+    }
+
+    #[test]
+    fn test_rule_timeout() {
+        let problem = Problem::rule_timeout("indentation", std::time::Duration::from_millis(500));
+        assert_eq!(problem.rule, RULE_TIMEOUT_RULE);
+        assert_eq!(problem.level, Level::Warning);
+        assert_eq!(
+            problem.message,
+            "rule indentation skipped after running longer than 500 ms"
+        );
+        assert_eq!(problem.code(), Some("YL90201"));
+        assert_eq!(Problem::file_error("unreadable").code(), Some("YL90100"));
+    }
+
     #[test]
     fn test_problem_with_suggestion() {
         let problem = Problem::with_suggestion(
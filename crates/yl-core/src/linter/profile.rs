@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Cumulative cost and results of a single rule over a run
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RuleTiming {
+    /// Rule identifier
+    pub rule: String,
+    /// Total time spent in the rule's `check`
+    pub total_time: Duration,
+    /// Number of files the rule ran on
+    pub files: usize,
+    /// Number of problems reported after inline directives were applied
+    pub problems: usize,
+}
+
+/// Collects per-rule timings while linting; safe to share across threads
+#[derive(Debug, Default)]
+pub struct RuleProfiler {
+    timings: Mutex<HashMap<String, RuleTiming>>,
+}
+
+impl RuleProfiler {
+    /// Create an empty profiler
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one run of a rule on a file
+    pub fn record(&self, rule: &str, elapsed: Duration, problems: usize) {
+        let mut timings = self.timings.lock().expect("rule profiler poisoned");
+        let timing = timings
+            .entry(rule.to_string())
+            .or_insert_with(|| RuleTiming {
+                rule: rule.to_string(),
+                ..Default::default()
+            });
+
+        timing.total_time += elapsed;
+        timing.files += 1;
+        timing.problems += problems;
+    }
+
+    /// Timings recorded so far, slowest rule first
+    pub fn timings(&self) -> Vec<RuleTiming> {
+        let mut timings: Vec<RuleTiming> = self
+            .timings
+            .lock()
+            .expect("rule profiler poisoned")
+            .values()
+            .cloned()
+            .collect();
+        timings.sort_by(|a, b| {
+            b.total_time
+                .cmp(&a.total_time)
+                .then_with(|| a.rule.cmp(&b.rule))
+        });
+        timings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_accumulates() {
+        let profiler = RuleProfiler::new();
+        profiler.record("line-length", Duration::from_millis(2), 1);
+        profiler.record("line-length", Duration::from_millis(3), 0);
+        profiler.record("truthy", Duration::from_millis(10), 4);
+
+        let timings = profiler.timings();
+        assert_eq!(timings.len(), 2);
+
+        assert_eq!(timings[0].rule, "truthy");
+        assert_eq!(timings[0].problems, 4);
+
+        assert_eq!(timings[1].rule, "line-length");
+        assert_eq!(timings[1].total_time, Duration::from_millis(5));
+        assert_eq!(timings[1].files, 2);
+        assert_eq!(timings[1].problems, 1);
+    }
+}
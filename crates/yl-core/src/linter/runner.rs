@@ -0,0 +1,186 @@
+use super::{LintContext, Problem};
+use crate::rules::{Rule, RuleConfig};
+use eyre::Result;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+/// Result of running one rule on a file
+#[derive(Debug)]
+pub enum RuleOutcome {
+    /// The rule finished, successfully or not
+    Finished(Result<Vec<Problem>>),
+    /// The rule ran longer than the timeout and was abandoned
+    TimedOut,
+}
+
+/// Runs the rules of one file, either directly or with a time limit per rule
+pub enum RuleRunner<'a> {
+    /// Rules run on the calling thread and are never interrupted
+    Direct(LintContext<'a>),
+    /// Rules run on a worker thread that is abandoned when a rule takes too long
+    Timed(TimedRunner),
+}
+
+impl<'a> RuleRunner<'a> {
+    /// Run rules on `content` directly, or with a per-rule `timeout` if one is given
+    pub fn new(file_path: &'a Path, content: &'a str, timeout: Option<Duration>) -> Self {
+        match timeout {
+            Some(timeout) => Self::Timed(TimedRunner::new(file_path, content, timeout)),
+            None => Self::Direct(LintContext::new(file_path, content)),
+        }
+    }
+
+    /// Run a rule on the file
+    pub fn check(&mut self, rule: &Arc<dyn Rule>, config: &RuleConfig) -> RuleOutcome {
+        match self {
+            Self::Direct(context) => RuleOutcome::Finished(rule.check(context, config)),
+            Self::Timed(runner) => runner.check(rule, config),
+        }
+    }
+}
+
+/// Worker thread holding the lint context of a file and running rules on request
+struct Worker {
+    jobs: mpsc::Sender<(Arc<dyn Rule>, RuleConfig)>,
+    results: mpsc::Receiver<Result<Vec<Problem>>>,
+}
+
+/// Runs rules on a worker thread, giving up on a rule once it exceeds the timeout.
+///
+/// A rule cannot be interrupted, so the worker running it is left to finish in the
+/// background and a new worker, with its own parse of the file, runs the remaining rules.
+pub struct TimedRunner {
+    file_path: PathBuf,
+    content: Arc<str>,
+    timeout: Duration,
+    worker: Option<Worker>,
+}
+
+impl TimedRunner {
+    /// Prepare to run rules on `content`, each for at most `timeout`
+    pub fn new(file_path: &Path, content: &str, timeout: Duration) -> Self {
+        Self {
+            file_path: file_path.to_path_buf(),
+            content: Arc::from(content),
+            timeout,
+            worker: None,
+        }
+    }
+
+    /// Run a rule on the worker, waiting at most the timeout for its problems
+    pub fn check(&mut self, rule: &Arc<dyn Rule>, config: &RuleConfig) -> RuleOutcome {
+        let worker = match self.worker.take() {
+            Some(worker) => worker,
+            None => match self.spawn_worker() {
+                Ok(worker) => worker,
+                Err(e) => return RuleOutcome::Finished(Err(e)),
+            },
+        };
+
+        if worker.jobs.send((rule.clone(), config.clone())).is_err() {
+            return RuleOutcome::Finished(Err(eyre::eyre!("rule worker thread stopped")));
+        }
+
+        match worker.results.recv_timeout(self.timeout) {
+            Ok(result) => {
+                self.worker = Some(worker);
+                RuleOutcome::Finished(result)
+            }
+            Err(RecvTimeoutError::Timeout) => RuleOutcome::TimedOut,
+            Err(RecvTimeoutError::Disconnected) => {
+                RuleOutcome::Finished(Err(eyre::eyre!("rule {} panicked", rule.id())))
+            }
+        }
+    }
+
+    /// Start a worker with its own lint context of the file
+    fn spawn_worker(&self) -> Result<Worker> {
+        let (jobs, job_receiver) = mpsc::channel::<(Arc<dyn Rule>, RuleConfig)>();
+        let (result_sender, results) = mpsc::channel();
+        let file_path = self.file_path.clone();
+        let content = self.content.clone();
+
+        thread::Builder::new()
+            .name("yl-rule".to_string())
+            .spawn(move || {
+                let context = LintContext::new(&file_path, &content);
+                // Ends once the runner drops or abandons the worker
+                for (rule, config) in job_receiver {
+                    if result_sender.send(rule.check(&context, &config)).is_err() {
+                        break;
+                    }
+                }
+            })
+            .map_err(|e| eyre::eyre!("Failed to start rule worker thread: {e}"))?;
+
+        Ok(Worker { jobs, results })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::Level;
+
+    /// Rule that sleeps before reporting one problem
+    struct SlowRule(Duration);
+
+    impl Rule for SlowRule {
+        fn id(&self) -> &'static str {
+            "slow"
+        }
+
+        fn check(&self, _context: &LintContext, _config: &RuleConfig) -> Result<Vec<Problem>> {
+            thread::sleep(self.0);
+            Ok(vec![Problem::new(
+                1,
+                1,
+                Level::Error,
+                "slow",
+                "slow problem",
+            )])
+        }
+
+        fn default_config(&self) -> RuleConfig {
+            RuleConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_timed_runner() {
+        let path = PathBuf::from("test.yaml");
+        let mut runner = RuleRunner::new(&path, "key: value\n", Some(Duration::from_millis(200)));
+        let fast: Arc<dyn Rule> = Arc::new(SlowRule(Duration::ZERO));
+        let slow: Arc<dyn Rule> = Arc::new(SlowRule(Duration::from_secs(2)));
+        let config = RuleConfig::default();
+
+        assert!(matches!(
+            runner.check(&fast, &config),
+            RuleOutcome::Finished(Ok(problems)) if problems.len() == 1
+        ));
+        assert!(matches!(
+            runner.check(&slow, &config),
+            RuleOutcome::TimedOut
+        ));
+        // A new worker runs the rules after a timeout
+        assert!(matches!(
+            runner.check(&fast, &config),
+            RuleOutcome::Finished(Ok(problems)) if problems.len() == 1
+        ));
+    }
+
+    #[test]
+    fn test_direct_runner() {
+        let path = PathBuf::from("test.yaml");
+        let mut runner = RuleRunner::new(&path, "key: value\n", None);
+        let rule: Arc<dyn Rule> = Arc::new(SlowRule(Duration::from_millis(10)));
+
+        assert!(matches!(
+            runner.check(&rule, &RuleConfig::default()),
+            RuleOutcome::Finished(Ok(_))
+        ));
+    }
+}
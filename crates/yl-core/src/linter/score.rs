@@ -0,0 +1,196 @@
+use super::{Level, Problem};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+/// Weighted problems per file at which the score drops to 50
+const HALF_SCORE_PENALTY: f64 = 10.0;
+
+/// Weight of each problem in the health score, from the `score-weights` configuration
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreWeights {
+    pub error: f64,
+    pub warning: f64,
+    pub info: f64,
+    /// Weights of rules, overriding the weight of the problem's severity
+    pub rules: HashMap<String, f64>,
+}
+
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        Self {
+            error: 5.0,
+            warning: 2.0,
+            info: 0.5,
+            rules: HashMap::new(),
+        }
+    }
+}
+
+impl ScoreWeights {
+    /// Weights from a `score-weights` section, whose keys are `error`, `warning`, `info`
+    /// or rule ids; severities not listed keep their default weight
+    pub fn from_config(weights: &HashMap<String, f64>) -> Self {
+        let mut score_weights = Self::default();
+        for (key, weight) in weights {
+            match key.as_str() {
+                "error" => score_weights.error = *weight,
+                "warning" => score_weights.warning = *weight,
+                "info" => score_weights.info = *weight,
+                rule => {
+                    score_weights.rules.insert(rule.to_string(), *weight);
+                }
+            }
+        }
+        score_weights
+    }
+
+    /// Weight of one problem
+    pub fn weight(&self, problem: &Problem) -> f64 {
+        self.rules
+            .get(&problem.rule)
+            .copied()
+            .unwrap_or(match problem.level {
+                Level::Error => self.error,
+                Level::Warning => self.warning,
+                Level::Info => self.info,
+            })
+    }
+}
+
+/// Health of a set of linted files as one trendable number
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HealthScore {
+    /// Score from 0 to 100, rounded to one decimal; 100 when there are no problems
+    pub score: f64,
+    /// Number of files linted
+    pub files: usize,
+    /// Number of problems found
+    pub problems: usize,
+    /// Sum of the weights of all problems
+    pub penalty: f64,
+    /// Penalty of each rule's problems
+    pub penalty_by_rule: BTreeMap<String, f64>,
+}
+
+/// A shields.io endpoint badge
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Badge {
+    pub schema_version: u8,
+    pub label: String,
+    pub message: String,
+    pub color: String,
+}
+
+impl HealthScore {
+    /// Score the problems of linted files. The score only depends on the weighted
+    /// problems per file, so it stays comparable as files are added, and halves when
+    /// they reach [`HALF_SCORE_PENALTY`].
+    pub fn from_results(results: &[(PathBuf, Vec<Problem>)], weights: &ScoreWeights) -> Self {
+        let mut penalty_by_rule = BTreeMap::new();
+        let mut problems = 0;
+
+        for problem in results.iter().flat_map(|(_, problems)| problems) {
+            problems += 1;
+            *penalty_by_rule.entry(problem.rule.clone()).or_insert(0.0) += weights.weight(problem);
+        }
+
+        let penalty: f64 = penalty_by_rule.values().sum();
+        let per_file = penalty / results.len().max(1) as f64;
+        let score = 100.0 * HALF_SCORE_PENALTY / (HALF_SCORE_PENALTY + per_file);
+
+        Self {
+            score: (score * 10.0).round() / 10.0,
+            files: results.len(),
+            problems,
+            penalty,
+            penalty_by_rule,
+        }
+    }
+
+    /// Rules by the penalty of their problems, highest first
+    pub fn top_rules(&self) -> Vec<(&str, f64)> {
+        let mut rules: Vec<(&str, f64)> = self
+            .penalty_by_rule
+            .iter()
+            .map(|(rule, penalty)| (rule.as_str(), *penalty))
+            .collect();
+        rules.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(b.0)));
+        rules
+    }
+
+    /// Badge showing the rounded score, colored from red to bright green
+    pub fn badge(&self, label: &str) -> Badge {
+        let color = match self.score {
+            score if score >= 90.0 => "brightgreen",
+            score if score >= 75.0 => "green",
+            score if score >= 60.0 => "yellow",
+            score if score >= 40.0 => "orange",
+            _ => "red",
+        };
+        Badge {
+            schema_version: 1,
+            label: label.to_string(),
+            message: format!("{:.0}", self.score),
+            color: color.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results() -> Vec<(PathBuf, Vec<Problem>)> {
+        vec![
+            (
+                PathBuf::from("a.yaml"),
+                vec![
+                    Problem::new(1, 1, Level::Error, "key-duplicates", "duplicate key"),
+                    Problem::new(2, 81, Level::Warning, "line-length", "line too long"),
+                ],
+            ),
+            (PathBuf::from("b.yaml"), Vec::new()),
+        ]
+    }
+
+    #[test]
+    fn test_score_without_problems() {
+        let score = HealthScore::from_results(&[], &ScoreWeights::default());
+        assert_eq!(score.score, 100.0);
+        assert_eq!(score.badge("yaml").color, "brightgreen");
+    }
+
+    #[test]
+    fn test_score_weights() {
+        // A penalty of 7 over 2 files: 100 * 10 / (10 + 3.5)
+        let score = HealthScore::from_results(&results(), &ScoreWeights::default());
+        assert_eq!(score.problems, 2);
+        assert_eq!(score.penalty, 7.0);
+        assert_eq!(score.score, 74.1);
+        assert_eq!(score.top_rules()[0], ("key-duplicates", 5.0));
+
+        let config = HashMap::from([
+            ("error".to_string(), 10.0),
+            ("line-length".to_string(), 0.0),
+        ]);
+        let score = HealthScore::from_results(&results(), &ScoreWeights::from_config(&config));
+        assert_eq!(score.penalty, 10.0);
+        assert_eq!(score.score, 66.7);
+    }
+
+    #[test]
+    fn test_badge() {
+        let score = HealthScore::from_results(&results(), &ScoreWeights::default());
+        let badge = score.badge("yaml health");
+        assert_eq!(
+            (badge.message.as_str(), badge.color.as_str()),
+            ("74", "yellow")
+        );
+        assert_eq!(
+            serde_yaml::to_string(&badge).unwrap(),
+            "schemaVersion: 1\nlabel: yaml health\nmessage: '74'\ncolor: yellow\n"
+        );
+    }
+}
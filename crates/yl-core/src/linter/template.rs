@@ -0,0 +1,213 @@
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// Templating language whose expressions are masked before linting
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TemplateMode {
+    /// Lint the content as plain YAML
+    #[default]
+    None,
+    /// Go templates as used by Helm charts (`{{ ... }}`)
+    Helm,
+    /// Jinja templates (`{{ ... }}`, `{% ... %}` and `{# ... #}`)
+    Jinja,
+}
+
+impl TemplateMode {
+    /// Opening and closing delimiters of template expressions
+    fn delimiters(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            TemplateMode::None => &[],
+            TemplateMode::Helm => &[("{{", "}}")],
+            TemplateMode::Jinja => &[("{{", "}}"), ("{%", "%}"), ("{#", "#}")],
+        }
+    }
+}
+
+/// Character used in place of masked template expressions
+const PLACEHOLDER: char = 'x';
+
+/// Replace template expressions with placeholders of equal length.
+///
+/// Inline expressions become plain scalars, so `image: {{ .Values.image }}` stays a valid
+/// mapping entry. Lines made up only of template expressions (control flow such as
+/// `{{- if .Values.enabled }}`) become comments. Every character is replaced one for one
+/// and line breaks are kept, so line and column positions are unchanged.
+pub fn mask_templates(content: &str, mode: TemplateMode) -> Cow<'_, str> {
+    let delimiters = mode.delimiters();
+    if !delimiters.iter().any(|(open, _)| content.contains(open)) {
+        return Cow::Borrowed(content);
+    }
+
+    let chars: Vec<char> = content.chars().collect();
+    let masked = masked_positions(&chars, delimiters);
+
+    let mut output = String::with_capacity(content.len());
+    for line in line_ranges(&chars) {
+        let first = line
+            .clone()
+            .find(|&i| !chars[i].is_whitespace())
+            .unwrap_or(line.end);
+        let whole_line =
+            first < line.end && line.clone().all(|i| masked[i] || chars[i].is_whitespace());
+
+        for i in line.clone() {
+            let c = if !masked[i] {
+                chars[i]
+            } else if whole_line && i == first {
+                '#'
+            } else if whole_line && i == first + 1 {
+                ' '
+            } else {
+                PLACEHOLDER
+            };
+            output.push(c);
+        }
+
+        if line.end < chars.len() {
+            output.push('\n');
+        }
+    }
+
+    Cow::Owned(output)
+}
+
+/// Mark every character that belongs to a template expression, delimiters included.
+/// Unterminated expressions are left untouched.
+fn masked_positions(chars: &[char], delimiters: &[(&str, &str)]) -> Vec<bool> {
+    let mut masked = vec![false; chars.len()];
+    let mut i = 0;
+
+    while i < chars.len() {
+        let opened = delimiters
+            .iter()
+            .find(|(open, _)| starts_with_at(chars, i, open));
+
+        if let Some((open, close)) = opened {
+            let body_start = i + open.chars().count();
+            let end = (body_start..chars.len())
+                .find(|&j| starts_with_at(chars, j, close))
+                .map(|j| j + close.chars().count());
+
+            if let Some(end) = end {
+                for position in masked.iter_mut().take(end).skip(i) {
+                    *position = true;
+                }
+                i = end;
+                continue;
+            }
+        }
+
+        i += 1;
+    }
+
+    // Line breaks inside multi-line expressions are kept as-is
+    for (position, c) in masked.iter_mut().zip(chars) {
+        if *c == '\n' || *c == '\r' {
+            *position = false;
+        }
+    }
+
+    masked
+}
+
+/// Check whether `pattern` occurs in `chars` at the given position
+fn starts_with_at(chars: &[char], position: usize, pattern: &str) -> bool {
+    pattern
+        .chars()
+        .enumerate()
+        .all(|(offset, expected)| chars.get(position + offset) == Some(&expected))
+}
+
+/// Character index ranges of each line, excluding the `\n` terminator
+fn line_ranges(chars: &[char]) -> Vec<std::ops::Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut start = 0;
+
+    for (i, c) in chars.iter().enumerate() {
+        if *c == '\n' {
+            ranges.push(start..i);
+            start = i + 1;
+        }
+    }
+    ranges.push(start..chars.len());
+
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mode_none_is_untouched() {
+        let content = "image: {{ .Values.image }}\n";
+        assert!(matches!(
+            mask_templates(content, TemplateMode::None),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn test_inline_expressions_keep_columns() {
+        let content = "image: \"{{ .Values.image }}:{{ .Values.tag }}\"\nport: 80\n";
+        let masked = mask_templates(content, TemplateMode::Helm);
+
+        assert_eq!(masked.len(), content.len());
+        assert_eq!(
+            masked,
+            "image: \"xxxxxxxxxxxxxxxxxxx:xxxxxxxxxxxxxxxxx\"\nport: 80\n"
+        );
+        assert!(serde_yaml::from_str::<serde_yaml::Value>(&masked).is_ok());
+    }
+
+    #[test]
+    fn test_control_lines_become_comments() {
+        let content = "spec:\n  {{- if .Values.enabled }}\n  replicas: 2\n  {{- end }}\n";
+        let masked = mask_templates(content, TemplateMode::Helm);
+
+        assert_eq!(
+            masked,
+            "spec:\n  # xxxxxxxxxxxxxxxxxxxxxxx\n  replicas: 2\n  # xxxxxxxx\n"
+        );
+        assert!(serde_yaml::from_str::<serde_yaml::Value>(&masked).is_ok());
+    }
+
+    #[test]
+    fn test_multiline_expression() {
+        let content = "a: 1\n{{- /*\nnote\n*/ -}}\nb: 2\n";
+        let masked = mask_templates(content, TemplateMode::Helm);
+
+        assert_eq!(masked, "a: 1\n# xxxx\n# xx\n# xxxx\nb: 2\n");
+    }
+
+    #[test]
+    fn test_jinja_blocks() {
+        let content = "{% if enabled %}\nkey: {{ value }} {# note #}\n{% endif %}\n";
+
+        let masked = mask_templates(content, TemplateMode::Jinja);
+        assert_eq!(
+            masked,
+            "# xxxxxxxxxxxxxx\nkey: xxxxxxxxxxx xxxxxxxxxx\n# xxxxxxxxx\n"
+        );
+
+        // Helm mode does not know about Jinja blocks
+        let masked = mask_templates(content, TemplateMode::Helm);
+        assert!(masked.starts_with("{% if enabled %}"));
+    }
+
+    #[test]
+    fn test_unterminated_expression_untouched() {
+        let content = "key: {{ value\n";
+        assert_eq!(mask_templates(content, TemplateMode::Helm), content);
+    }
+
+    #[test]
+    fn test_template_mode_serde() {
+        let mode: TemplateMode = serde_yaml::from_str("helm").unwrap();
+        assert_eq!(mode, TemplateMode::Helm);
+        let mode: TemplateMode = serde_yaml::from_str("none").unwrap();
+        assert_eq!(mode, TemplateMode::None);
+    }
+}
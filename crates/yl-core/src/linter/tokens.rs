@@ -0,0 +1,473 @@
+use crate::rules::common;
+use std::ops::{Range, RangeInclusive};
+
+/// Comments, quoted scalars and block scalars of a file, found once so that rules agree
+/// on which characters are YAML syntax
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TokenMap {
+    lines: Vec<LineTokens>,
+    block_scalars: Vec<RangeInclusive<usize>>,
+}
+
+/// Comment and quoted scalars of one line
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LineTokens {
+    /// Byte offset of the `#` starting the line's comment
+    pub comment: Option<usize>,
+    /// Byte ranges of quoted scalars, quotes included. A scalar continued from the
+    /// previous line starts at 0; one continued on the next line ends at the line's end.
+    pub strings: Vec<Range<usize>>,
+    /// Whether the line is content of a literal (`|`) or folded (`>`) block scalar
+    pub in_block_scalar: bool,
+}
+
+impl LineTokens {
+    /// Check if a byte offset is inside a quoted scalar
+    pub fn in_string(&self, offset: usize) -> bool {
+        self.strings.iter().any(|string| string.contains(&offset))
+    }
+
+    /// Check if a byte offset is inside the comment
+    pub fn in_comment(&self, offset: usize) -> bool {
+        self.comment.is_some_and(|comment| offset >= comment)
+    }
+
+    /// Check if the character at a byte offset is YAML syntax or a plain scalar: not part
+    /// of a comment, a quoted scalar or a block scalar
+    pub fn is_code(&self, offset: usize) -> bool {
+        !self.in_block_scalar && !self.in_string(offset) && !self.in_comment(offset)
+    }
+}
+
+/// Kind of a flow collection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlowKind {
+    /// A `[...]` sequence
+    Sequence,
+    /// A `{...}` mapping
+    Mapping,
+}
+
+/// A flow sequence or mapping found in the content
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlowCollection {
+    pub kind: FlowKind,
+    /// Line (1-based) of the opening bracket or brace
+    pub line: usize,
+    /// Column (1-based, in characters) of the opening bracket or brace
+    pub column: usize,
+    /// Line (1-based) of the closing bracket or brace
+    pub end_line: usize,
+    /// Number of entries; a nested collection is one entry of its parent
+    pub items: usize,
+    /// Nesting depth among flow collections, 1 for a collection in block context
+    pub depth: usize,
+    /// Line and column (1-based) of a comma directly before the closing bracket or brace,
+    /// as in `[a, b,]`
+    pub trailing_comma: Option<(usize, usize)>,
+    /// Lines and columns (1-based) of commas ending an entry without content, as the
+    /// second comma of `[a,, b]`
+    pub empty_entries: Vec<(usize, usize)>,
+    /// Keys of a flow mapping's entries, without quotes, with their line and column
+    /// (1-based) in order. Keys that are collections or span lines are left out.
+    pub keys: Vec<(usize, usize, String)>,
+}
+
+/// A flow collection still open while scanning
+struct OpenFlow {
+    collection: FlowCollection,
+    /// Line, column and byte offset where the current entry's content starts
+    entry: Option<(usize, usize, usize)>,
+    /// Whether the current entry's key has been found
+    has_key: bool,
+    /// Position of the last comma
+    last_comma: Option<(usize, usize)>,
+}
+
+impl OpenFlow {
+    /// Note content of the current entry at a position
+    fn content(&mut self, line: usize, column: usize, offset: usize) {
+        self.entry.get_or_insert((line, column, offset));
+    }
+}
+
+impl FlowCollection {
+    /// Check if the collection spans more than one line
+    pub fn is_multi_line(&self) -> bool {
+        self.end_line > self.line
+    }
+}
+
+impl TokenMap {
+    /// Scan content line by line. `block_scalars` are the line ranges (1-based, inclusive)
+    /// holding block scalar content, which has neither comments nor quoted scalars.
+    pub fn new(content: &str, block_scalars: Vec<RangeInclusive<usize>>) -> Self {
+        let mut lines = Vec::new();
+        // Quote character of a scalar continued from the previous line
+        let mut open_quote = None;
+
+        for (index, line) in content.lines().enumerate() {
+            let line_no = index + 1;
+            if block_scalars.iter().any(|span| span.contains(&line_no)) {
+                open_quote = None;
+                lines.push(LineTokens {
+                    in_block_scalar: true,
+                    ..Default::default()
+                });
+                continue;
+            }
+            // A document marker ends any unterminated scalar
+            if line.starts_with("---") || line.starts_with("...") {
+                open_quote = None;
+            }
+
+            let (tokens, quote) = Self::scan_line(line, open_quote);
+            open_quote = quote;
+            lines.push(tokens);
+        }
+
+        Self {
+            lines,
+            block_scalars,
+        }
+    }
+
+    /// Find the comment and quoted scalars of a line, returning the quote still open at
+    /// its end
+    fn scan_line(line: &str, open_quote: Option<char>) -> (LineTokens, Option<char>) {
+        let mut tokens = LineTokens::default();
+        let mut quote = open_quote.map(|quote| (quote, 0));
+        let mut previous = ' ';
+        let mut chars = line.char_indices().peekable();
+
+        while let Some((pos, ch)) = chars.next() {
+            match quote {
+                Some(('"', _)) if ch == '\\' => {
+                    // The escaped character cannot close the scalar
+                    chars.next();
+                }
+                Some(('\'', _)) if ch == '\'' && chars.peek().is_some_and(|(_, c)| *c == '\'') => {
+                    // `''` is an escaped single quote
+                    chars.next();
+                }
+                Some((open, start)) if ch == open => {
+                    tokens.strings.push(start..pos + 1);
+                    quote = None;
+                }
+                Some(_) => {}
+                None if matches!(ch, '"' | '\'') && common::is_quote_start(previous) => {
+                    quote = Some((ch, pos));
+                }
+                None if ch == '#' && previous.is_whitespace() => {
+                    tokens.comment = Some(pos);
+                    break;
+                }
+                None => {}
+            }
+            previous = ch;
+        }
+
+        if let Some((_, start)) = quote {
+            tokens.strings.push(start..line.len());
+        }
+        (tokens, quote.map(|(open, _)| open))
+    }
+
+    /// Tokens of a line (1-based)
+    pub fn line(&self, line_number: usize) -> Option<&LineTokens> {
+        line_number
+            .checked_sub(1)
+            .and_then(|index| self.lines.get(index))
+    }
+
+    /// Byte offset of the `#` starting a line's comment
+    pub fn comment_start(&self, line_number: usize) -> Option<usize> {
+        self.line(line_number).and_then(|tokens| tokens.comment)
+    }
+
+    /// Check if a byte offset of a line is inside a quoted scalar
+    pub fn in_string(&self, line_number: usize, offset: usize) -> bool {
+        self.line(line_number)
+            .is_some_and(|tokens| tokens.in_string(offset))
+    }
+
+    /// Check if a line is content of a block scalar
+    pub fn in_block_scalar(&self, line_number: usize) -> bool {
+        self.line(line_number)
+            .is_some_and(|tokens| tokens.in_block_scalar)
+    }
+
+    /// Check if the character at a byte offset of a line is YAML syntax or a plain scalar
+    pub fn is_code(&self, line_number: usize, offset: usize) -> bool {
+        self.line(line_number)
+            .is_some_and(|tokens| tokens.is_code(offset))
+    }
+
+    /// Line ranges (1-based, inclusive) holding block scalar content
+    pub fn block_scalars(&self) -> &[RangeInclusive<usize>] {
+        &self.block_scalars
+    }
+
+    /// Find the flow sequences and mappings of `content`, the content the map was built
+    /// from, ordered by position. In block context a `[` or `{` only opens a collection
+    /// where a node can start, so `a[0]: x` has none. Unclosed collections are left out.
+    pub fn flow_collections(&self, content: &str) -> Vec<FlowCollection> {
+        let mut collections = Vec::new();
+        // Open collections, innermost last
+        let mut open: Vec<OpenFlow> = Vec::new();
+
+        for (index, line) in content.lines().enumerate() {
+            let line_no = index + 1;
+            let Some(tokens) = self.line(line_no) else {
+                break;
+            };
+            if tokens.in_block_scalar {
+                continue;
+            }
+            if line.starts_with("---") || line.starts_with("...") {
+                open.clear();
+            }
+
+            for (column, (offset, ch)) in line.char_indices().enumerate() {
+                if !tokens.is_code(offset) {
+                    if tokens.in_string(offset)
+                        && let Some(current) = open.last_mut()
+                    {
+                        current.content(line_no, column + 1, offset);
+                    }
+                    continue;
+                }
+
+                match ch {
+                    '[' | '{' if !open.is_empty() || Self::starts_node(&line[..offset]) => {
+                        if let Some(current) = open.last_mut() {
+                            current.content(line_no, column + 1, offset);
+                        }
+                        let collection = FlowCollection {
+                            kind: if ch == '[' {
+                                FlowKind::Sequence
+                            } else {
+                                FlowKind::Mapping
+                            },
+                            line: line_no,
+                            column: column + 1,
+                            end_line: line_no,
+                            items: 0,
+                            depth: open.len() + 1,
+                            trailing_comma: None,
+                            empty_entries: Vec::new(),
+                            keys: Vec::new(),
+                        };
+                        open.push(OpenFlow {
+                            collection,
+                            entry: None,
+                            has_key: false,
+                            last_comma: None,
+                        });
+                    }
+                    ']' | '}' => {
+                        if let Some(OpenFlow {
+                            mut collection,
+                            entry,
+                            last_comma,
+                            ..
+                        }) = open.pop()
+                        {
+                            collection.items += usize::from(entry.is_some());
+                            collection.end_line = line_no;
+                            // A comma already reported as ending an empty entry is not
+                            // reported again
+                            collection.trailing_comma = last_comma.filter(|comma| {
+                                entry.is_none() && !collection.empty_entries.contains(comma)
+                            });
+                            collections.push(collection);
+                        }
+                    }
+                    ',' => {
+                        if let Some(current) = open.last_mut() {
+                            let collection = &mut current.collection;
+                            collection.items += usize::from(current.entry.is_some());
+                            if current.entry.is_none() {
+                                collection.empty_entries.push((line_no, column + 1));
+                            }
+                            current.entry = None;
+                            current.has_key = false;
+                            current.last_comma = Some((line_no, column + 1));
+                        }
+                    }
+                    ':' if open.last().is_some_and(|current| {
+                        current.collection.kind == FlowKind::Mapping
+                            && !current.has_key
+                            && current.entry.is_some()
+                    }) && (line[offset + 1..].starts_with([' ', '\t', ',', '}'])
+                        || offset + 1 == line.len()
+                        || line[..offset].ends_with(['"', '\''])) =>
+                    {
+                        let Some(current) = open.last_mut() else {
+                            continue;
+                        };
+                        current.has_key = true;
+                        if let Some((entry_line, entry_column, start)) = current.entry
+                            && entry_line == line_no
+                            && !line[start..].starts_with(['{', '['])
+                        {
+                            let key = line[start..offset].trim_end();
+                            let key = key
+                                .strip_prefix('"')
+                                .and_then(|k| k.strip_suffix('"'))
+                                .or_else(|| {
+                                    key.strip_prefix('\'').and_then(|k| k.strip_suffix('\''))
+                                })
+                                .unwrap_or(key);
+                            current
+                                .collection
+                                .keys
+                                .push((line_no, entry_column, key.to_string()));
+                        }
+                    }
+                    ch if !ch.is_whitespace() => {
+                        if let Some(current) = open.last_mut() {
+                            current.content(line_no, column + 1, offset);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        collections.sort_by_key(|collection| (collection.line, collection.column));
+        collections
+    }
+
+    /// Check if a node can start after `before`, the text preceding it on its line, in
+    /// block context: at the start of the line or after a key, `-`, `?`, an anchor or a tag
+    fn starts_node(before: &str) -> bool {
+        if !before.is_empty() && !before.ends_with(char::is_whitespace) {
+            return false;
+        }
+        match before.split_whitespace().next_back() {
+            None => true,
+            Some(word) => {
+                word.ends_with(':')
+                    || matches!(word, "-" | "?" | "---")
+                    || word.starts_with('&')
+                    || word.starts_with('!')
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::LintContext;
+    use std::path::PathBuf;
+
+    fn tokens(content: &str) -> TokenMap {
+        let path = PathBuf::from("test.yaml");
+        LintContext::new(&path, content).tokens().clone()
+    }
+
+    #[test]
+    fn test_comments() {
+        let map =
+            tokens("# top\nkey: value # inline\nurl: http://x/#anchor\nkey: \"a # b\"  # c\n");
+        assert_eq!(map.comment_start(1), Some(0));
+        assert_eq!(map.comment_start(2), Some(11));
+        assert_eq!(map.comment_start(3), None);
+        assert_eq!(map.comment_start(4), Some(14));
+        assert!(map.in_string(4, 7));
+        assert!(!map.is_code(4, 7));
+        assert!(!map.is_code(2, 13));
+        assert!(map.is_code(2, 3));
+    }
+
+    #[test]
+    fn test_quote_escapes() {
+        let map = tokens("a: 'it''s # x'\nb: \"say \\\"hi\\\" # y\"\nc: it's # z\n");
+        assert_eq!(map.line(1).unwrap().strings, vec![3..14]);
+        assert_eq!(map.comment_start(1), None);
+        assert_eq!(map.comment_start(2), None);
+        assert_eq!(map.comment_start(3), Some(8));
+        assert!(map.line(3).unwrap().strings.is_empty());
+    }
+
+    #[test]
+    fn test_multiline_string() {
+        let map = tokens("host: \"\\\n  a.com.,\\\n  b.com.\" # end\nnext: 'x\n");
+        assert_eq!(map.line(1).unwrap().strings, vec![6..8]);
+        assert_eq!(map.line(2).unwrap().strings, vec![0..10]);
+        assert_eq!(map.line(3).unwrap().strings, vec![0..9]);
+        assert_eq!(map.comment_start(3), Some(10));
+        // An unterminated scalar is one to the end of the file
+        assert!(map.in_string(4, 7));
+    }
+
+    #[test]
+    fn test_flow_collections() {
+        let content =
+            "a: [1, [2, 3], {x: \"4, 5\"}]\nb[0]: c\nd: {\n  e: 1,\n  f: 2,\n}\n- &x [] # [a, b]\n";
+        let map = tokens(content);
+        let collections = map.flow_collections(content);
+        let summary: Vec<_> = collections
+            .iter()
+            .map(|c| {
+                (
+                    c.kind,
+                    c.line,
+                    c.column,
+                    c.items,
+                    c.depth,
+                    c.is_multi_line(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (FlowKind::Sequence, 1, 4, 3, 1, false),
+                (FlowKind::Sequence, 1, 8, 2, 2, false),
+                (FlowKind::Mapping, 1, 16, 1, 2, false),
+                (FlowKind::Mapping, 3, 4, 2, 1, true),
+                (FlowKind::Sequence, 7, 6, 0, 1, false),
+            ]
+        );
+        assert_eq!(collections[3].trailing_comma, Some((5, 7)));
+        assert_eq!(collections[0].trailing_comma, None);
+        assert!(collections.iter().all(|c| c.empty_entries.is_empty()));
+        assert_eq!(collections[2].keys, vec![(1, 17, "x".to_string())]);
+        assert_eq!(
+            collections[3].keys,
+            vec![(4, 3, "e".to_string()), (5, 3, "f".to_string())]
+        );
+        assert!(collections[0].keys.is_empty());
+
+        let content = "a: [, x,, y, ]
+b: {k: 1,,}
+";
+        let collections = tokens(content).flow_collections(content);
+        assert_eq!(collections[0].items, 2);
+        assert_eq!(collections[0].empty_entries, vec![(1, 5), (1, 9)]);
+        assert_eq!(collections[0].trailing_comma, Some((1, 12)));
+        assert_eq!(collections[1].empty_entries, vec![(2, 10)]);
+        assert_eq!(collections[1].trailing_comma, None);
+
+        let content = "{\"b\":1, 'a': {c: 2}, d:e, ? f, g: [h: 1]}\n";
+        let collections = tokens(content).flow_collections(content);
+        let keys: Vec<_> = collections[0].keys.iter().map(|k| k.2.as_str()).collect();
+        assert_eq!(keys, vec!["b", "a", "g"]);
+        assert_eq!(collections[1].keys, vec![(1, 15, "c".to_string())]);
+    }
+
+    #[test]
+    fn test_block_scalars() {
+        let map = tokens("script: |\n  echo 'a # b\n  # not a comment\nkey: v # c\n");
+        assert_eq!(map.block_scalars(), &[2..=3]);
+        assert!(map.in_block_scalar(2));
+        assert_eq!(map.comment_start(3), None);
+        assert!(map.line(2).unwrap().strings.is_empty());
+        assert!(!map.is_code(2, 2));
+        assert_eq!(map.comment_start(4), Some(7));
+        assert!(map.line(10).is_none());
+    }
+}
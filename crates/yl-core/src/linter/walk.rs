@@ -0,0 +1,88 @@
+//! Directory walking shared by linting, fixing and migration
+
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+use walkdir::WalkDir;
+
+/// Regular files below `root`, in directory order.
+///
+/// Symbolic links are skipped unless `follow_symlinks` is set; when they are followed,
+/// a link back to one of its own ancestors is reported once and not descended into.
+/// FIFOs, sockets and devices are never returned, so reading them cannot block.
+pub fn walk_files(root: &Path, follow_symlinks: bool) -> impl Iterator<Item = PathBuf> {
+    WalkDir::new(root)
+        .follow_links(follow_symlinks)
+        .into_iter()
+        .filter_map(|entry| match entry {
+            Ok(entry) => Some(entry),
+            Err(error) if error.loop_ancestor().is_some() => {
+                warn!(path = ?error.path(), "skipping symbolic link cycle");
+                None
+            }
+            Err(error) => {
+                debug!(%error, "skipping unreadable directory entry");
+                None
+            }
+        })
+        .filter(|entry| {
+            let file_type = entry.file_type();
+            if !file_type.is_file() && !file_type.is_dir() {
+                debug!(path = %entry.path().display(), "skipping symbolic link or special file");
+            }
+            file_type.is_file()
+        })
+        .map(walkdir::DirEntry::into_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn walked(root: &Path, follow_symlinks: bool) -> Vec<String> {
+        let mut files: Vec<String> = walk_files(root, follow_symlinks)
+            .map(|path| {
+                path.strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect();
+        files.sort();
+        files
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_walk_files_symlinks() {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("dir")).unwrap();
+        fs::write(root.join("dir/a.yaml"), "a: 1\n").unwrap();
+        symlink(root.join("dir/a.yaml"), root.join("link.yaml")).unwrap();
+        // A link back to its own ancestor would loop forever
+        symlink(root, root.join("dir/loop")).unwrap();
+
+        assert_eq!(walked(root, false), vec!["dir/a.yaml"]);
+        assert_eq!(walked(root, true), vec!["dir/a.yaml", "link.yaml"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_walk_files_skips_fifos() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let root = temp_dir.path();
+        fs::write(root.join("a.yaml"), "a: 1\n").unwrap();
+        let status = std::process::Command::new("mkfifo")
+            .arg(root.join("pipe.yaml"))
+            .status();
+        if !status.is_ok_and(|status| status.success()) {
+            return; // mkfifo is not available
+        }
+
+        assert_eq!(walked(root, false), vec!["a.yaml"]);
+    }
+}
@@ -0,0 +1,626 @@
+//! Message catalog: the text of problem messages and CLI output, by message ID.
+//!
+//! Rules report a [`Message`], an ID with the values of its `{name}` placeholders, and the
+//! text is looked up in the active [`Catalog`]. English is built in; a translation is a
+//! YAML file mapping message IDs to translated text, and any message it leaves out stays
+//! English:
+//!
+//! ```yaml
+//! lang: de
+//! messages:
+//!   trailing-spaces.found: Leerzeichen am Zeilenende
+//!   line-length.too-long: Zeile zu lang ({length} > {max} {unit})
+//! ```
+
+use eyre::{Context, Result, eyre};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+/// English text of every message, by ID
+const ENGLISH: &[(&str, &str)] = &[
+    // Words used inside other messages
+    ("unit.chars", "characters"),
+    ("unit.bytes", "bytes"),
+    ("unit.display-width", "columns"),
+    ("collection.flow-sequence", "flow sequence"),
+    ("collection.flow-mapping", "flow mapping"),
+    // Style rules
+    (
+        "line-length.too-long",
+        "line too long ({length} > {max} {unit})",
+    ),
+    ("trailing-spaces.found", "trailing whitespace"),
+    (
+        "empty-lines.too-many-at-start",
+        "too many blank lines at beginning of file ({count} > {max})",
+    ),
+    (
+        "empty-lines.too-many-at-end",
+        "too many blank lines at end of file ({count} > {max})",
+    ),
+    (
+        "empty-lines.too-many",
+        "too many blank lines ({count} > {max})",
+    ),
+    (
+        "indentation.wrong",
+        "wrong indentation: expected multiple of {spaces}, got {actual}",
+    ),
+    (
+        "indentation.sequence-indented",
+        "sequence should be indented under its key",
+    ),
+    (
+        "indentation.sequence-aligned",
+        "sequence should be aligned with its key",
+    ),
+    (
+        "new-line-at-end-of-file.missing",
+        "missing newline at end of file",
+    ),
+    (
+        "no-tabs.in-indentation",
+        "found tab character in indentation",
+    ),
+    (
+        "no-tabs.at-end-of-line",
+        "found tab character at end of line",
+    ),
+    ("no-tabs.in-comment", "found tab character in comment"),
+    ("no-tabs.in-value", "found tab character in value"),
+    (
+        "value-length.block-scalar-too-long",
+        "block scalar too long ({length} > {max} characters)",
+    ),
+    (
+        "value-length.too-long",
+        "value too long ({length} > {max} characters)",
+    ),
+    // Formatting rules
+    (
+        "brackets.too-few-spaces-inside-empty",
+        "too few spaces inside empty brackets, expected at least {min}",
+    ),
+    (
+        "brackets.too-many-spaces-inside-empty",
+        "too many spaces inside empty brackets, expected at most {max}",
+    ),
+    (
+        "brackets.too-few-spaces-inside",
+        "too few spaces inside brackets, expected at least {min}",
+    ),
+    (
+        "brackets.too-many-spaces-inside",
+        "too many spaces inside brackets, expected at most {max}",
+    ),
+    (
+        "braces.too-few-spaces-inside-empty",
+        "too few spaces inside empty braces, expected at least {min}",
+    ),
+    (
+        "braces.too-many-spaces-inside-empty",
+        "too many spaces inside empty braces, expected at most {max}",
+    ),
+    (
+        "braces.too-few-spaces-inside",
+        "too few spaces inside braces, expected at least {min}",
+    ),
+    (
+        "braces.too-many-spaces-inside",
+        "too many spaces inside braces, expected at most {max}",
+    ),
+    (
+        "colons.too-many-spaces-before",
+        "too many spaces before colon, expected at most {max}",
+    ),
+    (
+        "colons.too-few-spaces-after",
+        "too few spaces after colon, expected at least {min}",
+    ),
+    (
+        "colons.too-many-spaces-after",
+        "too many spaces after colon, expected at most {max}",
+    ),
+    (
+        "commas.too-many-spaces-before",
+        "too many spaces before comma, expected at most {max}",
+    ),
+    (
+        "commas.too-few-spaces-after",
+        "too few spaces after comma, expected at least {min}",
+    ),
+    (
+        "commas.too-many-spaces-after",
+        "too many spaces after comma, expected at most {max}",
+    ),
+    ("hyphens.missing-space", "missing space after hyphen"),
+    (
+        "hyphens.too-many-spaces-after",
+        "too many spaces after hyphen, expected at most {max}",
+    ),
+    (
+        "flow-collections.too-many-entries",
+        "too many entries in {collection} ({count} > {max}), use block style",
+    ),
+    (
+        "flow-collections.too-deep",
+        "{collection} nested too deeply, expected at most {max} level(s) of flow collections",
+    ),
+    (
+        "flow-collections.multi-line",
+        "{collection} spans multiple lines, use block style",
+    ),
+    ("dangling-commas.empty-entry", "empty entry in {collection}"),
+    ("dangling-commas.trailing", "trailing comma in {collection}"),
+    // Semantic rules
+    (
+        "truthy.not-allowed",
+        "truthy value should be one of [{allowed}], not \"{value}\"",
+    ),
+    ("quoted-strings.single", "string should be single-quoted"),
+    ("quoted-strings.double", "string should be double-quoted"),
+    ("quoted-strings.not-needed", "string should not be quoted"),
+    (
+        "key-ordering.wrong",
+        "wrong ordering of key \"{key}\" in mapping",
+    ),
+    (
+        "float-values.scientific-notation",
+        "scientific notation is forbidden",
+    ),
+    (
+        "float-values.numeral-before-decimal",
+        "decimal number should have at least one numeral before decimal point",
+    ),
+    (
+        "octal-values.implicit",
+        "found implicit octal value \"{value}\"",
+    ),
+    (
+        "octal-values.explicit",
+        "found explicit octal value \"{value}\"",
+    ),
+    ("required-keys.missing", "missing required key \"{key}\""),
+    ("forbidden-keys.found", "found forbidden key \"{key}\""),
+    (
+        "sequence-duplicates.found",
+        "duplicate sequence item \"{value}\" (first on line {first_line})",
+    ),
+    (
+        "sequence-ordering.wrong",
+        "sequence item \"{value}\" should come before \"{previous}\"",
+    ),
+    (
+        "date-format.invalid",
+        "invalid date \"{value}\" for key \"{key}\" (expected ISO-8601)",
+    ),
+    ("quoted-keys.required", "key \"{key}\" should be quoted"),
+    ("quoted-keys.not-needed", "key {key} should not be quoted"),
+    (
+        "empty-collections.mapping-as-empty",
+        "empty mapping should be written as an empty value",
+    ),
+    (
+        "empty-collections.mapping-as-null",
+        "empty mapping should be written as null",
+    ),
+    (
+        "empty-collections.sequence-as-empty",
+        "empty sequence should be written as an empty value",
+    ),
+    (
+        "empty-collections.sequence-as-null",
+        "empty sequence should be written as null",
+    ),
+    (
+        "empty-collections.empty-as-mapping",
+        "empty value should be written as {}",
+    ),
+    (
+        "empty-collections.empty-as-sequence",
+        "empty value should be written as []",
+    ),
+    (
+        "empty-collections.empty-is-ambiguous",
+        "empty value should be written as {} or []",
+    ),
+    // Syntax rules
+    (
+        "key-duplicates.found",
+        "found duplicate key \"{key}\" (first occurrence at line {first_line})",
+    ),
+    (
+        "document-structure.missing-start",
+        "missing document start \"---\"",
+    ),
+    (
+        "document-structure.missing-end",
+        "missing document end \"...\" or \"---\"",
+    ),
+    (
+        "document-count.too-few",
+        "found {count} document, expected at least {min}",
+    ),
+    (
+        "document-count.too-few-plural",
+        "found {count} documents, expected at least {min}",
+    ),
+    (
+        "document-count.too-many",
+        "found {count} documents, expected at most {max}",
+    ),
+    (
+        "anchors.forbidden-anchor",
+        "found forbidden anchor \"{name}\"",
+    ),
+    (
+        "anchors.duplicate-anchor",
+        "found duplicate anchor \"{name}\"",
+    ),
+    (
+        "anchors.forbidden-alias",
+        "found forbidden alias \"{name}\"",
+    ),
+    (
+        "anchors.alias-before-anchor",
+        "found alias \"{name}\" before its anchor",
+    ),
+    (
+        "anchors.undefined-alias",
+        "found undefined alias \"{name}\"",
+    ),
+    (
+        "anchors.too-many-aliases",
+        "too many aliases in document (max {max})",
+    ),
+    ("anchors.unused-anchor", "found unused anchor \"{name}\""),
+    (
+        "anchors.merge-not-alias",
+        "merge key value \"{value}\" must be an alias to a mapping",
+    ),
+    (
+        "anchors.merge-not-mapping",
+        "merge key alias \"{name}\" does not refer to a mapping",
+    ),
+    ("yaml-syntax.error", "syntax error: {error}"),
+    (
+        "comments.missing-space",
+        "missing starting space in comment",
+    ),
+    (
+        "comments.too-few-spaces-before",
+        "too few spaces before comment, expected at least {min}",
+    ),
+    ("directives.missing-yaml", "missing %YAML directive"),
+    (
+        "directives.forbidden-yaml",
+        "found forbidden %YAML directive",
+    ),
+    (
+        "directives.duplicate-yaml",
+        "found duplicate %YAML directive",
+    ),
+    (
+        "directives.wrong-version",
+        "found %YAML version \"{version}\" (expected \"{expected}\")",
+    ),
+    (
+        "directives.forbidden-tag-handle",
+        "found forbidden tag handle \"{handle}\"",
+    ),
+    ("directives.unknown", "found unknown directive \"%{name}\""),
+    (
+        "directives.undeclared-tag-handle",
+        "found undeclared tag handle \"{handle}\"",
+    ),
+    // Reported by the linter itself
+    (
+        "rule-timeout.skipped",
+        "rule {rule} skipped after running longer than {timeout} ms",
+    ),
+    // Summary of a run
+    ("summary.no-problems", "No problems found"),
+    ("summary.found", "Found {counts}"),
+    ("summary.error", "{count} error"),
+    ("summary.errors", "{count} errors"),
+    ("summary.warning", "{count} warning"),
+    ("summary.warnings", "{count} warnings"),
+    ("summary.info", "{count} info"),
+    ("summary.file-error", "{count} file could not be linted"),
+    ("summary.file-errors", "{count} files could not be linted"),
+    (
+        "summary.suppressed",
+        "{summary} ({count} problem suppressed by directives)",
+    ),
+    (
+        "summary.suppressed-plural",
+        "{summary} ({count} problems suppressed by directives)",
+    ),
+    (
+        "summary.suppressed-count",
+        "{count} problem suppressed by directives",
+    ),
+    (
+        "summary.suppressed-count-plural",
+        "{count} problems suppressed by directives",
+    ),
+    (
+        "summary.stopped",
+        "Stopped after {max} problems (--max-problems); remaining files were not linted",
+    ),
+    (
+        "summary.skipped-generated",
+        "Skipped {count} generated file (skip-generated)",
+    ),
+    (
+        "summary.skipped-generated-plural",
+        "Skipped {count} generated files (skip-generated)",
+    ),
+    // Output of --fix
+    ("fix.would-fix", "Would fix: {path}"),
+    ("fix.fixed", "Fixed: {path}"),
+    ("fix.refused", "Refusing to fix {path}: {reason}"),
+    ("fix.would-fix-count", "Would fix {count} file"),
+    ("fix.would-fix-count-plural", "Would fix {count} files"),
+    ("fix.fixed-count", "Fixed {count} file"),
+    ("fix.fixed-count-plural", "Fixed {count} files"),
+    (
+        "fix.refused-count",
+        "Refused to fix {count} file that failed verification (--verify)",
+    ),
+    (
+        "fix.refused-count-plural",
+        "Refused to fix {count} files that failed verification (--verify)",
+    ),
+];
+
+/// Catalog used to render messages, English until [`set_catalog`] is called
+static ACTIVE: RwLock<Option<Catalog>> = RwLock::new(None);
+
+/// English text of a message, if the ID is known
+pub fn english(id: &str) -> Option<&'static str> {
+    ENGLISH
+        .iter()
+        .find(|(known, _)| *known == id)
+        .map(|(_, text)| *text)
+}
+
+/// Render every message from now on with `catalog` (`--lang`)
+pub fn set_catalog(catalog: Catalog) {
+    *ACTIVE.write().unwrap_or_else(|e| e.into_inner()) = Some(catalog);
+}
+
+/// Directory holding the translations `--lang <code>` looks for, `<code>.yaml`
+pub fn translations_dir() -> Option<PathBuf> {
+    dirs::config_local_dir()
+        .or_else(|| dirs::home_dir().map(|home| home.join(".config")))
+        .map(|dir| dir.join("yl").join("translations"))
+}
+
+/// A message: its ID in the catalog and the values of its placeholders
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    pub id: &'static str,
+    pub args: Vec<(&'static str, String)>,
+}
+
+impl Message {
+    /// Create a message without placeholders
+    pub fn new(id: &'static str) -> Self {
+        Self {
+            id,
+            args: Vec::new(),
+        }
+    }
+
+    /// Set the value of the `{name}` placeholder
+    pub fn arg(mut self, name: &'static str, value: impl fmt::Display) -> Self {
+        self.args.push((name, value.to_string()));
+        self
+    }
+
+    /// Text of the message in the active catalog
+    pub fn text(&self) -> String {
+        match &*ACTIVE.read().unwrap_or_else(|e| e.into_inner()) {
+            Some(catalog) => catalog.render(self),
+            None => Catalog::default().render(self),
+        }
+    }
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text())
+    }
+}
+
+/// Translations of messages into one language; messages without a translation are English
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Catalog {
+    /// Language of the translations, such as `de`
+    #[serde(default)]
+    pub lang: String,
+    /// Translated text by message ID, with the placeholders of the English text
+    #[serde(default)]
+    pub messages: BTreeMap<String, String>,
+}
+
+impl Catalog {
+    /// Read a translations file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read translations {}", path.display()))?;
+        serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse translations {}", path.display()))
+    }
+
+    /// The catalog `--lang` selects: `en` for English, the path of a translations file,
+    /// or a language code with a `<code>.yaml` file in [`translations_dir`]
+    pub fn for_lang(lang: &str) -> Result<Self> {
+        if lang.is_empty() || lang == "en" {
+            return Ok(Self::default());
+        }
+        let path = Path::new(lang);
+        if path
+            .extension()
+            .is_some_and(|ext| ext == "yaml" || ext == "yml")
+        {
+            return Self::load(path);
+        }
+        match translations_dir().map(|dir| dir.join(format!("{lang}.yaml"))) {
+            Some(path) if path.is_file() => Self::load(&path),
+            Some(path) => Err(eyre!(
+                "no translations for language \"{lang}\" (looked for {})",
+                path.display()
+            )),
+            None => Err(eyre!("no translations for language \"{lang}\"")),
+        }
+    }
+
+    /// The English catalog, every message translated to itself: the starting point of a
+    /// new translation
+    pub fn english() -> Self {
+        Self {
+            lang: "en".to_string(),
+            messages: ENGLISH
+                .iter()
+                .map(|(id, text)| (id.to_string(), text.to_string()))
+                .collect(),
+        }
+    }
+
+    /// Text of a message, with its placeholders filled in. Unknown IDs render as the ID
+    /// followed by their values.
+    pub fn render(&self, message: &Message) -> String {
+        let Some(template) = self
+            .messages
+            .get(message.id)
+            .map(String::as_str)
+            .or_else(|| english(message.id))
+        else {
+            let args: Vec<String> = message
+                .args
+                .iter()
+                .map(|(_, value)| value.clone())
+                .collect();
+            return format!("{} {}", message.id, args.join(" "))
+                .trim_end()
+                .to_string();
+        };
+
+        // One pass, so that values containing braces are left as they are
+        let mut text = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(open) = rest.find('{') {
+            text.push_str(&rest[..open]);
+            let after = &rest[open + 1..];
+            let value = after.find('}').and_then(|close| {
+                let name = &after[..close];
+                message
+                    .args
+                    .iter()
+                    .find(|(arg, _)| *arg == name)
+                    .map(|(_, value)| (value, close))
+            });
+            match value {
+                Some((value, close)) => {
+                    text.push_str(value);
+                    rest = &after[close + 1..];
+                }
+                None => {
+                    text.push('{');
+                    rest = after;
+                }
+            }
+        }
+        text.push_str(rest);
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_render_english() {
+        let catalog = Catalog::default();
+        let message = Message::new("line-length.too-long")
+            .arg("length", 90)
+            .arg("max", 80)
+            .arg("unit", "characters");
+        assert_eq!(
+            catalog.render(&message),
+            "line too long (90 > 80 characters)"
+        );
+        assert_eq!(
+            catalog.render(&Message::new("unknown.id").arg("value", 1)),
+            "unknown.id 1"
+        );
+    }
+
+    #[test]
+    fn test_render_translation() {
+        let catalog: Catalog = serde_yaml::from_str(
+            "lang: de\nmessages:\n  line-length.too-long: Zeile zu lang ({length} > {max})\n",
+        )
+        .unwrap();
+        let message = Message::new("line-length.too-long")
+            .arg("length", 90)
+            .arg("max", 80);
+        assert_eq!(catalog.render(&message), "Zeile zu lang (90 > 80)");
+        // Messages without a translation stay English
+        assert_eq!(
+            catalog.render(&Message::new("trailing-spaces.found")),
+            "trailing whitespace"
+        );
+        assert!(serde_yaml::from_str::<Catalog>("language: de\n").is_err());
+    }
+
+    #[test]
+    fn test_for_lang() {
+        assert_eq!(Catalog::for_lang("en").unwrap(), Catalog::default());
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("fr.yaml");
+        std::fs::write(
+            &path,
+            "lang: fr\nmessages:\n  summary.no-problems: Aucun problème\n",
+        )
+        .unwrap();
+        let catalog = Catalog::for_lang(path.to_str().unwrap()).unwrap();
+        assert_eq!(catalog.lang, "fr");
+        assert!(Catalog::for_lang("xx-does-not-exist").is_err());
+    }
+
+    #[test]
+    fn test_english_catalog_covers_every_message() {
+        let ids: HashSet<&str> = ENGLISH.iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids.len(), ENGLISH.len(), "duplicate message IDs");
+        assert_eq!(Catalog::english().messages.len(), ENGLISH.len());
+
+        let sources = [
+            include_str!("rules/style.rs"),
+            include_str!("rules/formatting.rs"),
+            include_str!("rules/semantic.rs"),
+            include_str!("rules/syntax.rs"),
+            include_str!("linter/problem.rs"),
+        ];
+        let used = Regex::new(r#"Message::new\(\s*"([a-z0-9.-]+)""#).unwrap();
+        for source in sources {
+            for id in used.captures_iter(source) {
+                assert!(
+                    ids.contains(&id[1]),
+                    "message {} has no English text",
+                    &id[1]
+                );
+            }
+        }
+    }
+}
@@ -33,6 +33,11 @@ pub enum Directive {
         rule: String,
         params: HashMap<String, String>,
     },
+    /// Override rule parameters for the whole file (must precede any YAML content)
+    Configure {
+        rule: String,
+        params: HashMap<String, String>,
+    },
     /// Ignore entire file
     IgnoreFile,
     /// Ignore rules for current YAML section
@@ -51,7 +56,7 @@ impl CommentProcessor {
     /// Create a new comment processor
     pub fn new() -> Self {
         let directive_regex =
-            Regex::new(r"#\s*yl:(disable-line|ignore-file|ignore-section|disable|enable|config|set)(?:\s+(.+))?")
+            Regex::new(r"#\s*yl:(disable-line|ignore-file|ignore-section|disable|enable|configure|config|set)(?:\s+(.+))?")
                 .expect("Invalid directive regex");
 
         let param_regex = Regex::new(r"([a-zA-Z0-9_-]+)\.([a-zA-Z0-9_-]+)=([^\s,]+)")
@@ -78,6 +83,7 @@ impl CommentProcessor {
                 "enable" => self.parse_enable(args, Scope::Block),
                 "set" => self.parse_set(args),
                 "config" => self.parse_config(args),
+                "configure" => self.parse_configure(args),
                 "ignore-file" => Ok(Some(Directive::IgnoreFile)),
                 "ignore-section" => self.parse_ignore_section(args),
                 _ => Ok(None),
@@ -155,6 +161,34 @@ impl CommentProcessor {
         Ok(Some(Directive::Config { rule, params }))
     }
 
+    /// Parse configure directive (rule param1=value1 param2=value2)
+    fn parse_configure(&self, args: &str) -> Result<Option<Directive>> {
+        let mut parts = args.split_whitespace();
+        let rule = parts
+            .next()
+            .ok_or_else(|| eyre::eyre!("Configure directive requires rule name"))?
+            .to_string();
+
+        let mut params = HashMap::new();
+        for param_str in parts.flat_map(|part| part.split(',')) {
+            let Some((key, value)) = param_str.split_once('=') else {
+                return Err(eyre::eyre!(
+                    "Invalid configure directive parameter '{}'. Expected: param=value",
+                    param_str
+                ));
+            };
+            params.insert(key.to_string(), value.to_string());
+        }
+
+        if params.is_empty() {
+            return Err(eyre::eyre!(
+                "Configure directive requires at least one param=value"
+            ));
+        }
+
+        Ok(Some(Directive::Configure { rule, params }))
+    }
+
     /// Parse ignore-section directive
     fn parse_ignore_section(&self, args: &str) -> Result<Option<Directive>> {
         let rules = if args.is_empty() {
@@ -291,6 +325,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_configure() {
+        let processor = processor();
+        let directive = processor
+            .parse_directive("# yl:configure line-length max=120 allow-non-breakable-words=false")
+            .unwrap()
+            .unwrap();
+
+        match directive {
+            Directive::Configure { rule, params } => {
+                assert_eq!(rule, "line-length");
+                assert_eq!(params.get("max"), Some(&"120".to_string()));
+                assert_eq!(
+                    params.get("allow-non-breakable-words"),
+                    Some(&"false".to_string())
+                );
+            }
+            _ => panic!("Expected Configure directive, got: {:?}", directive),
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid_configure() {
+        let processor = processor();
+        assert!(processor.parse_directive("# yl:configure").is_err());
+        assert!(
+            processor
+                .parse_directive("# yl:configure line-length")
+                .is_err()
+        );
+        assert!(
+            processor
+                .parse_directive("# yl:configure line-length max")
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_parse_ignore_file() {
         let processor = processor();
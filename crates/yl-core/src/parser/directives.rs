@@ -0,0 +1,199 @@
+//! `%YAML` and `%TAG` directives, which precede the start of a document
+
+use std::ops::Range;
+
+/// A directive found in the prelude of a document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum YamlDirective {
+    /// `%YAML <version>`
+    Yaml { line: usize, version: String },
+    /// `%TAG <handle> <prefix>`
+    Tag {
+        line: usize,
+        handle: String,
+        prefix: String,
+    },
+    /// Any other (reserved) directive, e.g. `%FOO bar`
+    Reserved { line: usize, name: String },
+}
+
+impl YamlDirective {
+    /// Line of the directive (1-based)
+    pub fn line(&self) -> usize {
+        match self {
+            Self::Yaml { line, .. } | Self::Tag { line, .. } | Self::Reserved { line, .. } => *line,
+        }
+    }
+}
+
+/// A document of a YAML stream with the directives that apply to it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentPrelude {
+    /// Directives preceding the document
+    pub directives: Vec<YamlDirective>,
+    /// Lines of the document, including its directives and markers (1-based, end exclusive)
+    pub lines: Range<usize>,
+    /// Whether the document has content besides markers, directives and comments; an
+    /// empty document is skipped by most tools
+    pub has_content: bool,
+}
+
+/// Split content into documents, collecting the directives of each.
+///
+/// Directives are only recognized before the first document or after a `...` end marker,
+/// as in the YAML spec; a `%` line elsewhere is document content.
+pub fn parse_documents(content: &str) -> Vec<DocumentPrelude> {
+    let mut documents = Vec::new();
+    let mut current = DocumentPrelude {
+        directives: Vec::new(),
+        lines: 1..1,
+        has_content: false,
+    };
+    let mut in_prelude = true;
+    let mut has_body = false;
+    let mut line_count = 0;
+
+    for (index, line) in content.lines().enumerate() {
+        let line_no = index + 1;
+        line_count = line_no;
+
+        if in_prelude && line.starts_with('%') {
+            current.directives.push(parse_directive(line_no, line));
+            continue;
+        }
+
+        let is_marker = |marker: &str| {
+            line.strip_prefix(marker)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with([' ', '\t']))
+        };
+        if is_marker("---") {
+            if has_body {
+                current.lines.end = line_no;
+                documents.push(std::mem::replace(
+                    &mut current,
+                    DocumentPrelude {
+                        directives: Vec::new(),
+                        lines: line_no..line_no,
+                        has_content: false,
+                    },
+                ));
+            }
+            // A node may start on the marker line, as in `--- !tag` or `--- text`
+            let rest = line[3..].trim();
+            current.has_content = !rest.is_empty() && !rest.starts_with('#');
+            has_body = true;
+            in_prelude = false;
+            continue;
+        }
+        if is_marker("...") {
+            current.lines.end = line_no + 1;
+            documents.push(std::mem::replace(
+                &mut current,
+                DocumentPrelude {
+                    directives: Vec::new(),
+                    lines: line_no + 1..line_no + 1,
+                    has_content: false,
+                },
+            ));
+            has_body = false;
+            in_prelude = true;
+            continue;
+        }
+
+        let trimmed = line.trim();
+        if !trimmed.is_empty() && !trimmed.starts_with('#') {
+            current.has_content = true;
+            has_body = true;
+            in_prelude = false;
+        }
+    }
+
+    if has_body || !current.directives.is_empty() || documents.is_empty() {
+        current.lines.end = line_count + 1;
+        documents.push(current);
+    }
+    documents
+}
+
+/// Parse a single `%` line
+fn parse_directive(line_no: usize, line: &str) -> YamlDirective {
+    let content = line.split(" #").next().unwrap_or(line);
+    let mut parts = content[1..].split_whitespace();
+    let name = parts.next().unwrap_or_default();
+
+    match name {
+        "YAML" => YamlDirective::Yaml {
+            line: line_no,
+            version: parts.next().unwrap_or_default().to_string(),
+        },
+        "TAG" => YamlDirective::Tag {
+            line: line_no,
+            handle: parts.next().unwrap_or_default().to_string(),
+            prefix: parts.next().unwrap_or_default().to_string(),
+        },
+        _ => YamlDirective::Reserved {
+            line: line_no,
+            name: name.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_documents_with_directives() {
+        let content = "%YAML 1.2\n%TAG !e! tag:example.com,2024: # ours\n---\na: 1\n...\n%YAML 1.1\n---\nb: 2\n---\nc: 3\n";
+        let documents = parse_documents(content);
+
+        assert_eq!(documents.len(), 3);
+        assert_eq!(
+            documents[0].directives,
+            vec![
+                YamlDirective::Yaml {
+                    line: 1,
+                    version: "1.2".to_string()
+                },
+                YamlDirective::Tag {
+                    line: 2,
+                    handle: "!e!".to_string(),
+                    prefix: "tag:example.com,2024:".to_string()
+                },
+            ]
+        );
+        assert_eq!(documents[0].lines, 1..6);
+        assert_eq!(documents[1].directives[0].line(), 6);
+        assert_eq!(documents[1].lines, 6..9);
+        assert!(documents[2].directives.is_empty());
+        assert_eq!(documents[2].lines, 9..11);
+    }
+
+    #[test]
+    fn test_parse_documents_content() {
+        let documents =
+            parse_documents("# header\n---\na: 1\n---\n# only a comment\n--- text\n---\n");
+        let content: Vec<bool> = documents.iter().map(|d| d.has_content).collect();
+        assert_eq!(content, vec![true, false, true, false]);
+
+        let documents = parse_documents("");
+        assert_eq!(documents.len(), 1);
+        assert!(!documents[0].has_content);
+    }
+
+    #[test]
+    fn test_parse_documents_percent_in_body() {
+        let documents = parse_documents("a: 1\n%FOO\n");
+        assert_eq!(documents.len(), 1);
+        assert!(documents[0].directives.is_empty());
+
+        let documents = parse_documents("%FOO bar\n---\n");
+        assert_eq!(
+            documents[0].directives,
+            vec![YamlDirective::Reserved {
+                line: 1,
+                name: "FOO".to_string()
+            }]
+        );
+    }
+}
@@ -0,0 +1,5 @@
+pub mod comments;
+pub mod directives;
+
+pub use comments::{CommentProcessor, Directive, Scope};
+pub use directives::{DocumentPrelude, YamlDirective, parse_documents};
@@ -0,0 +1,106 @@
+//! Reading of files to lint
+//!
+//! On request, files at least as large as a threshold are memory-mapped rather than copied
+//! into a `String`, so linting a multi-gigabyte corpus does not hold a second copy of every
+//! large file on the heap.
+//!
+//! A mapping is only sound while nothing else changes the file: bytes changed under a
+//! borrowed `&str` break its guarantees, and reading a page of a file truncated after it
+//! was mapped kills the process with `SIGBUS`. Mapping is therefore never the default, and
+//! is meant for files that stay unchanged while they are linted, such as a CI checkout.
+
+use memmap2::Mmap;
+use std::fs::{self, File};
+use std::io;
+use std::path::Path;
+
+/// Content of a file, read into memory or mapped from it
+#[derive(Debug)]
+pub enum FileContent {
+    Owned(String),
+    /// A mapping of the file, which must not change while the mapping lives
+    Mapped(Mmap),
+}
+
+impl FileContent {
+    /// Whether the content is mapped from the file rather than copied
+    pub fn is_mapped(&self) -> bool {
+        matches!(self, FileContent::Mapped(_))
+    }
+
+    /// The content as bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            FileContent::Owned(content) => content.as_bytes(),
+            FileContent::Mapped(map) => map,
+        }
+    }
+
+    /// The content as text, validating mapped content as UTF-8
+    pub fn as_str(&self) -> io::Result<&str> {
+        match self {
+            FileContent::Owned(content) => Ok(content),
+            FileContent::Mapped(map) => {
+                std::str::from_utf8(map).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            }
+        }
+    }
+}
+
+/// Read a file as UTF-8, mapping it if it is at least `mmap_threshold` bytes long.
+///
+/// Callers passing a threshold must make sure that no mapped file is changed or truncated
+/// while its content lives; callers that write the file back should pass `None`.
+pub fn read_file(path: &Path, mmap_threshold: Option<u64>) -> io::Result<FileContent> {
+    let file = File::open(path)?;
+    let len = file.metadata()?.len();
+    if len == 0 || mmap_threshold.is_none_or(|threshold| len < threshold) {
+        return fs::read_to_string(path).map(FileContent::Owned);
+    }
+
+    // SAFETY: the caller guarantees that the file does not change while it is mapped
+    let map = unsafe { Mmap::map(&file)? };
+    #[cfg(unix)]
+    let _ = map.advise(memmap2::Advice::Sequential);
+    std::str::from_utf8(&map).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    Ok(FileContent::Mapped(map))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_read_file_maps_above_threshold() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("a.yaml");
+        fs::write(&path, "key: value\n").unwrap();
+
+        let content = read_file(&path, Some(4)).unwrap();
+        assert!(content.is_mapped());
+        assert_eq!(content.as_str().unwrap(), "key: value\n");
+        assert_eq!(content.as_bytes(), b"key: value\n");
+
+        let content = read_file(&path, Some(1024)).unwrap();
+        assert!(!content.is_mapped());
+        assert_eq!(content.as_str().unwrap(), "key: value\n");
+        assert!(!read_file(&path, None).unwrap().is_mapped());
+
+        let empty = dir.path().join("empty.yaml");
+        fs::write(&empty, "").unwrap();
+        assert_eq!(read_file(&empty, Some(0)).unwrap().as_str().unwrap(), "");
+    }
+
+    #[test]
+    fn test_read_file_invalid_utf8() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("a.yaml");
+        fs::write(&path, b"key: \xff\xfe\n").unwrap();
+
+        for threshold in [Some(1), None] {
+            let error = read_file(&path, threshold).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        }
+    }
+}
@@ -21,10 +21,59 @@ pub fn is_comment_only_line(line: &str) -> bool {
 }
 
 /// Extract the comment portion from a line, if any
+#[allow(dead_code)] // Function is part of API for future phases
 pub fn extract_comment(line: &str) -> Option<&str> {
     line.find('#').map(|pos| &line[pos..])
 }
 
+/// Get the part of a line before its comment, ignoring `#` inside quoted scalars.
+///
+/// A `#` only starts a comment at the beginning of the line or after whitespace.
+pub fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut previous = ' ';
+
+    for (pos, ch) in line.char_indices() {
+        match (quote, ch) {
+            (None, '"' | '\'') if is_quote_start(previous) => quote = Some(ch),
+            (Some(open), _) if ch == open && !(open == '"' && previous == '\\') => quote = None,
+            (None, '#') if previous.is_whitespace() => return &line[..pos],
+            _ => {}
+        }
+        previous = ch;
+    }
+
+    line
+}
+
+/// Find the byte position of the colon separating a mapping key from its value,
+/// ignoring colons inside quoted scalars and colons not followed by whitespace
+pub fn find_mapping_colon(line: &str) -> Option<usize> {
+    let mut quote = None;
+    let mut previous = ' ';
+    let mut chars = line.char_indices().peekable();
+
+    while let Some((pos, ch)) = chars.next() {
+        match (quote, ch) {
+            (None, '"' | '\'') if is_quote_start(previous) => quote = Some(ch),
+            (Some(open), _) if ch == open && !(open == '"' && previous == '\\') => quote = None,
+            (None, ':') if chars.peek().is_none_or(|(_, next)| next.is_whitespace()) => {
+                return Some(pos);
+            }
+            _ => {}
+        }
+        previous = ch;
+    }
+
+    None
+}
+
+/// Whether a quote following `previous` opens a quoted scalar (rather than being
+/// part of a plain scalar such as `it's`)
+pub(crate) fn is_quote_start(previous: char) -> bool {
+    previous.is_whitespace() || matches!(previous, '-' | ':' | ',' | '[' | '{' | '?')
+}
+
 /// Check if a line has trailing whitespace
 pub fn has_trailing_whitespace(line: &str) -> bool {
     !line.is_empty() && line.ends_with(|c: char| c.is_whitespace())
@@ -92,6 +141,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_strip_comment() {
+        assert_eq!(strip_comment("key: value # comment"), "key: value ");
+        assert_eq!(strip_comment("# full comment"), "");
+        assert_eq!(strip_comment("key: \"a # b\""), "key: \"a # b\"");
+        assert_eq!(
+            strip_comment("url: http://x/#anchor"),
+            "url: http://x/#anchor"
+        );
+        assert_eq!(strip_comment("key: 'it''s' # c"), "key: 'it''s' ");
+        assert_eq!(strip_comment("key: it's # c"), "key: it's ");
+    }
+
+    #[test]
+    fn test_find_mapping_colon() {
+        assert_eq!(find_mapping_colon("key: value"), Some(3));
+        assert_eq!(find_mapping_colon("key:"), Some(3));
+        assert_eq!(find_mapping_colon("\"a: b\": value"), Some(6));
+        assert_eq!(find_mapping_colon("url: http://example.com"), Some(3));
+        assert_eq!(find_mapping_colon("- just a scalar"), None);
+        assert_eq!(find_mapping_colon("time: 12:30"), Some(4));
+    }
+
     #[test]
     fn test_has_trailing_whitespace() {
         assert!(!has_trailing_whitespace(""));
@@ -0,0 +1,1224 @@
+use super::{ConfigValue, ParamSchema, Rule, RuleConfig, RuleDocs};
+use crate::linter::{FlowCollection, FlowKind, Level, LintContext, Problem};
+use crate::messages::Message;
+use eyre::Result;
+
+/// Rule that checks bracket spacing and style
+#[derive(Debug, Default)]
+pub struct BracketsRule;
+
+impl BracketsRule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Rule for BracketsRule {
+    fn id(&self) -> &'static str {
+        "brackets"
+    }
+
+    fn description(&self) -> &'static str {
+        "Controls the use of brackets within arrays"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "Controls spacing inside the brackets of flow sequences.",
+            params: &[
+                (
+                    "min-spaces-inside",
+                    "minimum number of spaces after `[` and before `]`",
+                ),
+                (
+                    "max-spaces-inside",
+                    "maximum number of spaces after `[` and before `]`",
+                ),
+                (
+                    "min-spaces-inside-empty",
+                    "minimum number of spaces inside empty brackets",
+                ),
+                (
+                    "max-spaces-inside-empty",
+                    "maximum number of spaces inside empty brackets",
+                ),
+            ],
+            failing: "---\nitems: [   1, 2   ]\n",
+            passing: "---\nitems: [1, 2]\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        let mut problems = Vec::new();
+
+        let min_spaces_inside = config.get_int("min-spaces-inside").unwrap_or(0) as usize;
+        let max_spaces_inside = config.get_int("max-spaces-inside").unwrap_or(1) as usize;
+        let min_spaces_inside_empty =
+            config.get_int("min-spaces-inside-empty").unwrap_or(0) as usize;
+        let max_spaces_inside_empty =
+            config.get_int("max-spaces-inside-empty").unwrap_or(0) as usize;
+
+        for (line_no, line) in context.content.lines().enumerate() {
+            let line_number = line_no + 1;
+
+            // Find all bracket pairs in the line
+            let mut bracket_positions = Vec::new();
+            let chars: Vec<char> = line.chars().collect();
+
+            for (i, &ch) in chars.iter().enumerate() {
+                if ch == '[' {
+                    // Find the matching closing bracket
+                    let mut depth = 1;
+                    let mut j = i + 1;
+                    while j < chars.len() && depth > 0 {
+                        match chars[j] {
+                            '[' => depth += 1,
+                            ']' => depth -= 1,
+                            _ => {}
+                        }
+                        j += 1;
+                    }
+                    if depth == 0 {
+                        bracket_positions.push((i, j - 1));
+                    }
+                }
+            }
+
+            // Check spacing for each bracket pair
+            for (open_pos, close_pos) in bracket_positions {
+                let content_between = &chars[open_pos + 1..close_pos];
+                let content_str: String = content_between.iter().collect();
+                let trimmed_content = content_str.trim();
+
+                if trimmed_content.is_empty() {
+                    // Empty brackets
+                    let spaces_count = content_str.len();
+                    if spaces_count < min_spaces_inside_empty {
+                        problems.push(Problem::from_message(
+                            line_number,
+                            open_pos + 1,
+                            Level::Error,
+                            self.id(),
+                            Message::new("brackets.too-few-spaces-inside-empty")
+                                .arg("min", min_spaces_inside_empty),
+                        ));
+                    } else if spaces_count > max_spaces_inside_empty {
+                        problems.push(Problem::from_message(
+                            line_number,
+                            open_pos + 1,
+                            Level::Error,
+                            self.id(),
+                            Message::new("brackets.too-many-spaces-inside-empty")
+                                .arg("max", max_spaces_inside_empty),
+                        ));
+                    }
+                } else {
+                    // Non-empty brackets
+                    let leading_spaces = content_str.len() - content_str.trim_start().len();
+                    let trailing_spaces = content_str.len() - content_str.trim_end().len();
+
+                    if leading_spaces < min_spaces_inside {
+                        problems.push(Problem::from_message(
+                            line_number,
+                            open_pos + 1,
+                            Level::Error,
+                            self.id(),
+                            Message::new("brackets.too-few-spaces-inside")
+                                .arg("min", min_spaces_inside),
+                        ));
+                    } else if leading_spaces > max_spaces_inside {
+                        problems.push(Problem::from_message(
+                            line_number,
+                            open_pos + 1,
+                            Level::Error,
+                            self.id(),
+                            Message::new("brackets.too-many-spaces-inside")
+                                .arg("max", max_spaces_inside),
+                        ));
+                    }
+
+                    if trailing_spaces < min_spaces_inside {
+                        problems.push(Problem::from_message(
+                            line_number,
+                            close_pos + 1,
+                            Level::Error,
+                            self.id(),
+                            Message::new("brackets.too-few-spaces-inside")
+                                .arg("min", min_spaces_inside),
+                        ));
+                    } else if trailing_spaces > max_spaces_inside {
+                        problems.push(Problem::from_message(
+                            line_number,
+                            close_pos + 1,
+                            Level::Error,
+                            self.id(),
+                            Message::new("brackets.too-many-spaces-inside")
+                                .arg("max", max_spaces_inside),
+                        ));
+                    }
+                }
+            }
+        }
+
+        Ok(problems)
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(false, Level::Error); // Disabled by default
+        config.set_param("min-spaces-inside".to_string(), ConfigValue::Int(0));
+        config.set_param("max-spaces-inside".to_string(), ConfigValue::Int(1));
+        config.set_param("min-spaces-inside-empty".to_string(), ConfigValue::Int(0));
+        config.set_param("max-spaces-inside-empty".to_string(), ConfigValue::Int(0));
+        config
+    }
+
+    fn validate_config(&self, _config: &RuleConfig) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Rule that checks brace spacing and style
+#[derive(Debug, Default)]
+pub struct BracesRule;
+
+impl BracesRule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Rule for BracesRule {
+    fn id(&self) -> &'static str {
+        "braces"
+    }
+
+    fn description(&self) -> &'static str {
+        "Controls the use of braces within mappings"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "Controls spacing inside the braces of flow mappings.",
+            params: &[
+                (
+                    "min-spaces-inside",
+                    "minimum number of spaces after `{` and before `}`",
+                ),
+                (
+                    "max-spaces-inside",
+                    "maximum number of spaces after `{` and before `}`",
+                ),
+                (
+                    "min-spaces-inside-empty",
+                    "minimum number of spaces inside empty braces",
+                ),
+                (
+                    "max-spaces-inside-empty",
+                    "maximum number of spaces inside empty braces",
+                ),
+            ],
+            failing: "---\npoint: {   x: 1, y: 2   }\n",
+            passing: "---\npoint: {x: 1, y: 2}\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        let mut problems = Vec::new();
+
+        let min_spaces_inside = config.get_int("min-spaces-inside").unwrap_or(0) as usize;
+        let max_spaces_inside = config.get_int("max-spaces-inside").unwrap_or(1) as usize;
+        let min_spaces_inside_empty =
+            config.get_int("min-spaces-inside-empty").unwrap_or(0) as usize;
+        let max_spaces_inside_empty =
+            config.get_int("max-spaces-inside-empty").unwrap_or(0) as usize;
+
+        for (line_no, line) in context.content.lines().enumerate() {
+            let line_number = line_no + 1;
+
+            // Find all brace pairs in the line
+            let mut brace_positions = Vec::new();
+            let chars: Vec<char> = line.chars().collect();
+
+            for (i, &ch) in chars.iter().enumerate() {
+                if ch == '{' {
+                    // Find the matching closing brace
+                    let mut depth = 1;
+                    let mut j = i + 1;
+                    while j < chars.len() && depth > 0 {
+                        match chars[j] {
+                            '{' => depth += 1,
+                            '}' => depth -= 1,
+                            _ => {}
+                        }
+                        j += 1;
+                    }
+                    if depth == 0 {
+                        brace_positions.push((i, j - 1));
+                    }
+                }
+            }
+
+            // Check spacing for each brace pair
+            for (open_pos, close_pos) in brace_positions {
+                let content_between = &chars[open_pos + 1..close_pos];
+                let content_str: String = content_between.iter().collect();
+                let trimmed_content = content_str.trim();
+
+                if trimmed_content.is_empty() {
+                    // Empty braces
+                    let spaces_count = content_str.len();
+                    if spaces_count < min_spaces_inside_empty {
+                        problems.push(Problem::from_message(
+                            line_number,
+                            open_pos + 1,
+                            Level::Error,
+                            self.id(),
+                            Message::new("braces.too-few-spaces-inside-empty")
+                                .arg("min", min_spaces_inside_empty),
+                        ));
+                    } else if spaces_count > max_spaces_inside_empty {
+                        problems.push(Problem::from_message(
+                            line_number,
+                            open_pos + 1,
+                            Level::Error,
+                            self.id(),
+                            Message::new("braces.too-many-spaces-inside-empty")
+                                .arg("max", max_spaces_inside_empty),
+                        ));
+                    }
+                } else {
+                    // Non-empty braces
+                    let leading_spaces = content_str.len() - content_str.trim_start().len();
+                    let trailing_spaces = content_str.len() - content_str.trim_end().len();
+
+                    if leading_spaces < min_spaces_inside {
+                        problems.push(Problem::from_message(
+                            line_number,
+                            open_pos + 1,
+                            Level::Error,
+                            self.id(),
+                            Message::new("braces.too-few-spaces-inside")
+                                .arg("min", min_spaces_inside),
+                        ));
+                    } else if leading_spaces > max_spaces_inside {
+                        problems.push(Problem::from_message(
+                            line_number,
+                            open_pos + 1,
+                            Level::Error,
+                            self.id(),
+                            Message::new("braces.too-many-spaces-inside")
+                                .arg("max", max_spaces_inside),
+                        ));
+                    }
+
+                    if trailing_spaces < min_spaces_inside {
+                        problems.push(Problem::from_message(
+                            line_number,
+                            close_pos + 1,
+                            Level::Error,
+                            self.id(),
+                            Message::new("braces.too-few-spaces-inside")
+                                .arg("min", min_spaces_inside),
+                        ));
+                    } else if trailing_spaces > max_spaces_inside {
+                        problems.push(Problem::from_message(
+                            line_number,
+                            close_pos + 1,
+                            Level::Error,
+                            self.id(),
+                            Message::new("braces.too-many-spaces-inside")
+                                .arg("max", max_spaces_inside),
+                        ));
+                    }
+                }
+            }
+        }
+
+        Ok(problems)
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(false, Level::Error); // Disabled by default
+        config.set_param("min-spaces-inside".to_string(), ConfigValue::Int(0));
+        config.set_param("max-spaces-inside".to_string(), ConfigValue::Int(1));
+        config.set_param("min-spaces-inside-empty".to_string(), ConfigValue::Int(0));
+        config.set_param("max-spaces-inside-empty".to_string(), ConfigValue::Int(0));
+        config
+    }
+
+    fn validate_config(&self, _config: &RuleConfig) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Rule that checks colon spacing
+#[derive(Debug, Default)]
+pub struct ColonsRule;
+
+impl ColonsRule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Rule for ColonsRule {
+    fn id(&self) -> &'static str {
+        "colons"
+    }
+
+    fn description(&self) -> &'static str {
+        "Controls the use of colons within mappings"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "Controls spacing around the colons that separate keys from values.",
+            params: &[
+                (
+                    "max-spaces-before",
+                    "maximum number of spaces before a colon",
+                ),
+                ("min-spaces-after", "minimum number of spaces after a colon"),
+                ("max-spaces-after", "maximum number of spaces after a colon"),
+            ],
+            failing: "---\nkey :  value\n",
+            passing: "---\nkey: value\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        let mut problems = Vec::new();
+
+        let max_spaces_before = config.get_int("max-spaces-before").unwrap_or(0) as usize;
+        let min_spaces_after = config.get_int("min-spaces-after").unwrap_or(1) as usize;
+        let max_spaces_after = config.get_int("max-spaces-after").unwrap_or(1) as usize;
+
+        let tokens = context.tokens();
+        for (line_no, line) in context.content.lines().enumerate() {
+            let line_number = line_no + 1;
+            let Some(line_tokens) = tokens.line(line_number) else {
+                continue;
+            };
+
+            // Check the colons outside quoted scalars, comments and block scalars
+            let chars: Vec<char> = line.chars().collect();
+            for (i, (offset, ch)) in line.char_indices().enumerate() {
+                if ch != ':' || !line_tokens.is_code(offset) {
+                    continue;
+                }
+
+                // Check spaces before colon
+                let spaces_before = if i > 0 {
+                    let mut count = 0;
+                    let mut j = i;
+                    while j > 0 && chars[j - 1] == ' ' {
+                        count += 1;
+                        j -= 1;
+                    }
+                    count
+                } else {
+                    0
+                };
+
+                if spaces_before > max_spaces_before {
+                    problems.push(Problem::from_message(
+                        line_number,
+                        i + 1,
+                        Level::Error,
+                        self.id(),
+                        Message::new("colons.too-many-spaces-before").arg("max", max_spaces_before),
+                    ));
+                }
+
+                // Check spaces after colon
+                let spaces_after = if i + 1 < chars.len() {
+                    let mut count = 0;
+                    let mut j = i + 1;
+                    while j < chars.len() && chars[j] == ' ' {
+                        count += 1;
+                        j += 1;
+                    }
+                    count
+                } else {
+                    0
+                };
+
+                // Only check if there's content after the colon
+                if i + 1 + spaces_after < chars.len() {
+                    if spaces_after < min_spaces_after {
+                        problems.push(Problem::from_message(
+                            line_number,
+                            i + 2,
+                            Level::Error,
+                            self.id(),
+                            Message::new("colons.too-few-spaces-after")
+                                .arg("min", min_spaces_after),
+                        ));
+                    } else if spaces_after > max_spaces_after {
+                        problems.push(Problem::from_message(
+                            line_number,
+                            i + 2,
+                            Level::Error,
+                            self.id(),
+                            Message::new("colons.too-many-spaces-after")
+                                .arg("max", max_spaces_after),
+                        ));
+                    }
+                }
+            }
+        }
+
+        Ok(problems)
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(false, Level::Error); // Disabled by default
+        config.set_param("max-spaces-before".to_string(), ConfigValue::Int(0));
+        config.set_param("min-spaces-after".to_string(), ConfigValue::Int(1));
+        config.set_param("max-spaces-after".to_string(), ConfigValue::Int(1));
+        config
+    }
+
+    fn validate_config(&self, _config: &RuleConfig) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Rule that checks comma spacing
+#[derive(Debug, Default)]
+pub struct CommasRule;
+
+impl CommasRule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Rule for CommasRule {
+    fn id(&self) -> &'static str {
+        "commas"
+    }
+
+    fn description(&self) -> &'static str {
+        "Controls the use of commas in sequences and mappings"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "Controls spacing around the commas of flow sequences and mappings.",
+            params: &[
+                (
+                    "max-spaces-before",
+                    "maximum number of spaces before a comma",
+                ),
+                ("min-spaces-after", "minimum number of spaces after a comma"),
+                ("max-spaces-after", "maximum number of spaces after a comma"),
+            ],
+            failing: "---\nitems: [1 ,2]\n",
+            passing: "---\nitems: [1, 2]\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        let mut problems = Vec::new();
+
+        let max_spaces_before = config.get_int("max-spaces-before").unwrap_or(0) as usize;
+        let min_spaces_after = config.get_int("min-spaces-after").unwrap_or(1) as usize;
+        let max_spaces_after = config.get_int("max-spaces-after").unwrap_or(1) as usize;
+
+        let tokens = context.tokens();
+        for (line_no, line) in context.content.lines().enumerate() {
+            let line_number = line_no + 1;
+            let Some(line_tokens) = tokens.line(line_number) else {
+                continue;
+            };
+
+            // Check the commas outside quoted scalars, comments and block scalars
+            let chars: Vec<char> = line.chars().collect();
+            for (i, (offset, ch)) in line.char_indices().enumerate() {
+                if ch != ',' || !line_tokens.is_code(offset) {
+                    continue;
+                }
+
+                // Check spaces before comma
+                let spaces_before = if i > 0 {
+                    let mut count = 0;
+                    let mut j = i;
+                    while j > 0 && chars[j - 1] == ' ' {
+                        count += 1;
+                        j -= 1;
+                    }
+                    count
+                } else {
+                    0
+                };
+
+                if spaces_before > max_spaces_before {
+                    problems.push(Problem::from_message(
+                        line_number,
+                        i + 1,
+                        Level::Error,
+                        self.id(),
+                        Message::new("commas.too-many-spaces-before").arg("max", max_spaces_before),
+                    ));
+                }
+
+                // Check spaces after comma
+                let spaces_after = if i + 1 < chars.len() {
+                    let mut count = 0;
+                    let mut j = i + 1;
+                    while j < chars.len() && chars[j] == ' ' {
+                        count += 1;
+                        j += 1;
+                    }
+                    count
+                } else {
+                    0
+                };
+
+                // Only check if there's content after the comma
+                if i + 1 + spaces_after < chars.len() {
+                    if spaces_after < min_spaces_after {
+                        problems.push(Problem::from_message(
+                            line_number,
+                            i + 2,
+                            Level::Error,
+                            self.id(),
+                            Message::new("commas.too-few-spaces-after")
+                                .arg("min", min_spaces_after),
+                        ));
+                    } else if spaces_after > max_spaces_after {
+                        problems.push(Problem::from_message(
+                            line_number,
+                            i + 2,
+                            Level::Error,
+                            self.id(),
+                            Message::new("commas.too-many-spaces-after")
+                                .arg("max", max_spaces_after),
+                        ));
+                    }
+                }
+            }
+        }
+
+        Ok(problems)
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(false, Level::Error); // Disabled by default
+        config.set_param("max-spaces-before".to_string(), ConfigValue::Int(0));
+        config.set_param("min-spaces-after".to_string(), ConfigValue::Int(1));
+        config.set_param("max-spaces-after".to_string(), ConfigValue::Int(1));
+        config
+    }
+
+    fn validate_config(&self, _config: &RuleConfig) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Rule that checks hyphen spacing in sequences
+#[derive(Debug, Default)]
+pub struct HyphensRule;
+
+impl HyphensRule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Rule for HyphensRule {
+    fn id(&self) -> &'static str {
+        "hyphens"
+    }
+
+    fn description(&self) -> &'static str {
+        "Controls the use of hyphens in sequences"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "Controls spacing after the hyphens of block sequence items.",
+            params: &[(
+                "max-spaces-after",
+                "maximum number of spaces after a hyphen",
+            )],
+            failing: "items:\n  -   first\n",
+            passing: "items:\n  - first\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        let mut problems = Vec::new();
+
+        let max_spaces_after = config.get_int("max-spaces-after").unwrap_or(1) as usize;
+
+        for (line_no, line) in context.content.lines().enumerate() {
+            let line_number = line_no + 1;
+            let trimmed = line.trim_start();
+
+            // Skip comments and empty lines
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            // Check if this is a sequence item (starts with hyphen)
+            if trimmed.starts_with('-') {
+                let hyphen_pos = line.find('-').unwrap();
+                let chars: Vec<char> = line.chars().collect();
+
+                // Check spaces after hyphen
+                let spaces_after = if hyphen_pos + 1 < chars.len() {
+                    let mut count = 0;
+                    let mut j = hyphen_pos + 1;
+                    while j < chars.len() && chars[j] == ' ' {
+                        count += 1;
+                        j += 1;
+                    }
+                    count
+                } else {
+                    0
+                };
+
+                // Only check if there's content after the hyphen
+                if hyphen_pos + 1 + spaces_after < chars.len() {
+                    if spaces_after == 0 {
+                        problems.push(Problem::from_message(
+                            line_number,
+                            LintContext::char_column(line, hyphen_pos + 1),
+                            Level::Error,
+                            self.id(),
+                            Message::new("hyphens.missing-space"),
+                        ));
+                    } else if spaces_after > max_spaces_after {
+                        problems.push(Problem::from_message(
+                            line_number,
+                            LintContext::char_column(line, hyphen_pos + 1),
+                            Level::Error,
+                            self.id(),
+                            Message::new("hyphens.too-many-spaces-after")
+                                .arg("max", max_spaces_after),
+                        ));
+                    }
+                }
+            }
+        }
+
+        Ok(problems)
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(false, Level::Error); // Disabled by default
+        config.set_param("max-spaces-after".to_string(), ConfigValue::Int(1));
+        config
+    }
+
+    fn validate_config(&self, _config: &RuleConfig) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Rule that limits the size, nesting and line span of flow collections
+#[derive(Debug, Default)]
+pub struct FlowCollectionsRule;
+
+impl FlowCollectionsRule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Flow collections that may not span more than one line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MultiLineFlow {
+    None,
+    Sequences,
+    Mappings,
+    All,
+}
+
+impl MultiLineFlow {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "none" => Some(Self::None),
+            "sequences" => Some(Self::Sequences),
+            "mappings" => Some(Self::Mappings),
+            "all" => Some(Self::All),
+            _ => None,
+        }
+    }
+
+    fn forbids(self, kind: FlowKind) -> bool {
+        match self {
+            Self::None => false,
+            Self::Sequences => kind == FlowKind::Sequence,
+            Self::Mappings => kind == FlowKind::Mapping,
+            Self::All => true,
+        }
+    }
+}
+
+/// Parameters of the flow-collections rule; a limit of 0 is no limit
+#[derive(Debug)]
+struct FlowLimits {
+    max_items: usize,
+    max_depth: usize,
+    forbid_multi_line: MultiLineFlow,
+}
+
+impl FlowCollectionsRule {
+    fn settings(&self, config: &RuleConfig) -> Result<FlowLimits> {
+        let limit = |param: &str, default: usize| -> Result<usize> {
+            match config.params.get(param) {
+                Some(value) => value
+                    .as_int()
+                    .and_then(|limit| usize::try_from(limit).ok())
+                    .ok_or_else(|| eyre::eyre!("{param} must be a non-negative integer")),
+                None => Ok(default),
+            }
+        };
+
+        let forbid_multi_line = match config.get_string("forbid-multi-line") {
+            Some(value) => MultiLineFlow::parse(value).ok_or_else(|| {
+                eyre::eyre!(
+                    "forbid-multi-line must be \"none\", \"sequences\", \"mappings\" or \"all\", got \"{value}\""
+                )
+            })?,
+            None => MultiLineFlow::All,
+        };
+
+        Ok(FlowLimits {
+            max_items: limit("max-items", 10)?,
+            max_depth: limit("max-depth", 2)?,
+            forbid_multi_line,
+        })
+    }
+
+    fn name(collection: &FlowCollection) -> Message {
+        Message::new(match collection.kind {
+            FlowKind::Sequence => "collection.flow-sequence",
+            FlowKind::Mapping => "collection.flow-mapping",
+        })
+    }
+}
+
+impl Rule for FlowCollectionsRule {
+    fn id(&self) -> &'static str {
+        "flow-collections"
+    }
+
+    fn description(&self) -> &'static str {
+        "Limits the size, nesting and line span of flow collections"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "Limits flow sequences (`[...]`) and flow mappings (`{...}`) so that \
+                      larger collections are written in block style, which is easier to \
+                      read and to diff.",
+            params: &[
+                (
+                    "max-items",
+                    "maximum number of entries in a flow collection (0 for no limit)",
+                ),
+                (
+                    "max-depth",
+                    "maximum nesting depth of flow collections, 1 forbidding nesting (0 for no limit)",
+                ),
+                (
+                    "forbid-multi-line",
+                    "flow collections that must fit on one line: `none`, `sequences`, `mappings` or `all`",
+                ),
+            ],
+            failing: "---\nports: [\n  80,\n  443,\n]\n",
+            passing: "---\nports:\n  - 80\n  - 443\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        let limits = self.settings(config)?;
+        let mut problems = Vec::new();
+
+        for collection in context.flow_collections() {
+            let name = Self::name(&collection);
+            let mut report = |message: Message| {
+                problems.push(Problem::from_message(
+                    collection.line,
+                    collection.column,
+                    config.level.clone(),
+                    self.id(),
+                    message,
+                ));
+            };
+
+            if limits.max_items > 0 && collection.items > limits.max_items {
+                report(
+                    Message::new("flow-collections.too-many-entries")
+                        .arg("collection", &name)
+                        .arg("count", collection.items)
+                        .arg("max", limits.max_items),
+                );
+            }
+            if limits.max_depth > 0 && collection.depth == limits.max_depth + 1 {
+                report(
+                    Message::new("flow-collections.too-deep")
+                        .arg("collection", &name)
+                        .arg("max", limits.max_depth),
+                );
+            }
+            if collection.is_multi_line()
+                && collection.depth == 1
+                && limits.forbid_multi_line.forbids(collection.kind)
+            {
+                report(Message::new("flow-collections.multi-line").arg("collection", &name));
+            }
+        }
+
+        Ok(problems)
+    }
+
+    fn param_schema(&self) -> Vec<ParamSchema> {
+        ParamSchema::from_defaults(
+            &self.default_config(),
+            &self.docs(),
+            &[(
+                "forbid-multi-line",
+                &["none", "sequences", "mappings", "all"],
+            )],
+        )
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(false, Level::Warning); // Disabled by default
+        config.set_param("max-items".to_string(), ConfigValue::Int(10));
+        config.set_param("max-depth".to_string(), ConfigValue::Int(2));
+        config.set_param(
+            "forbid-multi-line".to_string(),
+            ConfigValue::String("all".to_string()),
+        );
+        config
+    }
+
+    fn validate_config(&self, config: &RuleConfig) -> Result<()> {
+        self.settings(config).map(|_| ())
+    }
+}
+
+/// Rule that reports commas of flow collections that end no entry
+#[derive(Debug, Default)]
+pub struct DanglingCommasRule;
+
+impl DanglingCommasRule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Rule for DanglingCommasRule {
+    fn id(&self) -> &'static str {
+        "dangling-commas"
+    }
+
+    fn fixable(&self) -> bool {
+        true
+    }
+
+    fn description(&self) -> &'static str {
+        "Forbids trailing commas and empty entries in flow collections"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "Reports a comma directly before the `]` or `}` closing a flow collection, \
+                      and commas with no entry before them, as in `[a,, b]`. YAML allows the \
+                      trailing comma but not the empty entry, which parsers report at the \
+                      start of the collection rather than at the comma. The fix removes the \
+                      comma.",
+            params: &[(
+                "allow-trailing",
+                "do not report a comma before the closing bracket or brace",
+            )],
+            failing: "---
+ports: [80, 443,]
+",
+            passing: "---
+ports: [80, 443]
+",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        let allow_trailing = config.get_bool("allow-trailing").unwrap_or(false);
+        let mut problems = Vec::new();
+
+        for collection in context.flow_collections() {
+            let name = FlowCollectionsRule::name(&collection);
+            for &(line, column) in &collection.empty_entries {
+                problems.push(Problem::from_message(
+                    line,
+                    column,
+                    config.level.clone(),
+                    self.id(),
+                    Message::new("dangling-commas.empty-entry").arg("collection", &name),
+                ));
+            }
+            if let Some((line, column)) = collection.trailing_comma
+                && !allow_trailing
+            {
+                problems.push(Problem::from_message(
+                    line,
+                    column,
+                    config.level.clone(),
+                    self.id(),
+                    Message::new("dangling-commas.trailing").arg("collection", &name),
+                ));
+            }
+        }
+
+        Ok(problems)
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(false, Level::Error); // Disabled by default
+        config.set_param("allow-trailing", false);
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn create_test_context<'a>(content: &'a str, path: &'a PathBuf) -> LintContext<'a> {
+        LintContext::new(path, content)
+    }
+
+    #[test]
+    fn test_brackets_rule_correct_spacing() {
+        let rule = BracketsRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("array: [ item1, item2 ]", &path);
+        let mut config = rule.default_config();
+        config.enabled = true;
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_brackets_rule_no_spacing() {
+        let rule = BracketsRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("array: [item1, item2]", &path);
+        let mut config = rule.default_config();
+        config.enabled = true;
+        config.set_param("min-spaces-inside".to_string(), ConfigValue::Int(1)); // Require at least 1 space
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 2); // Missing space after [ and before ]
+    }
+
+    #[test]
+    fn test_braces_rule_correct_spacing() {
+        let rule = BracesRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("mapping: { key: value }", &path);
+        let mut config = rule.default_config();
+        config.enabled = true;
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_colons_rule_correct_spacing() {
+        let rule = ColonsRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("key: value", &path);
+        let mut config = rule.default_config();
+        config.enabled = true;
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_colons_rule_no_space_after() {
+        let rule = ColonsRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("key:value", &path);
+        let mut config = rule.default_config();
+        config.enabled = true;
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].message.contains("too few spaces after colon"));
+    }
+
+    #[test]
+    fn test_colons_rule_space_before() {
+        let rule = ColonsRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("key : value", &path);
+        let mut config = rule.default_config();
+        config.enabled = true;
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].message.contains("too many spaces before colon"));
+    }
+
+    #[test]
+    fn test_commas_rule_correct_spacing() {
+        let rule = CommasRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("array: [item1, item2, item3]", &path);
+        let mut config = rule.default_config();
+        config.enabled = true;
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_commas_rule_no_space_after() {
+        let rule = CommasRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("array: [item1,item2]", &path);
+        let mut config = rule.default_config();
+        config.enabled = true;
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].message.contains("too few spaces after comma"));
+    }
+
+    #[test]
+    fn test_colons_and_commas_skip_strings_comments_and_block_scalars() {
+        let path = PathBuf::from("test.yaml");
+        let content = "host: \"\\\n  a.com.,\\\n  b.com:80\"\nkey: value # a:b,c\nscript: |\n  echo a:b,c\nitems: [a,b]\n";
+        let context = create_test_context(content, &path);
+
+        let rule = ColonsRule::new();
+        let mut config = rule.default_config();
+        config.enabled = true;
+        assert!(rule.check(&context, &config).unwrap().is_empty());
+
+        let rule = CommasRule::new();
+        let mut config = rule.default_config();
+        config.enabled = true;
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, 7);
+    }
+
+    #[test]
+    fn test_hyphens_rule_correct_spacing() {
+        let rule = HyphensRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("- item1\n- item2", &path);
+        let mut config = rule.default_config();
+        config.enabled = true;
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_hyphens_rule_no_space_after() {
+        let rule = HyphensRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("-item1\n-item2", &path);
+        let mut config = rule.default_config();
+        config.enabled = true;
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].message.contains("missing space after hyphen"));
+    }
+
+    #[test]
+    fn test_flow_collections_rule_limits() {
+        let rule = FlowCollectionsRule::new();
+        let path = PathBuf::from("test.yaml");
+        let content =
+            "small: [1, 2]\nbig: [1, 2, 3, 4]\nnested: [[[1]]]\nwrapped: {\n  a: [1,\n    2],\n}\n";
+        let context = create_test_context(content, &path);
+        let mut config = rule.default_config();
+        config.enabled = true;
+        config.set_param("max-items".to_string(), ConfigValue::Int(3));
+
+        let problems = rule.check(&context, &config).unwrap();
+        let positions: Vec<_> = problems.iter().map(|p| (p.line, p.column)).collect();
+        // The nested sequence spanning lines 5-6 is reported once, with its mapping
+        assert_eq!(positions, vec![(2, 6), (3, 11), (4, 10)]);
+        assert!(
+            problems[0]
+                .message
+                .contains("too many entries in flow sequence (4 > 3)")
+        );
+        assert!(problems[1].message.contains("nested too deeply"));
+        assert!(
+            problems[2]
+                .message
+                .contains("flow mapping spans multiple lines")
+        );
+
+        config.set_param("max-items".to_string(), ConfigValue::Int(0));
+        config.set_param("max-depth".to_string(), ConfigValue::Int(0));
+        config.set_param(
+            "forbid-multi-line".to_string(),
+            ConfigValue::String("sequences".to_string()),
+        );
+        assert!(rule.check(&context, &config).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_flow_collections_rule_validation() {
+        let rule = FlowCollectionsRule::new();
+        let mut config = rule.default_config();
+        assert!(rule.validate_config(&config).is_ok());
+
+        config.set_param(
+            "forbid-multi-line".to_string(),
+            ConfigValue::String("some".to_string()),
+        );
+        assert!(rule.validate_config(&config).is_err());
+
+        let mut config = rule.default_config();
+        config.set_param("max-items".to_string(), ConfigValue::Int(-1));
+        assert!(rule.validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_dangling_commas_rule() {
+        let rule = DanglingCommasRule::new();
+        let mut config = rule.default_config();
+        let path = PathBuf::from("test.yaml");
+        let check = |content: &str, config: &RuleConfig| {
+            let context = create_test_context(content, &path);
+            rule.check(&context, config)
+                .unwrap()
+                .into_iter()
+                .map(|problem| (problem.line, problem.column, problem.message))
+                .collect::<Vec<_>>()
+        };
+
+        assert!(check("a: [1, 2]\nb: {x: 1}\nc: \"[1,]\" # [2,]\n", &config).is_empty());
+        assert_eq!(
+            check("a: [1, 2,]\nb: {x: 1,, y: 2}\nc: [\n  3,\n]\n", &config),
+            vec![
+                (1, 9, "trailing comma in flow sequence".to_string()),
+                (2, 10, "empty entry in flow mapping".to_string()),
+                (4, 4, "trailing comma in flow sequence".to_string()),
+            ]
+        );
+
+        config.set_param("allow-trailing", true);
+        assert_eq!(
+            check("a: [1, 2,]\nb: [, 1]\n", &config),
+            vec![(2, 5, "empty entry in flow sequence".to_string())]
+        );
+    }
+}
@@ -0,0 +1,914 @@
+pub mod common;
+pub mod formatting;
+pub mod schema;
+pub mod semantic;
+pub mod style;
+pub mod syntax;
+
+use crate::config::Config;
+use crate::linter::{LintContext, Problem};
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, LazyLock};
+
+pub use schema::{ParamSchema, ParamType};
+
+/// Configuration value that can be used in rule parameters
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ConfigValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Array(Vec<ConfigValue>),
+    Map(BTreeMap<String, ConfigValue>),
+}
+
+#[allow(dead_code)] // Some methods are part of API for future phases
+impl ConfigValue {
+    /// Try to get the value as a boolean
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            ConfigValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Try to get the value as an integer
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            ConfigValue::Int(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// Try to get the value as a number, integers included
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            ConfigValue::Float(f) => Some(*f),
+            ConfigValue::Int(i) => Some(*i as f64),
+            _ => None,
+        }
+    }
+
+    /// Try to get the value as a string
+    pub fn as_string(&self) -> Option<&str> {
+        match self {
+            ConfigValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Try to get the value as an array
+    pub fn as_array(&self) -> Option<&Vec<ConfigValue>> {
+        match self {
+            ConfigValue::Array(arr) => Some(arr),
+            _ => None,
+        }
+    }
+
+    /// Try to get the value as a map
+    pub fn as_map(&self) -> Option<&BTreeMap<String, ConfigValue>> {
+        match self {
+            ConfigValue::Map(map) => Some(map),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ConfigValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigValue::Bool(b) => write!(f, "{b}"),
+            ConfigValue::Int(i) => write!(f, "{i}"),
+            ConfigValue::Float(n) => write!(f, "{n:?}"),
+            ConfigValue::String(s) => write!(f, "{s:?}"),
+            ConfigValue::Array(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, "]")
+            }
+            ConfigValue::Map(entries) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{key:?}: {value}")?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+impl From<bool> for ConfigValue {
+    fn from(value: bool) -> Self {
+        ConfigValue::Bool(value)
+    }
+}
+
+impl From<i64> for ConfigValue {
+    fn from(value: i64) -> Self {
+        ConfigValue::Int(value)
+    }
+}
+
+impl From<f64> for ConfigValue {
+    fn from(value: f64) -> Self {
+        ConfigValue::Float(value)
+    }
+}
+
+impl From<String> for ConfigValue {
+    fn from(value: String) -> Self {
+        ConfigValue::String(value)
+    }
+}
+
+impl From<&str> for ConfigValue {
+    fn from(value: &str) -> Self {
+        ConfigValue::String(value.to_string())
+    }
+}
+
+impl From<Vec<ConfigValue>> for ConfigValue {
+    fn from(value: Vec<ConfigValue>) -> Self {
+        ConfigValue::Array(value)
+    }
+}
+
+impl From<BTreeMap<String, ConfigValue>> for ConfigValue {
+    fn from(value: BTreeMap<String, ConfigValue>) -> Self {
+        ConfigValue::Map(value)
+    }
+}
+
+/// Configuration for a specific rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "RuleBlock")]
+pub struct RuleConfig {
+    /// Whether the rule is enabled
+    pub enabled: bool,
+    /// Severity level for problems found by this rule
+    pub level: crate::linter::Level,
+    /// Rule-specific parameters
+    pub params: HashMap<String, ConfigValue>,
+    /// Patterns of files this rule is not run on, with the same syntax as the global `ignore`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignore: Vec<String>,
+    /// Whether the rule has a block in a configuration file, rather than the rule's
+    /// default configuration
+    #[serde(skip)]
+    pub explicit: bool,
+    /// Parameters the user set, in a configuration file or on the command line, as
+    /// opposed to those holding the rule's defaults
+    #[serde(skip)]
+    pub user_params: HashSet<String>,
+}
+
+/// A rule block as written in a configuration file
+#[derive(Deserialize)]
+struct RuleBlock {
+    enabled: bool,
+    level: crate::linter::Level,
+    params: HashMap<String, ConfigValue>,
+    #[serde(default)]
+    ignore: Vec<String>,
+}
+
+impl From<RuleBlock> for RuleConfig {
+    fn from(block: RuleBlock) -> Self {
+        Self {
+            enabled: block.enabled,
+            level: block.level,
+            user_params: block.params.keys().cloned().collect(),
+            params: block.params,
+            ignore: block.ignore,
+            explicit: true,
+        }
+    }
+}
+
+/// Configurations are equal when they lint alike, wherever their settings came from
+impl PartialEq for RuleConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.enabled == other.enabled
+            && self.level == other.level
+            && self.params == other.params
+            && self.ignore == other.ignore
+    }
+}
+
+#[allow(dead_code)] // Some methods are part of API for future phases
+impl RuleConfig {
+    /// Create a new rule configuration
+    pub fn new(enabled: bool, level: crate::linter::Level) -> Self {
+        Self {
+            enabled,
+            level,
+            params: HashMap::new(),
+            ignore: Vec::new(),
+            explicit: false,
+            user_params: HashSet::new(),
+        }
+    }
+
+    /// Get a parameter value as a boolean
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.params.get(key)?.as_bool()
+    }
+
+    /// Get a parameter value as an integer
+    pub fn get_int(&self, key: &str) -> Option<i64> {
+        self.params.get(key)?.as_int()
+    }
+
+    /// Get a parameter value as a number
+    pub fn get_float(&self, key: &str) -> Option<f64> {
+        self.params.get(key)?.as_float()
+    }
+
+    /// Get a parameter value as a string
+    pub fn get_string(&self, key: &str) -> Option<&str> {
+        self.params.get(key)?.as_string()
+    }
+
+    /// Get a parameter value as a map
+    pub fn get_map(&self, key: &str) -> Option<&BTreeMap<String, ConfigValue>> {
+        self.params.get(key)?.as_map()
+    }
+
+    /// Set a parameter value
+    pub fn set_param(&mut self, key: impl Into<String>, value: impl Into<ConfigValue>) {
+        self.params.insert(key.into(), value.into());
+    }
+
+    /// Set a parameter value the user chose, which settings such as `.editorconfig`
+    /// defaults do not replace
+    pub fn set_user_param(&mut self, key: impl Into<String>, value: impl Into<ConfigValue>) {
+        let key = key.into();
+        self.user_params.insert(key.clone());
+        self.params.insert(key, value.into());
+    }
+}
+
+impl Default for RuleConfig {
+    fn default() -> Self {
+        Self::new(true, crate::linter::Level::Error)
+    }
+}
+
+/// Extended documentation for a rule, shown by `yl explain`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RuleDocs {
+    /// Longer explanation of what the rule checks and why
+    pub details: &'static str,
+    /// Meaning of each parameter, as (name, meaning) pairs
+    pub params: &'static [(&'static str, &'static str)],
+    /// YAML that the rule reports problems for
+    pub failing: &'static str,
+    /// The same YAML written so that the rule accepts it
+    pub passing: &'static str,
+}
+
+/// Settings of two enabled rules that contradict each other, or that make one of them
+/// report nothing the other does not
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RuleConflict {
+    /// Rule that declares the conflict
+    pub rule: String,
+    /// The other rule involved
+    pub other: String,
+    pub message: String,
+}
+
+impl RuleConflict {
+    pub fn new(rule: &str, other: &str, message: impl Into<String>) -> Self {
+        Self {
+            rule: rule.to_string(),
+            other: other.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for RuleConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} and {}: {}", self.rule, self.other, self.message)
+    }
+}
+
+/// Trait that all linting rules must implement
+pub trait Rule: Send + Sync {
+    /// Get the unique identifier for this rule
+    fn id(&self) -> &'static str;
+
+    /// Check the given context and return any problems found
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>>;
+
+    /// Get the default configuration for this rule
+    fn default_config(&self) -> RuleConfig;
+
+    /// Validate that the given configuration is valid for this rule
+    fn validate_config(&self, config: &RuleConfig) -> Result<()> {
+        // Default implementation accepts any configuration
+        let _ = config;
+        Ok(())
+    }
+
+    /// Get a human-readable description of this rule
+    fn description(&self) -> &'static str {
+        "No description available"
+    }
+
+    /// Get the extended documentation for this rule
+    fn docs(&self) -> RuleDocs {
+        RuleDocs::default()
+    }
+
+    /// Parameters the rule accepts, with their types, defaults and allowed values.
+    /// By default, the parameters of [`Rule::default_config`] described by [`Rule::docs`].
+    fn param_schema(&self) -> Vec<ParamSchema> {
+        ParamSchema::from_defaults(&self.default_config(), &self.docs(), &[])
+    }
+
+    /// Whether `yl fix` can fix this rule's problems automatically
+    fn fixable(&self) -> bool {
+        false
+    }
+
+    /// When the rule runs; rules of a later phase run after those of earlier ones
+    fn phase(&self) -> RulePhase {
+        RulePhase::Line
+    }
+
+    /// Report known conflicts between this rule's configuration and other rules'.
+    /// `enabled` holds the configuration of every enabled rule, this one included.
+    fn conflicts(
+        &self,
+        config: &RuleConfig,
+        enabled: &HashMap<String, RuleConfig>,
+    ) -> Vec<RuleConflict> {
+        let _ = (config, enabled);
+        Vec::new()
+    }
+}
+
+/// Default rules, for looking up rule metadata by ID
+static DEFAULT_RULES: LazyLock<RuleRegistry> = LazyLock::new(RuleRegistry::with_default_rules);
+
+/// Check whether problems reported by a default rule can be fixed automatically
+pub fn is_fixable(rule_id: &str) -> bool {
+    DEFAULT_RULES.is_fixable(rule_id)
+}
+
+/// Group of related rules, by what they check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RuleCategory {
+    /// Whitespace, line length and indentation
+    Style,
+    /// Document structure and YAML syntax
+    Syntax,
+    /// Spacing in flow collections and around punctuation
+    Formatting,
+    /// Values and keys
+    Semantic,
+}
+
+impl std::fmt::Display for RuleCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuleCategory::Style => write!(f, "style"),
+            RuleCategory::Syntax => write!(f, "syntax"),
+            RuleCategory::Formatting => write!(f, "formatting"),
+            RuleCategory::Semantic => write!(f, "semantic"),
+        }
+    }
+}
+
+/// Stage of linting a rule runs in, in running order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RulePhase {
+    /// Checks that the content parses; a problem from one of these rules means the
+    /// content is not valid YAML
+    Parse,
+    /// Checks of the text and its tokens, which work on content that does not parse
+    Line,
+    /// Checks of keys and values, which are skipped when a parse rule reports a problem
+    Semantic,
+}
+
+impl std::fmt::Display for RulePhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RulePhase::Parse => write!(f, "parse"),
+            RulePhase::Line => write!(f, "line"),
+            RulePhase::Semantic => write!(f, "semantic"),
+        }
+    }
+}
+
+/// Registry for managing all available rules
+#[derive(Default)]
+pub struct RuleRegistry {
+    rules: HashMap<String, Arc<dyn Rule>>,
+    categories: HashMap<String, RuleCategory>,
+    schemas: HashMap<String, Vec<ParamSchema>>,
+}
+
+#[allow(dead_code)] // Some methods are part of API for future phases
+impl RuleRegistry {
+    /// Create a new rule registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a rule
+    pub fn register(&mut self, rule: Box<dyn Rule>) {
+        let id = rule.id().to_string();
+        self.schemas.insert(id.clone(), rule.param_schema());
+        self.rules.insert(id, Arc::from(rule));
+    }
+
+    /// Register a rule as part of a category
+    pub fn register_in(&mut self, category: RuleCategory, rule: Box<dyn Rule>) {
+        self.categories.insert(rule.id().to_string(), category);
+        self.register(rule);
+    }
+
+    /// Category of a registered rule, if it was registered in one
+    pub fn category(&self, id: &str) -> Option<RuleCategory> {
+        self.categories.get(id).copied()
+    }
+
+    /// Declared parameters of a registered rule; none if the rule is unknown
+    pub fn param_schema(&self, id: &str) -> &[ParamSchema] {
+        self.schemas.get(id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Fill in the defaults of the rule's parameters missing from `config`, and convert
+    /// and check the others against their declared types. Parameters the rule does not
+    /// declare are left alone.
+    pub fn resolve_params(&self, id: &str, config: &mut RuleConfig) -> Result<()> {
+        for param in self.param_schema(id) {
+            let value = match config.params.remove(&param.name) {
+                Some(value) => param.coerce(value)?,
+                None => param.default.clone(),
+            };
+            config.params.insert(param.name.clone(), value);
+        }
+        Ok(())
+    }
+
+    /// Get a rule by ID
+    pub fn get(&self, id: &str) -> Option<&dyn Rule> {
+        self.rules.get(id).map(|r| r.as_ref())
+    }
+
+    /// Phase of a registered rule
+    pub fn phase(&self, id: &str) -> Option<RulePhase> {
+        self.get(id).map(|rule| rule.phase())
+    }
+
+    /// Get shared handles to all registered rules, e.g. to run them on other threads,
+    /// in running order: by phase, then by ID
+    pub fn shared_rules(&self) -> Vec<Arc<dyn Rule>> {
+        let mut rules: Vec<Arc<dyn Rule>> = self.rules.values().cloned().collect();
+        rules.sort_by_key(|rule| (rule.phase(), rule.id()));
+        rules
+    }
+
+    /// Get all registered rule IDs
+    pub fn rule_ids(&self) -> Vec<&str> {
+        self.rules.keys().map(|s| s.as_str()).collect()
+    }
+
+    /// Get all registered rules
+    pub fn rules(&self) -> Vec<&dyn Rule> {
+        self.rules.values().map(|r| r.as_ref()).collect()
+    }
+
+    /// Check whether a registered rule's problems can be fixed automatically
+    pub fn is_fixable(&self, id: &str) -> bool {
+        self.get(id).is_some_and(|rule| rule.fixable())
+    }
+
+    /// Known conflicts between the rules a configuration enables, in rule order
+    pub fn conflicts(&self, config: &Config) -> Vec<RuleConflict> {
+        let enabled: HashMap<String, RuleConfig> = self
+            .rules
+            .keys()
+            .map(|id| (id.clone(), config.get_rule_config(id, self)))
+            .filter(|(_, rule_config)| rule_config.enabled)
+            .collect();
+
+        let mut conflicts: Vec<RuleConflict> = enabled
+            .iter()
+            .filter_map(|(id, rule_config)| Some((self.get(id)?, rule_config)))
+            .flat_map(|(rule, rule_config)| rule.conflicts(rule_config, &enabled))
+            .collect();
+        conflicts.sort();
+        conflicts
+    }
+
+    /// Create a registry with default rules
+    pub fn with_default_rules() -> Self {
+        let mut registry = Self::new();
+
+        // Register style rules
+        registry.register_in(RuleCategory::Style, Box::new(style::LineLengthRule::new()));
+        registry.register_in(
+            RuleCategory::Style,
+            Box::new(style::TrailingSpacesRule::new()),
+        );
+        registry.register_in(RuleCategory::Style, Box::new(style::EmptyLinesRule::new()));
+        registry.register_in(RuleCategory::Style, Box::new(style::IndentationRule::new()));
+        registry.register_in(
+            RuleCategory::Style,
+            Box::new(style::NewLineAtEndOfFileRule::new()),
+        );
+        registry.register_in(RuleCategory::Style, Box::new(style::NoTabsRule::new()));
+        registry.register_in(RuleCategory::Style, Box::new(style::ValueLengthRule::new()));
+
+        // Register syntax rules
+        registry.register_in(
+            RuleCategory::Syntax,
+            Box::new(syntax::KeyDuplicatesRule::new()),
+        );
+        registry.register_in(
+            RuleCategory::Syntax,
+            Box::new(syntax::DocumentStructureRule::new()),
+        );
+        registry.register_in(
+            RuleCategory::Syntax,
+            Box::new(syntax::DocumentCountRule::new()),
+        );
+        registry.register_in(RuleCategory::Syntax, Box::new(syntax::AnchorsRule::new()));
+        registry.register_in(
+            RuleCategory::Syntax,
+            Box::new(syntax::YamlSyntaxRule::new()),
+        );
+        registry.register_in(RuleCategory::Syntax, Box::new(syntax::CommentsRule::new()));
+        registry.register_in(
+            RuleCategory::Syntax,
+            Box::new(syntax::DirectivesRule::new()),
+        );
+
+        // Register formatting rules
+        registry.register_in(
+            RuleCategory::Formatting,
+            Box::new(formatting::BracketsRule::new()),
+        );
+        registry.register_in(
+            RuleCategory::Formatting,
+            Box::new(formatting::BracesRule::new()),
+        );
+        registry.register_in(
+            RuleCategory::Formatting,
+            Box::new(formatting::ColonsRule::new()),
+        );
+        registry.register_in(
+            RuleCategory::Formatting,
+            Box::new(formatting::CommasRule::new()),
+        );
+        registry.register_in(
+            RuleCategory::Formatting,
+            Box::new(formatting::HyphensRule::new()),
+        );
+        registry.register_in(
+            RuleCategory::Formatting,
+            Box::new(formatting::FlowCollectionsRule::new()),
+        );
+        registry.register_in(
+            RuleCategory::Formatting,
+            Box::new(formatting::DanglingCommasRule::new()),
+        );
+
+        // Register semantic rules
+        registry.register_in(
+            RuleCategory::Semantic,
+            Box::new(semantic::TruthyRule::new()),
+        );
+        registry.register_in(
+            RuleCategory::Semantic,
+            Box::new(semantic::QuotedStringsRule::new()),
+        );
+        registry.register_in(
+            RuleCategory::Semantic,
+            Box::new(semantic::QuotedKeysRule::new()),
+        );
+        registry.register_in(
+            RuleCategory::Semantic,
+            Box::new(semantic::EmptyCollectionsRule::new()),
+        );
+        registry.register_in(
+            RuleCategory::Semantic,
+            Box::new(semantic::KeyOrderingRule::new()),
+        );
+        registry.register_in(
+            RuleCategory::Semantic,
+            Box::new(semantic::FloatValuesRule::new()),
+        );
+        registry.register_in(
+            RuleCategory::Semantic,
+            Box::new(semantic::OctalValuesRule::new()),
+        );
+        registry.register_in(
+            RuleCategory::Semantic,
+            Box::new(semantic::RequiredKeysRule::new()),
+        );
+        registry.register_in(
+            RuleCategory::Semantic,
+            Box::new(semantic::ForbiddenKeysRule::new()),
+        );
+        registry.register_in(
+            RuleCategory::Semantic,
+            Box::new(semantic::SequenceDuplicatesRule::new()),
+        );
+        registry.register_in(
+            RuleCategory::Semantic,
+            Box::new(semantic::SequenceOrderingRule::new()),
+        );
+        registry.register_in(
+            RuleCategory::Semantic,
+            Box::new(semantic::DateFormatRule::new()),
+        );
+
+        registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::Level;
+
+    #[test]
+    fn test_config_value_conversions() {
+        let bool_val = ConfigValue::from(true);
+        assert_eq!(bool_val.as_bool(), Some(true));
+        assert_eq!(bool_val.as_int(), None);
+
+        let int_val = ConfigValue::from(42i64);
+        assert_eq!(int_val.as_int(), Some(42));
+        assert_eq!(int_val.as_bool(), None);
+        assert_eq!(int_val.as_float(), Some(42.0));
+
+        let float_val = ConfigValue::from(0.5);
+        assert_eq!(float_val.as_float(), Some(0.5));
+        assert_eq!(float_val.as_int(), None);
+
+        let string_val = ConfigValue::from("test");
+        assert_eq!(string_val.as_string(), Some("test"));
+        assert_eq!(string_val.as_int(), None);
+
+        let array_val = ConfigValue::from(vec![ConfigValue::from(1i64), ConfigValue::from(2i64)]);
+        assert_eq!(array_val.as_array().unwrap().len(), 2);
+
+        let map_val = ConfigValue::from(BTreeMap::from([(
+            "key".to_string(),
+            ConfigValue::from("value"),
+        )]));
+        assert_eq!(map_val.as_map().unwrap()["key"].as_string(), Some("value"));
+        assert_eq!(map_val.as_array(), None);
+    }
+
+    #[test]
+    fn test_config_value_display() {
+        assert_eq!(ConfigValue::from(80i64).to_string(), "80");
+        assert_eq!(ConfigValue::from(true).to_string(), "true");
+        assert_eq!(ConfigValue::from("any").to_string(), "\"any\"");
+        assert_eq!(
+            ConfigValue::from(vec![ConfigValue::from("a"), ConfigValue::from("b")]).to_string(),
+            "[\"a\", \"b\"]"
+        );
+        assert_eq!(
+            ConfigValue::from(BTreeMap::from([(
+                "services/**".to_string(),
+                ConfigValue::from(vec![ConfigValue::from("name")])
+            )]))
+            .to_string(),
+            "{\"services/**\": [\"name\"]}"
+        );
+    }
+
+    #[test]
+    fn test_default_rules_are_documented() {
+        let registry = RuleRegistry::with_default_rules();
+
+        for rule in registry.rules() {
+            let docs = rule.docs();
+            assert!(!docs.details.is_empty(), "{} has no details", rule.id());
+            assert!(
+                !docs.failing.is_empty(),
+                "{} has no failing example",
+                rule.id()
+            );
+            assert!(
+                !docs.passing.is_empty(),
+                "{} has no passing example",
+                rule.id()
+            );
+
+            let config = rule.default_config();
+            for key in config.params.keys() {
+                assert!(
+                    docs.params.iter().any(|(name, _)| name == key),
+                    "{} does not document parameter {key}",
+                    rule.id()
+                );
+            }
+            for (name, _) in docs.params {
+                assert!(
+                    config.params.contains_key(*name),
+                    "{} documents unknown parameter {name}",
+                    rule.id()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_fixable_rules_have_fixes() {
+        let registry = RuleRegistry::with_default_rules();
+        let fixes = crate::fixes::FixEngine::new();
+
+        for rule in registry.rules() {
+            assert_eq!(
+                rule.fixable(),
+                fixes.has_fix(rule.id()),
+                "{} fixability does not match the fix engine",
+                rule.id()
+            );
+        }
+        assert!(is_fixable("trailing-spaces"));
+        assert!(!is_fixable("key-duplicates"));
+        assert!(!is_fixable("no-such-rule"));
+    }
+
+    #[test]
+    fn test_rule_config() {
+        let mut config = RuleConfig::new(true, Level::Warning);
+
+        assert!(config.enabled);
+        assert_eq!(config.level, Level::Warning);
+        assert!(config.params.is_empty());
+
+        config.set_param("max", 100i64);
+        config.set_param("enabled", true);
+
+        assert_eq!(config.get_int("max"), Some(100));
+        assert_eq!(config.get_bool("enabled"), Some(true));
+        assert_eq!(config.get_string("nonexistent"), None);
+    }
+
+    #[test]
+    fn test_rule_config_default() {
+        let config = RuleConfig::default();
+        assert!(config.enabled);
+        assert_eq!(config.level, Level::Error);
+        assert!(config.params.is_empty());
+    }
+
+    #[test]
+    fn test_rule_registry() {
+        let mut registry = RuleRegistry::new();
+        assert!(registry.rule_ids().is_empty());
+
+        let rule = Box::new(style::LineLengthRule::new());
+        let rule_id = rule.id();
+        registry.register(rule);
+
+        assert_eq!(registry.rule_ids(), vec![rule_id]);
+        assert!(registry.get(rule_id).is_some());
+        assert!(registry.get("nonexistent").is_none());
+    }
+
+    #[test]
+    fn test_rule_registry_with_defaults() {
+        let registry = RuleRegistry::with_default_rules();
+        assert!(!registry.rule_ids().is_empty());
+        assert!(registry.get("line-length").is_some());
+        assert_eq!(registry.category("line-length"), Some(RuleCategory::Style));
+        assert_eq!(registry.category("truthy"), Some(RuleCategory::Semantic));
+        assert_eq!(registry.phase("yaml-syntax"), Some(RulePhase::Parse));
+        assert_eq!(registry.phase("key-duplicates"), Some(RulePhase::Line));
+        assert_eq!(registry.phase("key-ordering"), Some(RulePhase::Semantic));
+
+        let phases: Vec<RulePhase> = registry
+            .shared_rules()
+            .iter()
+            .map(|rule| rule.phase())
+            .collect();
+        assert!(phases.is_sorted());
+        assert_eq!(phases[0], RulePhase::Parse);
+    }
+
+    #[test]
+    fn test_config_value_serde() {
+        let values = vec![
+            ConfigValue::Bool(true),
+            ConfigValue::Int(42),
+            ConfigValue::Float(0.5),
+            ConfigValue::String("test".to_string()),
+            ConfigValue::Array(vec![ConfigValue::Int(1), ConfigValue::Int(2)]),
+            ConfigValue::Map(BTreeMap::from([("a".to_string(), ConfigValue::Int(1))])),
+        ];
+
+        for value in values {
+            let serialized = serde_yaml::to_string(&value).expect("Failed to serialize");
+            let deserialized: ConfigValue =
+                serde_yaml::from_str(&serialized).expect("Failed to deserialize");
+            assert_eq!(value, deserialized);
+        }
+    }
+
+    #[test]
+    fn test_rule_registry_conflicts() {
+        let registry = RuleRegistry::with_default_rules();
+        assert!(registry.conflicts(&Config::default()).is_empty());
+
+        let mut config = Config::default();
+        let mut enable = |id: &str, params: &[(&str, ConfigValue)]| {
+            let rule = config.rules.get_mut(id).unwrap();
+            rule.enabled = true;
+            for (key, value) in params {
+                rule.set_param(*key, value.clone());
+            }
+        };
+        enable("document-structure", &[]);
+        enable("document-count", &[]);
+        enable(
+            "required-keys",
+            &[(
+                "files",
+                ConfigValue::Map(BTreeMap::from([(
+                    "*.yaml".to_string(),
+                    ConfigValue::Array(vec!["name".into(), "password".into()]),
+                )])),
+            )],
+        );
+        enable(
+            "forbidden-keys",
+            &[("keys", ConfigValue::Array(vec!["password".into()]))],
+        );
+        enable("value-length", &[("max", ConfigValue::Int(100))]);
+        enable(
+            "line-length",
+            &[("allow-non-breakable-words", ConfigValue::Bool(false))],
+        );
+
+        let conflicts: Vec<(String, String)> = registry
+            .conflicts(&config)
+            .into_iter()
+            .map(|conflict| (conflict.rule, conflict.other))
+            .collect();
+        assert_eq!(
+            conflicts,
+            vec![
+                ("forbidden-keys".to_string(), "required-keys".to_string()),
+                ("value-length".to_string(), "line-length".to_string()),
+            ]
+        );
+
+        // Non-breakable words are exempt from line-length, so value-length still applies
+        config
+            .rules
+            .get_mut("line-length")
+            .unwrap()
+            .set_param("allow-non-breakable-words", true);
+        assert_eq!(registry.conflicts(&config).len(), 1);
+
+        // Tabs fixed to two spaces are wrong indentation at four
+        let indentation = config.rules.get_mut("indentation").unwrap();
+        indentation.enabled = true;
+        indentation.set_param("spaces", 4i64);
+        assert!(
+            registry
+                .conflicts(&config)
+                .iter()
+                .any(|conflict| conflict.rule == "no-tabs" && conflict.other == "indentation")
+        );
+
+        // Keys quoted as required are unnecessary quotes to quoted-strings
+        let quoted_strings = config.rules.get_mut("quoted-strings").unwrap();
+        quoted_strings.enabled = true;
+        quoted_strings.set_param("required-only-when-needed", true);
+        let quoted_keys = config.rules.get_mut("quoted-keys").unwrap();
+        quoted_keys.enabled = true;
+        quoted_keys.set_param("required", "always");
+        assert!(
+            registry.conflicts(&config).iter().any(
+                |conflict| conflict.rule == "quoted-keys" && conflict.other == "quoted-strings"
+            )
+        );
+    }
+}
@@ -0,0 +1,287 @@
+//! Declared parameters of rules: their types, defaults and allowed values
+
+use super::{ConfigValue, RuleConfig, RuleDocs};
+use eyre::Result;
+use serde::Serialize;
+use std::fmt;
+
+/// Type of a rule parameter's values
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParamType {
+    Boolean,
+    Integer,
+    Number,
+    String,
+    List,
+    Map,
+}
+
+impl ParamType {
+    /// Type of a parameter with the given default
+    pub fn of(value: &ConfigValue) -> Self {
+        match value {
+            ConfigValue::Bool(_) => ParamType::Boolean,
+            ConfigValue::Int(_) => ParamType::Integer,
+            ConfigValue::Float(_) => ParamType::Number,
+            ConfigValue::String(_) => ParamType::String,
+            ConfigValue::Array(_) => ParamType::List,
+            ConfigValue::Map(_) => ParamType::Map,
+        }
+    }
+
+    /// The type's name with its article, for messages
+    fn article(&self) -> &'static str {
+        match self {
+            ParamType::Integer => "an",
+            _ => "a",
+        }
+    }
+}
+
+impl fmt::Display for ParamType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamType::Boolean => write!(f, "boolean"),
+            ParamType::Integer => write!(f, "integer"),
+            ParamType::Number => write!(f, "number"),
+            ParamType::String => write!(f, "string"),
+            ParamType::List => write!(f, "list"),
+            ParamType::Map => write!(f, "map"),
+        }
+    }
+}
+
+/// A parameter a rule accepts
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParamSchema {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: ParamType,
+    pub default: ConfigValue,
+    /// Values a string parameter is restricted to; any value if empty
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allowed: Vec<String>,
+    pub description: String,
+}
+
+impl ParamSchema {
+    /// Schema of the parameters of a rule's default configuration, sorted by name, with
+    /// descriptions from its documentation. `choices` restricts string parameters to
+    /// the listed values.
+    pub fn from_defaults(
+        config: &RuleConfig,
+        docs: &RuleDocs,
+        choices: &[(&str, &[&str])],
+    ) -> Vec<Self> {
+        let mut schema: Vec<Self> = config
+            .params
+            .iter()
+            .map(|(name, default)| Self {
+                name: name.clone(),
+                kind: ParamType::of(default),
+                default: default.clone(),
+                allowed: choices
+                    .iter()
+                    .find(|(param, _)| param == name)
+                    .map(|(_, values)| values.iter().map(|value| value.to_string()).collect())
+                    .unwrap_or_default(),
+                description: docs
+                    .params
+                    .iter()
+                    .find(|(param, _)| param == name)
+                    .map_or("", |(_, meaning)| meaning)
+                    .to_string(),
+            })
+            .collect();
+        schema.sort_by(|a, b| a.name.cmp(&b.name));
+        schema
+    }
+
+    /// Convert a configured value to the parameter's type where that is lossless
+    /// (integers to numbers, comma-separated strings to lists) and check it
+    pub fn coerce(&self, value: ConfigValue) -> Result<ConfigValue> {
+        let value = match (self.kind, value) {
+            (ParamType::Number, ConfigValue::Int(int)) => ConfigValue::Float(int as f64),
+            (ParamType::List, ConfigValue::String(list)) => ConfigValue::Array(
+                list.split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(ConfigValue::from)
+                    .collect(),
+            ),
+            (_, value) => value,
+        };
+
+        if ParamType::of(&value) != self.kind {
+            return Err(eyre::eyre!(
+                "{} must be {} {}, got {}",
+                self.name,
+                self.kind.article(),
+                self.kind,
+                value
+            ));
+        }
+        if let ConfigValue::String(string) = &value
+            && !self.allowed.is_empty()
+            && !self.allowed.contains(string)
+        {
+            return Err(eyre::eyre!(
+                "{} must be one of {}, got {}",
+                self.name,
+                self.allowed.join(", "),
+                value
+            ));
+        }
+        Ok(value)
+    }
+
+    /// Parse a value written on the command line, e.g. with `--set`. Lists are written
+    /// as `a,b,c` or in JSON syntax, maps in JSON syntax.
+    pub fn parse(&self, value: &str) -> Result<ConfigValue> {
+        let parsed = match self.kind {
+            ParamType::Boolean => value
+                .parse()
+                .map(ConfigValue::Bool)
+                .map_err(|_| eyre::eyre!("expected a boolean (true or false), got '{value}'"))?,
+            ParamType::Integer => value
+                .parse()
+                .map(ConfigValue::Int)
+                .map_err(|_| eyre::eyre!("expected an integer, got '{value}'"))?,
+            ParamType::Number => value
+                .parse::<f64>()
+                .ok()
+                .filter(|number| number.is_finite())
+                .map(ConfigValue::Float)
+                .ok_or_else(|| eyre::eyre!("expected a number, got '{value}'"))?,
+            ParamType::String => ConfigValue::String(value.to_string()),
+            ParamType::List if value.starts_with('[') => match parse_flow(value)? {
+                list @ ConfigValue::Array(_) => list,
+                _ => return Err(eyre::eyre!("expected a list, got '{value}'")),
+            },
+            ParamType::List => {
+                // Items are of the type of the default's items, or strings if it has none
+                let item = Self {
+                    name: self.name.clone(),
+                    kind: self
+                        .default
+                        .as_array()
+                        .and_then(|items| items.first())
+                        .map_or(ParamType::String, ParamType::of),
+                    default: ConfigValue::String(String::new()),
+                    allowed: Vec::new(),
+                    description: String::new(),
+                };
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .map(|value| item.parse(value))
+                    .collect::<Result<_>>()
+                    .map(ConfigValue::Array)?
+            }
+            ParamType::Map => match parse_flow(value) {
+                Ok(map @ ConfigValue::Map(_)) => map,
+                _ => {
+                    return Err(eyre::eyre!(
+                        "expected a map in JSON syntax, e.g. '{{\"*.yaml\": [\"name\"]}}', got '{value}'"
+                    ));
+                }
+            },
+        };
+
+        self.coerce(parsed)
+    }
+}
+
+/// Parse a list or map in JSON (or YAML flow) syntax
+fn parse_flow(value: &str) -> Result<ConfigValue> {
+    serde_yaml::from_str(value).map_err(|e| eyre::eyre!("invalid list or map '{value}': {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::Level;
+
+    fn schema(name: &str, default: impl Into<ConfigValue>) -> ParamSchema {
+        let mut config = RuleConfig::new(true, Level::Error);
+        config.set_param(name, default);
+        let docs = RuleDocs {
+            params: &[("unit", "what to count")],
+            ..RuleDocs::default()
+        };
+        ParamSchema::from_defaults(&config, &docs, &[("unit", &["chars", "bytes"])]).remove(0)
+    }
+
+    #[test]
+    fn test_from_defaults() {
+        let unit = schema("unit", "chars");
+        assert_eq!(unit.kind, ParamType::String);
+        assert_eq!(unit.allowed, vec!["chars", "bytes"]);
+        assert_eq!(unit.description, "what to count");
+        assert_eq!(
+            ParamType::of(&ConfigValue::Array(vec![])).to_string(),
+            "list"
+        );
+    }
+
+    #[test]
+    fn test_coerce() {
+        let unit = schema("unit", "chars");
+        assert!(unit.coerce("bytes".into()).is_ok());
+        assert_eq!(
+            unit.coerce("words".into()).unwrap_err().to_string(),
+            "unit must be one of chars, bytes, got \"words\""
+        );
+
+        let max = schema("max", 80i64);
+        assert_eq!(
+            max.coerce(true.into()).unwrap_err().to_string(),
+            "max must be an integer, got true"
+        );
+
+        let ratio = schema("ratio", 0.5);
+        assert_eq!(ratio.coerce(1i64.into()).unwrap(), ConfigValue::Float(1.0));
+
+        let keys = schema("keys", ConfigValue::Array(vec![]));
+        assert_eq!(
+            keys.coerce("a, b".into()).unwrap(),
+            ConfigValue::Array(vec!["a".into(), "b".into()])
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let keys = schema("keys", ConfigValue::Array(vec![]));
+        assert_eq!(
+            keys.parse("password, 42").unwrap(),
+            ConfigValue::Array(vec!["password".into(), "42".into()])
+        );
+        assert_eq!(
+            keys.parse(r#"["a", "b"]"#).unwrap(),
+            ConfigValue::Array(vec!["a".into(), "b".into()])
+        );
+        assert_eq!(keys.parse("").unwrap(), ConfigValue::Array(vec![]));
+
+        let values = schema("values", "true,false");
+        assert_eq!(
+            values.parse("true,false").unwrap(),
+            ConfigValue::String("true,false".to_string())
+        );
+        assert_eq!(
+            schema("ratio", 1.0).parse("2").unwrap(),
+            ConfigValue::Float(2.0)
+        );
+        assert_eq!(
+            schema("max", 80i64).parse("many").unwrap_err().to_string(),
+            "expected an integer, got 'many'"
+        );
+        assert!(
+            schema("files", ConfigValue::Map(Default::default()))
+                .parse("a,b")
+                .is_err()
+        );
+        assert!(schema("unit", "chars").parse("words").is_err());
+    }
+}
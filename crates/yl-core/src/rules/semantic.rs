@@ -0,0 +1,2572 @@
+use super::{ConfigValue, ParamSchema, Rule, RuleConfig, RuleConflict, RuleDocs, RulePhase};
+use crate::config::IgnorePatterns;
+use crate::config::glob;
+use crate::linter::{Level, LintContext, Problem};
+use crate::messages::Message;
+use crate::parser::parse_documents;
+use crate::rules::common;
+use eyre::Result;
+use regex::Regex;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::LazyLock;
+
+/// Rule that enforces consistent boolean value representation
+#[derive(Debug, Default)]
+pub struct TruthyRule;
+
+impl TruthyRule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Rule for TruthyRule {
+    fn id(&self) -> &'static str {
+        "truthy"
+    }
+
+    fn phase(&self) -> RulePhase {
+        RulePhase::Semantic
+    }
+
+    fn description(&self) -> &'static str {
+        "Enforces consistent boolean value representation"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "YAML 1.1 parsers read values such as `yes`, `no`, `on` and `off` as booleans, which is rarely intended. Only the allowed spellings may be used as unquoted values and keys.",
+            params: &[
+                ("allowed-values", "list of allowed boolean spellings"),
+                ("check-keys", "also check mapping keys"),
+                (
+                    "ignore-quoted",
+                    "skip quoted values; when false, quoted truthy values are reported too",
+                ),
+                (
+                    "check-quoted",
+                    "check quoted values against the allowed spellings too, so `\"True\"` is reported when only `true` is allowed; overrides `ignore-quoted`",
+                ),
+            ],
+            failing: "---\nenabled: yes\n",
+            passing: "---\nenabled: true\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        let mut problems = Vec::new();
+
+        let allowed_values = match config.params.get("allowed-values") {
+            Some(ConfigValue::Array(values)) => values
+                .iter()
+                .filter_map(ConfigValue::as_string)
+                .map(str::to_string)
+                .collect(),
+            // Older configurations spell the list as a comma-separated string
+            Some(ConfigValue::String(values)) => {
+                values.split(',').map(|s| s.trim().to_string()).collect()
+            }
+            _ => vec!["true".to_string(), "false".to_string()],
+        };
+
+        let check_keys = config.get_bool("check-keys").unwrap_or(true);
+        let ignore_quoted = !config.get_bool("check-quoted").unwrap_or(false)
+            && config.get_bool("ignore-quoted").unwrap_or(true);
+
+        let tokens = context.tokens();
+        for (line_no, line) in context.content.lines().enumerate() {
+            let line_number = line_no + 1;
+            // Block scalar content is text, not values
+            if tokens.in_block_scalar(line_number) {
+                continue;
+            }
+            let content = &line[..tokens.comment_start(line_number).unwrap_or(line.len())];
+            let trimmed = content.trim();
+
+            // Skip comments and empty lines
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            // Skip the sequence indicator so `- yes` is checked like a value
+            let mut start = content.len() - content.trim_start().len();
+            while content[start..].starts_with("- ") {
+                start += 2;
+                start += content[start..].len() - content[start..].trim_start().len();
+            }
+
+            let mut tokens = Vec::new();
+            match common::find_mapping_colon(&content[start..]) {
+                Some(colon) => {
+                    if check_keys {
+                        tokens.push(start);
+                    }
+                    let value_start = start + colon + 1;
+                    let rest = &content[value_start..];
+                    tokens.push(value_start + rest.len() - rest.trim_start().len());
+                }
+                None => tokens.push(start),
+            }
+
+            for token_start in tokens {
+                let token = self.scalar_token(&content[token_start..]);
+                let column = LintContext::char_column(line, token_start);
+                self.check_truthy_value(
+                    token,
+                    ignore_quoted,
+                    line_number,
+                    column,
+                    &allowed_values,
+                    &mut problems,
+                );
+            }
+        }
+
+        Ok(problems)
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(false, Level::Error); // Disabled by default
+        config.set_param(
+            "allowed-values".to_string(),
+            ConfigValue::Array(vec!["true".into(), "false".into()]),
+        );
+        config.set_param("check-keys".to_string(), ConfigValue::Bool(true));
+        config.set_param("ignore-quoted".to_string(), ConfigValue::Bool(true));
+        config.set_param("check-quoted".to_string(), ConfigValue::Bool(false));
+        config
+    }
+
+    fn validate_config(&self, _config: &RuleConfig) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl TruthyRule {
+    /// Extract the scalar token at the start of `text`, up to the key separator
+    fn scalar_token<'a>(&self, text: &'a str) -> &'a str {
+        let end = common::find_mapping_colon(text).unwrap_or(text.len());
+        text[..end].trim_end()
+    }
+
+    fn check_truthy_value(
+        &self,
+        token: &str,
+        ignore_quoted: bool,
+        line_number: usize,
+        column: usize,
+        allowed_values: &[String],
+        problems: &mut Vec<Problem>,
+    ) {
+        let truthy_variants = [
+            "yes", "Yes", "YES", "no", "No", "NO", "on", "On", "ON", "off", "Off", "OFF", "True",
+            "TRUE", "False", "FALSE",
+        ];
+
+        let quoted = token.len() >= 2
+            && ((token.starts_with('"') && token.ends_with('"'))
+                || (token.starts_with('\'') && token.ends_with('\'')));
+        if quoted && ignore_quoted {
+            return;
+        }
+        let value = if quoted {
+            &token[1..token.len() - 1]
+        } else {
+            token
+        };
+
+        for variant in &truthy_variants {
+            if value == *variant && !allowed_values.contains(&variant.to_string()) {
+                problems.push(Problem::from_message(
+                    line_number,
+                    column,
+                    Level::Error,
+                    self.id(),
+                    Message::new("truthy.not-allowed")
+                        .arg("allowed", allowed_values.join(", "))
+                        .arg("value", variant),
+                ));
+            }
+        }
+    }
+}
+
+/// Rule that enforces consistent string quoting
+#[derive(Debug, Default)]
+pub struct QuotedStringsRule;
+
+impl QuotedStringsRule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Rule for QuotedStringsRule {
+    fn id(&self) -> &'static str {
+        "quoted-strings"
+    }
+
+    fn phase(&self) -> RulePhase {
+        RulePhase::Semantic
+    }
+
+    fn description(&self) -> &'static str {
+        "Enforces consistent string quoting"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "Enforces a single quoting style for strings and, optionally, forbids quotes where they are not needed.",
+            params: &[
+                (
+                    "quote-type",
+                    "required quote style: `any`, `single` or `double`",
+                ),
+                (
+                    "required-only-when-needed",
+                    "report quoted strings that would be valid without quotes",
+                ),
+            ],
+            failing: "---\n# with required-only-when-needed: true\nname: \"plain\"\n",
+            passing: "---\n# with required-only-when-needed: true\nname: plain\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        let mut problems = Vec::new();
+
+        let quote_type = config.get_string("quote-type").unwrap_or("any");
+        let required_only_when_needed = config
+            .get_bool("required-only-when-needed")
+            .unwrap_or(false);
+
+        for (line_no, line) in context.content.lines().enumerate() {
+            let line_number = line_no + 1;
+            let trimmed = line.trim();
+
+            // Skip comments and empty lines
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            // Look for quoted strings
+            self.check_quoted_strings_in_line(
+                line,
+                line_number,
+                quote_type,
+                required_only_when_needed,
+                &mut problems,
+            );
+        }
+
+        Ok(problems)
+    }
+
+    fn param_schema(&self) -> Vec<ParamSchema> {
+        ParamSchema::from_defaults(
+            &self.default_config(),
+            &self.docs(),
+            &[("quote-type", &["any", "single", "double"])],
+        )
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(false, Level::Error); // Disabled by default
+        config.set_param(
+            "quote-type".to_string(),
+            ConfigValue::String("any".to_string()),
+        );
+        config.set_param(
+            "required-only-when-needed".to_string(),
+            ConfigValue::Bool(false),
+        );
+        config
+    }
+
+    fn validate_config(&self, _config: &RuleConfig) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl QuotedStringsRule {
+    fn check_quoted_strings_in_line(
+        &self,
+        line: &str,
+        line_number: usize,
+        quote_type: &str,
+        required_only_when_needed: bool,
+        problems: &mut Vec<Problem>,
+    ) {
+        let chars: Vec<char> = line.chars().collect();
+        let mut i = 0;
+
+        while i < chars.len() {
+            if chars[i] == '"' || chars[i] == '\'' {
+                let quote_char = chars[i];
+                let start_pos = i;
+                i += 1;
+
+                // Find the end of the string
+                while i < chars.len() && chars[i] != quote_char {
+                    if chars[i] == '\\' && i + 1 < chars.len() {
+                        i += 2; // Skip escaped character
+                    } else {
+                        i += 1;
+                    }
+                }
+
+                if i < chars.len() {
+                    // Found complete quoted string
+                    let string_content: String = chars[start_pos + 1..i].iter().collect();
+
+                    match quote_type {
+                        "single" if quote_char == '"' => {
+                            problems.push(Problem::from_message(
+                                line_number,
+                                start_pos + 1,
+                                Level::Error,
+                                self.id(),
+                                Message::new("quoted-strings.single"),
+                            ));
+                        }
+                        "double" if quote_char == '\'' => {
+                            problems.push(Problem::from_message(
+                                line_number,
+                                start_pos + 1,
+                                Level::Error,
+                                self.id(),
+                                Message::new("quoted-strings.double"),
+                            ));
+                        }
+                        _ => {}
+                    }
+
+                    if required_only_when_needed && !self.needs_quoting(&string_content) {
+                        problems.push(Problem::from_message(
+                            line_number,
+                            start_pos + 1,
+                            Level::Error,
+                            self.id(),
+                            Message::new("quoted-strings.not-needed"),
+                        ));
+                    }
+                }
+                i += 1;
+            } else {
+                i += 1;
+            }
+        }
+    }
+
+    fn needs_quoting(&self, content: &str) -> bool {
+        // Check if string needs quoting (contains special characters, etc.)
+        content.contains(':')
+            || content.contains('#')
+            || content.contains('[')
+            || content.contains(']')
+            || content.contains('{')
+            || content.contains('}')
+            || content.starts_with(' ')
+            || content.ends_with(' ')
+            || content.parse::<f64>().is_ok()
+            || content.parse::<bool>().is_ok()
+    }
+}
+
+/// How the key-ordering rule compares keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyOrder {
+    /// By Unicode code point
+    Lexical,
+    /// Runs of digits by numeric value, so `item2` comes before `item10`
+    Natural,
+    /// Dictionary order: letters and digits only, ignoring case
+    Locale,
+}
+
+impl KeyOrder {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "lexical" => Some(Self::Lexical),
+            "natural" => Some(Self::Natural),
+            "locale" => Some(Self::Locale),
+            _ => None,
+        }
+    }
+}
+
+/// Settings of the key-ordering rule, parsed from its configuration
+struct KeyOrdering {
+    order: KeyOrder,
+    ignore_case: bool,
+    ignored_keys: Vec<Regex>,
+    priority_keys: Vec<String>,
+}
+
+impl KeyOrdering {
+    /// Compare two keys: priority keys first in their listed order, then the rest
+    fn compare(&self, a: &str, b: &str) -> Ordering {
+        let priority = |key: &str| self.priority_keys.iter().position(|p| p == key);
+        match (priority(a), priority(b)) {
+            (Some(a), Some(b)) => return a.cmp(&b),
+            (Some(_), None) => return Ordering::Less,
+            (None, Some(_)) => return Ordering::Greater,
+            (None, None) => {}
+        }
+
+        let (a, b) = if self.ignore_case {
+            (a.to_lowercase(), b.to_lowercase())
+        } else {
+            (a.to_string(), b.to_string())
+        };
+        match self.order {
+            KeyOrder::Lexical => a.cmp(&b),
+            KeyOrder::Natural => natural_cmp(&a, &b),
+            KeyOrder::Locale => {
+                let letters = |key: &str| -> String {
+                    key.chars()
+                        .filter(|c| c.is_alphanumeric())
+                        .flat_map(char::to_lowercase)
+                        .collect()
+                };
+                letters(&a).cmp(&letters(&b)).then_with(|| a.cmp(&b))
+            }
+        }
+    }
+}
+
+/// Compare strings treating each run of ASCII digits as one number
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a, b);
+    loop {
+        let (Some(x), Some(y)) = (a.chars().next(), b.chars().next()) else {
+            return a.len().cmp(&b.len());
+        };
+
+        if x.is_ascii_digit() && y.is_ascii_digit() {
+            let split = |s: &str| s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+            let (a_digits, a_rest) = a.split_at(split(a));
+            let (b_digits, b_rest) = b.split_at(split(b));
+            let (a_num, b_num) = (
+                a_digits.trim_start_matches('0'),
+                b_digits.trim_start_matches('0'),
+            );
+            let ordering = a_num
+                .len()
+                .cmp(&b_num.len())
+                .then_with(|| a_num.cmp(b_num))
+                .then_with(|| a_digits.len().cmp(&b_digits.len()));
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+            (a, b) = (a_rest, b_rest);
+        } else {
+            if x != y {
+                return x.cmp(&y);
+            }
+            (a, b) = (&a[x.len_utf8()..], &b[y.len_utf8()..]);
+        }
+    }
+}
+
+/// Rule that enforces ordering of keys in mappings
+#[derive(Debug, Default)]
+pub struct KeyOrderingRule;
+
+impl KeyOrderingRule {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse the rule's parameters
+    fn settings(&self, config: &RuleConfig) -> Result<KeyOrdering> {
+        let order = match config.get_string("ordering") {
+            Some(order) => KeyOrder::parse(order).ok_or_else(|| {
+                eyre::eyre!(
+                    "ordering must be \"lexical\", \"natural\" or \"locale\", got \"{order}\""
+                )
+            })?,
+            None => KeyOrder::Lexical,
+        };
+
+        let strings = |param: &str| -> Result<Vec<&str>> {
+            match config.params.get(param) {
+                Some(values) => values
+                    .as_array()
+                    .and_then(|values| values.iter().map(ConfigValue::as_string).collect())
+                    .ok_or_else(|| eyre::eyre!("{param} must be a list of strings")),
+                None => Ok(Vec::new()),
+            }
+        };
+
+        let ignored_keys = strings("ignored-keys")?
+            .into_iter()
+            .map(|pattern| {
+                Regex::new(pattern)
+                    .map_err(|e| eyre::eyre!("invalid ignored-keys regex '{pattern}': {e}"))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(KeyOrdering {
+            order,
+            ignore_case: config.get_bool("ignore-case").unwrap_or(false),
+            ignored_keys,
+            priority_keys: strings("priority-keys")?
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+        })
+    }
+}
+
+impl Rule for KeyOrderingRule {
+    fn id(&self) -> &'static str {
+        "key-ordering"
+    }
+
+    fn phase(&self) -> RulePhase {
+        RulePhase::Semantic
+    }
+
+    fn description(&self) -> &'static str {
+        "Enforces ordering of keys in mappings"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "Requires the keys of each mapping to be sorted, which keeps large files easy to scan and merge. Keys listed in `priority-keys` come first, in the listed order.",
+            params: &[
+                (
+                    "ordering",
+                    "`lexical` (by code point), `natural` (numbers by value, so `item2` comes before `item10`) or `locale` (dictionary order, ignoring case and punctuation)",
+                ),
+                ("ignore-case", "compare keys without regard to case"),
+                (
+                    "ignored-keys",
+                    "regular expressions; matching keys are not checked and do not affect the order of others",
+                ),
+                (
+                    "priority-keys",
+                    "keys that must come before all others, in this order, such as `apiVersion`",
+                ),
+            ],
+            failing: "---\nname: app\nimage: app:latest\n",
+            passing: "---\nimage: app:latest\nname: app\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        let mut problems = Vec::new();
+        let settings = self.settings(config)?;
+
+        // Documents start after each `---`, so equal paths in different documents are
+        // different mappings
+        let document_starts: Vec<usize> = context
+            .lines()
+            .filter(|(_, line)| line.starts_with("---"))
+            .map(|(line_no, _)| line_no)
+            .collect();
+
+        // Greatest key so far of each mapping, by document and path of the mapping
+        let mut greatest: HashMap<(usize, Vec<String>), String> = HashMap::new();
+
+        for key in context.key_paths() {
+            let Some((name, parent)) = key.path.split_last() else {
+                continue;
+            };
+            if settings
+                .ignored_keys
+                .iter()
+                .any(|pattern| pattern.is_match(name))
+            {
+                continue;
+            }
+
+            let document = document_starts.partition_point(|&start| start < key.line);
+            match greatest.get_mut(&(document, parent.to_vec())) {
+                Some(max) if settings.compare(max, name) == Ordering::Greater => {
+                    problems.push(Problem::from_message(
+                        key.line,
+                        key.column,
+                        config.level.clone(),
+                        self.id(),
+                        Message::new("key-ordering.wrong").arg("key", name),
+                    ));
+                }
+                Some(max) => *max = name.clone(),
+                None => {
+                    greatest.insert((document, parent.to_vec()), name.clone());
+                }
+            }
+        }
+
+        // Each flow mapping is ordered on its own
+        for collection in context.flow_collections() {
+            let mut greatest: Option<&str> = None;
+            for (line, column, name) in &collection.keys {
+                if settings
+                    .ignored_keys
+                    .iter()
+                    .any(|pattern| pattern.is_match(name))
+                {
+                    continue;
+                }
+                match greatest {
+                    Some(max) if settings.compare(max, name) == Ordering::Greater => {
+                        problems.push(Problem::from_message(
+                            *line,
+                            *column,
+                            config.level.clone(),
+                            self.id(),
+                            Message::new("key-ordering.wrong").arg("key", name),
+                        ));
+                    }
+                    _ => greatest = Some(name),
+                }
+            }
+        }
+        problems.sort_by_key(|problem| (problem.line, problem.column));
+
+        Ok(problems)
+    }
+
+    fn param_schema(&self) -> Vec<ParamSchema> {
+        ParamSchema::from_defaults(
+            &self.default_config(),
+            &self.docs(),
+            &[("ordering", &["lexical", "natural", "locale"])],
+        )
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(false, Level::Error); // Disabled by default
+        config.set_param("ordering", "lexical");
+        config.set_param("ignore-case", false);
+        config.set_param("ignored-keys", Vec::<ConfigValue>::new());
+        config.set_param("priority-keys", Vec::<ConfigValue>::new());
+        config
+    }
+
+    fn validate_config(&self, config: &RuleConfig) -> Result<()> {
+        self.settings(config).map(|_| ())
+    }
+}
+
+/// Rule that validates float value formats
+#[derive(Debug, Default)]
+pub struct FloatValuesRule;
+
+impl FloatValuesRule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Rule for FloatValuesRule {
+    fn id(&self) -> &'static str {
+        "float-values"
+    }
+
+    fn phase(&self) -> RulePhase {
+        RulePhase::Semantic
+    }
+
+    fn description(&self) -> &'static str {
+        "Validates float value formats"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "Restricts how floating point numbers may be written.",
+            params: &[
+                (
+                    "forbid-scientific-notation",
+                    "report floats written in scientific notation, such as `1e3`",
+                ),
+                (
+                    "require-numeral-before-decimal",
+                    "report floats without a digit before the decimal point, such as `.5`",
+                ),
+            ],
+            failing: "---\n# with require-numeral-before-decimal: true\nratio: .5\n",
+            passing: "---\n# with require-numeral-before-decimal: true\nratio: 0.5\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        let mut problems = Vec::new();
+
+        let forbid_scientific_notation = config
+            .get_bool("forbid-scientific-notation")
+            .unwrap_or(false);
+        let require_numeral_before_decimal = config
+            .get_bool("require-numeral-before-decimal")
+            .unwrap_or(false);
+
+        for (line_no, line) in context.content.lines().enumerate() {
+            let line_number = line_no + 1;
+
+            // Look for float values
+            if let Some(colon_pos) = line.find(':') {
+                let value_part = line[colon_pos + 1..].trim();
+
+                if value_part.parse::<f64>().is_ok() {
+                    if forbid_scientific_notation
+                        && (value_part.contains('e') || value_part.contains('E'))
+                    {
+                        problems.push(Problem::from_message(
+                            line_number,
+                            LintContext::char_column(line, colon_pos + 1),
+                            Level::Error,
+                            self.id(),
+                            Message::new("float-values.scientific-notation"),
+                        ));
+                    }
+
+                    if require_numeral_before_decimal && value_part.starts_with('.') {
+                        problems.push(Problem::from_message(
+                            line_number,
+                            LintContext::char_column(line, colon_pos + 1),
+                            Level::Error,
+                            self.id(),
+                            Message::new("float-values.numeral-before-decimal"),
+                        ));
+                    }
+                }
+            }
+        }
+
+        Ok(problems)
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(false, Level::Error); // Disabled by default
+        config.set_param(
+            "forbid-scientific-notation".to_string(),
+            ConfigValue::Bool(false),
+        );
+        config.set_param(
+            "require-numeral-before-decimal".to_string(),
+            ConfigValue::Bool(false),
+        );
+        config
+    }
+
+    fn validate_config(&self, _config: &RuleConfig) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Rule that detects octal values
+#[derive(Debug, Default)]
+pub struct OctalValuesRule;
+
+impl OctalValuesRule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Rule for OctalValuesRule {
+    fn id(&self) -> &'static str {
+        "octal-values"
+    }
+
+    fn phase(&self) -> RulePhase {
+        RulePhase::Semantic
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects and forbids octal values"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "YAML 1.1 reads numbers with a leading zero such as `010` as octal, which is a common source of surprises in file modes and identifiers.",
+            params: &[
+                (
+                    "forbid-implicit-octal",
+                    "report numbers with a leading zero, such as `010`",
+                ),
+                (
+                    "forbid-explicit-octal",
+                    "report numbers with an explicit `0o` prefix, such as `0o10`",
+                ),
+            ],
+            failing: "---\nmode: 0644\n",
+            passing: "---\nmode: \"0644\"\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        let mut problems = Vec::new();
+
+        let forbid_implicit_octal = config.get_bool("forbid-implicit-octal").unwrap_or(true);
+        let forbid_explicit_octal = config.get_bool("forbid-explicit-octal").unwrap_or(false);
+
+        for (line_no, line) in context.content.lines().enumerate() {
+            let line_number = line_no + 1;
+
+            // Look for potential octal values
+            if let Some(colon_pos) = line.find(':') {
+                let value_part = line[colon_pos + 1..].trim();
+
+                // Check for implicit octal (starts with 0 followed by digits)
+                if forbid_implicit_octal
+                    && value_part.len() > 1
+                    && value_part.starts_with('0')
+                    && value_part.chars().nth(1).unwrap().is_ascii_digit()
+                {
+                    // Make sure it's not a decimal number
+                    if !value_part.contains('.') && value_part.parse::<i64>().is_ok() {
+                        problems.push(Problem::from_message(
+                            line_number,
+                            LintContext::char_column(line, colon_pos + 1),
+                            Level::Error,
+                            self.id(),
+                            Message::new("octal-values.implicit").arg("value", value_part),
+                        ));
+                    }
+                }
+
+                // Check for explicit octal (0o prefix)
+                if forbid_explicit_octal && value_part.starts_with("0o") {
+                    problems.push(Problem::from_message(
+                        line_number,
+                        LintContext::char_column(line, colon_pos + 1),
+                        Level::Error,
+                        self.id(),
+                        Message::new("octal-values.explicit").arg("value", value_part),
+                    ));
+                }
+            }
+        }
+
+        Ok(problems)
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(false, Level::Error); // Disabled by default
+        config.set_param("forbid-implicit-octal".to_string(), ConfigValue::Bool(true));
+        config.set_param(
+            "forbid-explicit-octal".to_string(),
+            ConfigValue::Bool(false),
+        );
+        config
+    }
+
+    fn validate_config(&self, _config: &RuleConfig) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Rule that requires top-level keys in files matching a pattern
+#[derive(Debug, Default)]
+pub struct RequiredKeysRule;
+
+impl RequiredKeysRule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Rule for RequiredKeysRule {
+    fn id(&self) -> &'static str {
+        "required-keys"
+    }
+
+    fn phase(&self) -> RulePhase {
+        RulePhase::Semantic
+    }
+
+    fn description(&self) -> &'static str {
+        "Requires top-level keys in files matching a pattern"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "Maps file patterns, with the same syntax as `ignore`, to the top-level keys every matching file must define, e.g. `services/**: [name, owner, tier]`. Each document of a multi-document file must define them; missing keys are reported on the document's last line.",
+            params: &[(
+                "files",
+                "map of file patterns to the list of top-level keys they require",
+            )],
+            failing: "---\n# with files: {\"*.yaml\": [name, owner]}\nname: payments\n",
+            passing: "---\n# with files: {\"*.yaml\": [name, owner]}\nname: payments\nowner: billing\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        let mut problems = Vec::new();
+
+        let Some(files) = config.get_map("files") else {
+            return Ok(problems);
+        };
+
+        // Ignore patterns double as the repo's path matcher
+        let required: Vec<&str> = files
+            .iter()
+            .filter(|(pattern, _)| IgnorePatterns::new(&[*pattern]).is_ignored(context.file_path))
+            .flat_map(|(_, keys)| keys.as_array().into_iter().flatten())
+            .filter_map(ConfigValue::as_string)
+            .collect();
+        if required.is_empty() {
+            return Ok(problems);
+        }
+
+        let spans = parse_documents(context.content);
+        for (index, document) in context.documents().iter().enumerate() {
+            let serde_yaml::Value::Mapping(document) = document else {
+                continue;
+            };
+            // Last line of the document, or of the file if the documents were not told apart
+            let line = spans
+                .get(index)
+                .map_or(context.line_count(), |span| span.lines.end - 1)
+                .clamp(1, context.line_count().max(1));
+
+            let mut reported = HashSet::new();
+            for &key in &required {
+                if !document.contains_key(key) && reported.insert(key) {
+                    problems.push(Problem::from_message(
+                        line,
+                        1,
+                        Level::Error,
+                        self.id(),
+                        Message::new("required-keys.missing").arg("key", key),
+                    ));
+                }
+            }
+        }
+
+        Ok(problems)
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(false, Level::Error); // Disabled by default
+        config.set_param("files".to_string(), ConfigValue::Map(BTreeMap::new()));
+        config
+    }
+
+    fn validate_config(&self, config: &RuleConfig) -> Result<()> {
+        let Some(files) = config.params.get("files") else {
+            return Ok(());
+        };
+        let files = files
+            .as_map()
+            .ok_or_else(|| eyre::eyre!("files must be a map of file patterns to key lists"))?;
+
+        for (pattern, keys) in files {
+            IgnorePatterns::validate(pattern)?;
+            let is_key_list = keys
+                .as_array()
+                .is_some_and(|keys| keys.iter().all(|key| key.as_string().is_some()));
+            if !is_key_list {
+                return Err(eyre::eyre!(
+                    "required keys for '{pattern}' must be a list of strings"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Rule that reports keys that must not be used
+#[derive(Debug, Default)]
+pub struct ForbiddenKeysRule;
+
+impl ForbiddenKeysRule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// How a `forbidden-keys` entry selects keys
+enum KeyPattern {
+    /// A bare key name, matched at any depth
+    Name(String),
+    /// A dotted path from the document root, where `*` matches any one segment
+    Path(String),
+    /// A regex matched against the dotted path
+    Regex(Regex),
+}
+
+impl KeyPattern {
+    /// Check if the key at `path`, written `dotted`, is selected
+    fn matches(&self, path: &[String], dotted: &str) -> bool {
+        match self {
+            Self::Name(name) => path.last() == Some(name),
+            Self::Path(pattern) => LintContext::path_matches(pattern, path),
+            Self::Regex(regex) => regex.is_match(dotted),
+        }
+    }
+}
+
+/// A parsed `forbidden-keys` entry
+struct ForbiddenKey {
+    pattern: KeyPattern,
+    message: Option<String>,
+    level: Option<Level>,
+}
+
+impl Rule for ForbiddenKeysRule {
+    fn id(&self) -> &'static str {
+        "forbidden-keys"
+    }
+
+    fn phase(&self) -> RulePhase {
+        RulePhase::Semantic
+    }
+
+    fn description(&self) -> &'static str {
+        "Reports keys that must not be used, such as deprecated fields"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "Each entry of `keys` is a bare key name matched at any depth (`password`), a dotted path from the document root where `*` matches any one key or sequence index (`spec.template.spec.hostNetwork`), or a regex between slashes matched against the dotted path (`/secret$/`). An entry can also be a map with `key`, and optionally `message` and `level` to override the rule's message and level.",
+            params: &[(
+                "keys",
+                "list of key names, dotted paths or /regexes/ to report",
+            )],
+            failing: "---\n# with keys: [spec.hostNetwork]\nspec:\n  hostNetwork: true\n",
+            passing: "---\n# with keys: [spec.hostNetwork]\nspec:\n  dnsPolicy: ClusterFirst\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        let mut problems = Vec::new();
+
+        let entries = self.parse_entries(config)?;
+        if entries.is_empty() {
+            return Ok(problems);
+        }
+
+        for key in context.key_paths() {
+            let dotted = key.dotted();
+            let Some(entry) = entries
+                .iter()
+                .find(|entry| entry.pattern.matches(&key.path, &dotted))
+            else {
+                continue;
+            };
+
+            let level = entry.level.clone().unwrap_or_else(|| config.level.clone());
+            problems.push(match &entry.message {
+                Some(message) => {
+                    Problem::new(key.line, key.column, level, self.id(), message.clone())
+                }
+                None => Problem::from_message(
+                    key.line,
+                    key.column,
+                    level,
+                    self.id(),
+                    Message::new("forbidden-keys.found").arg("key", &dotted),
+                ),
+            });
+        }
+
+        Ok(problems)
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(false, Level::Error); // Disabled by default
+        config.set_param("keys".to_string(), ConfigValue::Array(Vec::new()));
+        config
+    }
+
+    fn validate_config(&self, config: &RuleConfig) -> Result<()> {
+        self.parse_entries(config).map(|_| ())
+    }
+
+    fn conflicts(
+        &self,
+        config: &RuleConfig,
+        enabled: &HashMap<String, RuleConfig>,
+    ) -> Vec<RuleConflict> {
+        let (Ok(entries), Some(files)) = (
+            self.parse_entries(config),
+            enabled
+                .get("required-keys")
+                .and_then(|required| required.get_map("files")),
+        ) else {
+            return Vec::new();
+        };
+
+        // Required keys are top-level keys
+        let mut conflicts = Vec::new();
+        for (pattern, keys) in files {
+            for key in keys.as_array().into_iter().flatten() {
+                let Some(key) = key.as_string() else {
+                    continue;
+                };
+                if entries
+                    .iter()
+                    .any(|entry| entry.pattern.matches(&[key.to_string()], key))
+                {
+                    conflicts.push(RuleConflict::new(
+                        self.id(),
+                        "required-keys",
+                        format!("\"{key}\" is forbidden, but required in files matching {pattern}"),
+                    ));
+                }
+            }
+        }
+        conflicts
+    }
+}
+
+impl ForbiddenKeysRule {
+    /// Parse the `keys` parameter
+    fn parse_entries(&self, config: &RuleConfig) -> Result<Vec<ForbiddenKey>> {
+        let Some(keys) = config.params.get("keys") else {
+            return Ok(Vec::new());
+        };
+        let keys = keys
+            .as_array()
+            .ok_or_else(|| eyre::eyre!("keys must be a list"))?;
+
+        keys.iter().map(|entry| self.parse_entry(entry)).collect()
+    }
+
+    fn parse_entry(&self, entry: &ConfigValue) -> Result<ForbiddenKey> {
+        if let Some(key) = entry.as_string() {
+            return Ok(ForbiddenKey {
+                pattern: self.parse_pattern(key)?,
+                message: None,
+                level: None,
+            });
+        }
+
+        let entry = entry
+            .as_map()
+            .ok_or_else(|| eyre::eyre!("keys entries must be strings or maps, got {entry}"))?;
+        let key = entry
+            .get("key")
+            .and_then(ConfigValue::as_string)
+            .ok_or_else(|| eyre::eyre!("keys entries must have a string 'key'"))?;
+        let level = entry
+            .get("level")
+            .map(|level| {
+                level
+                    .as_string()
+                    .ok_or_else(|| eyre::eyre!("level of '{key}' must be a string"))?
+                    .parse::<Level>()
+            })
+            .transpose()?;
+
+        Ok(ForbiddenKey {
+            pattern: self.parse_pattern(key)?,
+            message: entry
+                .get("message")
+                .and_then(ConfigValue::as_string)
+                .map(str::to_string),
+            level,
+        })
+    }
+
+    fn parse_pattern(&self, key: &str) -> Result<KeyPattern> {
+        if let Some(regex) = key
+            .strip_prefix('/')
+            .and_then(|rest| rest.strip_suffix('/'))
+        {
+            let regex = Regex::new(regex)
+                .map_err(|e| eyre::eyre!("Invalid regex in forbidden key '{key}': {e}"))?;
+            Ok(KeyPattern::Regex(regex))
+        } else if key.contains('.') {
+            Ok(KeyPattern::Path(key.to_string()))
+        } else {
+            Ok(KeyPattern::Name(key.to_string()))
+        }
+    }
+}
+
+/// Rule that reports repeated items within a sequence
+#[derive(Debug, Default)]
+pub struct SequenceDuplicatesRule;
+
+impl SequenceDuplicatesRule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Rule for SequenceDuplicatesRule {
+    fn id(&self) -> &'static str {
+        "sequence-duplicates"
+    }
+
+    fn phase(&self) -> RulePhase {
+        RulePhase::Semantic
+    }
+
+    fn description(&self) -> &'static str {
+        "Reports repeated scalar items within the same sequence"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "Flags a scalar item that already appears earlier in the same block sequence, such as a host listed twice in an allowlist. `paths` restricts the check to sequences at the given dotted key paths, where `*` matches any one key or index.",
+            params: &[
+                ("ignore-case", "compare items without regard to case"),
+                (
+                    "paths",
+                    "dotted key paths of the sequences to check; empty for all sequences",
+                ),
+            ],
+            failing: "---\nallowlist:\n  - api.example.com\n  - api.example.com\n",
+            passing: "---\nallowlist:\n  - api.example.com\n  - web.example.com\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        let mut problems = Vec::new();
+
+        let ignore_case = config.get_bool("ignore-case").unwrap_or(false);
+        let paths: Vec<&str> = config
+            .params
+            .get("paths")
+            .and_then(ConfigValue::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(ConfigValue::as_string)
+            .collect();
+
+        // First line of each item seen so far, per sequence
+        let mut sequences: HashMap<Vec<String>, HashMap<String, usize>> = HashMap::new();
+
+        for item in context.sequence_items() {
+            if !paths.is_empty()
+                && !paths
+                    .iter()
+                    .any(|pattern| LintContext::path_matches(pattern, &item.path))
+            {
+                continue;
+            }
+
+            let seen = sequences.entry(item.path.clone()).or_default();
+            // The first item starts a new sequence at the same path, e.g. in the next document
+            if item.index == 0 {
+                seen.clear();
+            }
+
+            let value = if ignore_case {
+                item.value.to_lowercase()
+            } else {
+                item.value.clone()
+            };
+            match seen.get(&value) {
+                Some(first_line) => problems.push(Problem::from_message(
+                    item.line,
+                    item.column,
+                    config.level.clone(),
+                    self.id(),
+                    Message::new("sequence-duplicates.found")
+                        .arg("value", &item.value)
+                        .arg("first_line", first_line),
+                )),
+                None => {
+                    seen.insert(value, item.line);
+                }
+            }
+        }
+
+        Ok(problems)
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(false, Level::Error); // Disabled by default
+        config.set_param("ignore-case".to_string(), ConfigValue::Bool(false));
+        config.set_param("paths".to_string(), ConfigValue::Array(Vec::new()));
+        config
+    }
+
+    fn validate_config(&self, config: &RuleConfig) -> Result<()> {
+        if let Some(paths) = config.params.get("paths") {
+            let is_path_list = paths
+                .as_array()
+                .is_some_and(|paths| paths.iter().all(|path| path.as_string().is_some()));
+            if !is_path_list {
+                return Err(eyre::eyre!("paths must be a list of strings"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Rule that requires selected sequences to be sorted
+#[derive(Debug, Default)]
+pub struct SequenceOrderingRule;
+
+impl SequenceOrderingRule {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Compare two sequence items, numerically (numbers before other values) or lexically
+    pub fn compare_items(numeric: bool, a: &str, b: &str) -> Ordering {
+        if numeric {
+            match (a.parse::<f64>(), b.parse::<f64>()) {
+                (Ok(a), Ok(b)) => return a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+                (Ok(_), Err(_)) => return Ordering::Less,
+                (Err(_), Ok(_)) => return Ordering::Greater,
+                (Err(_), Err(_)) => {}
+            }
+        }
+        a.cmp(b)
+    }
+}
+
+impl Rule for SequenceOrderingRule {
+    fn id(&self) -> &'static str {
+        "sequence-ordering"
+    }
+
+    fn phase(&self) -> RulePhase {
+        RulePhase::Semantic
+    }
+
+    fn fixable(&self) -> bool {
+        true
+    }
+
+    fn description(&self) -> &'static str {
+        "Requires the items of selected sequences to be sorted"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "Requires the scalar items of block sequences at the given dotted key paths to be sorted, which keeps lists such as `dependencies` free of merge conflicts. `*` in a path matches any one key or index. The auto-fix sorts the items, moving comments directly above an item along with it.",
+            params: &[
+                (
+                    "paths",
+                    "dotted key paths of the sequences to check; empty for all sequences",
+                ),
+                ("order", "`lexical` or `numeric` (numbers first, by value)"),
+            ],
+            failing: "---\ndependencies:\n  - requests\n  - attrs\n",
+            passing: "---\ndependencies:\n  - attrs\n  - requests\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        let mut problems = Vec::new();
+
+        let numeric = config.get_string("order") == Some("numeric");
+        let paths: Vec<&str> = config
+            .params
+            .get("paths")
+            .and_then(ConfigValue::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(ConfigValue::as_string)
+            .collect();
+
+        // Previous item per sequence; the first item starts a new sequence at its path
+        let mut previous: HashMap<Vec<String>, (usize, String)> = HashMap::new();
+        // Sequences already reported, so each is reported (and fixed) once
+        let mut reported: HashSet<(Vec<String>, usize)> = HashSet::new();
+        let mut starts: HashMap<Vec<String>, usize> = HashMap::new();
+
+        for item in context.sequence_items() {
+            if !paths.is_empty()
+                && !paths
+                    .iter()
+                    .any(|pattern| LintContext::path_matches(pattern, &item.path))
+            {
+                continue;
+            }
+
+            if item.index == 0 {
+                starts.insert(item.path.clone(), item.line);
+                previous.remove(&item.path);
+            }
+            let start = starts.get(&item.path).copied().unwrap_or(item.line);
+
+            if let Some((index, value)) = previous.get(&item.path)
+                && *index + 1 == item.index
+                && Self::compare_items(numeric, value, &item.value) == Ordering::Greater
+                && reported.insert((item.path.clone(), start))
+            {
+                problems.push(Problem::from_message(
+                    item.line,
+                    item.column,
+                    config.level.clone(),
+                    self.id(),
+                    Message::new("sequence-ordering.wrong")
+                        .arg("value", &item.value)
+                        .arg("previous", value),
+                ));
+            }
+            previous.insert(item.path.clone(), (item.index, item.value.clone()));
+        }
+
+        Ok(problems)
+    }
+
+    fn param_schema(&self) -> Vec<ParamSchema> {
+        ParamSchema::from_defaults(
+            &self.default_config(),
+            &self.docs(),
+            &[("order", &["lexical", "numeric"])],
+        )
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(false, Level::Error); // Disabled by default
+        config.set_param("paths".to_string(), ConfigValue::Array(Vec::new()));
+        config.set_param(
+            "order".to_string(),
+            ConfigValue::String("lexical".to_string()),
+        );
+        config
+    }
+
+    fn validate_config(&self, config: &RuleConfig) -> Result<()> {
+        if let Some(order) = config.get_string("order")
+            && !matches!(order, "lexical" | "numeric")
+        {
+            return Err(eyre::eyre!(
+                "order must be \"lexical\" or \"numeric\", got \"{order}\""
+            ));
+        }
+        if let Some(paths) = config.params.get("paths") {
+            let is_path_list = paths
+                .as_array()
+                .is_some_and(|paths| paths.iter().all(|path| path.as_string().is_some()));
+            if !is_path_list {
+                return Err(eyre::eyre!("paths must be a list of strings"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// ISO-8601 calendar date with an optional time and offset
+static ISO_8601: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(\d{4})-(\d{2})-(\d{2})(?:[Tt](\d{2}):(\d{2})(?::(\d{2})(?:\.\d+)?)?(?:[Zz]|[+-]\d{2}(?::?\d{2})?)?)?$",
+    )
+    .expect("valid ISO-8601 regex")
+});
+
+/// Rule that requires dates under date-like keys to be ISO-8601
+#[derive(Debug, Default)]
+pub struct DateFormatRule;
+
+impl DateFormatRule {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Check that a value is an ISO-8601 date or date-time with in-range fields
+    fn is_iso_8601(value: &str) -> bool {
+        let Some(captures) = ISO_8601.captures(value) else {
+            return false;
+        };
+        let field = |i: usize| {
+            captures
+                .get(i)
+                .map(|m| m.as_str().parse::<u32>().unwrap_or(0))
+        };
+
+        let (year, month, day) = (field(1), field(2), field(3));
+        let leap = year.is_some_and(|y| y % 4 == 0 && (y % 100 != 0 || y % 400 == 0));
+        let days_in_month = match month {
+            Some(1 | 3 | 5 | 7 | 8 | 10 | 12) => 31,
+            Some(4 | 6 | 9 | 11) => 30,
+            Some(2) if leap => 29,
+            Some(2) => 28,
+            _ => return false,
+        };
+
+        day.is_some_and(|d| (1..=days_in_month).contains(&d))
+            && field(4).is_none_or(|hour| hour < 24)
+            && field(5).is_none_or(|minute| minute < 60)
+            && field(6).is_none_or(|second| second <= 60)
+    }
+
+    /// Find the plain or quoted scalar after the key at byte offset `key_start`, with its offset
+    fn key_value(line: &str, key_start: usize) -> Option<(usize, &str)> {
+        let content = common::strip_comment(line).trim_end();
+        let after_key = content.get(key_start..)?;
+        let colon = common::find_mapping_colon(after_key)?;
+        let mut rest = after_key[colon + 1..].trim_start();
+
+        // Skip anchors and tags in front of the value
+        while rest.starts_with(['&', '!']) {
+            rest = rest
+                .split_once(' ')
+                .map_or("", |(_, value)| value.trim_start());
+        }
+        if rest.is_empty() || rest.starts_with(['{', '[', '|', '>', '*']) {
+            return None;
+        }
+
+        let value = rest
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .or_else(|| rest.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+            .unwrap_or(rest);
+        Some((content.len() - rest.len(), value))
+    }
+}
+
+impl Rule for DateFormatRule {
+    fn id(&self) -> &'static str {
+        "date-format"
+    }
+
+    fn phase(&self) -> RulePhase {
+        RulePhase::Semantic
+    }
+
+    fn description(&self) -> &'static str {
+        "Requires values of date-like keys to be ISO-8601 dates or timestamps"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "Requires the scalar values of keys matching the given name globs to be ISO-8601 dates (`2024-01-05`) or date-times (`2024-01-05T09:30:00Z`). Formats such as `2024-1-5` or `05/01/2024` are ambiguous, and YAML 1.1 consumers may read values like `12:30:00` as sexagesimal numbers. Null values are allowed.",
+            params: &[(
+                "keys",
+                "globs matched against key names, e.g. `*_at` or `date`",
+            )],
+            failing: "---\ncreated_at: 2024-1-5\n",
+            passing: "---\ncreated_at: 2024-01-05\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        let mut problems = Vec::new();
+
+        let keys: Vec<Regex> = config
+            .params
+            .get("keys")
+            .and_then(ConfigValue::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(ConfigValue::as_string)
+            .filter_map(|key| glob::compile(&format!("^{}$", glob::translate(key))).ok())
+            .collect();
+        if keys.is_empty() {
+            return Ok(problems);
+        }
+
+        let lines: Vec<&str> = context.content.lines().collect();
+        for key in context.key_paths() {
+            let Some(name) = key.path.last() else {
+                continue;
+            };
+            if !keys.iter().any(|glob| glob.is_match(name)) {
+                continue;
+            }
+
+            let line = lines[key.line - 1];
+            let key_start = line
+                .char_indices()
+                .nth(key.column - 1)
+                .map_or(line.len(), |(offset, _)| offset);
+            let Some((offset, value)) = Self::key_value(line, key_start) else {
+                continue;
+            };
+            if matches!(value, "" | "~" | "null" | "Null" | "NULL") || Self::is_iso_8601(value) {
+                continue;
+            }
+
+            problems.push(Problem::from_message(
+                key.line,
+                LintContext::char_column(line, offset),
+                config.level.clone(),
+                self.id(),
+                Message::new("date-format.invalid")
+                    .arg("value", value)
+                    .arg("key", name),
+            ));
+        }
+
+        Ok(problems)
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(false, Level::Error); // Disabled by default
+        let keys = ["*_at", "*_date", "date", "timestamp"]
+            .into_iter()
+            .map(ConfigValue::from)
+            .collect::<Vec<_>>();
+        config.set_param("keys".to_string(), ConfigValue::Array(keys));
+        config
+    }
+
+    fn validate_config(&self, config: &RuleConfig) -> Result<()> {
+        if let Some(keys) = config.params.get("keys") {
+            let Some(keys) = keys.as_array() else {
+                return Err(eyre::eyre!("keys must be a list of globs"));
+            };
+            for key in keys {
+                let Some(key) = key.as_string() else {
+                    return Err(eyre::eyre!("keys must be a list of globs"));
+                };
+                glob::compile(&format!("^{}$", glob::translate(key)))
+                    .map_err(|e| eyre::eyre!("Invalid key glob '{key}': {e}"))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Rule that enforces when mapping keys are quoted
+#[derive(Debug, Default)]
+pub struct QuotedKeysRule;
+
+impl QuotedKeysRule {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Get the key written at byte offset `start` of `line`, with its quotes
+    pub fn key_token(line: &str, start: usize) -> &str {
+        let rest = line.get(start..).unwrap_or("");
+        let end = common::find_mapping_colon(rest).unwrap_or(rest.len());
+        rest[..end].trim_end()
+    }
+
+    /// Get the text between the quotes of a quoted key, or `None` for a plain key
+    pub fn quoted_content(token: &str) -> Option<&str> {
+        token
+            .strip_prefix('"')
+            .and_then(|k| k.strip_suffix('"'))
+            .or_else(|| token.strip_prefix('\'').and_then(|k| k.strip_suffix('\'')))
+    }
+
+    /// Check whether a quoted key would not parse, or read as a different key, without
+    /// its quotes: escapes, indicators, and plain scalars that are not strings such as
+    /// `true`, `1` or the merge key `<<`
+    pub fn needs_quotes(token: &str) -> bool {
+        let Some(content) = Self::quoted_content(token) else {
+            return false;
+        };
+        if (token.starts_with('"') && content.contains('\\'))
+            || (token.starts_with('\'') && content.contains('\''))
+        {
+            return true;
+        }
+
+        let yaml_11_boolean = matches!(
+            content.to_lowercase().as_str(),
+            "y" | "n" | "yes" | "no" | "on" | "off"
+        );
+        content.is_empty()
+            || content != content.trim()
+            || content.starts_with([
+                '-', '?', ':', ',', '[', ']', '{', '}', '#', '&', '*', '!', '|', '>', '\'', '"',
+                '%', '@', '`',
+            ])
+            || content.contains(": ")
+            || content.contains(" #")
+            || content.ends_with(':')
+            || content.chars().any(char::is_control)
+            || content == "<<"
+            || yaml_11_boolean
+            || !matches!(
+                serde_yaml::from_str::<serde_yaml::Value>(content),
+                Ok(serde_yaml::Value::String(plain)) if plain == content
+            )
+    }
+}
+
+impl Rule for QuotedKeysRule {
+    fn id(&self) -> &'static str {
+        "quoted-keys"
+    }
+
+    fn phase(&self) -> RulePhase {
+        RulePhase::Semantic
+    }
+
+    fn fixable(&self) -> bool {
+        true
+    }
+
+    fn description(&self) -> &'static str {
+        "Enforces when mapping keys are quoted"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "Requires the keys of block mappings to be always quoted, never quoted, or quoted only where the key would otherwise not parse or read as another value, such as `\"on\"`, `\"1\"` or `\"a: b\"`. Values are left to `quoted-strings`. The auto-fix removes quotes that are not needed.",
+            params: &[("required", "`always`, `never` or `only-when-needed`")],
+            failing: "---\n\"name\": app\n",
+            passing: "---\nname: app\n\"on\": push\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        let mut problems = Vec::new();
+        let required = config.get_string("required").unwrap_or("only-when-needed");
+
+        let lines: Vec<&str> = context.content.lines().collect();
+        for key in context.key_paths() {
+            let line = lines[key.line - 1];
+            let key_start = line
+                .char_indices()
+                .nth(key.column - 1)
+                .map_or(line.len(), |(offset, _)| offset);
+            let token = Self::key_token(line, key_start);
+            let quoted = Self::quoted_content(token).is_some();
+
+            let id = match required {
+                // Quoting the merge key would make it an ordinary key
+                "always" if !quoted && token != "<<" => "quoted-keys.required",
+                "never" if quoted => "quoted-keys.not-needed",
+                "only-when-needed" if quoted && !Self::needs_quotes(token) => {
+                    "quoted-keys.not-needed"
+                }
+                _ => continue,
+            };
+            problems.push(Problem::from_message(
+                key.line,
+                key.column,
+                config.level.clone(),
+                self.id(),
+                Message::new(id).arg("key", token),
+            ));
+        }
+
+        Ok(problems)
+    }
+
+    fn param_schema(&self) -> Vec<ParamSchema> {
+        ParamSchema::from_defaults(
+            &self.default_config(),
+            &self.docs(),
+            &[("required", &["always", "never", "only-when-needed"])],
+        )
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(false, Level::Error); // Disabled by default
+        config.set_param("required", "only-when-needed");
+        config
+    }
+
+    fn validate_config(&self, config: &RuleConfig) -> Result<()> {
+        if let Some(required) = config.get_string("required")
+            && !matches!(required, "always" | "never" | "only-when-needed")
+        {
+            return Err(eyre::eyre!(
+                "required must be \"always\", \"never\" or \"only-when-needed\", got \"{required}\""
+            ));
+        }
+        Ok(())
+    }
+
+    fn conflicts(
+        &self,
+        config: &RuleConfig,
+        enabled: &HashMap<String, RuleConfig>,
+    ) -> Vec<RuleConflict> {
+        // quoted-strings checks every quoted scalar on a line, keys included
+        let unquotes_strings = enabled.get("quoted-strings").is_some_and(|strings| {
+            strings
+                .get_bool("required-only-when-needed")
+                .unwrap_or(false)
+        });
+        if config.get_string("required") == Some("always") && unquotes_strings {
+            vec![RuleConflict::new(
+                self.id(),
+                "quoted-strings",
+                "keys must be quoted, but quoted-strings reports quotes that are not needed",
+            )]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Rule that enforces how empty mappings and sequences are written
+#[derive(Debug, Default)]
+pub struct EmptyCollectionsRule;
+
+impl EmptyCollectionsRule {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Find the value of a block mapping entry or sequence item on `line`, before its
+    /// comment. Returns the byte offset of the value and the column (0-based) of the key or
+    /// dash that owns it, or `None` for lines that start neither, such as the continuation
+    /// of a multi-line scalar.
+    fn value_of(line: &str) -> Option<(usize, usize)> {
+        let code = common::strip_comment(line).trim_end();
+        let mut start = common::count_leading_whitespace(code);
+        let mut item = None;
+
+        // A line can open several nested sequences, as in `- - a`
+        loop {
+            let rest = &code[start..];
+            if rest == "-" {
+                return Some((code.len(), start));
+            }
+            match rest.strip_prefix("- ") {
+                Some(after) => {
+                    item = Some(start);
+                    start = code.len() - after.trim_start().len();
+                }
+                None => break,
+            }
+        }
+
+        let rest = &code[start..];
+        if rest.starts_with('?') {
+            return None;
+        }
+        match common::find_mapping_colon(rest) {
+            Some(colon) => {
+                let value = &rest[colon + 1..];
+                Some((code.len() - value.trim_start().len(), start))
+            }
+            None => item.map(|dash| (start, dash)),
+        }
+    }
+
+    /// Find an empty flow collection ending `code`, such as `{}` or `[ ]`, returning the
+    /// byte offset of its opening brace or bracket
+    pub fn trailing_empty_collection(code: &str) -> Option<usize> {
+        let open = match code.chars().last()? {
+            '}' => '{',
+            ']' => '[',
+            _ => return None,
+        };
+        let start = code.rfind(open)?;
+        code[start + 1..code.len() - 1]
+            .trim()
+            .is_empty()
+            .then_some(start)
+    }
+
+    /// Check whether the node at `line_index` whose key or dash is at column `owner` has
+    /// content on the following lines: anything indented further, or a sequence written at
+    /// the indentation of its key
+    fn has_nested_content(lines: &[&str], line_index: usize, owner: usize, is_key: bool) -> bool {
+        let Some(next) = lines[line_index + 1..]
+            .iter()
+            .find(|line| !common::is_comment_only_line(line))
+        else {
+            return false;
+        };
+        let indent = common::count_leading_whitespace(next);
+        let rest = &next[indent..];
+        indent > owner || (is_key && indent == owner && (rest == "-" || rest.starts_with("- ")))
+    }
+}
+
+impl Rule for EmptyCollectionsRule {
+    fn id(&self) -> &'static str {
+        "empty-collections"
+    }
+
+    fn phase(&self) -> RulePhase {
+        RulePhase::Semantic
+    }
+
+    fn fixable(&self) -> bool {
+        true
+    }
+
+    fn description(&self) -> &'static str {
+        "Enforces how empty mappings and sequences are written"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "An empty collection can be written as `{}` or `[]`, or left out as in `key:`, which YAML reads as null. Some consumers, such as Helm charts merging values, treat an empty mapping and null differently. `mappings` sets how empty mappings are written (`braces` for `{}`, `empty` for nothing, `null` for `null`) and `sequences` does the same for `[]` with `brackets`; `any` allows every form. A value left out has no type, so it is only reported when a setting requires `{}` or `[]`. Explicit `null` and `~` values are left alone. The auto-fix rewrites each value to the required form, unless both `{}` and `[]` are required.",
+            params: &[
+                ("mappings", "`any`, `braces`, `empty` or `null`"),
+                ("sequences", "`any`, `brackets`, `empty` or `null`"),
+            ],
+            failing: "---\n# with mappings: braces\nresources:\nannotations: {}\n",
+            passing: "---\n# with mappings: braces\nresources: {}\nannotations: {}\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        let mut problems = Vec::new();
+        let mappings = config.get_string("mappings").unwrap_or("any");
+        let sequences = config.get_string("sequences").unwrap_or("any");
+
+        let block_scalars = context.block_scalar_spans();
+        let flow_collections = context.flow_collections();
+        let lines: Vec<&str> = context.content.lines().collect();
+        for (index, line) in lines.iter().enumerate() {
+            let line_no = index + 1;
+            if common::is_comment_only_line(line)
+                || line.starts_with("---")
+                || line.starts_with("...")
+                || line.starts_with('%')
+                || block_scalars.iter().any(|span| span.contains(&line_no))
+                // Lines inside a flow collection that spans several lines
+                || flow_collections
+                    .iter()
+                    .any(|collection| collection.line < line_no && line_no <= collection.end_line)
+            {
+                continue;
+            }
+            let Some((start, owner)) = Self::value_of(line) else {
+                continue;
+            };
+            let code = common::strip_comment(line).trim_end();
+
+            // Spaces inside an empty collection do not change it
+            let value = match Self::trailing_empty_collection(&code[start..]) {
+                Some(0) if code[start..].starts_with('{') => "{}",
+                Some(0) => "[]",
+                _ => &code[start..],
+            };
+            let id = match value {
+                "{}" if mappings == "empty" => "empty-collections.mapping-as-empty",
+                "{}" if mappings == "null" => "empty-collections.mapping-as-null",
+                "[]" if sequences == "empty" => "empty-collections.sequence-as-empty",
+                "[]" if sequences == "null" => "empty-collections.sequence-as-null",
+                "" => {
+                    let is_key = code[owner..] != *"-" && !code[owner..].starts_with("- ");
+                    if Self::has_nested_content(&lines, index, owner, is_key) {
+                        continue;
+                    }
+                    match (mappings == "braces", sequences == "brackets") {
+                        (true, true) => "empty-collections.empty-is-ambiguous",
+                        (true, false) => "empty-collections.empty-as-mapping",
+                        (false, true) => "empty-collections.empty-as-sequence",
+                        (false, false) => continue,
+                    }
+                }
+                _ => continue,
+            };
+            problems.push(Problem::from_message(
+                line_no,
+                LintContext::char_column(line, start),
+                config.level.clone(),
+                self.id(),
+                Message::new(id),
+            ));
+        }
+
+        Ok(problems)
+    }
+
+    fn param_schema(&self) -> Vec<ParamSchema> {
+        ParamSchema::from_defaults(
+            &self.default_config(),
+            &self.docs(),
+            &[
+                ("mappings", &["any", "braces", "empty", "null"]),
+                ("sequences", &["any", "brackets", "empty", "null"]),
+            ],
+        )
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(false, Level::Error); // Disabled by default
+        config.set_param("mappings", "any");
+        config.set_param("sequences", "any");
+        config
+    }
+
+    fn validate_config(&self, config: &RuleConfig) -> Result<()> {
+        if let Some(mappings) = config.get_string("mappings")
+            && !matches!(mappings, "any" | "braces" | "empty" | "null")
+        {
+            return Err(eyre::eyre!(
+                "mappings must be \"any\", \"braces\", \"empty\" or \"null\", got \"{mappings}\""
+            ));
+        }
+        if let Some(sequences) = config.get_string("sequences")
+            && !matches!(sequences, "any" | "brackets" | "empty" | "null")
+        {
+            return Err(eyre::eyre!(
+                "sequences must be \"any\", \"brackets\", \"empty\" or \"null\", got \"{sequences}\""
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn create_test_context<'a>(content: &'a str, path: &'a PathBuf) -> LintContext<'a> {
+        LintContext::new(path, content)
+    }
+
+    #[test]
+    fn test_truthy_rule_valid_values() {
+        let rule = TruthyRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("enabled: true\ndisabled: false", &path);
+        let mut config = rule.default_config();
+        config.enabled = true;
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_truthy_rule_invalid_values() {
+        let rule = TruthyRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("enabled: yes\ndisabled: no", &path);
+        let mut config = rule.default_config();
+        config.enabled = true;
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].message.contains("truthy value should be"));
+    }
+
+    #[test]
+    fn test_truthy_rule_columns() {
+        let rule = TruthyRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("enabled: yes\nlist:\n  - off\nYes: 1\n", &path);
+        let config = rule.default_config();
+
+        let problems = rule.check(&context, &config).unwrap();
+        let positions: Vec<(usize, usize)> = problems.iter().map(|p| (p.line, p.column)).collect();
+        assert_eq!(positions, vec![(1, 10), (3, 5), (4, 1)]);
+    }
+
+    #[test]
+    fn test_truthy_rule_skips_quoted_and_comments() {
+        let rule = TruthyRule::new();
+        let path = PathBuf::from("test.yaml");
+        let content = "a: \"yes\"\nb: 'on'\nc: value # note: yes\n# d: no\ne: yes # comment\n";
+        let context = create_test_context(content, &path);
+        let mut config = rule.default_config();
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, 5);
+
+        config.set_param("ignore-quoted", false);
+        let problems = rule.check(&context, &config).unwrap();
+        let lines: Vec<usize> = problems.iter().map(|p| p.line).collect();
+        assert_eq!(lines, vec![1, 2, 5]);
+        assert_eq!(problems[0].column, 4);
+    }
+
+    #[test]
+    fn test_truthy_rule_check_quoted() {
+        let rule = TruthyRule::new();
+        let path = PathBuf::from("test.yaml");
+        let content = "a: \"true\"\nb: \"True\"\nc: 'FALSE'\nd: \"false\"\ne: \"value\"\n";
+        let context = create_test_context(content, &path);
+        let mut config = rule.default_config();
+        assert!(rule.check(&context, &config).unwrap().is_empty());
+
+        config.set_param("check-quoted", true);
+        let problems = rule.check(&context, &config).unwrap();
+        let lines: Vec<usize> = problems.iter().map(|p| p.line).collect();
+        assert_eq!(lines, vec![2, 3]);
+        assert!(problems[0].message.ends_with("not \"True\""));
+
+        // Quoted spellings that are allowed pass
+        config.set_param(
+            "allowed-values",
+            ConfigValue::Array(vec!["True".into(), "FALSE".into()]),
+        );
+        let lines: Vec<usize> = rule
+            .check(&context, &config)
+            .unwrap()
+            .iter()
+            .map(|p| p.line)
+            .collect();
+        assert!(lines.is_empty());
+    }
+
+    #[test]
+    fn test_truthy_rule_skips_block_scalars() {
+        let rule = TruthyRule::new();
+        let path = PathBuf::from("test.yaml");
+        let content = "script: |\n  yes\n  on: it\nflag: on\n";
+        let context = create_test_context(content, &path);
+        let config = rule.default_config();
+
+        let problems = rule.check(&context, &config).unwrap();
+        let lines: Vec<usize> = problems.iter().map(|p| p.line).collect();
+        assert_eq!(lines, vec![4]);
+    }
+
+    #[test]
+    fn test_octal_values_rule_implicit_octal() {
+        let rule = OctalValuesRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("mode: 0755", &path);
+        let mut config = rule.default_config();
+        config.enabled = true;
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].message.contains("implicit octal"));
+    }
+
+    #[test]
+    fn test_float_values_rule_scientific_notation() {
+        let rule = FloatValuesRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("value: 1.23e-4", &path);
+        let mut config = rule.default_config();
+        config.enabled = true;
+        config.set_param(
+            "forbid-scientific-notation".to_string(),
+            ConfigValue::Bool(true),
+        );
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert!(
+            problems[0]
+                .message
+                .contains("scientific notation is forbidden")
+        );
+    }
+
+    fn required_keys_config(pattern: &str, keys: &[&str]) -> RuleConfig {
+        let rule = RequiredKeysRule::new();
+        let mut config = rule.default_config();
+        let keys = keys.iter().map(|key| ConfigValue::from(*key)).collect();
+        config.set_param(
+            "files",
+            BTreeMap::from([(pattern.to_string(), ConfigValue::Array(keys))]),
+        );
+        config
+    }
+
+    #[test]
+    fn test_required_keys_rule_missing_keys() {
+        let rule = RequiredKeysRule::new();
+        let path = PathBuf::from("services/payments.yaml");
+        let context = create_test_context("---\nname: payments\ntier: 1\n", &path);
+        let config = required_keys_config("services/", &["name", "owner", "tier"]);
+        assert!(rule.validate_config(&config).is_ok());
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, 3);
+        assert_eq!(problems[0].message, "missing required key \"owner\"");
+    }
+
+    #[test]
+    fn test_required_keys_rule_every_document() {
+        let rule = RequiredKeysRule::new();
+        let path = PathBuf::from("services/payments.yaml");
+        let content =
+            "---\nname: payments\nowner: billing\n---\nname: ledger\n---\n- not a mapping\n";
+        let context = create_test_context(content, &path);
+        let config = required_keys_config("services/", &["name", "owner"]);
+
+        let problems = rule.check(&context, &config).unwrap();
+        let found: Vec<_> = problems
+            .iter()
+            .map(|problem| (problem.line, problem.message.as_str()))
+            .collect();
+        assert_eq!(found, vec![(5, "missing required key \"owner\"")]);
+    }
+
+    #[test]
+    fn test_required_keys_rule_other_files() {
+        let rule = RequiredKeysRule::new();
+        let path = PathBuf::from("charts/values.yaml");
+        let context = create_test_context("---\nreplicas: 1\n", &path);
+        let config = required_keys_config("services/**", &["name"]);
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_required_keys_rule_invalid_config() {
+        let rule = RequiredKeysRule::new();
+        let mut config = rule.default_config();
+        config.set_param("files", "services/**");
+        assert!(rule.validate_config(&config).is_err());
+
+        config.set_param(
+            "files",
+            BTreeMap::from([("services/**".to_string(), ConfigValue::from("name"))]),
+        );
+        assert!(rule.validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_forbidden_keys_rule() {
+        let rule = ForbiddenKeysRule::new();
+        let path = PathBuf::from("test.yaml");
+        let content =
+            "spec:\n  hostNetwork: true\n  password: x\ndb:\n  password: y\n  api_secret: z\n";
+        let context = create_test_context(content, &path);
+
+        let mut config = rule.default_config();
+        config.set_param(
+            "keys",
+            vec![
+                ConfigValue::from("spec.hostNetwork"),
+                ConfigValue::from("password"),
+                ConfigValue::from(BTreeMap::from([
+                    ("key".to_string(), ConfigValue::from("/secret$/")),
+                    (
+                        "message".to_string(),
+                        ConfigValue::from("move secrets to vault"),
+                    ),
+                    ("level".to_string(), ConfigValue::from("warning")),
+                ])),
+            ],
+        );
+        assert!(rule.validate_config(&config).is_ok());
+
+        let problems = rule.check(&context, &config).unwrap();
+        let found: Vec<(usize, usize, &str)> = problems
+            .iter()
+            .map(|p| (p.line, p.column, p.message.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (2, 3, "found forbidden key \"spec.hostNetwork\""),
+                (3, 3, "found forbidden key \"spec.password\""),
+                (5, 3, "found forbidden key \"db.password\""),
+                (6, 3, "move secrets to vault"),
+            ]
+        );
+        assert_eq!(problems[3].level, Level::Warning);
+    }
+
+    #[test]
+    fn test_forbidden_keys_rule_invalid_config() {
+        let rule = ForbiddenKeysRule::new();
+        let mut config = rule.default_config();
+        config.set_param("keys", vec![ConfigValue::from("/[unclosed/")]);
+        assert!(rule.validate_config(&config).is_err());
+
+        config.set_param(
+            "keys",
+            vec![ConfigValue::from(BTreeMap::from([
+                ("key".to_string(), ConfigValue::from("password")),
+                ("level".to_string(), ConfigValue::from("fatal")),
+            ]))],
+        );
+        assert!(rule.validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_sequence_duplicates_rule() {
+        let rule = SequenceDuplicatesRule::new();
+        let path = PathBuf::from("test.yaml");
+        let content = "hosts:\n  - a.example.com\n  - b.example.com\n  - A.example.com\n  - a.example.com\nports:\n  - 80\n  - 80\n";
+        let context = create_test_context(content, &path);
+        let mut config = rule.default_config();
+
+        let problems = rule.check(&context, &config).unwrap();
+        let lines: Vec<usize> = problems.iter().map(|p| p.line).collect();
+        assert_eq!(lines, vec![5, 8]);
+        assert_eq!(
+            problems[0].message,
+            "duplicate sequence item \"a.example.com\" (first on line 2)"
+        );
+
+        config.set_param("ignore-case", true);
+        config.set_param("paths", vec![ConfigValue::from("hosts")]);
+        let problems = rule.check(&context, &config).unwrap();
+        let lines: Vec<usize> = problems.iter().map(|p| p.line).collect();
+        assert_eq!(lines, vec![4, 5]);
+    }
+
+    #[test]
+    fn test_sequence_duplicates_rule_per_sequence() {
+        let rule = SequenceDuplicatesRule::new();
+        let path = PathBuf::from("test.yaml");
+        let content = "a:\n  - x\nb:\n  - x\n---\na:\n  - x\n";
+        let context = create_test_context(content, &path);
+
+        let problems = rule.check(&context, &rule.default_config()).unwrap();
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_key_ordering_rule() {
+        let rule = KeyOrderingRule::new();
+        let path = PathBuf::from("test.yaml");
+        let content = "---\nb: 1\na:\n  y: 1\n  x: 2\nc: 3\n---\na: 1\n";
+        let context = create_test_context(content, &path);
+
+        let problems = rule.check(&context, &rule.default_config()).unwrap();
+        let positions: Vec<_> = problems.iter().map(|p| (p.line, p.column)).collect();
+        assert_eq!(positions, vec![(3, 1), (5, 3)]);
+        assert_eq!(
+            problems[0].message,
+            "wrong ordering of key \"a\" in mapping"
+        );
+    }
+
+    #[test]
+    fn test_key_ordering_rule_flow_mappings() {
+        let rule = KeyOrderingRule::new();
+        let path = PathBuf::from("test.yaml");
+        let content = "---\n{b: 1, a: 2}\n---\na: {x: {d: 1, c: 2}, y: 3}\nitems: [{a: 1, b: 2}]\n";
+        let context = create_test_context(content, &path);
+
+        let problems = rule.check(&context, &rule.default_config()).unwrap();
+        let positions: Vec<_> = problems.iter().map(|p| (p.line, p.column)).collect();
+        assert_eq!(positions, vec![(2, 8), (4, 15)]);
+        assert_eq!(
+            problems[1].message,
+            "wrong ordering of key \"c\" in mapping"
+        );
+    }
+
+    #[test]
+    fn test_key_ordering_rule_options() {
+        let rule = KeyOrderingRule::new();
+        let path = PathBuf::from("test.yaml");
+        let content =
+            "apiVersion: v1\nkind: Pod\nitem2: a\nitem10: b\nName: c\n_meta: d\nother: e\n";
+        let context = create_test_context(content, &path);
+        let mut config = rule.default_config();
+        assert!(!rule.check(&context, &config).unwrap().is_empty());
+
+        config.set_param("ordering", "natural");
+        config.set_param("ignore-case", true);
+        config.set_param(
+            "priority-keys",
+            vec![ConfigValue::from("apiVersion"), ConfigValue::from("kind")],
+        );
+        config.set_param("ignored-keys", vec![ConfigValue::from("^_")]);
+        assert!(rule.validate_config(&config).is_ok());
+        assert!(rule.check(&context, &config).unwrap().is_empty());
+
+        config.set_param("ordering", "random");
+        assert!(rule.validate_config(&config).is_err());
+        config.set_param("ordering", "lexical");
+        config.set_param("ignored-keys", vec![ConfigValue::from("(")]);
+        assert!(rule.validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_key_ordering_compare() {
+        assert_eq!(natural_cmp("item2", "item10"), Ordering::Less);
+        assert_eq!(natural_cmp("a01", "a1"), Ordering::Greater);
+        assert_eq!(natural_cmp("a", "a1"), Ordering::Less);
+
+        let settings = KeyOrdering {
+            order: KeyOrder::Locale,
+            ignore_case: false,
+            ignored_keys: Vec::new(),
+            priority_keys: Vec::new(),
+        };
+        assert_eq!(settings.compare("b_c", "Bb"), Ordering::Greater);
+        assert_eq!(settings.compare("Zeta", "alpha"), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_sequence_ordering_rule() {
+        let rule = SequenceOrderingRule::new();
+        let path = PathBuf::from("test.yaml");
+        let content = "dependencies:\n  - requests\n  - attrs\n  - certifi\n  - aiohttp\nports:\n  - 8080\n  - 443\n";
+        let context = create_test_context(content, &path);
+        let mut config = rule.default_config();
+        config.set_param("paths", vec![ConfigValue::from("dependencies")]);
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert_eq!((problems[0].line, problems[0].column), (3, 5));
+        assert_eq!(
+            problems[0].message,
+            "sequence item \"attrs\" should come before \"requests\""
+        );
+
+        config.set_param("paths", vec![ConfigValue::from("ports")]);
+        assert_eq!(rule.check(&context, &config).unwrap().len(), 1);
+        config.set_param("order", "numeric");
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems[0].line, 8);
+
+        config.set_param("order", "random");
+        assert!(rule.validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_sequence_ordering_compare_items() {
+        assert_eq!(
+            SequenceOrderingRule::compare_items(false, "10", "9"),
+            Ordering::Less
+        );
+        assert_eq!(
+            SequenceOrderingRule::compare_items(true, "10", "9"),
+            Ordering::Greater
+        );
+        assert_eq!(
+            SequenceOrderingRule::compare_items(true, "9", "any"),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn test_date_format_rule() {
+        let rule = DateFormatRule::new();
+        let path = PathBuf::from("test.yaml");
+        let content = "created_at: 2024-1-5\nupdated_at: \"2024-01-05T09:30:00Z\"\ndate: 05/01/2024 # local\nrelease:\n  date: 2024-02-30\n  build_date: ~\nname: 2024-1-5\n";
+        let context = create_test_context(content, &path);
+        let config = rule.default_config();
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 3);
+        assert_eq!((problems[0].line, problems[0].column), (1, 13));
+        assert_eq!(
+            problems[0].message,
+            "invalid date \"2024-1-5\" for key \"created_at\" (expected ISO-8601)"
+        );
+        assert_eq!(problems[1].line, 3);
+        assert_eq!(problems[2].line, 5);
+    }
+
+    #[test]
+    fn test_date_format_is_iso_8601() {
+        assert!(DateFormatRule::is_iso_8601("2024-02-29"));
+        assert!(DateFormatRule::is_iso_8601("2024-01-05T09:30"));
+        assert!(DateFormatRule::is_iso_8601("2024-01-05T09:30:00.123+02:00"));
+        assert!(!DateFormatRule::is_iso_8601("2023-02-29"));
+        assert!(!DateFormatRule::is_iso_8601("2024-01-05 09:30:00"));
+        assert!(!DateFormatRule::is_iso_8601("2024-13-01"));
+        assert!(!DateFormatRule::is_iso_8601("1700000000"));
+    }
+
+    #[test]
+    fn test_quoted_keys_rule() {
+        let rule = QuotedKeysRule::new();
+        let path = PathBuf::from("test.yaml");
+        let content = "\"name\": app\n'on': push\nimage: app\nspec:\n  - \"port\": 80\n<<: *base\n";
+        let context = create_test_context(content, &path);
+        let check = |required: &str| {
+            let mut config = rule.default_config();
+            config.set_param("required", required);
+            rule.check(&context, &config)
+                .unwrap()
+                .into_iter()
+                .map(|p| (p.line, p.column, p.message))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            check("only-when-needed"),
+            vec![
+                (1, 1, "key \"name\" should not be quoted".to_string()),
+                (5, 5, "key \"port\" should not be quoted".to_string()),
+            ]
+        );
+        assert_eq!(
+            check("never"),
+            vec![
+                (1, 1, "key \"name\" should not be quoted".to_string()),
+                (2, 1, "key 'on' should not be quoted".to_string()),
+                (5, 5, "key \"port\" should not be quoted".to_string()),
+            ]
+        );
+        assert_eq!(
+            check("always"),
+            vec![
+                (3, 1, "key \"image\" should be quoted".to_string()),
+                (4, 1, "key \"spec\" should be quoted".to_string()),
+            ]
+        );
+
+        let mut config = rule.default_config();
+        config.set_param("required", "sometimes");
+        assert!(rule.validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_quoted_keys_needs_quotes() {
+        for token in [
+            "\"on\"",
+            "'Yes'",
+            "\"1\"",
+            "\"1.5\"",
+            "\"null\"",
+            "\"~\"",
+            "\"a: b\"",
+            "\"a #b\"",
+            "\"-x\"",
+            "\"*x\"",
+            "\"\"",
+            "\" x\"",
+            "\"<<\"",
+            "\"a\\tb\"",
+            "'it''s'",
+            "\"[x]\"",
+        ] {
+            assert!(QuotedKeysRule::needs_quotes(token), "{token}");
+        }
+        for token in [
+            "\"name\"",
+            "'app.kubernetes.io/name'",
+            "\"x-y\"",
+            "\"a:b\"",
+            "plain",
+        ] {
+            assert!(!QuotedKeysRule::needs_quotes(token), "{token}");
+        }
+    }
+
+    #[test]
+    fn test_empty_collections_rule() {
+        let rule = EmptyCollectionsRule::new();
+        let path = PathBuf::from("test.yaml");
+        let content = "a: {}\nb: []\nc:\nd:\n  - {}\n  -\ne:\n- x\nf: null\ng: |\n  h: {}\ni: [\n  {},\n]\nj: # none\nk: {  }\nl: [ ] # none\n";
+        let context = create_test_context(content, &path);
+        let check = |mappings: &str, sequences: &str| {
+            let mut config = rule.default_config();
+            config.set_param("mappings", mappings);
+            config.set_param("sequences", sequences);
+            rule.check(&context, &config)
+                .unwrap()
+                .into_iter()
+                .map(|p| (p.line, p.column, p.message_id.unwrap()))
+                .collect::<Vec<_>>()
+        };
+        let at =
+            |line, column, variant: &str| (line, column, format!("empty-collections.{variant}"));
+
+        assert!(check("any", "any").is_empty());
+        assert_eq!(
+            check("empty", "null"),
+            vec![
+                at(1, 4, "mapping-as-empty"),
+                at(2, 4, "sequence-as-null"),
+                at(5, 5, "mapping-as-empty"),
+                at(16, 4, "mapping-as-empty"),
+                at(17, 4, "sequence-as-null"),
+            ]
+        );
+        assert_eq!(
+            check("braces", "any"),
+            vec![
+                at(3, 3, "empty-as-mapping"),
+                at(6, 4, "empty-as-mapping"),
+                at(15, 3, "empty-as-mapping"),
+            ]
+        );
+        assert_eq!(
+            check("braces", "brackets"),
+            vec![
+                at(3, 3, "empty-is-ambiguous"),
+                at(6, 4, "empty-is-ambiguous"),
+                at(15, 3, "empty-is-ambiguous"),
+            ]
+        );
+
+        let mut config = rule.default_config();
+        config.set_param("sequences", "braces");
+        assert!(rule.validate_config(&config).is_err());
+    }
+}
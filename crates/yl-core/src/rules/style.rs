@@ -0,0 +1,1631 @@
+use super::{ConfigValue, ParamSchema, Rule, RuleConfig, RuleConflict, RuleDocs};
+use crate::linter::{Level, LintContext, Problem};
+use crate::messages::Message;
+use crate::rules::common;
+use eyre::Result;
+use regex::Regex;
+use std::collections::HashMap;
+use unicode_width::UnicodeWidthChar;
+
+/// How the line-length rule measures a line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LengthUnit {
+    /// Unicode scalar values
+    Chars,
+    /// UTF-8 bytes
+    Bytes,
+    /// Terminal columns: wide (e.g. CJK) characters take two, combining marks none
+    DisplayWidth,
+}
+
+impl LengthUnit {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "chars" => Some(Self::Chars),
+            "bytes" => Some(Self::Bytes),
+            "display-width" => Some(Self::DisplayWidth),
+            _ => None,
+        }
+    }
+
+    /// Length a character adds to a line
+    fn width(self, c: char) -> usize {
+        match self {
+            Self::Chars => 1,
+            Self::Bytes => c.len_utf8(),
+            Self::DisplayWidth => c.width().unwrap_or(0),
+        }
+    }
+
+    /// Name of the unit in problem messages
+    fn plural(self) -> Message {
+        Message::new(match self {
+            Self::Chars => "unit.chars",
+            Self::Bytes => "unit.bytes",
+            Self::DisplayWidth => "unit.display-width",
+        })
+    }
+}
+
+/// Rule that checks line length limits
+#[derive(Debug)]
+pub struct LineLengthRule {
+    default_max: usize,
+}
+
+#[allow(dead_code)] // Some methods are part of API for future phases
+impl LineLengthRule {
+    /// Create a new line length rule with default maximum of 80 characters
+    pub fn new() -> Self {
+        Self { default_max: 80 }
+    }
+
+    /// Create a new line length rule with a custom default maximum
+    pub fn with_default_max(max: usize) -> Self {
+        Self { default_max: max }
+    }
+
+    /// Get the maximum line length from configuration
+    fn get_max_length(&self, config: &RuleConfig) -> usize {
+        config
+            .get_int("max")
+            .and_then(|i| if i > 0 { Some(i as usize) } else { None })
+            .unwrap_or(self.default_max)
+    }
+
+    /// Get the unit lines are measured in from configuration
+    fn length_unit(&self, config: &RuleConfig) -> LengthUnit {
+        config
+            .get_string("length-unit")
+            .and_then(LengthUnit::parse)
+            .unwrap_or(LengthUnit::Chars)
+    }
+
+    /// Check if non-breakable words should be allowed to exceed the limit
+    fn allow_non_breakable_words(&self, config: &RuleConfig) -> bool {
+        config.get_bool("allow-non-breakable-words").unwrap_or(true)
+    }
+
+    /// Check if long `key: value` lines with a non-breakable value should be allowed
+    fn allow_non_breakable_inline_mappings(&self, config: &RuleConfig) -> bool {
+        config
+            .get_bool("allow-non-breakable-inline-mappings")
+            .unwrap_or(false)
+    }
+
+    /// Compile the `ignore-patterns` parameter (a regex or list of regexes)
+    fn ignore_patterns(&self, config: &RuleConfig) -> Result<Vec<Regex>> {
+        let patterns: Vec<&str> = match config.params.get("ignore-patterns") {
+            Some(ConfigValue::String(pattern)) => vec![pattern.as_str()],
+            Some(ConfigValue::Array(values)) => values
+                .iter()
+                .map(|value| {
+                    value
+                        .as_string()
+                        .ok_or_else(|| eyre::eyre!("ignore-patterns must be a list of strings"))
+                })
+                .collect::<Result<_>>()?,
+            Some(_) => return Err(eyre::eyre!("ignore-patterns must be a list of strings")),
+            None => Vec::new(),
+        };
+
+        patterns
+            .into_iter()
+            .map(|pattern| {
+                Regex::new(pattern)
+                    .map_err(|e| eyre::eyre!("invalid ignore-patterns regex '{}': {}", pattern, e))
+            })
+            .collect()
+    }
+
+    /// Check if a line contains only non-breakable content
+    fn is_non_breakable_line(&self, line: &str, allow_inline_mappings: bool) -> bool {
+        let mut content = line.trim_start();
+
+        // Skip comment prefix or sequence indicators
+        if let Some(comment) = content.strip_prefix('#') {
+            content = comment.trim_start();
+        } else {
+            while let Some(item) = content.strip_prefix("- ") {
+                content = item.trim_start();
+            }
+        }
+
+        // Check if the line contains spaces (indicating breakable content)
+        if !content.contains(' ') {
+            return true;
+        }
+
+        // A single key with a non-breakable scalar value, e.g. `url: https://...`
+        allow_inline_mappings
+            && common::find_mapping_colon(content).is_some_and(|colon| {
+                let value = content[colon + 1..].trim();
+                !value.is_empty() && !value.contains(' ')
+            })
+    }
+}
+
+impl Default for LineLengthRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rule for LineLengthRule {
+    fn id(&self) -> &'static str {
+        "line-length"
+    }
+
+    fn description(&self) -> &'static str {
+        "Checks that lines do not exceed a maximum length"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "Long lines are hard to read and review. Lines longer than `max` characters are reported, except for lines made of a single word (such as a URL) when non-breakable words are allowed.",
+            params: &[
+                ("max", "maximum length allowed on a line"),
+                (
+                    "length-unit",
+                    "what `max` counts: `chars`, `bytes` or `display-width` (terminal columns, with wide CJK characters counting two)",
+                ),
+                (
+                    "allow-non-breakable-words",
+                    "ignore lines containing a single word that cannot be broken, such as a URL",
+                ),
+                (
+                    "allow-non-breakable-inline-mappings",
+                    "also ignore `key: <word>` lines whose value is a single unbreakable word",
+                ),
+                (
+                    "ignore-patterns",
+                    "regular expressions; lines matching any of them are never reported",
+                ),
+            ],
+            failing: "---\ndescription: this value goes on and on well past the eighty character limit of the rule\n",
+            passing: "---\ndescription: >\n  this value goes on and on but is folded\n  so that no line passes the limit\n",
+        }
+    }
+
+    fn param_schema(&self) -> Vec<ParamSchema> {
+        ParamSchema::from_defaults(
+            &self.default_config(),
+            &self.docs(),
+            &[("length-unit", &["chars", "bytes", "display-width"])],
+        )
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(true, Level::Error);
+        config.set_param("max", self.default_max as i64);
+        config.set_param("length-unit", "chars");
+        config.set_param("allow-non-breakable-words", true);
+        config.set_param("allow-non-breakable-inline-mappings", false);
+        config.set_param("ignore-patterns", Vec::<ConfigValue>::new());
+        config
+    }
+
+    fn validate_config(&self, config: &RuleConfig) -> Result<()> {
+        if let Some(max) = config.get_int("max")
+            && max <= 0
+        {
+            return Err(eyre::eyre!("max must be a positive integer, got {}", max));
+        }
+        if let Some(unit) = config.get_string("length-unit")
+            && LengthUnit::parse(unit).is_none()
+        {
+            return Err(eyre::eyre!(
+                "length-unit must be \"chars\", \"bytes\" or \"display-width\", got \"{unit}\""
+            ));
+        }
+        self.ignore_patterns(config)?;
+        Ok(())
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        if !config.enabled {
+            return Ok(Vec::new());
+        }
+
+        let max_length = self.get_max_length(config);
+        let unit = self.length_unit(config);
+        // Inline mappings are only exempt when non-breakable words are too (yamllint parity)
+        let allow_inline_mappings = self.allow_non_breakable_inline_mappings(config);
+        let allow_non_breakable = self.allow_non_breakable_words(config) || allow_inline_mappings;
+        let ignore_patterns = self.ignore_patterns(config)?;
+        let mut problems = Vec::new();
+
+        for (line_no, line) in context.lines() {
+            let line_length: usize = line.chars().map(|c| unit.width(c)).sum();
+
+            if line_length > max_length {
+                // If non-breakable words are allowed, check if this line qualifies
+                if allow_non_breakable && self.is_non_breakable_line(line, allow_inline_mappings) {
+                    continue;
+                }
+
+                if ignore_patterns.iter().any(|pattern| pattern.is_match(line)) {
+                    continue;
+                }
+
+                // The problem points at the character that goes past the limit
+                let mut length = 0;
+                let column = line
+                    .chars()
+                    .position(|c| {
+                        length += unit.width(c);
+                        length > max_length
+                    })
+                    .map_or(max_length + 1, |index| index + 1);
+
+                problems.push(Problem::from_message(
+                    line_no,
+                    column,
+                    config.level.clone(),
+                    self.id(),
+                    Message::new("line-length.too-long")
+                        .arg("length", line_length)
+                        .arg("max", max_length)
+                        .arg("unit", unit.plural()),
+                ));
+            }
+        }
+
+        Ok(problems)
+    }
+}
+
+/// Rule that checks for trailing whitespace
+#[derive(Debug, Default)]
+pub struct TrailingSpacesRule;
+
+impl TrailingSpacesRule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Rule for TrailingSpacesRule {
+    fn id(&self) -> &'static str {
+        "trailing-spaces"
+    }
+
+    fn fixable(&self) -> bool {
+        true
+    }
+
+    fn description(&self) -> &'static str {
+        "Checks for trailing whitespace at the end of lines"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "Trailing whitespace is invisible in most editors and creates noisy diffs.",
+            params: &[(
+                "skip-block-scalars",
+                "do not report trailing whitespace inside literal (`|`) and folded (`>`) block scalars",
+            )],
+            failing: "---\nkey: value   \n",
+            passing: "---\nkey: value\n",
+        }
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(true, Level::Error);
+        config.set_param("skip-block-scalars", false);
+        config
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        if !config.enabled {
+            return Ok(Vec::new());
+        }
+
+        // Trailing whitespace inside literal/folded scalars is part of the value
+        let block_scalars = if config.get_bool("skip-block-scalars").unwrap_or(false) {
+            context.block_scalar_spans()
+        } else {
+            &[]
+        };
+
+        let mut problems = Vec::new();
+
+        for (line_no, line) in context.lines() {
+            if block_scalars.iter().any(|span| span.contains(&line_no)) {
+                continue;
+            }
+
+            if common::has_trailing_whitespace(line)
+                && let Some(start_pos) = common::trailing_whitespace_start(line)
+            {
+                problems.push(Problem::from_message(
+                    line_no,
+                    LintContext::char_column(line, start_pos),
+                    config.level.clone(),
+                    self.id(),
+                    Message::new("trailing-spaces.found"),
+                ));
+            }
+        }
+
+        Ok(problems)
+    }
+}
+
+/// Rule that manages empty lines
+#[derive(Debug, Default)]
+pub struct EmptyLinesRule;
+
+impl EmptyLinesRule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Rule for EmptyLinesRule {
+    fn id(&self) -> &'static str {
+        "empty-lines"
+    }
+
+    fn fixable(&self) -> bool {
+        true
+    }
+
+    fn description(&self) -> &'static str {
+        "Controls the number of empty lines"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "Limits runs of consecutive blank lines within the file and at its beginning and end.",
+            params: &[
+                ("max", "maximum number of consecutive blank lines"),
+                (
+                    "max-start",
+                    "maximum number of blank lines at the beginning of the file",
+                ),
+                (
+                    "max-end",
+                    "maximum number of blank lines at the end of the file",
+                ),
+            ],
+            failing: "---\nfirst: 1\n\n\n\nsecond: 2\n",
+            passing: "---\nfirst: 1\n\nsecond: 2\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        if !config.enabled {
+            return Ok(Vec::new());
+        }
+
+        let mut problems = Vec::new();
+        let max_empty = config.get_int("max").unwrap_or(2) as usize;
+        let max_start = config.get_int("max-start").unwrap_or(0) as usize;
+        let max_end = config.get_int("max-end").unwrap_or(1) as usize;
+
+        let lines: Vec<&str> = context.content.lines().collect();
+        if lines.is_empty() {
+            return Ok(problems);
+        }
+
+        // Check empty lines at start
+        let mut start_empty_count = 0;
+        for line in &lines {
+            if line.trim().is_empty() {
+                start_empty_count += 1;
+            } else {
+                break;
+            }
+        }
+
+        if start_empty_count > max_start {
+            problems.push(Problem::from_message(
+                1,
+                1,
+                config.level.clone(),
+                self.id(),
+                Message::new("empty-lines.too-many-at-start")
+                    .arg("count", start_empty_count)
+                    .arg("max", max_start),
+            ));
+        }
+
+        // A file of blank lines is reported once, at its beginning
+        if start_empty_count == lines.len() {
+            return Ok(problems);
+        }
+
+        // Check empty lines at end
+        let mut end_empty_count = 0;
+        for line in lines.iter().rev() {
+            if line.trim().is_empty() {
+                end_empty_count += 1;
+            } else {
+                break;
+            }
+        }
+
+        if end_empty_count > max_end {
+            problems.push(Problem::from_message(
+                lines.len(),
+                1,
+                config.level.clone(),
+                self.id(),
+                Message::new("empty-lines.too-many-at-end")
+                    .arg("count", end_empty_count)
+                    .arg("max", max_end),
+            ));
+        }
+
+        // Check consecutive empty lines in middle
+        let mut consecutive_empty = 0;
+        for (line_no, line) in lines.iter().enumerate() {
+            if line.trim().is_empty() {
+                consecutive_empty += 1;
+            } else {
+                if consecutive_empty > max_empty {
+                    problems.push(Problem::from_message(
+                        line_no,
+                        1,
+                        config.level.clone(),
+                        self.id(),
+                        Message::new("empty-lines.too-many")
+                            .arg("count", consecutive_empty)
+                            .arg("max", max_empty),
+                    ));
+                }
+                consecutive_empty = 0;
+            }
+        }
+
+        Ok(problems)
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(false, Level::Error); // Disabled by default
+        config.set_param("max", 2i64);
+        config.set_param("max-start", 0i64);
+        config.set_param("max-end", 1i64);
+        config
+    }
+}
+
+/// A block sequence that is the value of a mapping key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NestedSequence {
+    /// Line of the first item (1-based)
+    pub line: usize,
+    /// Last line holding content of the sequence (1-based)
+    pub end: usize,
+    /// Indentation of the key the sequence belongs to
+    pub key_indent: usize,
+    /// Indentation of the sequence's `-` indicators
+    pub dash_indent: usize,
+}
+
+impl NestedSequence {
+    /// Whether the `-` indicators are indented past the key, rather than aligned with it
+    pub fn is_indented(&self) -> bool {
+        self.dash_indent > self.key_indent
+    }
+}
+
+/// Rule that checks indentation consistency
+#[derive(Debug, Default)]
+pub struct IndentationRule;
+
+impl IndentationRule {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// The configured `sequence-style`. Without one, yamllint's deprecated
+    /// `indent-sequences` is read the way `yl migrate` converts it.
+    fn sequence_style(config: &RuleConfig) -> &str {
+        match (
+            config.get_string("sequence-style").unwrap_or("any"),
+            config.params.get("indent-sequences"),
+        ) {
+            ("any", Some(ConfigValue::Bool(true))) => "indented",
+            ("any", Some(ConfigValue::Bool(false))) => "aligned",
+            ("any", Some(ConfigValue::String(value))) if value == "consistent" => "consistent",
+            (style, _) => style,
+        }
+    }
+
+    /// Whether a line is a block sequence item, `-` followed by whitespace or nothing
+    fn is_sequence_item(trimmed: &str) -> bool {
+        trimmed == "-" || trimmed.starts_with("- ") || trimmed.starts_with("-\t")
+    }
+
+    /// Indentation of the key of a line like `key:` or `- key: &anchor`, whose value
+    /// starts on the next line; `None` for any other line
+    fn key_without_value(line: &str) -> Option<usize> {
+        let mut indent = common::count_leading_whitespace(line);
+        while Self::is_sequence_item(&line[indent..]) {
+            indent += 1 + common::count_leading_whitespace(&line[indent + 1..]);
+        }
+
+        let content = common::strip_comment(&line[indent..]);
+        let colon = common::find_mapping_colon(content)?;
+        // Only properties may come between the key and its value
+        let value_follows = content[colon + 1..]
+            .split_whitespace()
+            .all(|token| token.starts_with(['&', '!']));
+        (colon > 0 && !content.starts_with(['?', '{', '[']) && value_follows).then_some(indent)
+    }
+
+    /// Find the block sequences that are the value of a mapping key, such as `key:\n- a`
+    /// or `key:\n  - a`
+    pub fn nested_sequences(context: &LintContext) -> Vec<NestedSequence> {
+        let lines: Vec<&str> = context.content.lines().collect();
+        let mut in_scalar = vec![false; lines.len()];
+        for scalar in context.block_scalars() {
+            for line in scalar.lines {
+                in_scalar[line - 1] = true;
+            }
+        }
+        let is_content = |i: usize| {
+            let line = lines[i];
+            !in_scalar[i] && !common::is_comment_only_line(line) && !line.contains('\t')
+        };
+
+        let mut sequences = Vec::new();
+        let mut previous: Option<usize> = None;
+        for i in 0..lines.len() {
+            if !is_content(i) {
+                continue;
+            }
+            let key_line = previous.replace(i);
+            let dash_indent = common::count_leading_whitespace(lines[i]);
+            if !Self::is_sequence_item(&lines[i][dash_indent..]) {
+                continue;
+            }
+            let Some(key_indent) = key_line.and_then(|line| Self::key_without_value(lines[line]))
+            else {
+                continue;
+            };
+            // A `-` left of the key starts an item of an outer sequence instead
+            if dash_indent < key_indent {
+                continue;
+            }
+
+            let mut end = i;
+            for (j, line) in lines.iter().enumerate().skip(i + 1) {
+                if in_scalar[j] {
+                    end = j;
+                    continue;
+                }
+                if !is_content(j) {
+                    continue;
+                }
+                let indent = common::count_leading_whitespace(line);
+                if indent > dash_indent
+                    || (indent == dash_indent && Self::is_sequence_item(&line[indent..]))
+                {
+                    end = j;
+                } else {
+                    break;
+                }
+            }
+
+            sequences.push(NestedSequence {
+                line: i + 1,
+                end: end + 1,
+                key_indent,
+                dash_indent,
+            });
+        }
+
+        sequences
+    }
+}
+
+impl Rule for IndentationRule {
+    fn id(&self) -> &'static str {
+        "indentation"
+    }
+
+    fn fixable(&self) -> bool {
+        true
+    }
+
+    fn description(&self) -> &'static str {
+        "Controls indentation consistency"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "Requires indentation to be a multiple of the configured width. Lines indented with tabs are left to `no-tabs`. With `sequence-style`, the `-` of a sequence under a mapping key must be indented past the key or aligned with it; the fix moves the whole sequence. yamllint's `indent-sequences` is deprecated, and read as the matching `sequence-style` when that is not set.",
+            params: &[
+                ("spaces", "number of spaces per indentation level"),
+                (
+                    "sequence-style",
+                    "where the `-` of a sequence under a mapping key goes: `indented` by `spaces`, `aligned` with the key, `consistent` with the file's first such sequence, or `any`",
+                ),
+                (
+                    "check-multi-line-strings",
+                    "also check the indentation of comment and multi-line string lines",
+                ),
+            ],
+            failing: "---\nparent:\n   child: value\n",
+            passing: "---\nparent:\n  child: value\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        if !config.enabled {
+            return Ok(Vec::new());
+        }
+
+        let mut problems = Vec::new();
+        let spaces = config.get_int("spaces").unwrap_or(2) as usize;
+        let check_multi_line_strings = config.get_bool("check-multi-line-strings").unwrap_or(false);
+
+        let _expected_indent = 0;
+        let _in_sequence = false;
+
+        for (line_no, line) in context.content.lines().enumerate() {
+            let line_number = line_no + 1;
+
+            // Skip empty lines and comments unless checking multi-line strings
+            if line.trim().is_empty() || (line.trim().starts_with('#') && !check_multi_line_strings)
+            {
+                continue;
+            }
+
+            let actual_indent = common::count_leading_whitespace(line);
+            let trimmed = line.trim_start();
+
+            // Tabs are reported by no-tabs; their width is unknown here
+            if line[..line.len() - trimmed.len()].contains('\t') {
+                continue;
+            }
+
+            // Determine if this is a sequence item
+            let is_sequence_item = trimmed.starts_with('-')
+                && trimmed.len() > 1
+                && trimmed.chars().nth(1).unwrap().is_whitespace();
+
+            if is_sequence_item {
+                let _in_sequence = true;
+                // Sequence items should be indented
+                if actual_indent % spaces != 0 {
+                    problems.push(Problem::from_message(
+                        line_number,
+                        1,
+                        Level::Error,
+                        self.id(),
+                        Message::new("indentation.wrong")
+                            .arg("spaces", spaces)
+                            .arg("actual", actual_indent),
+                    ));
+                }
+            } else {
+                // Regular key-value pairs
+                if actual_indent % spaces != 0 {
+                    problems.push(Problem::from_message(
+                        line_number,
+                        1,
+                        Level::Error,
+                        self.id(),
+                        Message::new("indentation.wrong")
+                            .arg("spaces", spaces)
+                            .arg("actual", actual_indent),
+                    ));
+                }
+                let _in_sequence = false;
+            }
+        }
+
+        // The first sequence under a key sets the style of a file when it must be consistent
+        let style = Self::sequence_style(config);
+        let mut indented = match style {
+            "indented" => Some(true),
+            "aligned" => Some(false),
+            _ => None,
+        };
+        let consistent = style == "consistent";
+        for sequence in Self::nested_sequences(context) {
+            let expected = match indented {
+                Some(expected) => expected,
+                None if consistent => *indented.insert(sequence.is_indented()),
+                None => continue,
+            };
+            if sequence.is_indented() != expected {
+                problems.push(Problem::from_message(
+                    sequence.line,
+                    sequence.dash_indent + 1,
+                    config.level.clone(),
+                    self.id(),
+                    Message::new(if expected {
+                        "indentation.sequence-indented"
+                    } else {
+                        "indentation.sequence-aligned"
+                    }),
+                ));
+            }
+        }
+        problems.sort_by_key(|problem| (problem.line, problem.column));
+
+        Ok(problems)
+    }
+
+    fn param_schema(&self) -> Vec<ParamSchema> {
+        ParamSchema::from_defaults(
+            &self.default_config(),
+            &self.docs(),
+            &[(
+                "sequence-style",
+                &["any", "indented", "aligned", "consistent"],
+            )],
+        )
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(false, Level::Error); // Disabled by default
+        config.set_param("spaces", 2i64);
+        config.set_param("sequence-style", "any");
+        config.set_param("check-multi-line-strings", false);
+        config
+    }
+}
+
+/// Rule that ensures files end with a newline
+#[derive(Debug, Default)]
+pub struct NewLineAtEndOfFileRule;
+
+impl NewLineAtEndOfFileRule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Rule for NewLineAtEndOfFileRule {
+    fn id(&self) -> &'static str {
+        "new-line-at-end-of-file"
+    }
+
+    fn fixable(&self) -> bool {
+        true
+    }
+
+    fn description(&self) -> &'static str {
+        "Requires a new line character at the end of files"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "POSIX tools expect text files to end with a line break; without it, concatenating files and some diffs misbehave.",
+            params: &[],
+            failing: "---\nkey: value",
+            passing: "---\nkey: value\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        if !config.enabled {
+            return Ok(Vec::new());
+        }
+
+        let mut problems = Vec::new();
+
+        // A file without content has no last line to end
+        if !context.is_blank() && !context.content.ends_with('\n') {
+            problems.push(Problem::from_message(
+                context.line_count(),
+                context
+                    .get_line(context.line_count())
+                    .map(|l| l.chars().count())
+                    .unwrap_or(0)
+                    + 1,
+                config.level.clone(),
+                self.id(),
+                Message::new("new-line-at-end-of-file.missing"),
+            ));
+        }
+
+        Ok(problems)
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        RuleConfig::new(false, Level::Error) // Disabled by default
+    }
+}
+
+/// Rule that forbids tab characters
+#[derive(Debug, Default)]
+pub struct NoTabsRule;
+
+impl NoTabsRule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Rule for NoTabsRule {
+    fn id(&self) -> &'static str {
+        "no-tabs"
+    }
+
+    fn fixable(&self) -> bool {
+        true
+    }
+
+    fn description(&self) -> &'static str {
+        "Forbids tab characters"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "YAML forbids tabs in indentation, and elsewhere they render differently from editor to editor. The fix expands indentation tabs to `indent-width` spaces, removes trailing tabs and replaces tabs in comments; tabs inside values are left alone.",
+            params: &[
+                (
+                    "allow-in-block-scalars",
+                    "do not report tabs inside literal (`|`) and folded (`>`) block scalars, where they are part of the value",
+                ),
+                ("allow-in-comments", "do not report tabs inside comments"),
+                (
+                    "indent-width",
+                    "number of spaces the fix replaces each tab with",
+                ),
+            ],
+            failing: "---\nparent:\n\tchild: value\n",
+            passing: "---\nparent:\n  child: value\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        if !config.enabled {
+            return Ok(Vec::new());
+        }
+
+        let allow_in_block_scalars = config.get_bool("allow-in-block-scalars").unwrap_or(true);
+        let allow_in_comments = config.get_bool("allow-in-comments").unwrap_or(false);
+        let tokens = context.tokens();
+
+        let mut problems = Vec::new();
+
+        for (line_no, line) in context.lines() {
+            if !line.contains('\t') || (allow_in_block_scalars && tokens.in_block_scalar(line_no)) {
+                continue;
+            }
+
+            let indent_end = line.len() - line.trim_start().len();
+            let trailing_start = common::trailing_whitespace_start(line).unwrap_or(line.len());
+            let comment_start = tokens.comment_start(line_no).unwrap_or(line.len());
+
+            // One problem per line, at the first tab that is not allowed
+            let found = line.match_indices('\t').find_map(|(pos, _)| {
+                let kind = if pos < indent_end {
+                    "no-tabs.in-indentation"
+                } else if pos >= trailing_start {
+                    "no-tabs.at-end-of-line"
+                } else if pos >= comment_start {
+                    if allow_in_comments {
+                        return None;
+                    }
+                    "no-tabs.in-comment"
+                } else {
+                    "no-tabs.in-value"
+                };
+                Some((pos, kind))
+            });
+
+            if let Some((pos, kind)) = found {
+                problems.push(Problem::from_message(
+                    line_no,
+                    LintContext::char_column(line, pos),
+                    config.level.clone(),
+                    self.id(),
+                    Message::new(kind),
+                ));
+            }
+        }
+
+        Ok(problems)
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(true, Level::Warning);
+        config.set_param("allow-in-block-scalars", true);
+        config.set_param("allow-in-comments", false);
+        config.set_param("indent-width", 2i64);
+        config
+    }
+
+    fn conflicts(
+        &self,
+        config: &RuleConfig,
+        enabled: &HashMap<String, RuleConfig>,
+    ) -> Vec<RuleConflict> {
+        let width = config.get_int("indent-width").unwrap_or(2);
+        let Some(indentation) = enabled.get("indentation") else {
+            return Vec::new();
+        };
+        let spaces = indentation.get_int("spaces").unwrap_or(2);
+
+        if width != spaces {
+            vec![RuleConflict::new(
+                self.id(),
+                "indentation",
+                format!(
+                    "no-tabs indent-width ({width}) differs from indentation spaces ({spaces}), so fixed tabs are reported as wrong indentation"
+                ),
+            )]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Rule that limits the size of scalar values
+#[derive(Debug, Default)]
+pub struct ValueLengthRule;
+
+impl ValueLengthRule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Rule for ValueLengthRule {
+    fn id(&self) -> &'static str {
+        "value-length"
+    }
+
+    fn description(&self) -> &'static str {
+        "Limits the length of scalar values"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "Flags scalar values longer than a limit, which usually means a certificate, key or base64 blob was inlined where a reference to a secret belongs. Plain and quoted scalars are measured on their line; block scalars (`|`, `>`) are measured over all their lines and reported at their header.",
+            params: &[
+                (
+                    "max",
+                    "maximum length of a plain or quoted scalar, in characters; 0 for no limit",
+                ),
+                (
+                    "max-block",
+                    "maximum length of a block scalar, in characters; 0 for no limit",
+                ),
+            ],
+            failing: "---\n# with max: 16\ntoken: bG9uZy1iYXNlNjQtYmxvYg==\n",
+            passing: "---\n# with max: 16\ntoken: ${TOKEN}\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        let mut problems = Vec::new();
+
+        let max = config.get_int("max").unwrap_or(0).max(0) as usize;
+        let max_block = config.get_int("max-block").unwrap_or(0).max(0) as usize;
+        let block_scalars = context.block_scalar_spans();
+        let lines: Vec<&str> = context.content.lines().collect();
+
+        if max_block > 0 {
+            for span in block_scalars {
+                let length: usize = span
+                    .clone()
+                    .map(|line_no| lines[line_no - 1].trim().chars().count())
+                    .sum();
+                if length <= max_block {
+                    continue;
+                }
+
+                let header_no = span.start() - 1;
+                let header = lines[header_no - 1];
+                let indicator = common::strip_comment(header).trim_end().rfind(['|', '>']);
+                problems.push(Problem::from_message(
+                    header_no,
+                    LintContext::char_column(header, indicator.unwrap_or(0)),
+                    config.level.clone(),
+                    self.id(),
+                    Message::new("value-length.block-scalar-too-long")
+                        .arg("length", length)
+                        .arg("max", max_block),
+                ));
+            }
+        }
+
+        if max > 0 {
+            for (line_no, line) in context.lines() {
+                if block_scalars.iter().any(|span| span.contains(&line_no)) {
+                    continue;
+                }
+                let Some((offset, length)) = self.scalar_value(line) else {
+                    continue;
+                };
+                if length > max {
+                    problems.push(Problem::from_message(
+                        line_no,
+                        LintContext::char_column(line, offset),
+                        config.level.clone(),
+                        self.id(),
+                        Message::new("value-length.too-long")
+                            .arg("length", length)
+                            .arg("max", max),
+                    ));
+                }
+            }
+        }
+
+        Ok(problems)
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(false, Level::Error); // Disabled by default
+        config.set_param("max", 1000i64);
+        config.set_param("max-block", 4000i64);
+        config
+    }
+
+    fn validate_config(&self, config: &RuleConfig) -> Result<()> {
+        for param in ["max", "max-block"] {
+            if let Some(max) = config.get_int(param)
+                && max < 0
+            {
+                return Err(eyre::eyre!("{param} must be 0 (unlimited) or positive"));
+            }
+        }
+        Ok(())
+    }
+
+    fn conflicts(
+        &self,
+        config: &RuleConfig,
+        enabled: &HashMap<String, RuleConfig>,
+    ) -> Vec<RuleConflict> {
+        let max = config.get_int("max").unwrap_or(0);
+        let Some(line_length) = enabled.get("line-length") else {
+            return Vec::new();
+        };
+        let line_length_rule = LineLengthRule::new();
+        let line_max = line_length.get_int("max").unwrap_or(80);
+
+        // Long values are what line-length exempts as non-breakable words
+        let exempts_values = line_length_rule.allow_non_breakable_words(line_length)
+            || !line_length_rule
+                .ignore_patterns(line_length)
+                .is_ok_and(|patterns| patterns.is_empty());
+        if max > 0 && max >= line_max && !exempts_values {
+            vec![RuleConflict::new(
+                self.id(),
+                "line-length",
+                format!(
+                    "value-length max ({max}) is not below line-length max ({line_max}), so line-length already reports every long plain or quoted value"
+                ),
+            )]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+impl ValueLengthRule {
+    /// Find the single-line scalar value on a line, returning its byte offset and
+    /// its length in characters without quotes
+    fn scalar_value(&self, line: &str) -> Option<(usize, usize)> {
+        let content = common::strip_comment(line).trim_end();
+        let mut rest = content.trim_start();
+
+        while let Some(item) = rest.strip_prefix("- ") {
+            rest = item.trim_start();
+        }
+        if let Some(colon) = common::find_mapping_colon(rest) {
+            rest = rest[colon + 1..].trim_start();
+        }
+
+        // Skip anchors and tags in front of the value
+        while rest.starts_with(['&', '!']) {
+            rest = rest
+                .split_once(' ')
+                .map_or("", |(_, value)| value.trim_start());
+        }
+
+        if rest.is_empty() || rest.starts_with(['{', '[', '|', '>', '*', '#']) {
+            return None;
+        }
+
+        let value = rest
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .or_else(|| rest.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+            .unwrap_or(rest);
+        Some((content.len() - rest.len(), value.chars().count()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn create_test_context<'a>(content: &'a str, path: &'a PathBuf) -> LintContext<'a> {
+        LintContext::new(path, content)
+    }
+
+    #[test]
+    fn test_line_length_rule_creation() {
+        let rule = LineLengthRule::new();
+        assert_eq!(rule.id(), "line-length");
+        assert_eq!(rule.default_max, 80);
+
+        let rule = LineLengthRule::with_default_max(120);
+        assert_eq!(rule.default_max, 120);
+    }
+
+    #[test]
+    fn test_line_length_rule_default_config() {
+        let rule = LineLengthRule::new();
+        let config = rule.default_config();
+
+        assert!(config.enabled);
+        assert_eq!(config.level, Level::Error);
+        assert_eq!(config.get_int("max"), Some(80));
+        assert_eq!(config.get_bool("allow-non-breakable-words"), Some(true));
+    }
+
+    #[test]
+    fn test_line_length_rule_config_validation() {
+        let rule = LineLengthRule::new();
+
+        let mut valid_config = rule.default_config();
+        valid_config.set_param("max", 100i64);
+        assert!(rule.validate_config(&valid_config).is_ok());
+
+        let mut invalid_config = rule.default_config();
+        invalid_config.set_param("max", -1i64);
+        assert!(rule.validate_config(&invalid_config).is_err());
+
+        let mut zero_config = rule.default_config();
+        zero_config.set_param("max", 0i64);
+        assert!(rule.validate_config(&zero_config).is_err());
+    }
+
+    #[test]
+    fn test_line_length_rule_check_short_lines() {
+        let rule = LineLengthRule::new();
+        let config = rule.default_config();
+        let path = PathBuf::from("test.yaml");
+        let content = "short line\nanother short line";
+        let context = create_test_context(content, &path);
+
+        let problems = rule.check(&context, &config).expect("Check failed");
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_line_length_rule_check_long_lines() {
+        let rule = LineLengthRule::new();
+        let config = rule.default_config();
+        let path = PathBuf::from("test.yaml");
+        // Create a long line with spaces (breakable)
+        let long_line = "this is a very long line with many words that definitely exceeds the eighty character limit";
+        let context = create_test_context(long_line, &path);
+
+        let problems = rule.check(&context, &config).expect("Check failed");
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, 1);
+        assert_eq!(problems[0].column, 81); // max + 1
+        assert_eq!(problems[0].rule, "line-length");
+        assert!(problems[0].message.contains("line too long"));
+    }
+
+    #[test]
+    fn test_line_length_rule_custom_max() {
+        let rule = LineLengthRule::new();
+        let mut config = rule.default_config();
+        config.set_param("max", 50i64);
+
+        let path = PathBuf::from("test.yaml");
+        // Use a line with spaces that exceeds 50 characters
+        let line = "this is a line with spaces that exceeds fifty characters";
+        let context = create_test_context(line, &path);
+
+        let problems = rule.check(&context, &config).expect("Check failed");
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].column, 51); // custom max + 1
+    }
+
+    #[test]
+    fn test_line_length_rule_non_breakable_words() {
+        let rule = LineLengthRule::new();
+        let config = rule.default_config();
+
+        // Long URL without spaces should be allowed
+        let path = PathBuf::from("test.yaml");
+        let url_line = "https://example.com/very/long/path/that/exceeds/eighty/characters/but/should/be/allowed/because/no/spaces";
+        let context = create_test_context(url_line, &path);
+
+        let problems = rule.check(&context, &config).expect("Check failed");
+        assert!(problems.is_empty());
+
+        // Long line with spaces should not be allowed
+        let breakable_line = "this is a very long line with many words that definitely exceeds the eighty character limit";
+        let context = create_test_context(breakable_line, &path);
+
+        let problems = rule.check(&context, &config).expect("Check failed");
+        assert_eq!(problems.len(), 1);
+    }
+
+    #[test]
+    fn test_line_length_rule_non_breakable_list_items_and_comments() {
+        let rule = LineLengthRule::new();
+        let config = rule.default_config();
+        let path = PathBuf::from("test.yaml");
+        let url = "https://example.com/very/long/path/that/exceeds/eighty/characters/for/sure/and/then/some";
+
+        for line in [format!("  - {url}"), format!("# {url}")] {
+            let context = create_test_context(&line, &path);
+            let problems = rule.check(&context, &config).expect("Check failed");
+            assert!(problems.is_empty(), "flagged: {line}");
+        }
+    }
+
+    #[test]
+    fn test_line_length_rule_non_breakable_inline_mappings() {
+        let rule = LineLengthRule::new();
+        let mut config = rule.default_config();
+        let path = PathBuf::from("test.yaml");
+        let line = "homepage: https://example.com/very/long/path/that/exceeds/eighty/characters/and/then/some";
+        let context = create_test_context(line, &path);
+
+        let problems = rule.check(&context, &config).expect("Check failed");
+        assert_eq!(problems.len(), 1);
+
+        config.set_param("allow-non-breakable-words", false);
+        config.set_param("allow-non-breakable-inline-mappings", true);
+        let problems = rule.check(&context, &config).expect("Check failed");
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_line_length_rule_ignore_patterns() {
+        let rule = LineLengthRule::new();
+        let mut config = rule.default_config();
+        config.set_param(
+            "ignore-patterns",
+            vec![ConfigValue::from(r"^\s*data: [A-Za-z0-9+/=]+ #")],
+        );
+        let path = PathBuf::from("test.yaml");
+        let content = format!("data: {} # base64 blob\n", "QUJD".repeat(30));
+        let context = create_test_context(&content, &path);
+
+        let problems = rule.check(&context, &config).expect("Check failed");
+        assert!(problems.is_empty());
+
+        config.set_param("ignore-patterns", vec![ConfigValue::from("(")]);
+        assert!(rule.validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_line_length_rule_length_units() {
+        let rule = LineLengthRule::new();
+        let mut config = rule.default_config();
+        config.set_param("max", 10i64);
+        let path = PathBuf::from("test.yaml");
+        // 8 characters, 20 bytes, 14 columns
+        let content = "名前: 山田太郎\n";
+        let context = create_test_context(content, &path);
+
+        assert!(rule.check(&context, &config).unwrap().is_empty());
+
+        config.set_param("length-unit", "display-width");
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].message, "line too long (14 > 10 columns)");
+        // 名前: 山田 fills ten columns, so 太 is the first character past the limit
+        assert_eq!(problems[0].column, 7);
+
+        config.set_param("length-unit", "bytes");
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems[0].message, "line too long (20 > 10 bytes)");
+        assert_eq!(problems[0].column, 5);
+
+        config.set_param("length-unit", "graphemes");
+        assert!(rule.validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_line_length_rule_disabled() {
+        let rule = LineLengthRule::new();
+        let mut config = rule.default_config();
+        config.enabled = false;
+
+        let path = PathBuf::from("test.yaml");
+        let long_line = "a".repeat(200);
+        let context = create_test_context(&long_line, &path);
+
+        let problems = rule.check(&context, &config).expect("Check failed");
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_trailing_spaces_rule_creation() {
+        let rule = TrailingSpacesRule::new();
+        assert_eq!(rule.id(), "trailing-spaces");
+    }
+
+    #[test]
+    fn test_trailing_spaces_rule_check_clean_lines() {
+        let rule = TrailingSpacesRule::new();
+        let config = rule.default_config();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("clean line\nanother clean line", &path);
+
+        let problems = rule.check(&context, &config).expect("Check failed");
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_trailing_spaces_rule_check_trailing_spaces() {
+        let rule = TrailingSpacesRule::new();
+        let config = rule.default_config();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("line with trailing spaces   \nclean line", &path);
+
+        let problems = rule.check(&context, &config).expect("Check failed");
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, 1);
+        assert_eq!(problems[0].rule, "trailing-spaces");
+        assert_eq!(problems[0].message, "trailing whitespace");
+    }
+
+    #[test]
+    fn test_trailing_spaces_rule_column_after_multibyte() {
+        let rule = TrailingSpacesRule::new();
+        let config = rule.default_config();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("clé: café 😀  \n", &path);
+
+        let problems = rule.check(&context, &config).expect("Check failed");
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].column, 12);
+    }
+
+    #[test]
+    fn test_trailing_spaces_rule_skip_block_scalars() {
+        let rule = TrailingSpacesRule::new();
+        let mut config = rule.default_config();
+        let path = PathBuf::from("test.yaml");
+        let content = "text: |\n  keep  \n  this \nkey: value \n";
+        let context = create_test_context(content, &path);
+
+        let problems = rule.check(&context, &config).expect("Check failed");
+        assert_eq!(problems.len(), 3);
+
+        config.set_param("skip-block-scalars", true);
+        let problems = rule.check(&context, &config).expect("Check failed");
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, 4);
+    }
+
+    #[test]
+    fn test_trailing_spaces_rule_disabled() {
+        let rule = TrailingSpacesRule::new();
+        let mut config = rule.default_config();
+        config.enabled = false;
+
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("line with trailing spaces   ", &path);
+
+        let problems = rule.check(&context, &config).expect("Check failed");
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_no_tabs_rule() {
+        let rule = NoTabsRule::new();
+        let mut config = rule.default_config();
+        let path = PathBuf::from("test.yaml");
+        let content = "a:\n\tb: 1\nc: x\ty\nd: 1\t\ne: 1  #\tnote\nf: |\n  one\ttwo\n";
+        let context = create_test_context(content, &path);
+
+        let problems = rule.check(&context, &config).expect("Check failed");
+        let found: Vec<(usize, usize, &str)> = problems
+            .iter()
+            .map(|p| (p.line, p.column, p.message.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (2, 1, "found tab character in indentation"),
+                (3, 5, "found tab character in value"),
+                (4, 5, "found tab character at end of line"),
+                (5, 8, "found tab character in comment"),
+            ]
+        );
+        assert!(problems.iter().all(|p| p.level == Level::Warning));
+
+        config.set_param("allow-in-comments", true);
+        config.set_param("allow-in-block-scalars", false);
+        let lines: Vec<usize> = rule
+            .check(&context, &config)
+            .expect("Check failed")
+            .iter()
+            .map(|p| p.line)
+            .collect();
+        assert_eq!(lines, vec![2, 3, 4, 7]);
+    }
+
+    #[test]
+    fn test_indentation_rule_leaves_tabs_to_no_tabs() {
+        let rule = IndentationRule::new();
+        let mut config = rule.default_config();
+        config.enabled = true;
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("a:\n\tb: 1\n", &path);
+
+        assert!(
+            rule.check(&context, &config)
+                .expect("Check failed")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_indentation_sequence_style() {
+        let rule = IndentationRule::new();
+        let path = PathBuf::from("test.yaml");
+        let content = "list:\n- a\n- b: c\n  d:\n    - x\nother: &anchor\n  - |\n    text\n";
+        let context = create_test_context(content, &path);
+        let check = |style: &str| {
+            let mut config = rule.default_config();
+            config.enabled = true;
+            config.set_param("sequence-style", style);
+            rule.check(&context, &config)
+                .expect("Check failed")
+                .into_iter()
+                .map(|problem| (problem.line, problem.column, problem.message))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            check("indented"),
+            vec![(
+                2,
+                1,
+                "sequence should be indented under its key".to_string()
+            )]
+        );
+        assert_eq!(
+            check("aligned"),
+            vec![
+                (5, 5, "sequence should be aligned with its key".to_string()),
+                (7, 3, "sequence should be aligned with its key".to_string()),
+            ]
+        );
+        // The first sequence, aligned with `list`, sets the style
+        assert_eq!(
+            check("consistent"),
+            vec![
+                (5, 5, "sequence should be aligned with its key".to_string()),
+                (7, 3, "sequence should be aligned with its key".to_string()),
+            ]
+        );
+        assert!(check("any").is_empty());
+
+        // The deprecated yamllint parameter applies when sequence-style is not set
+        let mut config = rule.default_config();
+        config.enabled = true;
+        config.set_param("indent-sequences", false);
+        assert_eq!(
+            rule.check(&context, &config).expect("Check failed").len(),
+            2
+        );
+        config.set_param("sequence-style", "indented");
+        assert_eq!(
+            rule.check(&context, &config).expect("Check failed").len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_nested_sequences() {
+        let path = PathBuf::from("test.yaml");
+        let content = "- key:\n  - a\n  - b\n-\n  # comment\nscript: |\n  list:\n  - not a sequence\nflow: [a]\ntop:\n- x\n\n- y\nafter: 1\n";
+        let context = create_test_context(content, &path);
+
+        assert_eq!(
+            IndentationRule::nested_sequences(&context),
+            vec![
+                NestedSequence {
+                    line: 2,
+                    end: 3,
+                    key_indent: 2,
+                    dash_indent: 2,
+                },
+                NestedSequence {
+                    line: 11,
+                    end: 13,
+                    key_indent: 0,
+                    dash_indent: 0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_is_non_breakable_line() {
+        let rule = LineLengthRule::new();
+
+        assert!(rule.is_non_breakable_line("https://example.com/very/long/url", false));
+        assert!(rule.is_non_breakable_line("  https://example.com/very/long/url", false));
+        assert!(rule.is_non_breakable_line("# https://example.com/very/long/url", false));
+        assert!(
+            rule.is_non_breakable_line("very-long-hyphenated-identifier-without-spaces", false)
+        );
+
+        assert!(!rule.is_non_breakable_line("this has spaces", false));
+        assert!(!rule.is_non_breakable_line("key: value with spaces", false));
+        assert!(!rule.is_non_breakable_line("# comment with spaces", false));
+    }
+
+    #[test]
+    fn test_value_length_rule_plain_scalars() {
+        let rule = ValueLengthRule::new();
+        let path = PathBuf::from("test.yaml");
+        let blob = "QUJD".repeat(10);
+        let content = format!(
+            "short: abc\ncert: {blob}\nquoted: \"{blob}\"\nlist:\n  - {blob}  # inline\nkey{blob}: 1\n"
+        );
+        let context = create_test_context(&content, &path);
+        let mut config = rule.default_config();
+        config.set_param("max", 32i64);
+
+        let problems = rule.check(&context, &config).unwrap();
+        let found: Vec<(usize, usize)> = problems.iter().map(|p| (p.line, p.column)).collect();
+        assert_eq!(found, vec![(2, 7), (3, 9), (5, 5)]);
+        assert_eq!(problems[0].message, "value too long (40 > 32 characters)");
+    }
+
+    #[test]
+    fn test_value_length_rule_block_scalars() {
+        let rule = ValueLengthRule::new();
+        let path = PathBuf::from("test.yaml");
+        let content =
+            "cert: |\n  MIIBszCCAVmgAwIBAgIUX\n  MIIBszCCAVmgAwIBAgIUX\nnote: >\n  short\n";
+        let context = create_test_context(content, &path);
+        let mut config = rule.default_config();
+        config.set_param("max", 10i64);
+        config.set_param("max-block", 30i64);
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert_eq!((problems[0].line, problems[0].column), (1, 7));
+        assert_eq!(
+            problems[0].message,
+            "block scalar too long (42 > 30 characters)"
+        );
+
+        config.set_param("max-block", -1i64);
+        assert!(rule.validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_blank_files() {
+        let path = PathBuf::from("test.yaml");
+        let new_line = NewLineAtEndOfFileRule::new();
+        let mut config = new_line.default_config();
+        config.enabled = true;
+        for content in ["", "  ", "\n\n  "] {
+            let context = create_test_context(content, &path);
+            assert!(new_line.check(&context, &config).unwrap().is_empty());
+        }
+
+        // Blank lines are reported once, not at both ends of the file
+        let empty_lines = EmptyLinesRule::new();
+        let mut config = empty_lines.default_config();
+        config.enabled = true;
+        let context = create_test_context("\n\n\n", &path);
+        let problems = empty_lines.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(
+            problems[0].message,
+            "too many blank lines at beginning of file (3 > 0)"
+        );
+    }
+}
@@ -0,0 +1,1557 @@
+use super::{ConfigValue, ParamSchema, Rule, RuleConfig, RuleDocs, RulePhase};
+use crate::linter::{Level, LintContext, Problem};
+use crate::messages::Message;
+use crate::parser::{YamlDirective, parse_documents};
+use crate::rules::common;
+use eyre::Result;
+use std::collections::{HashMap, HashSet};
+
+/// Rule that detects duplicate keys in YAML mappings
+#[derive(Debug, Default)]
+pub struct KeyDuplicatesRule;
+
+impl KeyDuplicatesRule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Rule for KeyDuplicatesRule {
+    fn id(&self) -> &'static str {
+        "key-duplicates"
+    }
+
+    fn description(&self) -> &'static str {
+        "Forbids duplications of a particular key"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "YAML parsers silently keep only one value of a repeated key, so duplicates usually hide mistakes.",
+            params: &[],
+            failing: "---\nname: first\nname: second\n",
+            passing: "---\nname: first\nalias: second\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, _config: &RuleConfig) -> Result<Vec<Problem>> {
+        let mut problems = Vec::new();
+
+        // Parse line by line to detect duplicate keys before serde_yaml processes them
+        self.check_duplicates_in_text(context, &mut problems)?;
+
+        Ok(problems)
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        RuleConfig::new(false, Level::Error) // Disabled by default for backward compatibility
+    }
+
+    fn validate_config(&self, _config: &RuleConfig) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl KeyDuplicatesRule {
+    fn check_duplicates_in_text(
+        &self,
+        context: &LintContext,
+        problems: &mut Vec<Problem>,
+    ) -> Result<()> {
+        let mut current_level_keys: Vec<HashMap<String, usize>> = vec![HashMap::new()];
+        let mut indent_stack = vec![0];
+
+        for (line_no, line) in context.content.lines().enumerate() {
+            let line_number = line_no + 1;
+            let trimmed = line.trim();
+
+            // Skip empty lines and comments
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            // Calculate indentation level
+            let indent = line.len() - line.trim_start().len();
+
+            // Adjust the stack based on indentation
+            while indent_stack.len() > 1 && indent <= indent_stack[indent_stack.len() - 1] {
+                indent_stack.pop();
+                current_level_keys.pop();
+            }
+
+            if indent > indent_stack[indent_stack.len() - 1] {
+                indent_stack.push(indent);
+                current_level_keys.push(HashMap::new());
+            }
+
+            // Look for key-value pairs
+            if let Some(colon_pos) = line.find(':') {
+                let key_part = line[..colon_pos].trim();
+
+                // Skip if this looks like a list item or complex key
+                if key_part.starts_with('-') || key_part.contains('[') || key_part.contains('{') {
+                    continue;
+                }
+
+                // Extract the key name (handle quoted keys); a lone quote is not a quoted key
+                let key = if key_part.len() >= 2
+                    && ((key_part.starts_with('"') && key_part.ends_with('"'))
+                        || (key_part.starts_with('\'') && key_part.ends_with('\'')))
+                {
+                    key_part[1..key_part.len() - 1].to_string()
+                } else {
+                    key_part.to_string()
+                };
+
+                if !key.is_empty() {
+                    let current_keys = current_level_keys.last_mut().unwrap();
+
+                    if let Some(&first_line) = current_keys.get(&key) {
+                        // Found duplicate key
+                        problems.push(Problem::from_message(
+                            line_number,
+                            LintContext::char_column(line, colon_pos),
+                            Level::Error,
+                            self.id(),
+                            Message::new("key-duplicates.found")
+                                .arg("key", &key)
+                                .arg("first_line", first_line),
+                        ));
+                    } else {
+                        current_keys.insert(key, line_number);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Rule that validates document structure (start/end markers)
+#[derive(Debug, Default)]
+pub struct DocumentStructureRule;
+
+impl DocumentStructureRule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Rule for DocumentStructureRule {
+    fn id(&self) -> &'static str {
+        "document-structure"
+    }
+
+    fn description(&self) -> &'static str {
+        "Requires document start and end markers"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "Explicit document markers make multi-document streams unambiguous and are required by some tools.",
+            params: &[
+                (
+                    "require-document-start",
+                    "require the file to start with `---`",
+                ),
+                (
+                    "require-document-end",
+                    "require the file to end with `...` or `---`",
+                ),
+                (
+                    "allow-empty-files",
+                    "accept empty and whitespace-only files without markers",
+                ),
+            ],
+            failing: "key: value\n",
+            passing: "---\nkey: value\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        let mut problems = Vec::new();
+
+        let require_start = config.get_bool("require-document-start").unwrap_or(true);
+        let require_end = config.get_bool("require-document-end").unwrap_or(false);
+
+        if context.is_blank() && config.get_bool("allow-empty-files").unwrap_or(false) {
+            return Ok(problems);
+        }
+
+        let lines: Vec<&str> = context.content.lines().collect();
+
+        if require_start {
+            // `%YAML` and `%TAG` directives come before the document start
+            let has_start = lines
+                .iter()
+                .find(|line| !line.starts_with('%'))
+                .is_some_and(|line| line.trim() == "---");
+            if !has_start {
+                problems.push(Problem::from_message(
+                    1,
+                    1,
+                    Level::Error,
+                    self.id(),
+                    Message::new("document-structure.missing-start"),
+                ));
+            }
+        }
+
+        if require_end {
+            let has_end = lines.last().is_some_and(|line| {
+                let trimmed = line.trim();
+                trimmed == "..." || trimmed == "---"
+            });
+            if !has_end {
+                problems.push(Problem::from_message(
+                    lines.len(),
+                    1,
+                    Level::Error,
+                    self.id(),
+                    Message::new("document-structure.missing-end"),
+                ));
+            }
+        }
+
+        Ok(problems)
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(false, Level::Error); // Disabled by default for backward compatibility
+        config.set_param(
+            "require-document-start".to_string(),
+            ConfigValue::Bool(true),
+        );
+        config.set_param("require-document-end".to_string(), ConfigValue::Bool(false));
+        config.set_param("allow-empty-files".to_string(), ConfigValue::Bool(false));
+        config
+    }
+
+    fn validate_config(&self, _config: &RuleConfig) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Rule that limits the number of documents in a file
+#[derive(Debug, Default)]
+pub struct DocumentCountRule;
+
+impl DocumentCountRule {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Read `min-documents` and `max-documents`; a maximum of 0 is no limit
+    fn limits(&self, config: &RuleConfig) -> Result<(usize, usize)> {
+        let limit = |param: &str, default: usize| -> Result<usize> {
+            match config.params.get(param) {
+                Some(value) => value
+                    .as_int()
+                    .and_then(|limit| usize::try_from(limit).ok())
+                    .ok_or_else(|| eyre::eyre!("{param} must be a non-negative integer")),
+                None => Ok(default),
+            }
+        };
+
+        let min = limit("min-documents", 0)?;
+        let max = limit("max-documents", 1)?;
+        if max > 0 && min > max {
+            return Err(eyre::eyre!(
+                "min-documents ({min}) must not be greater than max-documents ({max})"
+            ));
+        }
+        Ok((min, max))
+    }
+}
+
+impl Rule for DocumentCountRule {
+    fn id(&self) -> &'static str {
+        "document-count"
+    }
+
+    fn description(&self) -> &'static str {
+        "Limits the number of documents in a file"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "Some tools only read the first document of a file, while GitOps \
+                      repositories often want one resource per file. Documents holding \
+                      nothing but markers and comments are not counted.",
+            params: &[
+                ("min-documents", "minimum number of documents in a file"),
+                (
+                    "max-documents",
+                    "maximum number of documents in a file (0 for no limit)",
+                ),
+            ],
+            failing: "---\nkind: Service\n---\nkind: Deployment\n",
+            passing: "---\nkind: Service\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        let (min, max) = self.limits(config)?;
+        let documents: Vec<_> = parse_documents(context.content)
+            .into_iter()
+            .filter(|document| document.has_content)
+            .collect();
+        let mut problems = Vec::new();
+
+        if documents.len() < min {
+            problems.push(Problem::from_message(
+                1,
+                1,
+                config.level.clone(),
+                self.id(),
+                Message::new(if documents.len() == 1 {
+                    "document-count.too-few"
+                } else {
+                    "document-count.too-few-plural"
+                })
+                .arg("count", documents.len())
+                .arg("min", min),
+            ));
+        }
+        if max > 0 && documents.len() > max {
+            // Report where the first document over the limit starts
+            problems.push(Problem::from_message(
+                documents[max].lines.start,
+                1,
+                config.level.clone(),
+                self.id(),
+                Message::new("document-count.too-many")
+                    .arg("count", documents.len())
+                    .arg("max", max),
+            ));
+        }
+
+        Ok(problems)
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(false, Level::Error); // Disabled by default
+        config.set_param("min-documents".to_string(), ConfigValue::Int(0));
+        config.set_param("max-documents".to_string(), ConfigValue::Int(1));
+        config
+    }
+
+    fn validate_config(&self, config: &RuleConfig) -> Result<()> {
+        self.limits(config).map(|_| ())
+    }
+}
+
+/// Rule that validates YAML anchors and aliases
+#[derive(Debug, Default)]
+pub struct AnchorsRule;
+
+impl AnchorsRule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Rule for AnchorsRule {
+    fn id(&self) -> &'static str {
+        "anchors"
+    }
+
+    fn description(&self) -> &'static str {
+        "Validates YAML anchors and aliases"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "Checks anchors (`&name`) and aliases (`*name`) within each document: aliases must refer to an anchor declared earlier, and merge keys (`<<`) must refer to mappings. Anchors and aliases can also be banned outright.",
+            params: &[
+                (
+                    "forbid-undeclared-aliases",
+                    "report aliases whose anchor is not declared earlier in the document",
+                ),
+                (
+                    "forbid-duplicated-anchors",
+                    "report anchors declared more than once in a document",
+                ),
+                (
+                    "forbid-unused-anchors",
+                    "report anchors that are never referenced by an alias",
+                ),
+                (
+                    "forbid-invalid-merge-keys",
+                    "report merge keys whose value is not an alias to a mapping",
+                ),
+                (
+                    "max-aliases",
+                    "maximum number of aliases per document, 0 for no limit",
+                ),
+                (
+                    "scope",
+                    "`document` to allow reusing an anchor name in another document, `file` to report it",
+                ),
+                ("forbid", "report every anchor and alias"),
+            ],
+            failing: "---\nservice:\n  <<: *defaults\n",
+            passing: "---\ndefaults: &defaults\n  retries: 3\nservice:\n  <<: *defaults\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        let mut problems = Vec::new();
+        let lines: Vec<&str> = context.content.lines().collect();
+
+        // Anchors are scoped to a single document; names seen so far are only
+        // used to find duplicates across documents with `scope: file`
+        let mut file_anchors = HashSet::new();
+        for document in self.document_ranges(&lines) {
+            self.check_document(&lines, document, config, &mut file_anchors, &mut problems);
+        }
+
+        Ok(problems)
+    }
+
+    fn param_schema(&self) -> Vec<ParamSchema> {
+        ParamSchema::from_defaults(
+            &self.default_config(),
+            &self.docs(),
+            &[("scope", &["document", "file"])],
+        )
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(false, Level::Error); // Disabled by default for backward compatibility
+        config.set_param(
+            "forbid-undeclared-aliases".to_string(),
+            ConfigValue::Bool(true),
+        );
+        config.set_param(
+            "forbid-duplicated-anchors".to_string(),
+            ConfigValue::Bool(false),
+        );
+        config.set_param(
+            "forbid-unused-anchors".to_string(),
+            ConfigValue::Bool(false),
+        );
+        config.set_param(
+            "forbid-invalid-merge-keys".to_string(),
+            ConfigValue::Bool(true),
+        );
+        config.set_param("max-aliases".to_string(), ConfigValue::Int(0));
+        config.set_param(
+            "scope".to_string(),
+            ConfigValue::String("document".to_string()),
+        );
+        config.set_param("forbid".to_string(), ConfigValue::Bool(false));
+        config
+    }
+
+    fn validate_config(&self, config: &RuleConfig) -> Result<()> {
+        if let Some(max) = config.get_int("max-aliases")
+            && max < 0
+        {
+            return Err(eyre::eyre!("max-aliases must be 0 (unlimited) or positive"));
+        }
+        if let Some(scope) = config.get_string("scope")
+            && !matches!(scope, "document" | "file")
+        {
+            return Err(eyre::eyre!(
+                "scope must be \"document\" or \"file\", got \"{scope}\""
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Kind of node an anchor is attached to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NodeKind {
+    Mapping,
+    Sequence,
+    Scalar,
+}
+
+impl AnchorsRule {
+    /// Split the file into line ranges, one per YAML document
+    fn document_ranges(&self, lines: &[&str]) -> Vec<std::ops::Range<usize>> {
+        let mut ranges = Vec::new();
+        let mut start = 0;
+
+        for (index, line) in lines.iter().enumerate() {
+            let starts_document = *line == "---" || line.starts_with("--- ");
+            let ends_document = *line == "..." || line.starts_with("... ");
+
+            if starts_document && index > start {
+                ranges.push(start..index);
+                start = index;
+            } else if ends_document {
+                ranges.push(start..index + 1);
+                start = index + 1;
+            }
+        }
+
+        if start < lines.len() {
+            ranges.push(start..lines.len());
+        }
+
+        ranges
+    }
+
+    /// Check anchors, aliases and merge keys within a single document
+    fn check_document(
+        &self,
+        lines: &[&str],
+        document: std::ops::Range<usize>,
+        config: &RuleConfig,
+        file_anchors: &mut HashSet<String>,
+        problems: &mut Vec<Problem>,
+    ) {
+        let forbid = config.get_bool("forbid").unwrap_or(false);
+        let file_scope = config.get_string("scope") == Some("file");
+        let forbid_undeclared_aliases =
+            config.get_bool("forbid-undeclared-aliases").unwrap_or(true);
+        let forbid_duplicated_anchors = config
+            .get_bool("forbid-duplicated-anchors")
+            .unwrap_or(false);
+        let forbid_unused_anchors = config.get_bool("forbid-unused-anchors").unwrap_or(false);
+        let forbid_invalid_merge_keys =
+            config.get_bool("forbid-invalid-merge-keys").unwrap_or(true);
+        let max_aliases = config.get_int("max-aliases").unwrap_or(0).max(0) as usize;
+
+        // Anchors declared anywhere in the document, to tell undefined aliases
+        // apart from aliases that appear before their anchor
+        let declared_later: HashSet<String> = document
+            .clone()
+            .filter_map(|index| {
+                let anchor_pos = lines[index].find('&')?;
+                self.extract_anchor_name(&lines[index][anchor_pos..])
+            })
+            .collect();
+
+        let mut anchors: HashMap<String, NodeKind> = HashMap::new();
+        let mut aliases = HashSet::new();
+        let mut anchor_lines = HashMap::new();
+        let mut alias_count = 0;
+
+        for index in document {
+            let line = lines[index];
+            let line_number = index + 1;
+
+            // Look for anchors (&anchor_name)
+            if let Some(anchor_pos) = line.find('&')
+                && let Some(anchor_name) = self.extract_anchor_name(&line[anchor_pos..])
+            {
+                if forbid {
+                    problems.push(Problem::from_message(
+                        line_number,
+                        LintContext::char_column(line, anchor_pos),
+                        Level::Error,
+                        self.id(),
+                        Message::new("anchors.forbidden-anchor").arg("name", &anchor_name),
+                    ));
+                }
+                let duplicated = anchors.contains_key(&anchor_name)
+                    || (file_scope && file_anchors.contains(&anchor_name));
+                if forbid_duplicated_anchors && duplicated {
+                    problems.push(Problem::from_message(
+                        line_number,
+                        LintContext::char_column(line, anchor_pos),
+                        Level::Error,
+                        self.id(),
+                        Message::new("anchors.duplicate-anchor").arg("name", &anchor_name),
+                    ));
+                }
+                let after_name = anchor_pos + 1 + anchor_name.len();
+                let kind = self.anchored_node_kind(lines, index, after_name);
+                anchors.insert(anchor_name.clone(), kind);
+                file_anchors.insert(anchor_name.clone());
+                anchor_lines.insert(
+                    anchor_name,
+                    (line_number, LintContext::char_column(line, anchor_pos)),
+                );
+            }
+
+            // Look for aliases (*alias_name)
+            if let Some(alias_pos) = line.find('*')
+                && let Some(alias_name) = self.extract_alias_name(&line[alias_pos..])
+            {
+                aliases.insert(alias_name.clone());
+                alias_count += 1;
+
+                if forbid {
+                    problems.push(Problem::from_message(
+                        line_number,
+                        LintContext::char_column(line, alias_pos),
+                        Level::Error,
+                        self.id(),
+                        Message::new("anchors.forbidden-alias").arg("name", &alias_name),
+                    ));
+                }
+
+                if forbid_undeclared_aliases && !anchors.contains_key(&alias_name) {
+                    let id = if declared_later.contains(&alias_name) {
+                        "anchors.alias-before-anchor"
+                    } else {
+                        "anchors.undefined-alias"
+                    };
+                    problems.push(Problem::from_message(
+                        line_number,
+                        LintContext::char_column(line, alias_pos),
+                        Level::Error,
+                        self.id(),
+                        Message::new(id).arg("name", &alias_name),
+                    ));
+                }
+
+                if max_aliases > 0 && alias_count == max_aliases + 1 {
+                    problems.push(Problem::from_message(
+                        line_number,
+                        LintContext::char_column(line, alias_pos),
+                        Level::Error,
+                        self.id(),
+                        Message::new("anchors.too-many-aliases").arg("max", max_aliases),
+                    ));
+                }
+            }
+
+            if forbid_invalid_merge_keys {
+                self.check_merge_key(line, line_number, &anchors, problems);
+            }
+        }
+
+        // Check for unused anchors
+        if forbid_unused_anchors {
+            for (anchor, &(line_number, column)) in &anchor_lines {
+                if !aliases.contains(anchor) {
+                    problems.push(Problem::from_message(
+                        line_number,
+                        column,
+                        Level::Warning,
+                        self.id(),
+                        Message::new("anchors.unused-anchor").arg("name", anchor),
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Validate that a `<<:` merge key refers to mappings only
+    fn check_merge_key(
+        &self,
+        line: &str,
+        line_number: usize,
+        anchors: &HashMap<String, NodeKind>,
+        problems: &mut Vec<Problem>,
+    ) {
+        let trimmed = line.trim_start();
+        let trimmed = trimmed.strip_prefix("- ").unwrap_or(trimmed).trim_start();
+        let Some(value) = trimmed.strip_prefix("<<:") else {
+            return;
+        };
+        let value_column = LintContext::char_column(line, line.len() - value.trim_start().len());
+        let value = value.split(" #").next().unwrap_or("").trim();
+
+        // A nested block or flow mapping is always a valid merge source
+        if value.is_empty() || value.starts_with('{') {
+            return;
+        }
+
+        let alias_names: Vec<&str> = if let Some(list) = value.strip_prefix('[') {
+            list.trim_end_matches(']')
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .collect()
+        } else {
+            vec![value]
+        };
+
+        for item in alias_names {
+            let Some(alias_name) = item.strip_prefix('*') else {
+                problems.push(Problem::from_message(
+                    line_number,
+                    value_column,
+                    Level::Error,
+                    self.id(),
+                    Message::new("anchors.merge-not-alias").arg("value", item),
+                ));
+                continue;
+            };
+
+            if let Some(kind) = anchors.get(alias_name)
+                && *kind != NodeKind::Mapping
+            {
+                problems.push(Problem::from_message(
+                    line_number,
+                    value_column,
+                    Level::Error,
+                    self.id(),
+                    Message::new("anchors.merge-not-mapping").arg("name", alias_name),
+                ));
+            }
+        }
+    }
+
+    /// Determine the kind of node an anchor ending at `after_name` is attached to
+    fn anchored_node_kind(&self, lines: &[&str], index: usize, after_name: usize) -> NodeKind {
+        let rest = lines[index].get(after_name..).unwrap_or("").trim();
+        if rest.starts_with('{') {
+            return NodeKind::Mapping;
+        }
+        if rest.starts_with('[') {
+            return NodeKind::Sequence;
+        }
+        if !rest.is_empty() && !rest.starts_with('#') {
+            return NodeKind::Scalar;
+        }
+
+        // Block collection: look at the first content line below the anchor
+        let next = lines[index + 1..]
+            .iter()
+            .map(|line| line.trim())
+            .find(|line| !line.is_empty() && !line.starts_with('#'));
+
+        match next {
+            Some(line) if line == "-" || line.starts_with("- ") => NodeKind::Sequence,
+            Some(_) => NodeKind::Mapping,
+            None => NodeKind::Scalar,
+        }
+    }
+
+    fn extract_anchor_name(&self, text: &str) -> Option<String> {
+        // Extract anchor name from &anchor_name
+        if let Some(name_part) = text.strip_prefix('&') {
+            let end = name_part
+                .find(|c: char| c.is_whitespace() || c == ':' || c == ',' || c == ']' || c == '}')
+                .unwrap_or(name_part.len());
+            if end > 0 {
+                Some(name_part[..end].to_string())
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+
+    fn extract_alias_name(&self, text: &str) -> Option<String> {
+        // Extract alias name from *alias_name
+        if let Some(name_part) = text.strip_prefix('*') {
+            let end = name_part
+                .find(|c: char| c.is_whitespace() || c == ':' || c == ',' || c == ']' || c == '}')
+                .unwrap_or(name_part.len());
+            if end > 0 {
+                Some(name_part[..end].to_string())
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// Enhanced YAML syntax rule that catches parsing errors and syntax issues
+#[derive(Debug, Default)]
+pub struct YamlSyntaxRule;
+
+impl YamlSyntaxRule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Rule for YamlSyntaxRule {
+    fn id(&self) -> &'static str {
+        "yaml-syntax"
+    }
+
+    fn phase(&self) -> RulePhase {
+        RulePhase::Parse
+    }
+
+    fn description(&self) -> &'static str {
+        "Validates YAML syntax and catches parsing errors"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "Reports content that cannot be parsed as YAML.",
+            params: &[],
+            failing: "---\nkey: [unclosed\n",
+            passing: "---\nkey: [closed]\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, _config: &RuleConfig) -> Result<Vec<Problem>> {
+        let mut problems = Vec::new();
+
+        // The context's parse of the YAML, shared with other rules, tells of syntax errors
+        if let Some(error_msg) = context.parse_error() {
+            let (line, column) = self.extract_error_position(error_msg);
+
+            // Errors at the end of input point past the last line; report them at its end
+            let line_count = context.line_count().max(1);
+            let (line, column) = if line > line_count {
+                (line_count, usize::MAX)
+            } else {
+                (line.max(1), column)
+            };
+            let line_end = context
+                .get_line(line)
+                .map_or(0, |text| text.chars().count())
+                + 1;
+            let column = column.clamp(1, line_end);
+
+            problems.push(Problem::from_message(
+                line,
+                column,
+                Level::Error,
+                self.id(),
+                Message::new("yaml-syntax.error").arg("error", self.clean_error_message(error_msg)),
+            ));
+        }
+
+        Ok(problems)
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        RuleConfig::new(true, Level::Error) // Enabled by default
+    }
+
+    fn validate_config(&self, _config: &RuleConfig) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl YamlSyntaxRule {
+    fn extract_error_position(&self, error_msg: &str) -> (usize, usize) {
+        // Try to extract line and column from error message
+        // serde_yaml error format: "... at line X column Y"
+        if let Some(line_pos) = error_msg.find("line ")
+            && let Some(col_pos) = error_msg.find(" column ")
+        {
+            let line_str = &error_msg[line_pos + 5..col_pos];
+            let col_str = &error_msg[col_pos + 8..];
+
+            let line = line_str.parse::<usize>().unwrap_or(1);
+            let column = col_str
+                .split_whitespace()
+                .next()
+                .and_then(|s| s.parse::<usize>().ok())
+                .unwrap_or(1);
+
+            return (line, column);
+        }
+        (1, 1) // Default position
+    }
+
+    fn clean_error_message(&self, error_msg: &str) -> String {
+        // Clean up the error message to make it more user-friendly
+        error_msg
+            .replace("invalid type: ", "")
+            .replace("expected ", "")
+            .split(" at line")
+            .next()
+            .unwrap_or(error_msg)
+            .to_string()
+    }
+}
+
+/// Rule that validates comment formatting
+#[derive(Debug, Default)]
+pub struct CommentsRule;
+
+impl CommentsRule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Rule for CommentsRule {
+    fn id(&self) -> &'static str {
+        "comments"
+    }
+
+    fn description(&self) -> &'static str {
+        "Controls comment formatting and placement"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "Keeps comments readable by requiring a space after `#` and separating inline comments from the content before them.",
+            params: &[
+                (
+                    "require-starting-space",
+                    "require a space after the `#` of a comment",
+                ),
+                (
+                    "min-spaces-from-content",
+                    "minimum number of spaces between content and an inline comment",
+                ),
+            ],
+            failing: "---\nkey: value # comment\n#no space\n",
+            passing: "---\nkey: value  # comment\n# with space\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        let mut problems = Vec::new();
+
+        let require_starting_space = config.get_bool("require-starting-space").unwrap_or(true);
+        let min_spaces_from_content =
+            config.get_int("min-spaces-from-content").unwrap_or(2) as usize;
+
+        let tokens = context.tokens();
+        for (line_no, line) in context.content.lines().enumerate() {
+            let line_number = line_no + 1;
+            let Some(hash_pos) = tokens.comment_start(line_number) else {
+                continue;
+            };
+            let comment_part = &line[hash_pos..];
+
+            // Check for space after #
+            if require_starting_space && comment_part.len() > 1 {
+                let next_char = comment_part.chars().nth(1).unwrap();
+                if next_char != ' ' && next_char != '\t' {
+                    problems.push(Problem::from_message(
+                        line_number,
+                        LintContext::char_column(line, hash_pos + 1),
+                        Level::Error,
+                        self.id(),
+                        Message::new("comments.missing-space"),
+                    ));
+                }
+            }
+
+            // Check spacing from content (inline comments)
+            let content_before = &line[..hash_pos];
+            if !content_before.trim().is_empty() {
+                let spaces_before = content_before.len() - content_before.trim_end().len();
+                if spaces_before < min_spaces_from_content {
+                    problems.push(Problem::from_message(
+                        line_number,
+                        LintContext::char_column(line, hash_pos),
+                        Level::Error,
+                        self.id(),
+                        Message::new("comments.too-few-spaces-before")
+                            .arg("min", min_spaces_from_content),
+                    ));
+                }
+            }
+        }
+
+        Ok(problems)
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(false, Level::Error); // Disabled by default
+        config.set_param("require-starting-space", true);
+        config.set_param("min-spaces-from-content", 2i64);
+        config
+    }
+
+    fn validate_config(&self, _config: &RuleConfig) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Rule that governs `%YAML` and `%TAG` directives
+#[derive(Debug, Default)]
+pub struct DirectivesRule;
+
+impl DirectivesRule {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Find the named tag handles (`!name!`) used on a line, with their byte offsets
+    fn tag_handles(line: &str) -> Vec<(usize, &str)> {
+        let content = common::strip_comment(line);
+        let mut handles = Vec::new();
+        let mut previous = ' ';
+
+        for (offset, c) in content.char_indices() {
+            if c == '!' && matches!(previous, ' ' | '\t' | '[' | '{' | ',') {
+                let name_end = content[offset + 1..]
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+                    .map(|end| offset + 1 + end);
+                if let Some(end) = name_end
+                    && end > offset + 1
+                    && content[end..].starts_with('!')
+                {
+                    handles.push((offset, &content[offset..=end]));
+                }
+            }
+            previous = c;
+        }
+
+        handles
+    }
+}
+
+impl Rule for DirectivesRule {
+    fn id(&self) -> &'static str {
+        "directives"
+    }
+
+    fn description(&self) -> &'static str {
+        "Governs %YAML and %TAG directives"
+    }
+
+    fn docs(&self) -> RuleDocs {
+        RuleDocs {
+            details: "Checks the directives that precede a document. A `%YAML` directive can be required or forbidden and must name the expected version; `%TAG` directives may only declare allowed handles, named tag handles such as `!e!` must be declared by a `%TAG` directive of their document, and unknown directives are reported.",
+            params: &[
+                (
+                    "yaml-directive",
+                    "`allow`, `require` (the file must start with one) or `forbid`",
+                ),
+                (
+                    "version",
+                    "version a `%YAML` directive must name; empty for any",
+                ),
+                (
+                    "tag-handles",
+                    "named handles `%TAG` may declare, e.g. `!e!`; empty for any",
+                ),
+            ],
+            failing: "%YAML 1.1\n---\nkey: !e!thing value\n",
+            passing: "%YAML 1.2\n%TAG !e! tag:example.com,2024:\n---\nkey: !e!thing value\n",
+        }
+    }
+
+    fn check(&self, context: &LintContext, config: &RuleConfig) -> Result<Vec<Problem>> {
+        let mut problems = Vec::new();
+
+        let yaml_directive = config.get_string("yaml-directive").unwrap_or("allow");
+        let version = config.get_string("version").unwrap_or("1.2");
+        let allowed_handles: Vec<&str> = config
+            .params
+            .get("tag-handles")
+            .and_then(ConfigValue::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(ConfigValue::as_string)
+            .collect();
+
+        let documents = parse_documents(context.content);
+        let mut report = |line: usize, column: usize, message: Message| {
+            problems.push(Problem::from_message(
+                line,
+                column,
+                config.level.clone(),
+                self.id(),
+                message,
+            ));
+        };
+
+        let has_yaml_directive = documents[0]
+            .directives
+            .iter()
+            .any(|directive| matches!(directive, YamlDirective::Yaml { .. }));
+        if yaml_directive == "require" && !has_yaml_directive {
+            report(1, 1, Message::new("directives.missing-yaml"));
+        }
+
+        let lines: Vec<&str> = context.content.lines().collect();
+        let block_scalars = context.block_scalar_spans();
+
+        for document in &documents {
+            let mut declared = HashSet::new();
+            let mut seen_yaml = false;
+
+            for directive in &document.directives {
+                match directive {
+                    YamlDirective::Yaml {
+                        line,
+                        version: found,
+                    } => {
+                        if yaml_directive == "forbid" {
+                            report(*line, 1, Message::new("directives.forbidden-yaml"));
+                        } else if seen_yaml {
+                            report(*line, 1, Message::new("directives.duplicate-yaml"));
+                        } else if !version.is_empty() && found != version {
+                            report(
+                                *line,
+                                1,
+                                Message::new("directives.wrong-version")
+                                    .arg("version", found)
+                                    .arg("expected", version),
+                            );
+                        }
+                        seen_yaml = true;
+                    }
+                    YamlDirective::Tag { line, handle, .. } => {
+                        if !allowed_handles.is_empty()
+                            && !matches!(handle.as_str(), "!" | "!!")
+                            && !allowed_handles.contains(&handle.as_str())
+                        {
+                            report(
+                                *line,
+                                1,
+                                Message::new("directives.forbidden-tag-handle")
+                                    .arg("handle", handle),
+                            );
+                        }
+                        declared.insert(handle.as_str());
+                    }
+                    YamlDirective::Reserved { line, name } => {
+                        report(
+                            *line,
+                            1,
+                            Message::new("directives.unknown").arg("name", name),
+                        );
+                    }
+                }
+            }
+
+            let directive_lines: HashSet<usize> = document
+                .directives
+                .iter()
+                .map(YamlDirective::line)
+                .collect();
+            for line_no in document.lines.clone() {
+                if directive_lines.contains(&line_no)
+                    || block_scalars.iter().any(|span| span.contains(&line_no))
+                {
+                    continue;
+                }
+                let Some(line) = lines.get(line_no - 1) else {
+                    continue;
+                };
+                for (offset, handle) in Self::tag_handles(line) {
+                    if handle != "!!" && !declared.contains(handle) {
+                        report(
+                            line_no,
+                            LintContext::char_column(line, offset),
+                            Message::new("directives.undeclared-tag-handle").arg("handle", handle),
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(problems)
+    }
+
+    fn param_schema(&self) -> Vec<ParamSchema> {
+        ParamSchema::from_defaults(
+            &self.default_config(),
+            &self.docs(),
+            &[("yaml-directive", &["allow", "require", "forbid"])],
+        )
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::new(false, Level::Error); // Disabled by default
+        config.set_param(
+            "yaml-directive".to_string(),
+            ConfigValue::String("allow".to_string()),
+        );
+        config.set_param(
+            "version".to_string(),
+            ConfigValue::String("1.2".to_string()),
+        );
+        config.set_param("tag-handles".to_string(), ConfigValue::Array(Vec::new()));
+        config
+    }
+
+    fn validate_config(&self, config: &RuleConfig) -> Result<()> {
+        if let Some(policy) = config.get_string("yaml-directive")
+            && !matches!(policy, "allow" | "require" | "forbid")
+        {
+            return Err(eyre::eyre!(
+                "yaml-directive must be \"allow\", \"require\" or \"forbid\", got \"{policy}\""
+            ));
+        }
+        if let Some(handles) = config.params.get("tag-handles") {
+            let valid = handles.as_array().is_some_and(|handles| {
+                handles.iter().all(|handle| {
+                    handle
+                        .as_string()
+                        .is_some_and(|h| h.len() > 1 && h.starts_with('!') && h.ends_with('!'))
+                })
+            });
+            if !valid {
+                return Err(eyre::eyre!(
+                    "tag-handles must be a list of handles such as \"!e!\""
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn create_test_context<'a>(content: &'a str, path: &'a PathBuf) -> LintContext<'a> {
+        LintContext::new(path, content)
+    }
+
+    #[test]
+    fn test_key_duplicates_rule_no_duplicates() {
+        let rule = KeyDuplicatesRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("key1: value1\nkey2: value2", &path);
+        let mut config = rule.default_config();
+        config.enabled = true; // Enable for testing
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_key_duplicates_rule_with_duplicates() {
+        let rule = KeyDuplicatesRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("key1: value1\nkey1: value2", &path);
+        let mut config = rule.default_config();
+        config.enabled = true; // Enable for testing
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].rule, "key-duplicates");
+        assert!(problems[0].message.contains("duplicate key"));
+    }
+
+    #[test]
+    fn test_key_duplicates_rule_column_after_multibyte() {
+        let rule = KeyDuplicatesRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("clé: 1\nclé: 2", &path);
+        let config = rule.default_config();
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].column, 4);
+    }
+
+    #[test]
+    fn test_document_structure_rule_missing_start() {
+        let rule = DocumentStructureRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("key: value", &path);
+        let mut config = rule.default_config();
+        config.enabled = true; // Enable for testing
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].rule, "document-structure");
+        assert!(problems[0].message.contains("missing document start"));
+    }
+
+    #[test]
+    fn test_document_structure_rule_with_start() {
+        let rule = DocumentStructureRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("---\nkey: value", &path);
+        let mut config = rule.default_config();
+        config.enabled = true; // Enable for testing
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert!(problems.is_empty());
+
+        let context = create_test_context("%YAML 1.2\n---\nkey: value", &path);
+        assert!(rule.check(&context, &config).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_document_structure_rule_allow_empty_files() {
+        let rule = DocumentStructureRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("\n  \n", &path);
+        let mut config = rule.default_config();
+        config.enabled = true; // Enable for testing
+
+        assert_eq!(rule.check(&context, &config).unwrap().len(), 1);
+        config.set_param("allow-empty-files", true);
+        assert!(rule.check(&context, &config).unwrap().is_empty());
+
+        let context = create_test_context("key: value", &path);
+        assert_eq!(rule.check(&context, &config).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_document_count_rule() {
+        let rule = DocumentCountRule::new();
+        let path = PathBuf::from("test.yaml");
+        let mut config = rule.default_config();
+        config.enabled = true;
+
+        let context = create_test_context("---\na: 1\n---\n", &path);
+        assert!(rule.check(&context, &config).unwrap().is_empty());
+
+        let context = create_test_context("---\na: 1\n---\nb: 2\n---\nc: 3\n", &path);
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, 3);
+        assert_eq!(problems[0].message, "found 3 documents, expected at most 1");
+
+        config.set_param("max-documents".to_string(), ConfigValue::Int(0));
+        assert!(rule.check(&context, &config).unwrap().is_empty());
+
+        config.set_param("min-documents".to_string(), ConfigValue::Int(1));
+        let context = create_test_context("# nothing yet\n", &path);
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(
+            problems[0].message,
+            "found 0 documents, expected at least 1"
+        );
+
+        config.set_param("min-documents".to_string(), ConfigValue::Int(2));
+        let context = create_test_context(
+            "---
+a: 1
+",
+            &path,
+        );
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems[0].message, "found 1 document, expected at least 2");
+    }
+
+    #[test]
+    fn test_document_count_rule_validation() {
+        let rule = DocumentCountRule::new();
+        let mut config = rule.default_config();
+        assert!(rule.validate_config(&config).is_ok());
+
+        config.set_param("min-documents".to_string(), ConfigValue::Int(2));
+        assert!(rule.validate_config(&config).is_err());
+
+        config.set_param("max-documents".to_string(), ConfigValue::Int(-1));
+        assert!(rule.validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_anchors_rule_valid_anchor_alias() {
+        let rule = AnchorsRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("anchor: &my_anchor value\nalias: *my_anchor", &path);
+        let mut config = rule.default_config();
+        config.enabled = true; // Enable for testing
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_anchors_rule_undefined_alias() {
+        let rule = AnchorsRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("alias: *undefined_anchor", &path);
+        let mut config = rule.default_config();
+        config.enabled = true; // Enable for testing
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].rule, "anchors");
+        assert!(problems[0].message.contains("undefined alias"));
+    }
+
+    #[test]
+    fn test_anchors_rule_column_after_multibyte() {
+        let rule = AnchorsRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("ключ: *missing", &path);
+        let config = rule.default_config();
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].column, 7);
+    }
+
+    #[test]
+    fn test_anchors_rule_scoped_to_document() {
+        let rule = AnchorsRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("base: &base value\n---\nalias: *base\n", &path);
+        let config = rule.default_config();
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, 3);
+        assert!(problems[0].message.contains("undefined alias"));
+    }
+
+    #[test]
+    fn test_anchors_rule_alias_before_anchor() {
+        let rule = AnchorsRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("alias: *later\nanchor: &later value\n", &path);
+        let config = rule.default_config();
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].message.contains("before its anchor"));
+    }
+
+    #[test]
+    fn test_anchors_rule_merge_keys() {
+        let rule = AnchorsRule::new();
+        let path = PathBuf::from("test.yaml");
+        let content = "defaults: &defaults\n  a: 1\nlist: &list\n  - 1\nname: &name value\n\
+                       ok:\n  <<: *defaults\nbad_list:\n  <<: *list\nbad_scalar:\n  <<: [*defaults, *name]\n\
+                       bad_value:\n  <<: plain\n";
+        let context = create_test_context(content, &path);
+        let config = rule.default_config();
+
+        let problems = rule.check(&context, &config).unwrap();
+        let lines: Vec<usize> = problems.iter().map(|p| p.line).collect();
+        assert_eq!(lines, vec![9, 11, 13]);
+        assert!(
+            problems[0]
+                .message
+                .contains("\"list\" does not refer to a mapping")
+        );
+        assert!(
+            problems[1]
+                .message
+                .contains("\"name\" does not refer to a mapping")
+        );
+        assert!(problems[2].message.contains("must be an alias"));
+    }
+
+    #[test]
+    fn test_anchors_rule_max_aliases() {
+        let rule = AnchorsRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("a: &a 1\nb: *a\nc: *a\nd: *a\n", &path);
+        let mut config = rule.default_config();
+        config.set_param("max-aliases", 2i64);
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, 4);
+        assert!(problems[0].message.contains("too many aliases"));
+    }
+
+    #[test]
+    fn test_anchors_rule_duplicate_anchor() {
+        let rule = AnchorsRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context(
+            "anchor1: &my_anchor value1\nanchor2: &my_anchor value2",
+            &path,
+        );
+        let mut config = rule.default_config();
+        config.enabled = true; // Enable for testing
+        config.set_param(
+            "forbid-duplicated-anchors".to_string(),
+            ConfigValue::Bool(true),
+        );
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].rule, "anchors");
+        assert!(problems[0].message.contains("duplicate anchor"));
+    }
+
+    #[test]
+    fn test_anchors_rule_duplicate_anchor_scope() {
+        let rule = AnchorsRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("a: &base 1\n---\nb: &base 2\n", &path);
+        let mut config = rule.default_config();
+        config.set_param("forbid-duplicated-anchors", true);
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert!(problems.is_empty());
+
+        config.set_param("scope", "file");
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, 3);
+        assert!(problems[0].message.contains("duplicate anchor \"base\""));
+
+        config.set_param("scope", "stream");
+        assert!(rule.validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_anchors_rule_forbid() {
+        let rule = AnchorsRule::new();
+        let path = PathBuf::from("test.yaml");
+        let context = create_test_context("a: &base 1\nb: *base\nc: plain\n", &path);
+        let mut config = rule.default_config();
+        config.set_param("forbid", true);
+
+        let problems = rule.check(&context, &config).unwrap();
+        let messages: Vec<&str> = problems.iter().map(|p| p.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "found forbidden anchor \"base\"",
+                "found forbidden alias \"base\""
+            ]
+        );
+    }
+
+    #[test]
+    fn test_directives_rule_yaml_directive() {
+        let rule = DirectivesRule::new();
+        let path = PathBuf::from("test.yaml");
+        let mut config = rule.default_config();
+
+        let context = create_test_context("%YAML 1.1\n%YAML 1.2\n---\nkey: value\n", &path);
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 2);
+        assert_eq!(
+            problems[0].message,
+            "found %YAML version \"1.1\" (expected \"1.2\")"
+        );
+        assert_eq!(problems[1].message, "found duplicate %YAML directive");
+
+        let context = create_test_context("---\nkey: value\n", &path);
+        assert!(rule.check(&context, &config).unwrap().is_empty());
+        config.set_param("yaml-directive", "require");
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems[0].message, "missing %YAML directive");
+
+        let context = create_test_context("%YAML 1.2\n---\nkey: value\n", &path);
+        assert!(rule.check(&context, &config).unwrap().is_empty());
+        config.set_param("yaml-directive", "forbid");
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems[0].message, "found forbidden %YAML directive");
+
+        config.set_param("yaml-directive", "sometimes");
+        assert!(rule.validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_directives_rule_tag_handles() {
+        let rule = DirectivesRule::new();
+        let path = PathBuf::from("test.yaml");
+        let mut config = rule.default_config();
+        let content = "%TAG !e! tag:example.com,2024:\n%FOO bar\n---\na: !e!thing 1\nb: !x!thing 2\nc: !!str 3\n...\n---\nd: [!e!thing 4]\n";
+        let context = create_test_context(content, &path);
+
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems.len(), 3);
+        assert_eq!(problems[0].message, "found unknown directive \"%FOO\"");
+        assert_eq!((problems[1].line, problems[1].column), (5, 4));
+        assert_eq!(problems[1].message, "found undeclared tag handle \"!x!\"");
+        assert_eq!((problems[2].line, problems[2].column), (9, 5));
+
+        config.set_param("tag-handles", vec![ConfigValue::from("!k8s!")]);
+        let problems = rule.check(&context, &config).unwrap();
+        assert_eq!(problems[0].message, "found forbidden tag handle \"!e!\"");
+    }
+
+    #[test]
+    fn test_comments_rule() {
+        let rule = CommentsRule::new();
+        let path = PathBuf::from("test.yaml");
+        let content = "#bad\nkey: value  # ok\nother: value # too close\nquoted: \"a #b\"  # ok\nit: it's #bad\nurl: http://x/#a\nscript: |\n  #not a comment\n";
+        let context = create_test_context(content, &path);
+        let mut config = rule.default_config();
+        config.enabled = true;
+
+        let problems = rule.check(&context, &config).unwrap();
+        let positions: Vec<(usize, usize)> = problems.iter().map(|p| (p.line, p.column)).collect();
+        assert_eq!(positions, vec![(1, 2), (3, 14), (5, 11), (5, 10)]);
+    }
+}
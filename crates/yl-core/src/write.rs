@@ -0,0 +1,163 @@
+//! Safe replacement of user files
+//!
+//! Files are written to a temporary file next to the original and renamed over it, so a
+//! crash never leaves a truncated file behind. The replacement keeps the original's
+//! permissions and, where the process may set them, its owner and group.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Atomically replace the contents of `path`, creating it if it does not exist.
+///
+/// With `backup_suffix`, an existing file is first kept as `<path><suffix>`. Writing
+/// through a symbolic link replaces the file it points to and leaves the link in place.
+pub fn write_atomic(
+    path: &Path,
+    contents: impl AsRef<[u8]>,
+    backup_suffix: Option<&str>,
+) -> io::Result<()> {
+    let target = match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_symlink() => fs::canonicalize(path)?,
+        _ => path.to_path_buf(),
+    };
+    let original = fs::metadata(&target).ok();
+
+    let temp = temp_path(&target);
+    let result = write_temp(&temp, contents.as_ref(), original.as_ref()).and_then(|()| {
+        if let Some(suffix) = backup_suffix.filter(|_| original.is_some()) {
+            backup(&target, suffix)?;
+        }
+        fs::rename(&temp, &target)
+    });
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result
+}
+
+/// Hidden temporary file in the same directory, so the rename stays on one filesystem
+fn temp_path(target: &Path) -> PathBuf {
+    let name = target
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    target.with_file_name(format!(".{name}.yl-{}.tmp", std::process::id()))
+}
+
+/// Write and flush the temporary file with the original's permissions and ownership,
+/// set before any content is written
+fn write_temp(temp: &Path, contents: &[u8], original: Option<&fs::Metadata>) -> io::Result<()> {
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(temp)?;
+
+    if let Some(metadata) = original {
+        file.set_permissions(metadata.permissions())?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            // Only privileged processes may give a file away; others keep their own ownership
+            let _ = std::os::unix::fs::fchown(&file, Some(metadata.uid()), Some(metadata.gid()));
+        }
+    }
+
+    file.write_all(contents)?;
+    file.sync_all()
+}
+
+/// Keep the current file as `<target><suffix>`, replacing an older backup. A suffix
+/// that leaves the path unchanged is refused rather than deleting the file.
+fn backup(target: &Path, suffix: &str) -> io::Result<()> {
+    let mut backup = target.as_os_str().to_owned();
+    backup.push(suffix);
+    let backup = PathBuf::from(backup);
+    if backup == target {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "backup of {} would replace the file itself",
+                target.display()
+            ),
+        ));
+    }
+
+    match fs::remove_file(&backup) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+        _ => {}
+    }
+    // A hard link keeps the original file itself, attributes and all
+    if fs::hard_link(target, &backup).is_err() {
+        fs::copy(target, &backup)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_atomic_replaces_and_creates() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("a.yaml");
+
+        write_atomic(&path, "a: 1\n", None).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a: 1\n");
+        write_atomic(&path, "a: 2\n", None).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a: 2\n");
+
+        // No temporary files are left behind
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_write_atomic_backup() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("a.yaml");
+
+        // Nothing to back up for a new file
+        write_atomic(&path, "a: 1\n", Some(".bak")).unwrap();
+        assert!(!dir.path().join("a.yaml.bak").exists());
+
+        write_atomic(&path, "a: 2\n", Some(".bak")).unwrap();
+        write_atomic(&path, "a: 3\n", Some(".bak")).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a: 3\n");
+        assert_eq!(
+            fs::read_to_string(dir.path().join("a.yaml.bak")).unwrap(),
+            "a: 2\n"
+        );
+
+        // An empty suffix would name the file itself, which is left alone
+        assert!(write_atomic(&path, "a: 4\n", Some("")).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a: 3\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_atomic_keeps_mode_and_symlinks() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("a.yaml");
+        fs::write(&path, "a: 1\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o640)).unwrap();
+        let link = dir.path().join("link.yaml");
+        std::os::unix::fs::symlink(&path, &link).unwrap();
+
+        write_atomic(&link, "a: 2\n", None).unwrap();
+        assert!(
+            fs::symlink_metadata(&link)
+                .unwrap()
+                .file_type()
+                .is_symlink()
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a: 2\n");
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o640);
+    }
+}
@@ -0,0 +1,91 @@
+//! Property tests: auto-fixes only change whitespace, never what the YAML means
+
+use proptest::prelude::*;
+use serde_yaml::{Mapping, Value};
+use yl_core::fixes::semantic_differences;
+use yl_core::{Config, FixEngine, Linter};
+
+/// Scalars, including strings that serialize as block scalars or need quoting
+fn scalar() -> impl Strategy<Value = Value> {
+    prop_oneof![
+        any::<bool>().prop_map(Value::Bool),
+        any::<i64>().prop_map(|n| Value::Number(n.into())),
+        "[a-z]{1,8}".prop_map(Value::String),
+        "[a-z ]{0,6}( {1,3}\n)?[a-z]{0,6}\n{0,3}".prop_map(Value::String),
+        Just(Value::String("yes".to_string())),
+        Just(Value::Null),
+    ]
+}
+
+fn value() -> impl Strategy<Value = Value> {
+    scalar().prop_recursive(3, 24, 4, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..4).prop_map(Value::Sequence),
+            prop::collection::btree_map("[a-z]{1,6}", inner, 0..4).prop_map(|entries| {
+                Value::Mapping(
+                    entries
+                        .into_iter()
+                        .map(|(key, value)| (Value::String(key), value))
+                        .collect::<Mapping>(),
+                )
+            }),
+        ]
+    })
+}
+
+/// Serialized YAML with whitespace problems added between its lines
+fn messy_yaml() -> impl Strategy<Value = String> {
+    (
+        value(),
+        prop::collection::vec((0..4usize, 0..5usize), 1..40),
+        any::<bool>(),
+    )
+        .prop_map(|(value, noise, trim_end)| {
+            let yaml = serde_yaml::to_string(&value).expect("value serializes");
+            let mut content = String::new();
+            for (index, line) in yaml.lines().enumerate() {
+                let (spaces, blank_lines) = noise[index % noise.len()];
+                content.push_str(line);
+                content.push_str(&" ".repeat(spaces));
+                content.push('\n');
+                content.push_str(&"\n".repeat(blank_lines));
+            }
+            if trim_end {
+                content = content.trim_end_matches('\n').to_string();
+            }
+            content
+        })
+}
+
+/// Linter with every whitespace rule that has an auto-fix enabled
+fn linter() -> Linter {
+    let mut config = Config::default();
+    for rule in ["trailing-spaces", "empty-lines", "new-line-at-end-of-file"] {
+        if let Some(rule_config) = config.rules.get_mut(rule) {
+            rule_config.enabled = true;
+        }
+    }
+    Linter::new(config).with_config_discovery(false)
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(256))]
+
+    #[test]
+    fn fixes_preserve_semantics(content in messy_yaml()) {
+        // Only inputs that are valid YAML have a meaning to preserve
+        prop_assume!(serde_yaml::from_str::<Value>(&content).is_ok());
+
+        let fixed = linter()
+            .fix_content("prop.yaml", &content, &FixEngine::new())
+            .expect("fixing succeeds")
+            .content;
+
+        let differences = semantic_differences(&content, &fixed).expect("fixed content parses");
+        prop_assert!(
+            differences.is_empty(),
+            "fixes changed the meaning:\n{}\n--- before\n{content}\n--- after\n{fixed}",
+            differences.join("\n")
+        );
+    }
+}
@@ -0,0 +1,71 @@
+#ifndef YL_H
+#define YL_H
+
+/* Generated with cbindgen from crates/yl-ffi. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Severity of a problem
+typedef enum YlLevel {
+  YL_LEVEL_INFO = 0,
+  YL_LEVEL_WARNING = 1,
+  YL_LEVEL_ERROR = 2,
+} YlLevel;
+
+// A problem reported by a rule
+typedef struct YlProblem {
+  // 1-based line number
+  uintptr_t line;
+  // 1-based column number
+  uintptr_t column;
+  enum YlLevel level;
+  // Identifier of the rule that reported the problem
+  char *rule;
+  char *message;
+} YlProblem;
+
+// Result of linting: an array of problems, or an error message
+typedef struct YlResults {
+  // Problems found, or NULL when there are none or linting failed
+  struct YlProblem *problems;
+  // Number of entries in `problems`
+  uintptr_t len;
+  // Error message when linting failed, otherwise NULL
+  char *error;
+} YlResults;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Lint YAML content.
+//
+// `config` is the text of a yl configuration file, or NULL for the defaults. `path` is
+// used for nested config and `.editorconfig` lookup, or NULL; it does not need to exist.
+// The returned results are never NULL and must be released with [`yl_free_results`].
+//
+// # Safety
+//
+// `content` must be a valid NUL-terminated string. `config` and `path` must each be
+// NULL or a valid NUL-terminated string.
+struct YlResults *yl_lint_content(const char *content, const char *config, const char *path);
+
+// Release results returned by [`yl_lint_content`].
+//
+// # Safety
+//
+// `results` must be NULL or a pointer returned by [`yl_lint_content`] that has not
+// been freed yet.
+void yl_free_results(struct YlResults *results);
+
+// Version of the library as a static NUL-terminated string
+const char *yl_version(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* YL_H */
@@ -0,0 +1,280 @@
+//! C API for embedding yl in editors and tools written in other languages.
+//!
+//! Call [`yl_lint_content`] to lint a buffer and release the returned results with
+//! [`yl_free_results`]. All strings are NUL-terminated UTF-8. The header is generated
+//! with cbindgen into `include/yl.h`.
+
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use yl_core::{Config, Level, Linter, Problem};
+
+/// Severity of a problem
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum YlLevel {
+    Info = 0,
+    Warning = 1,
+    Error = 2,
+}
+
+/// A problem reported by a rule
+#[repr(C)]
+pub struct YlProblem {
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column number
+    pub column: usize,
+    pub level: YlLevel,
+    /// Identifier of the rule that reported the problem
+    pub rule: *mut c_char,
+    pub message: *mut c_char,
+}
+
+/// Result of linting: an array of problems, or an error message
+#[repr(C)]
+pub struct YlResults {
+    /// Problems found, or NULL when there are none or linting failed
+    pub problems: *mut YlProblem,
+    /// Number of entries in `problems`
+    pub len: usize,
+    /// Error message when linting failed, otherwise NULL
+    pub error: *mut c_char,
+}
+
+/// Lint YAML content.
+///
+/// `config` is the text of a yl configuration file, or NULL for the defaults. `path` is
+/// used for nested config and `.editorconfig` lookup, or NULL; it does not need to exist.
+/// The returned results are never NULL and must be released with [`yl_free_results`].
+///
+/// # Safety
+///
+/// `content` must be a valid NUL-terminated string. `config` and `path` must each be
+/// NULL or a valid NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn yl_lint_content(
+    content: *const c_char,
+    config: *const c_char,
+    path: *const c_char,
+) -> *mut YlResults {
+    // SAFETY: the caller guarantees the pointers are NULL or valid C strings
+    let (content, config, path) = unsafe { (to_str(content), to_str(config), to_str(path)) };
+
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+        let content = content.ok_or("content must be a valid UTF-8 string")??;
+        lint(
+            content,
+            config.transpose()?,
+            path.transpose()?.unwrap_or("stdin.yaml"),
+        )
+    }));
+
+    let results = match outcome {
+        Ok(Ok(problems)) => YlResults::from_problems(problems),
+        Ok(Err(message)) => YlResults::from_error(&message),
+        Err(_) => YlResults::from_error("internal error while linting"),
+    };
+
+    Box::into_raw(Box::new(results))
+}
+
+/// Release results returned by [`yl_lint_content`].
+///
+/// # Safety
+///
+/// `results` must be NULL or a pointer returned by [`yl_lint_content`] that has not
+/// been freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn yl_free_results(results: *mut YlResults) {
+    if results.is_null() {
+        return;
+    }
+
+    // SAFETY: the pointer came from Box::into_raw in yl_lint_content
+    let results = unsafe { Box::from_raw(results) };
+
+    if !results.problems.is_null() {
+        // SAFETY: problems and len came from a boxed slice in YlResults::from_problems
+        let problems =
+            unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(results.problems, results.len)) };
+        for problem in problems.iter() {
+            // SAFETY: the strings came from CString::into_raw
+            unsafe {
+                drop(CString::from_raw(problem.rule));
+                drop(CString::from_raw(problem.message));
+            }
+        }
+    }
+
+    if !results.error.is_null() {
+        // SAFETY: the string came from CString::into_raw
+        unsafe { drop(CString::from_raw(results.error)) };
+    }
+}
+
+/// Version of the library as a static NUL-terminated string
+#[unsafe(no_mangle)]
+pub extern "C" fn yl_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// Lint content with the given configuration text (or the defaults)
+fn lint(content: &str, config: Option<&str>, path: &str) -> Result<Vec<Problem>, String> {
+    let config = match config {
+        Some(yaml) => serde_yaml::from_str::<Config>(yaml)
+            .map_err(|e| format!("invalid yl configuration: {e}"))?,
+        None => Config::default(),
+    };
+
+    Linter::new(config)
+        .lint_content(path, content)
+        .map_err(|e| format!("{e:#}"))
+}
+
+/// Borrow a C string, returning `None` for NULL and an error for invalid UTF-8
+///
+/// # Safety
+///
+/// `s` must be NULL or a valid NUL-terminated string that outlives the returned reference.
+unsafe fn to_str<'a>(s: *const c_char) -> Option<Result<&'a str, String>> {
+    if s.is_null() {
+        return None;
+    }
+
+    // SAFETY: guaranteed by the caller
+    let s = unsafe { CStr::from_ptr(s) };
+    Some(
+        s.to_str()
+            .map_err(|_| "strings must be valid UTF-8".to_string()),
+    )
+}
+
+/// Convert a Rust string to an owned C string, dropping interior NUL bytes
+fn into_c_string(s: &str) -> *mut c_char {
+    CString::new(s.replace('\0', ""))
+        .expect("NUL bytes were removed")
+        .into_raw()
+}
+
+impl YlResults {
+    fn from_problems(problems: Vec<Problem>) -> Self {
+        if problems.is_empty() {
+            return Self {
+                problems: ptr::null_mut(),
+                len: 0,
+                error: ptr::null_mut(),
+            };
+        }
+
+        let problems: Box<[YlProblem]> = problems
+            .into_iter()
+            .map(|problem| YlProblem {
+                line: problem.line,
+                column: problem.column,
+                level: problem.level.into(),
+                rule: into_c_string(&problem.rule),
+                message: into_c_string(&problem.message),
+            })
+            .collect();
+        let len = problems.len();
+
+        Self {
+            problems: Box::into_raw(problems).cast(),
+            len,
+            error: ptr::null_mut(),
+        }
+    }
+
+    fn from_error(message: &str) -> Self {
+        Self {
+            problems: ptr::null_mut(),
+            len: 0,
+            error: into_c_string(message),
+        }
+    }
+}
+
+impl From<Level> for YlLevel {
+    fn from(level: Level) -> Self {
+        match level {
+            Level::Info => YlLevel::Info,
+            Level::Warning => YlLevel::Warning,
+            Level::Error => YlLevel::Error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lint_c(content: &str, config: Option<&str>) -> *mut YlResults {
+        let content = CString::new(content).unwrap();
+        let config = config.map(|c| CString::new(c).unwrap());
+        let config_ptr = config.as_ref().map_or(ptr::null(), |c| c.as_ptr());
+
+        unsafe { yl_lint_content(content.as_ptr(), config_ptr, ptr::null()) }
+    }
+
+    #[test]
+    fn test_lint_content_problems() {
+        let results = lint_c("key: value   \n", None);
+
+        unsafe {
+            assert!((*results).error.is_null());
+            assert_eq!((*results).len, 1);
+
+            let problem = &*(*results).problems;
+            assert_eq!(problem.line, 1);
+            assert_eq!(problem.level, YlLevel::Error);
+            assert_eq!(
+                CStr::from_ptr(problem.rule).to_str().unwrap(),
+                "trailing-spaces"
+            );
+
+            yl_free_results(results);
+        }
+    }
+
+    #[test]
+    fn test_lint_content_clean() {
+        let results = lint_c("key: value\n", None);
+
+        unsafe {
+            assert!((*results).problems.is_null());
+            assert_eq!((*results).len, 0);
+            assert!((*results).error.is_null());
+            yl_free_results(results);
+        }
+    }
+
+    #[test]
+    fn test_invalid_config_sets_error() {
+        let results = lint_c("key: value\n", Some("rules: [not, a, mapping]"));
+
+        unsafe {
+            assert_eq!((*results).len, 0);
+            let error = CStr::from_ptr((*results).error).to_str().unwrap();
+            assert!(error.starts_with("invalid yl configuration"));
+            yl_free_results(results);
+        }
+    }
+
+    #[test]
+    fn test_null_content_sets_error() {
+        unsafe {
+            let results = yl_lint_content(ptr::null(), ptr::null(), ptr::null());
+            assert!(!(*results).error.is_null());
+            yl_free_results(results);
+
+            yl_free_results(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_version() {
+        let version = unsafe { CStr::from_ptr(yl_version()) };
+        assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+    }
+}
@@ -0,0 +1,35 @@
+/** A problem reported by a yl rule */
+export interface Problem {
+  /** 1-based line number */
+  line: number
+  /** 1-based column number */
+  column: number
+  /** Severity: "error", "warning" or "info" */
+  level: 'error' | 'warning' | 'info'
+  /** Identifier of the rule that reported the problem */
+  rule: string
+  message: string
+}
+
+/** Problems found in one file */
+export interface FileResult {
+  path: string
+  problems: Problem[]
+}
+
+/**
+ * Lint YAML content.
+ *
+ * @param content YAML text to lint
+ * @param config Text of a yl configuration file; the default configuration is used when omitted
+ * @param path Path used for nested config and `.editorconfig` lookup; it does not need to exist
+ */
+export function lintContent(content: string, config?: string | undefined | null, path?: string | undefined | null): Problem[]
+
+/**
+ * Lint files and directories, walking directories for YAML files.
+ *
+ * @param paths Files or directories to lint
+ * @param config Text of a yl configuration file; the default configuration is used when omitted
+ */
+export function lintFiles(paths: string[], config?: string | undefined | null): FileResult[]
@@ -0,0 +1,7 @@
+const { platform, arch } = process
+
+// Load the prebuilt binding produced by `napi build --platform`
+const binding = require(`./yl.${platform}-${arch}.node`)
+
+module.exports.lintContent = binding.lintContent
+module.exports.lintFiles = binding.lintFiles
@@ -0,0 +1,85 @@
+//! Node.js bindings for yl, exposing `lintContent` and `lintFiles`.
+//! TypeScript declarations are in `index.d.ts`.
+
+use napi::{Error, Result};
+use napi_derive::napi;
+use yl_core::{Config, Linter};
+
+/// A problem reported by a yl rule
+#[napi(object)]
+pub struct Problem {
+    pub line: u32,
+    pub column: u32,
+    pub level: String,
+    pub rule: String,
+    pub message: String,
+}
+
+/// Problems found in one file
+#[napi(object)]
+pub struct FileResult {
+    pub path: String,
+    pub problems: Vec<Problem>,
+}
+
+/// Lint YAML content with the given configuration text (or the defaults)
+#[napi]
+pub fn lint_content(
+    content: String,
+    config: Option<String>,
+    path: Option<String>,
+) -> Result<Vec<Problem>> {
+    let linter = Linter::new(parse_config(config)?);
+    let path = path.unwrap_or_else(|| "stdin.yaml".to_string());
+
+    let problems = linter.lint_content(&path, &content).map_err(to_napi_error)?;
+    Ok(problems.into_iter().map(Problem::from).collect())
+}
+
+/// Lint files and directories with the given configuration text (or the defaults)
+#[napi]
+pub fn lint_files(paths: Vec<String>, config: Option<String>) -> Result<Vec<FileResult>> {
+    let linter = Linter::new(parse_config(config)?);
+
+    let results = linter.lint_paths(&paths).map_err(to_napi_error)?;
+    Ok(results
+        .into_iter()
+        .map(|(path, problems)| FileResult {
+            path: path.display().to_string(),
+            problems: problems.into_iter().map(Problem::from).collect(),
+        })
+        .collect())
+}
+
+impl From<yl_core::Problem> for Problem {
+    fn from(problem: yl_core::Problem) -> Self {
+        Self {
+            line: problem.line as u32,
+            column: problem.column as u32,
+            level: problem.level.to_string(),
+            rule: problem.rule,
+            message: problem.message,
+        }
+    }
+}
+
+/// Parse configuration text, falling back to the default configuration
+fn parse_config(config: Option<String>) -> Result<Config> {
+    let Some(yaml) = config else {
+        return Ok(Config::default());
+    };
+
+    let mut config: Config = serde_yaml::from_str(&yaml)
+        .map_err(|e| Error::from_reason(format!("invalid yl configuration: {e}")))?;
+
+    // Like a root config file, a configuration without yaml-files would match nothing
+    if config.yaml_files.is_empty() {
+        config.yaml_files = Config::default().yaml_files;
+    }
+
+    Ok(config)
+}
+
+fn to_napi_error(error: eyre::Report) -> Error {
+    Error::from_reason(format!("{error:#}"))
+}
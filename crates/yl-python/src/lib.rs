@@ -0,0 +1,58 @@
+//! Python bindings for yl.
+//!
+//! `yl.lint(content, config=None, filepath="stdin.yaml")` returns a list of dicts shaped
+//! like yamllint's `LintProblem` (`line`, `column`, `desc`, `rule`, `level`), so tooling
+//! built around `yamllint.linter.run` can switch without shelling out.
+
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use yl_core::{Config, Linter, Problem};
+
+/// Lint YAML content.
+///
+/// `config` is the text of a yl configuration file; the default configuration is used
+/// when it is omitted. `filepath` is used for nested config, `.editorconfig` lookup and
+/// template detection, and does not need to exist.
+#[pyfunction]
+#[pyo3(signature = (content, config=None, filepath="stdin.yaml"))]
+fn lint<'py>(
+    py: Python<'py>,
+    content: &str,
+    config: Option<&str>,
+    filepath: &str,
+) -> PyResult<Vec<Bound<'py, PyDict>>> {
+    let config = match config {
+        Some(yaml) => serde_yaml::from_str::<Config>(yaml)
+            .map_err(|e| PyValueError::new_err(format!("invalid yl configuration: {e}")))?,
+        None => Config::default(),
+    };
+
+    let linter = Linter::new(config);
+    let problems = py
+        .allow_threads(|| linter.lint_content(filepath, content))
+        .map_err(|e| PyRuntimeError::new_err(format!("{e:#}")))?;
+
+    problems
+        .iter()
+        .map(|problem| problem_to_dict(py, problem))
+        .collect()
+}
+
+/// Convert a problem to a yamllint `LintProblem`-shaped dict
+fn problem_to_dict<'py>(py: Python<'py>, problem: &Problem) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new_bound(py);
+    dict.set_item("line", problem.line)?;
+    dict.set_item("column", problem.column)?;
+    dict.set_item("desc", &problem.message)?;
+    dict.set_item("rule", &problem.rule)?;
+    dict.set_item("level", problem.level.to_string())?;
+    Ok(dict)
+}
+
+#[pymodule]
+fn yl(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(lint, m)?)?;
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    Ok(())
+}
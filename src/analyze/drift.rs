@@ -0,0 +1,120 @@
+use super::indentation::{FileIndentation, IndentStyle, IndentationReport, SequenceStyle};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+/// Prevailing style of a set of files
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StyleProfile {
+    /// Number of files in the set
+    pub files: usize,
+    pub style: Option<IndentStyle>,
+    pub sequences: Option<SequenceStyle>,
+}
+
+impl StyleProfile {
+    fn of(report: &IndentationReport) -> Self {
+        Self {
+            files: report.files.len(),
+            style: report.majority(),
+            sequences: report.sequence_majority(),
+        }
+    }
+}
+
+/// Style of recently modified files compared with the style of the whole repository
+#[derive(Debug, Serialize)]
+pub struct DriftReport {
+    pub repository: StyleProfile,
+    pub recent: StyleProfile,
+    /// Recent files whose indentation or sequence style differs from the repository's
+    pub diverging: Vec<FileIndentation>,
+}
+
+impl DriftReport {
+    /// Compare the files of `report` in `recent` (canonical paths) against all of them
+    pub fn compare(report: &IndentationReport, recent: &BTreeSet<PathBuf>) -> Self {
+        let recent = IndentationReport {
+            files: report
+                .files
+                .iter()
+                .filter(|file| {
+                    file.path
+                        .canonicalize()
+                        .is_ok_and(|canonical| recent.contains(&canonical))
+                })
+                .cloned()
+                .collect(),
+        };
+        let repository = StyleProfile::of(report);
+        let diverging = recent
+            .files
+            .iter()
+            .filter(|file| {
+                (file.style.is_some() && file.style != repository.style)
+                    || (file.sequences.is_some() && file.sequences != repository.sequences)
+            })
+            .cloned()
+            .collect();
+
+        Self {
+            recent: StyleProfile::of(&recent),
+            repository,
+            diverging,
+        }
+    }
+
+    /// Whether the recent files as a whole moved away from the repository's style
+    pub fn has_drifted(&self) -> bool {
+        differs(self.recent.style, self.repository.style)
+            || differs(self.recent.sequences, self.repository.sequences)
+    }
+}
+
+/// Whether both styles are known and differ
+fn differs<T: PartialEq>(recent: Option<T>, repository: Option<T>) -> bool {
+    recent.is_some() && repository.is_some() && recent != repository
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_compare() {
+        let temp_dir = TempDir::new().unwrap();
+        let write = |name: &str, content: &str| {
+            let path = temp_dir.path().join(name);
+            fs::write(&path, content).unwrap();
+            path
+        };
+        let files = vec![
+            write("a.yaml", "a:\n  b: 1\n"),
+            write("b.yaml", "a:\n  b: 1\n"),
+            write("c.yaml", "a:\n  - 1\n"),
+            write("new.yaml", "a:\n    b: 1\n"),
+            write("newer.yaml", "a:\n    b: 1\n"),
+        ];
+        let report = IndentationReport::scan(&files).unwrap();
+
+        let recent: BTreeSet<_> = [&files[2], &files[3], &files[4]]
+            .iter()
+            .map(|path| path.canonicalize().unwrap())
+            .collect();
+        let drift = DriftReport::compare(&report, &recent);
+        assert_eq!(drift.repository.files, 5);
+        assert_eq!(drift.repository.style, Some(IndentStyle::Spaces(2)));
+        assert_eq!(drift.recent.files, 3);
+        assert_eq!(drift.recent.style, Some(IndentStyle::Spaces(4)));
+        assert!(drift.has_drifted());
+        let diverging: Vec<_> = drift.diverging.iter().map(|file| &file.path).collect();
+        assert_eq!(diverging, vec![&files[3], &files[4]]);
+
+        let drift =
+            DriftReport::compare(&report, &BTreeSet::from([files[0].canonicalize().unwrap()]));
+        assert!(!drift.has_drifted());
+        assert!(drift.diverging.is_empty());
+    }
+}
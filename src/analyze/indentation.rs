@@ -0,0 +1,252 @@
+use crate::linter::LintContext;
+use eyre::{Context, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Indentation style detected in a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IndentStyle {
+    /// Nested lines are indented by this many spaces per level
+    Spaces(usize),
+    /// Lines are indented with tab characters
+    Tabs,
+}
+
+impl fmt::Display for IndentStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Spaces(1) => f.pad("1 space"),
+            Self::Spaces(spaces) => f.pad(&format!("{spaces} spaces")),
+            Self::Tabs => f.pad("tabs"),
+        }
+    }
+}
+
+/// Whether sequences nested under a key are indented relative to the key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SequenceStyle {
+    /// `key:` followed by `  - item`
+    Indented,
+    /// `key:` followed by `- item`
+    Flush,
+}
+
+impl fmt::Display for SequenceStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Indented => f.pad("indented sequences"),
+            Self::Flush => f.pad("non-indented sequences"),
+        }
+    }
+}
+
+/// Indentation detected in a single file; `None` where the file has nothing nested
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileIndentation {
+    pub path: PathBuf,
+    pub style: Option<IndentStyle>,
+    pub sequences: Option<SequenceStyle>,
+}
+
+impl FileIndentation {
+    /// Detect the indentation of a file's content
+    pub fn detect(path: &Path, content: &str) -> Self {
+        let context = LintContext::new(path, content);
+        let block_scalars = context.block_scalar_spans();
+
+        let mut steps: BTreeMap<usize, usize> = BTreeMap::new();
+        let mut sequences: BTreeMap<SequenceStyle, usize> = BTreeMap::new();
+        let mut tabs = 0;
+        // Indentation of the previous significant line, where its content starts, and
+        // whether it opened a nested block (`key:`)
+        let mut previous: Option<(usize, usize, bool)> = None;
+
+        for (line_no, line) in context.lines() {
+            let trimmed = line.trim_start();
+            if trimmed.is_empty()
+                || trimmed.starts_with('#')
+                || block_scalars.iter().any(|span| span.contains(&line_no))
+            {
+                continue;
+            }
+
+            let leading = &line[..line.len() - trimmed.len()];
+            if leading.contains('\t') {
+                tabs += 1;
+                continue;
+            }
+
+            let indent = leading.len();
+            let item = trimmed.starts_with("- ") || trimmed == "-";
+            if let Some((parent_indent, content_indent, opens_block)) = previous {
+                if item && opens_block {
+                    let style = if indent > parent_indent {
+                        SequenceStyle::Indented
+                    } else {
+                        SequenceStyle::Flush
+                    };
+                    *sequences.entry(style).or_default() += 1;
+                }
+                // Keys continuing the mapping of a `- key: value` item are not a new level
+                if indent > parent_indent && indent != content_indent {
+                    *steps.entry(indent - parent_indent).or_default() += 1;
+                }
+            }
+
+            let content_indent = if item {
+                indent + trimmed.len() - trimmed[1..].trim_start().len()
+            } else {
+                indent
+            };
+            let opens_block = trimmed.trim_end().ends_with(':');
+            previous = Some((indent, content_indent, opens_block));
+        }
+
+        let style = if tabs > 0 {
+            Some(IndentStyle::Tabs)
+        } else {
+            most_common(&steps).map(IndentStyle::Spaces)
+        };
+
+        Self {
+            path: path.to_path_buf(),
+            style,
+            sequences: most_common(&sequences),
+        }
+    }
+}
+
+/// Distribution of indentation styles across a set of files
+#[derive(Debug, Default, Serialize)]
+pub struct IndentationReport {
+    pub files: Vec<FileIndentation>,
+}
+
+impl IndentationReport {
+    /// Scan the given files for their indentation
+    pub fn scan(files: &[PathBuf]) -> Result<Self> {
+        let mut report = Self::default();
+
+        for file in files {
+            let content = std::fs::read_to_string(file)
+                .with_context(|| format!("Failed to read file: {}", file.display()))?;
+            report.files.push(FileIndentation::detect(file, &content));
+        }
+
+        Ok(report)
+    }
+
+    /// Number of files per indentation style
+    pub fn styles(&self) -> BTreeMap<IndentStyle, usize> {
+        let mut styles = BTreeMap::new();
+        for style in self.files.iter().filter_map(|file| file.style) {
+            *styles.entry(style).or_default() += 1;
+        }
+        styles
+    }
+
+    /// Number of files per sequence style
+    pub fn sequence_styles(&self) -> BTreeMap<SequenceStyle, usize> {
+        let mut styles = BTreeMap::new();
+        for style in self.files.iter().filter_map(|file| file.sequences) {
+            *styles.entry(style).or_default() += 1;
+        }
+        styles
+    }
+
+    /// The indentation style used by the most files
+    pub fn majority(&self) -> Option<IndentStyle> {
+        most_common(&self.styles())
+    }
+
+    /// The sequence style used by the most files
+    pub fn sequence_majority(&self) -> Option<SequenceStyle> {
+        most_common(&self.sequence_styles())
+    }
+
+    /// Files whose indentation or sequence style differs from the majority
+    pub fn inconsistent(&self) -> Vec<&FileIndentation> {
+        let (majority, sequence_majority) = (self.majority(), self.sequence_majority());
+        self.files
+            .iter()
+            .filter(|file| {
+                (file.style.is_some() && file.style != majority)
+                    || (file.sequences.is_some() && file.sequences != sequence_majority)
+            })
+            .collect()
+    }
+}
+
+/// The key with the highest count; ties go to the smallest key
+fn most_common<K: Copy + Ord>(counts: &BTreeMap<K, usize>) -> Option<K> {
+    counts
+        .iter()
+        .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))
+        .map(|(key, _)| *key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(content: &str) -> FileIndentation {
+        FileIndentation::detect(Path::new("test.yaml"), content)
+    }
+
+    #[test]
+    fn test_detect_spaces() {
+        let file = detect("a:\n  b:\n    c: 1\n  d: 2\n");
+        assert_eq!(file.style, Some(IndentStyle::Spaces(2)));
+
+        let file = detect("a:\n    b:\n        c: 1\n");
+        assert_eq!(file.style, Some(IndentStyle::Spaces(4)));
+
+        let file = detect("a: 1\nb: 2\n");
+        assert_eq!(file.style, None);
+        assert_eq!(file.sequences, None);
+    }
+
+    #[test]
+    fn test_detect_tabs() {
+        let file = detect("a:\n\tb: 1\n");
+        assert_eq!(file.style, Some(IndentStyle::Tabs));
+    }
+
+    #[test]
+    fn test_detect_sequences() {
+        let file = detect("items:\n  - name: a\n    value: 1\n  - name: b\n");
+        assert_eq!(file.style, Some(IndentStyle::Spaces(2)));
+        assert_eq!(file.sequences, Some(SequenceStyle::Indented));
+
+        let file = detect("items:\n- name: a\n  value: 1\nother:\n  key: 1\n");
+        assert_eq!(file.style, Some(IndentStyle::Spaces(2)));
+        assert_eq!(file.sequences, Some(SequenceStyle::Flush));
+    }
+
+    #[test]
+    fn test_detect_skips_block_scalars() {
+        let file = detect("a:\n  script: |\n        echo one\n          echo two\n  b: 1\n");
+        assert_eq!(file.style, Some(IndentStyle::Spaces(2)));
+    }
+
+    #[test]
+    fn test_report_inconsistent() {
+        let report = IndentationReport {
+            files: vec![
+                FileIndentation::detect(Path::new("a.yaml"), "a:\n  b: 1\n"),
+                FileIndentation::detect(Path::new("b.yaml"), "a:\n  b: 1\n"),
+                FileIndentation::detect(Path::new("c.yaml"), "a:\n    b: 1\n"),
+                FileIndentation::detect(Path::new("d.yaml"), "a: 1\n"),
+            ],
+        };
+
+        assert_eq!(report.majority(), Some(IndentStyle::Spaces(2)));
+        assert_eq!(report.styles().get(&IndentStyle::Spaces(4)), Some(&1));
+        let inconsistent: Vec<_> = report.inconsistent().iter().map(|f| &f.path).collect();
+        assert_eq!(inconsistent, vec![Path::new("c.yaml")]);
+    }
+}
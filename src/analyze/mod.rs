@@ -0,0 +1,5 @@
+pub mod drift;
+pub mod indentation;
+
+pub use drift::DriftReport;
+pub use indentation::IndentationReport;
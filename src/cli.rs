@@ -1,4 +1,5 @@
-use clap::{Parser, Subcommand, ValueEnum};
+use crate::output::store::StoreTarget;
+use clap::{ArgAction, ArgGroup, Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 
 /// Output format for linting results
@@ -6,8 +7,16 @@ use std::path::PathBuf;
 pub enum OutputFormat {
     /// Human-readable output with colors
     Human,
+    /// One `path:line:col: level: message [rule]` line per problem, for editors and grep
+    Compact,
     /// JSON format for machine processing
     Json,
+    /// One JSON object per problem, written as each file finishes
+    Ndjson,
+    /// Reviewdog Diagnostic Format, with fixes as suggestions
+    Rdjson,
+    /// SARIF 2.1.0, for code scanning services
+    Sarif,
 }
 
 impl Default for OutputFormat {
@@ -16,13 +25,41 @@ impl Default for OutputFormat {
     }
 }
 
+/// Order in which per-file results are written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputOrder {
+    /// Streaming formats write each file as soon as it is finished
+    #[default]
+    Parallel,
+    /// All results are written at the end, sorted by path
+    Sorted,
+}
+
+/// What results and statistics are grouped by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GroupBy {
+    /// The configured `projects` of a monorepo
+    Project,
+}
+
+/// Format of log messages written to stderr
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// One human-readable line per message
+    #[default]
+    Text,
+    /// One JSON object per message
+    Json,
+}
+
 /// Command-line interface for the YL YAML linter
-#[derive(Parser, Default)]
+#[derive(Parser, Default, Clone)]
 #[command(
     name = "yl",
     about = "A YAML linter written in Rust",
     version = env!("GIT_DESCRIBE"),
-    after_help = "For more information, see: https://github.com/scottidler/yl"
+    after_help = "For more information, see: https://github.com/scottidler/yl",
+    group(ArgGroup::new("changes").multiple(true).args(["changed_since", "base", "staged"]))
 )]
 pub struct Cli {
     #[command(subcommand)]
@@ -36,6 +73,14 @@ pub struct Cli {
     #[arg(short, long, help = "Path to configuration file")]
     pub config: Option<PathBuf>,
 
+    /// Profile of the configuration file to apply
+    #[arg(
+        long,
+        value_name = "NAME",
+        help = "Apply the named profile of the configuration file's `profiles` over its settings"
+    )]
+    pub profile: Option<String>,
+
     /// Output format
     #[arg(
         short = 'f',
@@ -50,12 +95,20 @@ pub struct Cli {
     #[arg(long, help = "Show only errors, suppress warnings")]
     pub errors_only: bool,
 
+    /// Show only problems that `yl fix` can fix
+    #[arg(long, help = "Show only problems that `yl fix` can fix automatically")]
+    pub only_fixable: bool,
+
+    /// List problems suppressed by directives, dimmed, in human output
+    #[arg(long, help = "List problems suppressed by directives (human output)")]
+    pub show_suppressed: bool,
+
     /// Disable specific rules
-    #[arg(long, help = "Disable specific rules (comma-separated)")]
+    #[arg(short, long, help = "Disable specific rules (comma-separated)")]
     pub disable: Vec<String>,
 
     /// Enable specific rules
-    #[arg(long, help = "Enable specific rules (comma-separated)")]
+    #[arg(short, long, help = "Enable specific rules (comma-separated)")]
     pub enable: Vec<String>,
 
     /// Set rule parameters (format: rule.param=value)
@@ -70,16 +123,244 @@ pub struct Cli {
     #[arg(long, help = "Show effective configuration and exit")]
     pub show_config: bool,
 
-    /// Enable verbose output
-    #[arg(short, long, help = "Enable verbose output")]
-    pub verbose: bool,
+    /// Only lint files changed since a git reference
+    #[arg(
+        long,
+        value_name = "GIT_REF",
+        help = "Only lint files changed since the given git reference (per `git diff --name-only`)"
+    )]
+    pub changed_since: Option<String>,
+
+    /// Only lint files changed since the branch forked from a git reference
+    #[arg(
+        long,
+        value_name = "GIT_REF",
+        conflicts_with = "changed_since",
+        help = "Only lint files changed since the merge-base with the given git reference, e.g. origin/main"
+    )]
+    pub base: Option<String>,
+
+    /// Only lint files with staged changes
+    #[arg(
+        long,
+        conflicts_with_all = ["changed_since", "base"],
+        help = "Only lint files with changes staged in the git index"
+    )]
+    pub staged: bool,
+
+    /// Report only problems on lines the change adds
+    #[arg(
+        long,
+        requires = "changes",
+        help = "With --changed-since, --base or --staged, report only problems on added lines"
+    )]
+    pub only_added_lines: bool,
+
+    /// Apply auto-fixes before reporting problems
+    #[arg(
+        long,
+        help = "Apply auto-fixes to the files, then report only the problems that remain"
+    )]
+    pub fix: bool,
+
+    /// Number of worker threads
+    #[arg(
+        short,
+        long,
+        value_name = "N",
+        help = "Number of files to lint in parallel (default: one per CPU)"
+    )]
+    pub jobs: Option<usize>,
+
+    /// Size from which files are memory-mapped; only safe for files that nothing changes
+    /// while yl runs, since truncating a mapped file crashes the process
+    #[arg(
+        long,
+        value_name = "BYTES",
+        help = "Memory-map files of at least BYTES when linting them instead of reading them into memory; the files must not change while yl runs, 0 to never map (default: never)"
+    )]
+    pub mmap_threshold: Option<u64>,
+
+    /// Order of per-file results
+    #[arg(
+        long,
+        value_enum,
+        default_value = "parallel",
+        help = "Write results as files finish (parallel) or sorted by path at the end (sorted)"
+    )]
+    pub output_order: OutputOrder,
+
+    /// Group results and statistics
+    #[arg(
+        long,
+        value_enum,
+        value_name = "KEY",
+        help = "Write results grouped by project, followed by per-project statistics"
+    )]
+    pub group_by: Option<GroupBy>,
+
+    /// Stop after this many problems have been found
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Stop linting after N problems have been found"
+    )]
+    pub max_problems: Option<usize>,
+
+    /// Print per-rule timing after linting
+    #[arg(
+        long,
+        help = "Print per-rule time, files and problems to stderr after linting"
+    )]
+    pub profile_rules: bool,
+
+    /// Directory to write run analytics to
+    #[arg(
+        long,
+        value_name = "DIR",
+        help = "Write run analytics to DIR after linting: analytics.json and per-rule, per-file times as folded stacks for flamegraphs (analytics.folded)"
+    )]
+    pub analytics_out: Option<std::path::PathBuf>,
+
+    /// Lint YAML embedded in other files
+    #[arg(
+        long,
+        help = "Also lint YAML embedded in Markdown, HTML and shell scripts (code fences, script elements, here-documents)"
+    )]
+    pub embedded: bool,
+
+    /// Ignore nested per-directory configuration files
+    #[arg(
+        long,
+        help = "Only use the root configuration, ignoring nested .yl.yaml files"
+    )]
+    pub no_nested_config: bool,
+
+    /// Leave `${VAR}` references in configuration files unexpanded
+    #[arg(
+        long,
+        help = "Do not expand ${VAR} and ${VAR:-default} in configuration files"
+    )]
+    pub no_env_expand: bool,
+
+    /// Team policy the configuration must satisfy
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Report configuration that breaks this team policy as `policy` problems"
+    )]
+    pub policy: Option<PathBuf>,
+
+    /// Store the results of the run for querying across runs
+    #[arg(
+        long,
+        value_name = "SINK",
+        help = "Also record the run's problems and file hashes in a store (sqlite:<path>)"
+    )]
+    pub store: Option<StoreTarget>,
+
+    /// File receiving the manifest of the run
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Write a JSON manifest of the run (yl version, config hash, rules, file hashes, problem counts) for audits"
+    )]
+    pub manifest: Option<PathBuf>,
+
+    /// Webhook receiving the run summary
+    #[arg(
+        long,
+        value_name = "URL",
+        help = "POST a JSON summary of the run (stats, top rules, failed files) to this URL"
+    )]
+    pub webhook: Option<String>,
+
+    /// Seconds to wait for each webhook attempt
+    #[arg(
+        long,
+        value_name = "SECS",
+        help = "Seconds to wait for each webhook attempt (default: 10)"
+    )]
+    pub webhook_timeout: Option<u64>,
+
+    /// Webhook attempts after the first one fails
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Retry a failed webhook post this many times (default: 2)"
+    )]
+    pub webhook_retries: Option<u32>,
+
+    /// Follow symbolic links when walking directories
+    #[arg(
+        long,
+        global = true,
+        help = "Follow symbolic links when walking directories (cycles are skipped)"
+    )]
+    pub follow_symlinks: bool,
+
+    /// Keep a backup of each file before writing changes to it
+    #[arg(
+        long,
+        global = true,
+        value_name = "EXT",
+        value_parser = clap::builder::NonEmptyStringValueParser::new(),
+        help = "Keep each file yl rewrites as <file><EXT> (e.g. --in-place-backup .orig)"
+    )]
+    pub in_place_backup: Option<String>,
+
+    /// Log verbosity: -v info, -vv debug, -vvv trace
+    #[arg(
+        short,
+        long,
+        action = ArgAction::Count,
+        help = "Increase log verbosity (-v info, -vv debug, -vvv trace)"
+    )]
+    pub verbose: u8,
+
+    /// Format of log messages
+    #[arg(long, value_enum, default_value = "text", help = "Log message format")]
+    pub log_format: LogFormat,
+
+    /// Language of problem messages and summaries
+    #[arg(
+        long,
+        global = true,
+        value_name = "LANG",
+        help = "Language of messages: en, a code with translations in ~/.config/yl/translations, or a translations file (default: $YL_LANG)"
+    )]
+    pub lang: Option<String>,
 }
 
 /// Available subcommands
-#[derive(Subcommand)]
+#[derive(Subcommand, Clone)]
 pub enum Commands {
     /// Start the Language Server Protocol (LSP) server
+    #[cfg(feature = "lsp")]
     Lsp,
+    /// Keep a warm linter running and answer lint requests over a local socket
+    #[cfg(feature = "server")]
+    Daemon {
+        /// Socket to listen on (default: $XDG_RUNTIME_DIR/yl.sock)
+        #[arg(long)]
+        socket: Option<PathBuf>,
+    },
+    /// Send requests to a running `yl daemon`
+    #[cfg(feature = "server")]
+    Client {
+        /// Socket the daemon listens on (default: $XDG_RUNTIME_DIR/yl.sock)
+        #[arg(long)]
+        socket: Option<PathBuf>,
+        #[command(subcommand)]
+        client_command: ClientCommands,
+    },
+    /// Serve a REST API for linting (`POST /lint`, `GET /rules`)
+    #[cfg(feature = "server")]
+    Serve {
+        /// Address to listen on for HTTP, e.g. `:8080` (all interfaces) or `127.0.0.1:8080`
+        #[arg(long, value_name = "ADDR")]
+        http: String,
+    },
     /// Fix auto-fixable problems in files
     Fix {
         /// Files or directories to fix
@@ -87,21 +368,142 @@ pub enum Commands {
         /// Show what would be fixed without making changes
         #[arg(long)]
         dry_run: bool,
+        /// Refuse to write a fix that changes the parsed document structure
+        #[arg(long)]
+        verify: bool,
+    },
+    /// Explain a rule: its parameters, examples and whether it can be auto-fixed
+    Explain {
+        /// Rule identifier, e.g. line-length
+        rule: String,
+    },
+    /// Generate reports about the linted files
+    Report {
+        #[command(subcommand)]
+        report_command: ReportCommands,
+    },
+    /// Analyze the style of the linted files without reporting problems
+    #[cfg(feature = "ml")]
+    Analyze {
+        #[command(subcommand)]
+        analyze_command: AnalyzeCommands,
     },
     /// Migrate from yamllint configuration and directives
     Migrate {
         #[command(subcommand)]
         migrate_command: MigrateCommands,
     },
+    /// Team policy management
+    Policy {
+        #[command(subcommand)]
+        policy_command: PolicyCommands,
+    },
     /// Plugin management
+    #[cfg(feature = "plugins")]
     Plugin {
         #[command(subcommand)]
         plugin_command: PluginCommands,
     },
+    /// Inspect the configuration
+    Config {
+        #[command(subcommand)]
+        config_command: ConfigCommands,
+    },
+    /// Print a shell completion script
+    Completions {
+        /// Shell to complete for
+        shell: clap_complete::Shell,
+    },
+    /// Print the man page in roff format
+    Man,
+    /// Print the English message catalog, the template for a translation
+    Messages,
+    /// Update yl to the latest GitHub release, verifying its checksum
+    #[cfg(feature = "self-update")]
+    SelfUpdate {
+        /// Only report whether a newer release is available
+        #[arg(long)]
+        check: bool,
+    },
+}
+
+/// Daemon client subcommands
+#[cfg(feature = "server")]
+#[derive(Subcommand, Clone)]
+pub enum ClientCommands {
+    /// Lint files or directories, reporting like `yl` itself
+    Lint {
+        /// Files or directories to lint
+        files: Vec<PathBuf>,
+        /// Lint standard input as if it were the file at this path
+        #[arg(long, value_name = "PATH", conflicts_with = "files")]
+        stdin_filename: Option<PathBuf>,
+    },
+    /// Make the daemon re-read configuration files
+    Reload,
+    /// Check that the daemon is running
+    Ping,
+    /// Stop the daemon
+    Shutdown,
+}
+
+/// Configuration subcommands
+#[derive(Subcommand, Clone)]
+pub enum ConfigCommands {
+    /// Print the effective configuration, or the configuration that applies to a file
+    /// with where each setting comes from
+    Show {
+        /// File to resolve the configuration for, after nested configuration files,
+        /// command-line options, `.editorconfig` and the file's directives
+        file: Option<PathBuf>,
+    },
+}
+
+/// Report subcommands
+#[derive(Subcommand, Clone)]
+pub enum ReportCommands {
+    /// Count yl suppression directives per rule and per file
+    Suppressions {
+        /// Files or directories to scan
+        files: Vec<PathBuf>,
+        /// Flag rules suppressed more than this many times
+        #[arg(long, default_value_t = 10)]
+        threshold: usize,
+    },
+    /// Score the linted files from 0 to 100 by their weighted problems
+    Score {
+        /// Files or directories to lint
+        files: Vec<PathBuf>,
+        /// Also write the score as a shields.io endpoint badge to this file
+        #[arg(long, value_name = "FILE")]
+        badge: Option<PathBuf>,
+        /// Label of the badge
+        #[arg(long, default_value = "yaml health")]
+        badge_label: String,
+    },
+}
+
+/// Analyze subcommands
+#[cfg(feature = "ml")]
+#[derive(Subcommand, Clone)]
+pub enum AnalyzeCommands {
+    /// Report the distribution of indentation styles and the files that differ from the majority
+    Indentation {
+        /// Files or directories to scan
+        files: Vec<PathBuf>,
+    },
+    /// Compare the style of files changed in recent commits with the style of all files
+    Drift {
+        /// Files or directories to scan
+        files: Vec<PathBuf>,
+        /// Number of recent commits whose files are compared
+        #[arg(long, value_name = "N", default_value_t = 10)]
+        commits: usize,
+    },
 }
 
 /// Migration subcommands
-#[derive(Subcommand)]
+#[derive(Subcommand, Clone)]
 pub enum MigrateCommands {
     /// Convert yamllint configuration to yl format
     Config {
@@ -124,8 +526,33 @@ pub enum MigrateCommands {
     },
 }
 
+/// Policy subcommands
+#[derive(Subcommand, Clone)]
+pub enum PolicyCommands {
+    /// Write a team policy requiring the rules that are currently enabled
+    Init {
+        /// Start from the effective configuration (config file and CLI overrides)
+        /// instead of the built-in defaults
+        #[arg(long)]
+        from_config: bool,
+        /// Name of the policy
+        #[arg(long, default_value = "team")]
+        name: String,
+        /// Also require the security-related rules the configuration disables
+        #[arg(long)]
+        promote_security: bool,
+        /// Path to write the policy to
+        #[arg(short, long, default_value = "yl-policy.yaml")]
+        output: PathBuf,
+        /// Overwrite the output file if it exists
+        #[arg(long)]
+        force: bool,
+    },
+}
+
 /// Plugin subcommands
-#[derive(Subcommand)]
+#[cfg(feature = "plugins")]
+#[derive(Subcommand, Clone)]
 pub enum PluginCommands {
     /// List loaded plugins
     List,
@@ -134,9 +561,26 @@ pub enum PluginCommands {
         /// Directory containing plugin libraries
         directory: PathBuf,
     },
+    /// Create a cargo project for a new plugin, with an example rule and tests
+    New {
+        /// Name of the plugin and its crate
+        name: String,
+        /// Directory to create (default: ./<name>)
+        #[arg(long, value_name = "DIR")]
+        path: Option<PathBuf>,
+        /// Depend on a local yl-core checkout instead of the release matching this yl
+        #[arg(long, value_name = "DIR")]
+        yl_core_path: Option<PathBuf>,
+    },
 }
 
 impl Cli {
+    /// Whether nested configuration files apply: never over a configuration given with
+    /// `--config`
+    pub fn nested_config(&self) -> bool {
+        !self.no_nested_config && self.config.is_none()
+    }
+
     /// Parse disable rules from comma-separated string
     pub fn get_disabled_rules(&self) -> Vec<String> {
         self.disable
@@ -200,6 +644,162 @@ mod tests {
         assert!(matches!(OutputFormat::default(), OutputFormat::Human));
     }
 
+    #[test]
+    fn test_verbosity_and_log_format() {
+        let cli = Cli::try_parse_from(["yl", "-vv", "--log-format", "json"]).unwrap();
+        assert_eq!(cli.verbose, 2);
+        assert_eq!(cli.log_format, LogFormat::Json);
+
+        let cli = Cli::try_parse_from(["yl"]).unwrap();
+        assert_eq!(cli.verbose, 0);
+        assert_eq!(cli.log_format, LogFormat::Text);
+    }
+
+    #[test]
+    fn test_changed_file_selection_flags() {
+        let cli = Cli::try_parse_from(["yl", "--base", "origin/main"]).unwrap();
+        assert_eq!(cli.base.as_deref(), Some("origin/main"));
+        assert!(!cli.staged);
+
+        assert!(Cli::try_parse_from(["yl", "--staged"]).unwrap().staged);
+        assert!(Cli::try_parse_from(["yl", "--staged", "--base", "main"]).is_err());
+        assert!(Cli::try_parse_from(["yl", "--changed-since", "HEAD", "--base", "main"]).is_err());
+        assert!(Cli::try_parse_from(["yl", "--staged", "--only-added-lines"]).is_ok());
+        assert!(Cli::try_parse_from(["yl", "--only-added-lines"]).is_err());
+    }
+
+    #[test]
+    fn test_short_rule_flags() {
+        let cli = Cli::try_parse_from(["yl", "-d", "truthy", "-e", "key-ordering"]).unwrap();
+        assert_eq!(cli.disable, vec!["truthy".to_string()]);
+        assert_eq!(cli.enable, vec!["key-ordering".to_string()]);
+    }
+
+    #[test]
+    fn test_max_problems() {
+        let cli = Cli::try_parse_from(["yl", "--max-problems", "5"]).unwrap();
+        assert_eq!(cli.max_problems, Some(5));
+
+        let cli = Cli::try_parse_from(["yl"]).unwrap();
+        assert_eq!(cli.max_problems, None);
+    }
+
+    #[test]
+    fn test_jobs_and_output_order() {
+        let cli = Cli::try_parse_from(["yl", "-j", "4", "--output-order", "sorted"]).unwrap();
+        assert_eq!(cli.jobs, Some(4));
+        assert_eq!(cli.output_order, OutputOrder::Sorted);
+
+        let cli = Cli::try_parse_from(["yl"]).unwrap();
+        assert_eq!(cli.jobs, None);
+        assert_eq!(cli.output_order, OutputOrder::Parallel);
+
+        let cli = Cli::try_parse_from(["yl", "--mmap-threshold", "0"]).unwrap();
+        assert_eq!(cli.mmap_threshold, Some(0));
+
+        let cli = Cli::try_parse_from(["yl", "--profile", "ci", "."]).unwrap();
+        assert_eq!(cli.profile.as_deref(), Some("ci"));
+
+        let cli = Cli::try_parse_from(["yl", "--no-env-expand", "."]).unwrap();
+        assert!(cli.no_env_expand);
+    }
+
+    #[test]
+    fn test_nested_config() {
+        assert!(Cli::try_parse_from(["yl", "."]).unwrap().nested_config());
+        let cli = Cli::try_parse_from(["yl", "--no-nested-config", "."]).unwrap();
+        assert!(!cli.nested_config());
+        // Discovered files never apply over an explicit configuration
+        let cli = Cli::try_parse_from(["yl", "--config", "ci.yaml", "."]).unwrap();
+        assert!(!cli.nested_config());
+    }
+
+    #[test]
+    fn test_config_show() {
+        let cli = Cli::try_parse_from(["yl", "config", "show", "charts/values.yaml"]).unwrap();
+        match cli.command {
+            Some(Commands::Config {
+                config_command: ConfigCommands::Show { file },
+            }) => assert_eq!(file, Some(PathBuf::from("charts/values.yaml"))),
+            _ => panic!("expected config show"),
+        }
+    }
+
+    #[test]
+    fn test_group_by() {
+        let cli = Cli::try_parse_from(["yl", "--group-by", "project", "."]).unwrap();
+        assert_eq!(cli.group_by, Some(GroupBy::Project));
+        assert!(Cli::try_parse_from(["yl", "--group-by", "rule", "."]).is_err());
+    }
+
+    #[test]
+    fn test_embedded() {
+        let cli = Cli::try_parse_from(["yl", "--embedded", "docs"]).unwrap();
+        assert!(cli.embedded);
+        assert_eq!(cli.files, vec![PathBuf::from("docs")]);
+    }
+
+    #[test]
+    fn test_follow_symlinks() {
+        let cli = Cli::try_parse_from(["yl", "fix", "--follow-symlinks", "."]).unwrap();
+        assert!(cli.follow_symlinks);
+
+        let cli = Cli::try_parse_from(["yl"]).unwrap();
+        assert!(!cli.follow_symlinks);
+    }
+
+    #[test]
+    fn test_in_place_backup() {
+        let cli = Cli::try_parse_from(["yl", "fix", "--in-place-backup", ".orig", "."]).unwrap();
+        assert_eq!(cli.in_place_backup.as_deref(), Some(".orig"));
+
+        let cli = Cli::try_parse_from(["yl", "--fix"]).unwrap();
+        assert_eq!(cli.in_place_backup, None);
+
+        // An empty suffix would name the file itself
+        assert!(Cli::try_parse_from(["yl", "fix", "--in-place-backup", "", "."]).is_err());
+    }
+
+    #[test]
+    fn test_fix_verify() {
+        let cli = Cli::try_parse_from(["yl", "fix", "--verify", "."]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Fix {
+                verify: true,
+                dry_run: false,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "server")]
+    fn test_client_lint_stdin() {
+        let cli =
+            Cli::try_parse_from(["yl", "client", "lint", "--stdin-filename", "a.yaml"]).unwrap();
+        let Some(Commands::Client {
+            socket: None,
+            client_command: ClientCommands::Lint { stdin_filename, .. },
+        }) = cli.command
+        else {
+            panic!("expected client lint");
+        };
+        assert_eq!(stdin_filename, Some(PathBuf::from("a.yaml")));
+
+        assert!(
+            Cli::try_parse_from([
+                "yl",
+                "client",
+                "lint",
+                "--stdin-filename",
+                "a.yaml",
+                "b.yaml"
+            ])
+            .is_err()
+        );
+    }
+
     #[test]
     fn test_get_disabled_rules() {
         let cli = Cli {
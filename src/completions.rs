@@ -0,0 +1,82 @@
+use crate::cli::Cli;
+use crate::rules::RuleRegistry;
+use clap::CommandFactory;
+use clap::builder::PossibleValuesParser;
+use clap_complete::Shell;
+use std::io::{self, Write};
+
+/// The command-line interface with rule IDs and parameters as completion candidates.
+///
+/// The candidates only feed the generated scripts; parsing still accepts comma-separated
+/// lists and rules added by plugins.
+pub fn command() -> clap::Command {
+    let registry = RuleRegistry::with_default_rules();
+    let mut rules = registry.rules();
+    rules.sort_by_key(|rule| rule.id());
+
+    let rule_ids: Vec<&'static str> = rules.iter().map(|rule| rule.id()).collect();
+    let params: Vec<String> = rules
+        .iter()
+        .flat_map(|rule| {
+            let mut keys: Vec<String> = rule.default_config().params.into_keys().collect();
+            keys.sort();
+            keys.into_iter()
+                .map(move |key| format!("{}.{key}=", rule.id()))
+        })
+        .collect();
+
+    Cli::command()
+        .mut_arg("disable", |arg| {
+            arg.value_parser(PossibleValuesParser::new(rule_ids.clone()))
+        })
+        .mut_arg("enable", |arg| {
+            arg.value_parser(PossibleValuesParser::new(rule_ids.clone()))
+        })
+        .mut_arg("set", |arg| {
+            arg.value_parser(PossibleValuesParser::new(params))
+        })
+        .mut_subcommand("explain", |explain| {
+            explain.mut_arg("rule", |arg| {
+                arg.value_parser(PossibleValuesParser::new(rule_ids.clone()))
+            })
+        })
+}
+
+/// Write the completion script for `shell`
+pub fn write_completions(shell: Shell, out: &mut impl Write) {
+    let mut command = command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, out);
+}
+
+/// Write the man page in roff format
+pub fn write_man_page(out: &mut impl Write) -> io::Result<()> {
+    clap_mangen::Man::new(command()).render(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_completions_list_rules() {
+        let mut script = Vec::new();
+        write_completions(Shell::Bash, &mut script);
+        let script = String::from_utf8(script).unwrap();
+
+        assert!(script.contains("line-length"));
+        assert!(script.contains("line-length.max="));
+        assert!(script.contains("completions"));
+    }
+
+    #[test]
+    fn test_man_page() {
+        let mut page = Vec::new();
+        write_man_page(&mut page).unwrap();
+        let page = String::from_utf8(page).unwrap();
+
+        assert!(page.starts_with(".ie"));
+        assert!(page.contains(".TH yl"));
+        assert!(page.contains("\\-\\-disable"));
+    }
+}
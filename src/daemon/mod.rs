@@ -0,0 +1,440 @@
+//! Long-running lint process answering requests over a local socket
+//!
+//! The daemon keeps its linter (resolved configurations, compiled globs) between requests,
+//! so editor plugins and scripts that lint on every save avoid the startup cost of a new
+//! process. Requests and responses are single-line JSON objects.
+
+use crate::config::{Config, ConfigReload, ConfigWatcher};
+use crate::linter::{Linter, Problem};
+use eyre::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tracing::{debug, info, warn};
+
+/// A request sent to the daemon
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "kebab-case")]
+pub enum Request {
+    /// Lint a file or directory, or `content` as if it were the file at `path`
+    Lint {
+        path: PathBuf,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        content: Option<String>,
+    },
+    /// Re-read configuration files
+    Reload,
+    /// Check that the daemon is running
+    Ping,
+    /// Stop the daemon
+    Shutdown,
+}
+
+/// The daemon's answer to a [`Request`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "result", rename_all = "kebab-case")]
+pub enum Response {
+    /// Problems of each linted file
+    Lint { files: Vec<FileProblems> },
+    /// The request succeeded and has nothing to return
+    Ok,
+    /// The request failed
+    Error { message: String },
+}
+
+/// Problems found in one file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileProblems {
+    pub path: PathBuf,
+    pub problems: Vec<Problem>,
+}
+
+/// Builds a linter from a (re)loaded root configuration
+type BuildLinter = Box<dyn Fn(Config) -> Linter + Send + Sync>;
+
+/// State shared by all connections
+pub struct Daemon {
+    /// Current linter; requests take a clone of the `Arc` so a reload never waits on them
+    linter: Mutex<Arc<Linter>>,
+    config_watcher: Mutex<ConfigWatcher>,
+    build_linter: BuildLinter,
+}
+
+impl Daemon {
+    /// Create a daemon for the configuration loaded from `config_path`.
+    /// `build_linter` is used again whenever the configuration file changes.
+    pub fn new<F>(config_path: Option<PathBuf>, config: Config, build_linter: F) -> Self
+    where
+        F: Fn(Config) -> Linter + Send + Sync + 'static,
+    {
+        let config_watcher = ConfigWatcher::new(config_path, config.clone());
+        Self {
+            linter: Mutex::new(Arc::new(build_linter(config))),
+            config_watcher: Mutex::new(config_watcher),
+            build_linter: Box::new(build_linter),
+        }
+    }
+
+    /// Apply the named profile to the configuration whenever it is reloaded
+    pub fn with_profile(self, profile: Option<String>) -> Self {
+        self.map_config_watcher(|watcher| watcher.with_profile(profile))
+    }
+
+    /// Enable or disable expansion of environment variables in reloaded configurations
+    pub fn with_env_expand(self, enabled: bool) -> Self {
+        self.map_config_watcher(|watcher| watcher.with_env_expand(enabled))
+    }
+
+    fn map_config_watcher(self, f: impl FnOnce(ConfigWatcher) -> ConfigWatcher) -> Self {
+        let config_watcher = f(self
+            .config_watcher
+            .into_inner()
+            .expect("config watcher lock"));
+        Self {
+            config_watcher: Mutex::new(config_watcher),
+            ..self
+        }
+    }
+
+    /// Replace the linter if the root configuration file changed
+    fn reload_if_changed(&self) {
+        let reload = self
+            .config_watcher
+            .lock()
+            .expect("config watcher lock")
+            .poll();
+        match reload {
+            ConfigReload::Unchanged => {}
+            ConfigReload::Reloaded { config, changes } => {
+                info!(changes = %changes.join(", "), "configuration reloaded");
+                *self.linter.lock().expect("linter lock") = Arc::new((self.build_linter)(*config));
+            }
+            ConfigReload::Invalid(e) => {
+                warn!(error = %format!("{e:#}"), "configuration is invalid, keeping the previous one");
+            }
+        }
+    }
+
+    /// Answer a request; linting runs on the calling thread
+    pub fn handle(&self, request: Request) -> Response {
+        match request {
+            Request::Lint { path, content } => {
+                self.reload_if_changed();
+                let linter = self.linter.lock().expect("linter lock").clone();
+                let results = match content {
+                    Some(content) => linter.lint_virtual(&[(path, content)]),
+                    None => linter.lint_paths(&[path]),
+                };
+                match results {
+                    Ok(results) => Response::Lint {
+                        files: results
+                            .into_iter()
+                            .map(|(path, problems)| FileProblems { path, problems })
+                            .collect(),
+                    },
+                    Err(e) => Response::Error {
+                        message: format!("{e:#}"),
+                    },
+                }
+            }
+            Request::Reload => {
+                self.linter
+                    .lock()
+                    .expect("linter lock")
+                    .clear_config_cache();
+                self.reload_if_changed();
+                Response::Ok
+            }
+            Request::Ping | Request::Shutdown => Response::Ok,
+        }
+    }
+}
+
+/// Socket used when none is given: `$XDG_RUNTIME_DIR/yl.sock`, or `yl.sock` in a per-user
+/// directory of the temporary directory that only the user can open
+pub fn default_socket_path() -> PathBuf {
+    match std::env::var_os("XDG_RUNTIME_DIR") {
+        Some(dir) => PathBuf::from(dir).join("yl.sock"),
+        None => fallback_socket_dir().join("yl.sock"),
+    }
+}
+
+/// Per-user directory of the default socket without `$XDG_RUNTIME_DIR`
+fn fallback_socket_dir() -> PathBuf {
+    let user = std::env::var("USER").unwrap_or_else(|_| "default".to_string());
+    std::env::temp_dir().join(format!("yl-{user}"))
+}
+
+#[cfg(unix)]
+pub use unix::{Client, serve};
+
+#[cfg(unix)]
+mod unix {
+    use super::*;
+    use std::os::unix::fs::{DirBuilderExt, FileTypeExt, PermissionsExt};
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::{UnixListener, UnixStream};
+    use tokio::sync::Notify;
+
+    /// Serve requests on `socket` until a client asks the daemon to shut down.
+    ///
+    /// Fails if another daemon is already listening on the socket; a socket left behind
+    /// by a daemon that is no longer running is replaced, and any other file is left
+    /// alone. The per-user directory of the default socket is created if needed.
+    pub async fn serve(socket: &Path, daemon: Arc<Daemon>) -> Result<()> {
+        if socket.parent() == Some(fallback_socket_dir().as_path()) {
+            private_dir(&fallback_socket_dir())?;
+        }
+        if let Ok(metadata) = std::fs::symlink_metadata(socket) {
+            if !metadata.file_type().is_socket() {
+                return Err(eyre::eyre!(
+                    "{} exists and is not a socket",
+                    socket.display()
+                ));
+            }
+            if UnixStream::connect(socket).await.is_ok() {
+                return Err(eyre::eyre!(
+                    "A daemon is already listening on {}",
+                    socket.display()
+                ));
+            }
+            std::fs::remove_file(socket)
+                .with_context(|| format!("Failed to remove stale socket {}", socket.display()))?;
+        }
+
+        let listener = UnixListener::bind(socket)
+            .with_context(|| format!("Failed to listen on {}", socket.display()))?;
+        info!(socket = %socket.display(), "daemon listening");
+
+        let shutdown = Arc::new(Notify::new());
+        loop {
+            tokio::select! {
+                accepted = listener.accept() => {
+                    let (stream, _) = accepted.context("Failed to accept connection")?;
+                    let (daemon, shutdown) = (daemon.clone(), shutdown.clone());
+                    tokio::spawn(async move {
+                        if let Err(e) = handle_connection(stream, daemon, shutdown).await {
+                            debug!(error = %e, "connection closed");
+                        }
+                    });
+                }
+                _ = shutdown.notified() => break,
+            }
+        }
+
+        info!("daemon shutting down");
+        let _ = std::fs::remove_file(socket);
+        Ok(())
+    }
+
+    /// Create `dir` accessible to the user only, or check that an existing one is a
+    /// directory closed to everyone else. A directory of another user that is closed to
+    /// everyone else cannot be used either, so its owner need not be checked.
+    pub(super) fn private_dir(dir: &Path) -> Result<()> {
+        match std::fs::DirBuilder::new().mode(0o700).create(dir) {
+            Ok(()) => return Ok(()),
+            Err(e) if e.kind() != std::io::ErrorKind::AlreadyExists => {
+                return Err(e).with_context(|| format!("Failed to create {}", dir.display()));
+            }
+            Err(_) => {}
+        }
+        let metadata = std::fs::symlink_metadata(dir)
+            .with_context(|| format!("Failed to read {}", dir.display()))?;
+        if !metadata.is_dir() || metadata.permissions().mode() & 0o077 != 0 {
+            return Err(eyre::eyre!(
+                "{} must be a directory that only its owner can access",
+                dir.display()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Answer the requests of one connection, one JSON line each
+    async fn handle_connection(
+        stream: UnixStream,
+        daemon: Arc<Daemon>,
+        shutdown: Arc<Notify>,
+    ) -> Result<()> {
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+
+        while let Some(line) = lines.next_line().await? {
+            let (response, stop) = match serde_json::from_str::<Request>(&line) {
+                Ok(request) => {
+                    let stop = request == Request::Shutdown;
+                    let daemon = daemon.clone();
+                    let response = tokio::task::spawn_blocking(move || daemon.handle(request))
+                        .await
+                        .context("Request handler panicked")?;
+                    (response, stop)
+                }
+                Err(e) => (
+                    Response::Error {
+                        message: format!("Invalid request: {e}"),
+                    },
+                    false,
+                ),
+            };
+
+            let mut output = serde_json::to_string(&response)?;
+            output.push('\n');
+            writer.write_all(output.as_bytes()).await?;
+
+            if stop {
+                shutdown.notify_one();
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// A connection to a running daemon
+    pub struct Client {
+        lines: tokio::io::Lines<BufReader<tokio::net::unix::OwnedReadHalf>>,
+        writer: tokio::net::unix::OwnedWriteHalf,
+    }
+
+    impl Client {
+        /// Connect to the daemon listening on `socket`
+        pub async fn connect(socket: &Path) -> Result<Self> {
+            let stream = UnixStream::connect(socket).await.with_context(|| {
+                format!(
+                    "No daemon is listening on {} (start one with `yl daemon`)",
+                    socket.display()
+                )
+            })?;
+            let (reader, writer) = stream.into_split();
+            Ok(Self {
+                lines: BufReader::new(reader).lines(),
+                writer,
+            })
+        }
+
+        /// Send a request and wait for its response
+        pub async fn send(&mut self, request: &Request) -> Result<Response> {
+            let mut line = serde_json::to_string(request)?;
+            line.push('\n');
+            self.writer.write_all(line.as_bytes()).await?;
+
+            let response = self
+                .lines
+                .next_line()
+                .await?
+                .ok_or_else(|| eyre::eyre!("The daemon closed the connection"))?;
+            serde_json::from_str(&response).context("Invalid response from the daemon")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_format() {
+        let request: Request =
+            serde_json::from_str(r#"{"method":"lint","path":"a.yaml"}"#).unwrap();
+        assert_eq!(
+            request,
+            Request::Lint {
+                path: PathBuf::from("a.yaml"),
+                content: None
+            }
+        );
+
+        let response = Response::Error {
+            message: "bad".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"result":"error","message":"bad"}"#
+        );
+    }
+
+    #[test]
+    fn test_handle_lint_content() {
+        let mut config = Config::default();
+        config.rules.get_mut("trailing-spaces").unwrap().enabled = true;
+        let daemon = Daemon::new(None, config, Linter::new);
+
+        let response = daemon.handle(Request::Lint {
+            path: PathBuf::from("virtual.yaml"),
+            content: Some("---\nkey: value   \n".to_string()),
+        });
+        let Response::Lint { files } = response else {
+            panic!("unexpected response: {response:?}");
+        };
+        assert_eq!(files.len(), 1);
+        assert!(
+            files[0]
+                .problems
+                .iter()
+                .any(|p| p.rule == "trailing-spaces")
+        );
+
+        let response = daemon.handle(Request::Lint {
+            path: PathBuf::from("/nonexistent/file.yaml"),
+            content: None,
+        });
+        assert!(matches!(response, Response::Error { .. }));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_serve_and_client() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let socket = dir.path().join("yl.sock");
+        let daemon = Arc::new(Daemon::new(None, Config::default(), Linter::new));
+
+        let server = tokio::spawn({
+            let socket = socket.clone();
+            async move { serve(&socket, daemon).await }
+        });
+        while !socket.exists() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        let mut client = Client::connect(&socket).await.unwrap();
+        assert_eq!(client.send(&Request::Ping).await.unwrap(), Response::Ok);
+        let response = client
+            .send(&Request::Lint {
+                path: PathBuf::from("virtual.yaml"),
+                content: Some("---\nkey: value\n".to_string()),
+            })
+            .await
+            .unwrap();
+        assert!(matches!(response, Response::Lint { .. }));
+
+        // A second daemon cannot take over a socket that is in use
+        let second = Arc::new(Daemon::new(None, Config::default(), Linter::new));
+        assert!(serve(&socket, second).await.is_err());
+
+        assert_eq!(client.send(&Request::Shutdown).await.unwrap(), Response::Ok);
+        server.await.unwrap().unwrap();
+        assert!(!socket.exists());
+
+        // A file that is not a socket is never removed
+        std::fs::write(&socket, "notes").unwrap();
+        let third = Arc::new(Daemon::new(None, Config::default(), Linter::new));
+        assert!(serve(&socket, third).await.is_err());
+        assert_eq!(std::fs::read_to_string(&socket).unwrap(), "notes");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_private_dir() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path().join("yl-test");
+        unix::private_dir(&dir).unwrap();
+        let mode = std::fs::metadata(&dir).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+        unix::private_dir(&dir).unwrap();
+
+        // A directory others can open is refused
+        std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o755)).unwrap();
+        assert!(unix::private_dir(&dir).is_err());
+    }
+}
@@ -7,3 +7,7 @@
 // - Path-based conditional rules
 // - Template system for reusable configurations
 // - Advanced directive processing logic
+
+pub mod suppressions;
+
+pub use suppressions::SuppressionReport;
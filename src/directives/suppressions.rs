@@ -0,0 +1,149 @@
+use crate::parser::{CommentProcessor, Directive};
+use eyre::{Context, Result};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Key used for directives that suppress every rule
+pub const ALL_RULES: &str = "*";
+
+/// Counts of suppression directives found across a set of files
+#[derive(Debug, Default)]
+pub struct SuppressionReport {
+    /// Total number of suppressed rules across all directives
+    pub total: usize,
+    /// Suppression count per rule (`*` for directives without a rule list)
+    pub by_rule: BTreeMap<String, usize>,
+    /// Suppression count per file
+    pub by_file: BTreeMap<PathBuf, usize>,
+}
+
+impl SuppressionReport {
+    /// Scan the given files for suppression directives
+    pub fn scan(files: &[PathBuf]) -> Result<Self> {
+        let processor = CommentProcessor::new();
+        let mut report = Self::default();
+
+        for file in files {
+            let content = std::fs::read_to_string(file)
+                .with_context(|| format!("Failed to read file: {}", file.display()))?;
+            report.add_content(&processor, file, &content);
+        }
+
+        Ok(report)
+    }
+
+    /// Record the suppression directives found in a file's content
+    pub fn add_content(&mut self, processor: &CommentProcessor, path: &Path, content: &str) {
+        for line in content.lines() {
+            let Some(comment_start) = line.find('#') else {
+                continue;
+            };
+
+            // Malformed directives are reported by the linter, not here
+            let Ok(Some(directive)) = processor.parse_directive(&line[comment_start..]) else {
+                continue;
+            };
+
+            for rule in Self::suppressed_rules(directive) {
+                self.total += 1;
+                *self.by_rule.entry(rule).or_default() += 1;
+                *self.by_file.entry(path.to_path_buf()).or_default() += 1;
+            }
+        }
+    }
+
+    /// Rules suppressed more than `threshold` times, most suppressed first.
+    /// Blanket suppressions are excluded since they cannot be disabled in configuration.
+    pub fn candidates(&self, threshold: usize) -> Vec<(&str, usize)> {
+        let mut candidates: Vec<(&str, usize)> = self
+            .by_rule
+            .iter()
+            .filter(|(rule, count)| rule.as_str() != ALL_RULES && **count > threshold)
+            .map(|(rule, count)| (rule.as_str(), *count))
+            .collect();
+        candidates.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        candidates
+    }
+
+    /// Rules suppressed by a directive, or nothing if it does not suppress anything
+    fn suppressed_rules(directive: Directive) -> Vec<String> {
+        let rules = match directive {
+            Directive::Disable { rules, .. }
+            | Directive::DisableLine { rules }
+            | Directive::IgnoreSection { rules } => rules,
+            Directive::IgnoreFile => Vec::new(),
+            _ => return Vec::new(),
+        };
+
+        if rules.is_empty() {
+            vec![ALL_RULES.to_string()]
+        } else {
+            rules
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report_for(files: &[(&str, &str)]) -> SuppressionReport {
+        let processor = CommentProcessor::new();
+        let mut report = SuppressionReport::default();
+        for (path, content) in files {
+            report.add_content(&processor, Path::new(path), content);
+        }
+        report
+    }
+
+    #[test]
+    fn test_counts_per_rule_and_file() {
+        let report = report_for(&[
+            (
+                "a.yaml",
+                "key: value # yl:disable-line line-length\n# yl:disable truthy,line-length\n",
+            ),
+            ("b.yaml", "# yl:ignore-file\nkey: value\n"),
+            ("c.yaml", "key: value\n"),
+        ]);
+
+        assert_eq!(report.total, 4);
+        assert_eq!(report.by_rule.get("line-length"), Some(&2));
+        assert_eq!(report.by_rule.get("truthy"), Some(&1));
+        assert_eq!(report.by_rule.get(ALL_RULES), Some(&1));
+        assert_eq!(report.by_file.get(Path::new("a.yaml")), Some(&3));
+        assert_eq!(report.by_file.get(Path::new("b.yaml")), Some(&1));
+        assert!(!report.by_file.contains_key(Path::new("c.yaml")));
+    }
+
+    #[test]
+    fn test_non_suppressing_directives_ignored() {
+        let report = report_for(&[(
+            "a.yaml",
+            "# yl:set line-length.max=120\n# yl:enable truthy\n# yl:set broken\nkey: value\n",
+        )]);
+
+        assert_eq!(report.total, 0);
+        assert!(report.by_rule.is_empty());
+    }
+
+    #[test]
+    fn test_candidates() {
+        let report = report_for(&[
+            (
+                "a.yaml",
+                "a: 1 # yl:disable-line truthy\nb: 2 # yl:disable-line truthy\nc: 3 # yl:disable-line\n",
+            ),
+            (
+                "b.yaml",
+                "a: 1 # yl:disable-line truthy\nb: 2 # yl:disable-line\nc: 3 # yl:disable-line line-length\n",
+            ),
+        ]);
+
+        assert_eq!(report.candidates(2), vec![("truthy", 3)]);
+        assert_eq!(
+            report.candidates(0),
+            vec![("truthy", 3), ("line-length", 1)]
+        );
+    }
+}
@@ -0,0 +1,496 @@
+use crate::linter::LineChange;
+use eyre::{Context, Result};
+use regex::Regex;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::LazyLock;
+
+/// Header of a hunk of a unified diff, with the start and length of the old and new ranges
+static HUNK_HEADER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^@@ -\d+(?:,(\d+))? \+(\d+)(?:,(\d+))? @@").expect("valid regex")
+});
+
+/// Files changed by a diff, keyed by canonical path, with the lines the diff touches
+pub type ChangedFiles = HashMap<PathBuf, ChangedLines>;
+
+/// Lines of a file's new version that a diff adds, and the unchanged lines it shows
+/// around them
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ChangedLines {
+    pub added: BTreeSet<usize>,
+    pub context: BTreeSet<usize>,
+}
+
+impl ChangedLines {
+    /// How the diff touches a line (1-based), if at all
+    pub fn change(&self, line: usize) -> Option<LineChange> {
+        if self.added.contains(&line) {
+            Some(LineChange::Added)
+        } else if self.context.contains(&line) {
+            Some(LineChange::Context)
+        } else {
+            None
+        }
+    }
+}
+
+/// Files changed relative to a git reference, as reported by `git diff --name-status <reference>`.
+///
+/// Paths are canonicalized so they can be compared with walked paths; renamed and copied
+/// files are named by their new path, and files that no longer exist (deletions) are left out.
+pub fn changed_files(dir: &Path, reference: &str) -> Result<ChangedFiles> {
+    diff(dir, &[reference])
+}
+
+/// Files changed on the current branch since it forked from `reference`, e.g. `origin/main`:
+/// the changes a pull request against `reference` would show, working tree included.
+pub fn changed_files_since_merge_base(dir: &Path, reference: &str) -> Result<ChangedFiles> {
+    let base = git(dir, &["merge-base", reference, "HEAD"])?;
+    changed_files(dir, base.trim())
+}
+
+/// Files with changes staged in the index, as `git diff --name-status --cached` reports them
+pub fn staged_files(dir: &Path) -> Result<ChangedFiles> {
+    diff(dir, &["--cached"])
+}
+
+/// Existing files named by `git diff --name-status <args>`, canonicalized, with the lines
+/// of the unified diff of each
+fn diff(dir: &Path, args: &[&str]) -> Result<ChangedFiles> {
+    let root = git(dir, &["rev-parse", "--show-toplevel"])?;
+    let root = PathBuf::from(root.trim());
+    let run = |options: &[&str]| {
+        let mut command = vec!["diff", "--find-renames"];
+        command.extend_from_slice(options);
+        command.extend_from_slice(args);
+        command.push("--");
+        git(dir, &command)
+    };
+
+    // The parser expects git's default prefixes and context, whatever the user's config
+    let unified = [
+        "--no-color",
+        "--no-ext-diff",
+        "--src-prefix=a/",
+        "--dst-prefix=b/",
+        "-U3",
+    ];
+    let mut lines: ChangedFiles = parse_unified_diff(&run(&unified)?)
+        .into_iter()
+        .filter_map(|(path, lines)| Some((root.join(path).canonicalize().ok()?, lines)))
+        .collect();
+
+    // Files without content changes (renames, mode changes) have no hunks
+    Ok(parse_name_status(&run(&["--name-status", "-z"])?)
+        .into_iter()
+        .filter_map(|path| root.join(path).canonicalize().ok())
+        .map(|path| {
+            let changed = lines.remove(&path).unwrap_or_default();
+            (path, changed)
+        })
+        .collect())
+}
+
+/// Paths of the new versions in `git diff --name-status -z` output, leaving out deleted
+/// files. Renames and copies (`R100`, `C075`) name the old path and then the new one.
+fn parse_name_status(output: &str) -> Vec<String> {
+    let mut fields = output.split('\0').filter(|field| !field.is_empty());
+    let mut paths = Vec::new();
+
+    while let Some(status) = fields.next() {
+        let path = match status.chars().next() {
+            Some('R' | 'C') => fields.nth(1),
+            _ => fields.next(),
+        };
+        match (status, path) {
+            (_, None) => break,
+            ("D", Some(_)) => {}
+            (_, Some(path)) => paths.push(path.to_string()),
+        }
+    }
+
+    paths
+}
+
+/// Path of the new version named by the `+++` header of a unified diff, without its `b/`
+/// prefix. Git quotes paths with unusual characters C-style and ends paths containing
+/// spaces with a tab.
+fn new_path(header: &str) -> Option<String> {
+    let header = header.strip_suffix('\t').unwrap_or(header);
+    let path = match header.strip_prefix('"') {
+        Some(quoted) => unquote(quoted.strip_suffix('"')?)?,
+        None => header.to_string(),
+    };
+    path.strip_prefix("b/").map(str::to_string)
+}
+
+/// Undo git's C-style quoting of a path: backslash escapes, with bytes of non-ASCII
+/// characters as three octal digits
+fn unquote(quoted: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(quoted.len());
+    let mut chars = quoted.bytes();
+
+    while let Some(byte) = chars.next() {
+        if byte != b'\\' {
+            bytes.push(byte);
+            continue;
+        }
+        let escaped = match chars.next()? {
+            b'n' => b'\n',
+            b't' => b'\t',
+            b'r' => b'\r',
+            b'a' => 0x07,
+            b'b' => 0x08,
+            b'f' => 0x0c,
+            b'v' => 0x0b,
+            digit @ b'0'..=b'7' => {
+                let octal = [digit, chars.next()?, chars.next()?];
+                u8::from_str_radix(std::str::from_utf8(&octal).ok()?, 8).ok()?
+            }
+            other => other,
+        };
+        bytes.push(escaped);
+    }
+
+    String::from_utf8(bytes).ok()
+}
+
+/// Lines of each file's new version in a unified diff, keyed by the path in the `+++` header
+fn parse_unified_diff(diff: &str) -> HashMap<String, ChangedLines> {
+    let mut files: HashMap<String, ChangedLines> = HashMap::new();
+    let mut file: Option<String> = None;
+    // Line of the new version the next hunk line is on, and the lines left in the hunk
+    let (mut line, mut old_left, mut new_left): (usize, usize, usize) = (0, 0, 0);
+
+    for text in diff.lines() {
+        if old_left == 0 && new_left == 0 {
+            if let Some(header) = text.strip_prefix("+++ ") {
+                file = new_path(header);
+            } else if let Some(captures) = HUNK_HEADER.captures(text) {
+                let count = |group| {
+                    captures
+                        .get(group)
+                        .map_or(1, |count: regex::Match| count.as_str().parse().unwrap_or(0))
+                };
+                old_left = count(1);
+                line = captures[2].parse().unwrap_or(0);
+                new_left = count(3);
+            }
+            continue;
+        }
+
+        // Deleted files have hunks too, with nothing to record
+        let mut lines = file
+            .as_ref()
+            .map(|file| files.entry(file.clone()).or_default());
+        match text.chars().next() {
+            Some('+') => {
+                if let Some(lines) = lines.as_mut() {
+                    lines.added.insert(line);
+                }
+                line += 1;
+                new_left = new_left.saturating_sub(1);
+            }
+            Some('-') => old_left = old_left.saturating_sub(1),
+            // "\ No newline at end of file"
+            Some('\\') => {}
+            _ => {
+                if let Some(lines) = lines.as_mut() {
+                    lines.context.insert(line);
+                }
+                line += 1;
+                old_left = old_left.saturating_sub(1);
+                new_left = new_left.saturating_sub(1);
+            }
+        }
+    }
+
+    files
+}
+
+/// Existing files touched by the last `commits` commits, canonicalized. Fewer commits
+/// are looked at if the history is shorter.
+#[cfg_attr(not(feature = "ml"), allow(dead_code))] // Only used by `yl analyze drift`
+pub fn files_in_recent_commits(dir: &Path, commits: usize) -> Result<BTreeSet<PathBuf>> {
+    let root = git(dir, &["rev-parse", "--show-toplevel"])?;
+    let root = PathBuf::from(root.trim());
+    let count = format!("--max-count={commits}");
+    let log = git(
+        dir,
+        &[
+            "log",
+            &count,
+            "--name-only",
+            "-z",
+            "--format=",
+            "--no-renames",
+        ],
+    )?;
+
+    Ok(log
+        .split('\0')
+        .map(|path| path.trim_start_matches('\n'))
+        .filter(|path| !path.is_empty())
+        .filter_map(|line| root.join(line).canonicalize().ok())
+        .collect())
+}
+
+/// The `user.name` configured for git in `dir`, if any
+pub fn user_name(dir: &Path) -> Option<String> {
+    let name = git(dir, &["config", "user.name"]).ok()?;
+    let name = name.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// The commit checked out in `dir`, if it is inside a git repository with commits
+pub fn head_commit(dir: &Path) -> Option<String> {
+    let commit = git(dir, &["rev-parse", "HEAD"]).ok()?;
+    Some(commit.trim().to_string())
+}
+
+/// Run a git command in `dir` and return its standard output
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .context("Failed to run git")?;
+
+    if !output.status.success() {
+        return Err(eyre::eyre!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    String::from_utf8(output.stdout).context("git produced invalid UTF-8 output")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn run(dir: &Path, args: &[&str]) {
+        git(dir, args).expect("git command failed");
+    }
+
+    #[test]
+    fn test_changed_files() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let dir = temp_dir.path();
+        run(dir, &["init", "-q"]);
+        run(dir, &["config", "user.email", "test@example.com"]);
+        run(dir, &["config", "user.name", "test"]);
+
+        fs::write(dir.join("a.yaml"), "a: 1\n").unwrap();
+        fs::write(dir.join("b.yaml"), "b: 1\n").unwrap();
+        fs::write(dir.join("c.yaml"), "c: 1\n").unwrap();
+        run(dir, &["add", "."]);
+        run(dir, &["commit", "-q", "-m", "initial"]);
+
+        fs::write(dir.join("a.yaml"), "a: 2\n").unwrap();
+        fs::remove_file(dir.join("c.yaml")).unwrap();
+
+        let changed = changed_files(dir, "HEAD").unwrap();
+        assert_eq!(changed.len(), 1);
+        let a = dir.join("a.yaml").canonicalize().unwrap();
+        assert_eq!(changed[&a].added, BTreeSet::from([1]));
+        assert_eq!(head_commit(dir).map(|commit| commit.len()), Some(40));
+
+        // Prefix settings of the user's git config do not hide the added lines
+        for setting in ["diff.mnemonicPrefix", "diff.noprefix"] {
+            run(dir, &["config", setting, "true"]);
+            let changed = changed_files(dir, "HEAD").unwrap();
+            assert_eq!(changed[&a].added, BTreeSet::from([1]), "{setting}");
+        }
+    }
+
+    #[test]
+    fn test_changed_files_since_merge_base_and_staged() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let dir = temp_dir.path();
+        run(dir, &["init", "-q", "-b", "main"]);
+        run(dir, &["config", "user.email", "test@example.com"]);
+        run(dir, &["config", "user.name", "test"]);
+
+        fs::write(dir.join("a.yaml"), "a: 1\n").unwrap();
+        fs::write(dir.join("b.yaml"), "b: 1\n").unwrap();
+        run(dir, &["add", "."]);
+        run(dir, &["commit", "-q", "-m", "initial"]);
+
+        // The feature branch changes a.yaml; main moves on with b.yaml afterwards
+        run(dir, &["checkout", "-q", "-b", "feature"]);
+        fs::write(dir.join("a.yaml"), "a: 2\n").unwrap();
+        run(dir, &["commit", "-q", "-am", "feature"]);
+        run(dir, &["checkout", "-q", "main"]);
+        fs::write(dir.join("b.yaml"), "b: 2\n").unwrap();
+        run(dir, &["commit", "-q", "-am", "main"]);
+        run(dir, &["checkout", "-q", "feature"]);
+
+        let canonical = |name: &str| dir.join(name).canonicalize().unwrap();
+        let since_base = changed_files_since_merge_base(dir, "main").unwrap();
+        assert_eq!(since_base.len(), 1);
+        assert_eq!(since_base[&canonical("a.yaml")].added, BTreeSet::from([1]));
+        assert_eq!(changed_files(dir, "main").unwrap().len(), 2);
+        assert_eq!(
+            files_in_recent_commits(dir, 1).unwrap(),
+            BTreeSet::from([canonical("a.yaml")])
+        );
+        assert_eq!(files_in_recent_commits(dir, 10).unwrap().len(), 2);
+
+        fs::write(dir.join("b.yaml"), "b: 3\n").unwrap();
+        assert!(staged_files(dir).unwrap().is_empty());
+        run(dir, &["add", "b.yaml"]);
+        let staged = staged_files(dir).unwrap();
+        assert_eq!(staged.len(), 1);
+        assert!(staged.contains_key(&canonical("b.yaml")));
+    }
+
+    #[test]
+    fn test_parse_unified_diff() {
+        let diff = "diff --git a/app.yaml b/app.yaml
+index 1111111..2222222 100644
+--- a/app.yaml
++++ b/app.yaml
+@@ -1,4 +1,5 @@
+ name: app
+-replicas: 1
++replicas: 2
++++ not a header: 1
+ image: app
+ port: 80
+@@ -10 +11,0 @@ port: 80
+-debug: true
+diff --git a/old.yaml b/old.yaml
+deleted file mode 100644
+--- a/old.yaml
++++ /dev/null
+@@ -1 +0,0 @@
+-gone: true
+diff --git a/new.yaml b/new.yaml
+--- /dev/null
++++ b/new.yaml
+@@ -0,0 +1 @@
++fresh: true
+\\ No newline at end of file
+";
+        let files = parse_unified_diff(diff);
+
+        assert_eq!(files.len(), 2);
+        let app = &files["app.yaml"];
+        assert_eq!(app.added, BTreeSet::from([2, 3]));
+        assert_eq!(app.context, BTreeSet::from([1, 4, 5]));
+        assert_eq!(app.change(3), Some(LineChange::Added));
+        assert_eq!(app.change(4), Some(LineChange::Context));
+        assert_eq!(app.change(11), None);
+        assert_eq!(files["new.yaml"].added, BTreeSet::from([1]));
+    }
+
+    #[test]
+    fn test_changed_files_renamed_and_quoted() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let dir = temp_dir.path();
+        run(dir, &["init", "-q"]);
+        run(dir, &["config", "user.email", "test@example.com"]);
+        run(dir, &["config", "user.name", "test"]);
+
+        fs::write(dir.join("old.yaml"), "a: 1\nb: 2\nc: 3\nd: 4\n").unwrap();
+        fs::write(dir.join("sp ace.yaml"), "x: 1\n").unwrap();
+        fs::write(dir.join("ünï.yaml"), "y: 1\n").unwrap();
+        run(dir, &["add", "."]);
+        run(dir, &["commit", "-q", "-m", "initial"]);
+
+        run(dir, &["mv", "old.yaml", "new.yaml"]);
+        fs::write(dir.join("new.yaml"), "a: 1\nb: 2\nc: 3\nd: 5\n").unwrap();
+        fs::write(dir.join("sp ace.yaml"), "x: 1\nx2: 2\n").unwrap();
+        fs::write(dir.join("ünï.yaml"), "y: 1\ny2: 2\n").unwrap();
+        run(dir, &["add", "."]);
+
+        let canonical = |name: &str| dir.join(name).canonicalize().unwrap();
+        let staged = staged_files(dir).unwrap();
+        assert_eq!(staged.len(), 3);
+        assert_eq!(staged[&canonical("new.yaml")].added, BTreeSet::from([4]));
+        assert_eq!(staged[&canonical("sp ace.yaml")].added, BTreeSet::from([2]));
+        assert_eq!(staged[&canonical("ünï.yaml")].added, BTreeSet::from([2]));
+
+        run(dir, &["commit", "-q", "-m", "rename"]);
+        assert!(
+            files_in_recent_commits(dir, 1)
+                .unwrap()
+                .contains(&canonical("ünï.yaml"))
+        );
+    }
+
+    #[test]
+    fn test_parse_name_status() {
+        // git diff --name-status -z -C
+        let output = "C075\0old.yaml\0copy.yaml\0R075\0old.yaml\0new.yaml\0\
+                      R100\0sp ace.yaml\0with space.yaml\0M\0ünï.yaml\0D\0gone.yaml\0\
+                      A\0fresh.yaml\0";
+        assert_eq!(
+            parse_name_status(output),
+            vec![
+                "copy.yaml",
+                "new.yaml",
+                "with space.yaml",
+                "ünï.yaml",
+                "fresh.yaml"
+            ]
+        );
+        assert!(parse_name_status("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_unified_diff_paths() {
+        let diff = "diff --git a/old.yaml b/new.yaml
+similarity index 75%
+rename from old.yaml
+rename to new.yaml
+--- a/old.yaml
++++ b/new.yaml
+@@ -3,2 +3,2 @@ b: 2
+ c: 3
+-d: 4
++d: 5
+diff --git a/sp ace.yaml b/with space.yaml
+rename from sp ace.yaml
+rename to with space.yaml
+--- a/sp ace.yaml\t
++++ b/with space.yaml\t
+@@ -1 +1,2 @@
+ x: 1
++x: 2
+diff --git \"a/\\303\\274n\\303\\257.yaml\" \"b/\\303\\274n\\303\\257.yaml\"
+--- \"a/\\303\\274n\\303\\257.yaml\"
++++ \"b/\\303\\274n\\303\\257.yaml\"
+@@ -1 +1,2 @@
+ y: 1
++y: 2
+";
+        let files = parse_unified_diff(diff);
+
+        assert_eq!(files.len(), 3);
+        assert_eq!(files["new.yaml"].added, BTreeSet::from([4]));
+        assert_eq!(files["with space.yaml"].added, BTreeSet::from([2]));
+        assert_eq!(files["ünï.yaml"].added, BTreeSet::from([2]));
+        assert_eq!(
+            new_path("\"b/tab\\there \\\"q\\\".yaml\""),
+            Some("tab\there \"q\".yaml".to_string())
+        );
+        assert_eq!(new_path("/dev/null"), None);
+    }
+
+    #[test]
+    fn test_changed_files_invalid_reference() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        run(temp_dir.path(), &["init", "-q"]);
+
+        assert!(changed_files(temp_dir.path(), "no-such-ref").is_err());
+        assert!(changed_files_since_merge_base(temp_dir.path(), "no-such-ref").is_err());
+        assert_eq!(head_commit(temp_dir.path()), None);
+    }
+}
@@ -1,7 +0,0 @@
-pub mod context;
-pub mod engine;
-pub mod problem;
-
-pub use context::LintContext;
-pub use engine::Linter;
-pub use problem::{Level, Problem};
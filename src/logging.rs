@@ -0,0 +1,223 @@
+//! Logging to stderr for `tracing` events emitted by yl and yl-core
+
+use crate::cli::LogFormat;
+use chrono::{SecondsFormat, Utc};
+use eyre::{Context, Result};
+use serde_json::{Map, Value, json};
+use std::fmt;
+use std::io::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+/// Destination for formatted log lines
+type Output = Box<dyn Fn(&str) + Send + Sync>;
+
+/// Install the logger as the global `tracing` subscriber
+pub fn init(verbosity: u8, format: LogFormat) -> Result<()> {
+    let output: Output = Box::new(|line| {
+        let _ = writeln!(std::io::stderr().lock(), "{line}");
+    });
+
+    tracing::subscriber::set_global_default(Logger::new(level_for(verbosity), format, output))
+        .context("Failed to install logger")
+}
+
+/// Map the number of `-v` flags to the most verbose level that is logged
+fn level_for(verbosity: u8) -> Level {
+    match verbosity {
+        0 => Level::WARN,
+        1 => Level::INFO,
+        2 => Level::DEBUG,
+        _ => Level::TRACE,
+    }
+}
+
+/// Minimal subscriber writing one line per event; spans are not tracked
+struct Logger {
+    max_level: Level,
+    format: LogFormat,
+    output: Output,
+    next_span: AtomicU64,
+}
+
+impl Logger {
+    fn new(max_level: Level, format: LogFormat, output: Output) -> Self {
+        Self {
+            max_level,
+            format,
+            output,
+            next_span: AtomicU64::new(1),
+        }
+    }
+
+    fn format_event(&self, event: &Event<'_>) -> String {
+        let metadata = event.metadata();
+        let mut fields = FieldVisitor::default();
+        event.record(&mut fields);
+
+        let timestamp = Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true);
+
+        match self.format {
+            LogFormat::Text => {
+                let mut line = format!(
+                    "{timestamp} {:>5} {}: {}",
+                    metadata.level(),
+                    metadata.target(),
+                    fields.message
+                );
+                for (name, value) in &fields.values {
+                    match value {
+                        Value::String(s) => line.push_str(&format!(" {name}={s}")),
+                        other => line.push_str(&format!(" {name}={other}")),
+                    }
+                }
+                line
+            }
+            LogFormat::Json => {
+                let mut values = fields.values;
+                values.insert("message".to_string(), Value::String(fields.message));
+
+                json!({
+                    "timestamp": timestamp,
+                    "level": metadata.level().as_str(),
+                    "target": metadata.target(),
+                    "fields": values,
+                })
+                .to_string()
+            }
+        }
+    }
+}
+
+impl Subscriber for Logger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        // Dependencies (tower-lsp, tokio, ...) log too; only our own crates are of interest
+        metadata.level() <= &self.max_level && metadata.target().starts_with("yl")
+    }
+
+    fn new_span(&self, _span: &Attributes<'_>) -> Id {
+        Id::from_u64(self.next_span.fetch_add(1, Ordering::Relaxed))
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        (self.output)(&self.format_event(event));
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+/// Collects the message and structured fields of an event
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    values: Map<String, Value>,
+}
+
+impl FieldVisitor {
+    fn insert(&mut self, field: &Field, value: Value) {
+        self.values.insert(field.name().to_string(), value);
+    }
+}
+
+impl Visit for FieldVisitor {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, json!(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, json!(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, json!(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, json!(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            self.insert(field, json!(value));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{value:?}");
+        } else {
+            self.insert(field, Value::String(format!("{value:?}")));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    fn capture(level: Level, format: LogFormat, log: impl FnOnce()) -> Vec<String> {
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&lines);
+        let output: Output = Box::new(move |line| sink.lock().unwrap().push(line.to_string()));
+
+        tracing::subscriber::with_default(Logger::new(level, format, output), log);
+
+        Arc::try_unwrap(lines).unwrap().into_inner().unwrap()
+    }
+
+    #[test]
+    fn test_level_for() {
+        assert_eq!(level_for(0), Level::WARN);
+        assert_eq!(level_for(1), Level::INFO);
+        assert_eq!(level_for(2), Level::DEBUG);
+        assert_eq!(level_for(5), Level::TRACE);
+    }
+
+    #[test]
+    fn test_text_format() {
+        let lines = capture(Level::DEBUG, LogFormat::Text, || {
+            tracing::debug!(file = "a.yaml", problems = 2, "linted file");
+            tracing::trace!("too verbose");
+        });
+
+        assert_eq!(lines.len(), 1);
+        assert!(
+            lines[0].ends_with("DEBUG yl::logging::tests: linted file file=a.yaml problems=2"),
+            "{}",
+            lines[0]
+        );
+    }
+
+    #[test]
+    fn test_json_format() {
+        let lines = capture(Level::INFO, LogFormat::Json, || {
+            tracing::info!(files = 3, "lint finished");
+        });
+
+        let entry: Value = serde_json::from_str(&lines[0]).unwrap();
+        assert_eq!(entry["level"], "INFO");
+        assert_eq!(entry["target"], "yl::logging::tests");
+        assert_eq!(entry["fields"]["message"], "lint finished");
+        assert_eq!(entry["fields"]["files"], 3);
+    }
+
+    #[test]
+    fn test_other_targets_ignored() {
+        let lines = capture(Level::TRACE, LogFormat::Text, || {
+            tracing::warn!(target: "tower_lsp", "not ours");
+        });
+
+        assert!(lines.is_empty());
+    }
+}
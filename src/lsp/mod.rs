@@ -1,35 +1,365 @@
-use crate::config::Config;
-use crate::linter::{Level, Linter, Problem};
+use crate::codes;
+use crate::config::{CONFIG_FILE_NAMES, Config, ConfigReload, ConfigWatcher};
+use crate::fixes::FixEngine;
+use crate::linter::{Level, LintContext, Linter, Problem};
+use crate::policy_types::TeamPolicy;
+use crate::rules::{ParamType, RuleRegistry};
 use eyre::Result;
+use regex::Regex;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock};
 use tokio::sync::Mutex;
 use tower_lsp::jsonrpc::Result as LspResult;
 use tower_lsp::lsp_types::*;
 use tower_lsp::{Client, LanguageServer, LspService, Server};
 
+/// Cancellation flags of lints still running, keyed by document
+type InFlightLints = Arc<std::sync::Mutex<HashMap<Url, Arc<AtomicBool>>>>;
+
+/// Command applying all auto-fixes to a document, with the document URI as argument
+const FIX_ALL_COMMAND: &str = "yl.fixAll";
+/// Command returning the resolved configuration of a document (URI argument) as JSON
+const SHOW_EFFECTIVE_CONFIG_COMMAND: &str = "yl.showEffectiveConfig";
+
+/// Rules whose names, parameters and values are completed in directives
+static RULES: LazyLock<RuleRegistry> = LazyLock::new(RuleRegistry::with_default_rules);
+
+/// A `yl:set`, `yl:configure` or `yl:config` directive up to the cursor
+static SETTING_DIRECTIVE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"#\s*yl:(configure|config|set)\s+(.*)$").expect("valid regex"));
+
 /// YL Language Server for editor integration
 pub struct YlLanguageServer {
     client: Client,
-    linter: Arc<Mutex<Linter>>,
+    /// Current linter; lints take a clone of the `Arc` so a config reload never waits on them
+    linter: Arc<Mutex<Arc<Linter>>>,
     document_map: Arc<Mutex<HashMap<Url, String>>>,
+    config_watcher: Arc<Mutex<ConfigWatcher>>,
+    in_flight: InFlightLints,
+    /// Team policy the configuration is checked against, with violations published on
+    /// the configuration file
+    policy: Option<PathBuf>,
+    /// Document the policy violations were last published on
+    policy_uri: Arc<Mutex<Option<Url>>>,
 }
 
 impl YlLanguageServer {
-    /// Create a new YL language server
-    pub fn new(client: Client) -> Self {
-        let config = Config::default();
+    /// Create a new YL language server, checking the configuration against `policy`
+    pub fn new(client: Client, policy: Option<PathBuf>) -> Self {
+        let config = Config::load(None)
+            .and_then(|mut config| config.expand_env().map(|()| config))
+            .unwrap_or_default();
+        let config_watcher = ConfigWatcher::new(None, config.clone());
         let linter = Linter::new(config);
 
         Self {
             client,
-            linter: Arc::new(Mutex::new(linter)),
+            linter: Arc::new(Mutex::new(Arc::new(linter))),
             document_map: Arc::new(Mutex::new(HashMap::new())),
+            config_watcher: Arc::new(Mutex::new(config_watcher)),
+            in_flight: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            policy: policy.map(|path| std::path::absolute(&path).unwrap_or(path)),
+            policy_uri: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Ask the client to notify us when configuration files change
+    async fn watch_config_files(&self) -> Result<()> {
+        let watchers = CONFIG_FILE_NAMES
+            .iter()
+            .chain(&[".editorconfig"])
+            .map(|name| format!("**/{name}"))
+            .chain(self.policy.iter().map(|path| path.display().to_string()))
+            .map(|pattern| FileSystemWatcher {
+                glob_pattern: GlobPattern::String(pattern),
+                kind: None,
+            })
+            .collect();
+        let options = DidChangeWatchedFilesRegistrationOptions { watchers };
+
+        self.client
+            .register_capability(vec![Registration {
+                id: "yl-config-watcher".to_string(),
+                method: "workspace/didChangeWatchedFiles".to_string(),
+                register_options: Some(serde_json::to_value(options)?),
+            }])
+            .await?;
+
+        Ok(())
+    }
+
+    /// Reload the configuration after a config file changed and re-lint open documents
+    async fn reload_config(&self) {
+        let reload = self.config_watcher.lock().await.poll();
+
+        match reload {
+            ConfigReload::Unchanged => {
+                // Only nested configs or .editorconfig changed
+                self.linter.lock().await.clear_config_cache();
+                self.client
+                    .log_message(
+                        MessageType::INFO,
+                        "yl configuration files changed, re-linting open documents",
+                    )
+                    .await;
+            }
+            ConfigReload::Reloaded { config, changes } => {
+                *self.linter.lock().await = Arc::new(Linter::new(*config));
+
+                let message = if changes.is_empty() {
+                    "yl configuration reloaded".to_string()
+                } else {
+                    format!("yl configuration reloaded: {}", changes.join(", "))
+                };
+                self.client.show_message(MessageType::INFO, message).await;
+            }
+            ConfigReload::Invalid(e) => {
+                self.client
+                    .show_message(
+                        MessageType::WARNING,
+                        format!("yl configuration is invalid, keeping the previous one: {e:#}"),
+                    )
+                    .await;
+                return;
+            }
+        }
+
+        let documents: Vec<(Url, String)> = self
+            .document_map
+            .lock()
+            .await
+            .iter()
+            .map(|(uri, content)| (uri.clone(), content.clone()))
+            .collect();
+
+        for (uri, content) in documents {
+            self.schedule_lint(uri, content, None).await;
+        }
+        self.publish_policy_diagnostics().await;
+    }
+
+    /// Whether a URI refers to a file whose change affects the diagnostics of every
+    /// document: a configuration file or the policy file
+    fn affects_all_documents(&self, uri: &Url) -> bool {
+        Self::is_config_uri(uri)
+            || uri
+                .to_file_path()
+                .is_ok_and(|path| self.policy.as_deref() == Some(path.as_path()))
+    }
+
+    /// Check the configuration against the policy and publish the violations on the
+    /// configuration file, clearing those published on a previous configuration file
+    async fn publish_policy_diagnostics(&self) {
+        let Some(policy_path) = &self.policy else {
+            return;
+        };
+
+        let (config, config_path) = {
+            let watcher = self.config_watcher.lock().await;
+            (
+                watcher.config().clone(),
+                watcher.watched_path().to_path_buf(),
+            )
+        };
+        let problems = match Self::policy_problems(policy_path, &config, &config_path) {
+            Ok(problems) => problems,
+            Err(e) => {
+                self.client
+                    .show_message(MessageType::WARNING, format!("{e:#}"))
+                    .await;
+                return;
+            }
+        };
+
+        let path = if config_path.is_file() {
+            std::path::absolute(&config_path).unwrap_or(config_path)
+        } else {
+            policy_path.clone()
+        };
+        let Ok(uri) = Url::from_file_path(&path) else {
+            return;
+        };
+        let content = std::fs::read_to_string(&path).unwrap_or_default();
+
+        let previous = self.policy_uri.lock().await.replace(uri.clone());
+        if let Some(previous) = previous.filter(|previous| *previous != uri) {
+            self.client
+                .publish_diagnostics(previous, vec![], None)
+                .await;
+        }
+        self.client
+            .publish_diagnostics(uri, Self::problems_to_diagnostics(&content, problems), None)
+            .await;
+    }
+
+    /// Policy violations of a configuration, positioned in its file when there is one
+    fn policy_problems(
+        policy_path: &Path,
+        config: &Config,
+        config_path: &Path,
+    ) -> Result<Vec<Problem>> {
+        let policy = TeamPolicy::load(policy_path)?;
+        let content = std::fs::read_to_string(config_path).ok();
+        Ok(policy
+            .validate(config, &RuleRegistry::with_default_rules())
+            .iter()
+            .map(|violation| violation.to_problem(content.as_deref()))
+            .collect())
+    }
+
+    /// Document URI passed as the first argument of a command
+    fn command_uri(arguments: &[serde_json::Value]) -> LspResult<Url> {
+        arguments
+            .first()
+            .and_then(|argument| serde_json::from_value(argument.clone()).ok())
+            .ok_or_else(|| tower_lsp::jsonrpc::Error::invalid_params("expected a document URI"))
+    }
+
+    /// Apply all auto-fixes to an open document through a workspace edit
+    async fn fix_all(&self, uri: Url) -> LspResult<()> {
+        let Some(content) = self.document_map.lock().await.get(&uri).cloned() else {
+            return Err(tower_lsp::jsonrpc::Error::invalid_params(format!(
+                "document is not open: {uri}"
+            )));
+        };
+        let path = uri
+            .to_file_path()
+            .map_err(|_| tower_lsp::jsonrpc::Error::invalid_params("Invalid file path"))?;
+
+        let linter = Arc::clone(&*self.linter.lock().await);
+        let original = content.clone();
+        let fixed = tokio::task::spawn_blocking(move || {
+            linter.fix_content(&path, &content, &FixEngine::new())
+        })
+        .await
+        .map_err(|e| tower_lsp::jsonrpc::Error::invalid_params(e.to_string()))?
+        .map_err(|e| tower_lsp::jsonrpc::Error::invalid_params(format!("{e:#}")))?;
+
+        if fixed.content == original {
+            return Ok(());
+        }
+        let edit = TextEdit {
+            range: Self::full_range(&original),
+            new_text: fixed.content,
+        };
+        let changes = HashMap::from([(uri, vec![edit])]);
+        if let Err(e) = self
+            .client
+            .apply_edit(WorkspaceEdit {
+                changes: Some(changes),
+                ..Default::default()
+            })
+            .await
+        {
+            self.client
+                .log_message(MessageType::ERROR, format!("Applying fixes failed: {e}"))
+                .await;
         }
+        Ok(())
+    }
+
+    /// Range covering all of a document's content
+    fn full_range(content: &str) -> Range {
+        let lines = content.matches('\n').count();
+        let last_line = content.rsplit('\n').next().unwrap_or_default();
+        Range::new(
+            Position::new(0, 0),
+            Position::new(lines as u32, last_line.encode_utf16().count() as u32),
+        )
+    }
+
+    /// Completions for the directive being typed on a line, up to the cursor: rule IDs,
+    /// then parameter names from the rule's schema, then the parameter's allowed values
+    fn directive_completions(registry: &RuleRegistry, prefix: &str) -> Vec<CompletionItem> {
+        let Some(captures) = SETTING_DIRECTIVE.captures(prefix) else {
+            return Vec::new();
+        };
+        let args = &captures[2];
+        let words: Vec<&str> = args.split_whitespace().collect();
+        let typing_word = !args.is_empty() && !args.ends_with(char::is_whitespace);
+
+        // `yl:set rule.param=value`, or `yl:configure rule param=value ...`
+        let (rule_id, setting) = if &captures[1] == "set" {
+            match args.split_once('.') {
+                Some((rule_id, setting)) if words.len() == 1 => (rule_id, setting),
+                None if words.len() <= 1 && (typing_word || words.is_empty()) => {
+                    return Self::rule_completions(registry);
+                }
+                _ => return Vec::new(),
+            }
+        } else {
+            if words.is_empty() || (words.len() == 1 && typing_word) {
+                return Self::rule_completions(registry);
+            }
+            let setting = if typing_word {
+                words[words.len() - 1]
+            } else {
+                ""
+            };
+            (words[0], setting)
+        };
+
+        let schema = registry.param_schema(rule_id);
+        let Some((name, _)) = setting.split_once('=') else {
+            return schema
+                .iter()
+                .map(|param| CompletionItem {
+                    label: param.name.clone(),
+                    kind: Some(CompletionItemKind::PROPERTY),
+                    detail: Some(format!("{}, default: {}", param.kind, param.default)),
+                    documentation: (!param.description.is_empty())
+                        .then(|| Documentation::String(param.description.clone())),
+                    insert_text: Some(format!("{}=", param.name)),
+                    ..Default::default()
+                })
+                .collect();
+        };
+
+        let Some(param) = schema.iter().find(|param| param.name == name) else {
+            return Vec::new();
+        };
+        let values = match param.kind {
+            ParamType::Boolean => vec!["true".to_string(), "false".to_string()],
+            _ => param.allowed.clone(),
+        };
+        values
+            .into_iter()
+            .map(|value| CompletionItem {
+                label: value,
+                kind: Some(CompletionItemKind::VALUE),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    /// Every rule, for completing a directive's rule ID
+    fn rule_completions(registry: &RuleRegistry) -> Vec<CompletionItem> {
+        let mut rules = registry.rules();
+        rules.sort_by_key(|rule| rule.id());
+        rules
+            .into_iter()
+            .map(|rule| CompletionItem {
+                label: rule.id().to_string(),
+                kind: Some(CompletionItemKind::MODULE),
+                detail: Some(rule.description().to_string()),
+                ..Default::default()
+            })
+            .collect()
     }
 
-    /// Convert YL problems to LSP diagnostics
-    fn problems_to_diagnostics(&self, problems: Vec<Problem>) -> Vec<Diagnostic> {
+    /// Whether a document URI refers to a configuration file
+    fn is_config_uri(uri: &Url) -> bool {
+        uri.to_file_path()
+            .is_ok_and(|path| ConfigWatcher::is_config_file(&path))
+    }
+
+    /// Convert YL problems to LSP diagnostics, mapping character columns to the
+    /// UTF-16 offsets LSP positions are measured in
+    fn problems_to_diagnostics(content: &str, problems: Vec<Problem>) -> Vec<Diagnostic> {
+        let lines: Vec<&str> = content.lines().collect();
+
         problems
             .into_iter()
             .map(|problem| {
@@ -39,22 +369,25 @@ impl YlLanguageServer {
                     Level::Info => DiagnosticSeverity::INFORMATION,
                 };
 
+                let line_index = problem.line.saturating_sub(1);
+                let line = lines.get(line_index).copied().unwrap_or_default();
+                let start = LintContext::utf16_offset(line, problem.column);
+                let end = LintContext::utf16_offset(line, problem.column + 1);
+
                 let range = Range::new(
-                    Position::new(
-                        (problem.line as u32).saturating_sub(1),
-                        (problem.column as u32).saturating_sub(1),
-                    ),
-                    Position::new(
-                        (problem.line as u32).saturating_sub(1),
-                        problem.column as u32,
-                    ),
+                    Position::new(line_index as u32, start as u32),
+                    Position::new(line_index as u32, end as u32),
                 );
 
                 Diagnostic {
                     range,
                     severity: Some(severity),
-                    code: Some(NumberOrString::String(problem.rule.clone())),
-                    code_description: None,
+                    code: Some(NumberOrString::String(
+                        problem.code().unwrap_or(&problem.rule).to_string(),
+                    )),
+                    code_description: codes::docs_url(&problem.rule)
+                        .and_then(|url| Url::parse(&url).ok())
+                        .map(|href| CodeDescription { href }),
                     source: Some("yl".to_string()),
                     message: problem.message,
                     related_information: None,
@@ -65,23 +398,86 @@ impl YlLanguageServer {
             .collect()
     }
 
-    /// Lint a document and publish diagnostics
-    async fn lint_and_publish(&self, uri: Url, content: &str) -> Result<()> {
+    /// Lint a document in the background and publish its diagnostics.
+    ///
+    /// A lint of the same document that is still running is cancelled, so only the
+    /// newest content is published and the server keeps handling requests meanwhile.
+    async fn schedule_lint(&self, uri: Url, content: String, version: Option<i32>) {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        if let Some(previous) = self
+            .in_flight
+            .lock()
+            .expect("in-flight lints poisoned")
+            .insert(uri.clone(), Arc::clone(&cancelled))
+        {
+            previous.store(true, Ordering::Relaxed);
+        }
+
+        let linter = Arc::clone(&*self.linter.lock().await);
+        let client = self.client.clone();
+        let in_flight = Arc::clone(&self.in_flight);
+
+        tokio::spawn(async move {
+            let result = Self::lint_document(linter, &uri, content, Arc::clone(&cancelled)).await;
+
+            // Forget this lint unless a newer one has already replaced it
+            {
+                let mut in_flight = in_flight.lock().expect("in-flight lints poisoned");
+                if in_flight
+                    .get(&uri)
+                    .is_some_and(|current| Arc::ptr_eq(current, &cancelled))
+                {
+                    in_flight.remove(&uri);
+                }
+            }
+
+            match result {
+                Ok(Some(diagnostics)) if !cancelled.load(Ordering::Relaxed) => {
+                    client.publish_diagnostics(uri, diagnostics, version).await;
+                }
+                // Superseded by a newer lint of the same document
+                Ok(_) => {}
+                Err(e) => {
+                    client
+                        .log_message(MessageType::ERROR, format!("Linting failed: {e}"))
+                        .await;
+                }
+            }
+        });
+    }
+
+    /// Lint a document on the blocking thread pool, returning `None` if cancelled
+    async fn lint_document(
+        linter: Arc<Linter>,
+        uri: &Url,
+        content: String,
+        cancelled: Arc<AtomicBool>,
+    ) -> Result<Option<Vec<Diagnostic>>> {
         let path = uri
             .to_file_path()
             .map_err(|_| eyre::eyre!("Invalid file path"))?;
 
-        let linter = self.linter.lock().await;
-        let problems = linter.lint_content(&path, content)?;
-        drop(linter);
-
-        let diagnostics = self.problems_to_diagnostics(problems);
+        let diagnostics = tokio::task::spawn_blocking(move || {
+            let problems = linter.lint_content_cancellable(&path, &content, &cancelled)?;
+            Ok::<_, eyre::Report>(
+                problems.map(|problems| Self::problems_to_diagnostics(&content, problems)),
+            )
+        })
+        .await??;
 
-        self.client
-            .publish_diagnostics(uri, diagnostics, None)
-            .await;
+        Ok(diagnostics)
+    }
 
-        Ok(())
+    /// Cancel any running lint of a document
+    fn cancel_lint(&self, uri: &Url) {
+        if let Some(cancelled) = self
+            .in_flight
+            .lock()
+            .expect("in-flight lints poisoned")
+            .remove(uri)
+        {
+            cancelled.store(true, Ordering::Relaxed);
+        }
     }
 }
 
@@ -102,6 +498,21 @@ impl LanguageServer for YlLanguageServer {
                     },
                 )),
                 code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+                completion_provider: Some(CompletionOptions {
+                    trigger_characters: Some(vec![
+                        ".".to_string(),
+                        " ".to_string(),
+                        "=".to_string(),
+                    ]),
+                    ..Default::default()
+                }),
+                execute_command_provider: Some(ExecuteCommandOptions {
+                    commands: vec![
+                        FIX_ALL_COMMAND.to_string(),
+                        SHOW_EFFECTIVE_CONFIG_COMMAND.to_string(),
+                    ],
+                    work_done_progress_options: WorkDoneProgressOptions::default(),
+                }),
                 ..Default::default()
             },
             server_info: Some(ServerInfo {
@@ -115,6 +526,55 @@ impl LanguageServer for YlLanguageServer {
         self.client
             .log_message(MessageType::INFO, "YL Language Server initialized")
             .await;
+
+        if let Err(e) = self.watch_config_files().await {
+            self.client
+                .log_message(
+                    MessageType::WARNING,
+                    format!("Could not watch configuration files: {e}"),
+                )
+                .await;
+        }
+        self.publish_policy_diagnostics().await;
+    }
+
+    async fn did_change_watched_files(&self, params: DidChangeWatchedFilesParams) {
+        if params
+            .changes
+            .iter()
+            .any(|change| self.affects_all_documents(&change.uri))
+        {
+            self.reload_config().await;
+        }
+    }
+
+    async fn execute_command(
+        &self,
+        params: ExecuteCommandParams,
+    ) -> LspResult<Option<serde_json::Value>> {
+        match params.command.as_str() {
+            FIX_ALL_COMMAND => {
+                self.fix_all(Self::command_uri(&params.arguments)?).await?;
+                Ok(None)
+            }
+            SHOW_EFFECTIVE_CONFIG_COMMAND => {
+                let path = Self::command_uri(&params.arguments)?
+                    .to_file_path()
+                    .map_err(|_| tower_lsp::jsonrpc::Error::invalid_params("Invalid file path"))?;
+                let config = self
+                    .linter
+                    .lock()
+                    .await
+                    .config_for(&path)
+                    .map_err(|e| tower_lsp::jsonrpc::Error::invalid_params(format!("{e:#}")))?;
+                serde_json::to_value(&*config)
+                    .map(Some)
+                    .map_err(|e| tower_lsp::jsonrpc::Error::invalid_params(e.to_string()))
+            }
+            command => Err(tower_lsp::jsonrpc::Error::invalid_params(format!(
+                "unknown command: {command}"
+            ))),
+        }
     }
 
     async fn shutdown(&self) -> LspResult<()> {
@@ -124,6 +584,7 @@ impl LanguageServer for YlLanguageServer {
     async fn did_open(&self, params: DidOpenTextDocumentParams) {
         let uri = params.text_document.uri;
         let content = params.text_document.text;
+        let version = params.text_document.version;
 
         // Store document content
         self.document_map
@@ -132,15 +593,12 @@ impl LanguageServer for YlLanguageServer {
             .insert(uri.clone(), content.clone());
 
         // Lint and publish diagnostics
-        if let Err(e) = self.lint_and_publish(uri, &content).await {
-            self.client
-                .log_message(MessageType::ERROR, format!("Linting failed: {e}"))
-                .await;
-        }
+        self.schedule_lint(uri, content, Some(version)).await;
     }
 
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
         let uri = params.text_document.uri;
+        let version = params.text_document.version;
 
         if let Some(change) = params.content_changes.into_iter().next() {
             let content = change.text;
@@ -151,25 +609,22 @@ impl LanguageServer for YlLanguageServer {
                 .await
                 .insert(uri.clone(), content.clone());
 
-            // Lint and publish diagnostics
-            if let Err(e) = self.lint_and_publish(uri, &content).await {
-                self.client
-                    .log_message(MessageType::ERROR, format!("Linting failed: {e}"))
-                    .await;
-            }
+            // Lint and publish diagnostics, cancelling the lint of the previous content
+            self.schedule_lint(uri, content, Some(version)).await;
         }
     }
 
     async fn did_save(&self, params: DidSaveTextDocumentParams) {
         let uri = params.text_document.uri;
 
+        // Clients that cannot watch files still report saves of open config files
+        if self.affects_all_documents(&uri) {
+            self.reload_config().await;
+        }
+
         if let Some(content) = self.document_map.lock().await.get(&uri).cloned() {
             // Re-lint on save
-            if let Err(e) = self.lint_and_publish(uri, &content).await {
-                self.client
-                    .log_message(MessageType::ERROR, format!("Linting failed: {e}"))
-                    .await;
-            }
+            self.schedule_lint(uri, content, None).await;
         }
     }
 
@@ -177,10 +632,35 @@ impl LanguageServer for YlLanguageServer {
         let uri = params.text_document.uri;
 
         // Remove document from memory and clear diagnostics
+        self.cancel_lint(&uri);
         self.document_map.lock().await.remove(&uri);
         self.client.publish_diagnostics(uri, vec![], None).await;
     }
 
+    async fn completion(&self, params: CompletionParams) -> LspResult<Option<CompletionResponse>> {
+        let position = params.text_document_position.position;
+        let uri = params.text_document_position.text_document.uri;
+        let Some(content) = self.document_map.lock().await.get(&uri).cloned() else {
+            return Ok(None);
+        };
+        let Some(line) = content.lines().nth(position.line as usize) else {
+            return Ok(None);
+        };
+
+        // The cursor's character is counted in UTF-16 code units
+        let mut units = 0;
+        let end = line
+            .char_indices()
+            .find(|(_, c)| {
+                units += c.len_utf16();
+                units > position.character as usize
+            })
+            .map_or(line.len(), |(index, _)| index);
+
+        let items = Self::directive_completions(&RULES, &line[..end]);
+        Ok((!items.is_empty()).then_some(CompletionResponse::Array(items)))
+    }
+
     async fn code_action(&self, params: CodeActionParams) -> LspResult<Option<CodeActionResponse>> {
         let uri = params.text_document.uri;
         let _range = params.range;
@@ -189,7 +669,9 @@ impl LanguageServer for YlLanguageServer {
 
         // Add disable rule actions for diagnostics in range
         for diagnostic in &params.context.diagnostics {
-            if let Some(NumberOrString::String(rule_id)) = &diagnostic.code {
+            if let Some(NumberOrString::String(code)) = &diagnostic.code {
+                // Directives name the rule, not the code
+                let rule_id = codes::rule_of(code).unwrap_or(code);
                 // Disable line action
                 let disable_line_action = CodeAction {
                     title: format!("Disable {rule_id} for this line"),
@@ -239,12 +721,12 @@ impl LanguageServer for YlLanguageServer {
     }
 }
 
-/// Start the LSP server
-pub async fn start_lsp_server() -> Result<()> {
+/// Start the LSP server, checking the configuration against `policy` if given
+pub async fn start_lsp_server(policy: Option<PathBuf>) -> Result<()> {
     let stdin = tokio::io::stdin();
     let stdout = tokio::io::stdout();
 
-    let (service, socket) = LspService::new(YlLanguageServer::new);
+    let (service, socket) = LspService::new(|client| YlLanguageServer::new(client, policy));
 
     Server::new(stdin, stdout, socket).serve(service).await;
 
@@ -265,7 +747,7 @@ mod tests {
 
         // Create a temporary server instance for testing (we'll use a dummy client)
         let (_service, _socket) =
-            tower_lsp::LspService::new(|client| YlLanguageServer::new(client));
+            tower_lsp::LspService::new(|client| YlLanguageServer::new(client, None));
 
         // Test the conversion logic by creating diagnostics manually
         let diagnostics: Vec<Diagnostic> = problems
@@ -291,7 +773,9 @@ mod tests {
                 Diagnostic {
                     range,
                     severity: Some(severity),
-                    code: Some(NumberOrString::String(problem.rule.clone())),
+                    code: Some(NumberOrString::String(
+                        problem.code().unwrap_or(&problem.rule).to_string(),
+                    )),
                     code_description: None,
                     source: Some("yl".to_string()),
                     message: problem.message,
@@ -309,11 +793,123 @@ mod tests {
         assert_eq!(diagnostics[1].message, "Test warning message");
     }
 
+    #[test]
+    fn test_problems_to_diagnostics_utf16_columns() {
+        // "😀" takes two UTF-16 code units, so character column 7 maps to offset 7, not 6
+        let content = "key: value\né: 😀 #comment\n";
+        let problems = vec![Problem::new(
+            2,
+            7,
+            Level::Error,
+            "comments",
+            "missing space",
+        )];
+
+        let diagnostics = YlLanguageServer::problems_to_diagnostics(content, problems);
+
+        assert_eq!(diagnostics[0].range.start, Position::new(1, 7));
+        assert_eq!(diagnostics[0].range.end, Position::new(1, 8));
+        assert_eq!(
+            diagnostics[0].code,
+            Some(NumberOrString::String("YL20600".to_string()))
+        );
+        assert_eq!(
+            diagnostics[0]
+                .code_description
+                .as_ref()
+                .map(|description| description.href.as_str()),
+            Some("https://github.com/scottidler/yl/blob/main/docs/codes.md#comments")
+        );
+    }
+
+    #[test]
+    fn test_directive_completions() {
+        let registry = RuleRegistry::with_default_rules();
+        let labels = |prefix| {
+            YlLanguageServer::directive_completions(&registry, prefix)
+                .into_iter()
+                .map(|item| item.label)
+                .collect::<Vec<_>>()
+        };
+
+        assert!(labels("# yl:set line-").contains(&"line-length".to_string()));
+        assert!(labels("key: value  # yl:configure ").contains(&"truthy".to_string()));
+
+        let params = YlLanguageServer::directive_completions(&registry, "# yl:set line-length.");
+        let max = params.iter().find(|item| item.label == "max").unwrap();
+        assert_eq!(max.detail.as_deref(), Some("integer, default: 80"));
+        assert_eq!(max.insert_text.as_deref(), Some("max="));
+        assert!(labels("# yl:configure line-length max=120 ").contains(&"length-unit".to_string()));
+
+        assert_eq!(
+            labels("# yl:set line-length.length-unit="),
+            vec!["chars", "bytes", "display-width"]
+        );
+        assert_eq!(
+            labels("# yl:config line-length allow-non-breakable-words="),
+            vec!["true", "false"]
+        );
+        assert!(labels("# yl:set line-length.max=").is_empty());
+        assert!(labels("# yl:disable ").is_empty());
+        assert!(labels("key: value").is_empty());
+    }
+
+    #[test]
+    fn test_is_config_uri() {
+        let config = Url::from_file_path("/repo/.yl.yaml").unwrap();
+        let document = Url::from_file_path("/repo/values.yaml").unwrap();
+
+        assert!(YlLanguageServer::is_config_uri(&config));
+        assert!(!YlLanguageServer::is_config_uri(&document));
+    }
+
+    #[test]
+    fn test_full_range() {
+        assert_eq!(
+            YlLanguageServer::full_range("a: 1\nb: é\n"),
+            Range::new(Position::new(0, 0), Position::new(2, 0))
+        );
+        assert_eq!(
+            YlLanguageServer::full_range("a: 1\nb: é"),
+            Range::new(Position::new(0, 0), Position::new(1, 4))
+        );
+    }
+
+    #[test]
+    fn test_command_uri() {
+        let uri = Url::from_file_path("/repo/values.yaml").unwrap();
+        assert_eq!(
+            YlLanguageServer::command_uri(&[serde_json::json!(uri.as_str())]).unwrap(),
+            uri
+        );
+        assert!(YlLanguageServer::command_uri(&[]).is_err());
+        assert!(YlLanguageServer::command_uri(&[serde_json::json!(42)]).is_err());
+    }
+
+    #[test]
+    fn test_policy_problems() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let registry = RuleRegistry::with_default_rules();
+        let mut team = TeamPolicy::from_config("team", "", &Config::default(), &registry, false);
+        team.required_rules = vec!["truthy".to_string()];
+        team.min_severity.clear();
+        let policy = dir.path().join("policy.yaml");
+        std::fs::write(&policy, serde_yaml::to_string(&team).unwrap()).unwrap();
+        let config_path = dir.path().join(".yl.yaml");
+        std::fs::write(&config_path, "rules:\n  truthy:\n    enabled: false\n").unwrap();
+
+        let mut config = Config::default();
+        config.rules.get_mut("truthy").unwrap().enabled = false;
+        let problems = YlLanguageServer::policy_problems(&policy, &config, &config_path).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert_eq!((problems[0].line, problems[0].column), (2, 3));
+    }
+
     #[test]
     fn test_lsp_service_creation() {
         // Test that we can create the LSP service
         let (_service, _socket) =
-            tower_lsp::LspService::new(|client| YlLanguageServer::new(client));
+            tower_lsp::LspService::new(|client| YlLanguageServer::new(client, None));
         // If we get here without panicking, the service was created successfully
         assert!(true);
     }
@@ -1,82 +1,345 @@
 use clap::Parser;
 use eyre::{Context, Result};
+use std::io::Write;
+use tracing::{info, warn};
 
+#[cfg(feature = "ml")]
+mod analyze;
 mod cli;
-mod config;
+mod completions;
+#[cfg(feature = "server")]
+mod daemon;
 mod directives;
-mod fixes;
-mod linter;
+mod git;
+mod logging;
+#[cfg(feature = "lsp")]
 mod lsp;
 mod migration;
 mod output;
-mod parser;
+#[cfg(feature = "plugins")]
 mod plugins;
-mod rules;
-
-use cli::{Cli, Commands, MigrateCommands, PluginCommands};
-use config::Config;
+mod policy_types;
+#[cfg(feature = "server")]
+mod serve;
+#[cfg(feature = "self-update")]
+mod update;
+
+use yl_core::{codes, config, fixes, linter, messages, parser, rules, write};
+
+#[cfg(feature = "ml")]
+use analyze::{DriftReport, IndentationReport};
+#[cfg(feature = "ml")]
+use cli::AnalyzeCommands;
+#[cfg(feature = "server")]
+use cli::ClientCommands;
+#[cfg(feature = "plugins")]
+use cli::PluginCommands;
+use cli::{
+    Cli, Commands, ConfigCommands, GroupBy, MigrateCommands, OutputFormat, OutputOrder,
+    PolicyCommands, ReportCommands,
+};
+use config::{Config, ConfigLayer, ConfigSource, WebhookConfig};
+use directives::SuppressionReport;
 use fixes::FixEngine;
-use linter::Linter;
+#[cfg(feature = "analytics")]
+use linter::LintAnalytics;
+use linter::{EffectiveConfig, Extractor, HealthScore, Linter, ScoreWeights};
+use messages::{Catalog, Message};
 use migration::YamllintMigrator;
+use output::manifest::{self, ConfigRecord, PolicyRecord, RuleRecord, RunManifest};
+use output::store::{RunInfo, open_store};
+use output::webhook::{RunSummary, post_summary};
 use output::{LintStats, get_formatter};
+#[cfg(feature = "plugins")]
 use plugins::PluginManager;
-use rules::{ConfigValue, RuleRegistry};
+#[cfg(feature = "plugins")]
+use plugins::scaffold::{CoreDependency, scaffold};
+use policy_types::TeamPolicy;
+use rules::{ConfigValue, ParamSchema, RuleRegistry};
+use write::write_atomic;
 
-#[tokio::main]
-async fn main() -> Result<()> {
+fn main() -> Result<()> {
     // Parse CLI arguments
     let cli = Cli::parse();
+    logging::init(cli.verbose, cli.log_format)?;
+    if let Some(lang) = cli.lang.clone().or_else(|| std::env::var("YL_LANG").ok()) {
+        messages::set_catalog(Catalog::for_lang(&lang)?);
+    }
 
     // Handle subcommands
     if let Some(command) = &cli.command {
-        return handle_subcommand(command).await;
+        return handle_subcommand(command, &cli);
     }
 
     // Load configuration
-    let mut config = Config::load(cli.config.as_ref()).context("Failed to load configuration")?;
+    let mut config = load_config(&cli)?;
 
     // Apply CLI overrides to configuration
     apply_cli_overrides(&mut config, &cli)?;
 
     // Handle special commands
     if cli.list_rules {
-        return list_rules();
+        return list_rules(&cli.format);
     }
 
     if cli.show_config {
         return show_config(&config);
     }
 
-    // Create linter
-    let linter = Linter::new(config);
+    // Conflicting rule settings are reported, but do not stop the run
+    for conflict in RuleRegistry::with_default_rules().conflicts(&config) {
+        warn!("conflicting rule settings: {conflict}");
+    }
+
+    let started_at = chrono::Utc::now();
+    let started = std::time::Instant::now();
+
+    // Configuration that breaks the policy is reported like the problems of any other file
+    let policy_result = cli
+        .policy
+        .as_ref()
+        .map(|policy| policy_problems(policy, &config, &cli))
+        .transpose()?;
+
+    // The manifest records the root configuration before the linter takes it over
+    let manifest_config = cli
+        .manifest
+        .as_ref()
+        .map(|_| -> Result<_> {
+            let record = ConfigRecord {
+                path: root_config_path(&cli),
+                profile: cli.profile.clone(),
+                sha256: manifest::config_hash(&config)?,
+            };
+            let policy = cli.policy.as_ref().map(|path| PolicyRecord {
+                path: path.clone(),
+                sha256: output::store::file_hash(path),
+                violations: policy_result
+                    .as_ref()
+                    .map_or(0, |(_, problems)| problems.len()),
+            });
+            Ok((record, manifest::rule_records(&config), policy))
+        })
+        .transpose()?;
+
+    // Formats embedding fixes compute them with the root configuration
+    let formatter = get_formatter(&cli.format, &config);
+    let webhook = config.webhook.clone();
+
+    // Create linter; CLI overrides are re-applied over any nested per-directory configs
+    let overrides = cli.clone();
+    let linter = Linter::new(config)
+        .with_config_discovery(cli.nested_config())
+        .with_config_env_expand(!cli.no_env_expand)
+        .with_follow_symlinks(cli.follow_symlinks)
+        .with_config_overrides(move |config| {
+            // Overrides were already validated against the root configuration above
+            let _ = apply_cli_overrides(config, &overrides);
+        })
+        .with_rule_profiling(cli.profile_rules)
+        .with_max_problems(cli.max_problems)
+        .with_backup_suffix(cli.in_place_backup.clone())
+        .with_suppressed_problems(true)
+        .with_mmap_threshold(cli.mmap_threshold.filter(|&bytes| bytes > 0))
+        .with_jobs(cli.jobs);
+    #[cfg(feature = "analytics")]
+    let analytics = cli
+        .analytics_out
+        .as_ref()
+        .map(|_| std::sync::Arc::new(LintAnalytics::new()));
+    #[cfg(feature = "analytics")]
+    let linter = match &analytics {
+        Some(analytics) => linter.with_observer(analytics.clone()),
+        None => linter,
+    };
+    #[cfg(not(feature = "analytics"))]
+    if cli.analytics_out.is_some() {
+        return Err(eyre::eyre!(
+            "this build of yl does not include run analytics (feature \"analytics\")"
+        ));
+    }
 
     // Get files to lint
     let files = cli.get_files();
 
-    // Perform linting
-    let results = linter.lint_paths(&files).context("Linting failed")?;
+    // Restrict to files changed since the given reference, since the branch forked from
+    // it, or staged for commit, if requested
+    let mut file_paths = linter.collect_files(&files)?;
+    let here = std::path::Path::new(".");
+    let changed = if let Some(reference) = &cli.changed_since {
+        Some(
+            git::changed_files(here, reference)
+                .with_context(|| format!("Failed to list files changed since {reference}"))?,
+        )
+    } else if let Some(reference) = &cli.base {
+        Some(
+            git::changed_files_since_merge_base(here, reference).with_context(|| {
+                format!("Failed to list files changed since the merge-base with {reference}")
+            })?,
+        )
+    } else if cli.staged {
+        Some(git::staged_files(here).context("Failed to list staged files")?)
+    } else {
+        None
+    };
+    if let Some(changed) = &changed {
+        file_paths = select_changed_files(file_paths, changed);
+    }
+
+    // Streaming formats write each file as soon as it is finished, unless sorted or
+    // grouped output was requested
+    let streams =
+        formatter.streams() && cli.output_order == OutputOrder::Parallel && cli.group_by.is_none();
+    let on_file = |path: &std::path::Path, problems: &[linter::Problem]| {
+        if !streams {
+            return;
+        }
+        let mut problems: Vec<_> = problems
+            .iter()
+            .filter(|problem| !problem.suppressed && is_reported(problem, &cli))
+            .cloned()
+            .collect();
+        if let Some(changed) = &changed {
+            annotate_changes(path, &mut problems, changed, cli.only_added_lines);
+        }
+        let output = formatter.format_file(path, &problems);
+        // A closed pipe (e.g. `| head`) must not take down the worker threads
+        if !output.is_empty() {
+            let _ = writeln!(std::io::stdout().lock(), "{output}");
+        }
+    };
+
+    if let Some((path, problems)) = &policy_result {
+        on_file(path, problems);
+    }
+
+    // Perform linting, fixing what can be fixed first if requested
+    let mut results = if cli.fix {
+        linter.fix_files_streaming(&file_paths, &FixEngine::new(), on_file)
+    } else {
+        linter.lint_files_streaming(&file_paths, on_file)
+    }
+    .context("Linting failed")?;
+    if let Some(changed) = &changed {
+        for (path, problems) in &mut results {
+            annotate_changes(path, problems, changed, cli.only_added_lines);
+        }
+    }
+    results.splice(0..0, policy_result);
 
     // Filter results based on CLI options
-    let filtered_results = filter_results(results, &cli);
+    let mut filtered_results = filter_results(results, &cli);
+    if cli.output_order == OutputOrder::Sorted {
+        filtered_results.sort_by(|(a, _), (b, _)| a.cmp(b));
+    }
+    if cli.group_by == Some(GroupBy::Project) {
+        // Files outside any project come last; the sort is stable, so each project keeps
+        // the order above
+        filtered_results.sort_by_cached_key(|(path, _)| {
+            let project = linter.project_for(path);
+            (project.is_none(), project.map(str::to_string))
+        });
+    }
+
+    // Problems suppressed by directives are counted, and listed only in human output on
+    // request; everything else sees the reported problems only
+    let all_results = filtered_results;
+    let filtered_results = without_suppressed(&all_results);
+    let shown_results = if cli.show_suppressed && matches!(cli.format, OutputFormat::Human) {
+        &all_results
+    } else {
+        &filtered_results
+    };
 
     // Format and output results
-    let formatter = get_formatter(&cli.format);
-    let output = formatter.format_results(&filtered_results);
-    println!("{output}");
+    if !streams {
+        let output = formatter.format_results(shown_results);
+        if !output.is_empty() {
+            println!("{output}");
+        }
+    }
 
-    // Calculate statistics and determine exit code
-    let stats = LintStats::from_results(&filtered_results);
-
-    if cli.verbose {
-        eprintln!("Processed {} files", stats.total_files);
-        if stats.has_problems() {
-            eprintln!(
-                "Found {} problems in {} files",
-                stats.total_problems, stats.files_with_problems
-            );
+    if cli.group_by == Some(GroupBy::Project)
+        && matches!(cli.format, OutputFormat::Human | OutputFormat::Compact)
+    {
+        print_project_stats(&LintStats::by_project(&filtered_results, |path| {
+            linter.project_for(path)
+        }));
+    }
+
+    if let Some(max) = cli.max_problems.filter(|_| linter.problem_limit_reached()) {
+        eprintln!("{}", Message::new("summary.stopped").arg("max", max));
+    }
+
+    // Each skipped file is logged at -v; the count is always part of the summary
+    let skipped = linter.skipped_generated().len();
+    if skipped > 0 {
+        let id = if skipped == 1 {
+            "summary.skipped-generated"
+        } else {
+            "summary.skipped-generated-plural"
+        };
+        eprintln!("{}", Message::new(id).arg("count", skipped));
+    }
+
+    // Profile goes to stderr so it never mixes with the lint output
+    if let Some(timings) = linter.rule_timings() {
+        eprintln!("{}", format_rule_timings(&timings, &cli.format)?);
+    }
+
+    #[cfg(feature = "analytics")]
+    if let (Some(dir), Some(analytics)) = (&cli.analytics_out, &analytics) {
+        write_analytics(dir, analytics)?;
+    }
+
+    if cli.store.is_some() || cli.manifest.is_some() {
+        let directory = std::env::current_dir().context("Failed to get current directory")?;
+        let run = RunInfo {
+            started_at,
+            duration: started.elapsed(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_commit: git::head_commit(&directory),
+            directory,
+            arguments: std::env::args().skip(1).collect(),
+        };
+        if let Some(target) = &cli.store {
+            let run_id = open_store(target)?.store_run(&run, &filtered_results)?;
+            info!(run = run_id, "stored results");
+        }
+        if let (Some(path), Some((config, rules, policy))) = (&cli.manifest, manifest_config) {
+            write_manifest(path, &run, config, rules, policy, &all_results, &linter)?;
+        }
+    }
+
+    // An unreachable webhook must not change the outcome of the run
+    if let Some(webhook) = &webhook {
+        let summary = RunSummary::from_results(&filtered_results);
+        if let Err(e) = post_summary(webhook, &summary) {
+            warn!(error = format!("{e:#}"), "webhook failed");
         }
     }
 
+    // Calculate statistics and determine exit code
+    let stats = LintStats::from_results(&all_results);
+
+    if cli.verbose > 0 && stats.suppressed > 0 {
+        let id = if stats.suppressed == 1 {
+            "summary.suppressed-count"
+        } else {
+            "summary.suppressed-count-plural"
+        };
+        eprintln!("{}", Message::new(id).arg("count", stats.suppressed));
+    }
+
+    info!(files = stats.total_files, "processed files");
+    if stats.has_problems() {
+        info!(
+            problems = stats.total_problems,
+            files = stats.files_with_problems,
+            "found problems"
+        );
+    }
+
     // Exit with error code if there are errors
     if stats.has_errors() {
         std::process::exit(1);
@@ -85,6 +348,185 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Check the effective configuration against a team policy. The problems are attributed
+/// to the configuration file, or to the policy file when there is no configuration file.
+fn policy_problems(
+    policy_path: &std::path::Path,
+    config: &Config,
+    cli: &Cli,
+) -> Result<(std::path::PathBuf, Vec<linter::Problem>)> {
+    let policy = TeamPolicy::load(policy_path)?;
+    let violations = policy.validate(config, &RuleRegistry::with_default_rules());
+
+    let config_path = root_config_path(cli);
+    let content = config_path
+        .as_ref()
+        .and_then(|path| std::fs::read_to_string(path).ok());
+
+    let problems = violations
+        .iter()
+        .map(|violation| violation.to_problem(content.as_deref()))
+        .collect();
+    let path = config_path.unwrap_or_else(|| policy_path.to_path_buf());
+    Ok((path, problems))
+}
+
+/// Load the root configuration, with the profile selected by `--profile` applied
+fn load_config(cli: &Cli) -> Result<Config> {
+    let config = load_root_config(cli)?;
+    match &cli.profile {
+        Some(name) => config.with_profile(name),
+        None => Ok(config),
+    }
+}
+
+/// Load the root configuration, with environment variables expanded unless
+/// `--no-env-expand` is given
+fn load_root_config(cli: &Cli) -> Result<Config> {
+    let mut config = Config::load(cli.config.as_ref()).context("Failed to load configuration")?;
+    if !cli.no_env_expand {
+        config.expand_env()?;
+    }
+    Ok(config)
+}
+
+/// The root configuration file `Config::load` reads, if there is one
+fn root_config_path(cli: &Cli) -> Option<std::path::PathBuf> {
+    match &cli.config {
+        Some(path) => Some(path.clone()),
+        None => Config::default_config_path().ok(),
+    }
+    .filter(|path| path.is_file())
+}
+
+/// Handle configuration subcommands
+fn handle_config_command(config_command: &ConfigCommands, cli: &Cli) -> Result<()> {
+    match config_command {
+        ConfigCommands::Show { file } => {
+            let config = load_root_config(cli)?;
+            let profiled = match &cli.profile {
+                Some(name) => Some(config.clone().with_profile(name)?),
+                None => None,
+            };
+            let mut root_config = profiled.clone().unwrap_or_else(|| config.clone());
+            apply_cli_overrides(&mut root_config, cli)?;
+            let Some(file) = file else {
+                return show_config(&root_config);
+            };
+
+            // The root layers end with the loaded root configuration, before CLI overrides
+            let mut root = match root_config_path(cli) {
+                Some(path) => Config::layers(&path)?,
+                None => Vec::new(),
+            };
+            match root.last_mut() {
+                Some(layer) => layer.config = config,
+                None => root.push(ConfigLayer::new(ConfigSource::Default, config)),
+            }
+            if let (Some(name), Some(profiled)) = (&cli.profile, profiled) {
+                root.push(ConfigLayer::new(
+                    ConfigSource::Profile(name.clone()),
+                    profiled,
+                ));
+            }
+
+            let overrides = cli.clone();
+            let linter = Linter::new(root_config)
+                .with_config_discovery(cli.nested_config())
+                .with_config_env_expand(!cli.no_env_expand)
+                .with_config_overrides(move |config| {
+                    let _ = apply_cli_overrides(config, &overrides);
+                });
+            let effective = linter.effective_config(file, root)?;
+
+            let violations: Vec<String> = match &cli.policy {
+                Some(policy) => TeamPolicy::load(policy)?
+                    .validate(
+                        &*linter.config_for(file)?,
+                        &RuleRegistry::with_default_rules(),
+                    )
+                    .into_iter()
+                    .map(|violation| violation.message)
+                    .collect(),
+                None => Vec::new(),
+            };
+
+            match cli.format {
+                OutputFormat::Json
+                | OutputFormat::Ndjson
+                | OutputFormat::Rdjson
+                | OutputFormat::Sarif => {
+                    let mut output = serde_json::to_value(&effective)?;
+                    if cli.policy.is_some() {
+                        output["policy_violations"] = serde_json::json!(violations);
+                    }
+                    println!("{}", serde_json::to_string_pretty(&output)?);
+                }
+                OutputFormat::Human | OutputFormat::Compact => {
+                    print_effective_config(&effective, &violations)
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Print the configuration of a file, annotating each setting with its source
+fn print_effective_config(effective: &EffectiveConfig, violations: &[String]) {
+    // Values are written like YAML flow values, strings unquoted
+    fn inline<T: serde::Serialize>(value: &T) -> String {
+        match serde_json::to_value(value) {
+            Ok(serde_json::Value::String(text)) => text,
+            Ok(value) => value.to_string(),
+            Err(_) => "?".to_string(),
+        }
+    }
+    let line = |indent: usize, key: &str, value: String, source: &ConfigSource| {
+        let setting = format!("{:indent$}{key}: {value}", "");
+        println!("{setting:<48} # {source}");
+    };
+
+    println!("Configuration for {}", effective.path.display());
+    let sources: Vec<String> = effective.sources.iter().map(ToString::to_string).collect();
+    println!("Sources, last wins: {}", sources.join(", "));
+    if let Some(reason) = &effective.skipped {
+        println!("Not linted: {reason}");
+    }
+
+    println!();
+    println!("Settings:");
+    for (key, setting) in &effective.settings {
+        line(2, key, inline(&setting.value), &setting.source);
+    }
+
+    println!();
+    println!("Rules:");
+    for rule in &effective.rules {
+        println!("  {}:", rule.id);
+        line(
+            4,
+            "enabled",
+            rule.enabled.value.to_string(),
+            &rule.enabled.source,
+        );
+        line(4, "level", rule.level.value.to_string(), &rule.level.source);
+        for (key, setting) in &rule.params {
+            line(4, key, inline(&setting.value), &setting.source);
+        }
+        if rule.ignored {
+            println!("    # not run on this file: matched by the rule's ignore patterns");
+        }
+    }
+
+    if !violations.is_empty() {
+        println!();
+        println!("Policy violations:");
+        for violation in violations {
+            println!("  - {violation}");
+        }
+    }
+}
+
 /// Apply CLI overrides to the configuration
 fn apply_cli_overrides(config: &mut Config, cli: &Cli) -> Result<()> {
     let registry = RuleRegistry::with_default_rules();
@@ -135,50 +577,237 @@ fn apply_cli_overrides(config: &mut Config, cli: &Cli) -> Result<()> {
                 return Err(eyre::eyre!("Invalid boolean value for enabled: {}", value));
             }
         } else {
-            // Parse the value based on common types
-            let config_value = parse_config_value(&value)?;
-            rule_config.set_param(param, config_value);
+            // Parse the value as the type of the rule's parameter, if the rule is known
+            let expected = match registry.get(&rule_id) {
+                Some(_) => {
+                    let schema = registry.param_schema(&rule_id);
+                    let expected = schema.iter().find(|p| p.name == param);
+                    if expected.is_none() {
+                        let known: Vec<&str> = schema.iter().map(|p| p.name.as_str()).collect();
+                        return Err(eyre::eyre!(
+                            "Unknown parameter '{}' for rule '{}' (parameters: {})",
+                            param,
+                            rule_id,
+                            known.join(", ")
+                        ));
+                    }
+                    expected
+                }
+                None => None,
+            };
+            let config_value = parse_config_value(&value, expected)
+                .with_context(|| format!("Invalid value for {rule_id}.{param}"))?;
+            rule_config.set_user_param(param, config_value);
+        }
+    }
+
+    // Lint embedded YAML with every extractor
+    if cli.embedded {
+        config.embedded = Extractor::ALL.to_vec();
+    }
+
+    // A webhook URL on the command line keeps the configured timeout and retries
+    if let Some(url) = &cli.webhook {
+        config
+            .webhook
+            .get_or_insert_with(|| WebhookConfig::new(url.as_str()))
+            .url = url.clone();
+    }
+    if let Some(webhook) = config.webhook.as_mut() {
+        if let Some(timeout) = cli.webhook_timeout {
+            webhook.timeout = timeout;
+        }
+        if let Some(retries) = cli.webhook_retries {
+            webhook.retries = retries;
         }
     }
 
     Ok(())
 }
 
-/// Parse a string value into a ConfigValue
-fn parse_config_value(value: &str) -> Result<ConfigValue> {
-    // Try to parse as boolean
+/// Parse a `--set` value as the type `expected` declares, or as the type it looks like
+/// if the parameter is unknown
+fn parse_config_value(value: &str, expected: Option<&ParamSchema>) -> Result<ConfigValue> {
+    match expected {
+        Some(schema) => schema.parse(value),
+        None => parse_untyped_config_value(value),
+    }
+}
+
+/// Parse a value of a parameter no rule declares, by what it looks like
+fn parse_untyped_config_value(value: &str) -> Result<ConfigValue> {
+    if value.starts_with('[') || value.starts_with('{') {
+        return parse_flow_config_value(value);
+    }
     if let Ok(bool_val) = value.parse::<bool>() {
         return Ok(ConfigValue::Bool(bool_val));
     }
-
-    // Try to parse as integer
     if let Ok(int_val) = value.parse::<i64>() {
         return Ok(ConfigValue::Int(int_val));
     }
+    if let Some(float_val) = parse_float(value) {
+        return Ok(ConfigValue::Float(float_val));
+    }
+    if value.contains(',') {
+        return value
+            .split(',')
+            .map(|item| parse_untyped_config_value(item.trim()))
+            .collect::<Result<_>>()
+            .map(ConfigValue::Array);
+    }
 
     // Default to string
     Ok(ConfigValue::String(value.to_string()))
 }
 
+/// Parse a list or map in JSON (or YAML flow) syntax
+fn parse_flow_config_value(value: &str) -> Result<ConfigValue> {
+    serde_yaml::from_str(value).map_err(|e| eyre::eyre!("invalid list or map '{value}': {e}"))
+}
+
+/// Parse a finite number, so that words such as `inf` stay strings
+fn parse_float(value: &str) -> Option<f64> {
+    value.parse::<f64>().ok().filter(|float| float.is_finite())
+}
+
 /// List all available rules
-fn list_rules() -> Result<()> {
-    let registry = RuleRegistry::with_default_rules();
+fn list_rules(format: &OutputFormat) -> Result<()> {
+    print!(
+        "{}",
+        format_rule_list(&RuleRegistry::with_default_rules(), format)?
+    );
+    Ok(())
+}
 
-    println!("Available rules:");
-    println!();
+/// Format the rules of a registry with their parameters, as text or as JSON metadata
+fn format_rule_list(registry: &RuleRegistry, format: &OutputFormat) -> Result<String> {
+    let mut rules = registry.rules();
+    rules.sort_by_key(|rule| rule.id());
+
+    match format {
+        OutputFormat::Json | OutputFormat::Ndjson | OutputFormat::Rdjson | OutputFormat::Sarif => {
+            let rules: Vec<_> = rules
+                .iter()
+                .map(|rule| {
+                    let config = rule.default_config();
+                    serde_json::json!({
+                        "id": rule.id(),
+                        "description": rule.description(),
+                        "category": registry.category(rule.id()).map(|c| c.to_string()),
+                        "phase": rule.phase().to_string(),
+                        "fixable": rule.fixable(),
+                        "enabled": config.enabled,
+                        "level": config.level,
+                        "params": registry.param_schema(rule.id()),
+                    })
+                })
+                .collect();
+            Ok(format!(
+                "{}\n",
+                serde_json::to_string_pretty(&serde_json::json!({ "rules": rules }))?
+            ))
+        }
+        OutputFormat::Human | OutputFormat::Compact => {
+            let mut output = String::from("Available rules:\n\n");
+            for rule in rules {
+                if rule.fixable() {
+                    output.push_str(&format!("  {} (fixable)\n", rule.id()));
+                } else {
+                    output.push_str(&format!("  {}\n", rule.id()));
+                }
+                output.push_str(&format!("    {}\n", rule.description()));
+
+                let schema = registry.param_schema(rule.id());
+                if !schema.is_empty() {
+                    output.push_str("    Parameters:\n");
+                    for param in schema {
+                        output.push_str(&format!(
+                            "      {} ({}, default: {})\n",
+                            param.name, param.kind, param.default
+                        ));
+                    }
+                }
+                output.push('\n');
+            }
+            Ok(output)
+        }
+    }
+}
 
-    for rule in registry.rules() {
-        println!("  {}", rule.id());
-        println!("    {}", rule.description());
+/// Print the full documentation of a rule
+fn explain_rule(rule_id: &str) -> Result<()> {
+    // A problem code such as YL10101 explains the rule it belongs to
+    let rule_id = codes::rule_of(rule_id).unwrap_or(rule_id);
+    let registry = RuleRegistry::with_default_rules();
+    let rule = registry.get(rule_id).ok_or_else(|| {
+        eyre::eyre!("Unknown rule: {rule_id} (run `yl --list-rules` to see available rules)")
+    })?;
+    let docs = rule.docs();
+    let config = rule.default_config();
+    let schema = registry.param_schema(rule_id);
+
+    println!("{}", rule.id());
+    println!("  {}", rule.description());
+    if !docs.details.is_empty() {
+        println!();
+        println!("  {}", docs.details);
+    }
+    println!();
+    println!(
+        "Enabled by default: {}",
+        if config.enabled { "yes" } else { "no" }
+    );
+    println!(
+        "Auto-fix available: {}",
+        if rule.fixable() { "yes" } else { "no" }
+    );
+
+    let rule_codes: Vec<_> = codes::all()
+        .filter(|(_, id)| id.split_once('.').map_or(*id, |(rule, _)| rule) == rule_id)
+        .collect();
+    if !rule_codes.is_empty() {
+        println!();
+        println!("Codes:");
+        for (code, id) in rule_codes {
+            println!(
+                "  {code}  {}",
+                messages::english(id).unwrap_or("any other message")
+            );
+        }
+    }
+    if let Some(url) = codes::docs_url(rule_id) {
+        println!("Documentation: {url}");
+    }
 
-        let config = rule.default_config();
-        if !config.params.is_empty() {
-            println!("    Parameters:");
-            for (key, value) in &config.params {
-                println!("      {key}: {value:?}");
+    if !schema.is_empty() {
+        println!();
+        println!("Parameters:");
+        for param in schema {
+            println!(
+                "  {} ({}, default: {})",
+                param.name, param.kind, param.default
+            );
+            if !param.allowed.is_empty() {
+                println!("    one of: {}", param.allowed.join(", "));
+            }
+            if !param.description.is_empty() {
+                println!("    {}", param.description);
             }
         }
+    }
+
+    for (heading, example) in [
+        ("Failing example", docs.failing),
+        ("Passing example", docs.passing),
+    ] {
+        if example.is_empty() {
+            continue;
+        }
         println!();
+        println!("{heading}:");
+        for line in example.lines() {
+            println!("    {line}");
+        }
     }
 
     Ok(())
@@ -186,7 +815,7 @@ fn list_rules() -> Result<()> {
 
 /// Show the effective configuration
 fn show_config(config: &Config) -> Result<()> {
-    let yaml = serde_yaml::to_string(config).context("Failed to serialize configuration")?;
+    let yaml = config.to_canonical_yaml(&RuleRegistry::with_default_rules(), None)?;
 
     println!("Effective configuration:");
     println!("{yaml}");
@@ -194,28 +823,187 @@ fn show_config(config: &Config) -> Result<()> {
     Ok(())
 }
 
+/// Keep only the files that appear in the set of changed (canonical) paths
+fn select_changed_files(
+    files: Vec<std::path::PathBuf>,
+    changed: &git::ChangedFiles,
+) -> Vec<std::path::PathBuf> {
+    files
+        .into_iter()
+        .filter(|file| {
+            file.canonicalize()
+                .is_ok_and(|canonical| changed.contains_key(&canonical))
+        })
+        .collect()
+}
+
+/// Mark each problem of a changed file as on an added or a context line of the diff, and
+/// with `only_added` drop the others. Files outside the diff, such as the configuration
+/// checked against a policy, are left alone; so are problems about the whole file.
+fn annotate_changes(
+    path: &std::path::Path,
+    problems: &mut Vec<linter::Problem>,
+    changed: &git::ChangedFiles,
+    only_added: bool,
+) {
+    let Some(lines) = path
+        .canonicalize()
+        .ok()
+        .and_then(|canonical| changed.get(&canonical))
+    else {
+        return;
+    };
+
+    for problem in problems.iter_mut() {
+        problem.change = lines.change(problem.line);
+    }
+    if only_added {
+        problems.retain(|problem| {
+            problem.is_file_error() || problem.change == Some(linter::LineChange::Added)
+        });
+    }
+}
+
+/// Format per-rule timings as a table or as JSON
+fn format_rule_timings(timings: &[linter::RuleTiming], format: &OutputFormat) -> Result<String> {
+    match format {
+        OutputFormat::Json | OutputFormat::Ndjson | OutputFormat::Rdjson | OutputFormat::Sarif => {
+            let rules: Vec<_> = timings
+                .iter()
+                .map(|timing| {
+                    serde_json::json!({
+                        "rule": timing.rule,
+                        "time_ms": timing.total_time.as_secs_f64() * 1000.0,
+                        "files": timing.files,
+                        "problems": timing.problems,
+                    })
+                })
+                .collect();
+            Ok(serde_json::to_string_pretty(
+                &serde_json::json!({ "rules": rules }),
+            )?)
+        }
+        OutputFormat::Human | OutputFormat::Compact => {
+            let width = timings
+                .iter()
+                .map(|timing| timing.rule.len())
+                .max()
+                .unwrap_or(0)
+                .max("Rule".len());
+
+            let mut lines = vec![format!(
+                "{:<width$}  {:>10}  {:>6}  {:>8}",
+                "Rule", "Time (ms)", "Files", "Problems"
+            )];
+            for timing in timings {
+                lines.push(format!(
+                    "{:<width$}  {:>10.3}  {:>6}  {:>8}",
+                    timing.rule,
+                    timing.total_time.as_secs_f64() * 1000.0,
+                    timing.files,
+                    timing.problems
+                ));
+            }
+            Ok(lines.join("\n"))
+        }
+    }
+}
+
+/// Write the analytics of a run to `dir` as `analytics.json`, and the time of each rule
+/// on each file as `analytics.folded`
+#[cfg(feature = "analytics")]
+fn write_analytics(dir: &std::path::Path, analytics: &LintAnalytics) -> Result<()> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create analytics directory {}", dir.display()))?;
+    let report = analytics.report();
+    let json = serde_json::json!({
+        "report": report,
+        "suggestions": analytics.suggest_optimizations(),
+    });
+
+    for (name, contents) in [
+        ("analytics.json", serde_json::to_string_pretty(&json)?),
+        ("analytics.folded", report.to_folded()),
+    ] {
+        let path = dir.join(name);
+        std::fs::write(&path, contents)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Write the `--manifest` of a run; files under nested configuration files record the hash
+/// of the configuration that applied to them
+fn write_manifest(
+    path: &std::path::Path,
+    run: &RunInfo,
+    config: ConfigRecord,
+    rules: Vec<RuleRecord>,
+    policy: Option<PolicyRecord>,
+    results: &[(std::path::PathBuf, Vec<linter::Problem>)],
+    linter: &Linter,
+) -> Result<()> {
+    // Directories without their own configuration file share the same resolved config
+    let mut hashes: std::collections::HashMap<*const Config, Option<String>> =
+        std::collections::HashMap::new();
+    let manifest = RunManifest::new(run, config, rules, policy, results, |file| {
+        let config = linter.config_for(file).ok()?;
+        hashes
+            .entry(std::sync::Arc::as_ptr(&config))
+            .or_insert_with(|| manifest::config_hash(&config).ok())
+            .clone()
+    });
+    let json = serde_json::to_string_pretty(&manifest)? + "\n";
+    write_atomic(path, json, None)
+        .with_context(|| format!("Failed to write manifest {}", path.display()))?;
+    info!(path = %path.display(), files = manifest.files.len(), "wrote manifest");
+    Ok(())
+}
+
 /// Filter results based on CLI options
 fn filter_results(
     results: Vec<(std::path::PathBuf, Vec<linter::Problem>)>,
     cli: &Cli,
 ) -> Vec<(std::path::PathBuf, Vec<linter::Problem>)> {
-    if !cli.errors_only {
+    if !cli.errors_only && !cli.only_fixable {
         return results;
     }
 
-    // Filter to only show errors
     results
         .into_iter()
         .map(|(path, problems)| {
             let error_problems = problems
                 .into_iter()
-                .filter(|p| matches!(p.level, linter::Level::Error))
+                .filter(|p| is_reported(p, cli))
                 .collect();
             (path, error_problems)
         })
         .collect()
 }
 
+/// Results without the problems suppressed by directives
+fn without_suppressed(
+    results: &[(std::path::PathBuf, Vec<linter::Problem>)],
+) -> Vec<(std::path::PathBuf, Vec<linter::Problem>)> {
+    results
+        .iter()
+        .map(|(path, problems)| {
+            let reported = problems
+                .iter()
+                .filter(|problem| !problem.suppressed)
+                .cloned()
+                .collect();
+            (path.clone(), reported)
+        })
+        .collect()
+}
+
+/// Whether a problem passes the CLI's level and fixability filters
+fn is_reported(problem: &linter::Problem, cli: &Cli) -> bool {
+    (!cli.errors_only || matches!(problem.level, linter::Level::Error))
+        && (!cli.only_fixable || rules::is_fixable(&problem.rule))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -224,15 +1012,167 @@ mod tests {
 
     #[test]
     fn test_parse_config_value() {
-        assert_eq!(parse_config_value("true").unwrap(), ConfigValue::Bool(true));
+        let parse = |value| parse_config_value(value, None).unwrap();
+        assert_eq!(parse("true"), ConfigValue::Bool(true));
+        assert_eq!(parse("false"), ConfigValue::Bool(false));
+        assert_eq!(parse("42"), ConfigValue::Int(42));
+        assert_eq!(parse("0.5"), ConfigValue::Float(0.5));
+        assert_eq!(parse("inf"), ConfigValue::String("inf".to_string()));
+        assert_eq!(parse("hello"), ConfigValue::String("hello".to_string()));
+        assert_eq!(
+            parse("a,1"),
+            ConfigValue::Array(vec!["a".into(), ConfigValue::Int(1)])
+        );
+        assert_eq!(
+            parse(r#"{"a": [1]}"#),
+            ConfigValue::Map(std::collections::BTreeMap::from([(
+                "a".to_string(),
+                ConfigValue::Array(vec![ConfigValue::Int(1)])
+            )]))
+        );
+    }
+
+    #[test]
+    fn test_format_rule_list() {
+        let registry = RuleRegistry::with_default_rules();
+
+        let text = format_rule_list(&registry, &OutputFormat::Human).unwrap();
+        assert!(text.contains("  line-length\n"));
+        assert!(text.contains("      max (integer, default: 80)\n"));
+
+        let json = format_rule_list(&registry, &OutputFormat::Json).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let rule = json["rules"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|rule| rule["id"] == "line-length")
+            .unwrap();
+        assert_eq!(rule["category"], "style");
+        assert_eq!(rule["phase"], "line");
+        let unit = rule["params"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|param| param["name"] == "length-unit")
+            .unwrap();
+        assert_eq!(unit["type"], "string");
+        assert_eq!(unit["default"], "chars");
+        assert_eq!(unit["allowed"][1], "bytes");
+    }
+
+    #[test]
+    fn test_explain_unknown_rule() {
+        assert!(explain_rule("no-such-rule").is_err());
+    }
+
+    #[test]
+    fn test_select_changed_files() {
+        let temp_dir = tempfile::TempDir::new().expect("Failed to create temp dir");
+        let changed_file = temp_dir.path().join("changed.yaml");
+        let other_file = temp_dir.path().join("other.yaml");
+        std::fs::write(&changed_file, "a: 1\n").unwrap();
+        std::fs::write(&other_file, "b: 1\n").unwrap();
+
+        let changed = git::ChangedFiles::from([(
+            changed_file.canonicalize().unwrap(),
+            git::ChangedLines::default(),
+        )]);
+        let selected = select_changed_files(vec![changed_file.clone(), other_file], &changed);
+
+        assert_eq!(selected, vec![changed_file]);
+    }
+
+    #[test]
+    fn test_annotate_changes() {
+        let temp_dir = tempfile::TempDir::new().expect("Failed to create temp dir");
+        let file = temp_dir.path().join("changed.yaml");
+        std::fs::write(&file, "a: 1\nb: 2\nc: 3\n").unwrap();
+        let lines = git::ChangedLines {
+            added: [2].into(),
+            context: [1, 3].into(),
+        };
+        let changed = git::ChangedFiles::from([(file.canonicalize().unwrap(), lines)]);
+        let problems = vec![
+            Problem::new(
+                1,
+                1,
+                Level::Error,
+                "document-start",
+                "missing document start",
+            ),
+            Problem::new(2, 5, Level::Error, "trailing-spaces", "trailing spaces"),
+            Problem::new(4, 1, Level::Error, "empty-lines", "too many blank lines"),
+        ];
+
+        let mut annotated = problems.clone();
+        annotate_changes(&file, &mut annotated, &changed, false);
+        let changes: Vec<_> = annotated.iter().map(|problem| problem.change).collect();
+        assert_eq!(
+            changes,
+            vec![
+                Some(linter::LineChange::Context),
+                Some(linter::LineChange::Added),
+                None
+            ]
+        );
+
+        let mut added = problems.clone();
+        annotate_changes(&file, &mut added, &changed, true);
+        assert_eq!(added.len(), 1);
+        assert_eq!(added[0].line, 2);
+
+        let mut elsewhere = problems;
+        annotate_changes(
+            &temp_dir.path().join("policy.yaml"),
+            &mut elsewhere,
+            &changed,
+            true,
+        );
+        assert_eq!(elsewhere.len(), 3);
+    }
+
+    #[test]
+    fn test_format_rule_timings() {
+        let timings = vec![linter::RuleTiming {
+            rule: "line-length".to_string(),
+            total_time: std::time::Duration::from_micros(1500),
+            files: 3,
+            problems: 2,
+        }];
+
+        let table = format_rule_timings(&timings, &OutputFormat::Human).unwrap();
         assert_eq!(
-            parse_config_value("false").unwrap(),
-            ConfigValue::Bool(false)
+            table,
+            "Rule          Time (ms)   Files  Problems\nline-length       1.500       3         2"
         );
-        assert_eq!(parse_config_value("42").unwrap(), ConfigValue::Int(42));
+
+        let json = format_rule_timings(&timings, &OutputFormat::Json).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["rules"][0]["rule"], "line-length");
+        assert_eq!(value["rules"][0]["files"], 3);
+    }
+
+    #[test]
+    #[cfg(feature = "analytics")]
+    fn test_write_analytics() {
+        use linter::LintObserver;
+
+        let temp_dir = tempfile::TempDir::new().expect("Failed to create temp dir");
+        let dir = temp_dir.path().join("analytics");
+        let analytics = LintAnalytics::new();
+        let path = std::path::Path::new("a.yaml");
+        analytics.on_rule_end(path, "truthy", std::time::Duration::from_micros(12));
+        analytics.on_file_end(path, &[], std::time::Duration::from_micros(20));
+
+        write_analytics(&dir, &analytics).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(dir.join("analytics.json")).unwrap())
+                .unwrap();
+        assert_eq!(json["report"]["files"], 1);
         assert_eq!(
-            parse_config_value("hello").unwrap(),
-            ConfigValue::String("hello".to_string())
+            std::fs::read_to_string(dir.join("analytics.folded")).unwrap(),
+            "truthy;a.yaml 12\n"
         );
     }
 
@@ -275,6 +1215,25 @@ mod tests {
         assert_eq!(filtered[0].1[0].level, Level::Error);
     }
 
+    #[test]
+    fn test_filter_results_only_fixable() {
+        let cli = Cli {
+            only_fixable: true,
+            ..Default::default()
+        };
+        let results = vec![(
+            PathBuf::from("test.yaml"),
+            vec![
+                Problem::new(1, 1, Level::Error, "line-length", "line too long"),
+                Problem::new(2, 5, Level::Warning, "trailing-spaces", "trailing"),
+            ],
+        )];
+
+        let filtered = filter_results(results, &cli);
+        assert_eq!(filtered[0].1.len(), 1);
+        assert_eq!(filtered[0].1[0].rule, "trailing-spaces");
+    }
+
     #[test]
     fn test_apply_cli_overrides_disable() {
         let mut config = Config::default();
@@ -302,31 +1261,282 @@ mod tests {
         let rule_config = config.rules.get("line-length").unwrap();
         assert_eq!(rule_config.get_int("max"), Some(120));
     }
+
+    #[test]
+    fn test_apply_cli_overrides_set_param_errors() {
+        let apply = |set: &str| {
+            let cli = Cli {
+                set: vec![set.to_string()],
+                ..Default::default()
+            };
+            apply_cli_overrides(&mut Config::default(), &cli).map_err(|e| format!("{e:#}"))
+        };
+
+        assert_eq!(
+            apply("line-length.max=long").unwrap_err(),
+            "Invalid value for line-length.max: expected an integer, got 'long'"
+        );
+        let error = apply("line-length.maximum=120").unwrap_err();
+        assert!(
+            error.starts_with("Unknown parameter 'maximum' for rule 'line-length' (parameters: "),
+            "{error}"
+        );
+        assert!(apply("forbidden-keys.keys=password,secret").is_ok());
+        assert_eq!(
+            apply("line-length.length-unit=words").unwrap_err(),
+            "Invalid value for line-length.length-unit: length-unit must be one of chars, bytes, display-width, got \"words\""
+        );
+    }
+
+    #[test]
+    fn test_verify_fix() {
+        assert!(verify_fix("key: value   \n", "key: value\n").is_ok());
+
+        let reason = verify_fix("key: value\n", "key: other\n").unwrap_err();
+        assert!(reason.contains("key: \"value\" -> \"other\""), "{reason}");
+
+        assert!(verify_fix("key: value\n", "key: [value\n").is_err());
+    }
 }
 
 /// Handle subcommands
-async fn handle_subcommand(command: &Commands) -> Result<()> {
+fn handle_subcommand(command: &Commands, cli: &Cli) -> Result<()> {
     match command {
+        #[cfg(feature = "lsp")]
         Commands::Lsp => {
-            lsp::start_lsp_server().await?;
+            block_on(lsp::start_lsp_server(cli.policy.clone()))?;
+        }
+        #[cfg(feature = "server")]
+        Commands::Daemon { socket } => {
+            block_on(run_daemon(
+                socket.clone().unwrap_or_else(daemon::default_socket_path),
+                cli,
+            ))?;
+        }
+        #[cfg(feature = "server")]
+        Commands::Client {
+            socket,
+            client_command,
+        } => {
+            let socket = socket.clone().unwrap_or_else(daemon::default_socket_path);
+            block_on(run_client(&socket, client_command, cli))?;
+        }
+        #[cfg(feature = "server")]
+        Commands::Serve { http } => {
+            let mut config = load_config(cli)?;
+            apply_cli_overrides(&mut config, cli)?;
+            eprintln!("yl serving HTTP on {}", serve::listen_address(http));
+            block_on(serve::serve(
+                http,
+                std::sync::Arc::new(serve::LintServer::new(config)),
+            ))?;
+        }
+        Commands::Fix {
+            files,
+            dry_run,
+            verify,
+        } => {
+            handle_fix_command(files, *dry_run, *verify, cli)?;
         }
-        Commands::Fix { files, dry_run } => {
-            handle_fix_command(files, *dry_run)?;
+        Commands::Explain { rule } => {
+            explain_rule(rule)?;
+        }
+        Commands::Report { report_command } => {
+            handle_report_command(report_command, cli)?;
+        }
+        #[cfg(feature = "ml")]
+        Commands::Analyze { analyze_command } => {
+            handle_analyze_command(analyze_command, cli)?;
         }
         Commands::Migrate { migrate_command } => {
-            handle_migrate_command(migrate_command)?;
+            handle_migrate_command(migrate_command, cli)?;
         }
+        Commands::Policy { policy_command } => {
+            handle_policy_command(policy_command, cli)?;
+        }
+        #[cfg(feature = "plugins")]
         Commands::Plugin { plugin_command } => {
-            handle_plugin_command(plugin_command)?;
+            handle_plugin_command(plugin_command, cli)?;
+        }
+        Commands::Config { config_command } => {
+            handle_config_command(config_command, cli)?;
+        }
+        Commands::Completions { shell } => {
+            completions::write_completions(*shell, &mut std::io::stdout());
+        }
+        Commands::Man => {
+            completions::write_man_page(&mut std::io::stdout())?;
+        }
+        Commands::Messages => {
+            print!("{}", serde_yaml::to_string(&Catalog::english())?);
+        }
+        #[cfg(feature = "self-update")]
+        Commands::SelfUpdate { check } => {
+            println!("{}", update::self_update(*check)?);
         }
     }
     Ok(())
 }
 
+/// Run the future of an async subcommand on a new runtime; the rest of yl is synchronous
+#[cfg(any(feature = "lsp", feature = "server"))]
+fn block_on<T>(future: impl std::future::Future<Output = Result<T>>) -> Result<T> {
+    tokio::runtime::Runtime::new()
+        .context("Failed to start the async runtime")?
+        .block_on(future)
+}
+
+/// Run the lint daemon until a client shuts it down
+#[cfg(all(feature = "server", unix))]
+async fn run_daemon(socket: std::path::PathBuf, cli: &Cli) -> Result<()> {
+    let mut config = load_config(cli)?;
+    apply_cli_overrides(&mut config, cli)?;
+
+    let options = cli.clone();
+    let daemon = daemon::Daemon::new(cli.config.clone(), config, move |mut config| {
+        // Overrides were already validated against the configuration at startup
+        let _ = apply_cli_overrides(&mut config, &options);
+        let overrides = options.clone();
+        Linter::new(config)
+            .with_config_discovery(options.nested_config())
+            .with_config_env_expand(!options.no_env_expand)
+            .with_follow_symlinks(options.follow_symlinks)
+            .with_config_overrides(move |config| {
+                let _ = apply_cli_overrides(config, &overrides);
+            })
+            .with_jobs(options.jobs)
+    })
+    .with_profile(cli.profile.clone())
+    .with_env_expand(!cli.no_env_expand);
+
+    eprintln!("yl daemon listening on {}", socket.display());
+    daemon::serve(&socket, std::sync::Arc::new(daemon)).await
+}
+
+#[cfg(all(feature = "server", not(unix)))]
+async fn run_daemon(_socket: std::path::PathBuf, _cli: &Cli) -> Result<()> {
+    Err(eyre::eyre!("yl daemon requires unix domain sockets"))
+}
+
+/// Send a request to a running daemon and report its response
+#[cfg(all(feature = "server", unix))]
+async fn run_client(
+    socket: &std::path::Path,
+    client_command: &ClientCommands,
+    cli: &Cli,
+) -> Result<()> {
+    let mut client = daemon::Client::connect(socket).await?;
+
+    let requests = match client_command {
+        ClientCommands::Lint {
+            stdin_filename: Some(path),
+            ..
+        } => {
+            let mut content = String::new();
+            std::io::Read::read_to_string(&mut std::io::stdin(), &mut content)
+                .context("Failed to read standard input")?;
+            vec![daemon::Request::Lint {
+                path: absolute_path(path)?,
+                content: Some(content),
+            }]
+        }
+        ClientCommands::Lint { files, .. } => {
+            // The daemon may run in another directory
+            let files = if files.is_empty() {
+                vec![std::path::PathBuf::from(".")]
+            } else {
+                files.clone()
+            };
+            files
+                .iter()
+                .map(|path| {
+                    Ok(daemon::Request::Lint {
+                        path: absolute_path(path)?,
+                        content: None,
+                    })
+                })
+                .collect::<Result<_>>()?
+        }
+        ClientCommands::Reload => vec![daemon::Request::Reload],
+        ClientCommands::Ping => vec![daemon::Request::Ping],
+        ClientCommands::Shutdown => vec![daemon::Request::Shutdown],
+    };
+
+    let mut results = Vec::new();
+    for request in &requests {
+        match client.send(request).await? {
+            daemon::Response::Lint { files } => {
+                results.extend(files.into_iter().map(|file| (file.path, file.problems)));
+            }
+            daemon::Response::Ok => println!("ok"),
+            daemon::Response::Error { message } => return Err(eyre::eyre!(message)),
+        }
+    }
+
+    if matches!(client_command, ClientCommands::Lint { .. }) {
+        let results = filter_results(results, cli);
+        // The daemon's configuration is not known here; suggestions use the local one
+        let config = load_config(cli)?;
+        let formatter = match (&cli.format, &requests[..]) {
+            (
+                OutputFormat::Rdjson,
+                [
+                    daemon::Request::Lint {
+                        path,
+                        content: Some(content),
+                    },
+                ],
+            ) => Box::new(
+                output::rdjson::RdjsonFormatter::new(config)
+                    .with_source(path.clone(), content.clone()),
+            ),
+            _ => get_formatter(&cli.format, &config),
+        };
+        let output = formatter.format_results(&results);
+        if !output.is_empty() {
+            println!("{output}");
+        }
+        if LintStats::from_results(&results).has_errors() {
+            std::process::exit(1);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(all(feature = "server", not(unix)))]
+async fn run_client(
+    _socket: &std::path::Path,
+    _client_command: &ClientCommands,
+    _cli: &Cli,
+) -> Result<()> {
+    Err(eyre::eyre!("yl client requires unix domain sockets"))
+}
+
+/// Make a path absolute against the working directory
+#[cfg(all(feature = "server", unix))]
+fn absolute_path(path: &std::path::Path) -> Result<std::path::PathBuf> {
+    std::path::absolute(path).with_context(|| format!("Invalid path: {}", path.display()))
+}
+
 /// Handle fix command
-fn handle_fix_command(files: &[std::path::PathBuf], dry_run: bool) -> Result<()> {
-    let config = Config::default();
-    let linter = Linter::new(config);
+fn handle_fix_command(
+    files: &[std::path::PathBuf],
+    dry_run: bool,
+    verify: bool,
+    cli: &Cli,
+) -> Result<()> {
+    let mut config = load_config(cli)?;
+    apply_cli_overrides(&mut config, cli)?;
+
+    let overrides = cli.clone();
+    let linter = Linter::new(config)
+        .with_config_discovery(cli.nested_config())
+        .with_config_env_expand(!cli.no_env_expand)
+        .with_follow_symlinks(cli.follow_symlinks)
+        .with_config_overrides(move |config| {
+            let _ = apply_cli_overrides(config, &overrides);
+        });
     let fix_engine = FixEngine::new();
 
     let files_to_process = if files.is_empty() {
@@ -337,46 +1547,508 @@ fn handle_fix_command(files: &[std::path::PathBuf], dry_run: bool) -> Result<()>
 
     let results = linter.lint_paths(&files_to_process)?;
     let mut total_fixes = 0;
+    let mut refused = 0;
 
     for (file_path, problems) in results {
         if problems.is_empty() {
             continue;
         }
 
+        // Files that could not be linted cannot be fixed either
+        if let Some(error) = problems.iter().find(|p| p.is_file_error()) {
+            warn!(file = %file_path.display(), error = %error.message, "skipping file");
+            continue;
+        }
+
         let content = std::fs::read_to_string(&file_path)?;
-        let fixed_content = fix_engine.fix_problems(&content, &problems)?;
+        let fixed_content = linter
+            .fix_content(&file_path, &content, &fix_engine)?
+            .content;
 
         if content != fixed_content {
+            if verify && let Err(reason) = verify_fix(&content, &fixed_content) {
+                refused += 1;
+                eprintln!(
+                    "{}",
+                    Message::new("fix.refused")
+                        .arg("path", file_path.display())
+                        .arg("reason", reason)
+                );
+                continue;
+            }
+
             total_fixes += 1;
 
             if dry_run {
-                println!("Would fix: {}", file_path.display());
+                println!(
+                    "{}",
+                    Message::new("fix.would-fix").arg("path", file_path.display())
+                );
             } else {
-                std::fs::write(&file_path, fixed_content)?;
-                println!("Fixed: {}", file_path.display());
+                write_atomic(&file_path, fixed_content, cli.in_place_backup.as_deref())
+                    .with_context(|| format!("Failed to write {}", file_path.display()))?;
+                println!(
+                    "{}",
+                    Message::new("fix.fixed").arg("path", file_path.display())
+                );
             }
         }
     }
 
-    if dry_run {
-        println!("Would fix {total_fixes} files");
-    } else {
-        println!("Fixed {total_fixes} files");
+    let id = match (dry_run, total_fixes == 1) {
+        (true, true) => "fix.would-fix-count",
+        (true, false) => "fix.would-fix-count-plural",
+        (false, true) => "fix.fixed-count",
+        (false, false) => "fix.fixed-count-plural",
+    };
+    println!("{}", Message::new(id).arg("count", total_fixes));
+
+    if refused > 0 {
+        let id = if refused == 1 {
+            "fix.refused-count"
+        } else {
+            "fix.refused-count-plural"
+        };
+        eprintln!("{}", Message::new(id).arg("count", refused));
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Check that a fix leaves the parsed document structure unchanged; the error describes
+/// the parse failure or lists the changed values
+fn verify_fix(content: &str, fixed_content: &str) -> std::result::Result<(), String> {
+    let differences = fixes::semantic_differences(content, fixed_content)
+        .map_err(|error| format!("{error:#}"))?;
+    if differences.is_empty() {
+        return Ok(());
+    }
+
+    let mut reason = String::from("fix changes the document structure");
+    for difference in differences {
+        reason.push_str("\n  ");
+        reason.push_str(&difference);
+    }
+    Err(reason)
+}
+
+/// Handle report command
+fn handle_report_command(report_command: &ReportCommands, cli: &Cli) -> Result<()> {
+    match report_command {
+        ReportCommands::Suppressions { files, threshold } => {
+            let mut config = load_config(cli)?;
+            apply_cli_overrides(&mut config, cli)?;
+            let linter = Linter::new(config)
+                .with_config_discovery(cli.nested_config())
+                .with_config_env_expand(!cli.no_env_expand)
+                .with_follow_symlinks(cli.follow_symlinks);
+
+            let paths = if files.is_empty() {
+                vec![std::path::PathBuf::from(".")]
+            } else {
+                files.to_vec()
+            };
+            let report = SuppressionReport::scan(&linter.collect_files(&paths)?)?;
+            let candidates = report.candidates(*threshold);
+
+            match cli.format {
+                OutputFormat::Json
+                | OutputFormat::Ndjson
+                | OutputFormat::Rdjson
+                | OutputFormat::Sarif => {
+                    let output = serde_json::json!({
+                        "total": report.total,
+                        "by_rule": report.by_rule,
+                        "by_file": report.by_file,
+                        "threshold": threshold,
+                        "candidates": candidates
+                            .iter()
+                            .map(|(rule, count)| serde_json::json!({ "rule": rule, "count": count }))
+                            .collect::<Vec<_>>(),
+                    });
+                    println!("{}", serde_json::to_string_pretty(&output)?);
+                }
+                OutputFormat::Human | OutputFormat::Compact => {
+                    print_suppression_report(&report, &candidates, *threshold)
+                }
+            }
+        }
+        ReportCommands::Score {
+            files,
+            badge,
+            badge_label,
+        } => {
+            let mut config = load_config(cli)?;
+            apply_cli_overrides(&mut config, cli)?;
+            let weights = ScoreWeights::from_config(&config.score_weights);
+            let linter = Linter::new(config)
+                .with_config_discovery(cli.nested_config())
+                .with_config_env_expand(!cli.no_env_expand)
+                .with_follow_symlinks(cli.follow_symlinks);
+
+            let paths = if files.is_empty() {
+                vec![std::path::PathBuf::from(".")]
+            } else {
+                files.to_vec()
+            };
+            let results = filter_results(linter.lint_paths(&paths)?, cli);
+            let score = HealthScore::from_results(&results, &weights);
+
+            if let Some(badge_path) = badge {
+                let badge = serde_json::to_string_pretty(&score.badge(badge_label))?;
+                write_atomic(badge_path, badge + "\n", None)
+                    .with_context(|| format!("Failed to write {}", badge_path.display()))?;
+            }
+
+            match cli.format {
+                OutputFormat::Json
+                | OutputFormat::Ndjson
+                | OutputFormat::Rdjson
+                | OutputFormat::Sarif => {
+                    println!("{}", serde_json::to_string_pretty(&score)?);
+                }
+                OutputFormat::Human | OutputFormat::Compact => print_health_score(&score),
+            }
+        }
     }
+    Ok(())
+}
 
+/// Handle policy command
+fn handle_policy_command(policy_command: &PolicyCommands, cli: &Cli) -> Result<()> {
+    match policy_command {
+        PolicyCommands::Init {
+            from_config,
+            name,
+            promote_security,
+            output,
+            force,
+        } => {
+            if output.exists() && !force {
+                return Err(eyre::eyre!(
+                    "{} already exists (use --force to overwrite)",
+                    output.display()
+                ));
+            }
+
+            let config = if *from_config {
+                let mut config = load_config(cli)?;
+                apply_cli_overrides(&mut config, cli)?;
+                config
+            } else {
+                Config::default()
+            };
+            let registry = RuleRegistry::with_default_rules();
+            let author = git::user_name(std::path::Path::new(".")).unwrap_or_default();
+
+            let promoted = if *promote_security {
+                TeamPolicy::promotable_rules(&config, &registry)
+            } else {
+                Vec::new()
+            };
+            let policy =
+                TeamPolicy::from_config(name, &author, &config, &registry, *promote_security);
+            let yaml = policy.to_commented_yaml(&promoted)?;
+            write_atomic(output, yaml, cli.in_place_backup.as_deref())
+                .with_context(|| format!("Failed to write {}", output.display()))?;
+
+            println!(
+                "Wrote policy \"{name}\" to {} ({} required rules)",
+                output.display(),
+                policy.required_rules.len()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Handle analyze command
+#[cfg(feature = "ml")]
+fn handle_analyze_command(analyze_command: &AnalyzeCommands, cli: &Cli) -> Result<()> {
+    let (AnalyzeCommands::Indentation { files } | AnalyzeCommands::Drift { files, .. }) =
+        analyze_command;
+    let mut config = load_config(cli)?;
+    apply_cli_overrides(&mut config, cli)?;
+    let linter = Linter::new(config)
+        .with_config_discovery(cli.nested_config())
+        .with_config_env_expand(!cli.no_env_expand)
+        .with_follow_symlinks(cli.follow_symlinks);
+
+    let paths = if files.is_empty() {
+        vec![std::path::PathBuf::from(".")]
+    } else {
+        files.to_vec()
+    };
+    let report = IndentationReport::scan(&linter.collect_files(&paths)?)?;
+
+    match analyze_command {
+        AnalyzeCommands::Indentation { .. } => match cli.format {
+            OutputFormat::Json
+            | OutputFormat::Ndjson
+            | OutputFormat::Rdjson
+            | OutputFormat::Sarif => {
+                let output = serde_json::json!({
+                    "total": report.files.len(),
+                    "styles": report
+                        .styles()
+                        .iter()
+                        .map(|(style, count)| serde_json::json!({ "style": style, "count": count }))
+                        .collect::<Vec<_>>(),
+                    "sequences": report
+                        .sequence_styles()
+                        .iter()
+                        .map(|(style, count)| serde_json::json!({ "style": style, "count": count }))
+                        .collect::<Vec<_>>(),
+                    "majority": report.majority(),
+                    "sequence_majority": report.sequence_majority(),
+                    "inconsistent": report.inconsistent(),
+                });
+                println!("{}", serde_json::to_string_pretty(&output)?);
+            }
+            OutputFormat::Human | OutputFormat::Compact => print_indentation_report(&report),
+        },
+        AnalyzeCommands::Drift { commits, .. } => {
+            let recent = git::files_in_recent_commits(std::path::Path::new("."), *commits)
+                .context("Failed to list files of recent commits")?;
+            let drift = DriftReport::compare(&report, &recent);
+
+            match cli.format {
+                OutputFormat::Json
+                | OutputFormat::Ndjson
+                | OutputFormat::Rdjson
+                | OutputFormat::Sarif => {
+                    let mut output = serde_json::to_value(&drift)?;
+                    output["drifted"] = drift.has_drifted().into();
+                    println!("{}", serde_json::to_string_pretty(&output)?);
+                }
+                OutputFormat::Human | OutputFormat::Compact => print_drift_report(&drift, *commits),
+            }
+        }
+    }
     Ok(())
 }
 
+/// Print a style drift report in human-readable form
+#[cfg(feature = "ml")]
+fn print_drift_report(drift: &DriftReport, commits: usize) {
+    let describe = |profile: &analyze::drift::StyleProfile| {
+        let styles: Vec<String> = [
+            profile.style.map(|style| style.to_string()),
+            profile.sequences.map(|style| style.to_string()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if styles.is_empty() {
+            "(nothing nested)".to_string()
+        } else {
+            styles.join(", ")
+        }
+    };
+
+    println!(
+        "Repository: {} ({} files)",
+        describe(&drift.repository),
+        drift.repository.files
+    );
+    println!(
+        "Changed in the last {commits} commits: {} ({} files)",
+        describe(&drift.recent),
+        drift.recent.files
+    );
+    if drift.has_drifted() {
+        println!();
+        println!("Recent changes have drifted from the repository's style");
+    }
+
+    if !drift.diverging.is_empty() {
+        println!();
+        println!("Recent files diverging from the repository:");
+        for file in &drift.diverging {
+            let mut styles = Vec::new();
+            if let Some(style) = file
+                .style
+                .filter(|style| Some(*style) != drift.repository.style)
+            {
+                styles.push(style.to_string());
+            }
+            if let Some(style) = file
+                .sequences
+                .filter(|style| Some(*style) != drift.repository.sequences)
+            {
+                styles.push(style.to_string());
+            }
+            println!("  {}: {}", file.path.display(), styles.join(", "));
+        }
+    }
+}
+
+/// Print an indentation report in human-readable form
+#[cfg(feature = "ml")]
+fn print_indentation_report(report: &IndentationReport) {
+    println!("Analyzed indentation of {} files", report.files.len());
+    let styles = report.styles();
+    if styles.is_empty() {
+        return;
+    }
+
+    let majority = report.majority();
+    println!();
+    println!("Indentation:");
+    for (style, count) in &styles {
+        let marker = if Some(*style) == majority {
+            " (majority)"
+        } else {
+            ""
+        };
+        println!("  {style:<24} {count:>6} files{marker}");
+    }
+    let nested = report.files.iter().filter(|f| f.style.is_some()).count();
+    if nested < report.files.len() {
+        println!(
+            "  {:<24} {:>6} files",
+            "(nothing nested)",
+            report.files.len() - nested
+        );
+    }
+
+    let sequence_styles = report.sequence_styles();
+    let sequence_majority = report.sequence_majority();
+    if !sequence_styles.is_empty() {
+        println!();
+        println!("Sequences:");
+        for (style, count) in &sequence_styles {
+            let marker = if Some(*style) == sequence_majority {
+                " (majority)"
+            } else {
+                ""
+            };
+            println!("  {style:<24} {count:>6} files{marker}");
+        }
+    }
+
+    let inconsistent = report.inconsistent();
+    if !inconsistent.is_empty() {
+        println!();
+        println!("Inconsistent with the majority:");
+        for file in inconsistent {
+            let mut styles = Vec::new();
+            if let Some(style) = file.style.filter(|style| Some(*style) != majority) {
+                styles.push(style.to_string());
+            }
+            if let Some(style) = file
+                .sequences
+                .filter(|style| Some(*style) != sequence_majority)
+            {
+                styles.push(style.to_string());
+            }
+            println!("  {}: {}", file.path.display(), styles.join(", "));
+        }
+    }
+
+    if let Some(analyze::indentation::IndentStyle::Spaces(spaces)) = majority {
+        println!();
+        println!(
+            "To enforce the majority, enable the indentation rule: --enable indentation --set indentation.spaces={spaces}"
+        );
+    }
+}
+
+/// Print the statistics of each project after grouped results
+fn print_project_stats(groups: &[(Option<String>, LintStats)]) {
+    println!();
+    println!("Problems by project:");
+    for (project, stats) in groups {
+        println!(
+            "  {:<24} {:>5} files  {:>5} errors  {:>5} warnings  {:>5} info",
+            project.as_deref().unwrap_or("(no project)"),
+            stats.total_files,
+            stats.errors,
+            stats.warnings,
+            stats.info
+        );
+    }
+}
+
+/// Print a health score with the penalty of each rule
+fn print_health_score(score: &HealthScore) {
+    println!(
+        "Health score: {:.1}/100 ({} problems in {} files, penalty {:.1})",
+        score.score, score.problems, score.files, score.penalty
+    );
+
+    let top_rules = score.top_rules();
+    if top_rules.is_empty() {
+        return;
+    }
+    println!();
+    println!("Penalty by rule:");
+    for (rule, penalty) in top_rules {
+        println!("  {penalty:>7.1}  {rule}");
+    }
+}
+
+/// Print a suppression report in human-readable form
+fn print_suppression_report(
+    report: &SuppressionReport,
+    candidates: &[(&str, usize)],
+    threshold: usize,
+) {
+    println!(
+        "Found {} suppressions in {} files",
+        report.total,
+        report.by_file.len()
+    );
+    if report.total == 0 {
+        return;
+    }
+
+    let mut by_rule: Vec<_> = report.by_rule.iter().collect();
+    by_rule.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+    println!();
+    println!("By rule:");
+    for (rule, count) in by_rule {
+        let rule = if rule == directives::suppressions::ALL_RULES {
+            "(all rules)"
+        } else {
+            rule.as_str()
+        };
+        println!("  {count:>5}  {rule}");
+    }
+
+    let mut by_file: Vec<_> = report.by_file.iter().collect();
+    by_file.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+    println!();
+    println!("By file:");
+    for (path, count) in by_file {
+        println!("  {count:>5}  {}", path.display());
+    }
+
+    if !candidates.is_empty() {
+        println!();
+        println!(
+            "Rules suppressed more than {threshold} times (consider disabling them in configuration):"
+        );
+        for (rule, count) in candidates {
+            println!("  {rule} ({count} suppressions)");
+        }
+    }
+}
+
 /// Handle migrate command
-fn handle_migrate_command(migrate_command: &MigrateCommands) -> Result<()> {
+fn handle_migrate_command(migrate_command: &MigrateCommands, cli: &Cli) -> Result<()> {
     match migrate_command {
         MigrateCommands::Config { input, output } => {
             let yl_config = YamllintMigrator::convert_config(input)?;
             let default_output = std::path::PathBuf::from(".yl.yaml");
             let output_path = output.as_ref().unwrap_or(&default_output);
 
-            let config_content = serde_yaml::to_string(&yl_config)?;
-            std::fs::write(output_path, config_content)?;
+            let existing = std::fs::read_to_string(output_path).ok();
+            let config_content = yl_config
+                .to_canonical_yaml(&RuleRegistry::with_default_rules(), existing.as_deref())?;
+            write_atomic(output_path, config_content, cli.in_place_backup.as_deref())
+                .with_context(|| format!("Failed to write {}", output_path.display()))?;
 
             println!("Converted yamllint config to: {}", output_path.display());
         }
@@ -386,43 +2058,103 @@ fn handle_migrate_command(migrate_command: &MigrateCommands) -> Result<()> {
                 let converted = YamllintMigrator::convert_directives(&content);
 
                 if content != converted {
-                    std::fs::write(file_path, converted)?;
+                    write_atomic(file_path, converted, cli.in_place_backup.as_deref())
+                        .with_context(|| format!("Failed to write {}", file_path.display()))?;
                     println!("Converted directives in: {}", file_path.display());
                 }
             }
         }
         MigrateCommands::Project { path } => {
-            YamllintMigrator::migrate_project(path)?;
+            YamllintMigrator::migrate_project(
+                path,
+                cli.follow_symlinks,
+                cli.in_place_backup.as_deref(),
+            )?;
             println!("Project migration completed");
         }
     }
     Ok(())
 }
 
+/// Print what each loaded plugin is and the library it came from
+#[cfg(feature = "plugins")]
+fn print_plugins(plugins: &[&plugins::LoadedPlugin]) {
+    for loaded in plugins {
+        let plugin = &loaded.plugin;
+        println!(
+            "  {}@{} - {}",
+            plugin.name(),
+            plugin.version(),
+            plugin.description()
+        );
+        println!("    library: {}", loaded.path.display());
+        println!("    sha256:  {}", loaded.sha256);
+    }
+}
+
 /// Handle plugin command
-fn handle_plugin_command(plugin_command: &PluginCommands) -> Result<()> {
-    let mut plugin_manager = PluginManager::new();
+#[cfg(feature = "plugins")]
+fn handle_plugin_command(plugin_command: &PluginCommands, cli: &Cli) -> Result<()> {
+    // Only the plugins section is needed; an unreadable configuration allows nothing
+    let policy = match plugin_command {
+        PluginCommands::New { .. } => Default::default(),
+        _ => load_config(cli)?.plugins,
+    };
+    let directories = policy.directories();
+    let mut plugin_manager = PluginManager::new().with_policy(policy);
 
     match plugin_command {
         PluginCommands::List => {
+            for directory in &directories {
+                plugin_manager.load_plugins_from_dir(directory)?;
+            }
             let plugins = plugin_manager.plugins();
             if plugins.is_empty() {
                 println!("No plugins loaded");
             } else {
                 println!("Loaded plugins:");
-                for plugin in plugins {
-                    println!(
-                        "  {} v{} - {}",
-                        plugin.name(),
-                        plugin.version(),
-                        plugin.description()
-                    );
-                }
+                print_plugins(&plugins);
             }
         }
         PluginCommands::Load { directory } => {
             let loaded = plugin_manager.load_plugins_from_dir(directory)?;
             println!("Loaded {} plugins from {}", loaded, directory.display());
+            print_plugins(&plugin_manager.plugins());
+        }
+        PluginCommands::New {
+            name,
+            path,
+            yl_core_path,
+        } => {
+            let dir = path
+                .clone()
+                .unwrap_or_else(|| std::path::PathBuf::from(name));
+            let core = match yl_core_path {
+                Some(path) => CoreDependency::Path(
+                    path.canonicalize()
+                        .with_context(|| format!("Failed to resolve {}", path.display()))?,
+                ),
+                None => CoreDependency::Version(env!("CARGO_PKG_VERSION").to_string()),
+            };
+            for file in scaffold(name, &dir, &core)? {
+                println!("Created {}", file.display());
+            }
+            // Plugins are only loaded once the configuration allows their library
+            let library = format!(
+                "{}{}{}",
+                std::env::consts::DLL_PREFIX,
+                name.replace('-', "_"),
+                std::env::consts::DLL_SUFFIX
+            );
+            println!();
+            println!("Build it with cargo build --release, allow it in your configuration:");
+            println!();
+            println!("plugins:\n  allow:\n    - plugin: {name}@0.1.0\n      file: {library}");
+            println!();
+            println!(
+                "and load it with: yl plugin load {}/target/release",
+                dir.display()
+            );
         }
     }
     Ok(())
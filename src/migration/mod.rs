@@ -1,6 +1,7 @@
-use crate::config::Config;
-use crate::linter::Level;
-use crate::rules::{ConfigValue, RuleConfig};
+use crate::config::{Config, IgnorePatterns};
+use crate::linter::{Level, walk_files};
+use crate::rules::{ConfigValue, RuleConfig, RuleRegistry};
+use crate::write::write_atomic;
 use eyre::Result;
 use regex::Regex;
 use serde_yaml::Value;
@@ -33,7 +34,10 @@ impl YamllintMigrator {
             for (rule_name, rule_config) in rules_map {
                 if let Some(rule_name_str) = rule_name.as_str() {
                     let yl_rule_name = Self::convert_rule_name(rule_name_str);
-                    let yl_rule_config = Self::convert_rule_config(rule_config)?;
+                    let mut yl_rule_config = Self::convert_rule_config(rule_config)?;
+                    if yl_rule_name == "indentation" {
+                        Self::convert_indent_sequences(&mut yl_rule_config);
+                    }
                     yl_config.rules.insert(yl_rule_name, yl_rule_config);
                 }
             }
@@ -41,14 +45,7 @@ impl YamllintMigrator {
 
         // Convert ignore patterns
         if let Some(ignore) = yamllint_config.get("ignore") {
-            if let Some(ignore_str) = ignore.as_str() {
-                yl_config.ignore = vec![ignore_str.to_string()];
-            } else if let Some(ignore_seq) = ignore.as_sequence() {
-                yl_config.ignore = ignore_seq
-                    .iter()
-                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                    .collect();
-            }
+            yl_config.ignore = Self::convert_ignore(ignore);
         }
 
         Ok(yl_config)
@@ -144,10 +141,16 @@ impl YamllintMigrator {
                     };
                 }
 
+                // Files the rule skips
+                if let Some(ignore) = map.get(Value::String("ignore".to_string())) {
+                    rule_config.ignore = Self::convert_ignore(ignore);
+                }
+
                 // Convert other parameters
                 for (key, value) in map {
                     if let Some(key_str) = key.as_str()
                         && key_str != "level"
+                        && key_str != "ignore"
                     {
                         let config_value = Self::convert_config_value(value)?;
                         rule_config.params.insert(key_str.to_string(), config_value);
@@ -160,6 +163,35 @@ impl YamllintMigrator {
         }
     }
 
+    /// Convert yamllint's `indent-sequences` (`true`, `false`, `whatever` or `consistent`),
+    /// which says where sequences under a key go, to `sequence-style`
+    fn convert_indent_sequences(config: &mut RuleConfig) {
+        let style = match config.params.remove("indent-sequences") {
+            Some(ConfigValue::Bool(true)) => "indented",
+            Some(ConfigValue::Bool(false)) => "aligned",
+            Some(ConfigValue::String(value)) if value == "consistent" => "consistent",
+            Some(_) => "any",
+            None => return,
+        };
+        config.set_param("sequence-style", style);
+    }
+
+    /// Convert yamllint ignore patterns, given as a list or one pattern per line
+    fn convert_ignore(ignore: &Value) -> Vec<String> {
+        let patterns: Vec<&str> = match ignore {
+            Value::String(s) => s.lines().collect(),
+            Value::Sequence(seq) => seq.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+
+        patterns
+            .into_iter()
+            .map(str::trim)
+            .filter(|pattern| !pattern.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
     /// Convert yamllint config values to yl ConfigValue
     fn convert_config_value(value: &Value) -> Result<ConfigValue> {
         match value {
@@ -167,6 +199,8 @@ impl YamllintMigrator {
             Value::Number(n) => {
                 if let Some(i) = n.as_i64() {
                     Ok(ConfigValue::Int(i))
+                } else if let Some(f) = n.as_f64() {
+                    Ok(ConfigValue::Float(f))
                 } else {
                     Ok(ConfigValue::String(n.to_string()))
                 }
@@ -177,6 +211,16 @@ impl YamllintMigrator {
                     seq.iter().map(Self::convert_config_value).collect();
                 Ok(ConfigValue::Array(converted?))
             }
+            Value::Mapping(map) => {
+                let mut converted = std::collections::BTreeMap::new();
+                for (key, value) in map {
+                    let key = key
+                        .as_str()
+                        .ok_or_else(|| eyre::eyre!("Unsupported non-string key: {key:?}"))?;
+                    converted.insert(key.to_string(), Self::convert_config_value(value)?);
+                }
+                Ok(ConfigValue::Map(converted))
+            }
             _ => Ok(ConfigValue::String(format!("{value:?}"))),
         }
     }
@@ -238,8 +282,13 @@ impl YamllintMigrator {
         Ok(report)
     }
 
-    /// Migrate a complete yamllint project to yl
-    pub fn migrate_project<P: AsRef<Path>>(project_path: P) -> Result<()> {
+    /// Migrate a complete yamllint project to yl. With `backup_suffix`, each file that is
+    /// rewritten is first kept as `<file><suffix>`.
+    pub fn migrate_project<P: AsRef<Path>>(
+        project_path: P,
+        follow_symlinks: bool,
+        backup_suffix: Option<&str>,
+    ) -> Result<()> {
         let project_path = project_path.as_ref();
 
         // Look for yamllint config files
@@ -248,6 +297,7 @@ impl YamllintMigrator {
             project_path.join(".yamllint.yml"),
             project_path.join(".yamllint.yaml"),
         ];
+        let mut ignore = Config::default().ignore;
 
         for config_path in yamllint_configs {
             if config_path.exists() {
@@ -255,11 +305,16 @@ impl YamllintMigrator {
 
                 // Convert config
                 let yl_config = Self::convert_config(&config_path)?;
+                if !yl_config.ignore.is_empty() {
+                    ignore = yl_config.ignore.clone();
+                }
 
                 // Write yl config
                 let yl_config_path = project_path.join(".yl.yaml");
-                let yl_config_content = serde_yaml::to_string(&yl_config)?;
-                fs::write(&yl_config_path, yl_config_content)?;
+                let existing = fs::read_to_string(&yl_config_path).ok();
+                let yl_config_content = yl_config
+                    .to_canonical_yaml(&RuleRegistry::with_default_rules(), existing.as_deref())?;
+                write_atomic(&yl_config_path, yl_config_content, backup_suffix)?;
 
                 println!("Created yl config: {}", yl_config_path.display());
 
@@ -267,7 +322,7 @@ impl YamllintMigrator {
                 let original_content = fs::read_to_string(&config_path)?;
                 let report = Self::generate_migration_report(&original_content, &yl_config)?;
                 let report_path = project_path.join("yl-migration-report.md");
-                fs::write(&report_path, report)?;
+                write_atomic(&report_path, report, backup_suffix)?;
 
                 println!("Generated migration report: {}", report_path.display());
                 break;
@@ -275,31 +330,42 @@ impl YamllintMigrator {
         }
 
         // Convert directives in YAML files
-        Self::migrate_directives_in_directory(project_path)?;
+        Self::migrate_directives_in_directory(
+            project_path,
+            &IgnorePatterns::new(&ignore),
+            follow_symlinks,
+            backup_suffix,
+        )?;
 
         Ok(())
     }
 
-    /// Migrate yamllint directives in all YAML files in a directory
-    fn migrate_directives_in_directory<P: AsRef<Path>>(dir: P) -> Result<()> {
-        use walkdir::WalkDir;
-
+    /// Migrate yamllint directives in all YAML files in a directory that are not ignored
+    fn migrate_directives_in_directory<P: AsRef<Path>>(
+        dir: P,
+        ignore: &IgnorePatterns,
+        follow_symlinks: bool,
+        backup_suffix: Option<&str>,
+    ) -> Result<()> {
         let dir = dir.as_ref();
         let mut converted_files = 0;
 
-        for entry in WalkDir::new(dir).into_iter().filter_map(|e| e.ok()) {
-            let path = entry.path();
+        for path in walk_files(dir, follow_symlinks) {
+            // Ignore patterns are relative to the project
+            if ignore.is_ignored(path.strip_prefix(dir).unwrap_or(&path)) {
+                continue;
+            }
 
             // Check if it's a YAML file
             if let Some(extension) = path.extension() {
                 let is_yaml = matches!(extension.to_str(), Some("yaml") | Some("yml"));
 
                 if is_yaml {
-                    let content = fs::read_to_string(path)?;
+                    let content = fs::read_to_string(&path)?;
                     let converted_content = Self::convert_directives(&content);
 
                     if content != converted_content {
-                        fs::write(path, converted_content)?;
+                        write_atomic(&path, converted_content, backup_suffix)?;
                         converted_files += 1;
                         println!("Converted directives in: {}", path.display());
                     }
@@ -386,6 +452,36 @@ clean_content: "value"
         assert!(!config.enabled);
     }
 
+    #[test]
+    fn test_convert_rule_config_ignore() {
+        let rule: Value =
+            serde_yaml::from_str("max: 120\nignore: |\n  *.generated.yaml\n  vendor/\n").unwrap();
+        let config = YamllintMigrator::convert_rule_config(&rule).unwrap();
+
+        assert_eq!(config.ignore, vec!["*.generated.yaml", "vendor/"]);
+        assert!(!config.params.contains_key("ignore"));
+        assert_eq!(config.get_int("max"), Some(120));
+    }
+
+    #[test]
+    fn test_convert_indent_sequences() {
+        for (value, style) in [
+            ("true", "indented"),
+            ("false", "aligned"),
+            ("consistent", "consistent"),
+            ("whatever", "any"),
+        ] {
+            let rule: Value =
+                serde_yaml::from_str(&format!("spaces: 2\nindent-sequences: {value}\n")).unwrap();
+            let mut config = YamllintMigrator::convert_rule_config(&rule).unwrap();
+            YamllintMigrator::convert_indent_sequences(&mut config);
+
+            assert_eq!(config.get_string("sequence-style"), Some(style));
+            assert!(!config.params.contains_key("indent-sequences"));
+            assert_eq!(config.get_int("spaces"), Some(2));
+        }
+    }
+
     #[test]
     fn test_generate_migration_report() {
         let mut config = Config::default();
@@ -1,11 +1,21 @@
 use super::{LintStats, OutputFormatter};
 use crate::linter::{Level, Problem};
+use crate::messages::Message;
 use std::path::{Path, PathBuf};
 
 /// Human-readable output formatter
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct HumanFormatter {
     use_colors: bool,
+    /// Group problems under their file and end with a summary; otherwise write one
+    /// `path:line:col: level: message [rule]` line per problem and nothing else
+    grouped: bool,
+}
+
+impl Default for HumanFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[allow(dead_code)] // Some methods are part of API for future phases
@@ -14,12 +24,46 @@ impl HumanFormatter {
     pub fn new() -> Self {
         Self {
             use_colors: Self::should_use_colors(),
+            grouped: true,
         }
     }
 
     /// Create a new human formatter with explicit color setting
     pub fn with_colors(use_colors: bool) -> Self {
-        Self { use_colors }
+        Self {
+            use_colors,
+            grouped: true,
+        }
+    }
+
+    /// Create a formatter writing one line per problem without colors, for editor
+    /// quickfix lists and grep
+    pub fn compact() -> Self {
+        Self {
+            use_colors: false,
+            grouped: false,
+        }
+    }
+
+    /// Rule of a problem followed by its code, when it has one
+    fn rule_label(problem: &Problem) -> String {
+        match problem.code() {
+            Some(code) => format!("{} {code}", problem.rule),
+            None => problem.rule.clone(),
+        }
+    }
+
+    /// Format a problem as a single self-contained line
+    fn format_compact(path: &Path, problem: &Problem) -> String {
+        format!(
+            "{}:{}:{}: {}: {} [{}]",
+            path.display(),
+            problem.line,
+            problem.column,
+            problem.level,
+            problem.message,
+            Self::rule_label(problem)
+        )
     }
 
     /// Determine if colors should be used based on environment
@@ -70,65 +114,98 @@ impl HumanFormatter {
 
     /// Format statistics summary
     fn format_stats(&self, stats: &LintStats) -> String {
-        let mut parts = Vec::new();
+        // Message for `count` of something, in the given color when colors are on
+        let count = |count: usize, singular: &'static str, plural: &'static str, color: &str| {
+            let id = if count == 1 { singular } else { plural };
+            let text = Message::new(id).arg("count", count).text();
+            if self.use_colors && !color.is_empty() {
+                format!("\x1b[{color}m{text}\x1b[0m")
+            } else {
+                text
+            }
+        };
 
+        let mut parts = Vec::new();
         if stats.errors > 0 {
-            let text = if self.use_colors {
-                format!(
-                    "\x1b[31m{} error{}\x1b[0m",
-                    stats.errors,
-                    if stats.errors == 1 { "" } else { "s" }
-                )
-            } else {
-                format!(
-                    "{} error{}",
-                    stats.errors,
-                    if stats.errors == 1 { "" } else { "s" }
-                )
-            };
-            parts.push(text);
+            parts.push(count(stats.errors, "summary.error", "summary.errors", "31"));
         }
-
         if stats.warnings > 0 {
-            let text = if self.use_colors {
-                format!(
-                    "\x1b[33m{} warning{}\x1b[0m",
-                    stats.warnings,
-                    if stats.warnings == 1 { "" } else { "s" }
-                )
-            } else {
-                format!(
-                    "{} warning{}",
-                    stats.warnings,
-                    if stats.warnings == 1 { "" } else { "s" }
-                )
-            };
-            parts.push(text);
+            parts.push(count(
+                stats.warnings,
+                "summary.warning",
+                "summary.warnings",
+                "33",
+            ));
         }
-
         if stats.info > 0 {
-            let text = if self.use_colors {
-                format!("\x1b[36m{} info\x1b[0m", stats.info)
-            } else {
-                format!("{} info", stats.info)
-            };
-            parts.push(text);
+            parts.push(count(stats.info, "summary.info", "summary.info", "36"));
+        }
+        if stats.file_errors > 0 {
+            parts.push(count(
+                stats.file_errors,
+                "summary.file-error",
+                "summary.file-errors",
+                "",
+            ));
         }
 
-        if parts.is_empty() {
+        let summary = if parts.is_empty() {
+            let text = Message::new("summary.no-problems").text();
             if self.use_colors {
-                "\x1b[32mNo problems found\x1b[0m".to_string() // Green
+                format!("\x1b[32m{text}\x1b[0m") // Green
             } else {
-                "No problems found".to_string()
+                text
             }
         } else {
-            format!("Found {}", parts.join(", "))
+            Message::new("summary.found")
+                .arg("counts", parts.join(", "))
+                .text()
+        };
+
+        if stats.suppressed > 0 {
+            let id = if stats.suppressed == 1 {
+                "summary.suppressed"
+            } else {
+                "summary.suppressed-plural"
+            };
+            Message::new(id)
+                .arg("summary", summary)
+                .arg("count", stats.suppressed)
+                .text()
+        } else {
+            summary
+        }
+    }
+
+    /// Format a problem suppressed by a directive, dimmed
+    fn format_suppressed(&self, problem: &Problem) -> String {
+        let line = format!(
+            "  {}:{}: {} {} ({}) [suppressed]",
+            problem.line,
+            problem.column,
+            problem.level,
+            problem.message,
+            Self::rule_label(problem)
+        );
+        if self.use_colors {
+            format!("\x1b[2m{line}\x1b[0m") // Dim
+        } else {
+            line
         }
     }
 }
 
 impl OutputFormatter for HumanFormatter {
     fn format_results(&self, results: &[(PathBuf, Vec<Problem>)]) -> String {
+        if !self.grouped {
+            return results
+                .iter()
+                .map(|(path, problems)| self.format_file(path, problems))
+                .filter(|lines| !lines.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+
         let mut output = Vec::new();
         let stats = LintStats::from_results(results);
 
@@ -141,13 +218,22 @@ impl OutputFormatter for HumanFormatter {
             output.push(self.format_path(file_path));
 
             for problem in problems {
+                if problem.suppressed {
+                    output.push(self.format_suppressed(problem));
+                    continue;
+                }
+
                 let level = self.format_level(&problem.level);
                 let position = self.format_position(problem.line, problem.column);
-                let rule = self.format_rule(&problem.rule);
+                let rule = self.format_rule(&Self::rule_label(problem));
+                let change = problem
+                    .change
+                    .map(|change| format!(" [{change}]"))
+                    .unwrap_or_default();
 
                 output.push(format!(
-                    "  {}: {} {} {}",
-                    position, level, problem.message, rule
+                    "  {}: {} {} {}{}",
+                    position, level, problem.message, rule, change
                 ));
 
                 // Add suggestion if available
@@ -169,6 +255,18 @@ impl OutputFormatter for HumanFormatter {
 
         output.join("\n")
     }
+
+    fn streams(&self) -> bool {
+        !self.grouped
+    }
+
+    fn format_file(&self, path: &Path, problems: &[Problem]) -> String {
+        problems
+            .iter()
+            .map(|problem| Self::format_compact(path, problem))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 }
 
 // Add atty dependency for color detection
@@ -235,15 +333,90 @@ mod tests {
         let lines: Vec<&str> = output.lines().collect();
 
         assert_eq!(lines[0], "test.yaml");
-        assert_eq!(lines[1], "  10:5: error line too long (line-length)");
+        assert_eq!(
+            lines[1],
+            "  10:5: error line too long (line-length YL10100)"
+        );
         assert_eq!(
             lines[2],
-            "  15:1: warning trailing whitespace (trailing-spaces)"
+            "  15:1: warning trailing whitespace (trailing-spaces YL10200)"
         );
         assert_eq!(lines[3], "    Suggestion: Remove trailing spaces");
         assert_eq!(lines[5], "Found 1 error, 1 warning");
     }
 
+    #[test]
+    fn test_human_formatter_suppressed_problems() {
+        let mut suppressed = Problem::new(3, 8, Level::Error, "truthy", "truthy value");
+        suppressed.suppressed = true;
+        let results = vec![(
+            PathBuf::from("test.yaml"),
+            vec![
+                Problem::new(1, 81, Level::Warning, "line-length", "line too long"),
+                suppressed,
+            ],
+        )];
+
+        let output = HumanFormatter::with_colors(false).format_results(&results);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines[2],
+            "  3:8: error truthy value (truthy YL40100) [suppressed]"
+        );
+        assert_eq!(
+            lines[4],
+            "Found 1 warning (1 problem suppressed by directives)"
+        );
+
+        let output = HumanFormatter::with_colors(true).format_results(&results);
+        assert!(
+            output
+                .contains("\x1b[2m  3:8: error truthy value (truthy YL40100) [suppressed]\x1b[0m")
+        );
+    }
+
+    #[test]
+    fn test_compact_formatter() {
+        let formatter = HumanFormatter::compact();
+        let results = vec![
+            (
+                PathBuf::from("a.yaml"),
+                vec![
+                    Problem::new(10, 5, Level::Error, "line-length", "line too long"),
+                    Problem::with_suggestion(
+                        15,
+                        1,
+                        Level::Warning,
+                        "trailing-spaces",
+                        "trailing whitespace",
+                        "Remove trailing spaces",
+                    ),
+                ],
+            ),
+            (PathBuf::from("clean.yaml"), vec![]),
+            (
+                PathBuf::from("b.yaml"),
+                vec![
+                    Problem::new(2, 1, Level::Info, "comments", "missing space"),
+                    Problem::new(3, 1, Level::Info, "todo-plugin", "found TODO"),
+                ],
+            ),
+        ];
+
+        assert!(formatter.streams());
+        assert_eq!(
+            formatter.format_results(&results),
+            "a.yaml:10:5: error: line too long [line-length YL10100]\n\
+             a.yaml:15:1: warning: trailing whitespace [trailing-spaces YL10200]\n\
+             b.yaml:2:1: info: missing space [comments YL20600]\n\
+             b.yaml:3:1: info: found TODO [todo-plugin]"
+        );
+        assert_eq!(
+            formatter.format_results(&[(PathBuf::from("clean.yaml"), vec![])]),
+            ""
+        );
+    }
+
     #[test]
     fn test_format_level_no_colors() {
         let formatter = HumanFormatter::with_colors(false);
@@ -298,6 +471,8 @@ mod tests {
             errors: 2,
             warnings: 2,
             info: 1,
+            file_errors: 0,
+            suppressed: 0,
         };
 
         assert_eq!(
@@ -316,8 +491,28 @@ mod tests {
             errors: 1,
             warnings: 0,
             info: 0,
+            file_errors: 0,
+            suppressed: 0,
         };
 
         assert_eq!(formatter.format_stats(&stats), "Found 1 error");
     }
+
+    #[test]
+    fn test_format_stats_file_errors() {
+        let formatter = HumanFormatter::with_colors(false);
+        let stats = LintStats {
+            total_files: 2,
+            files_with_problems: 1,
+            total_problems: 1,
+            errors: 1,
+            file_errors: 1,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            formatter.format_stats(&stats),
+            "Found 1 error, 1 file could not be linted"
+        );
+    }
 }
@@ -1,6 +1,8 @@
 use super::{LintStats, OutputFormatter};
-use crate::linter::Problem;
+use crate::linter::{LineChange, Problem};
+use crate::rules;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 /// JSON output formatter
@@ -32,6 +34,7 @@ struct JsonStats {
     errors: usize,
     warnings: usize,
     info: usize,
+    file_errors: usize,
 }
 
 impl From<&LintStats> for JsonStats {
@@ -43,6 +46,7 @@ impl From<&LintStats> for JsonStats {
             errors: stats.errors,
             warnings: stats.warnings,
             info: stats.info,
+            file_errors: stats.file_errors,
         }
     }
 }
@@ -58,20 +62,38 @@ struct JsonFileResult {
 
 /// JSON representation of a single problem
 #[derive(Debug, Serialize, Deserialize)]
-struct JsonProblem {
+pub(super) struct JsonProblem {
     /// Line number (1-based)
-    line: usize,
+    pub(super) line: usize,
     /// Column number (1-based)
-    column: usize,
+    pub(super) column: usize,
     /// Severity level
-    level: String,
+    pub(super) level: String,
     /// Rule that detected the problem
-    rule: String,
+    pub(super) rule: String,
+    /// Stable code of the problem, such as `YL10101`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) code: Option<String>,
     /// Problem description
-    message: String,
+    pub(super) message: String,
+    /// ID of the message in the message catalog, stable across languages
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) message_id: Option<String>,
+    /// Values of the message's placeholders
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub(super) message_args: BTreeMap<String, String>,
     /// Optional suggestion for fixing the problem
     #[serde(skip_serializing_if = "Option::is_none")]
-    suggestion: Option<String>,
+    pub(super) suggestion: Option<String>,
+    /// Whether `yl fix` can fix the problem
+    #[serde(default)]
+    pub(super) fixable: bool,
+    /// Project of the file, when the configuration defines `projects`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) project: Option<String>,
+    /// Whether the line was added by the change or is context around it, in diff mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) change: Option<LineChange>,
 }
 
 impl From<&Problem> for JsonProblem {
@@ -81,8 +103,14 @@ impl From<&Problem> for JsonProblem {
             column: problem.column,
             level: problem.level.to_string(),
             rule: problem.rule.clone(),
+            code: problem.code().map(str::to_string),
             message: problem.message.clone(),
+            message_id: problem.message_id.clone(),
+            message_args: problem.message_args.clone(),
             suggestion: problem.suggestion.clone(),
+            fixable: rules::is_fixable(&problem.rule),
+            project: problem.project.clone(),
+            change: problem.change,
         }
     }
 }
@@ -170,6 +198,7 @@ mod tests {
         assert_eq!(first_problem.rule, "line-length");
         assert_eq!(first_problem.message, "line too long");
         assert_eq!(first_problem.suggestion, None);
+        assert!(!first_problem.fixable);
 
         let second_problem = &first_file.problems[1];
         assert_eq!(second_problem.line, 15);
@@ -181,6 +210,7 @@ mod tests {
             second_problem.suggestion,
             Some("Remove trailing spaces".to_string())
         );
+        assert!(second_problem.fixable);
 
         // Check second file without problems
         let second_file = &parsed.files[1];
@@ -218,6 +248,8 @@ mod tests {
             errors: 4,
             warnings: 5,
             info: 1,
+            file_errors: 0,
+            suppressed: 0,
         };
 
         let json_stats = JsonStats::from(&stats);
@@ -240,6 +272,7 @@ mod tests {
                 errors: 1,
                 warnings: 0,
                 info: 0,
+                file_errors: 0,
             },
             files: vec![JsonFileResult {
                 path: "test.yaml".to_string(),
@@ -248,8 +281,14 @@ mod tests {
                     column: 1,
                     level: "error".to_string(),
                     rule: "test-rule".to_string(),
+                    code: Some("YL10101".to_string()),
                     message: "test message".to_string(),
+                    message_id: Some("test-rule.found".to_string()),
+                    message_args: BTreeMap::from([("key".to_string(), "name".to_string())]),
                     suggestion: None,
+                    fixable: false,
+                    project: None,
+                    change: Some(LineChange::Added),
                 }],
             }],
         };
@@ -265,5 +304,19 @@ mod tests {
             deserialized.files[0].problems.len(),
             original.files[0].problems.len()
         );
+        assert!(serialized.contains(r#""change":"added""#));
+        assert!(serialized.contains(r#""message_id":"test-rule.found""#));
+        assert_eq!(
+            deserialized.files[0].problems[0].code.as_deref(),
+            Some("YL10101")
+        );
+        assert_eq!(
+            deserialized.files[0].problems[0].message_args,
+            original.files[0].problems[0].message_args
+        );
+        assert_eq!(
+            deserialized.files[0].problems[0].change,
+            Some(LineChange::Added)
+        );
     }
 }
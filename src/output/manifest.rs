@@ -0,0 +1,301 @@
+//! `--manifest`: a JSON record of exactly what a run did, to reproduce or audit it later
+//!
+//! The manifest pins the yl build, the resolved configuration (as a hash of its canonical
+//! form, with the profile and command-line overrides applied), the settings of every
+//! enabled rule, and the content hash and problem counts of every linted file. Files and
+//! rules are sorted, so two manifests of the same run compare equal apart from timings.
+
+use super::LintStats;
+use super::store::{RunInfo, file_hash, sha256_hex};
+use crate::config::Config;
+use crate::linter::{Level, Problem};
+use crate::rules::{ConfigValue, RuleRegistry};
+use chrono::{DateTime, Utc};
+use eyre::{Context, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Format version of the manifest, raised when fields change meaning or are removed
+const MANIFEST_VERSION: u32 = 1;
+
+/// Everything recorded about a run
+#[derive(Debug, Clone, Serialize)]
+pub struct RunManifest {
+    pub manifest_version: u32,
+    /// Version of yl that ran
+    pub version: String,
+    /// Build the binary came from, as `git describe` output
+    pub build: String,
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: u128,
+    /// Working directory of the run
+    pub directory: PathBuf,
+    /// Command-line arguments, without the program name
+    pub arguments: Vec<String>,
+    /// Commit checked out in the working directory, if it is a git repository
+    pub git_commit: Option<String>,
+    pub config: ConfigRecord,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub policy: Option<PolicyRecord>,
+    /// Enabled rules with their resolved settings, by id
+    pub rules: Vec<RuleRecord>,
+    /// Linted files, by path
+    pub files: Vec<FileRecord>,
+    pub totals: LintStats,
+}
+
+/// The root configuration of a run
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigRecord {
+    /// Configuration file that was loaded, if any
+    pub path: Option<PathBuf>,
+    /// Profile selected with `--profile`
+    pub profile: Option<String>,
+    /// SHA-256 of the resolved configuration in canonical form
+    pub sha256: String,
+}
+
+/// The team policy a run was checked against with `--policy`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PolicyRecord {
+    pub path: PathBuf,
+    /// SHA-256 of the policy file
+    pub sha256: Option<String>,
+    pub violations: usize,
+}
+
+/// An enabled rule as the root configuration sets it up
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RuleRecord {
+    pub id: String,
+    /// Built-in rules are versioned with yl itself
+    pub version: String,
+    pub level: Level,
+    pub params: BTreeMap<String, ConfigValue>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub ignore: Vec<String>,
+}
+
+/// A linted file
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileRecord {
+    pub path: PathBuf,
+    /// SHA-256 of the file's content, `None` if it cannot be read
+    pub sha256: Option<String>,
+    /// SHA-256 of the configuration applied to the file, when nested configuration files
+    /// make it differ from the root configuration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config_sha256: Option<String>,
+    pub problems: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    pub info: usize,
+    /// Problems suppressed by directives, not counted in `problems`
+    pub suppressed: usize,
+}
+
+/// SHA-256 of a configuration in canonical form, so formatting and key order of the
+/// configuration file do not change it
+pub fn config_hash(config: &Config) -> Result<String> {
+    let canonical = config
+        .to_canonical_yaml(&RuleRegistry::with_default_rules(), None)
+        .context("Failed to hash configuration")?;
+    Ok(sha256_hex(canonical.as_bytes()))
+}
+
+/// The enabled rules of `config`, by id
+pub fn rule_records(config: &Config) -> Vec<RuleRecord> {
+    let registry = RuleRegistry::with_default_rules();
+    let mut ids = registry.rule_ids();
+    ids.sort_unstable();
+    ids.into_iter()
+        .map(|id| (id, config.get_rule_config(id, &registry)))
+        .filter(|(_, rule_config)| rule_config.enabled)
+        .map(|(id, rule_config)| RuleRecord {
+            id: id.to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            level: rule_config.level,
+            params: rule_config.params.into_iter().collect(),
+            ignore: rule_config.ignore,
+        })
+        .collect()
+}
+
+impl RunManifest {
+    /// Record a run; `results` include suppressed problems, which are counted separately,
+    /// and `file_config` gives the configuration hash of a file
+    pub fn new<F>(
+        run: &RunInfo,
+        config: ConfigRecord,
+        rules: Vec<RuleRecord>,
+        policy: Option<PolicyRecord>,
+        results: &[(PathBuf, Vec<Problem>)],
+        mut file_config: F,
+    ) -> Self
+    where
+        F: FnMut(&Path) -> Option<String>,
+    {
+        let mut files: Vec<FileRecord> = results
+            .iter()
+            .map(|(path, problems)| {
+                let count = |level: Level| {
+                    problems
+                        .iter()
+                        .filter(|problem| !problem.suppressed && problem.level == level)
+                        .count()
+                };
+                let suppressed = problems.iter().filter(|problem| problem.suppressed).count();
+                FileRecord {
+                    path: path.clone(),
+                    sha256: file_hash(path),
+                    config_sha256: file_config(path).filter(|hash| *hash != config.sha256),
+                    problems: problems.len() - suppressed,
+                    errors: count(Level::Error),
+                    warnings: count(Level::Warning),
+                    info: count(Level::Info),
+                    suppressed,
+                }
+            })
+            .collect();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Self {
+            manifest_version: MANIFEST_VERSION,
+            version: run.version.clone(),
+            build: env!("GIT_DESCRIBE").to_string(),
+            started_at: run.started_at,
+            duration_ms: run.duration.as_millis(),
+            directory: run.directory.clone(),
+            arguments: run.arguments.clone(),
+            git_commit: run.git_commit.clone(),
+            config,
+            policy,
+            rules,
+            files,
+            totals: LintStats::from_results(results),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn problem(level: Level, suppressed: bool) -> Problem {
+        let mut problem = Problem::new(1, 1, level, "trailing-spaces", "trailing spaces");
+        problem.suppressed = suppressed;
+        problem
+    }
+
+    fn run() -> RunInfo {
+        RunInfo {
+            started_at: Utc::now(),
+            duration: Duration::from_millis(42),
+            version: "0.1.0".to_string(),
+            git_commit: None,
+            directory: PathBuf::from("/repo"),
+            arguments: vec!["--manifest".to_string(), "run.json".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_config_hash_is_canonical() {
+        let dir = TempDir::new().unwrap();
+        let load = |name: &str, yaml: &str| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, yaml).unwrap();
+            Config::from_file(&path).unwrap()
+        };
+        let config = load(
+            "a.yaml",
+            "rules:\n  truthy: {enabled: true, level: Warning, params: {}}\n",
+        );
+        let reordered = load(
+            "b.yaml",
+            "# Same settings\nrules:\n  truthy:\n    params: {}\n    level: Warning\n    enabled: true\n",
+        );
+        assert_eq!(
+            config_hash(&config).unwrap(),
+            config_hash(&reordered).unwrap()
+        );
+        assert_ne!(
+            config_hash(&config).unwrap(),
+            config_hash(&Config::default()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rule_records_list_enabled_rules() {
+        let mut config = Config::default();
+        let records = rule_records(&config);
+        assert!(records.windows(2).all(|pair| pair[0].id < pair[1].id));
+        assert!(records.iter().any(|record| record.id == "line-length"));
+
+        let registry = RuleRegistry::with_default_rules();
+        let mut line_length = config.get_rule_config("line-length", &registry);
+        line_length.enabled = false;
+        config.rules.insert("line-length".to_string(), line_length);
+        assert!(
+            !rule_records(&config)
+                .iter()
+                .any(|record| record.id == "line-length")
+        );
+    }
+
+    #[test]
+    fn test_manifest_records_files() {
+        let dir = TempDir::new().unwrap();
+        let a = dir.path().join("a.yaml");
+        let b = dir.path().join("b.yaml");
+        std::fs::write(&a, "hello").unwrap();
+        std::fs::write(&b, "key: value\n").unwrap();
+        let results = vec![
+            (
+                b.clone(),
+                vec![
+                    problem(Level::Error, false),
+                    problem(Level::Warning, false),
+                    problem(Level::Error, true),
+                ],
+            ),
+            (a.clone(), vec![]),
+            (dir.path().join("deleted.yaml"), vec![]),
+        ];
+        let config = ConfigRecord {
+            path: None,
+            profile: None,
+            sha256: "root".to_string(),
+        };
+
+        let manifest = RunManifest::new(&run(), config, vec![], None, &results, |path| {
+            Some(if path == b { "nested" } else { "root" }.to_string())
+        });
+
+        let paths: Vec<_> = manifest.files.iter().map(|file| &file.path).collect();
+        assert_eq!(paths, [&a, &b, &dir.path().join("deleted.yaml")]);
+        assert_eq!(
+            manifest.files[0].sha256.as_deref(),
+            Some("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824")
+        );
+        assert_eq!(manifest.files[0].config_sha256, None);
+        assert_eq!(manifest.files[2].sha256, None);
+
+        let file = &manifest.files[1];
+        assert_eq!(file.config_sha256.as_deref(), Some("nested"));
+        assert_eq!(
+            (file.problems, file.errors, file.warnings, file.suppressed),
+            (2, 1, 1, 1)
+        );
+        assert_eq!(manifest.totals.total_files, 3);
+        assert_eq!(manifest.totals.suppressed, 1);
+        assert_eq!(manifest.duration_ms, 42);
+
+        let json = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(json["manifest_version"], 1);
+        assert_eq!(json["totals"]["errors"], 1);
+        assert!(json.get("policy").is_none());
+    }
+}
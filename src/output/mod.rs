@@ -1,25 +1,55 @@
 pub mod human;
 pub mod json;
+pub mod manifest;
+pub mod ndjson;
+pub mod rdjson;
+pub mod sarif;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod store;
+pub mod webhook;
 
+use crate::config::Config;
 use crate::linter::Problem;
-use std::path::PathBuf;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
-/// Trait for formatting linting results
-pub trait OutputFormatter {
+/// Trait for formatting linting results; shared with the worker threads when streaming
+pub trait OutputFormatter: Sync {
     /// Format the linting results for output
     fn format_results(&self, results: &[(PathBuf, Vec<Problem>)]) -> String;
+
+    /// Whether results are written per file as they finish, via [`Self::format_file`],
+    /// instead of all at once at the end
+    fn streams(&self) -> bool {
+        false
+    }
+
+    /// Format the problems of a single finished file; empty if there is nothing to write
+    fn format_file(&self, _path: &Path, _problems: &[Problem]) -> String {
+        String::new()
+    }
 }
 
-/// Get the appropriate formatter for the given format
-pub fn get_formatter(format: &crate::cli::OutputFormat) -> Box<dyn OutputFormatter> {
+/// Get the appropriate formatter for the given format; `config` is used by formats that
+/// embed fixes
+pub fn get_formatter(
+    format: &crate::cli::OutputFormat,
+    config: &Config,
+) -> Box<dyn OutputFormatter> {
     match format {
         crate::cli::OutputFormat::Human => Box::new(human::HumanFormatter::new()),
+        crate::cli::OutputFormat::Compact => Box::new(human::HumanFormatter::compact()),
         crate::cli::OutputFormat::Json => Box::new(json::JsonFormatter::new()),
+        crate::cli::OutputFormat::Ndjson => Box::new(ndjson::NdjsonFormatter::new()),
+        crate::cli::OutputFormat::Rdjson => Box::new(rdjson::RdjsonFormatter::new(config.clone())),
+        crate::cli::OutputFormat::Sarif => Box::new(sarif::SarifFormatter::new()),
     }
 }
 
 /// Statistics about linting results
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct LintStats {
     pub total_files: usize,
     pub files_with_problems: usize,
@@ -27,6 +57,10 @@ pub struct LintStats {
     pub errors: usize,
     pub warnings: usize,
     pub info: usize,
+    /// Files that could not be read or linted
+    pub file_errors: usize,
+    /// Problems suppressed by directives, which are not counted anywhere else
+    pub suppressed: usize,
 }
 
 impl LintStats {
@@ -36,15 +70,22 @@ impl LintStats {
             total_files: results.len(),
             files_with_problems: results
                 .iter()
-                .filter(|(_, problems)| !problems.is_empty())
+                .filter(|(_, problems)| problems.iter().any(|problem| !problem.suppressed))
                 .count(),
             ..Default::default()
         };
 
         for (_, problems) in results {
-            stats.total_problems += problems.len();
+            if problems.iter().any(Problem::is_file_error) {
+                stats.file_errors += 1;
+            }
 
             for problem in problems {
+                if problem.suppressed {
+                    stats.suppressed += 1;
+                    continue;
+                }
+                stats.total_problems += 1;
                 match problem.level {
                     crate::linter::Level::Error => stats.errors += 1,
                     crate::linter::Level::Warning => stats.warnings += 1,
@@ -56,6 +97,31 @@ impl LintStats {
         stats
     }
 
+    /// Calculate statistics for each project, named projects first in name order and
+    /// files outside any project last
+    pub fn by_project<'a, F>(
+        results: &[(PathBuf, Vec<Problem>)],
+        project_of: F,
+    ) -> Vec<(Option<String>, Self)>
+    where
+        F: Fn(&Path) -> Option<&'a str>,
+    {
+        let mut groups: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for (path, problems) in results {
+            let project = project_of(path);
+            groups
+                .entry((project.is_none(), project))
+                .or_default()
+                .push((path.clone(), problems.clone()));
+        }
+        groups
+            .into_iter()
+            .map(|((_, project), results)| {
+                (project.map(str::to_string), Self::from_results(&results))
+            })
+            .collect()
+    }
+
     /// Check if there are any errors
     pub fn has_errors(&self) -> bool {
         self.errors > 0
@@ -83,6 +149,7 @@ mod tests {
         assert_eq!(stats.errors, 0);
         assert_eq!(stats.warnings, 0);
         assert_eq!(stats.info, 0);
+        assert_eq!(stats.file_errors, 0);
         assert!(!stats.has_errors());
         assert!(!stats.has_problems());
     }
@@ -115,4 +182,83 @@ mod tests {
         assert!(stats.has_errors());
         assert!(stats.has_problems());
     }
+
+    #[test]
+    fn test_lint_stats_file_errors() {
+        let results = vec![
+            (
+                PathBuf::from("bad.yaml"),
+                vec![Problem::file_error("Failed to read file")],
+            ),
+            (PathBuf::from("good.yaml"), vec![]),
+        ];
+
+        let stats = LintStats::from_results(&results);
+
+        assert_eq!(stats.total_files, 2);
+        assert_eq!(stats.file_errors, 1);
+        assert_eq!(stats.errors, 1);
+        assert!(stats.has_errors());
+    }
+
+    #[test]
+    fn test_lint_stats_suppressed() {
+        let mut suppressed = Problem::new(2, 1, Level::Error, "truthy", "truthy value");
+        suppressed.suppressed = true;
+        let results = vec![
+            (
+                PathBuf::from("a.yaml"),
+                vec![
+                    Problem::new(1, 1, Level::Warning, "line-length", "line too long"),
+                    suppressed.clone(),
+                ],
+            ),
+            (PathBuf::from("b.yaml"), vec![suppressed]),
+        ];
+
+        let stats = LintStats::from_results(&results);
+
+        assert_eq!(stats.suppressed, 2);
+        assert_eq!(stats.total_problems, 1);
+        assert_eq!(stats.files_with_problems, 1);
+        assert!(!stats.has_errors());
+    }
+
+    #[test]
+    fn test_lint_stats_by_project() {
+        let results = vec![
+            (
+                PathBuf::from("web/a.yaml"),
+                vec![Problem::new(1, 1, Level::Error, "rule1", "error message")],
+            ),
+            (PathBuf::from("root.yaml"), vec![]),
+            (
+                PathBuf::from("api/b.yaml"),
+                vec![Problem::new(
+                    1,
+                    1,
+                    Level::Warning,
+                    "rule2",
+                    "warning message",
+                )],
+            ),
+            (PathBuf::from("api/c.yaml"), vec![]),
+        ];
+
+        let groups = LintStats::by_project(&results, |path| {
+            ["api", "web"]
+                .into_iter()
+                .find(|project| path.starts_with(project))
+        });
+
+        let projects: Vec<_> = groups
+            .iter()
+            .map(|(project, _)| project.as_deref())
+            .collect();
+        assert_eq!(projects, vec![Some("api"), Some("web"), None]);
+        assert_eq!(groups[0].1.total_files, 2);
+        assert_eq!(groups[0].1.warnings, 1);
+        assert_eq!(groups[1].1.errors, 1);
+        assert_eq!(groups[2].1.total_problems, 0);
+    }
 }
@@ -0,0 +1,109 @@
+use super::OutputFormatter;
+use super::json::JsonProblem;
+use crate::linter::Problem;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// JSON Lines output formatter: one JSON object per problem, written per file
+#[derive(Debug, Default)]
+pub struct NdjsonFormatter;
+
+impl NdjsonFormatter {
+    /// Create a new JSON Lines formatter
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// A single problem together with the file it was found in
+#[derive(Debug, Serialize, Deserialize)]
+struct NdjsonProblem {
+    /// Path to the file
+    path: String,
+    #[serde(flatten)]
+    problem: JsonProblem,
+}
+
+impl OutputFormatter for NdjsonFormatter {
+    fn format_results(&self, results: &[(PathBuf, Vec<Problem>)]) -> String {
+        results
+            .iter()
+            .map(|(path, problems)| self.format_file(path, problems))
+            .filter(|lines| !lines.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn streams(&self) -> bool {
+        true
+    }
+
+    fn format_file(&self, path: &Path, problems: &[Problem]) -> String {
+        problems
+            .iter()
+            .map(|problem| {
+                let line = NdjsonProblem {
+                    path: path.display().to_string(),
+                    problem: JsonProblem::from(problem),
+                };
+                serde_json::to_string(&line)
+                    .unwrap_or_else(|e| format!(r#"{{"error": "Failed to serialize JSON: {e}"}}"#))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::Level;
+
+    #[test]
+    fn test_ndjson_one_line_per_problem() {
+        let formatter = NdjsonFormatter::new();
+        let results = vec![
+            (
+                PathBuf::from("a.yaml"),
+                vec![
+                    Problem::new(1, 5, Level::Error, "trailing-spaces", "trailing whitespace"),
+                    Problem::new(3, 81, Level::Warning, "line-length", "line too long"),
+                ],
+            ),
+            (PathBuf::from("clean.yaml"), vec![]),
+            (
+                PathBuf::from("b.yaml"),
+                vec![Problem::new(
+                    2,
+                    1,
+                    Level::Error,
+                    "key-duplicates",
+                    "duplicate key",
+                )],
+            ),
+        ];
+
+        let output = formatter.format_results(&results);
+        let lines: Vec<NdjsonProblem> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("Invalid JSON line"))
+            .collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].path, "a.yaml");
+        assert_eq!(lines[0].problem.rule, "trailing-spaces");
+        assert_eq!(lines[1].problem.level, "warning");
+        assert_eq!(lines[2].path, "b.yaml");
+        assert_eq!(lines[2].problem.line, 2);
+    }
+
+    #[test]
+    fn test_ndjson_clean_file_is_empty() {
+        let formatter = NdjsonFormatter::new();
+        assert!(
+            formatter
+                .format_file(Path::new("clean.yaml"), &[])
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,347 @@
+use super::OutputFormatter;
+use crate::codes;
+use crate::config::Config;
+use crate::fixes::{FixEdit, FixEngine};
+use crate::linter::{Level, Problem};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Name and homepage reported as the source of every diagnostic
+const SOURCE_NAME: &str = "yl";
+const SOURCE_URL: &str = "https://github.com/scottidler/yl";
+
+/// Reviewdog Diagnostic Format (rdjson) output formatter.
+///
+/// Problems that can be fixed automatically carry the fix as a suggestion, computed by
+/// applying the fix to the file with the given configuration.
+pub struct RdjsonFormatter {
+    config: Config,
+    fixes: FixEngine,
+    /// Content of files that were linted from memory rather than from disk
+    sources: HashMap<PathBuf, String>,
+}
+
+impl RdjsonFormatter {
+    /// Create a new rdjson formatter computing suggestions with `config`
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            fixes: FixEngine::new(),
+            sources: HashMap::new(),
+        }
+    }
+
+    /// Use `content` as the content of `path` instead of reading it from disk
+    #[cfg_attr(not(feature = "server"), allow(dead_code))] // Only used by `yl client` and `yl serve`
+    pub fn with_source(mut self, path: PathBuf, content: String) -> Self {
+        self.sources.insert(path, content);
+        self
+    }
+
+    /// Build the diagnostics of one file
+    fn diagnostics(&self, path: &Path, problems: &[Problem]) -> Vec<Diagnostic> {
+        // Without the content, columns stay in characters and no suggestions are made
+        let content = match self.sources.get(path) {
+            Some(content) => Some(content.clone()),
+            None => std::fs::read_to_string(path).ok(),
+        };
+
+        problems
+            .iter()
+            .map(|problem| {
+                let column = content
+                    .as_deref()
+                    .and_then(|content| content.lines().nth(problem.line.saturating_sub(1)))
+                    .map_or(problem.column, |line| byte_column(line, problem.column));
+
+                Diagnostic {
+                    message: problem.message.clone(),
+                    location: Location {
+                        path: path.display().to_string(),
+                        range: Range {
+                            start: Position {
+                                line: problem.line,
+                                column,
+                            },
+                            end: None,
+                        },
+                    },
+                    severity: severity(&problem.level),
+                    code: Code {
+                        value: problem.code().unwrap_or(&problem.rule).to_string(),
+                        url: codes::docs_url(&problem.rule),
+                    },
+                    suggestions: content
+                        .as_deref()
+                        .and_then(|content| self.suggestion(content, problem))
+                        .into_iter()
+                        .collect(),
+                }
+            })
+            .collect()
+    }
+
+    /// The edit that fixes a single problem, if it can be fixed automatically
+    fn suggestion(&self, content: &str, problem: &Problem) -> Option<Suggestion> {
+        if !self.fixes.has_fix(&problem.rule) {
+            return None;
+        }
+        let plan = self
+            .fixes
+            .plan(content, std::slice::from_ref(problem), &self.config)
+            .ok()?;
+        plan.edits
+            .first()
+            .map(|edit| edit_suggestion(content, edit))
+    }
+}
+
+/// Byte-based column (1-based) of a character-based column in a line
+fn byte_column(line: &str, column: usize) -> usize {
+    line.char_indices()
+        .nth(column.saturating_sub(1))
+        .map_or(line.len(), |(index, _)| index)
+        + 1
+}
+
+/// Map a problem level to an rdjson severity
+fn severity(level: &Level) -> &'static str {
+    match level {
+        Level::Error => "ERROR",
+        Level::Warning => "WARNING",
+        Level::Info => "INFO",
+    }
+}
+
+/// Suggestion replacing the lines of `content` that an edit replaces
+fn edit_suggestion(content: &str, edit: &FixEdit) -> Suggestion {
+    let start = Position {
+        line: edit.start_line,
+        column: 1,
+    };
+    // The end is the start of the line after the region, or the end of a last line
+    // without a line break
+    let last = content
+        .split_inclusive('\n')
+        .nth(edit.end_line().saturating_sub(2))
+        .filter(|_| edit.removed_lines > 0);
+    let end = match last {
+        Some(last) if !last.ends_with('\n') => Position {
+            line: edit.end_line() - 1,
+            column: last.len() + 1,
+        },
+        _ => Position {
+            line: edit.end_line(),
+            column: 1,
+        },
+    };
+
+    Suggestion {
+        range: Range {
+            start,
+            end: Some(end),
+        },
+        text: edit.text.clone(),
+    }
+}
+
+/// rdjson `DiagnosticResult`
+#[derive(Debug, Serialize)]
+struct DiagnosticResult {
+    source: Source,
+    diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug, Serialize)]
+struct Source {
+    name: &'static str,
+    url: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct Diagnostic {
+    message: String,
+    location: Location,
+    severity: &'static str,
+    code: Code,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    suggestions: Vec<Suggestion>,
+}
+
+#[derive(Debug, Serialize)]
+struct Location {
+    path: String,
+    range: Range,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct Range {
+    start: Position,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end: Option<Position>,
+}
+
+/// 1-based line and byte-based column
+#[derive(Debug, PartialEq, Serialize)]
+struct Position {
+    line: usize,
+    column: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct Code {
+    /// Code of the problem, or its rule when it has none
+    value: String,
+    /// Documentation of the rule
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct Suggestion {
+    range: Range,
+    text: String,
+}
+
+impl OutputFormatter for RdjsonFormatter {
+    fn format_results(&self, results: &[(PathBuf, Vec<Problem>)]) -> String {
+        let output = DiagnosticResult {
+            source: Source {
+                name: SOURCE_NAME,
+                url: SOURCE_URL,
+            },
+            diagnostics: results
+                .iter()
+                .flat_map(|(path, problems)| self.diagnostics(path, problems))
+                .collect(),
+        };
+
+        serde_json::to_string_pretty(&output)
+            .unwrap_or_else(|e| format!(r#"{{"error": "Failed to serialize JSON: {e}"}}"#))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_rdjson_output() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("a.yaml");
+        std::fs::write(&file, "---\nname: \"é\"   \nkey: value\n").unwrap();
+
+        let formatter = RdjsonFormatter::new(Config::default());
+        let results = vec![(
+            file.clone(),
+            vec![
+                Problem::new(
+                    2,
+                    11,
+                    Level::Error,
+                    "trailing-spaces",
+                    "trailing whitespace",
+                ),
+                Problem::new(3, 1, Level::Warning, "key-ordering", "wrong order"),
+            ],
+        )];
+        let output: serde_json::Value =
+            serde_json::from_str(&formatter.format_results(&results)).unwrap();
+
+        assert_eq!(output["source"]["name"], "yl");
+        let diagnostics = output["diagnostics"].as_array().unwrap();
+        assert_eq!(diagnostics.len(), 2);
+
+        let trailing = &diagnostics[0];
+        assert_eq!(trailing["severity"], "ERROR");
+        assert_eq!(trailing["code"]["value"], "YL10200");
+        assert_eq!(
+            trailing["code"]["url"],
+            "https://github.com/scottidler/yl/blob/main/docs/codes.md#trailing-spaces"
+        );
+        assert_eq!(trailing["location"]["path"], file.display().to_string());
+        // The é before the trailing spaces takes two bytes
+        assert_eq!(trailing["location"]["range"]["start"]["column"], 12);
+        let suggestion = &trailing["suggestions"][0];
+        assert_eq!(suggestion["text"], "name: \"é\"\n");
+        assert_eq!(
+            suggestion["range"],
+            serde_json::json!({ "start": { "line": 2, "column": 1 }, "end": { "line": 3, "column": 1 } })
+        );
+
+        assert_eq!(diagnostics[1]["severity"], "WARNING");
+        assert!(diagnostics[1].get("suggestions").is_none());
+    }
+
+    #[test]
+    fn test_edit_suggestion() {
+        let problem = |rule| Problem::new(2, 1, Level::Error, rule, "problem");
+
+        // A line break added at the end of the file
+        let content = "a: 1\nb: 2";
+        let edit = FixEdit::between(content, "a: 1\nb: 2\n", &problem("new-line-at-end-of-file"));
+        let suggestion = edit_suggestion(content, &edit.unwrap());
+        assert_eq!(
+            suggestion.range,
+            Range {
+                start: Position { line: 2, column: 1 },
+                end: Some(Position { line: 2, column: 5 }),
+            }
+        );
+        assert_eq!(suggestion.text, "b: 2\n");
+
+        // Lines removed from the middle
+        let content = "a: 1\n\n\n\nb: 2\n";
+        let edit = FixEdit::between(content, "a: 1\n\nb: 2\n", &problem("empty-lines"));
+        let suggestion = edit_suggestion(content, &edit.unwrap());
+        assert_eq!(suggestion.range.start, Position { line: 3, column: 1 });
+        assert_eq!(suggestion.range.end, Some(Position { line: 5, column: 1 }));
+        assert_eq!(suggestion.text, "");
+    }
+
+    #[test]
+    fn test_source_from_memory() {
+        let path = PathBuf::from("/nonexistent/a.yaml");
+        let formatter = RdjsonFormatter::new(Config::default())
+            .with_source(path.clone(), "---\nkey: value  \n".to_string());
+        let results = vec![(
+            path,
+            vec![Problem::new(
+                2,
+                11,
+                Level::Error,
+                "trailing-spaces",
+                "trailing whitespace",
+            )],
+        )];
+        let output: serde_json::Value =
+            serde_json::from_str(&formatter.format_results(&results)).unwrap();
+        assert_eq!(
+            output["diagnostics"][0]["suggestions"][0]["text"],
+            "key: value\n"
+        );
+    }
+
+    #[test]
+    fn test_missing_file_has_no_suggestions() {
+        let formatter = RdjsonFormatter::new(Config::default());
+        let results = vec![(
+            PathBuf::from("/nonexistent/a.yaml"),
+            vec![Problem::new(
+                1,
+                5,
+                Level::Info,
+                "trailing-spaces",
+                "trailing whitespace",
+            )],
+        )];
+        let output: serde_json::Value =
+            serde_json::from_str(&formatter.format_results(&results)).unwrap();
+        let diagnostic = &output["diagnostics"][0];
+        assert_eq!(diagnostic["severity"], "INFO");
+        assert_eq!(diagnostic["location"]["range"]["start"]["column"], 5);
+        assert!(diagnostic.get("suggestions").is_none());
+    }
+}
@@ -0,0 +1,278 @@
+use super::OutputFormatter;
+use crate::codes;
+use crate::linter::{Level, Problem};
+use crate::rules::RuleRegistry;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const TOOL_URL: &str = "https://github.com/scottidler/yl";
+
+/// SARIF 2.1.0 output formatter, for code scanning services such as GitHub's.
+///
+/// Results are reported under their problem code (`YL10101`), with a rule descriptor per
+/// code that names the yl rule and links to its documentation. Problems of rules without
+/// codes, such as plugins, are reported under the rule ID.
+pub struct SarifFormatter {
+    /// Description of every built-in rule, by ID
+    descriptions: HashMap<String, &'static str>,
+}
+
+impl SarifFormatter {
+    /// Create a new SARIF formatter
+    pub fn new() -> Self {
+        Self {
+            descriptions: RuleRegistry::with_default_rules()
+                .rules()
+                .into_iter()
+                .map(|rule| (rule.id().to_string(), rule.description()))
+                .collect(),
+        }
+    }
+
+    /// Descriptor of the rule that `problem` is reported under
+    fn descriptor(&self, problem: &Problem) -> ReportingDescriptor {
+        ReportingDescriptor {
+            id: problem.code().unwrap_or(&problem.rule).to_string(),
+            name: problem.rule.clone(),
+            short_description: Text {
+                text: self
+                    .descriptions
+                    .get(&problem.rule)
+                    .map_or_else(|| problem.rule.clone(), |text| text.to_string()),
+            },
+            help_uri: codes::docs_url(&problem.rule),
+        }
+    }
+}
+
+impl Default for SarifFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Map a problem level to a SARIF result level
+fn level(level: &Level) -> &'static str {
+    match level {
+        Level::Error => "error",
+        Level::Warning => "warning",
+        Level::Info => "note",
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Run {
+    tool: Tool,
+    /// Columns count characters, as problem columns do
+    column_kind: &'static str,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Serialize)]
+struct Tool {
+    driver: Driver,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Driver {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<ReportingDescriptor>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReportingDescriptor {
+    id: String,
+    name: String,
+    short_description: Text,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    help_uri: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct Text {
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: String,
+    rule_index: usize,
+    level: &'static str,
+    message: Text,
+    locations: Vec<Location>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    region: Region,
+}
+
+#[derive(Debug, Serialize)]
+struct ArtifactLocation {
+    uri: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: usize,
+    start_column: usize,
+}
+
+impl OutputFormatter for SarifFormatter {
+    fn format_results(&self, results: &[(PathBuf, Vec<Problem>)]) -> String {
+        let problems: Vec<_> = results
+            .iter()
+            .flat_map(|(path, problems)| problems.iter().map(move |problem| (path, problem)))
+            .collect();
+
+        // One descriptor per code, sorted, so results can refer to them by index
+        let descriptors: BTreeMap<String, ReportingDescriptor> = problems
+            .iter()
+            .map(|(_, problem)| {
+                let descriptor = self.descriptor(problem);
+                (descriptor.id.clone(), descriptor)
+            })
+            .collect();
+        let index: HashMap<&str, usize> = descriptors
+            .keys()
+            .enumerate()
+            .map(|(index, id)| (id.as_str(), index))
+            .collect();
+
+        let results = problems
+            .iter()
+            .map(|(path, problem)| {
+                let rule_id = problem.code().unwrap_or(&problem.rule);
+                SarifResult {
+                    rule_id: rule_id.to_string(),
+                    rule_index: index[rule_id],
+                    level: level(&problem.level),
+                    message: Text {
+                        text: problem.message.clone(),
+                    },
+                    locations: vec![Location {
+                        physical_location: PhysicalLocation {
+                            artifact_location: ArtifactLocation {
+                                uri: path.to_string_lossy().replace('\\', "/"),
+                            },
+                            region: Region {
+                                start_line: problem.line,
+                                start_column: problem.column,
+                            },
+                        },
+                    }],
+                }
+            })
+            .collect();
+
+        let log = SarifLog {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: vec![Run {
+                tool: Tool {
+                    driver: Driver {
+                        name: "yl",
+                        version: env!("CARGO_PKG_VERSION"),
+                        information_uri: TOOL_URL,
+                        rules: descriptors.into_values().collect(),
+                    },
+                },
+                column_kind: "unicodeCodePoints",
+                results,
+            }],
+        };
+
+        serde_json::to_string_pretty(&log)
+            .unwrap_or_else(|e| format!(r#"{{"error": "Failed to serialize JSON: {e}"}}"#))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::Message;
+
+    #[test]
+    fn test_sarif_output() {
+        let results = vec![
+            (
+                PathBuf::from("a.yaml"),
+                vec![
+                    Problem::from_message(
+                        2,
+                        11,
+                        Level::Error,
+                        "trailing-spaces",
+                        Message::new("trailing-spaces.found"),
+                    ),
+                    Problem::new(3, 1, Level::Info, "todo-plugin", "found TODO"),
+                ],
+            ),
+            (
+                PathBuf::from("b.yaml"),
+                vec![Problem::from_message(
+                    1,
+                    1,
+                    Level::Warning,
+                    "trailing-spaces",
+                    Message::new("trailing-spaces.found"),
+                )],
+            ),
+        ];
+        let output: serde_json::Value =
+            serde_json::from_str(&SarifFormatter::new().format_results(&results)).unwrap();
+
+        assert_eq!(output["version"], "2.1.0");
+        let run = &output["runs"][0];
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0]["id"], "YL10201");
+        assert_eq!(rules[0]["name"], "trailing-spaces");
+        assert_eq!(
+            rules[0]["helpUri"],
+            "https://github.com/scottidler/yl/blob/main/docs/codes.md#trailing-spaces"
+        );
+        // Plugin rules have no code or documentation
+        assert_eq!(rules[1]["id"], "todo-plugin");
+        assert!(rules[1].get("helpUri").is_none());
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0]["ruleId"], "YL10201");
+        assert_eq!(results[0]["ruleIndex"], 0);
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(results[0]["message"]["text"], "trailing whitespace");
+        let location = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "a.yaml");
+        assert_eq!(location["region"]["startColumn"], 11);
+        assert_eq!(results[1]["ruleIndex"], 1);
+        assert_eq!(results[1]["level"], "note");
+        assert_eq!(results[2]["ruleIndex"], 0);
+    }
+}
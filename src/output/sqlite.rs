@@ -0,0 +1,186 @@
+use super::store::{ResultStore, RunInfo, file_hash};
+use crate::linter::Problem;
+use eyre::{Context, Result};
+use rusqlite::{Connection, params};
+use std::path::{Path, PathBuf};
+
+/// Schema of the results database; `user_version` records which version a file has
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS runs (
+    id INTEGER PRIMARY KEY,
+    started_at TEXT NOT NULL,
+    duration_ms INTEGER NOT NULL,
+    version TEXT NOT NULL,
+    git_commit TEXT,
+    directory TEXT NOT NULL,
+    arguments TEXT NOT NULL,
+    files INTEGER NOT NULL,
+    problems INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS files (
+    run_id INTEGER NOT NULL REFERENCES runs(id),
+    path TEXT NOT NULL,
+    sha256 TEXT,
+    problems INTEGER NOT NULL,
+    PRIMARY KEY (run_id, path)
+);
+CREATE TABLE IF NOT EXISTS problems (
+    run_id INTEGER NOT NULL REFERENCES runs(id),
+    path TEXT NOT NULL,
+    line INTEGER NOT NULL,
+    column INTEGER NOT NULL,
+    level TEXT NOT NULL,
+    rule TEXT NOT NULL,
+    message TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS problems_by_rule ON problems (rule, run_id);
+CREATE INDEX IF NOT EXISTS files_by_path ON files (path, run_id);
+PRAGMA user_version = 1;
+";
+
+/// Results store in a SQLite database, one row in `runs` per run
+pub struct SqliteStore {
+    connection: Connection,
+}
+
+impl SqliteStore {
+    /// Open or create the database at `path`
+    pub fn open(path: &Path) -> Result<Self> {
+        let connection = Connection::open(path)
+            .with_context(|| format!("Failed to open results store {}", path.display()))?;
+        connection
+            .execute_batch(SCHEMA)
+            .with_context(|| format!("Failed to create results store {}", path.display()))?;
+        Ok(Self { connection })
+    }
+}
+
+impl ResultStore for SqliteStore {
+    fn store_run(&mut self, run: &RunInfo, results: &[(PathBuf, Vec<Problem>)]) -> Result<i64> {
+        let transaction = self.connection.transaction()?;
+        let problem_count: usize = results.iter().map(|(_, problems)| problems.len()).sum();
+
+        transaction.execute(
+            "INSERT INTO runs (started_at, duration_ms, version, git_commit, directory, arguments, files, problems)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                run.started_at.to_rfc3339(),
+                run.duration.as_millis() as i64,
+                run.version,
+                run.git_commit,
+                run.directory.display().to_string(),
+                serde_json::to_string(&run.arguments)?,
+                results.len() as i64,
+                problem_count as i64,
+            ],
+        )?;
+        let run_id = transaction.last_insert_rowid();
+
+        {
+            let mut insert_file = transaction.prepare(
+                "INSERT OR REPLACE INTO files (run_id, path, sha256, problems) VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            let mut insert_problem = transaction.prepare(
+                "INSERT INTO problems (run_id, path, line, column, level, rule, message)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            )?;
+
+            for (path, problems) in results {
+                let path_text = path.display().to_string();
+                insert_file.execute(params![
+                    run_id,
+                    path_text,
+                    file_hash(path),
+                    problems.len() as i64,
+                ])?;
+                for problem in problems {
+                    insert_problem.execute(params![
+                        run_id,
+                        path_text,
+                        problem.line as i64,
+                        problem.column as i64,
+                        problem.level.to_string(),
+                        problem.rule,
+                        problem.message,
+                    ])?;
+                }
+            }
+        }
+
+        transaction
+            .commit()
+            .context("Failed to write results store")?;
+        Ok(run_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::Level;
+    use chrono::Utc;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn run_info() -> RunInfo {
+        RunInfo {
+            started_at: Utc::now(),
+            duration: Duration::from_millis(12),
+            version: "0.1.4".to_string(),
+            git_commit: None,
+            directory: PathBuf::from("/repo"),
+            arguments: vec!["--store".to_string(), "sqlite:yl.db".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_store_runs() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("a.yaml");
+        std::fs::write(&file, "a: 1\n").unwrap();
+        let database = temp_dir.path().join("yl.db");
+        let results = vec![(
+            file.clone(),
+            vec![Problem::new(
+                1,
+                5,
+                Level::Warning,
+                "line-length",
+                "too long",
+            )],
+        )];
+
+        let mut store = SqliteStore::open(&database).unwrap();
+        assert_eq!(store.store_run(&run_info(), &results).unwrap(), 1);
+        // Reopening keeps earlier runs
+        let mut store = SqliteStore::open(&database).unwrap();
+        assert_eq!(store.store_run(&run_info(), &results).unwrap(), 2);
+
+        let connection = &store.connection;
+        let count: i64 = connection
+            .query_row(
+                "SELECT COUNT(*) FROM problems WHERE rule = 'line-length'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 2);
+
+        let (hash, problems): (String, i64) = connection
+            .query_row(
+                "SELECT sha256, problems FROM files WHERE run_id = 2",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(hash.len(), 64);
+        assert_eq!(problems, 1);
+
+        let arguments: String = connection
+            .query_row("SELECT arguments FROM runs WHERE id = 1", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(arguments, r#"["--store","sqlite:yl.db"]"#);
+    }
+}
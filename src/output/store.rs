@@ -0,0 +1,94 @@
+use crate::linter::Problem;
+use chrono::{DateTime, Utc};
+use eyre::Result;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Where `--store` keeps the results of each run, written as `<kind>:<location>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StoreTarget {
+    /// A SQLite database file, `sqlite:results.db`
+    Sqlite(PathBuf),
+}
+
+impl FromStr for StoreTarget {
+    type Err = eyre::Report;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.split_once(':') {
+            Some(("sqlite", path)) if !path.is_empty() => Ok(Self::Sqlite(PathBuf::from(path))),
+            Some(("sqlite", _)) => Err(eyre::eyre!("sqlite store needs a path: sqlite:<path>")),
+            _ => Err(eyre::eyre!(
+                "unsupported store \"{value}\", expected sqlite:<path>"
+            )),
+        }
+    }
+}
+
+/// What a stored run was and when it happened
+#[derive(Debug, Clone)]
+pub struct RunInfo {
+    pub started_at: DateTime<Utc>,
+    pub duration: Duration,
+    /// Version of yl that produced the results
+    pub version: String,
+    /// Commit checked out in the working directory, if it is a git repository
+    pub git_commit: Option<String>,
+    /// Working directory of the run
+    pub directory: PathBuf,
+    /// Command-line arguments, without the program name
+    pub arguments: Vec<String>,
+}
+
+/// A sink keeping the reported results of whole runs, next to the output formatters
+pub trait ResultStore {
+    /// Record a run with its results, returning the run's id in the store
+    fn store_run(&mut self, run: &RunInfo, results: &[(PathBuf, Vec<Problem>)]) -> Result<i64>;
+}
+
+/// SHA-256 of a file's content, or `None` if it cannot be read (e.g. a deleted file)
+pub fn file_hash(path: &Path) -> Option<String> {
+    std::fs::read(path).ok().map(|content| sha256_hex(&content))
+}
+
+/// Lowercase hex SHA-256 digest of `bytes`
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Open the store a `--store` target names, creating it if needed
+pub fn open_store(target: &StoreTarget) -> Result<Box<dyn ResultStore>> {
+    match target {
+        #[cfg(feature = "sqlite")]
+        StoreTarget::Sqlite(path) => Ok(Box::new(super::sqlite::SqliteStore::open(path)?)),
+        #[cfg(not(feature = "sqlite"))]
+        StoreTarget::Sqlite(_) => Err(eyre::eyre!(
+            "this build of yl does not include the sqlite store (feature \"sqlite\")"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_store_target() {
+        assert_eq!(
+            "sqlite:results.db".parse::<StoreTarget>().unwrap(),
+            StoreTarget::Sqlite(PathBuf::from("results.db"))
+        );
+        assert_eq!(
+            "sqlite:/tmp/a:b.db".parse::<StoreTarget>().unwrap(),
+            StoreTarget::Sqlite(PathBuf::from("/tmp/a:b.db"))
+        );
+        assert!("sqlite:".parse::<StoreTarget>().is_err());
+        assert!("postgres://db".parse::<StoreTarget>().is_err());
+        assert!("results.db".parse::<StoreTarget>().is_err());
+    }
+}
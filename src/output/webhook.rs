@@ -0,0 +1,279 @@
+use crate::config::WebhookConfig;
+use crate::linter::{Level, Problem};
+use eyre::Result;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Number of rules listed in `top_rules`
+const TOP_RULES: usize = 10;
+
+/// Summary of a lint run, POSTed as JSON to the webhook
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RunSummary {
+    /// Version of yl that ran
+    pub version: String,
+    /// Whether the run found no errors, i.e. exits successfully
+    pub passed: bool,
+    pub files: usize,
+    pub files_with_problems: usize,
+    pub problems: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    pub info: usize,
+    /// Rules with the most problems, most first
+    pub top_rules: Vec<RuleCount>,
+    /// Files with errors, in the order they were reported
+    pub failed_files: Vec<FailedFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RuleCount {
+    pub rule: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FailedFile {
+    pub path: PathBuf,
+    pub errors: usize,
+    pub warnings: usize,
+}
+
+impl RunSummary {
+    /// Summarize the reported results of a run
+    pub fn from_results(results: &[(PathBuf, Vec<Problem>)]) -> Self {
+        let stats = super::LintStats::from_results(results);
+
+        let mut by_rule: HashMap<&str, usize> = HashMap::new();
+        for problem in results.iter().flat_map(|(_, problems)| problems) {
+            *by_rule.entry(problem.rule.as_str()).or_default() += 1;
+        }
+        let mut top_rules: Vec<RuleCount> = by_rule
+            .into_iter()
+            .map(|(rule, count)| RuleCount {
+                rule: rule.to_string(),
+                count,
+            })
+            .collect();
+        top_rules.sort_by(|a, b| b.count.cmp(&a.count).then(a.rule.cmp(&b.rule)));
+        top_rules.truncate(TOP_RULES);
+
+        let count = |problems: &[Problem], level| {
+            problems
+                .iter()
+                .filter(|problem| problem.level == level)
+                .count()
+        };
+        let failed_files = results
+            .iter()
+            .filter(|(_, problems)| count(problems, Level::Error) > 0)
+            .map(|(path, problems)| FailedFile {
+                path: path.clone(),
+                errors: count(problems, Level::Error),
+                warnings: count(problems, Level::Warning),
+            })
+            .collect();
+
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            passed: !stats.has_errors(),
+            files: stats.total_files,
+            files_with_problems: stats.files_with_problems,
+            problems: stats.total_problems,
+            errors: stats.errors,
+            warnings: stats.warnings,
+            info: stats.info,
+            top_rules,
+            failed_files,
+        }
+    }
+}
+
+/// POST the summary to the webhook, retrying failed attempts with a growing delay.
+/// Client errors other than `429 Too Many Requests` are not retried.
+#[cfg(feature = "webhook")]
+pub fn post_summary(webhook: &WebhookConfig, summary: &RunSummary) -> Result<()> {
+    use std::time::Duration;
+
+    const FIRST_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+    let body = serde_json::to_string(summary)?;
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(Duration::from_secs(webhook.timeout)))
+        .http_status_as_error(false)
+        .build()
+        .into();
+
+    let mut attempt = 0;
+    loop {
+        let error = match agent
+            .post(&webhook.url)
+            .header("Content-Type", "application/json")
+            .send(&body)
+        {
+            Ok(response) if response.status().is_success() => return Ok(()),
+            Ok(response) => {
+                let status = response.status();
+                if status.is_client_error() && status.as_u16() != 429 {
+                    return Err(eyre::eyre!(
+                        "Webhook {} rejected the run summary: {status}",
+                        webhook.url
+                    ));
+                }
+                eyre::eyre!("{status}")
+            }
+            Err(e) => eyre::eyre!(e),
+        };
+
+        if attempt >= webhook.retries {
+            return Err(error.wrap_err(format!(
+                "Failed to post run summary to {} after {} attempt(s)",
+                webhook.url,
+                attempt + 1
+            )));
+        }
+        tracing::debug!(attempt, error = %error, "retrying webhook");
+        std::thread::sleep(FIRST_RETRY_DELAY * 2u32.pow(attempt));
+        attempt += 1;
+    }
+}
+
+#[cfg(not(feature = "webhook"))]
+pub fn post_summary(_webhook: &WebhookConfig, _summary: &RunSummary) -> Result<()> {
+    Err(eyre::eyre!(
+        "this build of yl does not include webhook support (feature \"webhook\")"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results() -> Vec<(PathBuf, Vec<Problem>)> {
+        vec![
+            (
+                PathBuf::from("a.yaml"),
+                vec![
+                    Problem::new(1, 1, Level::Error, "key-duplicates", "duplicate key"),
+                    Problem::new(2, 81, Level::Warning, "line-length", "line too long"),
+                    Problem::new(3, 81, Level::Warning, "line-length", "line too long"),
+                ],
+            ),
+            (
+                PathBuf::from("b.yaml"),
+                vec![Problem::new(
+                    4,
+                    81,
+                    Level::Warning,
+                    "line-length",
+                    "line too long",
+                )],
+            ),
+            (PathBuf::from("c.yaml"), Vec::new()),
+        ]
+    }
+
+    #[test]
+    fn test_run_summary() {
+        let summary = RunSummary::from_results(&results());
+
+        assert!(!summary.passed);
+        assert_eq!((summary.files, summary.files_with_problems), (3, 2));
+        assert_eq!((summary.errors, summary.warnings), (1, 3));
+        assert_eq!(
+            summary.top_rules[0],
+            RuleCount {
+                rule: "line-length".to_string(),
+                count: 3
+            }
+        );
+        assert_eq!(
+            summary.failed_files,
+            vec![FailedFile {
+                path: PathBuf::from("a.yaml"),
+                errors: 1,
+                warnings: 2
+            }]
+        );
+    }
+
+    #[cfg(feature = "webhook")]
+    #[test]
+    fn test_post_summary_retries() {
+        use std::io::{BufRead, BufReader, Read, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+
+        // Fail the first attempt, accept the second and hand back its body
+        let server = std::thread::spawn(move || {
+            let mut body = String::new();
+            for status in ["503 Service Unavailable", "200 OK"] {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':')
+                        && name.eq_ignore_ascii_case("content-length")
+                    {
+                        length = value.trim().parse().unwrap();
+                    }
+                }
+                let mut content = vec![0; length];
+                reader.read_exact(&mut content).unwrap();
+                body = String::from_utf8(content).unwrap();
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                )
+                .unwrap();
+            }
+            body
+        });
+
+        let webhook = WebhookConfig {
+            url,
+            timeout: 5,
+            retries: 1,
+        };
+        post_summary(&webhook, &RunSummary::from_results(&results())).unwrap();
+
+        let body: serde_json::Value = serde_json::from_str(&server.join().unwrap()).unwrap();
+        assert_eq!(body["problems"], 4);
+        assert_eq!(body["failed_files"][0]["path"], "a.yaml");
+    }
+
+    #[cfg(feature = "webhook")]
+    #[test]
+    fn test_post_summary_client_error() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buffer = [0; 4096];
+            let _ = stream.read(&mut buffer);
+            let _ = write!(
+                stream,
+                "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+            );
+        });
+
+        let webhook = WebhookConfig {
+            url,
+            timeout: 5,
+            retries: 3,
+        };
+        let error = post_summary(&webhook, &RunSummary::from_results(&[])).unwrap_err();
+        assert!(error.to_string().contains("rejected the run summary: 404"));
+    }
+}
@@ -1,3 +0,0 @@
-pub mod comments;
-
-pub use comments::{CommentProcessor, Directive, Scope};
@@ -1,8 +1,14 @@
+pub mod scaffold;
+
+use crate::config::PluginsConfig;
 use crate::rules::{Rule, RuleConfig};
-use eyre::Result;
+use eyre::{Context, Result, eyre};
 use libloading::{Library, Symbol};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+use tracing::{info, warn};
 
 /// Trait that plugins must implement to provide rules
 pub trait RulePlugin: Send + Sync {
@@ -16,27 +22,77 @@ pub trait RulePlugin: Send + Sync {
     fn description(&self) -> &'static str;
 }
 
-/// Plugin manager for loading and managing rule plugins
+/// A plugin that was loaded, with the library it came from
+pub struct LoadedPlugin {
+    pub plugin: Box<dyn RulePlugin>,
+    pub path: PathBuf,
+    /// SHA-256 of the library, in hex
+    pub sha256: String,
+}
+
+/// Plugin manager for loading and managing rule plugins.
+///
+/// Only plugins in the `allow` list of the [`PluginsConfig`] are loaded. A library is
+/// matched to its allowlist entry by file name or checksum before it is opened, so an
+/// unlisted library is never opened. The bytes that were checked are opened from a
+/// private copy, which replacing the original file cannot change, and a plugin whose
+/// name or version differs from its entry is unloaded before any other method is called.
 pub struct PluginManager {
-    plugins: HashMap<String, Box<dyn RulePlugin>>,
+    policy: PluginsConfig,
+    plugins: HashMap<String, LoadedPlugin>,
     libraries: Vec<Library>, // Keep libraries loaded
+    copies: Vec<TempDir>,    // Removed after the libraries are unloaded
 }
 
 impl PluginManager {
-    /// Create a new plugin manager
+    /// Create a new plugin manager, which refuses every plugin until an allowlist is given
     pub fn new() -> Self {
         Self {
+            policy: PluginsConfig::default(),
             plugins: HashMap::new(),
             libraries: Vec::new(),
+            copies: Vec::new(),
         }
     }
 
-    /// Load a plugin from a shared library
+    /// Load only the plugins `policy` allows
+    pub fn with_policy(mut self, policy: PluginsConfig) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Load a plugin from a shared library, if the policy allows it
     pub fn load_plugin<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
         let path = path.as_ref();
+        let bytes =
+            std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let sha256: String = Sha256::digest(&bytes)
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        let file_name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        let Some(allowed) = self.policy.allowed_library(file_name, &sha256).cloned() else {
+            return Err(eyre!(
+                "{file_name} with checksum {sha256} matches no plugin in plugins.allow; the library was not opened"
+            ));
+        };
+        if let Some(expected) = allowed.sha256.as_deref().filter(|&sum| sum != sha256) {
+            return Err(eyre!(
+                "checksum of {file_name} is {sha256}, but plugins.allow expects {expected} for {allowed}; the library was not opened"
+            ));
+        }
+
+        // Open the bytes that were checked, not whatever the path holds by now
+        let copy = TempDir::new().context("Failed to create a directory for the plugin")?;
+        let copy_path = copy.path().join(file_name);
+        std::fs::write(&copy_path, &bytes)
+            .with_context(|| format!("Failed to copy {}", path.display()))?;
 
         unsafe {
-            let lib = Library::new(path)?;
+            let lib = Library::new(&copy_path)?;
 
             // Get the plugin creation function
             let create_plugin: Symbol<unsafe extern "C" fn() -> *mut dyn RulePlugin> =
@@ -45,19 +101,35 @@ impl PluginManager {
             let plugin_ptr = create_plugin();
             let plugin = Box::from_raw(plugin_ptr);
 
-            let plugin_name = plugin.name().to_string();
+            // The plugin is dropped before its library on refusal
+            let (name, version) = (plugin.name(), plugin.version());
+            if name != allowed.name || version != allowed.version {
+                return Err(eyre!(
+                    "{file_name} is {name}@{version}, but plugins.allow lists it as {allowed}"
+                ));
+            }
 
             // Store the plugin and keep the library loaded
-            self.plugins.insert(plugin_name, plugin);
+            self.plugins.insert(
+                name.to_string(),
+                LoadedPlugin {
+                    plugin,
+                    path: path.to_path_buf(),
+                    sha256,
+                },
+            );
             self.libraries.push(lib);
+            self.copies.push(copy);
         }
 
         Ok(())
     }
 
-    /// Get all loaded plugins
-    pub fn plugins(&self) -> Vec<&dyn RulePlugin> {
-        self.plugins.values().map(|p| p.as_ref()).collect()
+    /// Get all loaded plugins, by name
+    pub fn plugins(&self) -> Vec<&LoadedPlugin> {
+        let mut plugins: Vec<_> = self.plugins.values().collect();
+        plugins.sort_by_key(|loaded| loaded.plugin.name());
+        plugins
     }
 
     /// Load plugins from a directory
@@ -86,10 +158,10 @@ impl PluginManager {
                     match self.load_plugin(&path) {
                         Ok(()) => {
                             loaded_count += 1;
-                            eprintln!("Loaded plugin: {}", path.display());
+                            info!(plugin = %path.display(), "loaded plugin");
                         }
                         Err(e) => {
-                            eprintln!("Failed to load plugin {}: {}", path.display(), e);
+                            warn!(plugin = %path.display(), error = %e, "failed to load plugin");
                         }
                     }
                 }
@@ -191,6 +263,29 @@ mod tests {
         assert_eq!(manager.plugins().len(), 0);
     }
 
+    #[test]
+    fn test_load_plugin_checks_allowlist_before_opening() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("libother.so");
+        std::fs::write(&path, "not a library").unwrap();
+        let listed = temp_dir.path().join("libteam_rules.so");
+        std::fs::write(&listed, "not a library either").unwrap();
+
+        let policy: PluginsConfig = serde_yaml::from_str(&format!(
+            "allow:\n  - plugin: team-rules@0.1.0\n    file: libteam_rules.so\n    sha256: {}\n",
+            "0".repeat(64)
+        ))
+        .unwrap();
+        let mut manager = PluginManager::new().with_policy(policy);
+        // Neither the unlisted library nor the listed one with another checksum is opened
+        for path in [&path, &listed] {
+            let error = manager.load_plugin(path).unwrap_err().to_string();
+            assert!(error.contains("was not opened"), "{error}");
+        }
+        assert_eq!(manager.load_plugins_from_dir(temp_dir.path()).unwrap(), 0);
+        assert!(manager.plugins().is_empty());
+    }
+
     #[test]
     fn test_example_plugin() {
         let plugin = ExamplePlugin;
@@ -0,0 +1,137 @@
+//! Generation of new plugin projects
+
+use eyre::{Result, eyre};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CARGO_TOML: &str = include_str!("template/Cargo.toml.tmpl");
+const LIB_RS: &str = include_str!("template/lib.rs.tmpl");
+const LOAD_TEST: &str = include_str!("template/load.rs.tmpl");
+const README: &str = include_str!("template/README.md.tmpl");
+const GITIGNORE: &str = include_str!("template/gitignore.tmpl");
+
+/// Where a generated plugin gets `yl-core` from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoreDependency {
+    /// The registry release matching this yl
+    Version(String),
+    /// A local checkout of the `yl-core` crate
+    Path(PathBuf),
+}
+
+impl CoreDependency {
+    fn to_toml(&self) -> String {
+        match self {
+            Self::Version(version) => format!("yl-core = \"{version}\""),
+            Self::Path(path) => format!("yl-core = {{ path = {:?} }}", path.display().to_string()),
+        }
+    }
+}
+
+/// Create a cargo project for a plugin named `name` in `dir`, which must not exist yet,
+/// and return the files written
+pub fn scaffold(name: &str, dir: &Path, core: &CoreDependency) -> Result<Vec<PathBuf>> {
+    let valid = name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    if !valid {
+        return Err(eyre!(
+            "Invalid plugin name '{name}': use lowercase letters, digits, '-' and '_', starting with a letter"
+        ));
+    }
+    if dir.exists() {
+        return Err(eyre!("{} already exists", dir.display()));
+    }
+
+    let crate_name = name.replace('-', "_");
+    let type_name: String = crate_name
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| part[..1].to_uppercase() + &part[1..])
+        .collect();
+    let render = |template: &str| {
+        template
+            .replace("{{name}}", name)
+            .replace("{{crate}}", &crate_name)
+            .replace("{{Type}}", &type_name)
+            .replace("{{yl_core}}", &core.to_toml())
+    };
+
+    let files = [
+        ("Cargo.toml", CARGO_TOML),
+        ("src/lib.rs", LIB_RS),
+        ("tests/load.rs", LOAD_TEST),
+        ("README.md", README),
+        (".gitignore", GITIGNORE),
+    ];
+    let mut written = Vec::new();
+    for (file, template) in files {
+        let path = dir.join(file);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, render(template))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_scaffold() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("team-rules");
+        let core = CoreDependency::Version("0.1.4".to_string());
+
+        let files = scaffold("team-rules", &dir, &core).unwrap();
+        assert_eq!(files.len(), 5);
+
+        let manifest = fs::read_to_string(dir.join("Cargo.toml")).unwrap();
+        assert!(manifest.contains("name = \"team-rules\""));
+        assert!(manifest.contains("yl-core = \"0.1.4\""));
+        let lib = fs::read_to_string(dir.join("src/lib.rs")).unwrap();
+        assert!(lib.contains("pub struct TeamRulesPlugin;"));
+        assert!(lib.contains("\"team-rules/no-todo\""));
+        let test = fs::read_to_string(dir.join("tests/load.rs")).unwrap();
+        assert!(test.contains("\"{}team_rules{}\""));
+        for file in &files {
+            assert!(!fs::read_to_string(file).unwrap().contains("{{"));
+        }
+
+        assert!(scaffold("team-rules", &dir, &core).is_err());
+        for name in ["Team", "1rules", "team rules", ""] {
+            assert!(scaffold(name, &temp_dir.path().join("x"), &core).is_err());
+        }
+    }
+
+    #[test]
+    fn test_template_matches_plugin_trait() {
+        // yl calls plugins through the trait's vtable, so the copy in the template must
+        // declare the same methods in the same order
+        let methods = |source: &str| -> Vec<String> {
+            let start = source.find("pub trait RulePlugin").unwrap();
+            let end = start + source[start..].find("\n}").unwrap();
+            source[start..end]
+                .lines()
+                .map(str::trim)
+                .filter(|line| line.starts_with("fn "))
+                .map(str::to_string)
+                .collect()
+        };
+        assert_eq!(methods(LIB_RS), methods(include_str!("mod.rs")));
+    }
+
+    #[test]
+    fn test_core_dependency_toml() {
+        let core = CoreDependency::Path(PathBuf::from("/src/yl/crates/yl-core"));
+        assert_eq!(
+            core.to_toml(),
+            "yl-core = { path = \"/src/yl/crates/yl-core\" }"
+        );
+    }
+}
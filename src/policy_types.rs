@@ -0,0 +1,349 @@
+//! Team policy definitions
+//!
+//! A team policy lists the rules every project of a team must keep enabled, the rules they
+//! must not enable, and the settings they start from.
+
+use crate::config::Config;
+use crate::linter::{Level, Problem};
+use crate::rules::{RuleConfig, RuleRegistry};
+use eyre::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Rule ID of the problems reported for configurations that break the active policy
+pub const POLICY_RULE: &str = "policy";
+
+/// Rules guarding against values that parse differently than they read; these can be
+/// promoted to required when a policy is generated even if the configuration disables them
+pub const SECURITY_RULES: &[&str] = &[
+    "anchors",
+    "forbidden-keys",
+    "key-duplicates",
+    "octal-values",
+    "truthy",
+];
+
+/// A named set of linting requirements shared across projects
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TeamPolicy {
+    pub name: String,
+    pub version: String,
+    pub description: String,
+    pub author: String,
+    /// Settings of the rules projects start from
+    pub rules: BTreeMap<String, RuleConfig>,
+    /// Rules every project must keep enabled
+    pub required_rules: Vec<String>,
+    /// Rules projects must not enable
+    pub forbidden_rules: Vec<String>,
+    /// Lowest level each rule may be reported at
+    pub min_severity: BTreeMap<String, Level>,
+    /// Policy this one builds on
+    pub extends: Option<String>,
+    pub metadata: PolicyMetadata,
+}
+
+/// Bookkeeping information about a policy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicyMetadata {
+    pub created_at: String,
+    pub updated_at: String,
+    pub tags: Vec<String>,
+    pub documentation_url: Option<String>,
+    pub maintainers: Vec<String>,
+}
+
+/// Kind of policy requirement a configuration breaks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViolationType {
+    /// A required rule is disabled
+    RequiredRuleDisabled,
+    /// A forbidden rule is enabled
+    ForbiddenRuleEnabled,
+    /// A rule is reported at a lower level than the policy's minimum
+    SeverityTooLow,
+}
+
+/// A policy requirement that a configuration breaks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyViolation {
+    pub rule_id: String,
+    pub violation_type: ViolationType,
+    pub message: String,
+}
+
+impl PolicyViolation {
+    /// Report the violation as a problem of the [`POLICY_RULE`], positioned at the rule's
+    /// entry in the configuration file content when it has one
+    pub fn to_problem(&self, config_content: Option<&str>) -> Problem {
+        let position = config_content.and_then(|content| {
+            content.lines().enumerate().find_map(|(index, line)| {
+                let trimmed = line.trim_start();
+                let indent = line.len() - trimmed.len();
+                (indent > 0
+                    && trimmed
+                        .strip_prefix(self.rule_id.as_str())?
+                        .starts_with(':'))
+                .then_some((index + 1, indent + 1))
+            })
+        });
+        let (line, column) = position.unwrap_or((1, 1));
+        Problem::new(line, column, Level::Error, POLICY_RULE, &self.message)
+    }
+}
+
+impl TeamPolicy {
+    /// Read a policy file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read policy file: {}", path.display()))?;
+        serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse policy file: {}", path.display()))
+    }
+
+    /// Check a configuration against the policy's required and forbidden rules and
+    /// minimum levels
+    pub fn validate(&self, config: &Config, registry: &RuleRegistry) -> Vec<PolicyViolation> {
+        let mut violations = Vec::new();
+
+        for rule_id in &self.required_rules {
+            if !config.get_rule_config(rule_id, registry).enabled {
+                violations.push(PolicyViolation {
+                    rule_id: rule_id.clone(),
+                    violation_type: ViolationType::RequiredRuleDisabled,
+                    message: format!(
+                        "rule \"{rule_id}\" is required by policy \"{}\" but disabled",
+                        self.name
+                    ),
+                });
+            }
+        }
+
+        for rule_id in &self.forbidden_rules {
+            if config.get_rule_config(rule_id, registry).enabled {
+                violations.push(PolicyViolation {
+                    rule_id: rule_id.clone(),
+                    violation_type: ViolationType::ForbiddenRuleEnabled,
+                    message: format!(
+                        "rule \"{rule_id}\" is forbidden by policy \"{}\" but enabled",
+                        self.name
+                    ),
+                });
+            }
+        }
+
+        for (rule_id, min_level) in &self.min_severity {
+            let rule_config = config.get_rule_config(rule_id, registry);
+            if rule_config.enabled && rule_config.level < *min_level {
+                violations.push(PolicyViolation {
+                    rule_id: rule_id.clone(),
+                    violation_type: ViolationType::SeverityTooLow,
+                    message: format!(
+                        "rule \"{rule_id}\" is reported at {} but policy \"{}\" requires at least {min_level}",
+                        rule_config.level, self.name
+                    ),
+                });
+            }
+        }
+
+        violations
+    }
+
+    /// Build a policy from a configuration: every enabled rule becomes required at its
+    /// configured level. With `promote_security`, disabled [`SECURITY_RULES`] are enabled
+    /// at error level and required as well.
+    pub fn from_config(
+        name: &str,
+        author: &str,
+        config: &Config,
+        registry: &RuleRegistry,
+        promote_security: bool,
+    ) -> Self {
+        let promoted = if promote_security {
+            Self::promotable_rules(config, registry)
+        } else {
+            Vec::new()
+        };
+
+        let mut rules = BTreeMap::new();
+        for rule in registry.rules() {
+            let mut rule_config = config.get_rule_config(rule.id(), registry);
+            if promoted.contains(&rule.id()) {
+                rule_config.enabled = true;
+                rule_config.level = Level::Error;
+            }
+            rules.insert(rule.id().to_string(), rule_config);
+        }
+
+        let enabled: Vec<_> = rules
+            .iter()
+            .filter(|(_, rule_config)| rule_config.enabled)
+            .collect();
+        let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+
+        Self {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            description: format!(
+                "Linting requirements of {name}, generated from its yl configuration"
+            ),
+            author: author.to_string(),
+            required_rules: enabled.iter().map(|(id, _)| id.to_string()).collect(),
+            forbidden_rules: Vec::new(),
+            min_severity: enabled
+                .iter()
+                .map(|(id, rule_config)| (id.to_string(), rule_config.level.clone()))
+                .collect(),
+            extends: None,
+            metadata: PolicyMetadata {
+                created_at: now.clone(),
+                updated_at: now,
+                tags: Vec::new(),
+                documentation_url: None,
+                maintainers: if author.is_empty() {
+                    Vec::new()
+                } else {
+                    vec![author.to_string()]
+                },
+            },
+            rules,
+        }
+    }
+
+    /// The [`SECURITY_RULES`] a configuration leaves disabled
+    pub fn promotable_rules(config: &Config, registry: &RuleRegistry) -> Vec<&'static str> {
+        SECURITY_RULES
+            .iter()
+            .copied()
+            .filter(|id| registry.get(id).is_some())
+            .filter(|id| !config.get_rule_config(id, registry).enabled)
+            .collect()
+    }
+
+    /// Serialize the policy as YAML, preceded by comments explaining its sections.
+    /// `promoted` lists the rules that were enabled by the policy rather than the configuration.
+    pub fn to_commented_yaml(&self, promoted: &[&str]) -> Result<String> {
+        let yaml = serde_yaml::to_string(self).context("Failed to serialize policy")?;
+
+        let mut output = format!(
+            "# yl team policy \"{}\", generated by `yl policy init`\n\
+             #\n\
+             # required_rules: rules every project must keep enabled\n\
+             # forbidden_rules: rules projects must not enable\n\
+             # min_severity: lowest level each rule may be reported at\n\
+             # rules: the settings projects start from\n",
+            self.name
+        );
+        if !promoted.is_empty() {
+            output.push_str(&format!(
+                "#\n# Disabled in the configuration, promoted to required:\n#   {}\n",
+                promoted.join(", ")
+            ));
+        }
+        output.push_str("---\n");
+        output.push_str(&yaml);
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_config() {
+        let registry = RuleRegistry::with_default_rules();
+        let mut config = Config::default();
+        let mut line_length = config.get_rule_config("line-length", &registry);
+        line_length.enabled = true;
+        line_length.level = Level::Warning;
+        config.rules.insert("line-length".to_string(), line_length);
+        let mut truthy = config.get_rule_config("truthy", &registry);
+        truthy.enabled = false;
+        config.rules.insert("truthy".to_string(), truthy);
+
+        let policy = TeamPolicy::from_config("team", "Jo", &config, &registry, false);
+        assert!(policy.required_rules.contains(&"line-length".to_string()));
+        assert!(!policy.required_rules.contains(&"truthy".to_string()));
+        assert_eq!(
+            policy.min_severity.get("line-length"),
+            Some(&Level::Warning)
+        );
+        assert_eq!(policy.rules.len(), registry.rules().len());
+        assert_eq!(policy.metadata.maintainers, vec!["Jo".to_string()]);
+
+        assert!(TeamPolicy::promotable_rules(&config, &registry).contains(&"truthy"));
+        let policy = TeamPolicy::from_config("team", "", &config, &registry, true);
+        assert!(policy.required_rules.contains(&"truthy".to_string()));
+        assert_eq!(policy.min_severity.get("truthy"), Some(&Level::Error));
+        assert!(policy.metadata.maintainers.is_empty());
+    }
+
+    #[test]
+    fn test_validate() {
+        let registry = RuleRegistry::with_default_rules();
+        let mut policy = TeamPolicy::from_config("team", "", &Config::default(), &registry, false);
+        policy.required_rules = vec!["line-length".to_string()];
+        policy.forbidden_rules = vec!["truthy".to_string()];
+        policy.min_severity = BTreeMap::from([("trailing-spaces".to_string(), Level::Error)]);
+
+        let mut config = Config::default();
+        let mut line_length = config.get_rule_config("line-length", &registry);
+        line_length.enabled = false;
+        config.rules.insert("line-length".to_string(), line_length);
+        let mut truthy = config.get_rule_config("truthy", &registry);
+        truthy.enabled = true;
+        config.rules.insert("truthy".to_string(), truthy);
+        let mut trailing = config.get_rule_config("trailing-spaces", &registry);
+        trailing.enabled = true;
+        trailing.level = Level::Warning;
+        config.rules.insert("trailing-spaces".to_string(), trailing);
+
+        let violations = policy.validate(&config, &registry);
+        let types: Vec<_> = violations.iter().map(|v| v.violation_type).collect();
+        assert_eq!(
+            types,
+            vec![
+                ViolationType::RequiredRuleDisabled,
+                ViolationType::ForbiddenRuleEnabled,
+                ViolationType::SeverityTooLow,
+            ]
+        );
+        assert_eq!(
+            violations[2].message,
+            "rule \"trailing-spaces\" is reported at warning but policy \"team\" requires at least error"
+        );
+    }
+
+    #[test]
+    fn test_violation_to_problem() {
+        let violation = PolicyViolation {
+            rule_id: "line-length".to_string(),
+            violation_type: ViolationType::RequiredRuleDisabled,
+            message: "disabled".to_string(),
+        };
+
+        let config = "---\nrules:\n  line-length-extra:\n    enabled: true\n  line-length:\n    enabled: false\n";
+        let problem = violation.to_problem(Some(config));
+        assert_eq!((problem.line, problem.column), (5, 3));
+        assert_eq!(problem.rule, POLICY_RULE);
+        assert_eq!(problem.level, Level::Error);
+
+        let problem = violation.to_problem(None);
+        assert_eq!((problem.line, problem.column), (1, 1));
+    }
+
+    #[test]
+    fn test_commented_yaml_round_trips() {
+        let registry = RuleRegistry::with_default_rules();
+        let policy = TeamPolicy::from_config("team", "Jo", &Config::default(), &registry, true);
+
+        let yaml = policy.to_commented_yaml(&["truthy"]).unwrap();
+        assert!(yaml.starts_with("# yl team policy \"team\""));
+        assert!(yaml.contains("promoted to required:\n#   truthy\n"));
+
+        let parsed: TeamPolicy = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed, policy);
+    }
+}
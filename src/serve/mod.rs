@@ -0,0 +1,535 @@
+//! HTTP server exposing linting as a small REST API
+//!
+//! - `POST /lint` lints the `content` of a JSON body, optionally under a virtual `path` and
+//!   with its own `config`, and answers in the `format` query parameter's output format.
+//! - `GET /rules` lists the available rules with their defaults.
+//!
+//! Requests never read the server's filesystem: nested and `.editorconfig` configuration is
+//! not looked up, and request configurations cannot use `extends`.
+
+use crate::cli::OutputFormat;
+use crate::config::Config;
+use crate::linter::Linter;
+use crate::output::get_formatter;
+use crate::output::rdjson::RdjsonFormatter;
+use crate::rules::RuleRegistry;
+use clap::ValueEnum;
+use eyre::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{debug, info};
+
+/// Largest accepted request head (request line and headers)
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+
+/// Largest accepted request body
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Time a client has to send its whole request
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Virtual path of linted content when the request does not name one
+const DEFAULT_PATH: &str = "input.yaml";
+
+/// Body of a `POST /lint` request
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LintRequest {
+    /// YAML to lint
+    content: String,
+    /// Path the content is linted as, for path-dependent configuration such as `ignore`
+    #[serde(default)]
+    path: Option<String>,
+    /// Configuration replacing the server's, as YAML text or as an object
+    #[serde(default)]
+    config: Option<serde_json::Value>,
+}
+
+/// A response ready to be written
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl HttpResponse {
+    fn json(status: u16, body: &serde_json::Value) -> Self {
+        Self {
+            status,
+            content_type: "application/json",
+            body: body.to_string(),
+        }
+    }
+
+    fn error(status: u16, message: impl std::fmt::Display) -> Self {
+        Self::json(status, &serde_json::json!({ "error": message.to_string() }))
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            408 => "Request Timeout",
+            411 => "Length Required",
+            413 => "Payload Too Large",
+            _ => "Internal Server Error",
+        }
+    }
+}
+
+/// Lints request content with the server's configuration or the request's own
+pub struct LintServer {
+    config: Config,
+}
+
+impl LintServer {
+    /// Create a server using `config` for requests without a configuration
+    pub fn new(config: Config) -> Self {
+        Self { config }
+    }
+
+    /// Answer a request
+    pub fn route(
+        &self,
+        method: &str,
+        path: &str,
+        query: &HashMap<String, String>,
+        body: &[u8],
+    ) -> HttpResponse {
+        match (method, path) {
+            ("POST", "/lint") => self.lint(query, body),
+            ("GET", "/rules") => Self::rules(),
+            (_, "/lint" | "/rules") => HttpResponse::error(405, format!("{method} not allowed")),
+            _ => HttpResponse::error(404, format!("no such endpoint: {path}")),
+        }
+    }
+
+    fn lint(&self, query: &HashMap<String, String>, body: &[u8]) -> HttpResponse {
+        let format = match query.get("format") {
+            Some(format) => match OutputFormat::from_str(format, true) {
+                Ok(format) => format,
+                Err(_) => return HttpResponse::error(400, format!("unknown format: {format}")),
+            },
+            None => OutputFormat::Json,
+        };
+
+        let request: LintRequest = match serde_json::from_slice(body) {
+            Ok(request) => request,
+            Err(e) => return HttpResponse::error(400, format!("invalid request body: {e}")),
+        };
+
+        let mut config = match request.config {
+            Some(config) => match request_config(config) {
+                Ok(config) => config,
+                Err(e) => return HttpResponse::error(400, format!("{e:#}")),
+            },
+            None => self.config.clone(),
+        };
+        config.editorconfig = false;
+
+        let path = PathBuf::from(request.path.as_deref().unwrap_or(DEFAULT_PATH));
+        // The content is not on disk, so rdjson suggestions are computed from the request
+        let formatter = match format {
+            OutputFormat::Rdjson => Box::new(
+                RdjsonFormatter::new(config.clone())
+                    .with_source(path.clone(), request.content.clone()),
+            ),
+            _ => get_formatter(&format, &config),
+        };
+        let linter = Linter::new(config).with_config_discovery(false);
+        let results = match linter.lint_virtual(&[(path, request.content)]) {
+            Ok(results) => results,
+            Err(e) => return HttpResponse::error(500, format!("{e:#}")),
+        };
+
+        HttpResponse {
+            status: 200,
+            content_type: match format {
+                OutputFormat::Json | OutputFormat::Rdjson => "application/json",
+                OutputFormat::Sarif => "application/sarif+json",
+                OutputFormat::Ndjson => "application/x-ndjson",
+                OutputFormat::Human | OutputFormat::Compact => "text/plain; charset=utf-8",
+            },
+            body: formatter.format_results(&results),
+        }
+    }
+
+    fn rules() -> HttpResponse {
+        let registry = RuleRegistry::with_default_rules();
+        let rules: Vec<_> = registry
+            .rules()
+            .iter()
+            .map(|rule| {
+                let config = rule.default_config();
+                serde_json::json!({
+                    "id": rule.id(),
+                    "description": rule.description(),
+                    "enabled": config.enabled,
+                    "level": config.level.to_string(),
+                    "fixable": rule.fixable(),
+                    "params": config.params,
+                })
+            })
+            .collect();
+        HttpResponse::json(200, &serde_json::json!({ "rules": rules }))
+    }
+}
+
+/// Parse the configuration of a request, given as YAML text or as an object
+fn request_config(value: serde_json::Value) -> Result<Config> {
+    let config: Config = match value {
+        serde_json::Value::String(yaml) => {
+            serde_yaml::from_str(&yaml).context("invalid configuration")?
+        }
+        value => serde_json::from_value(value).context("invalid configuration")?,
+    };
+    if config.extends.is_some() {
+        return Err(eyre::eyre!(
+            "`extends` is not supported in request configurations"
+        ));
+    }
+    Ok(config)
+}
+
+/// Turn `:8080` into an address on all interfaces; other addresses are used as given
+pub fn listen_address(address: &str) -> String {
+    match address.strip_prefix(':') {
+        Some(port) => format!("0.0.0.0:{port}"),
+        None => address.to_string(),
+    }
+}
+
+/// Accept connections on `address` until the process is stopped
+pub async fn serve(address: &str, server: Arc<LintServer>) -> Result<()> {
+    let listener = TcpListener::bind(listen_address(address))
+        .await
+        .with_context(|| format!("Failed to listen on {address}"))?;
+    serve_listener(listener, server).await
+}
+
+/// Accept connections on a bound listener; each connection carries a single request
+pub async fn serve_listener(listener: TcpListener, server: Arc<LintServer>) -> Result<()> {
+    accept_connections(listener, server, READ_TIMEOUT).await
+}
+
+/// Accept connections, giving each client `read_timeout` to send its request
+async fn accept_connections(
+    listener: TcpListener,
+    server: Arc<LintServer>,
+    read_timeout: Duration,
+) -> Result<()> {
+    info!(address = %listener.local_addr()?, "http server listening");
+    loop {
+        let (stream, peer) = listener
+            .accept()
+            .await
+            .context("Failed to accept connection")?;
+        let server = server.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, server, read_timeout).await {
+                debug!(peer = %peer, error = %e, "connection failed");
+            }
+        });
+    }
+}
+
+/// Read one request, answer it and close the connection. A client that does not send its
+/// request within the read timeout is answered with 408, so idle connections do not pile up.
+async fn handle_connection(
+    mut stream: TcpStream,
+    server: Arc<LintServer>,
+    read_timeout: Duration,
+) -> Result<()> {
+    let request = tokio::time::timeout(read_timeout, read_request(&mut stream))
+        .await
+        .unwrap_or_else(|_| Ok(Err(HttpResponse::error(408, "request timed out"))));
+    let response = match request? {
+        Ok((method, target, body)) => {
+            let (path, query) = split_target(&target);
+            debug!(method = %method, path = %path, "http request");
+            tokio::task::spawn_blocking(move || server.route(&method, &path, &query, &body))
+                .await
+                .context("Request handler panicked")?
+        }
+        Err(response) => response,
+    };
+
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        response.reason(),
+        response.content_type,
+        response.body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(response.body.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// Read the method, target and body of a request, or the error response to send instead
+async fn read_request(
+    stream: &mut TcpStream,
+) -> Result<std::result::Result<(String, String, Vec<u8>), HttpResponse>> {
+    let mut buffer = Vec::new();
+    let head_end = loop {
+        if let Some(end) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            break end;
+        }
+        if buffer.len() > MAX_HEAD_BYTES {
+            return Ok(Err(HttpResponse::error(413, "request head too large")));
+        }
+        let mut chunk = [0; 4096];
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Err(eyre::eyre!(
+                "connection closed before the request was complete"
+            ));
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..head_end]).into_owned();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let (Some(method), Some(target)) = (request_line.next(), request_line.next()) else {
+        return Ok(Err(HttpResponse::error(400, "malformed request line")));
+    };
+
+    let content_length = lines
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+        .map(|(_, value)| value.trim().parse::<usize>());
+    let content_length = match content_length {
+        Some(Ok(length)) => length,
+        Some(Err(_)) => return Ok(Err(HttpResponse::error(400, "invalid Content-Length"))),
+        None if method == "POST" => {
+            return Ok(Err(HttpResponse::error(411, "Content-Length is required")));
+        }
+        None => 0,
+    };
+    if content_length > MAX_BODY_BYTES {
+        return Ok(Err(HttpResponse::error(
+            413,
+            format!("request body larger than {MAX_BODY_BYTES} bytes"),
+        )));
+    }
+
+    let mut body = buffer[head_end + 4..].to_vec();
+    while body.len() < content_length {
+        let mut chunk = vec![0; content_length - body.len()];
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Err(eyre::eyre!(
+                "connection closed before the body was complete"
+            ));
+        }
+        body.extend_from_slice(&chunk[..read]);
+    }
+    body.truncate(content_length);
+
+    Ok(Ok((method.to_string(), target.to_string(), body)))
+}
+
+/// Split a request target into its path and query parameters, decoding the parameters
+fn split_target(target: &str) -> (String, HashMap<String, String>) {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key), percent_decode(value))
+        })
+        .collect();
+    (path.to_string(), query)
+}
+
+/// Decode `%XX` escapes and `+` of a query component; malformed escapes are kept as given
+fn percent_decode(component: &str) -> String {
+    let bytes = component.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let escaped = (bytes[index] == b'%')
+            .then(|| component.get(index + 1..index + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[index], escaped) {
+            (_, Some(byte)) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            (b'+', None) => {
+                decoded.push(b' ');
+                index += 1;
+            }
+            (byte, None) => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lint(server: &LintServer, query: &[(&str, &str)], body: serde_json::Value) -> HttpResponse {
+        let query = query
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        server.route("POST", "/lint", &query, body.to_string().as_bytes())
+    }
+
+    #[test]
+    fn test_lint_with_request_config() {
+        let server = LintServer::new(Config::default());
+        let body = serde_json::json!({
+            "content": "---\nkey: value   \n",
+            "config": "rules:\n  trailing-spaces:\n    enabled: true\n    level: Error\n    params: {}\n",
+        });
+
+        let response = lint(&server, &[], body);
+        assert_eq!(response.status, 200);
+        assert_eq!(response.content_type, "application/json");
+        let output: serde_json::Value = serde_json::from_str(&response.body).unwrap();
+        assert_eq!(output["files"][0]["path"], DEFAULT_PATH);
+        assert_eq!(output["files"][0]["problems"][0]["rule"], "trailing-spaces");
+    }
+
+    #[test]
+    fn test_lint_rejects_bad_requests() {
+        let server = LintServer::new(Config::default());
+
+        let response = server.route("POST", "/lint", &HashMap::new(), b"not json");
+        assert_eq!(response.status, 400);
+
+        let body =
+            serde_json::json!({ "content": "a: 1\n", "config": { "extends": "/etc/yl.yaml" } });
+        let response = lint(&server, &[], body);
+        assert_eq!(response.status, 400);
+        assert!(response.body.contains("extends"), "{}", response.body);
+
+        let response = lint(
+            &server,
+            &[("format", "xml")],
+            serde_json::json!({ "content": "" }),
+        );
+        assert_eq!(response.status, 400);
+    }
+
+    #[test]
+    fn test_lint_formats() {
+        let server = LintServer::new(Config::default());
+        let body = serde_json::json!({ "content": "---\na: 1\n", "path": "app/values.yaml" });
+        let response = lint(&server, &[("format", "ndjson")], body);
+        assert_eq!(response.status, 200);
+        assert_eq!(response.content_type, "application/x-ndjson");
+
+        // Suggestions come from the request content, not the server's filesystem
+        let body = serde_json::json!({
+            "content": "---\nkey: value   \n",
+            "config": "rules:\n  trailing-spaces:\n    enabled: true\n    level: Error\n    params: {}\n",
+        });
+        let response = lint(&server, &[("format", "rdjson")], body);
+        assert_eq!(response.content_type, "application/json");
+        let output: serde_json::Value = serde_json::from_str(&response.body).unwrap();
+        assert_eq!(
+            output["diagnostics"][0]["suggestions"][0]["text"],
+            "key: value\n"
+        );
+    }
+
+    #[test]
+    fn test_rules_and_routing() {
+        let server = LintServer::new(Config::default());
+
+        let response = server.route("GET", "/rules", &HashMap::new(), b"");
+        assert_eq!(response.status, 200);
+        let output: serde_json::Value = serde_json::from_str(&response.body).unwrap();
+        let rules = output["rules"].as_array().unwrap();
+        assert!(
+            rules
+                .iter()
+                .any(|rule| rule["id"] == "trailing-spaces" && rule["fixable"] == true)
+        );
+
+        assert_eq!(
+            server.route("GET", "/lint", &HashMap::new(), b"").status,
+            405
+        );
+        assert_eq!(server.route("GET", "/", &HashMap::new(), b"").status, 404);
+    }
+
+    #[test]
+    fn test_listen_address_and_target() {
+        assert_eq!(listen_address(":8080"), "0.0.0.0:8080");
+        assert_eq!(listen_address("127.0.0.1:9000"), "127.0.0.1:9000");
+
+        let (path, query) = split_target("/lint?format=human&x");
+        assert_eq!(path, "/lint");
+        assert_eq!(query.get("format").map(String::as_str), Some("human"));
+        assert_eq!(query.get("x").map(String::as_str), Some(""));
+
+        let (_, query) = split_target("/lint?format=%67ithub&path=a%2Fb+c.yaml&bad=%zz%4");
+        assert_eq!(query.get("format").map(String::as_str), Some("github"));
+        assert_eq!(query.get("path").map(String::as_str), Some("a/b c.yaml"));
+        assert_eq!(query.get("bad").map(String::as_str), Some("%zz%4"));
+    }
+
+    #[tokio::test]
+    async fn test_serve_over_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = Arc::new(LintServer::new(Config::default()));
+        tokio::spawn(serve_listener(listener, server));
+
+        let body = r#"{"content": "---\na: 1\n"}"#;
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        let request = format!(
+            "POST /lint HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(request.as_bytes()).await.unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{response}");
+        assert!(response.contains("\"files\""), "{response}");
+    }
+
+    #[tokio::test]
+    async fn test_serve_times_out_slow_requests() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = Arc::new(LintServer::new(Config::default()));
+        tokio::spawn(accept_connections(
+            listener,
+            server,
+            Duration::from_millis(50),
+        ));
+
+        // The head is never finished
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        stream.write_all(b"POST /lint HTTP/1.1\r\n").await.unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(
+            response.starts_with("HTTP/1.1 408 Request Timeout\r\n"),
+            "{response}"
+        );
+    }
+}
@@ -0,0 +1,308 @@
+//! `yl self-update`: replace the running binary with the latest GitHub release
+//!
+//! Each release carries a `yl-<tag>-<platform>.tar.gz` archive per platform and a
+//! `.sha256` file next to it. The archive is only unpacked once its checksum matches, and
+//! the binary is swapped with an atomic rename, so an interrupted update leaves the old
+//! binary in place.
+
+use eyre::{Context, Result, eyre};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use std::path::PathBuf;
+use std::time::Duration;
+use yl_core::write::write_atomic;
+
+/// Latest release of the yl repository
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/scottidler/yl/releases/latest";
+
+/// Largest archive that is downloaded
+const MAX_ARCHIVE_SIZE: u64 = 256 * 1024 * 1024;
+
+/// A GitHub release, as far as updating is concerned
+#[derive(Debug, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    pub assets: Vec<Asset>,
+}
+
+/// A file attached to a release
+#[derive(Debug, Deserialize)]
+pub struct Asset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+impl Release {
+    /// The archive built for `platform` and its checksum file
+    fn artifacts(&self, platform: &str) -> Result<(&Asset, &Asset)> {
+        let archive_name = format!("yl-{}-{platform}.tar.gz", self.tag_name);
+        let checksum_name = format!("{archive_name}.sha256");
+        let find = |name: &str| self.assets.iter().find(|asset| asset.name == name);
+
+        let archive = find(&archive_name)
+            .ok_or_else(|| eyre!("release {} has no {archive_name} to install", self.tag_name))?;
+        let checksum = find(&checksum_name).ok_or_else(|| {
+            eyre!(
+                "release {} has no {checksum_name}; refusing to install an unverified binary",
+                self.tag_name
+            )
+        })?;
+        Ok((archive, checksum))
+    }
+}
+
+/// Result of `yl self-update`
+#[derive(Debug, PartialEq, Eq)]
+pub enum UpdateStatus {
+    /// The running version is the latest release
+    UpToDate { current: String },
+    /// A newer release exists; with `--check` nothing was installed
+    Available { current: String, latest: String },
+    /// The binary at `path` was replaced by the latest release
+    Updated {
+        current: String,
+        latest: String,
+        path: PathBuf,
+    },
+}
+
+impl std::fmt::Display for UpdateStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UpToDate { current } => write!(f, "yl {current} is up to date"),
+            Self::Available { current, latest } => {
+                write!(f, "yl {latest} is available (current: {current})")
+            }
+            Self::Updated {
+                current,
+                latest,
+                path,
+            } => write!(f, "Updated {} from {current} to {latest}", path.display()),
+        }
+    }
+}
+
+/// Suffix of the release archives built for the running platform
+pub fn platform() -> Option<&'static str> {
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("linux", "x86_64") => Some("linux"),
+        ("macos", "x86_64") => Some("macos"),
+        _ => None,
+    }
+}
+
+/// Parse a version such as `v0.1.4` or `0.1.4` into its numeric parts
+fn parse_version(version: &str) -> Option<Vec<u64>> {
+    version
+        .trim_start_matches('v')
+        .split('.')
+        .map(|part| part.parse().ok())
+        .collect()
+}
+
+/// Check whether release `latest` is newer than version `current`
+pub fn is_newer(latest: &str, current: &str) -> bool {
+    match (parse_version(latest), parse_version(current)) {
+        (Some(latest), Some(current)) => latest > current,
+        _ => false,
+    }
+}
+
+/// Get the hex digest from the contents of a `.sha256` file, either a bare digest or
+/// `sha256sum` output
+pub fn parse_checksum(contents: &str) -> Result<String> {
+    contents
+        .split_whitespace()
+        .next()
+        .filter(|digest| digest.len() == 64 && digest.chars().all(|c| c.is_ascii_hexdigit()))
+        .map(str::to_ascii_lowercase)
+        .ok_or_else(|| eyre!("malformed checksum file"))
+}
+
+/// Check that `bytes` hash to the `expected` SHA-256 hex digest
+pub fn verify_checksum(bytes: &[u8], expected: &str) -> Result<()> {
+    let actual: String = Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    if actual != expected {
+        return Err(eyre!(
+            "checksum mismatch: expected {expected}, downloaded archive has {actual}"
+        ));
+    }
+    Ok(())
+}
+
+/// Get the `yl` binary out of a release archive
+pub fn extract_binary(archive: &[u8]) -> Result<Vec<u8>> {
+    let mut entries = tar::Archive::new(flate2::read::GzDecoder::new(archive));
+    for entry in entries
+        .entries()
+        .context("Failed to read release archive")?
+    {
+        let mut entry = entry.context("Failed to read release archive")?;
+        if entry.path()?.file_name().is_some_and(|name| name == "yl") {
+            let mut binary = Vec::new();
+            entry
+                .read_to_end(&mut binary)
+                .context("Failed to unpack yl from the release archive")?;
+            return Ok(binary);
+        }
+    }
+    Err(eyre!("release archive does not contain a yl binary"))
+}
+
+/// Check for a newer release and, unless `check_only`, install it over the running binary
+pub fn self_update(check_only: bool) -> Result<UpdateStatus> {
+    let current = env!("CARGO_PKG_VERSION").to_string();
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(Duration::from_secs(60)))
+        .build()
+        .into();
+
+    let release: Release = serde_json::from_str(
+        &get(&agent, LATEST_RELEASE_URL)?
+            .body_mut()
+            .read_to_string()
+            .context("Failed to read the latest release")?,
+    )
+    .context("Failed to parse the latest release")?;
+    let latest = release.tag_name.trim_start_matches('v').to_string();
+
+    if !is_newer(&release.tag_name, &current) {
+        return Ok(UpdateStatus::UpToDate { current });
+    }
+    if check_only {
+        return Ok(UpdateStatus::Available { current, latest });
+    }
+
+    let platform = platform().ok_or_else(|| {
+        eyre!(
+            "no release builds for {}-{}; install from source with `cargo install yl`",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        )
+    })?;
+    let (archive, checksum) = release.artifacts(platform)?;
+
+    let expected = parse_checksum(
+        &get(&agent, &checksum.browser_download_url)?
+            .body_mut()
+            .read_to_string()
+            .with_context(|| format!("Failed to download {}", checksum.name))?,
+    )
+    .with_context(|| format!("Invalid {}", checksum.name))?;
+    let bytes = get(&agent, &archive.browser_download_url)?
+        .body_mut()
+        .with_config()
+        .limit(MAX_ARCHIVE_SIZE)
+        .read_to_vec()
+        .with_context(|| format!("Failed to download {}", archive.name))?;
+    verify_checksum(&bytes, &expected).with_context(|| format!("Refusing {}", archive.name))?;
+    let binary = extract_binary(&bytes)?;
+
+    let path = std::env::current_exe().context("Failed to locate the running yl binary")?;
+    write_atomic(&path, binary, None)
+        .with_context(|| format!("Failed to replace {}", path.display()))?;
+
+    Ok(UpdateStatus::Updated {
+        current,
+        latest,
+        path,
+    })
+}
+
+/// GET `url`, authenticated with `GITHUB_TOKEN` when it is set to avoid rate limits
+fn get(agent: &ureq::Agent, url: &str) -> Result<ureq::http::Response<ureq::Body>> {
+    let mut request = agent
+        .get(url)
+        .header("User-Agent", concat!("yl/", env!("CARGO_PKG_VERSION")));
+    if let Ok(token) = std::env::var("GITHUB_TOKEN") {
+        request = request.header("Authorization", format!("Bearer {token}"));
+    }
+    request
+        .call()
+        .with_context(|| format!("Failed to fetch {url}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn archive(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::default(),
+        ));
+        for (name, contents) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o755);
+            header.set_cksum();
+            builder.append_data(&mut header, name, *contents).unwrap();
+        }
+        let mut encoder = builder.into_inner().unwrap();
+        encoder.flush().unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_is_newer() {
+        assert!(is_newer("v0.1.5", "0.1.4"));
+        assert!(is_newer("v0.10.0", "0.9.9"));
+        assert!(!is_newer("v0.1.4", "0.1.4"));
+        assert!(!is_newer("v0.1.3", "0.1.4"));
+        assert!(!is_newer("nightly", "0.1.4"));
+    }
+
+    #[test]
+    fn test_checksums() {
+        let digest = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+        assert_eq!(parse_checksum(digest).unwrap(), digest);
+        assert_eq!(
+            parse_checksum(&format!(
+                "{}  yl-v0.1.5-linux.tar.gz\n",
+                digest.to_uppercase()
+            ))
+            .unwrap(),
+            digest
+        );
+        assert!(parse_checksum("not a digest").is_err());
+
+        assert!(verify_checksum(b"hello", digest).is_ok());
+        assert!(verify_checksum(b"hello!", digest).is_err());
+    }
+
+    #[test]
+    fn test_extract_binary() {
+        let bytes = archive(&[("README.md", b"docs"), ("yl", b"\x7fELF binary")]);
+        assert_eq!(extract_binary(&bytes).unwrap(), b"\x7fELF binary");
+
+        let bytes = archive(&[("README.md", b"docs")]);
+        assert!(extract_binary(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_release_artifacts() {
+        let asset = |name: &str| Asset {
+            name: name.to_string(),
+            browser_download_url: format!("https://example.com/{name}"),
+        };
+        let release = Release {
+            tag_name: "v0.1.5".to_string(),
+            assets: vec![
+                asset("yl-v0.1.5-linux.tar.gz"),
+                asset("yl-v0.1.5-linux.tar.gz.sha256"),
+                asset("yl-v0.1.5-macos.tar.gz"),
+            ],
+        };
+
+        let (archive, checksum) = release.artifacts("linux").unwrap();
+        assert_eq!(archive.name, "yl-v0.1.5-linux.tar.gz");
+        assert_eq!(checksum.name, "yl-v0.1.5-linux.tar.gz.sha256");
+        // An archive without a checksum is never installed
+        assert!(release.artifacts("macos").is_err());
+    }
+}
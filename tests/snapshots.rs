@@ -0,0 +1,144 @@
+//! Snapshot tests for rules.
+//!
+//! Each fixture under `tests/snapshots/<rule-id>/` is linted with that rule enabled, and
+//! the problems are compared to the `# expect: <rule> <line>:<column>` comments that end
+//! the fixture. Only the problems of the directory's rule and of rules named in the
+//! expectations are compared. The expectation block is not linted, so it never shifts
+//! or causes problems itself; rule parameters are set with `# yl:configure` as usual.
+//!
+//! Run with `YL_BLESS=1` to rewrite the expectations from the current output.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+use yl_core::{Config, Linter};
+
+const EXPECT_PREFIX: &str = "# expect:";
+
+/// A fixture split into the YAML that is linted and its expected problems
+struct Fixture {
+    body: String,
+    expected: Vec<String>,
+}
+
+impl Fixture {
+    fn parse(content: &str) -> Self {
+        let lines: Vec<&str> = content.lines().collect();
+        let block_start = lines
+            .iter()
+            .rposition(|line| !line.trim().is_empty() && !line.starts_with(EXPECT_PREFIX))
+            .map_or(0, |last_body_line| last_body_line + 1);
+
+        let mut body = lines[..block_start].join("\n");
+        if !body.is_empty() {
+            body.push('\n');
+        }
+        let expected = lines[block_start..]
+            .iter()
+            .filter_map(|line| line.strip_prefix(EXPECT_PREFIX))
+            .map(|expectation| expectation.trim().to_string())
+            .collect();
+
+        Self { body, expected }
+    }
+
+    fn render(&self, actual: &[String]) -> String {
+        let mut content = self.body.clone();
+        for expectation in actual {
+            content.push_str(&format!("{EXPECT_PREFIX} {expectation}\n"));
+        }
+        content
+    }
+}
+
+/// Lint a fixture with its rule enabled, returning `<rule> <line>:<column>` per problem
+fn lint_fixture(rule: &str, path: &Path, fixture: &Fixture) -> Vec<String> {
+    let mut config = Config::default();
+    if let Some(rule_config) = config.rules.get_mut(rule) {
+        rule_config.enabled = true;
+    }
+    let linter = Linter::new(config).with_config_discovery(false);
+
+    let compared: BTreeSet<&str> = fixture
+        .expected
+        .iter()
+        .filter_map(|expectation| expectation.split_whitespace().next())
+        .chain([rule])
+        .collect();
+
+    linter
+        .lint_content(path, &fixture.body)
+        .unwrap_or_else(|e| panic!("failed to lint {}: {e}", path.display()))
+        .iter()
+        .filter(|problem| compared.contains(problem.rule.as_str()))
+        .map(|problem| format!("{} {}:{}", problem.rule, problem.line, problem.column))
+        .collect()
+}
+
+fn fixtures() -> Vec<(String, PathBuf)> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots");
+    let mut fixtures: Vec<(String, PathBuf)> = WalkDir::new(&root)
+        .min_depth(2)
+        .max_depth(2)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "yaml"))
+        .map(|entry| {
+            let rule = entry
+                .path()
+                .parent()
+                .and_then(Path::file_name)
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            (rule, entry.into_path())
+        })
+        .collect();
+    fixtures.sort();
+    fixtures
+}
+
+#[test]
+fn test_rule_snapshots() {
+    let bless = std::env::var_os("YL_BLESS").is_some();
+    let fixtures = fixtures();
+    assert!(!fixtures.is_empty(), "no snapshot fixtures found");
+
+    let mut failures = Vec::new();
+    for (rule, path) in fixtures {
+        let content = fs::read_to_string(&path).expect("failed to read fixture");
+        let fixture = Fixture::parse(&content);
+        let actual = lint_fixture(&rule, &path, &fixture);
+
+        if bless {
+            let blessed = fixture.render(&actual);
+            if blessed != content {
+                fs::write(&path, blessed).expect("failed to write fixture");
+                println!("blessed {}", path.display());
+            }
+        } else if actual != fixture.expected {
+            failures.push(format!(
+                "{}\n  expected: {:?}\n  actual:   {:?}",
+                path.display(),
+                fixture.expected,
+                actual
+            ));
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "snapshot mismatches (run with YL_BLESS=1 to accept):\n{}",
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn test_fixture_parse() {
+    let fixture = Fixture::parse("key: value\n\n# expect: line-length 1:81\n");
+    assert_eq!(fixture.body, "key: value\n");
+    assert_eq!(fixture.expected, vec!["line-length 1:81"]);
+
+    let rendered = fixture.render(&["trailing-spaces 1:11".to_string()]);
+    assert_eq!(rendered, "key: value\n# expect: trailing-spaces 1:11\n");
+}